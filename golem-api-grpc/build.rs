@@ -51,6 +51,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "proto/golem/component/export_instance.proto",
                 "proto/golem/component/function_parameter.proto",
                 "proto/golem/component/function_result.proto",
+                "proto/golem/component/initial_component_file.proto",
+                "proto/golem/component/component_retention_policy.proto",
                 "proto/golem/component/component.proto",
                 "proto/golem/component/component_error.proto",
                 "proto/golem/component/component_id.proto",
@@ -61,13 +63,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "proto/golem/component/component_service.proto",
                 "proto/golem/componentcompilation/component_compilation_service.proto",
                 "proto/golem/worker/calling_convention.proto",
+                "proto/golem/worker/cancel_invocation_status.proto",
                 "proto/golem/worker/complete_parameters.proto",
                 "proto/golem/worker/idempotency_key.proto",
                 "proto/golem/worker/invoke_parameters.proto",
                 "proto/golem/worker/invoke_result.proto",
                 "proto/golem/worker/log_event.proto",
+                "proto/golem/worker/oplog.proto",
+                "proto/golem/worker/pending_promise.proto",
                 "proto/golem/worker/promise_id.proto",
+                "proto/golem/worker/scheduled_invocation.proto",
                 "proto/golem/worker/worker_execution_error.proto",
+                "proto/golem/worker/worker_file.proto",
                 "proto/golem/worker/worker_error.proto",
                 "proto/golem/worker/worker_id.proto",
                 "proto/golem/worker/worker_metadata.proto",
@@ -76,6 +76,20 @@ pub enum ApiDefinitionSubcommand {
         version: ApiDefinitionVersion,
     },
 
+    /// Exports an existing api definition as an OpenAPI file
+    ///
+    /// The schemas of the requests and responses are derived from the bound component functions
+    #[command()]
+    Export {
+        /// Api definition id
+        #[arg(short, long)]
+        id: ApiDefinitionId,
+
+        /// Version of the api definition
+        #[arg(short = 'V', long)]
+        version: ApiDefinitionVersion,
+    },
+
     /// Deletes an existing api definition
     #[command()]
     Delete {
@@ -122,6 +136,10 @@ impl<C: ApiDefinitionClient + Send + Sync> ApiDefinitionHandler for ApiDefinitio
                     definition,
                 ))))
             }
+            ApiDefinitionSubcommand::Export { id, version } => {
+                let openapi = self.client.export(id, version).await?;
+                Ok(GolemResult::Json(openapi))
+            }
             ApiDefinitionSubcommand::List { id } => {
                 let definitions = self.client.list(id.as_ref()).await?;
                 Ok(GolemResult::Ok(Box::new(definitions)))
@@ -168,6 +186,9 @@ mod tests {
                 id: "".to_string(),
                 version: "".to_string(),
                 routes: vec![],
+                security_schemes: None,
+                cors: None,
+                functions: None,
                 draft: false,
             })
         }
@@ -183,6 +204,9 @@ mod tests {
                 id: "".to_string(),
                 version: "".to_string(),
                 routes: vec![],
+                security_schemes: None,
+                cors: None,
+                functions: None,
                 draft: false,
             })
         }
@@ -199,6 +223,9 @@ mod tests {
                 id: "".to_string(),
                 version: "".to_string(),
                 routes: vec![],
+                security_schemes: None,
+                cors: None,
+                functions: None,
                 draft: false,
             })
         }
@@ -213,10 +240,23 @@ mod tests {
                 id: "".to_string(),
                 version: "".to_string(),
                 routes: vec![],
+                security_schemes: None,
+                cors: None,
+                functions: None,
                 draft: false,
             })
         }
 
+        async fn export_open_api(
+            &self,
+            api_definition_id: &str,
+            version: &str,
+        ) -> Result<serde_json::Value, Error<ApiDefinitionError>> {
+            let mut calls = self.calls.lock().unwrap();
+            calls.push_str(format!("export: {}/{}", api_definition_id, version).as_str());
+            Ok(serde_json::json!({}))
+        }
+
         async fn delete_definition(
             &self,
             api_definition_id: &str,
@@ -278,6 +318,18 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    pub async fn export() {
+        let subcommand = ApiDefinitionSubcommand::Export {
+            id: ApiDefinitionId("id".to_string()),
+            version: ApiDefinitionVersion("version".to_string()),
+        };
+        let checked = handle(subcommand).await;
+        if let Ok(calls) = checked {
+            assert_eq!(calls, "export: id/version");
+        }
+    }
+
     #[tokio::test]
     pub async fn delete() {
         let subcommand = ApiDefinitionSubcommand::Delete {
@@ -39,6 +39,11 @@ pub trait ApiDefinitionClient {
     async fn create(&self, path: PathBufOrStdin) -> Result<HttpApiDefinition, GolemError>;
     async fn update(&self, path: PathBufOrStdin) -> Result<HttpApiDefinition, GolemError>;
     async fn import(&self, path: PathBufOrStdin) -> Result<HttpApiDefinition, GolemError>;
+    async fn export(
+        &self,
+        id: ApiDefinitionId,
+        version: ApiDefinitionVersion,
+    ) -> Result<serde_json::Value, GolemError>;
     async fn delete(
         &self,
         id: ApiDefinitionId,
@@ -158,6 +163,19 @@ impl<C: golem_client::api::ApiDefinitionClient + Sync + Send> ApiDefinitionClien
         create_or_update_api_definition(Action::Import, &self.client, path).await
     }
 
+    async fn export(
+        &self,
+        id: ApiDefinitionId,
+        version: ApiDefinitionVersion,
+    ) -> Result<serde_json::Value, GolemError> {
+        info!("Exporting api definition {}/{} as OpenAPI", id.0, version.0);
+
+        Ok(self
+            .client
+            .export_open_api(id.0.as_str(), version.0.as_str())
+            .await?)
+    }
+
     async fn delete(
         &self,
         id: ApiDefinitionId,
@@ -12,15 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::Read;
+use std::io::{Cursor, Read, Write};
 
 use async_trait::async_trait;
-use golem_client::model::Component;
+use golem_client::model::{
+    Component, ComponentFilePermissions, InitialComponentFilePermissions,
+    InitialComponentFilePermissionsList,
+};
 
 use tokio::fs::File;
 use tracing::info;
+use zip::write::FileOptions;
+use zip::ZipWriter;
 
-use crate::model::{ComponentId, ComponentName, GolemError, PathBufOrStdin};
+use crate::model::{ComponentId, ComponentName, GolemError, InitialFile, PathBufOrStdin};
 
 #[async_trait]
 pub trait ComponentClient {
@@ -34,8 +39,12 @@ pub trait ComponentClient {
         component_id: &ComponentId,
     ) -> Result<Component, GolemError>;
     async fn find(&self, name: Option<ComponentName>) -> Result<Vec<Component>, GolemError>;
-    async fn add(&self, name: ComponentName, file: PathBufOrStdin)
-        -> Result<Component, GolemError>;
+    async fn add(
+        &self,
+        name: ComponentName,
+        file: PathBufOrStdin,
+        files: Vec<InitialFile>,
+    ) -> Result<Component, GolemError>;
     async fn update(&self, id: ComponentId, file: PathBufOrStdin) -> Result<Component, GolemError>;
 }
 
@@ -85,16 +94,26 @@ impl<C: golem_client::api::ComponentClient + Sync + Send> ComponentClient
         &self,
         name: ComponentName,
         path: PathBufOrStdin,
+        files: Vec<InitialFile>,
     ) -> Result<Component, GolemError> {
         info!("Adding component {name:?} from {path:?}");
 
+        let (archive, permissions) = if files.is_empty() {
+            (None, None)
+        } else {
+            let (archive, permissions) = archive_files(&files)?;
+            (Some(archive), Some(permissions))
+        };
+
         let component = match path {
             PathBufOrStdin::Path(path) => {
                 let file = File::open(path)
                     .await
                     .map_err(|e| GolemError(format!("Can't open component file: {e}")))?;
 
-                self.client.create_component(&name.0, file).await?
+                self.client
+                    .create_component(&name.0, file, archive, permissions.as_ref())
+                    .await?
             }
             PathBufOrStdin::Stdin => {
                 let mut bytes = Vec::new();
@@ -103,7 +122,9 @@ impl<C: golem_client::api::ComponentClient + Sync + Send> ComponentClient
                     .read_to_end(&mut bytes) // TODO: steaming request from stdin
                     .map_err(|e| GolemError(format!("Failed to read stdin: {e:?}")))?;
 
-                self.client.create_component(&name.0, bytes).await?
+                self.client
+                    .create_component(&name.0, bytes, archive, permissions.as_ref())
+                    .await?
             }
         };
 
@@ -135,3 +156,45 @@ impl<C: golem_client::api::ComponentClient + Sync + Send> ComponentClient
         Ok(component)
     }
 }
+
+// Packs the initial files of a new component into a zip archive, as expected by the component
+// service
+fn archive_files(
+    files: &[InitialFile],
+) -> Result<(Vec<u8>, InitialComponentFilePermissionsList), GolemError> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let mut permissions = Vec::new();
+
+    for file in files {
+        let content = std::fs::read(&file.source)
+            .map_err(|e| GolemError(format!("Can't read file {}: {e}", file.source.display())))?;
+
+        writer
+            .start_file(file.path.clone(), FileOptions::default())
+            .map_err(|e| GolemError(format!("Failed to archive file {}: {e}", file.path)))?;
+        writer
+            .write_all(&content)
+            .map_err(|e| GolemError(format!("Failed to archive file {}: {e}", file.path)))?;
+
+        permissions.push(InitialComponentFilePermissions {
+            path: file.path.clone(),
+            permissions: if file.read_only {
+                ComponentFilePermissions::ReadOnly
+            } else {
+                ComponentFilePermissions::ReadWrite
+            },
+        });
+    }
+
+    let archive = writer
+        .finish()
+        .map_err(|e| GolemError(format!("Failed to archive files: {e}")))?
+        .into_inner();
+
+    Ok((
+        archive,
+        InitialComponentFilePermissionsList {
+            values: permissions,
+        },
+    ))
+}
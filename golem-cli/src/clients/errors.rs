@@ -20,9 +20,10 @@ use golem_client::model::{
     GolemErrorFailedToResumeWorker, GolemErrorGetLatestVersionOfComponentFailed,
     GolemErrorInterrupted, GolemErrorInvalidRequest, GolemErrorInvalidShardId,
     GolemErrorPromiseAlreadyCompleted, GolemErrorPromiseDropped, GolemErrorPromiseNotFound,
-    GolemErrorRuntimeError, GolemErrorUnexpectedOplogEntry, GolemErrorUnknown,
-    GolemErrorValueMismatch, GolemErrorWorkerAlreadyExists, GolemErrorWorkerCreationFailed,
-    GolemErrorWorkerNotFound, PromiseId, WorkerId, WorkerServiceErrorsBody,
+    GolemErrorPromiseTimedOut, GolemErrorRuntimeError, GolemErrorUnexpectedOplogEntry,
+    GolemErrorUnknown, GolemErrorValueMismatch, GolemErrorWorkerAlreadyExists,
+    GolemErrorWorkerCreationFailed, GolemErrorWorkerNotFound, PromiseId, WorkerId,
+    WorkerServiceErrorsBody,
 };
 use itertools::Itertools;
 
@@ -155,6 +156,9 @@ fn display_golem_error(error: golem_client::model::GolemError) -> String {
                 display_promise_id(promise_id)
             )
         }
+        GolemError::PromiseTimedOut(GolemErrorPromiseTimedOut { promise_id }) => {
+            format!("Promise timed out: {}", display_promise_id(promise_id))
+        }
         GolemError::Interrupted(GolemErrorInterrupted {
             recover_immediately,
         }) => {
@@ -216,7 +220,10 @@ fn display_worker_service_errors_body(error: WorkerServiceErrorsBody) -> String
         WorkerServiceErrorsBody::Validation(validation) => validation
             .errors
             .iter()
-            .map(|e| format!("{}/{}/{}/{}", e.method, e.path, e.component, e.detail))
+            .map(|e| match e.component {
+                Some(component) => format!("{}/{}/{}/{}", e.method, e.path, component, e.detail),
+                None => format!("{}/{}/{}", e.method, e.path, e.detail),
+            })
             .join("\n"),
     }
 }
@@ -289,18 +296,24 @@ mod tests {
                     RouteValidationError {
                         method: MethodPattern::Get,
                         path: "path".to_string(),
-                        component: Uuid::parse_str("02f09a3f-1624-3b1d-8409-44eff7708208").unwrap(),
+                        component: Some(
+                            Uuid::parse_str("02f09a3f-1624-3b1d-8409-44eff7708208").unwrap(),
+                        ),
                         detail: "Duplicate route".to_string(),
                     },
                     RouteValidationError {
                         method: MethodPattern::Post,
                         path: "path2".to_string(),
-                        component: Uuid::parse_str("02f09a3f-1624-3b1d-8409-44eff7708209").unwrap(),
+                        component: None,
                         detail: "Other route".to_string(),
                     },
                 ],
             },
         ));
-        assert_eq!(error.map(), "Get/path/02f09a3f-1624-3b1d-8409-44eff7708208/Duplicate route\nPost/path2/02f09a3f-1624-3b1d-8409-44eff7708209/Other route".to_string())
+        assert_eq!(
+            error.map(),
+            "Get/path/02f09a3f-1624-3b1d-8409-44eff7708208/Duplicate route\nPost/path2/Other route"
+                .to_string()
+        )
     }
 }
@@ -17,9 +17,11 @@ use std::time::Duration;
 use async_trait::async_trait;
 use futures_util::{future, pin_mut, SinkExt, StreamExt};
 use golem_client::model::{
-    CallingConvention, InvokeParameters, InvokeResult, ScanCursor, UpdateWorkerRequest,
-    WorkerCreationRequest, WorkerFilter, WorkerId, WorkerMetadata, WorkersMetadataRequest,
-    WorkersMetadataResponse,
+    CallingConvention, CancelInvocationStatus, ForkWorkerRequest, GetOplogResponse,
+    GetPendingPromisesResponse, GetScheduledInvocationsResponse, InvokeParameters, InvokeResult,
+    ListWorkerDirectoryResponse, RevertWorkerTarget, ScanCursor, ScheduleInvocationParameters,
+    ScheduledInvocation, SearchOplogRequest, UpdateWorkerRequest, WorkerCreationRequest,
+    WorkerFilter, WorkerId, WorkerMetadata, WorkersMetadataRequest, WorkersMetadataResponse,
 };
 use golem_client::Context;
 use native_tls::TlsConnector;
@@ -29,6 +31,7 @@ use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::protocol::Message;
 use tokio_tungstenite::{connect_async_tls_with_config, Connector};
 use tracing::{debug, info};
+use uuid::Uuid;
 
 use crate::model::{ComponentId, GolemError, IdempotencyKey, WorkerName, WorkerUpdateMode};
 
@@ -102,6 +105,84 @@ pub trait WorkerClient {
         mode: WorkerUpdateMode,
         target_version: u64,
     ) -> Result<(), GolemError>;
+
+    async fn get_oplog(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        from: Option<u64>,
+        count: Option<u64>,
+    ) -> Result<GetOplogResponse, GolemError>;
+
+    async fn search_oplog(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        request: SearchOplogRequest,
+    ) -> Result<GetOplogResponse, GolemError>;
+
+    async fn fork(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        target_name: WorkerName,
+        oplog_index_cutoff: u64,
+    ) -> Result<(), GolemError>;
+
+    async fn revert(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        target: RevertWorkerTarget,
+    ) -> Result<(), GolemError>;
+
+    async fn cancel_invocation(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        idempotency_key: IdempotencyKey,
+    ) -> Result<CancelInvocationStatus, GolemError>;
+
+    async fn schedule_invocation(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        function: String,
+        parameters: ScheduleInvocationParameters,
+    ) -> Result<ScheduledInvocation, GolemError>;
+
+    async fn get_scheduled_invocations(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+    ) -> Result<GetScheduledInvocationsResponse, GolemError>;
+
+    async fn cancel_scheduled_invocation(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        schedule_id: Uuid,
+    ) -> Result<bool, GolemError>;
+
+    async fn list_directory(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        path: String,
+    ) -> Result<ListWorkerDirectoryResponse, GolemError>;
+
+    async fn read_file(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        path: String,
+    ) -> Result<Vec<u8>, GolemError>;
+
+    async fn get_pending_promises(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+    ) -> Result<GetPendingPromisesResponse, GolemError>;
 }
 
 #[derive(Clone)]
@@ -485,6 +566,191 @@ impl<C: golem_client::api::WorkerClient + Sync + Send> WorkerClient for WorkerCl
             .await?;
         Ok(())
     }
+
+    async fn get_oplog(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        from: Option<u64>,
+        count: Option<u64>,
+    ) -> Result<GetOplogResponse, GolemError> {
+        info!("Getting oplog of worker {}/{}", component_id.0, name.0);
+
+        Ok(self
+            .client
+            .get_oplog(&component_id.0, &name.0, from, count)
+            .await?)
+    }
+
+    async fn search_oplog(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        request: SearchOplogRequest,
+    ) -> Result<GetOplogResponse, GolemError> {
+        info!("Searching oplog of worker {}/{}", component_id.0, name.0);
+
+        Ok(self
+            .client
+            .search_oplog(&component_id.0, &name.0, &request)
+            .await?)
+    }
+
+    async fn fork(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        target_name: WorkerName,
+        oplog_index_cutoff: u64,
+    ) -> Result<(), GolemError> {
+        info!(
+            "Forking worker {}/{} into {} at oplog index {oplog_index_cutoff}",
+            component_id.0, name.0, target_name.0
+        );
+
+        let _ = self
+            .client
+            .fork_worker(
+                &component_id.0,
+                &name.0,
+                &ForkWorkerRequest {
+                    target_worker_name: target_name.0,
+                    oplog_index_cutoff,
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn revert(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        target: RevertWorkerTarget,
+    ) -> Result<(), GolemError> {
+        info!(
+            "Reverting worker {}/{} to {target:?}",
+            component_id.0, name.0
+        );
+
+        let _ = self
+            .client
+            .revert_worker(&component_id.0, &name.0, &target)
+            .await?;
+        Ok(())
+    }
+
+    async fn cancel_invocation(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        idempotency_key: IdempotencyKey,
+    ) -> Result<CancelInvocationStatus, GolemError> {
+        info!(
+            "Canceling invocation {} of {}/{}",
+            idempotency_key.0, component_id.0, name.0
+        );
+
+        let response = self
+            .client
+            .cancel_invocation(&component_id.0, &name.0, &idempotency_key.0)
+            .await?;
+        Ok(response.status)
+    }
+
+    async fn schedule_invocation(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        function: String,
+        parameters: ScheduleInvocationParameters,
+    ) -> Result<ScheduledInvocation, GolemError> {
+        info!(
+            "Scheduling invocation of {function} in {}/{}",
+            component_id.0, name.0
+        );
+
+        Ok(self
+            .client
+            .schedule_invocation(&component_id.0, &name.0, &function, &parameters)
+            .await?)
+    }
+
+    async fn get_scheduled_invocations(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+    ) -> Result<GetScheduledInvocationsResponse, GolemError> {
+        info!(
+            "Getting scheduled invocations of {}/{}",
+            component_id.0, name.0
+        );
+
+        Ok(self
+            .client
+            .get_scheduled_invocations(&component_id.0, &name.0)
+            .await?)
+    }
+
+    async fn cancel_scheduled_invocation(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        schedule_id: Uuid,
+    ) -> Result<bool, GolemError> {
+        info!(
+            "Canceling scheduled invocation {schedule_id} of {}/{}",
+            component_id.0, name.0
+        );
+
+        let response = self
+            .client
+            .cancel_scheduled_invocation(&component_id.0, &name.0, &schedule_id)
+            .await?;
+        Ok(response.canceled)
+    }
+
+    async fn list_directory(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        path: String,
+    ) -> Result<ListWorkerDirectoryResponse, GolemError> {
+        info!("Listing {path} of {}/{}", component_id.0, name.0);
+
+        Ok(self
+            .client
+            .list_worker_directory(&component_id.0, &name.0, Some(&path))
+            .await?)
+    }
+
+    async fn read_file(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+        path: String,
+    ) -> Result<Vec<u8>, GolemError> {
+        info!("Reading {path} of {}/{}", component_id.0, name.0);
+
+        let content = self
+            .client
+            .read_worker_file(&component_id.0, &name.0, &path)
+            .await?;
+        Ok(content.to_vec())
+    }
+
+    async fn get_pending_promises(
+        &self,
+        name: WorkerName,
+        component_id: ComponentId,
+    ) -> Result<GetPendingPromisesResponse, GolemError> {
+        info!("Getting pending promises of {}/{}", component_id.0, name.0);
+
+        Ok(self
+            .client
+            .get_pending_promises(&component_id.0, &name.0)
+            .await?)
+    }
 }
 
 #[derive(Deserialize, Debug)]
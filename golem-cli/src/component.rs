@@ -22,7 +22,8 @@ use crate::clients::component::ComponentClient;
 use crate::model::component::ComponentView;
 use crate::model::text::{ComponentAddView, ComponentGetView, ComponentUpdateView};
 use crate::model::{
-    ComponentId, ComponentIdOrName, ComponentName, GolemError, GolemResult, PathBufOrStdin,
+    ComponentId, ComponentIdOrName, ComponentName, GolemError, GolemResult, InitialFile,
+    PathBufOrStdin,
 };
 
 #[derive(Subcommand, Debug)]
@@ -38,6 +39,11 @@ pub enum ComponentSubCommand {
         /// The WASM file to be used as a Golem component
         #[arg(value_name = "component-file", value_hint = clap::ValueHint::FilePath)]
         component_file: PathBufOrStdin, // TODO: validate exists
+
+        /// Files to copy into the file system of each worker of the component, as
+        /// <local-path>:<path>[:ro|rw]. Files are read-only unless marked rw
+        #[arg(long, value_name = "file")]
+        files: Vec<InitialFile>,
     },
 
     /// Updates an existing component by uploading a new version of its WASM
@@ -101,8 +107,12 @@ impl<C: ComponentClient + Send + Sync> ComponentHandler for ComponentHandlerLive
             ComponentSubCommand::Add {
                 component_name,
                 component_file,
+                files,
             } => {
-                let component = self.client.add(component_name, component_file).await?;
+                let component = self
+                    .client
+                    .add(component_name, component_file, files)
+                    .await?;
                 let view: ComponentView = component.into();
 
                 Ok(GolemResult::Ok(Box::new(ComponentAddView(view))))
@@ -328,6 +328,46 @@ impl FromStr for PathBufOrStdin {
     }
 }
 
+/// A file to upload with a new component, given as `<local-path>:<path>[:ro|rw]` where `path` is
+/// where the file appears in the file system of the workers
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InitialFile {
+    pub source: PathBuf,
+    pub path: String,
+    pub read_only: bool,
+}
+
+impl FromStr for InitialFile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        let (source, path, read_only) = match parts.as_slice() {
+            [source, path] => (source, path, true),
+            [source, path, "ro"] => (source, path, true),
+            [source, path, "rw"] => (source, path, false),
+            _ => {
+                return Err(format!(
+                    "Invalid file: {s}. Expected <local-path>:<path>[:ro|rw]"
+                ))
+            }
+        };
+
+        let path = path.trim_start_matches('/');
+        if source.is_empty() || path.is_empty() {
+            return Err(format!(
+                "Invalid file: {s}. Expected <local-path>:<path>[:ro|rw]"
+            ));
+        }
+
+        Ok(InitialFile {
+            source: PathBuf::from(source),
+            path: path.to_string(),
+            read_only,
+        })
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug, Display)]
 pub enum WorkerUpdateMode {
     Automatic,
@@ -147,6 +147,8 @@ mod tests {
                     results: func_res,
                 })],
             },
+            files: None,
+            retention_policy: None,
         };
 
         InvokeResultView::try_parse_or_json(InvokeResult { result: json }, &component, "func_name")
@@ -3,7 +3,9 @@ use crate::model::invoke_result_view::InvokeResultView;
 use crate::model::{ExampleDescription, IdempotencyKey};
 use cli_table::{format::Justify, print_stdout, Table, WithTitle};
 use golem_client::model::{
-    ApiDeployment, HttpApiDefinition, Route, ScanCursor, WorkerId, WorkerMetadata,
+    ApiDeployment, GetOplogResponse, GetPendingPromisesResponse, GetScheduledInvocationsResponse,
+    HttpApiDefinition, ListWorkerDirectoryResponse, PendingPromise, PublicOplogEntry, Route,
+    ScanCursor, ScheduledInvocation, WorkerFileEntry, WorkerId, WorkerMetadata,
     WorkersMetadataResponse,
 };
 use golem_examples::model::{ExampleName, GuestLanguage, GuestLanguageTier};
@@ -75,13 +77,25 @@ struct RouteView {
 
 impl From<&Route> for RouteView {
     fn from(value: &Route) -> Self {
-        let component_str = value.binding.component_id.to_string();
-        let component_end = &component_str[component_str.len() - 7..];
+        // Static and file server routes are served by the gateway, without a worker
+        let component_id = match &value.binding.component_id {
+            Some(component_id) => {
+                let component_str = component_id.to_string();
+                let component_end = &component_str[component_str.len() - 7..];
+                format!("*{component_end}")
+            }
+            None => "-".to_string(),
+        };
+
         RouteView {
             method: value.method.to_string(),
             path: value.path.to_string(),
-            component_id: format!("*{component_end}"),
-            worker_name: value.binding.worker_name.to_string(),
+            component_id,
+            worker_name: value
+                .binding
+                .worker_name
+                .clone()
+                .unwrap_or_else(|| "-".to_string()),
         }
     }
 }
@@ -398,6 +412,218 @@ impl TextFormat for WorkersMetadataResponse {
     }
 }
 
+#[derive(Table)]
+struct OplogEntryView {
+    #[table(title = "Index", justify = "Justify::Right")]
+    pub oplog_index: u64,
+    #[table(title = "Timestamp")]
+    pub timestamp: String,
+    #[table(title = "Entry")]
+    pub entry_type: String,
+    #[table(title = "Function")]
+    pub function_name: String,
+    #[table(title = "Payload")]
+    pub payload: String,
+    #[table(title = "Details")]
+    pub details: String,
+}
+
+impl From<&PublicOplogEntry> for OplogEntryView {
+    fn from(value: &PublicOplogEntry) -> Self {
+        let payload = value
+            .response
+            .as_ref()
+            .or(value.request.as_ref())
+            .map(|payload| {
+                payload
+                    .wave
+                    .clone()
+                    .unwrap_or_else(|| payload.json.to_string())
+            })
+            .unwrap_or_default();
+
+        Self {
+            oplog_index: value.oplog_index,
+            timestamp: value.timestamp.to_rfc3339(),
+            entry_type: value.entry_type.to_string(),
+            function_name: value.function_name.clone().unwrap_or_default(),
+            payload,
+            details: value.details.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl TextFormat for GetOplogResponse {
+    fn print(&self) {
+        print_stdout(
+            self.entries
+                .iter()
+                .map(OplogEntryView::from)
+                .collect::<Vec<_>>()
+                .with_title(),
+        )
+        .unwrap();
+
+        if let Some(next) = self.next {
+            let last_index = self.last_index;
+            printdoc!(
+                "
+                There are more oplog entries to display (last index is {last_index}).
+                To fetch next page use index {next} this way:
+                worker oplog --from {next} ...
+                "
+            )
+        }
+    }
+}
+
+impl TextFormat for ScheduledInvocation {
+    fn print(&self) {
+        let id = self.id;
+        let function_name = &self.function_name;
+        let scheduled_at = self.scheduled_at.to_rfc3339();
+        match &self.cron {
+            Some(cron) => printdoc!(
+                "
+                Scheduled invocation {id} of {function_name} with cron schedule '{cron}'
+                First invocation at {scheduled_at}
+                "
+            ),
+            None => printdoc!(
+                "
+                Scheduled invocation {id} of {function_name} at {scheduled_at}
+                "
+            ),
+        }
+    }
+}
+
+#[derive(Table)]
+struct ScheduledInvocationView {
+    #[table(title = "ID")]
+    pub id: Uuid,
+    #[table(title = "Next invocation")]
+    pub scheduled_at: String,
+    #[table(title = "Function")]
+    pub function_name: String,
+    #[table(title = "Parameters")]
+    pub params: String,
+    #[table(title = "Cron")]
+    pub cron: String,
+}
+
+impl From<&ScheduledInvocation> for ScheduledInvocationView {
+    fn from(value: &ScheduledInvocation) -> Self {
+        Self {
+            id: value.id,
+            scheduled_at: value.scheduled_at.to_rfc3339(),
+            function_name: value.function_name.clone(),
+            params: value
+                .params
+                .as_ref()
+                .map(|params| {
+                    params
+                        .wave
+                        .clone()
+                        .unwrap_or_else(|| params.json.to_string())
+                })
+                .unwrap_or_default(),
+            cron: value.cron.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl TextFormat for GetScheduledInvocationsResponse {
+    fn print(&self) {
+        print_stdout(
+            self.invocations
+                .iter()
+                .map(ScheduledInvocationView::from)
+                .collect::<Vec<_>>()
+                .with_title(),
+        )
+        .unwrap();
+    }
+}
+
+#[derive(Table)]
+struct WorkerFileEntryView {
+    #[table(title = "Name")]
+    pub name: String,
+    #[table(title = "Size")]
+    pub size: String,
+    #[table(title = "Permissions")]
+    pub permissions: String,
+}
+
+impl From<&WorkerFileEntry> for WorkerFileEntryView {
+    fn from(value: &WorkerFileEntry) -> Self {
+        Self {
+            name: if value.is_directory {
+                format!("{}/", value.name)
+            } else {
+                value.name.clone()
+            },
+            size: if value.is_directory {
+                String::new()
+            } else {
+                value.size.to_string()
+            },
+            permissions: if value.read_only {
+                "read-only".to_string()
+            } else {
+                "read-write".to_string()
+            },
+        }
+    }
+}
+
+impl TextFormat for ListWorkerDirectoryResponse {
+    fn print(&self) {
+        print_stdout(
+            self.entries
+                .iter()
+                .map(WorkerFileEntryView::from)
+                .collect::<Vec<_>>()
+                .with_title(),
+        )
+        .unwrap();
+    }
+}
+
+#[derive(Table)]
+struct PendingPromiseView {
+    #[table(title = "Oplog index")]
+    pub oplog_idx: u64,
+    #[table(title = "Expires at")]
+    pub expires_at: String,
+}
+
+impl From<&PendingPromise> for PendingPromiseView {
+    fn from(value: &PendingPromise) -> Self {
+        Self {
+            oplog_idx: value.promise_id.oplog_idx,
+            expires_at: value
+                .expires_at
+                .map(|expires_at| expires_at.to_rfc3339())
+                .unwrap_or("never".to_string()),
+        }
+    }
+}
+
+impl TextFormat for GetPendingPromisesResponse {
+    fn print(&self) {
+        print_stdout(
+            self.promises
+                .iter()
+                .map(PendingPromiseView::from)
+                .collect::<Vec<_>>()
+                .with_title(),
+        )
+        .unwrap();
+    }
+}
+
 impl TextFormat for ScanCursor {
     fn print(&self) {
         let layer = self.layer;
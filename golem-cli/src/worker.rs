@@ -14,14 +14,18 @@
 
 use crate::clients::component::ComponentClientLive;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use clap::builder::ValueParser;
 use clap::Subcommand;
 use golem_client::model::{
-    Component, InvokeParameters, InvokeResult, ScanCursor, StringFilterComparator, Type,
-    WorkerFilter, WorkerMetadata, WorkerNameFilter, WorkersMetadataResponse,
+    CancelInvocationStatus, Component, GetOplogResponse, InvokeParameters, InvokeResult,
+    OplogEntryType, RevertLastInvocations, RevertToOplogIndex, RevertWorkerTarget, ScanCursor,
+    ScheduleInvocationParameters, SearchOplogRequest, StringFilterComparator, Type, WorkerFilter,
+    WorkerMetadata, WorkerNameFilter, WorkersMetadataResponse,
 };
 use golem_client::Context;
 use golem_wasm_rpc::TypeAnnotatedValue;
+use std::path::PathBuf;
 use tokio::task::JoinHandle;
 use tracing::{error, info};
 use uuid::Uuid;
@@ -248,6 +252,227 @@ pub enum WorkerSubcommand {
         #[arg(short = 't', long)]
         target_version: u64,
     },
+    /// Lists and searches the oplog of a worker
+    #[command()]
+    Oplog {
+        /// The Golem component of the worker, identified by either its name or its component ID
+        #[command(flatten)]
+        component_id_or_name: ComponentIdOrName,
+
+        /// Name of the worker
+        #[arg(short, long)]
+        worker_name: WorkerName,
+
+        /// Oplog index where to start listing, if not provided, starts from the beginning
+        ///
+        /// It is used to get the next page of results. To get next page, use the next index returned in the response.
+        #[arg(short = 'i', long)]
+        from: Option<u64>,
+
+        /// Count of listed entries, if count is not provided, returns all entries
+        #[arg(short = 'n', long)]
+        count: Option<u64>,
+
+        /// Only list entries of the given type, for example `ExportedFunctionInvoked`
+        ///
+        /// Can be used multiple times (OR condition is applied between them)
+        #[arg(short = 't', long = "entry-type", value_parser = parse_oplog_entry_type)]
+        entry_types: Vec<OplogEntryType>,
+
+        /// Only list entries recorded after the given time (RFC 3339)
+        #[arg(short, long)]
+        after: Option<DateTime<Utc>>,
+
+        /// Only list entries recorded before the given time (RFC 3339)
+        #[arg(short, long)]
+        before: Option<DateTime<Utc>>,
+
+        /// Only list entries belonging to invocations of the given function
+        #[arg(short, long)]
+        function: Option<String>,
+    },
+    /// Creates a new worker by copying an existing worker's oplog up to a given index
+    ///
+    /// The new worker replays the copied oplog and then continues running live from that point.
+    #[command()]
+    Fork {
+        /// The Golem component of the worker, identified by either its name or its component ID
+        #[command(flatten)]
+        component_id_or_name: ComponentIdOrName,
+
+        /// Name of the worker to fork
+        #[arg(short, long)]
+        worker_name: WorkerName,
+
+        /// Name of the newly created worker
+        #[arg(short, long)]
+        target_worker_name: WorkerName,
+
+        /// The last oplog index to be copied to the new worker
+        #[arg(short = 'i', long)]
+        oplog_index_cutoff: u64,
+    },
+    /// Reverts a worker to an earlier state by dropping the end of its oplog
+    ///
+    /// The worker gets restarted and recovers to the state it had at the target oplog index.
+    #[command()]
+    Revert {
+        /// The Golem component of the worker, identified by either its name or its component ID
+        #[command(flatten)]
+        component_id_or_name: ComponentIdOrName,
+
+        /// Name of the worker to revert
+        #[arg(short, long)]
+        worker_name: WorkerName,
+
+        /// The last oplog index to keep
+        #[arg(
+            short = 'i',
+            long,
+            conflicts_with = "number_of_invocations",
+            required = true
+        )]
+        last_oplog_index: Option<u64>,
+
+        /// The number of invocations to revert, counting from the last one
+        #[arg(short, long, conflicts_with = "last_oplog_index", required = true)]
+        number_of_invocations: Option<u64>,
+    },
+    /// Cancels a pending invocation of a worker, if it has not been started yet
+    #[command()]
+    CancelInvocation {
+        /// The Golem component of the worker, identified by either its name or its component ID
+        #[command(flatten)]
+        component_id_or_name: ComponentIdOrName,
+
+        /// Name of the worker
+        #[arg(short, long)]
+        worker_name: WorkerName,
+
+        /// The idempotency key of the invocation to be canceled
+        #[arg(short = 'k', long)]
+        idempotency_key: IdempotencyKey,
+    },
+    /// Schedules an invocation of a function at a given time or recurring by a cron schedule
+    #[command()]
+    ScheduleInvocation {
+        /// The Golem component of the worker, identified by either its name or its component ID
+        #[command(flatten)]
+        component_id_or_name: ComponentIdOrName,
+
+        /// Name of the worker
+        #[arg(short, long)]
+        worker_name: WorkerName,
+
+        /// Name of the function to be invoked
+        #[arg(short, long)]
+        function: String,
+
+        /// JSON array representing the parameters to be passed to the function
+        #[arg(short = 'j', long, value_name = "json", value_parser = ValueParser::new(JsonValueParser), conflicts_with = "wave")]
+        parameters: Option<serde_json::value::Value>,
+
+        /// Function parameter in WAVE format
+        ///
+        /// You can specify this argument multiple times for multiple parameters.
+        #[arg(
+            short = 'p',
+            long = "param",
+            value_name = "wave",
+            conflicts_with = "parameters"
+        )]
+        wave: Vec<String>,
+
+        /// Invoke the function once at the given time (RFC 3339)
+        #[arg(short, long, conflicts_with = "cron", required = true)]
+        at: Option<DateTime<Utc>>,
+
+        /// Invoke the function repeatedly by the given cron expression, evaluated in UTC
+        #[arg(long, conflicts_with = "at", required = true)]
+        cron: Option<String>,
+    },
+    /// Lists the scheduled invocations of a worker
+    #[command()]
+    ListScheduledInvocations {
+        /// The Golem component of the worker, identified by either its name or its component ID
+        #[command(flatten)]
+        component_id_or_name: ComponentIdOrName,
+
+        /// Name of the worker
+        #[arg(short, long)]
+        worker_name: WorkerName,
+    },
+    /// Cancels a scheduled invocation, including all of its future occurrences
+    #[command()]
+    CancelScheduledInvocation {
+        /// The Golem component of the worker, identified by either its name or its component ID
+        #[command(flatten)]
+        component_id_or_name: ComponentIdOrName,
+
+        /// Name of the worker
+        #[arg(short, long)]
+        worker_name: WorkerName,
+
+        /// The ID of the scheduled invocation
+        #[arg(short, long)]
+        schedule_id: Uuid,
+    },
+    /// Lists the promises a worker created which are not completed yet
+    #[command()]
+    ListPromises {
+        /// The Golem component of the worker, identified by either its name or its component ID
+        #[command(flatten)]
+        component_id_or_name: ComponentIdOrName,
+
+        /// Name of the worker
+        #[arg(short, long)]
+        worker_name: WorkerName,
+    },
+    /// Inspects the files of a running or suspended worker
+    #[command()]
+    Files {
+        #[command(subcommand)]
+        subcommand: WorkerFilesSubcommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+#[command()]
+pub enum WorkerFilesSubcommand {
+    /// Lists a directory of the worker's file system
+    #[command()]
+    Ls {
+        /// The Golem component of the worker, identified by either its name or its component ID
+        #[command(flatten)]
+        component_id_or_name: ComponentIdOrName,
+
+        /// Name of the worker
+        #[arg(short, long)]
+        worker_name: WorkerName,
+
+        /// Path of the directory, relative to the root of the worker's file system
+        #[arg(short, long, default_value = "/")]
+        path: String,
+    },
+    /// Prints the content of a file of the worker's file system
+    #[command()]
+    Cat {
+        /// The Golem component of the worker, identified by either its name or its component ID
+        #[command(flatten)]
+        component_id_or_name: ComponentIdOrName,
+
+        /// Name of the worker
+        #[arg(short, long)]
+        worker_name: WorkerName,
+
+        /// Path of the file, relative to the root of the worker's file system
+        #[arg(short, long)]
+        path: String,
+
+        /// Saves the content to a local file instead of printing it
+        #[arg(short, long, value_name = "file")]
+        output: Option<PathBuf>,
+    },
 }
 
 #[async_trait]
@@ -690,10 +915,280 @@ impl<'r, C: WorkerClient + Send + Sync, R: ComponentHandler + Send + Sync> Worke
 
                 Ok(GolemResult::Str("Updated".to_string()))
             }
+            WorkerSubcommand::Oplog {
+                component_id_or_name,
+                worker_name,
+                from,
+                count,
+                entry_types,
+                after,
+                before,
+                function,
+            } => {
+                let component_id = self.components.resolve_id(component_id_or_name).await?;
+
+                let has_filter = !entry_types.is_empty()
+                    || after.is_some()
+                    || before.is_some()
+                    || function.is_some();
+
+                let get_page = |from: Option<u64>, count: Option<u64>| {
+                    let worker_name = worker_name.clone();
+                    let component_id = component_id.clone();
+                    let request = SearchOplogRequest {
+                        entry_types: Some(entry_types.clone()),
+                        after,
+                        before,
+                        function_name: function.clone(),
+                        cursor: from,
+                        count,
+                    };
+                    async move {
+                        if has_filter {
+                            self.client
+                                .search_oplog(worker_name, component_id, request)
+                                .await
+                        } else {
+                            self.client
+                                .get_oplog(worker_name, component_id, from, count)
+                                .await
+                        }
+                    }
+                };
+
+                if count.is_some() {
+                    let response = get_page(from, count).await?;
+
+                    Ok(GolemResult::Ok(Box::new(response)))
+                } else {
+                    let mut entries = vec![];
+                    let mut next = from;
+
+                    loop {
+                        let response = get_page(next, Some(50)).await?;
+
+                        entries.extend(response.entries);
+                        next = response.next;
+
+                        if next.is_none() {
+                            break Ok(GolemResult::Ok(Box::new(GetOplogResponse {
+                                entries,
+                                next: None,
+                                last_index: response.last_index,
+                            })));
+                        }
+                    }
+                }
+            }
+            WorkerSubcommand::Fork {
+                component_id_or_name,
+                worker_name,
+                target_worker_name,
+                oplog_index_cutoff,
+            } => {
+                let component_id = self.components.resolve_id(component_id_or_name).await?;
+
+                self.client
+                    .fork(
+                        worker_name,
+                        component_id,
+                        target_worker_name,
+                        oplog_index_cutoff,
+                    )
+                    .await?;
+
+                Ok(GolemResult::Str("Forked".to_string()))
+            }
+            WorkerSubcommand::Revert {
+                component_id_or_name,
+                worker_name,
+                last_oplog_index,
+                number_of_invocations,
+            } => {
+                let component_id = self.components.resolve_id(component_id_or_name).await?;
+
+                let target = match (last_oplog_index, number_of_invocations) {
+                    (Some(last_oplog_index), _) => {
+                        RevertWorkerTarget::RevertToOplogIndex(RevertToOplogIndex {
+                            last_oplog_index,
+                        })
+                    }
+                    (None, Some(number_of_invocations)) => {
+                        RevertWorkerTarget::RevertLastInvocations(RevertLastInvocations {
+                            number_of_invocations,
+                        })
+                    }
+                    (None, None) => return Err(GolemError("Missing revert target".to_string())),
+                };
+
+                self.client
+                    .revert(worker_name, component_id, target)
+                    .await?;
+
+                Ok(GolemResult::Str("Reverted".to_string()))
+            }
+            WorkerSubcommand::CancelInvocation {
+                component_id_or_name,
+                worker_name,
+                idempotency_key,
+            } => {
+                let component_id = self.components.resolve_id(component_id_or_name).await?;
+
+                let status = self
+                    .client
+                    .cancel_invocation(worker_name, component_id, idempotency_key)
+                    .await?;
+
+                let message = match status {
+                    CancelInvocationStatus::Canceled => "Canceled",
+                    CancelInvocationStatus::AlreadyRunning => "Invocation is already running",
+                    CancelInvocationStatus::AlreadyCompleted => "Invocation was already completed",
+                    CancelInvocationStatus::NotFound => "Invocation not found",
+                };
+                Ok(GolemResult::Str(message.to_string()))
+            }
+            WorkerSubcommand::ScheduleInvocation {
+                component_id_or_name,
+                worker_name,
+                function,
+                parameters,
+                wave,
+                at,
+                cron,
+            } => {
+                let component_id = self.components.resolve_id(component_id_or_name).await?;
+
+                let (parameters, _) = resolve_parameters(
+                    &self.client,
+                    self.components,
+                    &component_id,
+                    &worker_name,
+                    parameters,
+                    wave,
+                    &function,
+                )
+                .await?;
+
+                let invocation = self
+                    .client
+                    .schedule_invocation(
+                        worker_name,
+                        component_id,
+                        function,
+                        ScheduleInvocationParameters {
+                            params: parameters,
+                            at,
+                            cron,
+                        },
+                    )
+                    .await?;
+
+                Ok(GolemResult::Ok(Box::new(invocation)))
+            }
+            WorkerSubcommand::ListScheduledInvocations {
+                component_id_or_name,
+                worker_name,
+            } => {
+                let component_id = self.components.resolve_id(component_id_or_name).await?;
+
+                let response = self
+                    .client
+                    .get_scheduled_invocations(worker_name, component_id)
+                    .await?;
+
+                Ok(GolemResult::Ok(Box::new(response)))
+            }
+            WorkerSubcommand::CancelScheduledInvocation {
+                component_id_or_name,
+                worker_name,
+                schedule_id,
+            } => {
+                let component_id = self.components.resolve_id(component_id_or_name).await?;
+
+                let canceled = self
+                    .client
+                    .cancel_scheduled_invocation(worker_name, component_id, schedule_id)
+                    .await?;
+
+                if canceled {
+                    Ok(GolemResult::Str("Canceled".to_string()))
+                } else {
+                    Ok(GolemResult::Str("No such scheduled invocation".to_string()))
+                }
+            }
+            WorkerSubcommand::ListPromises {
+                component_id_or_name,
+                worker_name,
+            } => {
+                let component_id = self.components.resolve_id(component_id_or_name).await?;
+
+                let response = self
+                    .client
+                    .get_pending_promises(worker_name, component_id)
+                    .await?;
+
+                Ok(GolemResult::Ok(Box::new(response)))
+            }
+            WorkerSubcommand::Files {
+                subcommand:
+                    WorkerFilesSubcommand::Ls {
+                        component_id_or_name,
+                        worker_name,
+                        path,
+                    },
+            } => {
+                let component_id = self.components.resolve_id(component_id_or_name).await?;
+
+                let response = self
+                    .client
+                    .list_directory(worker_name, component_id, path)
+                    .await?;
+
+                Ok(GolemResult::Ok(Box::new(response)))
+            }
+            WorkerSubcommand::Files {
+                subcommand:
+                    WorkerFilesSubcommand::Cat {
+                        component_id_or_name,
+                        worker_name,
+                        path,
+                        output,
+                    },
+            } => {
+                let component_id = self.components.resolve_id(component_id_or_name).await?;
+
+                let content = self
+                    .client
+                    .read_file(worker_name, component_id, path.clone())
+                    .await?;
+
+                match output {
+                    Some(output) => {
+                        std::fs::write(&output, content).map_err(|e| {
+                            GolemError(format!("Failed to write {}: {e}", output.display()))
+                        })?;
+                        Ok(GolemResult::Str(format!(
+                            "Saved {path} to {}",
+                            output.display()
+                        )))
+                    }
+                    None => Ok(GolemResult::Str(
+                        String::from_utf8_lossy(&content).to_string(),
+                    )),
+                }
+            }
         }
     }
 }
 
+fn parse_oplog_entry_type(
+    s: &str,
+) -> Result<OplogEntryType, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    Ok(serde_json::from_value(serde_json::Value::String(
+        s.to_string(),
+    ))?)
+}
+
 fn parse_cursor(s: &str) -> Result<ScanCursor, Box<dyn std::error::Error + Send + Sync + 'static>> {
     let parts = s.split('/').collect::<Vec<_>>();
 
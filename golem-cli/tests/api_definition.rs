@@ -1,7 +1,9 @@
 use crate::cli::{Cli, CliLive};
 use crate::worker::make_component_from_file;
 use golem_cli::model::component::ComponentView;
-use golem_client::model::{GolemWorkerBinding, HttpApiDefinition, MethodPattern, Route};
+use golem_client::model::{
+    BindingType, GolemWorkerBinding, HttpApiDefinition, MethodPattern, Route,
+};
 use golem_test_framework::config::TestDependencies;
 use libtest_mimic::{Failed, Trial};
 use serde_json::json;
@@ -108,17 +110,25 @@ fn golem_def_with_response(id: &str, component_id: &str, response: String) -> Ht
             method: MethodPattern::Get,
             path: "/{user-id}/get-cart-contents".to_string(),
             binding: GolemWorkerBinding {
-                component_id: Uuid::parse_str(component_id).unwrap(),
-                worker_name: "worker-${request.path.user-id}".to_string(),
+                binding_type: Some(BindingType::WitWorker),
+                component_id: Some(Uuid::parse_str(component_id).unwrap()),
+                worker_name: Some("worker-${request.path.user-id}".to_string()),
                 idempotency_key: None,
                 response,
+                workers: Some(vec![]),
             },
+            security: None,
+            rate_limit: None,
+            stream: None,
         }],
+        security_schemes: Some(vec![]),
+        cors: None,
+        functions: Some(vec![]),
     }
 }
 
 pub fn golem_def(id: &str, component_id: &str) -> HttpApiDefinition {
-    golem_def_with_response(id, component_id, "${{headers: {ContentType: \"json\", userid: \"foo\"}, body: worker.response, status: 200}}".to_string())
+    golem_def_with_response(id, component_id, "${{headers: {ContentType: \"json\", userid: \"foo\"}, body: request.path.user-id, status: 200}}".to_string())
 }
 
 pub fn make_golem_file(def: &HttpApiDefinition) -> Result<PathBuf, Failed> {
@@ -142,7 +152,7 @@ pub fn make_open_api_file(id: &str, component_id: &str) -> Result<PathBuf, Faile
               "x-golem-worker-bridge": {
                 "worker-name": "worker-${request.path.user-id}",
                 "component-id": component_id,
-                "response" : "${{headers : {ContentType: \"json\", userid: \"foo\"}, body: worker.response, status: 200}}"
+                "response" : "${{headers : {ContentType: \"json\", userid: \"foo\"}, body: request.path.user-id, status: 200}}"
               },
               "get": {
                 "summary": "Get Cart Contents",
@@ -256,7 +266,7 @@ fn api_definition_update(
     let updated = golem_def_with_response(
         &component_name,
         &component.component_id,
-        "${{headers: {ContentType: \"json\", userid: \"bar\"}, body: worker.response, status: 200}}"
+        "${{headers: {ContentType: \"json\", userid: \"bar\"}, body: request.path.user-id, status: 200}}"
             .to_string(),
     );
     let path = make_golem_file(&updated)?;
@@ -282,7 +292,7 @@ fn api_definition_update_immutable(
     let path = make_golem_file(&def)?;
     let _: HttpApiDefinition = cli.run(&["api-definition", "add", path.to_str().unwrap()])?;
 
-    let updated = golem_def_with_response(&component_name, &component.component_id, "${{headers: {ContentType: \"json\", userid: \"bar\"}, body: worker.response, status: 200}}".to_string());
+    let updated = golem_def_with_response(&component_name, &component.component_id, "${{headers: {ContentType: \"json\", userid: \"bar\"}, body: request.path.user-id, status: 200}}".to_string());
     let path = make_golem_file(&updated)?;
     let res = cli.run_string(&["api-definition", "update", path.to_str().unwrap()]);
 
@@ -1,7 +1,10 @@
 use crate::cli::{Cli, CliLive};
 use golem_cli::model::component::ComponentView;
 use golem_cli::model::{Format, IdempotencyKey};
-use golem_client::model::{UpdateRecord, WorkerId, WorkersMetadataResponse};
+use golem_client::model::{
+    GetOplogResponse, OplogEntryType, UpdateRecord, WorkerId, WorkerMetadata,
+    WorkersMetadataResponse,
+};
 use golem_test_framework::config::TestDependencies;
 use indoc::formatdoc;
 use libtest_mimic::{Failed, Trial};
@@ -70,6 +73,25 @@ fn make(
         ),
         Trial::test_in_context(format!("worker_list{suffix}"), ctx.clone(), worker_list),
         Trial::test_in_context(format!("worker_update{suffix}"), ctx.clone(), worker_update),
+        Trial::test_in_context(format!("worker_oplog{suffix}"), ctx.clone(), worker_oplog),
+        Trial::test_in_context(format!("worker_fork{suffix}"), ctx.clone(), worker_fork),
+        Trial::test_in_context(format!("worker_revert{suffix}"), ctx.clone(), worker_revert),
+        Trial::test_in_context(
+            format!("worker_cancel_invocation{suffix}"),
+            ctx.clone(),
+            worker_cancel_invocation,
+        ),
+        Trial::test_in_context(
+            format!("worker_scheduled_invocations{suffix}"),
+            ctx.clone(),
+            worker_scheduled_invocations,
+        ),
+        Trial::test_in_context(format!("worker_files{suffix}"), ctx.clone(), worker_files),
+        Trial::test_in_context(
+            format!("worker_list_promises{suffix}"),
+            ctx.clone(),
+            worker_list_promises,
+        ),
     ]
 }
 
@@ -755,3 +777,493 @@ fn worker_update(
     assert_eq!(target_version, 1);
     Ok(())
 }
+
+fn worker_oplog(
+    (deps, name, cli): (
+        Arc<dyn TestDependencies + Send + Sync + 'static>,
+        String,
+        CliLive,
+    ),
+) -> Result<(), Failed> {
+    let component_id = make_component(deps, &format!("{name} worker_oplog"), &cli)?.component_id;
+    let worker_name = format!("{name}_worker_oplog");
+    let cfg = &cli.config;
+    let _: WorkerId = cli.run(&[
+        "worker",
+        "add",
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        "test-arg",
+    ])?;
+    let _ = cli.run_json(&[
+        "worker",
+        "invoke-and-await",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('f', "function"),
+        "golem:it/api/get-arguments",
+        &cfg.arg('j', "parameters"),
+        "[]",
+    ])?;
+
+    let oplog: GetOplogResponse = cli.run(&[
+        "worker",
+        "oplog",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+    ])?;
+
+    assert_eq!(oplog.entries[0].entry_type, OplogEntryType::Create);
+    assert_eq!(oplog.next, None);
+
+    let invocations: GetOplogResponse = cli.run(&[
+        "worker",
+        "oplog",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('t', "entry-type"),
+        "ExportedFunctionCompleted",
+        &cfg.arg('f', "function"),
+        "golem:it/api/get-arguments",
+    ])?;
+
+    assert_eq!(invocations.entries.len(), 1);
+    assert_eq!(
+        invocations.entries[0]
+            .response
+            .as_ref()
+            .map(|r| r.json.clone()),
+        Some(json!([{"ok": ["test-arg"]}]))
+    );
+    Ok(())
+}
+
+fn worker_fork(
+    (deps, name, cli): (
+        Arc<dyn TestDependencies + Send + Sync + 'static>,
+        String,
+        CliLive,
+    ),
+) -> Result<(), Failed> {
+    let component_id = make_component(deps, &format!("{name} worker_fork"), &cli)?.component_id;
+    let worker_name = format!("{name}_worker_fork");
+    let forked_worker_name = format!("{name}_worker_fork_forked");
+    let cfg = &cli.config;
+    let _: WorkerId = cli.run(&[
+        "worker",
+        "add",
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        "test-arg",
+    ])?;
+    let _ = cli.run_json(&[
+        "worker",
+        "invoke-and-await",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('f', "function"),
+        "golem:it/api/get-arguments",
+        &cfg.arg('j', "parameters"),
+        "[]",
+    ])?;
+
+    cli.run_unit(&[
+        "worker",
+        "fork",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('t', "target-worker-name"),
+        &forked_worker_name,
+        &cfg.arg('i', "oplog-index-cutoff"),
+        "1",
+    ])?;
+
+    let forked: WorkerMetadata = cli.run(&[
+        "worker",
+        "get",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &forked_worker_name,
+    ])?;
+
+    assert_eq!(forked.worker_id.worker_name, forked_worker_name);
+    assert_eq!(forked.args, vec!["test-arg".to_string()]);
+    Ok(())
+}
+
+fn worker_revert(
+    (deps, name, cli): (
+        Arc<dyn TestDependencies + Send + Sync + 'static>,
+        String,
+        CliLive,
+    ),
+) -> Result<(), Failed> {
+    let component_id = make_component(deps, &format!("{name} worker_revert"), &cli)?.component_id;
+    let worker_name = format!("{name}_worker_revert");
+    let cfg = &cli.config;
+    let _: WorkerId = cli.run(&[
+        "worker",
+        "add",
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        "test-arg",
+    ])?;
+    for _ in 0..2 {
+        let _ = cli.run_json(&[
+            "worker",
+            "invoke-and-await",
+            &cfg.arg('C', "component-id"),
+            &component_id,
+            &cfg.arg('w', "worker-name"),
+            &worker_name,
+            &cfg.arg('f', "function"),
+            "golem:it/api/get-arguments",
+            &cfg.arg('j', "parameters"),
+            "[]",
+        ])?;
+    }
+
+    cli.run_unit(&[
+        "worker",
+        "revert",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('n', "number-of-invocations"),
+        "1",
+    ])?;
+
+    let jumps: GetOplogResponse = cli.run(&[
+        "worker",
+        "oplog",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('t', "entry-type"),
+        "Jump",
+    ])?;
+
+    assert_eq!(jumps.entries.len(), 1);
+    Ok(())
+}
+
+fn worker_cancel_invocation(
+    (deps, name, cli): (
+        Arc<dyn TestDependencies + Send + Sync + 'static>,
+        String,
+        CliLive,
+    ),
+) -> Result<(), Failed> {
+    let component_id =
+        make_component(deps, &format!("{name} worker_cancel_invocation"), &cli)?.component_id;
+    let worker_name = format!("{name}_worker_cancel_invocation");
+    let cfg = &cli.config;
+    let _: WorkerId = cli.run(&[
+        "worker",
+        "add",
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('C', "component-id"),
+        &component_id,
+    ])?;
+    let idempotency_key = IdempotencyKey::fresh();
+    let _ = cli.run_json(&[
+        "worker",
+        "invoke-and-await",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('f', "function"),
+        "golem:it/api/get-arguments",
+        &cfg.arg('j', "parameters"),
+        "[]",
+        &cfg.arg('k', "idempotency-key"),
+        &idempotency_key.0,
+    ])?;
+
+    let result = cli.run_string(&[
+        "worker",
+        "cancel-invocation",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('k', "idempotency-key"),
+        &idempotency_key.0,
+    ])?;
+
+    assert!(result.contains("already completed"));
+    Ok(())
+}
+
+fn worker_scheduled_invocations(
+    (deps, name, cli): (
+        Arc<dyn TestDependencies + Send + Sync + 'static>,
+        String,
+        CliLive,
+    ),
+) -> Result<(), Failed> {
+    let component_id =
+        make_component(deps, &format!("{name} worker_scheduled_invocations"), &cli)?.component_id;
+    let worker_name = format!("{name}_worker_scheduled_invocations");
+    let cfg = &cli.config;
+    let _: WorkerId = cli.run(&[
+        "worker",
+        "add",
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('C', "component-id"),
+        &component_id,
+    ])?;
+
+    let scheduled = cli.run_json(&[
+        "worker",
+        "schedule-invocation",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('f', "function"),
+        "golem:it/api/get-arguments",
+        &cfg.arg('j', "parameters"),
+        "[]",
+        "--cron",
+        "0 0 1 1 *",
+    ])?;
+    let schedule_id = scheduled
+        .as_object()
+        .unwrap()
+        .get("id")
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let listed = cli.run_json(&[
+        "worker",
+        "list-scheduled-invocations",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+    ])?;
+    let invocations = listed
+        .as_object()
+        .unwrap()
+        .get("invocations")
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .clone();
+    assert_eq!(invocations.len(), 1);
+    assert_eq!(
+        invocations[0].as_object().unwrap().get("cron").unwrap(),
+        "0 0 1 1 *"
+    );
+
+    let result = cli.run_string(&[
+        "worker",
+        "cancel-scheduled-invocation",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('s', "schedule-id"),
+        &schedule_id,
+    ])?;
+    assert!(result.contains("Canceled"));
+
+    let listed = cli.run_json(&[
+        "worker",
+        "list-scheduled-invocations",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+    ])?;
+    assert_eq!(
+        listed
+            .as_object()
+            .unwrap()
+            .get("invocations")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .len(),
+        0
+    );
+    Ok(())
+}
+
+fn worker_files(
+    (deps, name, cli): (
+        Arc<dyn TestDependencies + Send + Sync + 'static>,
+        String,
+        CliLive,
+    ),
+) -> Result<(), Failed> {
+    let component_id = make_component_from_file(
+        deps,
+        &format!("{name} worker_files"),
+        &cli,
+        "file-service.wasm",
+    )?
+    .component_id;
+    let worker_name = format!("{name}_worker_files");
+    let cfg = &cli.config;
+    let _: WorkerId = cli.run(&[
+        "worker",
+        "add",
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('C', "component-id"),
+        &component_id,
+    ])?;
+
+    let _ = cli.run_json(&[
+        "worker",
+        "invoke-and-await",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('f', "function"),
+        "golem:it/api/write-file",
+        &cfg.arg('j', "parameters"),
+        "[\"/testfile.txt\", \"hello world\"]",
+    ])?;
+
+    let listed = cli.run_json(&[
+        "worker",
+        "files",
+        "ls",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+    ])?;
+    let entries = listed
+        .as_object()
+        .unwrap()
+        .get("entries")
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .clone();
+    assert!(entries
+        .iter()
+        .any(|entry| entry.as_object().unwrap().get("name").unwrap() == "testfile.txt"));
+
+    let content = cli.run_string(&[
+        "worker",
+        "files",
+        "cat",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('p', "path"),
+        "/testfile.txt",
+    ])?;
+    assert!(content.contains("hello world"));
+    Ok(())
+}
+
+fn worker_list_promises(
+    (deps, name, cli): (
+        Arc<dyn TestDependencies + Send + Sync + 'static>,
+        String,
+        CliLive,
+    ),
+) -> Result<(), Failed> {
+    let component_id = make_component_from_file(
+        deps,
+        &format!("{name} worker_list_promises"),
+        &cli,
+        "promise.wasm",
+    )?
+    .component_id;
+    let worker_name = format!("{name}_worker_list_promises");
+    let cfg = &cli.config;
+    let _: WorkerId = cli.run(&[
+        "worker",
+        "add",
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('C', "component-id"),
+        &component_id,
+    ])?;
+
+    let list_promises = || -> Result<Vec<serde_json::Value>, Failed> {
+        let response = cli.run_json(&[
+            "worker",
+            "list-promises",
+            &cfg.arg('C', "component-id"),
+            &component_id,
+            &cfg.arg('w', "worker-name"),
+            &worker_name,
+        ])?;
+        Ok(response
+            .as_object()
+            .unwrap()
+            .get("promises")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .clone())
+    };
+
+    assert!(list_promises()?.is_empty());
+
+    // The worker creates a promise and suspends until it gets completed
+    cli.run_unit(&[
+        "worker",
+        "invoke",
+        &cfg.arg('C', "component-id"),
+        &component_id,
+        &cfg.arg('w', "worker-name"),
+        &worker_name,
+        &cfg.arg('f', "function"),
+        "run",
+    ])?;
+
+    let mut promises = list_promises()?;
+    for _ in 0..10 {
+        if !promises.is_empty() {
+            break;
+        }
+        std::thread::sleep(Duration::from_secs(1));
+        promises = list_promises()?;
+    }
+
+    assert_eq!(promises.len(), 1);
+    let promise_id = promises[0]
+        .as_object()
+        .unwrap()
+        .get("promiseId")
+        .unwrap()
+        .as_object()
+        .unwrap();
+    assert!(promise_id.get("oplogIdx").unwrap().as_u64().unwrap() > 0);
+    Ok(())
+}
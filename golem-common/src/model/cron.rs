@@ -0,0 +1,354 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use bincode::{Decode, Encode};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+
+/// A recurring schedule described by a standard five-field cron expression
+/// (`minute hour day-of-month month day-of-week`), evaluated in UTC.
+///
+/// Every field accepts `*`, single values, ranges (`1-5`), steps (`*/15`, `10-40/10`) and
+/// comma separated lists of these. Months and days of week can also be given by their
+/// three-letter English names. The `@yearly`, `@monthly`, `@weekly`, `@daily` and `@hourly`
+/// shorthands are supported as well.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u32,
+    days_of_month: u32,
+    months: u16,
+    days_of_week: u8,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    /// The maximum number of days searched for the next matching occurrence
+    const MAX_SEARCH_DAYS: i64 = 366 * 5;
+
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let trimmed = expression.trim();
+        let normalized = match trimmed {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = normalized.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "Invalid cron expression '{trimmed}': expected 5 fields, got {}",
+                fields.len()
+            ));
+        }
+
+        let minutes = parse_field(fields[0], 0, 59, &[])?;
+        let hours = parse_field(fields[1], 0, 23, &[])?;
+        let days_of_month = parse_field(fields[2], 1, 31, &[])?;
+        let months = parse_field(fields[3], 1, 12, &MONTH_NAMES)?;
+        let mut days_of_week = parse_field(fields[4], 0, 7, &DAY_NAMES)?;
+        // Both 0 and 7 stand for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            expression: trimmed.to_string(),
+            minutes,
+            hours: hours as u32,
+            days_of_month: days_of_month as u32,
+            months: months as u16,
+            days_of_week: days_of_week as u8,
+            day_of_month_restricted: !fields[2].starts_with('*'),
+            day_of_week_restricted: !fields[4].starts_with('*'),
+        })
+    }
+
+    /// The original cron expression this schedule was parsed from
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// Gets the first occurrence of this schedule strictly after the given point in time,
+    /// or `None` if there is no such occurrence in the next few years.
+    pub fn next_after(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = Utc
+            .with_ymd_and_hms(
+                time.year(),
+                time.month(),
+                time.day(),
+                time.hour(),
+                time.minute(),
+                0,
+            )
+            .single()?
+            + Duration::minutes(1);
+        let limit = start + Duration::days(Self::MAX_SEARCH_DAYS);
+
+        let mut current = start;
+        while current < limit {
+            if !self.matches_month(current.month()) {
+                current = start_of_next_month(current)?;
+            } else if !self.matches_day(&current) {
+                current = start_of_day(current)? + Duration::days(1);
+            } else if self.hours & (1 << current.hour()) == 0 {
+                current = start_of_hour(current)? + Duration::hours(1);
+            } else if self.minutes & (1 << current.minute()) == 0 {
+                current += Duration::minutes(1);
+            } else {
+                return Some(current);
+            }
+        }
+        None
+    }
+
+    fn matches_month(&self, month: u32) -> bool {
+        self.months & (1 << month) != 0
+    }
+
+    fn matches_day(&self, time: &DateTime<Utc>) -> bool {
+        let day_of_month = self.days_of_month & (1 << time.day()) != 0;
+        let day_of_week = self.days_of_week & (1 << time.weekday().num_days_from_sunday()) != 0;
+        // Following the usual cron semantics, if both fields are restricted it is enough if
+        // either of them matches
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            (true, false) => day_of_month,
+            (false, true) => day_of_week,
+            (false, false) => true,
+        }
+    }
+}
+
+impl Display for CronSchedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+const MONTH_NAMES: [(&str, u64); 12] = [
+    ("jan", 1),
+    ("feb", 2),
+    ("mar", 3),
+    ("apr", 4),
+    ("may", 5),
+    ("jun", 6),
+    ("jul", 7),
+    ("aug", 8),
+    ("sep", 9),
+    ("oct", 10),
+    ("nov", 11),
+    ("dec", 12),
+];
+
+const DAY_NAMES: [(&str, u64); 7] = [
+    ("sun", 0),
+    ("mon", 1),
+    ("tue", 2),
+    ("wed", 3),
+    ("thu", 4),
+    ("fri", 5),
+    ("sat", 6),
+];
+
+/// Parses a single cron field into a bit set of the matching values
+fn parse_field(field: &str, min: u64, max: u64, names: &[(&str, u64)]) -> Result<u64, String> {
+    let mut result = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u64 = step
+                    .parse()
+                    .map_err(|_| format!("Invalid step '{step}' in cron field '{field}'"))?;
+                if step == 0 {
+                    return Err(format!("Step cannot be zero in cron field '{field}'"));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (from, to) = if range == "*" {
+            (min, max)
+        } else {
+            match range.split_once('-') {
+                Some((from, to)) => (
+                    parse_value(from, min, max, names, field)?,
+                    parse_value(to, min, max, names, field)?,
+                ),
+                None => {
+                    let value = parse_value(range, min, max, names, field)?;
+                    // `5/15` means starting from 5, every 15
+                    if step > 1 {
+                        (value, max)
+                    } else {
+                        (value, value)
+                    }
+                }
+            }
+        };
+
+        if from > to {
+            return Err(format!("Invalid range '{range}' in cron field '{field}'"));
+        }
+
+        let mut value = from;
+        while value <= to {
+            result |= 1 << value;
+            value += step;
+        }
+    }
+    Ok(result)
+}
+
+fn parse_value(
+    value: &str,
+    min: u64,
+    max: u64,
+    names: &[(&str, u64)],
+    field: &str,
+) -> Result<u64, String> {
+    let lowercase = value.to_lowercase();
+    let parsed = match names.iter().find(|(name, _)| *name == lowercase) {
+        Some((_, value)) => *value,
+        None => value
+            .parse()
+            .map_err(|_| format!("Invalid value '{value}' in cron field '{field}'"))?,
+    };
+    if parsed < min || parsed > max {
+        Err(format!(
+            "Value {parsed} is out of range {min}-{max} in cron field '{field}'"
+        ))
+    } else {
+        Ok(parsed)
+    }
+}
+
+fn start_of_hour(time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    Utc.with_ymd_and_hms(time.year(), time.month(), time.day(), time.hour(), 0, 0)
+        .single()
+}
+
+fn start_of_day(time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    Utc.with_ymd_and_hms(time.year(), time.month(), time.day(), 0, 0, 0)
+        .single()
+}
+
+fn start_of_next_month(time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if time.month() == 12 {
+        Utc.with_ymd_and_hms(time.year() + 1, 1, 1, 0, 0, 0)
+            .single()
+    } else {
+        Utc.with_ymd_and_hms(time.year(), time.month() + 1, 1, 0, 0, 0)
+            .single()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use crate::model::cron::CronSchedule;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    pub fn every_fifteen_minutes() {
+        let schedule = CronSchedule::parse("*/15 * * * *").unwrap();
+        assert_eq!(
+            schedule.next_after(utc(2024, 5, 10, 12, 7)),
+            Some(utc(2024, 5, 10, 12, 15))
+        );
+        assert_eq!(
+            schedule.next_after(utc(2024, 5, 10, 12, 45)),
+            Some(utc(2024, 5, 10, 13, 0))
+        );
+    }
+
+    #[test]
+    pub fn next_is_strictly_after() {
+        let schedule = CronSchedule::parse("30 2 * * *").unwrap();
+        assert_eq!(
+            schedule.next_after(utc(2024, 5, 10, 2, 30)),
+            Some(utc(2024, 5, 11, 2, 30))
+        );
+    }
+
+    #[test]
+    pub fn day_of_week_and_month_names() {
+        let schedule = CronSchedule::parse("0 9 * jan-mar mon-fri").unwrap();
+        // 2024-03-29 is a Friday
+        assert_eq!(
+            schedule.next_after(utc(2024, 3, 29, 10, 0)),
+            Some(utc(2025, 1, 1, 9, 0))
+        );
+    }
+
+    #[test]
+    pub fn restricted_day_of_month_or_day_of_week() {
+        // Either on the 1st or on Sundays; 2024-06-02 is a Sunday
+        let schedule = CronSchedule::parse("0 0 1 * 7").unwrap();
+        assert_eq!(
+            schedule.next_after(utc(2024, 5, 28, 0, 0)),
+            Some(utc(2024, 6, 1, 0, 0))
+        );
+        assert_eq!(
+            schedule.next_after(utc(2024, 6, 1, 0, 0)),
+            Some(utc(2024, 6, 2, 0, 0))
+        );
+    }
+
+    #[test]
+    pub fn shorthands() {
+        let schedule = CronSchedule::parse("@monthly").unwrap();
+        assert_eq!(schedule.expression(), "@monthly");
+        assert_eq!(
+            schedule.next_after(utc(2024, 12, 15, 8, 0)),
+            Some(utc(2025, 1, 1, 0, 0))
+        );
+    }
+
+    #[test]
+    pub fn impossible_schedule() {
+        let schedule = CronSchedule::parse("0 0 30 2 *").unwrap();
+        assert_eq!(schedule.next_after(utc(2024, 1, 1, 0, 0)), None);
+    }
+
+    #[test]
+    pub fn invalid_expressions() {
+        assert!(CronSchedule::parse("* * * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("5-1 * * * *").is_err());
+        assert!(CronSchedule::parse("* * * foo *").is_err());
+    }
+}
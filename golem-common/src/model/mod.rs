@@ -26,6 +26,7 @@ use bincode::enc::write::Writer;
 use bincode::enc::Encoder;
 use bincode::error::{DecodeError, EncodeError};
 use bincode::{BorrowDecode, Decode, Encode};
+use chrono::{DateTime, Utc};
 use derive_more::FromStr;
 use golem_api_grpc::proto::golem::worker::Cursor;
 use poem_openapi::registry::{MetaSchema, MetaSchemaRef};
@@ -36,10 +37,12 @@ use serde_json::Value;
 use uuid::Uuid;
 
 use crate::config::RetryConfig;
+use crate::model::cron::CronSchedule;
 use crate::model::oplog::{OplogIndex, TimestampedUpdateDescription};
 use crate::model::regions::DeletedRegions;
 use crate::newtype_uuid;
 
+pub mod cron;
 pub mod oplog;
 pub mod regions;
 
@@ -50,6 +53,11 @@ newtype_uuid!(
 
 newtype_uuid!(ProjectId, golem_api_grpc::proto::golem::common::ProjectId);
 
+newtype_uuid!(
+    ScheduledInvocationId,
+    golem_api_grpc::proto::golem::worker::ScheduledInvocationId
+);
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[repr(transparent)]
 pub struct Timestamp(iso8601_timestamp::Timestamp);
@@ -382,7 +390,7 @@ impl Display for PromiseId {
 }
 
 /// Actions that can be scheduled to be executed at a given point in time
-#[derive(Debug, Clone, Hash, Serialize, Deserialize, Encode, Decode)]
+#[derive(Debug, Clone, Encode, Decode)]
 pub enum ScheduledAction {
     /// Completes a given promise
     CompletePromise {
@@ -397,6 +405,29 @@ pub enum ScheduledAction {
         last_oplog_index: OplogIndex,
         next_after: Duration,
     },
+    /// Enqueues an invocation of an exported function on a worker. Recurring invocations
+    /// schedule their next occurrence when they get enqueued.
+    Invoke {
+        owned_worker_id: OwnedWorkerId,
+        invocation: ScheduledInvocation,
+    },
+    /// Deletes a worker and its oplog if the last oplog index did not change, enforcing
+    /// the retention policy of its component.
+    DeleteWorker {
+        owned_worker_id: OwnedWorkerId,
+        last_oplog_index: OplogIndex,
+    },
+    /// Fails a given promise with a timeout error if it has not been completed yet
+    ExpirePromise {
+        account_id: AccountId,
+        promise_id: PromiseId,
+    },
+    /// Archives all entries of an oplog down to its last layer if the last oplog index did
+    /// not change, enforcing the retention policy of a failed worker's component.
+    ArchiveOplogToLastLayer {
+        owned_worker_id: OwnedWorkerId,
+        last_oplog_index: OplogIndex,
+    },
 }
 
 impl ScheduledAction {
@@ -409,6 +440,19 @@ impl ScheduledAction {
             ScheduledAction::ArchiveOplog {
                 owned_worker_id, ..
             } => owned_worker_id.clone(),
+            ScheduledAction::Invoke {
+                owned_worker_id, ..
+            } => owned_worker_id.clone(),
+            ScheduledAction::DeleteWorker {
+                owned_worker_id, ..
+            } => owned_worker_id.clone(),
+            ScheduledAction::ExpirePromise {
+                account_id,
+                promise_id,
+            } => OwnedWorkerId::new(account_id, &promise_id.worker_id),
+            ScheduledAction::ArchiveOplogToLastLayer {
+                owned_worker_id, ..
+            } => owned_worker_id.clone(),
         }
     }
 }
@@ -424,11 +468,30 @@ impl Display for ScheduledAction {
             } => {
                 write!(f, "archive[{}]", owned_worker_id)
             }
+            ScheduledAction::Invoke {
+                owned_worker_id,
+                invocation,
+            } => {
+                write!(f, "invoke[{}/{}]", owned_worker_id, invocation.id)
+            }
+            ScheduledAction::DeleteWorker {
+                owned_worker_id, ..
+            } => {
+                write!(f, "delete[{}]", owned_worker_id)
+            }
+            ScheduledAction::ExpirePromise { promise_id, .. } => {
+                write!(f, "expire[{}]", promise_id)
+            }
+            ScheduledAction::ArchiveOplogToLastLayer {
+                owned_worker_id, ..
+            } => {
+                write!(f, "archive-to-last-layer[{}]", owned_worker_id)
+            }
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Encode, Decode)]
+#[derive(Debug, Encode, Decode)]
 pub struct ScheduleId {
     pub timestamp: i64,
     pub action: ScheduledAction,
@@ -440,6 +503,117 @@ impl Display for ScheduleId {
     }
 }
 
+/// Describes when a scheduled invocation should be performed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvocationSchedule {
+    /// A single invocation at the given point in time
+    At(Timestamp),
+    /// A recurring invocation at every occurrence of a cron schedule
+    Cron(CronSchedule),
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::worker::InvocationSchedule> for InvocationSchedule {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::worker::InvocationSchedule,
+    ) -> Result<Self, Self::Error> {
+        match value.schedule.ok_or("Missing schedule")? {
+            golem_api_grpc::proto::golem::worker::invocation_schedule::Schedule::At(at) => {
+                Ok(InvocationSchedule::At(at.into()))
+            }
+            golem_api_grpc::proto::golem::worker::invocation_schedule::Schedule::Cron(cron) => {
+                Ok(InvocationSchedule::Cron(CronSchedule::parse(&cron)?))
+            }
+        }
+    }
+}
+
+impl From<InvocationSchedule> for golem_api_grpc::proto::golem::worker::InvocationSchedule {
+    fn from(value: InvocationSchedule) -> Self {
+        let schedule = match value {
+            InvocationSchedule::At(at) => {
+                golem_api_grpc::proto::golem::worker::invocation_schedule::Schedule::At(at.into())
+            }
+            InvocationSchedule::Cron(cron) => {
+                golem_api_grpc::proto::golem::worker::invocation_schedule::Schedule::Cron(
+                    cron.expression().to_string(),
+                )
+            }
+        };
+        Self {
+            schedule: Some(schedule),
+        }
+    }
+}
+
+/// An exported function invocation scheduled to be enqueued on a worker at a given time
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+pub struct ScheduledInvocation {
+    pub id: ScheduledInvocationId,
+    pub scheduled_at: Timestamp,
+    pub full_function_name: String,
+    pub function_input: Vec<golem_wasm_rpc::Value>,
+    pub cron: Option<CronSchedule>,
+}
+
+impl ScheduledInvocation {
+    /// The idempotency key used for enqueuing the invocation, unique for each occurrence
+    /// of a recurring invocation
+    pub fn idempotency_key(&self) -> IdempotencyKey {
+        IdempotencyKey::new(format!("{}-{}", self.id, self.scheduled_at.to_millis()))
+    }
+
+    /// Gets the next occurrence of a recurring invocation after the given point in time
+    pub fn next_occurrence(&self, after: DateTime<Utc>) -> Option<ScheduledInvocation> {
+        let cron = self.cron.as_ref()?;
+        let next = cron.next_after(after)?;
+        Some(ScheduledInvocation {
+            scheduled_at: Timestamp::from(next.timestamp_millis() as u64),
+            ..self.clone()
+        })
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::worker::ScheduledInvocation> for ScheduledInvocation {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::worker::ScheduledInvocation,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.id.ok_or("Missing id")?.try_into()?,
+            scheduled_at: value.scheduled_at.ok_or("Missing scheduled_at")?.into(),
+            full_function_name: value.function_name,
+            function_input: value
+                .input
+                .into_iter()
+                .map(|val| val.try_into())
+                .collect::<Result<Vec<_>, _>>()?,
+            cron: value
+                .cron
+                .map(|cron| CronSchedule::parse(&cron))
+                .transpose()?,
+        })
+    }
+}
+
+impl From<ScheduledInvocation> for golem_api_grpc::proto::golem::worker::ScheduledInvocation {
+    fn from(value: ScheduledInvocation) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            scheduled_at: Some(value.scheduled_at.into()),
+            function_name: value.full_function_name,
+            input: value
+                .function_input
+                .into_iter()
+                .map(|val| val.into())
+                .collect(),
+            cron: value.cron.map(|cron| cron.expression().to_string()),
+        }
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -736,6 +910,7 @@ pub struct WorkerStatusRecord {
     pub invocation_results: HashMap<IdempotencyKey, OplogIndex>,
     pub current_idempotency_key: Option<IdempotencyKey>,
     pub component_version: ComponentVersion,
+    pub last_snapshot: Option<OplogIndex>,
     pub oplog_idx: OplogIndex,
 }
 
@@ -752,6 +927,7 @@ impl Default for WorkerStatusRecord {
             invocation_results: HashMap::new(),
             current_idempotency_key: None,
             component_version: 0,
+            last_snapshot: None,
             oplog_idx: OplogIndex::default(),
         }
     }
@@ -1836,15 +2012,21 @@ impl From<ScanCursor> for Cursor {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::{HashMap, VecDeque};
     use std::str::FromStr;
     use std::vec;
 
     use bincode::{Decode, Encode};
     use serde::{Deserialize, Serialize};
 
+    use crate::config::RetryConfig;
+    use crate::model::oplog::{OplogIndex, TimestampedUpdateDescription};
+    use crate::model::regions::DeletedRegions;
     use crate::model::{
-        parse_function_name, AccountId, ComponentId, FilterComparator, StringFilterComparator,
-        Timestamp, WorkerFilter, WorkerId, WorkerMetadata, WorkerStatus, WorkerStatusRecord,
+        parse_function_name, AccountId, ComponentId, ComponentVersion, FailedUpdateRecord,
+        FilterComparator, IdempotencyKey, StringFilterComparator, SuccessfulUpdateRecord,
+        Timestamp, TimestampedWorkerInvocation, WorkerFilter, WorkerId, WorkerMetadata,
+        WorkerStatus, WorkerStatusRecord,
     };
 
     #[test]
@@ -2174,4 +2356,43 @@ mod tests {
             ))
             .matches(&worker_metadata));
     }
+
+    #[test]
+    fn worker_status_record_without_last_snapshot_is_rejected() {
+        // Layout of the records stored before `last_snapshot` was added
+        #[derive(Encode)]
+        struct LegacyWorkerStatusRecord {
+            status: WorkerStatus,
+            deleted_regions: DeletedRegions,
+            overridden_retry_config: Option<RetryConfig>,
+            pending_invocations: Vec<TimestampedWorkerInvocation>,
+            pending_updates: VecDeque<TimestampedUpdateDescription>,
+            failed_updates: Vec<FailedUpdateRecord>,
+            successful_updates: Vec<SuccessfulUpdateRecord>,
+            invocation_results: HashMap<IdempotencyKey, OplogIndex>,
+            current_idempotency_key: Option<IdempotencyKey>,
+            component_version: ComponentVersion,
+            oplog_idx: OplogIndex,
+        }
+
+        for oplog_idx in [0, 1, 2, 250, 251, 100000] {
+            let legacy = LegacyWorkerStatusRecord {
+                status: WorkerStatus::Idle,
+                deleted_regions: DeletedRegions::new(),
+                overridden_retry_config: None,
+                pending_invocations: Vec::new(),
+                pending_updates: VecDeque::new(),
+                failed_updates: Vec::new(),
+                successful_updates: Vec::new(),
+                invocation_results: HashMap::new(),
+                current_idempotency_key: None,
+                component_version: 3,
+                oplog_idx: OplogIndex::from_u64(oplog_idx),
+            };
+            let bytes = crate::serialization::serialize(&legacy).unwrap();
+            let result: Result<WorkerStatusRecord, String> =
+                crate::serialization::deserialize(&bytes);
+            assert!(result.is_err());
+        }
+    }
 }
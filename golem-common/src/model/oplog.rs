@@ -212,6 +212,17 @@ pub enum OplogEntry {
         target_version: ComponentVersion,
         details: Option<String>,
     },
+    /// A pending invocation has been canceled before it got started
+    CancelPendingInvocation {
+        timestamp: Timestamp,
+        idempotency_key: IdempotencyKey,
+    },
+    /// A snapshot of the worker's state saved by its `save-snapshot` export. Recovery starts by
+    /// loading the latest snapshot, so the entries preceding it are no longer replayed.
+    Snapshot {
+        timestamp: Timestamp,
+        data: OplogPayload,
+    },
 }
 
 impl OplogEntry {
@@ -332,6 +343,13 @@ impl OplogEntry {
         }
     }
 
+    pub fn cancel_pending_invocation(idempotency_key: IdempotencyKey) -> OplogEntry {
+        OplogEntry::CancelPendingInvocation {
+            timestamp: Timestamp::now_utc(),
+            idempotency_key,
+        }
+    }
+
     pub fn is_end_atomic_region(&self, idx: OplogIndex) -> bool {
         matches!(self, OplogEntry::EndAtomicRegion { begin_index, .. } if *begin_index == idx)
     }
@@ -352,6 +370,8 @@ impl OplogEntry {
                 | OplogEntry::PendingUpdate { .. }
                 | OplogEntry::SuccessfulUpdate { .. }
                 | OplogEntry::FailedUpdate { .. }
+                | OplogEntry::CancelPendingInvocation { .. }
+                | OplogEntry::Snapshot { .. }
         )
     }
 
@@ -375,7 +395,9 @@ impl OplogEntry {
             | OplogEntry::PendingWorkerInvocation { timestamp, .. }
             | OplogEntry::PendingUpdate { timestamp, .. }
             | OplogEntry::SuccessfulUpdate { timestamp, .. }
-            | OplogEntry::FailedUpdate { timestamp, .. } => *timestamp,
+            | OplogEntry::FailedUpdate { timestamp, .. }
+            | OplogEntry::CancelPendingInvocation { timestamp, .. }
+            | OplogEntry::Snapshot { timestamp, .. } => *timestamp,
         }
     }
 }
@@ -444,3 +466,45 @@ impl Display for WorkerError {
         }
     }
 }
+
+/// Describes how far a worker should be reverted
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RevertWorkerTarget {
+    /// Reverts the worker to the state it had right after the given oplog index
+    RevertToOplogIndex(OplogIndex),
+    /// Reverts the worker to the state it had before the last N invocations
+    RevertLastInvocations(u64),
+}
+
+impl From<RevertWorkerTarget> for golem_api_grpc::proto::golem::worker::RevertWorkerTarget {
+    fn from(value: RevertWorkerTarget) -> Self {
+        use golem_api_grpc::proto::golem::worker::revert_worker_target::Target;
+
+        let target = match value {
+            RevertWorkerTarget::RevertToOplogIndex(index) => Target::LastOplogIndex(index.into()),
+            RevertWorkerTarget::RevertLastInvocations(count) => Target::NumberOfInvocations(count),
+        };
+        golem_api_grpc::proto::golem::worker::RevertWorkerTarget {
+            target: Some(target),
+        }
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::worker::RevertWorkerTarget> for RevertWorkerTarget {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::worker::RevertWorkerTarget,
+    ) -> Result<Self, Self::Error> {
+        use golem_api_grpc::proto::golem::worker::revert_worker_target::Target;
+
+        match value.target.ok_or("Missing revert target")? {
+            Target::LastOplogIndex(index) => Ok(RevertWorkerTarget::RevertToOplogIndex(
+                OplogIndex::from_u64(index),
+            )),
+            Target::NumberOfInvocations(count) => {
+                Ok(RevertWorkerTarget::RevertLastInvocations(count))
+            }
+        }
+    }
+}
@@ -0,0 +1 @@
+ALTER TABLE components ADD COLUMN files jsonb NOT NULL DEFAULT '[]';
@@ -0,0 +1 @@
+ALTER TABLE components ADD COLUMN retention_policy jsonb NOT NULL DEFAULT 'null';
@@ -15,7 +15,9 @@
 use futures_util::TryStreamExt;
 use std::sync::Arc;
 
-use crate::service::component::{ComponentError as ComponentServiceError, ComponentService};
+use crate::service::component::{
+    ComponentError as ComponentServiceError, ComponentService, InitialFilesArchive,
+};
 use golem_common::model::ComponentId;
 use golem_service_base::api_tags::ApiTags;
 use golem_service_base::model::*;
@@ -23,7 +25,7 @@ use poem::error::ReadBodyError;
 use poem::Body;
 use poem_openapi::param::{Path, Query};
 use poem_openapi::payload::{Binary, Json};
-use poem_openapi::types::multipart::Upload;
+use poem_openapi::types::multipart::{JsonField, Upload};
 use poem_openapi::*;
 
 #[derive(ApiResponse)]
@@ -46,6 +48,10 @@ pub enum ComponentError {
 pub struct UploadPayload {
     name: ComponentName,
     component: Upload,
+    /// A zip archive of the files to copy into the file system of each worker of the component
+    files: Option<Upload>,
+    /// The permissions of the files in the archive, the ones not listed are read-only
+    permissions: Option<JsonField<InitialComponentFilePermissionsList>>,
 }
 
 type Result<T> = std::result::Result<T, ComponentError>;
@@ -74,6 +80,11 @@ impl From<ComponentServiceError> for ComponentError {
                     errors: vec![error.to_string()],
                 }))
             }
+            ComponentServiceError::InvalidInitialFiles(_) => {
+                ComponentError::BadRequest(Json(ErrorsBody {
+                    errors: vec![error.to_string()],
+                }))
+            }
         }
     }
 }
@@ -104,7 +115,23 @@ impl ComponentApi {
     async fn create_component(&self, payload: UploadPayload) -> Result<Json<Component>> {
         let data = payload.component.into_vec().await?;
         let component_name = payload.name;
-        let response = self.component_service.create(&component_name, data).await?;
+        let files = match payload.files {
+            Some(files) => Some(InitialFilesArchive {
+                archive: files.into_vec().await?,
+                permissions: payload
+                    .permissions
+                    .map(|permissions| permissions.0.values)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|file| (file.path, file.permissions))
+                    .collect(),
+            }),
+            None => None,
+        };
+        let response = self
+            .component_service
+            .create(&component_name, data, files)
+            .await?;
         Ok(Json(response))
     }
 
@@ -123,6 +150,23 @@ impl ComponentApi {
         Ok(Json(response))
     }
 
+    #[oai(
+        path = "/:component_id/retention-policy",
+        method = "put",
+        operation_id = "update_component_retention_policy"
+    )]
+    async fn update_component_retention_policy(
+        &self,
+        component_id: Path<ComponentId>,
+        retention_policy: Json<ComponentRetentionPolicy>,
+    ) -> Result<Json<Component>> {
+        let response = self
+            .component_service
+            .update_retention_policy(&component_id.0, Some(retention_policy.0))
+            .await?;
+        Ok(Json(response))
+    }
+
     #[oai(
         path = "/:component_id/download",
         method = "get",
@@ -142,6 +186,25 @@ impl ComponentApi {
         }))))
     }
 
+    #[oai(
+        path = "/:component_id/files",
+        method = "get",
+        operation_id = "download_component_file"
+    )]
+    async fn download_component_file(
+        &self,
+        component_id: Path<ComponentId>,
+        key: Query<String>,
+    ) -> Result<Binary<Body>> {
+        let bytes = self
+            .component_service
+            .download_file_stream(&component_id.0, &key.0)
+            .await?;
+        Ok(Binary(Body::from_bytes_stream(bytes.map_err(|e| {
+            std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
+        }))))
+    }
+
     #[oai(
         path = "/:component_id",
         method = "get",
@@ -25,17 +25,19 @@ use golem_api_grpc::proto::golem::component::{
     get_component_metadata_all_versions_response, get_component_metadata_response,
     get_components_response, update_component_request, update_component_response,
     CreateComponentRequest, CreateComponentRequestHeader, CreateComponentResponse,
-    DownloadComponentRequest, DownloadComponentResponse, GetComponentMetadataAllVersionsResponse,
-    GetComponentMetadataResponse, GetComponentMetadataSuccessResponse, GetComponentRequest,
-    GetComponentSuccessResponse, GetComponentsRequest, GetComponentsResponse,
-    GetComponentsSuccessResponse, GetLatestComponentRequest, GetVersionedComponentRequest,
-    UpdateComponentRequest, UpdateComponentRequestHeader, UpdateComponentResponse,
+    DownloadComponentFileRequest, DownloadComponentRequest, DownloadComponentResponse,
+    GetComponentMetadataAllVersionsResponse, GetComponentMetadataResponse,
+    GetComponentMetadataSuccessResponse, GetComponentRequest, GetComponentSuccessResponse,
+    GetComponentsRequest, GetComponentsResponse, GetComponentsSuccessResponse,
+    GetLatestComponentRequest, GetVersionedComponentRequest, UpdateComponentRequest,
+    UpdateComponentRequestHeader, UpdateComponentResponse, UpdateComponentRetentionPolicyRequest,
 };
 use golem_common::model::ComponentId;
 use golem_service_base::stream::ByteStream;
 use tonic::{Request, Response, Status, Streaming};
 
 use crate::service::component;
+use crate::service::component::InitialFilesArchive;
 
 impl From<component::ComponentError> for ComponentError {
     fn from(value: component::ComponentError) -> Self {
@@ -56,6 +58,11 @@ impl From<component::ComponentError> for ComponentError {
                     errors: vec![error.to_string()],
                 })
             }
+            component::ComponentError::InvalidInitialFiles(_) => {
+                component_error::Error::BadRequest(ErrorsBody {
+                    errors: vec![value.to_string()],
+                })
+            }
             component::ComponentError::Internal(error) => {
                 component_error::Error::InternalError(ErrorBody {
                     error: error.to_string(),
@@ -82,6 +89,36 @@ fn internal_error(error: &str) -> ComponentError {
     }
 }
 
+fn download_response_stream(
+    result: Result<ByteStream, ComponentError>,
+) -> BoxStream<'static, Result<DownloadComponentResponse, Status>> {
+    match result {
+        Ok(response) => {
+            let stream = response.map(|content| {
+                let res = match content {
+                    Ok(content) => DownloadComponentResponse {
+                        result: Some(download_component_response::Result::SuccessChunk(content)),
+                    },
+                    Err(_) => DownloadComponentResponse {
+                        result: Some(download_component_response::Result::Error(internal_error(
+                            "Internal error",
+                        ))),
+                    },
+                };
+                Ok(res)
+            });
+            Box::pin(stream)
+        }
+        Err(err) => {
+            let res = DownloadComponentResponse {
+                result: Some(download_component_response::Result::Error(err)),
+            };
+
+            Box::pin(tokio_stream::iter([Ok(res)]))
+        }
+    }
+}
+
 pub struct ComponentGrpcApi {
     pub component_service: Arc<dyn component::ComponentService + Sync + Send>,
 }
@@ -162,13 +199,41 @@ impl ComponentGrpcApi {
         Ok(result)
     }
 
+    async fn download_file(
+        &self,
+        request: DownloadComponentFileRequest,
+    ) -> Result<ByteStream, ComponentError> {
+        let id: ComponentId = request
+            .component_id
+            .and_then(|id| id.try_into().ok())
+            .ok_or_else(|| bad_request_error("Missing component id"))?;
+        let result = self
+            .component_service
+            .download_file_stream(&id, &request.key)
+            .await?;
+        Ok(result)
+    }
+
     async fn create(
         &self,
         request: CreateComponentRequestHeader,
         data: Vec<u8>,
+        files: Option<Vec<u8>>,
     ) -> Result<Component, ComponentError> {
         let name = golem_service_base::model::ComponentName(request.component_name);
-        let result = self.component_service.create(&name, data).await?;
+        let files = files.map(|archive| InitialFilesArchive {
+            archive,
+            permissions: request
+                .file_permissions
+                .into_iter()
+                .map(|file| {
+                    let file: golem_service_base::model::InitialComponentFilePermissions =
+                        file.into();
+                    (file.path, file.permissions)
+                })
+                .collect(),
+        });
+        let result = self.component_service.create(&name, data, files).await?;
         Ok(result.into())
     }
 
@@ -184,6 +249,22 @@ impl ComponentGrpcApi {
         let result = self.component_service.update(&id, data).await?;
         Ok(result.into())
     }
+
+    async fn update_retention_policy(
+        &self,
+        request: UpdateComponentRetentionPolicyRequest,
+    ) -> Result<Component, ComponentError> {
+        let id: ComponentId = request
+            .component_id
+            .and_then(|id| id.try_into().ok())
+            .ok_or_else(|| bad_request_error("Missing component id"))?;
+        let retention_policy = request.retention_policy.map(|policy| policy.into());
+        let result = self
+            .component_service
+            .update_retention_policy(&id, retention_policy)
+            .await?;
+        Ok(result.into())
+    }
 }
 
 #[async_trait::async_trait]
@@ -231,7 +312,21 @@ impl ComponentService for ComponentGrpcApi {
                             .unwrap_or_default()
                     })
                     .collect();
-                self.create(request, data).await
+                let files_chunks: Vec<Vec<u8>> = chunks
+                    .iter()
+                    .filter_map(|c| {
+                        c.clone().data.and_then(|d| match d {
+                            create_component_request::Data::FilesChunk(d) => Some(d.files_chunk),
+                            _ => None,
+                        })
+                    })
+                    .collect();
+                let files = if files_chunks.is_empty() {
+                    None
+                } else {
+                    Some(files_chunks.concat())
+                };
+                self.create(request, data, files).await
             }
             None => Err(bad_request_error("Missing request")),
         };
@@ -252,35 +347,19 @@ impl ComponentService for ComponentGrpcApi {
         &self,
         request: Request<DownloadComponentRequest>,
     ) -> Result<Response<Self::DownloadComponentStream>, Status> {
-        match self.download(request.into_inner()).await {
-            Ok(response) => {
-                let stream = response.map(|content| {
-                    let res = match content {
-                        Ok(content) => DownloadComponentResponse {
-                            result: Some(download_component_response::Result::SuccessChunk(
-                                content,
-                            )),
-                        },
-                        Err(_) => DownloadComponentResponse {
-                            result: Some(download_component_response::Result::Error(
-                                internal_error("Internal error"),
-                            )),
-                        },
-                    };
-                    Ok(res)
-                });
-                let stream: Self::DownloadComponentStream = Box::pin(stream);
-                Ok(Response::new(stream))
-            }
-            Err(err) => {
-                let res = DownloadComponentResponse {
-                    result: Some(download_component_response::Result::Error(err)),
-                };
+        let result = self.download(request.into_inner()).await;
+        Ok(Response::new(download_response_stream(result)))
+    }
 
-                let stream: Self::DownloadComponentStream = Box::pin(tokio_stream::iter([Ok(res)]));
-                Ok(Response::new(stream))
-            }
-        }
+    type DownloadComponentFileStream =
+        BoxStream<'static, Result<DownloadComponentResponse, Status>>;
+
+    async fn download_component_file(
+        &self,
+        request: Request<DownloadComponentFileRequest>,
+    ) -> Result<Response<Self::DownloadComponentFileStream>, Status> {
+        let result = self.download_file(request.into_inner()).await;
+        Ok(Response::new(download_response_stream(result)))
     }
 
     async fn get_component_metadata_all_versions(
@@ -367,6 +446,20 @@ impl ComponentService for ComponentGrpcApi {
         }
     }
 
+    async fn update_component_retention_policy(
+        &self,
+        request: Request<UpdateComponentRetentionPolicyRequest>,
+    ) -> Result<Response<UpdateComponentResponse>, Status> {
+        match self.update_retention_policy(request.into_inner()).await {
+            Ok(v) => Ok(Response::new(UpdateComponentResponse {
+                result: Some(update_component_response::Result::Success(v)),
+            })),
+            Err(err) => Ok(Response::new(UpdateComponentResponse {
+                result: Some(update_component_response::Result::Error(err)),
+            })),
+        }
+    }
+
     async fn get_component_metadata(
         &self,
         request: Request<GetVersionedComponentRequest>,
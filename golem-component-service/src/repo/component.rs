@@ -34,11 +34,16 @@ pub struct ComponentRecord {
     pub protected_component: String,
     pub protector_version: Option<i64>,
     pub metadata: String,
+    pub files: String,
+    pub retention_policy: String,
 }
 
 impl From<ComponentRecord> for Component {
     fn from(value: ComponentRecord) -> Self {
         let metadata: ComponentMetadata = serde_json::from_str(&value.metadata).unwrap();
+        let files: Vec<InitialComponentFile> = serde_json::from_str(&value.files).unwrap();
+        let retention_policy: Option<ComponentRetentionPolicy> =
+            serde_json::from_str(&value.retention_policy).unwrap();
         let versioned_component_id: VersionedComponentId = VersionedComponentId {
             component_id: ComponentId(value.component_id),
             version: value.version as u64,
@@ -56,6 +61,8 @@ impl From<ComponentRecord> for Component {
             versioned_component_id,
             user_component_id,
             protected_component_id,
+            files,
+            retention_policy,
         }
     }
 }
@@ -71,6 +78,8 @@ impl From<Component> for ComponentRecord {
             protected_component: value.protected_component_id.slug(),
             protector_version: None,
             metadata: serde_json::to_string(&value.metadata).unwrap(),
+            files: serde_json::to_string(&value.files).unwrap(),
+            retention_policy: serde_json::to_string(&value.retention_policy).unwrap(),
         }
     }
 }
@@ -115,16 +124,18 @@ impl ComponentRepo for DbComponentRepo<sqlx::Sqlite> {
         sqlx::query(
             r#"
               INSERT INTO components
-                (component_id, version, name, size, user_component, protected_component, protector_version, metadata)
+                (component_id, version, name, size, user_component, protected_component, protector_version, metadata, files, retention_policy)
               VALUES
-                ($1, $2, $3, $4, $5, $6, $7, $8::jsonb)
+                ($1, $2, $3, $4, $5, $6, $7, $8::jsonb, $9::jsonb, $10::jsonb)
               ON CONFLICT (component_id, version) DO UPDATE
               SET name = $3,
                   size = $4,
                   user_component = $5,
                   protected_component = $6,
                   protector_version = $7,
-                  metadata = $8::jsonb
+                  metadata = $8::jsonb,
+                  files = $9::jsonb,
+                  retention_policy = $10::jsonb
                "#,
         )
             .bind(component.component_id)
@@ -135,6 +146,8 @@ impl ComponentRepo for DbComponentRepo<sqlx::Sqlite> {
             .bind(component.protected_component.clone())
             .bind(component.protector_version)
             .bind(component.metadata.clone())
+            .bind(component.files.clone())
+            .bind(component.retention_policy.clone())
             .execute(self.db_pool.deref())
             .await?;
 
@@ -142,7 +155,7 @@ impl ComponentRepo for DbComponentRepo<sqlx::Sqlite> {
     }
 
     async fn get(&self, component_id: &Uuid) -> Result<Vec<ComponentRecord>, RepoError> {
-        sqlx::query_as::<_, ComponentRecord>("SELECT component_id, version, name, size, user_component, protected_component, protector_version,  CAST(metadata AS TEXT) AS metadata  FROM components WHERE component_id = $1")
+        sqlx::query_as::<_, ComponentRecord>("SELECT component_id, version, name, size, user_component, protected_component, protector_version,  CAST(metadata AS TEXT) AS metadata, CAST(files AS TEXT) AS files, CAST(retention_policy AS TEXT) AS retention_policy  FROM components WHERE component_id = $1")
             .bind(component_id)
             .fetch_all(self.db_pool.deref())
             .await
@@ -150,7 +163,7 @@ impl ComponentRepo for DbComponentRepo<sqlx::Sqlite> {
     }
 
     async fn get_all(&self) -> Result<Vec<ComponentRecord>, RepoError> {
-        sqlx::query_as::<_, ComponentRecord>("SELECT component_id, version, name, size, user_component, protected_component, protector_version,  CAST(metadata AS TEXT) AS metadata  FROM components")
+        sqlx::query_as::<_, ComponentRecord>("SELECT component_id, version, name, size, user_component, protected_component, protector_version,  CAST(metadata AS TEXT) AS metadata, CAST(files AS TEXT) AS files, CAST(retention_policy AS TEXT) AS retention_policy  FROM components")
             .fetch_all(self.db_pool.deref())
             .await
             .map_err(|e| e.into())
@@ -161,7 +174,7 @@ impl ComponentRepo for DbComponentRepo<sqlx::Sqlite> {
         component_id: &Uuid,
     ) -> Result<Option<ComponentRecord>, RepoError> {
         sqlx::query_as::<_, ComponentRecord>(
-            "SELECT component_id, version, name, size, user_component, protected_component, protector_version,  CAST(metadata AS TEXT) AS metadata FROM components WHERE component_id = $1 ORDER BY version DESC LIMIT 1",
+            "SELECT component_id, version, name, size, user_component, protected_component, protector_version,  CAST(metadata AS TEXT) AS metadata, CAST(files AS TEXT) AS files, CAST(retention_policy AS TEXT) AS retention_policy FROM components WHERE component_id = $1 ORDER BY version DESC LIMIT 1",
         )
             .bind(component_id)
             .fetch_optional(self.db_pool.deref())
@@ -175,7 +188,7 @@ impl ComponentRepo for DbComponentRepo<sqlx::Sqlite> {
         version: u64,
     ) -> Result<Option<ComponentRecord>, RepoError> {
         sqlx::query_as::<_, ComponentRecord>(
-            "SELECT component_id, version, name, size, user_component, protected_component, protector_version,  CAST(metadata AS TEXT) AS metadata  FROM components WHERE component_id = $1 AND version = $2",
+            "SELECT component_id, version, name, size, user_component, protected_component, protector_version,  CAST(metadata AS TEXT) AS metadata, CAST(files AS TEXT) AS files, CAST(retention_policy AS TEXT) AS retention_policy  FROM components WHERE component_id = $1 AND version = $2",
         )
             .bind(component_id)
             .bind(version as i64)
@@ -186,7 +199,7 @@ impl ComponentRepo for DbComponentRepo<sqlx::Sqlite> {
 
     async fn get_by_name(&self, name: &str) -> Result<Vec<ComponentRecord>, RepoError> {
         sqlx::query_as::<_, ComponentRecord>(
-            "SELECT component_id, version, name, size, user_component, protected_component, protector_version,  CAST(metadata AS TEXT) AS metadata FROM components WHERE name = $1",
+            "SELECT component_id, version, name, size, user_component, protected_component, protector_version,  CAST(metadata AS TEXT) AS metadata, CAST(files AS TEXT) AS files, CAST(retention_policy AS TEXT) AS retention_policy FROM components WHERE name = $1",
         )
             .bind(name)
             .fetch_all(self.db_pool.deref())
@@ -209,16 +222,18 @@ impl ComponentRepo for DbComponentRepo<sqlx::Postgres> {
         sqlx::query(
             r#"
               INSERT INTO components
-                (component_id, version, name, size, user_component, protected_component, protector_version, metadata)
+                (component_id, version, name, size, user_component, protected_component, protector_version, metadata, files, retention_policy)
               VALUES
-                ($1, $2, $3, $4, $5, $6, $7, $8::jsonb)
+                ($1, $2, $3, $4, $5, $6, $7, $8::jsonb, $9::jsonb, $10::jsonb)
               ON CONFLICT (component_id, version) DO UPDATE
               SET name = $3,
                   size = $4,
                   user_component = $5,
                   protected_component = $6,
                   protector_version = $7,
-                  metadata = $8::jsonb
+                  metadata = $8::jsonb,
+                  files = $9::jsonb,
+                  retention_policy = $10::jsonb
             "#,
         )
             .bind(component.component_id)
@@ -229,6 +244,8 @@ impl ComponentRepo for DbComponentRepo<sqlx::Postgres> {
             .bind(component.protected_component.clone())
             .bind(component.protector_version)
             .bind(component.metadata.clone())
+            .bind(component.files.clone())
+            .bind(component.retention_policy.clone())
             .execute(self.db_pool.deref())
             .await?;
 
@@ -236,14 +253,14 @@ impl ComponentRepo for DbComponentRepo<sqlx::Postgres> {
     }
 
     async fn get_all(&self) -> Result<Vec<ComponentRecord>, RepoError> {
-        sqlx::query_as::<_, ComponentRecord>("SELECT component_id, name, size, version, user_component, protected_component, protector_version, jsonb_pretty(components.metadata) AS metadata  FROM components")
+        sqlx::query_as::<_, ComponentRecord>("SELECT component_id, name, size, version, user_component, protected_component, protector_version, jsonb_pretty(components.metadata) AS metadata, jsonb_pretty(components.files) AS files, jsonb_pretty(components.retention_policy) AS retention_policy  FROM components")
             .fetch_all(self.db_pool.deref())
             .await
             .map_err(|e| e.into())
     }
 
     async fn get(&self, component_id: &Uuid) -> Result<Vec<ComponentRecord>, RepoError> {
-        sqlx::query_as::<_, ComponentRecord>("SELECT component_id, name, size, version, user_component, protected_component, protector_version, jsonb_pretty(components.metadata) AS metadata  FROM components WHERE component_id = $1")
+        sqlx::query_as::<_, ComponentRecord>("SELECT component_id, name, size, version, user_component, protected_component, protector_version, jsonb_pretty(components.metadata) AS metadata, jsonb_pretty(components.files) AS files, jsonb_pretty(components.retention_policy) AS retention_policy  FROM components WHERE component_id = $1")
             .bind(component_id)
             .fetch_all(self.db_pool.deref())
             .await
@@ -252,7 +269,7 @@ impl ComponentRepo for DbComponentRepo<sqlx::Postgres> {
 
     async fn get_by_name(&self, name: &str) -> Result<Vec<ComponentRecord>, RepoError> {
         sqlx::query_as::<_, ComponentRecord>(
-            "SELECT component_id, name, size, version, user_component, protected_component, protector_version, jsonb_pretty(components.metadata) AS metadata FROM components WHERE name = $1",
+            "SELECT component_id, name, size, version, user_component, protected_component, protector_version, jsonb_pretty(components.metadata) AS metadata, jsonb_pretty(components.files) AS files, jsonb_pretty(components.retention_policy) AS retention_policy FROM components WHERE name = $1",
         )
             .bind(name)
             .fetch_all(self.db_pool.deref())
@@ -265,7 +282,7 @@ impl ComponentRepo for DbComponentRepo<sqlx::Postgres> {
         component_id: &Uuid,
     ) -> Result<Option<ComponentRecord>, RepoError> {
         sqlx::query_as::<_, ComponentRecord>(
-            "SELECT component_id, name, size, version, user_component, protected_component, protector_version, jsonb_pretty(components.metadata) AS metadata FROM components WHERE component_id = $1 ORDER BY version DESC LIMIT 1",
+            "SELECT component_id, name, size, version, user_component, protected_component, protector_version, jsonb_pretty(components.metadata) AS metadata, jsonb_pretty(components.files) AS files, jsonb_pretty(components.retention_policy) AS retention_policy FROM components WHERE component_id = $1 ORDER BY version DESC LIMIT 1",
         )
             .bind(component_id)
             .fetch_optional(self.db_pool.deref())
@@ -279,7 +296,7 @@ impl ComponentRepo for DbComponentRepo<sqlx::Postgres> {
         version: u64,
     ) -> Result<Option<ComponentRecord>, RepoError> {
         sqlx::query_as::<_, ComponentRecord>(
-            "SELECT component_id, name, size, version, user_component, protected_component, protector_version, jsonb_pretty(components.metadata) AS metadata  FROM components WHERE component_id = $1 AND version = $2",
+            "SELECT component_id, name, size, version, user_component, protected_component, protector_version, jsonb_pretty(components.metadata) AS metadata, jsonb_pretty(components.files) AS files, jsonb_pretty(components.retention_policy) AS retention_policy  FROM components WHERE component_id = $1 AND version = $2",
         )
             .bind(component_id)
             .bind(version as i64)
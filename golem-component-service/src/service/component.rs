@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::io::{Cursor, Read};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -21,8 +23,10 @@ use golem_component_service_base::service::component_compilation::ComponentCompi
 use golem_component_service_base::service::component_processor::{
     process_component, ComponentProcessingError,
 };
+use sha2::{Digest, Sha256};
 use tap::TapFallible;
 use tracing::{error, info};
+use zip::ZipArchive;
 
 use crate::repo::component::ComponentRepo;
 use crate::repo::RepoError;
@@ -40,6 +44,8 @@ pub enum ComponentError {
     UnknownVersionedComponentId(VersionedComponentId),
     #[error(transparent)]
     ComponentProcessingError(#[from] ComponentProcessingError),
+    #[error("Invalid initial files: {0}")]
+    InvalidInitialFiles(String),
     #[error("Internal error: {0}")]
     Internal(anyhow::Error),
 }
@@ -60,12 +66,21 @@ impl From<RepoError> for ComponentError {
     }
 }
 
+/// The initial files of a new component, uploaded as a zip archive. Files without an entry in
+/// `permissions` are read-only.
+#[derive(Debug, Clone, Default)]
+pub struct InitialFilesArchive {
+    pub archive: Vec<u8>,
+    pub permissions: HashMap<String, ComponentFilePermissions>,
+}
+
 #[async_trait]
 pub trait ComponentService {
     async fn create(
         &self,
         component_name: &ComponentName,
         data: Vec<u8>,
+        files: Option<InitialFilesArchive>,
     ) -> Result<Component, ComponentError>;
 
     async fn update(
@@ -108,6 +123,20 @@ pub trait ComponentService {
     ) -> Result<Option<Component>, ComponentError>;
 
     async fn get(&self, component_id: &ComponentId) -> Result<Vec<Component>, ComponentError>;
+
+    async fn download_file_stream(
+        &self,
+        component_id: &ComponentId,
+        key: &str,
+    ) -> Result<ByteStream, ComponentError>;
+
+    /// Sets the retention policy of the latest version of a component. New versions of the
+    /// component inherit it.
+    async fn update_retention_policy(
+        &self,
+        component_id: &ComponentId,
+        retention_policy: Option<ComponentRetentionPolicy>,
+    ) -> Result<Component, ComponentError>;
 }
 
 pub struct ComponentServiceDefault {
@@ -136,6 +165,7 @@ impl ComponentService for ComponentServiceDefault {
         &self,
         component_name: &ComponentName,
         data: Vec<u8>,
+        files: Option<InitialFilesArchive>,
     ) -> Result<Component, ComponentError> {
         let tn = component_name.0.clone();
         info!("Creating component  with name {}", tn);
@@ -146,6 +176,11 @@ impl ComponentService for ComponentServiceDefault {
 
         let component_id = ComponentId::new_v4();
 
+        let files = match files {
+            Some(files) => self.upload_initial_files(&component_id, files).await?,
+            None => vec![],
+        };
+
         let versioned_component_id = VersionedComponentId {
             component_id,
             version: 0,
@@ -180,6 +215,7 @@ impl ComponentService for ComponentServiceDefault {
             versioned_component_id,
             user_component_id,
             protected_component_id,
+            files,
         };
 
         self.component_repo
@@ -414,6 +450,53 @@ impl ComponentService for ComponentServiceDefault {
             .await?;
         Ok(result.map(|t| t.into()))
     }
+
+    async fn download_file_stream(
+        &self,
+        component_id: &ComponentId,
+        key: &str,
+    ) -> Result<ByteStream, ComponentError> {
+        info!("Downloading file {} of component {}", key, component_id);
+
+        // The keys of the initial files are prefixed by the component id, so a component can
+        // only be used to download its own files
+        if !key.starts_with(&Self::get_file_object_store_key_prefix(component_id)) {
+            return Err(ComponentError::UnknownComponentId(component_id.clone()));
+        }
+
+        let stream = self.object_store.get_stream(key).await;
+
+        Ok(stream)
+    }
+
+    async fn update_retention_policy(
+        &self,
+        component_id: &ComponentId,
+        retention_policy: Option<ComponentRetentionPolicy>,
+    ) -> Result<Component, ComponentError> {
+        info!(
+            "Updating retention policy of component {}: {:?}",
+            component_id, retention_policy
+        );
+
+        let component = self
+            .component_repo
+            .get_latest_version(&component_id.0)
+            .await?
+            .map(Component::from)
+            .ok_or(ComponentError::UnknownComponentId(component_id.clone()))?;
+
+        let component = Component {
+            retention_policy,
+            ..component
+        };
+
+        self.component_repo
+            .upsert(&component.clone().into())
+            .await?;
+
+        Ok(component)
+    }
 }
 
 impl ComponentServiceDefault {
@@ -443,6 +526,47 @@ impl ComponentServiceDefault {
         id.slug()
     }
 
+    fn get_file_object_store_key_prefix(component_id: &ComponentId) -> String {
+        format!("{}:file:", component_id.0)
+    }
+
+    // File contents are addressed by their hash, so the versions of a component share them
+    fn get_file_object_store_key(component_id: &ComponentId, data: &[u8]) -> String {
+        format!(
+            "{}{:x}",
+            Self::get_file_object_store_key_prefix(component_id),
+            Sha256::digest(data)
+        )
+    }
+
+    async fn upload_initial_files(
+        &self,
+        component_id: &ComponentId,
+        files: InitialFilesArchive,
+    ) -> Result<Vec<InitialComponentFile>, ComponentError> {
+        let mut result = vec![];
+
+        for (path, permissions, data) in unpack_initial_files(files)? {
+            info!("Uploading file {} of component {}", path, component_id);
+
+            let key = Self::get_file_object_store_key(component_id, &data);
+            let size = data.len() as u64;
+
+            self.object_store.put(&key, data).await.map_err(|e| {
+                ComponentError::internal(e.to_string(), "Failed to upload component file")
+            })?;
+
+            result.push(InitialComponentFile {
+                path,
+                permissions,
+                key,
+                size,
+            });
+        }
+
+        Ok(result)
+    }
+
     async fn upload_user_component(
         &self,
         user_component_id: &UserComponentId,
@@ -487,6 +611,7 @@ impl ComponentService for ComponentServiceNoop {
         &self,
         _component_name: &ComponentName,
         _data: Vec<u8>,
+        _files: Option<InitialFilesArchive>,
     ) -> Result<Component, ComponentError> {
         let fake_component = Component {
             component_name: ComponentName("fake".to_string()),
@@ -511,6 +636,8 @@ impl ComponentService for ComponentServiceNoop {
                     version: 0,
                 },
             },
+            files: vec![],
+            retention_policy: None,
         };
 
         Ok(fake_component)
@@ -544,6 +671,8 @@ impl ComponentService for ComponentServiceNoop {
                     version: 0,
                 },
             },
+            files: vec![],
+            retention_policy: None,
         };
 
         Ok(fake_component)
@@ -597,4 +726,208 @@ impl ComponentService for ComponentServiceNoop {
     async fn get(&self, _component_id: &ComponentId) -> Result<Vec<Component>, ComponentError> {
         Ok(vec![])
     }
+
+    async fn download_file_stream(
+        &self,
+        _component_id: &ComponentId,
+        _key: &str,
+    ) -> Result<ByteStream, ComponentError> {
+        Ok(ByteStream::empty())
+    }
+
+    async fn update_retention_policy(
+        &self,
+        component_id: &ComponentId,
+        _retention_policy: Option<ComponentRetentionPolicy>,
+    ) -> Result<Component, ComponentError> {
+        Err(ComponentError::UnknownComponentId(component_id.clone()))
+    }
+}
+
+/// The maximum uncompressed size of a single initial file
+const MAX_INITIAL_FILE_SIZE: u64 = 64 * 1024 * 1024;
+
+/// The maximum uncompressed size of all the initial files of a component
+const MAX_INITIAL_FILES_TOTAL_SIZE: u64 = 256 * 1024 * 1024;
+
+// Reads the files of the archive with their permissions, rejecting paths which would point outside
+// of the worker's directory. The uncompressed sizes are limited, as the archive is fully unpacked
+// into memory.
+fn unpack_initial_files(
+    files: InitialFilesArchive,
+) -> Result<Vec<(String, ComponentFilePermissions, Vec<u8>)>, ComponentError> {
+    unpack_initial_files_with_limits(files, MAX_INITIAL_FILE_SIZE, MAX_INITIAL_FILES_TOTAL_SIZE)
+}
+
+fn unpack_initial_files_with_limits(
+    files: InitialFilesArchive,
+    max_file_size: u64,
+    max_total_size: u64,
+) -> Result<Vec<(String, ComponentFilePermissions, Vec<u8>)>, ComponentError> {
+    let mut archive = ZipArchive::new(Cursor::new(files.archive))
+        .map_err(|e| ComponentError::InvalidInitialFiles(e.to_string()))?;
+
+    let mut result = vec![];
+    let mut total_size = 0u64;
+
+    for index in 0..archive.len() {
+        let mut file = archive
+            .by_index(index)
+            .map_err(|e| ComponentError::InvalidInitialFiles(e.to_string()))?;
+
+        if file.is_dir() {
+            continue;
+        }
+
+        let path = file
+            .enclosed_name()
+            .and_then(|path| {
+                path.iter()
+                    .map(|part| part.to_str())
+                    .collect::<Option<Vec<_>>>()
+            })
+            .map(|parts| parts.join("/"))
+            .ok_or_else(|| {
+                ComponentError::InvalidInitialFiles(format!("Invalid path {}", file.name()))
+            })?;
+
+        // The sizes in the archive cannot be trusted, so at most one byte over the limit is read
+        let limit = max_file_size.min(max_total_size - total_size);
+        let mut data = vec![];
+        (&mut file)
+            .take(limit + 1)
+            .read_to_end(&mut data)
+            .map_err(|e| ComponentError::InvalidInitialFiles(e.to_string()))?;
+        if data.len() as u64 > limit {
+            return Err(ComponentError::InvalidInitialFiles(format!(
+                "The initial files exceed the size limit of {max_file_size} bytes per file and {max_total_size} bytes in total at {path}"
+            )));
+        }
+        total_size += data.len() as u64;
+
+        let permissions = files
+            .permissions
+            .get(&path)
+            .copied()
+            .unwrap_or(ComponentFilePermissions::ReadOnly);
+
+        result.push((path, permissions, data));
+    }
+
+    for path in files.permissions.keys() {
+        if !result.iter().any(|(file_path, _, _)| file_path == path) {
+            return Err(ComponentError::InvalidInitialFiles(format!(
+                "Permissions are given for {path}, which is not in the archive"
+            )));
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io::{Cursor, Write};
+
+    use golem_service_base::model::ComponentFilePermissions;
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
+
+    use crate::service::component::{
+        unpack_initial_files, unpack_initial_files_with_limits, ComponentError, InitialFilesArchive,
+    };
+
+    fn archive(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (path, content) in files {
+            writer.start_file(*path, FileOptions::default()).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn unpack_with_permissions() {
+        let files = InitialFilesArchive {
+            archive: archive(&[("config.json", "{}"), ("data/state.txt", "0")]),
+            permissions: HashMap::from([(
+                "data/state.txt".to_string(),
+                ComponentFilePermissions::ReadWrite,
+            )]),
+        };
+
+        let result = unpack_initial_files(files).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                (
+                    "config.json".to_string(),
+                    ComponentFilePermissions::ReadOnly,
+                    b"{}".to_vec()
+                ),
+                (
+                    "data/state.txt".to_string(),
+                    ComponentFilePermissions::ReadWrite,
+                    b"0".to_vec()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn unpack_rejects_paths_outside_of_the_root() {
+        let files = InitialFilesArchive {
+            archive: archive(&[("../passwd", "")]),
+            permissions: HashMap::new(),
+        };
+
+        let result = unpack_initial_files(files);
+
+        assert!(matches!(
+            result,
+            Err(ComponentError::InvalidInitialFiles(_))
+        ));
+    }
+
+    #[test]
+    fn unpack_rejects_permissions_of_missing_files() {
+        let files = InitialFilesArchive {
+            archive: archive(&[("config.json", "{}")]),
+            permissions: HashMap::from([(
+                "other.json".to_string(),
+                ComponentFilePermissions::ReadWrite,
+            )]),
+        };
+
+        let result = unpack_initial_files(files);
+
+        assert!(matches!(
+            result,
+            Err(ComponentError::InvalidInitialFiles(_))
+        ));
+    }
+
+    #[test]
+    fn unpack_rejects_files_over_the_size_limits() {
+        let files = || InitialFilesArchive {
+            archive: archive(&[("a.txt", "12345"), ("b.txt", "123456")]),
+            permissions: HashMap::new(),
+        };
+
+        let within_limits = unpack_initial_files_with_limits(files(), 6, 11);
+        let file_too_large = unpack_initial_files_with_limits(files(), 5, 100);
+        let total_too_large = unpack_initial_files_with_limits(files(), 6, 10);
+
+        assert!(within_limits.is_ok());
+        assert!(matches!(
+            file_too_large,
+            Err(ComponentError::InvalidInitialFiles(_))
+        ));
+        assert!(matches!(
+            total_too_large,
+            Err(ComponentError::InvalidInitialFiles(_))
+        ));
+    }
 }
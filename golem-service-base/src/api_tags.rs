@@ -2,6 +2,7 @@ use poem_openapi::Tags;
 
 #[derive(Tags)]
 pub enum ApiTags {
+    ApiAsset,
     ApiDeployment,
     ApiDefinition,
     Component,
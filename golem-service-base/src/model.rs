@@ -15,9 +15,10 @@
 use golem_api_grpc::proto::golem::shardmanager::{
     Pod as GrpcPod, RoutingTable as GrpcRoutingTable, RoutingTableEntry as GrpcRoutingTableEntry,
 };
+use golem_common::model::cron::CronSchedule;
 use golem_common::model::{
-    parse_function_name, ComponentId, ComponentVersion, ScanCursor, ShardId, Timestamp,
-    WorkerFilter, WorkerStatus,
+    parse_function_name, ComponentId, ComponentVersion, InvocationSchedule, ScanCursor,
+    ScheduledInvocationId, ShardId, Timestamp, WorkerFilter, WorkerStatus,
 };
 use golem_wasm_ast::analysis::{AnalysedResourceId, AnalysedResourceMode};
 use http::Uri;
@@ -1913,12 +1914,18 @@ impl std::fmt::Display for WorkerId {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
+/// Completes a promise either with raw data, or with a value of the payload type the promise
+/// declares, given as JSON or in the WAVE format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
 #[serde(rename_all = "camelCase")]
 #[oai(rename_all = "camelCase")]
 pub struct CompleteParameters {
     pub oplog_idx: u64,
+    #[serde(default)]
+    #[oai(default)]
     pub data: Vec<u8>,
+    pub json: Option<serde_json::Value>,
+    pub wave: Option<String>,
 }
 
 impl From<CompleteParameters> for golem_api_grpc::proto::golem::worker::CompleteParameters {
@@ -1926,6 +1933,8 @@ impl From<CompleteParameters> for golem_api_grpc::proto::golem::worker::Complete
         Self {
             oplog_idx: value.oplog_idx,
             data: value.data,
+            json: value.json.map(|json| json.to_string()),
+            wave: value.wave,
         }
     }
 }
@@ -2360,6 +2369,37 @@ impl From<GolemErrorPromiseAlreadyCompleted>
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object, thiserror::Error)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+#[error("Promise timed out: {promise_id}")]
+pub struct GolemErrorPromiseTimedOut {
+    pub promise_id: PromiseId,
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::worker::PromiseTimedOut> for GolemErrorPromiseTimedOut {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::worker::PromiseTimedOut,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            promise_id: value
+                .promise_id
+                .ok_or("Missing field: promise_id")?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<GolemErrorPromiseTimedOut> for golem_api_grpc::proto::golem::worker::PromiseTimedOut {
+    fn from(value: GolemErrorPromiseTimedOut) -> Self {
+        Self {
+            promise_id: Some(value.promise_id.into()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object, thiserror::Error)]
 #[serde(rename_all = "camelCase")]
 #[oai(rename_all = "camelCase")]
@@ -2664,6 +2704,448 @@ pub struct UpdateWorkerRequest {
     pub target_version: ComponentVersion,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum OplogEntryType {
+    Create,
+    ImportedFunctionInvoked,
+    ExportedFunctionInvoked,
+    ExportedFunctionCompleted,
+    Suspend,
+    Error,
+    NoOp,
+    Jump,
+    Interrupted,
+    Exited,
+    ChangeRetryPolicy,
+    BeginAtomicRegion,
+    EndAtomicRegion,
+    BeginRemoteWrite,
+    EndRemoteWrite,
+    PendingWorkerInvocation,
+    PendingUpdate,
+    SuccessfulUpdate,
+    FailedUpdate,
+    CancelPendingInvocation,
+    Snapshot,
+}
+
+impl From<golem_api_grpc::proto::golem::worker::OplogEntryType> for OplogEntryType {
+    fn from(value: golem_api_grpc::proto::golem::worker::OplogEntryType) -> Self {
+        match value {
+            golem_api_grpc::proto::golem::worker::OplogEntryType::Create => OplogEntryType::Create,
+            golem_api_grpc::proto::golem::worker::OplogEntryType::ImportedFunctionInvoked => {
+                OplogEntryType::ImportedFunctionInvoked
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::ExportedFunctionInvoked => {
+                OplogEntryType::ExportedFunctionInvoked
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::ExportedFunctionCompleted => {
+                OplogEntryType::ExportedFunctionCompleted
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::Suspend => {
+                OplogEntryType::Suspend
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::Error => OplogEntryType::Error,
+            golem_api_grpc::proto::golem::worker::OplogEntryType::NoOp => OplogEntryType::NoOp,
+            golem_api_grpc::proto::golem::worker::OplogEntryType::Jump => OplogEntryType::Jump,
+            golem_api_grpc::proto::golem::worker::OplogEntryType::Interrupted => {
+                OplogEntryType::Interrupted
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::Exited => OplogEntryType::Exited,
+            golem_api_grpc::proto::golem::worker::OplogEntryType::ChangeRetryPolicy => {
+                OplogEntryType::ChangeRetryPolicy
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::BeginAtomicRegion => {
+                OplogEntryType::BeginAtomicRegion
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::EndAtomicRegion => {
+                OplogEntryType::EndAtomicRegion
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::BeginRemoteWrite => {
+                OplogEntryType::BeginRemoteWrite
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::EndRemoteWrite => {
+                OplogEntryType::EndRemoteWrite
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::PendingWorkerInvocation => {
+                OplogEntryType::PendingWorkerInvocation
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::PendingUpdate => {
+                OplogEntryType::PendingUpdate
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::SuccessfulUpdate => {
+                OplogEntryType::SuccessfulUpdate
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::FailedUpdate => {
+                OplogEntryType::FailedUpdate
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::CancelPendingInvocation => {
+                OplogEntryType::CancelPendingInvocation
+            }
+            golem_api_grpc::proto::golem::worker::OplogEntryType::Snapshot => {
+                OplogEntryType::Snapshot
+            }
+        }
+    }
+}
+
+impl From<OplogEntryType> for golem_api_grpc::proto::golem::worker::OplogEntryType {
+    fn from(value: OplogEntryType) -> Self {
+        match value {
+            OplogEntryType::Create => golem_api_grpc::proto::golem::worker::OplogEntryType::Create,
+            OplogEntryType::ImportedFunctionInvoked => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::ImportedFunctionInvoked
+            }
+            OplogEntryType::ExportedFunctionInvoked => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::ExportedFunctionInvoked
+            }
+            OplogEntryType::ExportedFunctionCompleted => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::ExportedFunctionCompleted
+            }
+            OplogEntryType::Suspend => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::Suspend
+            }
+            OplogEntryType::Error => golem_api_grpc::proto::golem::worker::OplogEntryType::Error,
+            OplogEntryType::NoOp => golem_api_grpc::proto::golem::worker::OplogEntryType::NoOp,
+            OplogEntryType::Jump => golem_api_grpc::proto::golem::worker::OplogEntryType::Jump,
+            OplogEntryType::Interrupted => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::Interrupted
+            }
+            OplogEntryType::Exited => golem_api_grpc::proto::golem::worker::OplogEntryType::Exited,
+            OplogEntryType::ChangeRetryPolicy => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::ChangeRetryPolicy
+            }
+            OplogEntryType::BeginAtomicRegion => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::BeginAtomicRegion
+            }
+            OplogEntryType::EndAtomicRegion => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::EndAtomicRegion
+            }
+            OplogEntryType::BeginRemoteWrite => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::BeginRemoteWrite
+            }
+            OplogEntryType::EndRemoteWrite => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::EndRemoteWrite
+            }
+            OplogEntryType::PendingWorkerInvocation => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::PendingWorkerInvocation
+            }
+            OplogEntryType::PendingUpdate => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::PendingUpdate
+            }
+            OplogEntryType::SuccessfulUpdate => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::SuccessfulUpdate
+            }
+            OplogEntryType::FailedUpdate => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::FailedUpdate
+            }
+            OplogEntryType::CancelPendingInvocation => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::CancelPendingInvocation
+            }
+            OplogEntryType::Snapshot => {
+                golem_api_grpc::proto::golem::worker::OplogEntryType::Snapshot
+            }
+        }
+    }
+}
+
+/// A decoded function request or response stored in the oplog
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct OplogEntryPayload {
+    pub json: serde_json::value::Value,
+    pub wave: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct PublicOplogEntry {
+    pub oplog_index: u64,
+    pub timestamp: Timestamp,
+    pub entry_type: OplogEntryType,
+    pub function_name: Option<String>,
+    pub idempotency_key: Option<String>,
+    pub request: Option<OplogEntryPayload>,
+    pub response: Option<OplogEntryPayload>,
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct GetOplogResponse {
+    pub entries: Vec<PublicOplogEntry>,
+    pub next: Option<u64>,
+    pub last_index: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct SearchOplogRequest {
+    pub entry_types: Option<Vec<OplogEntryType>>,
+    pub after: Option<Timestamp>,
+    pub before: Option<Timestamp>,
+    pub function_name: Option<String>,
+    pub cursor: Option<u64>,
+    pub count: Option<u64>,
+}
+
+impl From<SearchOplogRequest> for golem_api_grpc::proto::golem::worker::OplogFilter {
+    fn from(value: SearchOplogRequest) -> Self {
+        Self {
+            entry_types: value
+                .entry_types
+                .unwrap_or_default()
+                .into_iter()
+                .map(|entry_type| {
+                    let entry_type: golem_api_grpc::proto::golem::worker::OplogEntryType =
+                        entry_type.into();
+                    entry_type as i32
+                })
+                .collect(),
+            after: value.after.map(|t| t.into()),
+            before: value.before.map(|t| t.into()),
+            function_name: value.function_name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ForkWorkerRequest {
+    pub target_worker_name: String,
+    pub oplog_index_cutoff: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
+pub struct ForkWorkerResponse {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Union)]
+#[serde(rename_all = "camelCase")]
+#[oai(discriminator_name = "type", one_of = true, rename_all = "camelCase")]
+pub enum RevertWorkerTarget {
+    RevertToOplogIndex(RevertToOplogIndex),
+    RevertLastInvocations(RevertLastInvocations),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct RevertToOplogIndex {
+    pub last_oplog_index: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct RevertLastInvocations {
+    pub number_of_invocations: u64,
+}
+
+impl From<RevertWorkerTarget> for golem_common::model::oplog::RevertWorkerTarget {
+    fn from(value: RevertWorkerTarget) -> Self {
+        match value {
+            RevertWorkerTarget::RevertToOplogIndex(target) => {
+                golem_common::model::oplog::RevertWorkerTarget::RevertToOplogIndex(
+                    golem_common::model::oplog::OplogIndex::from_u64(target.last_oplog_index),
+                )
+            }
+            RevertWorkerTarget::RevertLastInvocations(target) => {
+                golem_common::model::oplog::RevertWorkerTarget::RevertLastInvocations(
+                    target.number_of_invocations,
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
+pub struct RevertWorkerResponse {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Enum)]
+pub enum CancelInvocationStatus {
+    Canceled,
+    AlreadyRunning,
+    AlreadyCompleted,
+    NotFound,
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::worker::CancelInvocationStatus>
+    for CancelInvocationStatus
+{
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::worker::CancelInvocationStatus,
+    ) -> Result<Self, Self::Error> {
+        match value {
+            golem_api_grpc::proto::golem::worker::CancelInvocationStatus::Unspecified => {
+                Err("Unspecified cancel invocation status".to_string())
+            }
+            golem_api_grpc::proto::golem::worker::CancelInvocationStatus::Canceled => {
+                Ok(CancelInvocationStatus::Canceled)
+            }
+            golem_api_grpc::proto::golem::worker::CancelInvocationStatus::AlreadyRunning => {
+                Ok(CancelInvocationStatus::AlreadyRunning)
+            }
+            golem_api_grpc::proto::golem::worker::CancelInvocationStatus::AlreadyCompleted => {
+                Ok(CancelInvocationStatus::AlreadyCompleted)
+            }
+            golem_api_grpc::proto::golem::worker::CancelInvocationStatus::NotFound => {
+                Ok(CancelInvocationStatus::NotFound)
+            }
+        }
+    }
+}
+
+impl From<CancelInvocationStatus> for golem_api_grpc::proto::golem::worker::CancelInvocationStatus {
+    fn from(value: CancelInvocationStatus) -> Self {
+        match value {
+            CancelInvocationStatus::Canceled => {
+                golem_api_grpc::proto::golem::worker::CancelInvocationStatus::Canceled
+            }
+            CancelInvocationStatus::AlreadyRunning => {
+                golem_api_grpc::proto::golem::worker::CancelInvocationStatus::AlreadyRunning
+            }
+            CancelInvocationStatus::AlreadyCompleted => {
+                golem_api_grpc::proto::golem::worker::CancelInvocationStatus::AlreadyCompleted
+            }
+            CancelInvocationStatus::NotFound => {
+                golem_api_grpc::proto::golem::worker::CancelInvocationStatus::NotFound
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
+pub struct CancelInvocationResponse {
+    pub status: CancelInvocationStatus,
+}
+
+/// Parameters of an invocation scheduled either at a given point in time or recurring
+/// according to a cron expression. Exactly one of `at` and `cron` has to be specified.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ScheduleInvocationParameters {
+    pub params: serde_json::value::Value,
+    pub at: Option<Timestamp>,
+    pub cron: Option<String>,
+}
+
+impl ScheduleInvocationParameters {
+    pub fn schedule(&self) -> Result<InvocationSchedule, String> {
+        match (&self.at, &self.cron) {
+            (Some(at), None) => Ok(InvocationSchedule::At(*at)),
+            (None, Some(cron)) => Ok(InvocationSchedule::Cron(CronSchedule::parse(cron)?)),
+            _ => Err("Exactly one of 'at' and 'cron' must be specified".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ScheduledInvocation {
+    pub id: ScheduledInvocationId,
+    pub scheduled_at: Timestamp,
+    pub function_name: String,
+    pub params: Option<OplogEntryPayload>,
+    pub cron: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct GetScheduledInvocationsResponse {
+    pub invocations: Vec<ScheduledInvocation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
+pub struct CancelScheduledInvocationResponse {
+    pub canceled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct WorkerFileEntry {
+    pub name: String,
+    pub is_directory: bool,
+    pub size: u64,
+    pub read_only: bool,
+}
+
+impl From<golem_api_grpc::proto::golem::worker::WorkerFileEntry> for WorkerFileEntry {
+    fn from(value: golem_api_grpc::proto::golem::worker::WorkerFileEntry) -> Self {
+        Self {
+            name: value.name,
+            is_directory: value.is_directory,
+            size: value.size,
+            read_only: value.read_only,
+        }
+    }
+}
+
+impl From<WorkerFileEntry> for golem_api_grpc::proto::golem::worker::WorkerFileEntry {
+    fn from(value: WorkerFileEntry) -> Self {
+        Self {
+            name: value.name,
+            is_directory: value.is_directory,
+            size: value.size,
+            read_only: value.read_only,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ListWorkerDirectoryResponse {
+    pub entries: Vec<WorkerFileEntry>,
+}
+
+/// A promise created by a worker which is not completed yet. The oplog index of its id
+/// is the index of the oplog entry the worker created it at. Promises with a payload type
+/// can only be completed with a JSON or WAVE value of that type, or with raw data holding
+/// the value encoded as JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct PendingPromise {
+    pub promise_id: PromiseId,
+    pub expires_at: Option<Timestamp>,
+    pub payload_type: Option<Type>,
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::worker::PendingPromise> for PendingPromise {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::worker::PendingPromise,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            promise_id: value
+                .promise_id
+                .ok_or("Missing field: promise_id")?
+                .try_into()?,
+            expires_at: value.expires_at.map(|expires_at| expires_at.into()),
+            payload_type: value.payload_type.map(|typ| typ.try_into()).transpose()?,
+        })
+    }
+}
+
+impl From<PendingPromise> for golem_api_grpc::proto::golem::worker::PendingPromise {
+    fn from(value: PendingPromise) -> Self {
+        Self {
+            promise_id: Some(value.promise_id.into()),
+            expires_at: value.expires_at.map(|expires_at| expires_at.into()),
+            payload_type: value.payload_type.map(|typ| typ.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct GetPendingPromisesResponse {
+    pub promises: Vec<PendingPromise>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct WorkersMetadataRequest {
     pub filter: Option<WorkerFilter>,
@@ -2878,6 +3360,8 @@ pub enum GolemError {
     #[error(transparent)]
     PromiseAlreadyCompleted(GolemErrorPromiseAlreadyCompleted),
     #[error(transparent)]
+    PromiseTimedOut(GolemErrorPromiseTimedOut),
+    #[error(transparent)]
     Interrupted(GolemErrorInterrupted),
     #[error(transparent)]
     ParamTypeMismatch(GolemErrorParamTypeMismatch),
@@ -2945,6 +3429,9 @@ impl TryFrom<golem_api_grpc::proto::golem::worker::WorkerExecutionError> for Gol
             Some(golem_api_grpc::proto::golem::worker::worker_execution_error::Error::PromiseAlreadyCompleted(err)) => {
                 Ok(GolemError::PromiseAlreadyCompleted(err.try_into()?))
             }
+            Some(golem_api_grpc::proto::golem::worker::worker_execution_error::Error::PromiseTimedOut(err)) => {
+                Ok(GolemError::PromiseTimedOut(err.try_into()?))
+            }
             Some(golem_api_grpc::proto::golem::worker::worker_execution_error::Error::Interrupted(err)) => {
                 Ok(GolemError::Interrupted(err.into()))
             }
@@ -3041,6 +3528,11 @@ impl From<GolemError> for golem_api_grpc::proto::golem::worker::WorkerExecutionE
                     error: Some(golem_api_grpc::proto::golem::worker::worker_execution_error::Error::PromiseAlreadyCompleted(err.into())),
                 }
             }
+            GolemError::PromiseTimedOut(err) => {
+                golem_api_grpc::proto::golem::worker::WorkerExecutionError {
+                    error: Some(golem_api_grpc::proto::golem::worker::worker_execution_error::Error::PromiseTimedOut(err.into())),
+                }
+            }
             GolemError::Interrupted(err) => {
                 golem_api_grpc::proto::golem::worker::WorkerExecutionError {
                     error: Some(golem_api_grpc::proto::golem::worker::worker_execution_error::Error::Interrupted(err.into())),
@@ -3152,6 +3644,11 @@ pub struct Component {
     pub component_name: ComponentName,
     pub component_size: u64,
     pub metadata: ComponentMetadata,
+    #[serde(default)]
+    #[oai(default)]
+    pub files: Vec<InitialComponentFile>,
+    #[serde(default)]
+    pub retention_policy: Option<ComponentRetentionPolicy>,
 }
 
 impl TryFrom<golem_api_grpc::proto::golem::component::Component> for Component {
@@ -3176,6 +3673,8 @@ impl TryFrom<golem_api_grpc::proto::golem::component::Component> for Component {
             component_name: ComponentName(value.component_name),
             component_size: value.component_size,
             metadata: value.metadata.ok_or("Missing metadata")?.try_into()?,
+            files: value.files.into_iter().map(|file| file.into()).collect(),
+            retention_policy: value.retention_policy.map(|policy| policy.into()),
         })
     }
 }
@@ -3190,6 +3689,8 @@ impl From<Component> for golem_api_grpc::proto::golem::component::Component {
             component_size: value.component_size,
             metadata: Some(value.metadata.into()),
             project_id: None,
+            files: value.files.into_iter().map(|file| file.into()).collect(),
+            retention_policy: value.retention_policy.map(|policy| policy.into()),
         }
     }
 }
@@ -3213,6 +3714,155 @@ impl Component {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum ComponentFilePermissions {
+    ReadOnly,
+    ReadWrite,
+}
+
+impl From<golem_api_grpc::proto::golem::component::ComponentFilePermissions>
+    for ComponentFilePermissions
+{
+    fn from(value: golem_api_grpc::proto::golem::component::ComponentFilePermissions) -> Self {
+        match value {
+            golem_api_grpc::proto::golem::component::ComponentFilePermissions::ReadOnly => {
+                ComponentFilePermissions::ReadOnly
+            }
+            golem_api_grpc::proto::golem::component::ComponentFilePermissions::ReadWrite => {
+                ComponentFilePermissions::ReadWrite
+            }
+        }
+    }
+}
+
+impl From<ComponentFilePermissions>
+    for golem_api_grpc::proto::golem::component::ComponentFilePermissions
+{
+    fn from(value: ComponentFilePermissions) -> Self {
+        match value {
+            ComponentFilePermissions::ReadOnly => {
+                golem_api_grpc::proto::golem::component::ComponentFilePermissions::ReadOnly
+            }
+            ComponentFilePermissions::ReadWrite => {
+                golem_api_grpc::proto::golem::component::ComponentFilePermissions::ReadWrite
+            }
+        }
+    }
+}
+
+/// Determines how long the workers of a component which will never run again are kept. Every
+/// limit is optional, workers are kept forever if none is set.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ComponentRetentionPolicy {
+    /// Exited workers are deleted this many seconds after they exited
+    pub delete_exited_after_seconds: Option<u64>,
+    /// Failed workers are deleted this many seconds after they failed
+    pub delete_failed_after_seconds: Option<u64>,
+    /// The oplog of failed workers is moved to the last archive layer this many seconds after
+    /// they failed
+    pub archive_failed_after_seconds: Option<u64>,
+}
+
+impl From<golem_api_grpc::proto::golem::component::ComponentRetentionPolicy>
+    for ComponentRetentionPolicy
+{
+    fn from(value: golem_api_grpc::proto::golem::component::ComponentRetentionPolicy) -> Self {
+        Self {
+            delete_exited_after_seconds: value.delete_exited_after_seconds,
+            delete_failed_after_seconds: value.delete_failed_after_seconds,
+            archive_failed_after_seconds: value.archive_failed_after_seconds,
+        }
+    }
+}
+
+impl From<ComponentRetentionPolicy>
+    for golem_api_grpc::proto::golem::component::ComponentRetentionPolicy
+{
+    fn from(value: ComponentRetentionPolicy) -> Self {
+        Self {
+            delete_exited_after_seconds: value.delete_exited_after_seconds,
+            delete_failed_after_seconds: value.delete_failed_after_seconds,
+            archive_failed_after_seconds: value.archive_failed_after_seconds,
+        }
+    }
+}
+
+/// A file that is copied into the file system of every worker of the component when it is
+/// created. The contents are stored in the component object store under `key`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct InitialComponentFile {
+    pub path: String,
+    pub permissions: ComponentFilePermissions,
+    pub key: String,
+    pub size: u64,
+}
+
+impl From<golem_api_grpc::proto::golem::component::InitialComponentFile> for InitialComponentFile {
+    fn from(value: golem_api_grpc::proto::golem::component::InitialComponentFile) -> Self {
+        Self {
+            permissions: value.permissions().into(),
+            path: value.path,
+            key: value.key,
+            size: value.size,
+        }
+    }
+}
+
+impl From<InitialComponentFile> for golem_api_grpc::proto::golem::component::InitialComponentFile {
+    fn from(value: InitialComponentFile) -> Self {
+        let permissions: golem_api_grpc::proto::golem::component::ComponentFilePermissions =
+            value.permissions.into();
+        Self {
+            path: value.path,
+            permissions: permissions as i32,
+            key: value.key,
+            size: value.size,
+        }
+    }
+}
+
+/// The permissions of one of the initial files uploaded with a new component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct InitialComponentFilePermissions {
+    pub path: String,
+    pub permissions: ComponentFilePermissions,
+}
+
+impl From<golem_api_grpc::proto::golem::component::InitialComponentFilePermissions>
+    for InitialComponentFilePermissions
+{
+    fn from(
+        value: golem_api_grpc::proto::golem::component::InitialComponentFilePermissions,
+    ) -> Self {
+        Self {
+            permissions: value.permissions().into(),
+            path: value.path,
+        }
+    }
+}
+
+impl From<InitialComponentFilePermissions>
+    for golem_api_grpc::proto::golem::component::InitialComponentFilePermissions
+{
+    fn from(value: InitialComponentFilePermissions) -> Self {
+        let permissions: golem_api_grpc::proto::golem::component::ComponentFilePermissions =
+            value.permissions.into();
+        Self {
+            path: value.path,
+            permissions: permissions as i32,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct InitialComponentFilePermissionsList {
+    pub values: Vec<InitialComponentFilePermissions>,
+}
+
 #[derive(Clone)]
 pub struct NumberOfShards {
     pub value: usize,
@@ -130,6 +130,7 @@ pub trait ComponentService {
             data: Some(Data::Header(CreateComponentRequestHeader {
                 project_id: None,
                 component_name: name.to_string(),
+                file_permissions: vec![],
             })),
         }];
 
@@ -20,12 +20,20 @@ use tonic::Streaming;
 use crate::components::component_service::ComponentService;
 use golem_api_grpc::proto::golem::common::{Empty, ResourceLimits};
 use golem_api_grpc::proto::golem::worker::{
-    ConnectWorkerRequest, DeleteWorkerRequest, DeleteWorkerResponse, GetWorkerMetadataRequest,
-    GetWorkerMetadataResponse, InterruptWorkerRequest, InterruptWorkerResponse,
-    InvokeAndAwaitRequest, InvokeAndAwaitResponse, InvokeRequest, InvokeResponse, InvokeResult,
-    LaunchNewWorkerRequest, LaunchNewWorkerResponse, LaunchNewWorkerSuccessResponse, LogEvent,
-    ResumeWorkerRequest, ResumeWorkerResponse, UpdateWorkerRequest, UpdateWorkerResponse,
-    WorkerError, WorkerId,
+    CancelInvocationRequest, CancelInvocationResponse, CancelScheduledInvocationRequest,
+    CancelScheduledInvocationResponse, CompletePromiseRequest, CompletePromiseResponse,
+    ConnectWorkerRequest, DeleteWorkerRequest, DeleteWorkerResponse, ForkWorkerRequest,
+    ForkWorkerResponse, GetOplogRequest, GetOplogResponse, GetOplogSuccessResponse,
+    GetPendingPromisesRequest, GetPendingPromisesResponse, GetPendingPromisesSuccessResponse,
+    GetScheduledInvocationsRequest, GetScheduledInvocationsResponse,
+    GetScheduledInvocationsSuccessResponse, GetWorkerMetadataRequest, GetWorkerMetadataResponse,
+    InterruptWorkerRequest, InterruptWorkerResponse, InvokeAndAwaitRequest, InvokeAndAwaitResponse,
+    InvokeRequest, InvokeResponse, InvokeResult, LaunchNewWorkerRequest, LaunchNewWorkerResponse,
+    LaunchNewWorkerSuccessResponse, ListWorkerDirectoryRequest, ListWorkerDirectoryResponse,
+    ListWorkerDirectorySuccessResponse, LogEvent, PromiseId, ResumeWorkerRequest,
+    ResumeWorkerResponse, RevertWorkerRequest, RevertWorkerResponse, ScheduleInvocationRequest,
+    ScheduleInvocationResponse, SearchOplogRequest, SearchOplogResponse,
+    SearchOplogSuccessResponse, UpdateWorkerRequest, UpdateWorkerResponse, WorkerError, WorkerId,
 };
 use golem_api_grpc::proto::golem::workerexecutor::CreateWorkerRequest;
 use golem_api_grpc::proto::golem::{worker, workerexecutor};
@@ -433,6 +441,514 @@ impl WorkerService for ForwardingWorkerService {
         }
     }
 
+    async fn get_oplog(&self, request: GetOplogRequest) -> GetOplogResponse {
+        let result = self
+            .worker_executor
+            .client()
+            .await
+            .get_oplog(workerexecutor::GetOplogRequest {
+                worker_id: request.worker_id,
+                from_oplog_index: request.from_oplog_index,
+                count: request.count,
+                account_id: Some(
+                    AccountId {
+                        value: "test-account".to_string(),
+                    }
+                    .into(),
+                ),
+            })
+            .await
+            .expect("Failed to call golem-worker-executor")
+            .into_inner();
+
+        match result.result {
+            None => {
+                panic!("No response from golem-worker-executor get-oplog call");
+            }
+            Some(workerexecutor::get_oplog_response::Result::Success(
+                workerexecutor::GetOplogSuccessResponse {
+                    entries,
+                    next,
+                    last_index,
+                },
+            )) => GetOplogResponse {
+                result: Some(worker::get_oplog_response::Result::Success(
+                    GetOplogSuccessResponse {
+                        entries,
+                        next,
+                        last_index,
+                    },
+                )),
+            },
+            Some(workerexecutor::get_oplog_response::Result::Failure(error)) => GetOplogResponse {
+                result: Some(worker::get_oplog_response::Result::Error(WorkerError {
+                    error: Some(worker::worker_error::Error::InternalError(error)),
+                })),
+            },
+        }
+    }
+
+    async fn search_oplog(&self, request: SearchOplogRequest) -> SearchOplogResponse {
+        let result = self
+            .worker_executor
+            .client()
+            .await
+            .search_oplog(workerexecutor::SearchOplogRequest {
+                worker_id: request.worker_id,
+                filter: request.filter,
+                cursor: request.cursor,
+                count: request.count,
+                account_id: Some(
+                    AccountId {
+                        value: "test-account".to_string(),
+                    }
+                    .into(),
+                ),
+            })
+            .await
+            .expect("Failed to call golem-worker-executor")
+            .into_inner();
+
+        match result.result {
+            None => {
+                panic!("No response from golem-worker-executor search-oplog call");
+            }
+            Some(workerexecutor::search_oplog_response::Result::Success(
+                workerexecutor::SearchOplogSuccessResponse {
+                    entries,
+                    next,
+                    last_index,
+                },
+            )) => SearchOplogResponse {
+                result: Some(worker::search_oplog_response::Result::Success(
+                    SearchOplogSuccessResponse {
+                        entries,
+                        next,
+                        last_index,
+                    },
+                )),
+            },
+            Some(workerexecutor::search_oplog_response::Result::Failure(error)) => {
+                SearchOplogResponse {
+                    result: Some(worker::search_oplog_response::Result::Error(WorkerError {
+                        error: Some(worker::worker_error::Error::InternalError(error)),
+                    })),
+                }
+            }
+        }
+    }
+
+    async fn fork_worker(&self, request: ForkWorkerRequest) -> ForkWorkerResponse {
+        let result = self
+            .worker_executor
+            .client()
+            .await
+            .fork_worker(workerexecutor::ForkWorkerRequest {
+                source_worker_id: request.source_worker_id,
+                target_worker_id: request.target_worker_id,
+                oplog_index_cutoff: request.oplog_index_cutoff,
+                account_id: Some(
+                    AccountId {
+                        value: "test-account".to_string(),
+                    }
+                    .into(),
+                ),
+            })
+            .await
+            .expect("Failed to call golem-worker-executor")
+            .into_inner();
+
+        match result.result {
+            None => {
+                panic!("No response from golem-worker-executor fork-worker call");
+            }
+            Some(workerexecutor::fork_worker_response::Result::Success(_)) => ForkWorkerResponse {
+                result: Some(worker::fork_worker_response::Result::Success(Empty {})),
+            },
+            Some(workerexecutor::fork_worker_response::Result::Failure(error)) => {
+                ForkWorkerResponse {
+                    result: Some(worker::fork_worker_response::Result::Error(WorkerError {
+                        error: Some(worker::worker_error::Error::InternalError(error)),
+                    })),
+                }
+            }
+        }
+    }
+
+    async fn revert_worker(&self, request: RevertWorkerRequest) -> RevertWorkerResponse {
+        let result = self
+            .worker_executor
+            .client()
+            .await
+            .revert_worker(workerexecutor::RevertWorkerRequest {
+                worker_id: request.worker_id,
+                target: request.target,
+                account_id: Some(
+                    AccountId {
+                        value: "test-account".to_string(),
+                    }
+                    .into(),
+                ),
+            })
+            .await
+            .expect("Failed to call golem-worker-executor")
+            .into_inner();
+
+        match result.result {
+            None => {
+                panic!("No response from golem-worker-executor revert-worker call");
+            }
+            Some(workerexecutor::revert_worker_response::Result::Success(_)) => {
+                RevertWorkerResponse {
+                    result: Some(worker::revert_worker_response::Result::Success(Empty {})),
+                }
+            }
+            Some(workerexecutor::revert_worker_response::Result::Failure(error)) => {
+                RevertWorkerResponse {
+                    result: Some(worker::revert_worker_response::Result::Error(WorkerError {
+                        error: Some(worker::worker_error::Error::InternalError(error)),
+                    })),
+                }
+            }
+        }
+    }
+
+    async fn cancel_invocation(
+        &self,
+        request: CancelInvocationRequest,
+    ) -> CancelInvocationResponse {
+        let result = self
+            .worker_executor
+            .client()
+            .await
+            .cancel_invocation(workerexecutor::CancelInvocationRequest {
+                worker_id: request.worker_id,
+                idempotency_key: request.idempotency_key,
+                account_id: Some(
+                    AccountId {
+                        value: "test-account".to_string(),
+                    }
+                    .into(),
+                ),
+            })
+            .await
+            .expect("Failed to call golem-worker-executor")
+            .into_inner();
+
+        match result.result {
+            None => {
+                panic!("No response from golem-worker-executor cancel-invocation call");
+            }
+            Some(workerexecutor::cancel_invocation_response::Result::Success(canceled)) => {
+                CancelInvocationResponse {
+                    result: Some(worker::cancel_invocation_response::Result::Success(
+                        canceled,
+                    )),
+                }
+            }
+            Some(workerexecutor::cancel_invocation_response::Result::Failure(error)) => {
+                CancelInvocationResponse {
+                    result: Some(worker::cancel_invocation_response::Result::Error(
+                        WorkerError {
+                            error: Some(worker::worker_error::Error::InternalError(error)),
+                        },
+                    )),
+                }
+            }
+        }
+    }
+
+    async fn schedule_invocation(
+        &self,
+        request: ScheduleInvocationRequest,
+    ) -> ScheduleInvocationResponse {
+        let result = self
+            .worker_executor
+            .client()
+            .await
+            .schedule_invocation(workerexecutor::ScheduleInvocationRequest {
+                worker_id: request.worker_id,
+                name: request.function,
+                input: request
+                    .invoke_parameters
+                    .map(|p| p.params.clone())
+                    .unwrap_or_default(),
+                schedule: request.schedule,
+                account_id: Some(
+                    AccountId {
+                        value: "test-account".to_string(),
+                    }
+                    .into(),
+                ),
+            })
+            .await
+            .expect("Failed to call golem-worker-executor")
+            .into_inner();
+
+        match result.result {
+            None => {
+                panic!("No response from golem-worker-executor schedule-invocation call");
+            }
+            Some(workerexecutor::schedule_invocation_response::Result::Success(success)) => {
+                ScheduleInvocationResponse {
+                    result: Some(worker::schedule_invocation_response::Result::Success(
+                        success,
+                    )),
+                }
+            }
+            Some(workerexecutor::schedule_invocation_response::Result::Failure(error)) => {
+                ScheduleInvocationResponse {
+                    result: Some(worker::schedule_invocation_response::Result::Error(
+                        WorkerError {
+                            error: Some(worker::worker_error::Error::InternalError(error)),
+                        },
+                    )),
+                }
+            }
+        }
+    }
+
+    async fn get_scheduled_invocations(
+        &self,
+        request: GetScheduledInvocationsRequest,
+    ) -> GetScheduledInvocationsResponse {
+        let result = self
+            .worker_executor
+            .client()
+            .await
+            .get_scheduled_invocations(workerexecutor::GetScheduledInvocationsRequest {
+                worker_id: request.worker_id,
+                account_id: Some(
+                    AccountId {
+                        value: "test-account".to_string(),
+                    }
+                    .into(),
+                ),
+            })
+            .await
+            .expect("Failed to call golem-worker-executor")
+            .into_inner();
+
+        match result.result {
+            None => {
+                panic!("No response from golem-worker-executor get-scheduled-invocations call");
+            }
+            Some(workerexecutor::get_scheduled_invocations_response::Result::Success(success)) => {
+                GetScheduledInvocationsResponse {
+                    result: Some(worker::get_scheduled_invocations_response::Result::Success(
+                        GetScheduledInvocationsSuccessResponse {
+                            invocations: success.invocations,
+                        },
+                    )),
+                }
+            }
+            Some(workerexecutor::get_scheduled_invocations_response::Result::Failure(error)) => {
+                GetScheduledInvocationsResponse {
+                    result: Some(worker::get_scheduled_invocations_response::Result::Error(
+                        WorkerError {
+                            error: Some(worker::worker_error::Error::InternalError(error)),
+                        },
+                    )),
+                }
+            }
+        }
+    }
+
+    async fn cancel_scheduled_invocation(
+        &self,
+        request: CancelScheduledInvocationRequest,
+    ) -> CancelScheduledInvocationResponse {
+        let result = self
+            .worker_executor
+            .client()
+            .await
+            .cancel_scheduled_invocation(workerexecutor::CancelScheduledInvocationRequest {
+                worker_id: request.worker_id,
+                id: request.id,
+                account_id: Some(
+                    AccountId {
+                        value: "test-account".to_string(),
+                    }
+                    .into(),
+                ),
+            })
+            .await
+            .expect("Failed to call golem-worker-executor")
+            .into_inner();
+
+        match result.result {
+            None => {
+                panic!("No response from golem-worker-executor cancel-scheduled-invocation call");
+            }
+            Some(workerexecutor::cancel_scheduled_invocation_response::Result::Success(
+                success,
+            )) => CancelScheduledInvocationResponse {
+                result: Some(
+                    worker::cancel_scheduled_invocation_response::Result::Success(success),
+                ),
+            },
+            Some(workerexecutor::cancel_scheduled_invocation_response::Result::Failure(error)) => {
+                CancelScheduledInvocationResponse {
+                    result: Some(worker::cancel_scheduled_invocation_response::Result::Error(
+                        WorkerError {
+                            error: Some(worker::worker_error::Error::InternalError(error)),
+                        },
+                    )),
+                }
+            }
+        }
+    }
+
+    async fn list_worker_directory(
+        &self,
+        request: ListWorkerDirectoryRequest,
+    ) -> ListWorkerDirectoryResponse {
+        let result = self
+            .worker_executor
+            .client()
+            .await
+            .list_worker_directory(workerexecutor::ListWorkerDirectoryRequest {
+                worker_id: request.worker_id,
+                path: request.path,
+                account_id: Some(
+                    AccountId {
+                        value: "test-account".to_string(),
+                    }
+                    .into(),
+                ),
+            })
+            .await
+            .expect("Failed to call golem-worker-executor")
+            .into_inner();
+
+        match result.result {
+            None => {
+                panic!("No response from golem-worker-executor list-worker-directory call");
+            }
+            Some(workerexecutor::list_worker_directory_response::Result::Success(success)) => {
+                ListWorkerDirectoryResponse {
+                    result: Some(worker::list_worker_directory_response::Result::Success(
+                        ListWorkerDirectorySuccessResponse {
+                            entries: success.entries,
+                        },
+                    )),
+                }
+            }
+            Some(workerexecutor::list_worker_directory_response::Result::Failure(error)) => {
+                ListWorkerDirectoryResponse {
+                    result: Some(worker::list_worker_directory_response::Result::Error(
+                        WorkerError {
+                            error: Some(worker::worker_error::Error::InternalError(error)),
+                        },
+                    )),
+                }
+            }
+        }
+    }
+
+    async fn get_pending_promises(
+        &self,
+        request: GetPendingPromisesRequest,
+    ) -> GetPendingPromisesResponse {
+        let result = self
+            .worker_executor
+            .client()
+            .await
+            .get_pending_promises(workerexecutor::GetPendingPromisesRequest {
+                worker_id: request.worker_id,
+                account_id: Some(
+                    AccountId {
+                        value: "test-account".to_string(),
+                    }
+                    .into(),
+                ),
+            })
+            .await
+            .expect("Failed to call golem-worker-executor")
+            .into_inner();
+
+        match result.result {
+            None => {
+                panic!("No response from golem-worker-executor get-pending-promises call");
+            }
+            Some(workerexecutor::get_pending_promises_response::Result::Success(success)) => {
+                GetPendingPromisesResponse {
+                    result: Some(worker::get_pending_promises_response::Result::Success(
+                        GetPendingPromisesSuccessResponse {
+                            promises: success.promises,
+                        },
+                    )),
+                }
+            }
+            Some(workerexecutor::get_pending_promises_response::Result::Failure(error)) => {
+                GetPendingPromisesResponse {
+                    result: Some(worker::get_pending_promises_response::Result::Error(
+                        WorkerError {
+                            error: Some(worker::worker_error::Error::InternalError(error)),
+                        },
+                    )),
+                }
+            }
+        }
+    }
+
+    async fn complete_promise(&self, request: CompletePromiseRequest) -> CompletePromiseResponse {
+        let parameters = request
+            .complete_parameters
+            .expect("Requires complete parameters");
+        // JSON values are delivered as they are, but parsing WAVE values needs the payload type
+        let data = match (parameters.json, parameters.wave) {
+            (Some(json), _) => json.into_bytes(),
+            (None, Some(_)) => {
+                panic!("WAVE values can not be forwarded to worker-executor");
+            }
+            (None, None) => parameters.data,
+        };
+
+        let result = self
+            .worker_executor
+            .client()
+            .await
+            .complete_promise(workerexecutor::CompletePromiseRequest {
+                promise_id: Some(PromiseId {
+                    worker_id: request.worker_id,
+                    oplog_idx: parameters.oplog_idx,
+                }),
+                data,
+                account_id: Some(
+                    AccountId {
+                        value: "test-account".to_string(),
+                    }
+                    .into(),
+                ),
+            })
+            .await
+            .expect("Failed to call golem-worker-executor")
+            .into_inner();
+
+        match result.result {
+            None => {
+                panic!("No response from golem-worker-executor complete-promise call");
+            }
+            Some(workerexecutor::complete_promise_response::Result::Success(success)) => {
+                CompletePromiseResponse {
+                    result: Some(worker::complete_promise_response::Result::Success(
+                        success.completed,
+                    )),
+                }
+            }
+            Some(workerexecutor::complete_promise_response::Result::Failure(error)) => {
+                CompletePromiseResponse {
+                    result: Some(worker::complete_promise_response::Result::Error(
+                        WorkerError {
+                            error: Some(worker::worker_error::Error::InternalError(error)),
+                        },
+                    )),
+                }
+            }
+        }
+    }
+
     fn private_host(&self) -> String {
         panic!("No real golem-worker-service, forwarding requests to worker-executor");
     }
@@ -23,11 +23,19 @@ use tracing::Level;
 
 use golem_api_grpc::proto::golem::worker::worker_service_client::WorkerServiceClient;
 use golem_api_grpc::proto::golem::worker::{
-    ConnectWorkerRequest, DeleteWorkerRequest, DeleteWorkerResponse, GetWorkerMetadataRequest,
-    GetWorkerMetadataResponse, GetWorkersMetadataRequest, GetWorkersMetadataResponse,
-    InterruptWorkerRequest, InterruptWorkerResponse, InvokeAndAwaitRequest, InvokeAndAwaitResponse,
-    InvokeRequest, InvokeResponse, LaunchNewWorkerRequest, LaunchNewWorkerResponse, LogEvent,
-    ResumeWorkerRequest, ResumeWorkerResponse, UpdateWorkerRequest, UpdateWorkerResponse,
+    CancelInvocationRequest, CancelInvocationResponse, CancelScheduledInvocationRequest,
+    CancelScheduledInvocationResponse, CompletePromiseRequest, CompletePromiseResponse,
+    ConnectWorkerRequest, DeleteWorkerRequest, DeleteWorkerResponse, ForkWorkerRequest,
+    ForkWorkerResponse, GetOplogRequest, GetOplogResponse, GetPendingPromisesRequest,
+    GetPendingPromisesResponse, GetScheduledInvocationsRequest, GetScheduledInvocationsResponse,
+    GetWorkerMetadataRequest, GetWorkerMetadataResponse, GetWorkersMetadataRequest,
+    GetWorkersMetadataResponse, InterruptWorkerRequest, InterruptWorkerResponse,
+    InvokeAndAwaitRequest, InvokeAndAwaitResponse, InvokeRequest, InvokeResponse,
+    LaunchNewWorkerRequest, LaunchNewWorkerResponse, ListWorkerDirectoryRequest,
+    ListWorkerDirectoryResponse, LogEvent, ResumeWorkerRequest, ResumeWorkerResponse,
+    RevertWorkerRequest, RevertWorkerResponse, ScheduleInvocationRequest,
+    ScheduleInvocationResponse, SearchOplogRequest, SearchOplogResponse, UpdateWorkerRequest,
+    UpdateWorkerResponse,
 };
 
 use crate::components::component_service::ComponentService;
@@ -147,6 +155,123 @@ pub trait WorkerService {
             .into_inner()
     }
 
+    async fn get_oplog(&self, request: GetOplogRequest) -> GetOplogResponse {
+        self.client()
+            .await
+            .get_oplog(request)
+            .await
+            .expect("Failed to call golem-worker-service")
+            .into_inner()
+    }
+
+    async fn search_oplog(&self, request: SearchOplogRequest) -> SearchOplogResponse {
+        self.client()
+            .await
+            .search_oplog(request)
+            .await
+            .expect("Failed to call golem-worker-service")
+            .into_inner()
+    }
+
+    async fn fork_worker(&self, request: ForkWorkerRequest) -> ForkWorkerResponse {
+        self.client()
+            .await
+            .fork_worker(request)
+            .await
+            .expect("Failed to call golem-worker-service")
+            .into_inner()
+    }
+
+    async fn revert_worker(&self, request: RevertWorkerRequest) -> RevertWorkerResponse {
+        self.client()
+            .await
+            .revert_worker(request)
+            .await
+            .expect("Failed to call golem-worker-service")
+            .into_inner()
+    }
+
+    async fn cancel_invocation(
+        &self,
+        request: CancelInvocationRequest,
+    ) -> CancelInvocationResponse {
+        self.client()
+            .await
+            .cancel_invocation(request)
+            .await
+            .expect("Failed to call golem-worker-service")
+            .into_inner()
+    }
+
+    async fn schedule_invocation(
+        &self,
+        request: ScheduleInvocationRequest,
+    ) -> ScheduleInvocationResponse {
+        self.client()
+            .await
+            .schedule_invocation(request)
+            .await
+            .expect("Failed to call golem-worker-service")
+            .into_inner()
+    }
+
+    async fn get_scheduled_invocations(
+        &self,
+        request: GetScheduledInvocationsRequest,
+    ) -> GetScheduledInvocationsResponse {
+        self.client()
+            .await
+            .get_scheduled_invocations(request)
+            .await
+            .expect("Failed to call golem-worker-service")
+            .into_inner()
+    }
+
+    async fn cancel_scheduled_invocation(
+        &self,
+        request: CancelScheduledInvocationRequest,
+    ) -> CancelScheduledInvocationResponse {
+        self.client()
+            .await
+            .cancel_scheduled_invocation(request)
+            .await
+            .expect("Failed to call golem-worker-service")
+            .into_inner()
+    }
+
+    async fn list_worker_directory(
+        &self,
+        request: ListWorkerDirectoryRequest,
+    ) -> ListWorkerDirectoryResponse {
+        self.client()
+            .await
+            .list_worker_directory(request)
+            .await
+            .expect("Failed to call golem-worker-service")
+            .into_inner()
+    }
+
+    async fn get_pending_promises(
+        &self,
+        request: GetPendingPromisesRequest,
+    ) -> GetPendingPromisesResponse {
+        self.client()
+            .await
+            .get_pending_promises(request)
+            .await
+            .expect("Failed to call golem-worker-service")
+            .into_inner()
+    }
+
+    async fn complete_promise(&self, request: CompletePromiseRequest) -> CompletePromiseResponse {
+        self.client()
+            .await
+            .complete_promise(request)
+            .await
+            .expect("Failed to call golem-worker-service")
+            .into_inner()
+    }
+
     fn private_host(&self) -> String;
     fn private_http_port(&self) -> u16;
     fn private_grpc_port(&self) -> u16;
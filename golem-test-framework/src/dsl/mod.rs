@@ -20,20 +20,31 @@ use golem_api_grpc::proto::golem::common::ErrorsBody;
 use golem_api_grpc::proto::golem::worker::update_record::Update;
 use golem_api_grpc::proto::golem::worker::worker_error::Error;
 use golem_api_grpc::proto::golem::worker::{
-    get_worker_metadata_response, get_workers_metadata_response, interrupt_worker_response,
-    invoke_and_await_response, invoke_response, launch_new_worker_response, log_event,
-    resume_worker_response, update_worker_response, worker_execution_error, CallingConvention,
-    ConnectWorkerRequest, DeleteWorkerRequest, GetWorkerMetadataRequest, GetWorkersMetadataRequest,
-    GetWorkersMetadataSuccessResponse, InterruptWorkerRequest, InterruptWorkerResponse,
-    InvokeAndAwaitRequest, InvokeParameters, InvokeRequest, LaunchNewWorkerRequest, LogEvent,
-    ResumeWorkerRequest, StdErrLog, StdOutLog, UpdateMode, UpdateWorkerRequest,
-    UpdateWorkerResponse, WorkerError, WorkerExecutionError,
+    cancel_invocation_response, cancel_scheduled_invocation_response, complete_promise_response,
+    fork_worker_response, get_oplog_response, get_pending_promises_response,
+    get_scheduled_invocations_response, get_worker_metadata_response,
+    get_workers_metadata_response, interrupt_worker_response, invoke_and_await_response,
+    invoke_response, launch_new_worker_response, list_worker_directory_response, log_event,
+    resume_worker_response, revert_worker_response, schedule_invocation_response,
+    search_oplog_response, update_worker_response, worker_execution_error, CallingConvention,
+    CancelInvocationRequest, CancelInvocationStatus, CancelScheduledInvocationRequest,
+    CompleteParameters, CompletePromiseRequest, ConnectWorkerRequest, DeleteWorkerRequest,
+    ForkWorkerRequest, GetOplogRequest, GetPendingPromisesRequest, GetScheduledInvocationsRequest,
+    GetWorkerMetadataRequest, GetWorkersMetadataRequest, GetWorkersMetadataSuccessResponse,
+    InterruptWorkerRequest, InterruptWorkerResponse, InvokeAndAwaitRequest, InvokeParameters,
+    InvokeRequest, LaunchNewWorkerRequest, ListWorkerDirectoryRequest, LogEvent, OplogEntry,
+    OplogFilter, PendingPromise, ResumeWorkerRequest, RevertWorkerRequest,
+    ScheduleInvocationRequest, SearchOplogRequest, StdErrLog, StdOutLog, UpdateMode,
+    UpdateWorkerRequest, UpdateWorkerResponse, WorkerError, WorkerExecutionError, WorkerFileEntry,
+};
+use golem_common::model::oplog::{
+    OplogIndex, RevertWorkerTarget, TimestampedUpdateDescription, UpdateDescription,
 };
-use golem_common::model::oplog::{OplogIndex, TimestampedUpdateDescription, UpdateDescription};
 use golem_common::model::regions::DeletedRegions;
 use golem_common::model::{
-    ComponentId, ComponentVersion, FailedUpdateRecord, IdempotencyKey, ScanCursor,
-    SuccessfulUpdateRecord, WorkerFilter, WorkerId, WorkerMetadata, WorkerStatusRecord,
+    ComponentId, ComponentVersion, FailedUpdateRecord, IdempotencyKey, InvocationSchedule,
+    ScanCursor, ScheduledInvocation, ScheduledInvocationId, SuccessfulUpdateRecord, WorkerFilter,
+    WorkerId, WorkerMetadata, WorkerStatusRecord,
 };
 use golem_wasm_ast::analysis::AnalysisContext;
 use golem_wasm_ast::component::Component;
@@ -149,6 +160,54 @@ pub trait TestDsl {
     async fn simulated_crash(&self, worker_id: &WorkerId);
     async fn auto_update_worker(&self, worker_id: &WorkerId, target_version: ComponentVersion);
     async fn manual_update_worker(&self, worker_id: &WorkerId, target_version: ComponentVersion);
+    async fn get_oplog(&self, worker_id: &WorkerId, from: OplogIndex) -> Vec<OplogEntry>;
+    async fn search_oplog(&self, worker_id: &WorkerId, filter: OplogFilter) -> Vec<OplogEntry>;
+    async fn fork_worker(
+        &self,
+        source_worker_id: &WorkerId,
+        target_worker_name: &str,
+        oplog_index_cutoff: OplogIndex,
+    ) -> Result<WorkerId, Error>;
+    async fn revert_worker(
+        &self,
+        worker_id: &WorkerId,
+        target: RevertWorkerTarget,
+    ) -> Result<(), Error>;
+    async fn cancel_invocation(
+        &self,
+        worker_id: &WorkerId,
+        idempotency_key: &IdempotencyKey,
+    ) -> Result<CancelInvocationStatus, Error>;
+    async fn schedule_invocation(
+        &self,
+        worker_id: &WorkerId,
+        function_name: &str,
+        params: Vec<Value>,
+        schedule: InvocationSchedule,
+    ) -> Result<ScheduledInvocation, Error>;
+    async fn get_scheduled_invocations(
+        &self,
+        worker_id: &WorkerId,
+    ) -> Result<Vec<ScheduledInvocation>, Error>;
+    async fn cancel_scheduled_invocation(
+        &self,
+        worker_id: &WorkerId,
+        id: &ScheduledInvocationId,
+    ) -> Result<bool, Error>;
+    async fn list_worker_directory(
+        &self,
+        worker_id: &WorkerId,
+        path: &str,
+    ) -> Result<Vec<WorkerFileEntry>, Error>;
+    async fn get_pending_promises(
+        &self,
+        worker_id: &WorkerId,
+    ) -> Result<Vec<PendingPromise>, Error>;
+    async fn complete_promise(
+        &self,
+        worker_id: &WorkerId,
+        parameters: CompleteParameters,
+    ) -> Result<bool, Error>;
 }
 
 #[async_trait]
@@ -713,6 +772,311 @@ impl<T: TestDependencies + Send + Sync> TestDsl for T {
             _ => panic!("Failed to update worker: unknown error"),
         }
     }
+
+    async fn get_oplog(&self, worker_id: &WorkerId, from: OplogIndex) -> Vec<OplogEntry> {
+        let mut entries = Vec::new();
+        let mut from = Some(from.into());
+
+        while let Some(from_oplog_index) = from {
+            let response = self
+                .worker_service()
+                .get_oplog(GetOplogRequest {
+                    worker_id: Some(worker_id.clone().into()),
+                    from_oplog_index,
+                    count: 100,
+                })
+                .await;
+
+            match response.result {
+                Some(get_oplog_response::Result::Success(page)) => {
+                    entries.extend(page.entries);
+                    from = page.next;
+                }
+                Some(get_oplog_response::Result::Error(error)) => {
+                    panic!("Failed to get oplog: {error:?}")
+                }
+                None => panic!("Failed to get oplog: unknown error"),
+            }
+        }
+
+        entries
+    }
+
+    async fn search_oplog(&self, worker_id: &WorkerId, filter: OplogFilter) -> Vec<OplogEntry> {
+        let mut entries = Vec::new();
+        let mut cursor = Some(OplogIndex::INITIAL.into());
+
+        while cursor.is_some() {
+            let response = self
+                .worker_service()
+                .search_oplog(SearchOplogRequest {
+                    worker_id: Some(worker_id.clone().into()),
+                    filter: Some(filter.clone()),
+                    cursor,
+                    count: 100,
+                })
+                .await;
+
+            match response.result {
+                Some(search_oplog_response::Result::Success(page)) => {
+                    entries.extend(page.entries);
+                    cursor = page.next;
+                }
+                Some(search_oplog_response::Result::Error(error)) => {
+                    panic!("Failed to search oplog: {error:?}")
+                }
+                None => panic!("Failed to search oplog: unknown error"),
+            }
+        }
+
+        entries
+    }
+
+    async fn fork_worker(
+        &self,
+        source_worker_id: &WorkerId,
+        target_worker_name: &str,
+        oplog_index_cutoff: OplogIndex,
+    ) -> Result<WorkerId, Error> {
+        let target_worker_id = WorkerId {
+            component_id: source_worker_id.component_id.clone(),
+            worker_name: target_worker_name.to_string(),
+        };
+        let response = self
+            .worker_service()
+            .fork_worker(ForkWorkerRequest {
+                source_worker_id: Some(source_worker_id.clone().into()),
+                target_worker_id: Some(target_worker_id.clone().into()),
+                oplog_index_cutoff: oplog_index_cutoff.into(),
+            })
+            .await;
+
+        match response.result {
+            Some(fork_worker_response::Result::Success(_)) => Ok(target_worker_id),
+            Some(fork_worker_response::Result::Error(WorkerError { error: Some(error) })) => {
+                Err(error)
+            }
+            Some(fork_worker_response::Result::Error(_)) => {
+                panic!("Failed to fork worker: unknown error")
+            }
+            None => panic!("No response from fork_worker"),
+        }
+    }
+
+    async fn revert_worker(
+        &self,
+        worker_id: &WorkerId,
+        target: RevertWorkerTarget,
+    ) -> Result<(), Error> {
+        let response = self
+            .worker_service()
+            .revert_worker(RevertWorkerRequest {
+                worker_id: Some(worker_id.clone().into()),
+                target: Some(target.into()),
+            })
+            .await;
+
+        match response.result {
+            Some(revert_worker_response::Result::Success(_)) => Ok(()),
+            Some(revert_worker_response::Result::Error(WorkerError { error: Some(error) })) => {
+                Err(error)
+            }
+            Some(revert_worker_response::Result::Error(_)) => {
+                panic!("Failed to revert worker: unknown error")
+            }
+            None => panic!("No response from revert_worker"),
+        }
+    }
+
+    async fn cancel_invocation(
+        &self,
+        worker_id: &WorkerId,
+        idempotency_key: &IdempotencyKey,
+    ) -> Result<CancelInvocationStatus, Error> {
+        let response = self
+            .worker_service()
+            .cancel_invocation(CancelInvocationRequest {
+                worker_id: Some(worker_id.clone().into()),
+                idempotency_key: Some(idempotency_key.clone().into()),
+            })
+            .await;
+
+        match response.result {
+            Some(cancel_invocation_response::Result::Success(status)) => {
+                Ok(CancelInvocationStatus::try_from(status)
+                    .expect("Invalid status returned by cancel_invocation"))
+            }
+            Some(cancel_invocation_response::Result::Error(WorkerError { error: Some(error) })) => {
+                Err(error)
+            }
+            Some(cancel_invocation_response::Result::Error(_)) => {
+                panic!("Failed to cancel invocation: unknown error")
+            }
+            None => panic!("No response from cancel_invocation"),
+        }
+    }
+
+    async fn schedule_invocation(
+        &self,
+        worker_id: &WorkerId,
+        function_name: &str,
+        params: Vec<Value>,
+        schedule: InvocationSchedule,
+    ) -> Result<ScheduledInvocation, Error> {
+        let response = self
+            .worker_service()
+            .schedule_invocation(ScheduleInvocationRequest {
+                worker_id: Some(worker_id.clone().into()),
+                function: function_name.to_string(),
+                invoke_parameters: Some(InvokeParameters {
+                    params: params.into_iter().map(|v| v.into()).collect(),
+                }),
+                schedule: Some(schedule.into()),
+            })
+            .await;
+
+        match response.result {
+            Some(schedule_invocation_response::Result::Success(invocation)) => Ok(invocation
+                .try_into()
+                .expect("Failed to convert scheduled invocation")),
+            Some(schedule_invocation_response::Result::Error(WorkerError {
+                error: Some(error),
+            })) => Err(error),
+            Some(schedule_invocation_response::Result::Error(_)) => {
+                panic!("Failed to schedule invocation: unknown error")
+            }
+            None => panic!("No response from schedule_invocation"),
+        }
+    }
+
+    async fn get_scheduled_invocations(
+        &self,
+        worker_id: &WorkerId,
+    ) -> Result<Vec<ScheduledInvocation>, Error> {
+        let response = self
+            .worker_service()
+            .get_scheduled_invocations(GetScheduledInvocationsRequest {
+                worker_id: Some(worker_id.clone().into()),
+            })
+            .await;
+
+        match response.result {
+            Some(get_scheduled_invocations_response::Result::Success(success)) => Ok(success
+                .invocations
+                .into_iter()
+                .map(|invocation| {
+                    invocation
+                        .try_into()
+                        .expect("Failed to convert scheduled invocation")
+                })
+                .collect()),
+            Some(get_scheduled_invocations_response::Result::Error(WorkerError {
+                error: Some(error),
+            })) => Err(error),
+            Some(get_scheduled_invocations_response::Result::Error(_)) => {
+                panic!("Failed to get scheduled invocations: unknown error")
+            }
+            None => panic!("No response from get_scheduled_invocations"),
+        }
+    }
+
+    async fn cancel_scheduled_invocation(
+        &self,
+        worker_id: &WorkerId,
+        id: &ScheduledInvocationId,
+    ) -> Result<bool, Error> {
+        let response = self
+            .worker_service()
+            .cancel_scheduled_invocation(CancelScheduledInvocationRequest {
+                worker_id: Some(worker_id.clone().into()),
+                id: Some(id.clone().into()),
+            })
+            .await;
+
+        match response.result {
+            Some(cancel_scheduled_invocation_response::Result::Success(canceled)) => Ok(canceled),
+            Some(cancel_scheduled_invocation_response::Result::Error(WorkerError {
+                error: Some(error),
+            })) => Err(error),
+            Some(cancel_scheduled_invocation_response::Result::Error(_)) => {
+                panic!("Failed to cancel scheduled invocation: unknown error")
+            }
+            None => panic!("No response from cancel_scheduled_invocation"),
+        }
+    }
+
+    async fn list_worker_directory(
+        &self,
+        worker_id: &WorkerId,
+        path: &str,
+    ) -> Result<Vec<WorkerFileEntry>, Error> {
+        let response = self
+            .worker_service()
+            .list_worker_directory(ListWorkerDirectoryRequest {
+                worker_id: Some(worker_id.clone().into()),
+                path: path.to_string(),
+            })
+            .await;
+
+        match response.result {
+            Some(list_worker_directory_response::Result::Success(success)) => Ok(success.entries),
+            Some(list_worker_directory_response::Result::Error(WorkerError {
+                error: Some(error),
+            })) => Err(error),
+            Some(list_worker_directory_response::Result::Error(_)) => {
+                panic!("Failed to list worker directory: unknown error")
+            }
+            None => panic!("No response from list_worker_directory"),
+        }
+    }
+
+    async fn get_pending_promises(
+        &self,
+        worker_id: &WorkerId,
+    ) -> Result<Vec<PendingPromise>, Error> {
+        let response = self
+            .worker_service()
+            .get_pending_promises(GetPendingPromisesRequest {
+                worker_id: Some(worker_id.clone().into()),
+            })
+            .await;
+
+        match response.result {
+            Some(get_pending_promises_response::Result::Success(success)) => Ok(success.promises),
+            Some(get_pending_promises_response::Result::Error(WorkerError {
+                error: Some(error),
+            })) => Err(error),
+            Some(get_pending_promises_response::Result::Error(_)) => {
+                panic!("Failed to get pending promises: unknown error")
+            }
+            None => panic!("No response from get_pending_promises"),
+        }
+    }
+
+    async fn complete_promise(
+        &self,
+        worker_id: &WorkerId,
+        parameters: CompleteParameters,
+    ) -> Result<bool, Error> {
+        let response = self
+            .worker_service()
+            .complete_promise(CompletePromiseRequest {
+                worker_id: Some(worker_id.clone().into()),
+                complete_parameters: Some(parameters),
+            })
+            .await;
+
+        match response.result {
+            Some(complete_promise_response::Result::Success(completed)) => Ok(completed),
+            Some(complete_promise_response::Result::Error(WorkerError { error: Some(error) })) => {
+                Err(error)
+            }
+            Some(complete_promise_response::Result::Error(_)) => {
+                panic!("Failed to complete promise: unknown error")
+            }
+            None => panic!("No response from complete_promise"),
+        }
+    }
 }
 
 pub fn stdout_event(s: &str) -> LogEvent {
@@ -829,6 +1193,9 @@ pub fn worker_error_message(error: &Error) -> String {
                 worker_execution_error::Error::PromiseAlreadyCompleted(error) => {
                     format!("Promise already completed: {:?}", error.promise_id)
                 }
+                worker_execution_error::Error::PromiseTimedOut(error) => {
+                    format!("Promise timed out: {:?}", error.promise_id)
+                }
                 worker_execution_error::Error::Interrupted(error) => {
                     if error.recover_immediately {
                         "Simulated crash".to_string()
@@ -966,6 +1333,7 @@ pub fn to_worker_metadata(
             invocation_results: HashMap::new(),
             current_idempotency_key: None,
             component_version: metadata.component_version,
+            last_snapshot: None, // not passed through gRPC
         },
     }
 }
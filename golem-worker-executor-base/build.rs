@@ -5,12 +5,39 @@ use std::path::Path;
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let golem_wit_root = find_package_root("golem-wit");
     let out_dir = var_os("OUT_DIR").unwrap();
+    let wit_root = Path::new(&out_dir).join("wit");
     let target_file = Path::new(&out_dir).join("preview2_mod.rs");
 
-    std::fs::write(target_file, preview2_mod_gen(&golem_wit_root)).unwrap();
+    // golem:api/host gets extended ahead of the golem-wit releases, so its definition is taken
+    // from this crate, and all the other packages from golem-wit
+    if wit_root.exists() {
+        std::fs::remove_dir_all(&wit_root)?;
+    }
+    copy_dir(&Path::new(&golem_wit_root).join("wit"), &wit_root)?;
+    std::fs::copy(
+        "wit/golem-host.wit",
+        wit_root.join("deps/golem/golem-host.wit"),
+    )?;
+
+    std::fs::write(target_file, preview2_mod_gen(&wit_root.to_string_lossy())).unwrap();
 
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=wit/golem-host.wit");
+
+    Ok(())
+}
 
+fn copy_dir(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(target)?;
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let target = target.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), target)?;
+        }
+    }
     Ok(())
 }
 
@@ -23,10 +50,10 @@ fn find_package_root(name: &str) -> String {
     package.manifest_path.parent().unwrap().to_string()
 }
 
-fn preview2_mod_gen(golem_wit_path: &str) -> String {
+fn preview2_mod_gen(wit_path: &str) -> String {
     format!(
         r#"wasmtime::component::bindgen!({{
-        path: "{golem_wit_path}/wit",
+        path: "{wit_path}",
         interfaces: "
           import golem:api/host@0.2.0;
 
@@ -56,6 +83,7 @@ fn preview2_mod_gen(golem_wit_path: &str) -> String {
             "wasi:keyvalue/types/incoming-value": super::durable_host::keyvalue::types::IncomingValueEntry,
             "wasi:keyvalue/types/outgoing-value": super::durable_host::keyvalue::types::OutgoingValueEntry,
             "golem:api/host/get-workers": super::durable_host::golem::GetWorkersEntry,
+            "golem:rpc/types": golem_wasm_rpc::golem::rpc::types,
         }}
     }});
         "#
@@ -0,0 +1,53 @@
+CREATE TABLE kv_storage
+(
+    namespace TEXT NOT NULL,
+    key       TEXT NOT NULL,
+    value     BLOB NOT NULL,
+    PRIMARY KEY (namespace, key)
+);
+
+CREATE TABLE set_storage
+(
+    namespace TEXT NOT NULL,
+    key       TEXT NOT NULL,
+    value     BLOB NOT NULL,
+    PRIMARY KEY (namespace, key, value)
+);
+
+CREATE TABLE sorted_set_storage
+(
+    namespace TEXT NOT NULL,
+    key       TEXT NOT NULL,
+    value     BLOB NOT NULL,
+    score     REAL NOT NULL,
+    PRIMARY KEY (namespace, key, value)
+);
+
+CREATE INDEX sorted_set_storage_score_idx ON sorted_set_storage (namespace, key, score);
+
+CREATE TABLE index_storage
+(
+    namespace TEXT    NOT NULL,
+    key       TEXT    NOT NULL,
+    id        INTEGER NOT NULL,
+    value     BLOB    NOT NULL,
+    PRIMARY KEY (namespace, key, id)
+);
+
+CREATE TABLE blob_storage
+(
+    namespace        TEXT    NOT NULL,
+    parent           TEXT    NOT NULL,
+    name             TEXT    NOT NULL,
+    value            BLOB    NOT NULL,
+    size             INTEGER NOT NULL,
+    last_modified_at INTEGER NOT NULL,
+    PRIMARY KEY (namespace, parent, name)
+);
+
+CREATE TABLE blob_storage_directories
+(
+    namespace TEXT NOT NULL,
+    path      TEXT NOT NULL,
+    PRIMARY KEY (namespace, path)
+);
@@ -301,6 +301,15 @@ impl<Ctx: WorkerCtx> HostDescriptor for DurableWorkerCtx<Ctx> {
         new_path: String,
     ) -> Result<(), FsError> {
         record_host_function_call("filesystem::types::descriptor", "link_at");
+        // A hard link to a read-only file could be opened for writing
+        let follow = old_path_flags.contains(PathFlags::SYMLINK_FOLLOW);
+        if self.is_read_only(&self_, &old_path, follow, false).await?
+            || self
+                .is_read_only(&new_descriptor, &new_path, false, false)
+                .await?
+        {
+            return Err(ErrorCode::NotPermitted.into());
+        }
         HostDescriptor::link_at(
             &mut self.as_wasi_view(),
             self_,
@@ -321,6 +330,13 @@ impl<Ctx: WorkerCtx> HostDescriptor for DurableWorkerCtx<Ctx> {
         flags: DescriptorFlags,
     ) -> Result<Resource<Descriptor>, FsError> {
         record_host_function_call("filesystem::types::descriptor", "open_at");
+        let writes = flags.contains(DescriptorFlags::WRITE)
+            || flags.contains(DescriptorFlags::MUTATE_DIRECTORY)
+            || open_flags.contains(OpenFlags::TRUNCATE);
+        let follow = path_flags.contains(PathFlags::SYMLINK_FOLLOW);
+        if writes && self.is_read_only(&self_, &path, follow, false).await? {
+            return Err(ErrorCode::NotPermitted.into());
+        }
         HostDescriptor::open_at(
             &mut self.as_wasi_view(),
             self_,
@@ -358,6 +374,14 @@ impl<Ctx: WorkerCtx> HostDescriptor for DurableWorkerCtx<Ctx> {
         new_path: String,
     ) -> Result<(), FsError> {
         record_host_function_call("filesystem::types::descriptor", "rename_at");
+        // Renaming a directory would move the read-only files in it as well
+        if self.is_read_only(&self_, &old_path, false, true).await?
+            || self
+                .is_read_only(&new_descriptor, &new_path, false, false)
+                .await?
+        {
+            return Err(ErrorCode::NotPermitted.into());
+        }
         HostDescriptor::rename_at(
             &mut self.as_wasi_view(),
             self_,
@@ -375,6 +399,9 @@ impl<Ctx: WorkerCtx> HostDescriptor for DurableWorkerCtx<Ctx> {
         new_path: String,
     ) -> Result<(), FsError> {
         record_host_function_call("filesystem::types::descriptor", "symlink_at");
+        if self.is_read_only(&self_, &new_path, false, false).await? {
+            return Err(ErrorCode::NotPermitted.into());
+        }
         HostDescriptor::symlink_at(&mut self.as_wasi_view(), self_, old_path, new_path.clone())
             .await
     }
@@ -385,6 +412,9 @@ impl<Ctx: WorkerCtx> HostDescriptor for DurableWorkerCtx<Ctx> {
         path: String,
     ) -> Result<(), FsError> {
         record_host_function_call("filesystem::types::descriptor", "unlink_file_at");
+        if self.is_read_only(&self_, &path, false, false).await? {
+            return Err(ErrorCode::NotPermitted.into());
+        }
         HostDescriptor::unlink_file_at(&mut self.as_wasi_view(), self_, path.clone()).await
     }
 
@@ -456,6 +486,47 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
     }
 }
 
+impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
+    /// Checks whether a path relative to a descriptor points to a read-only initial file, or with
+    /// `include_parents` to a directory containing one. The last component of the path is only
+    /// resolved if it is a symbolic link that gets followed.
+    async fn is_read_only(
+        &self,
+        descriptor: &Resource<Descriptor>,
+        path: &str,
+        follow: bool,
+        include_parents: bool,
+    ) -> Result<bool, FsError> {
+        if self.read_only_paths.is_empty() {
+            return Ok(false);
+        }
+
+        let base = match self.table.get(descriptor)? {
+            Descriptor::File(f) => f.path.clone(),
+            Descriptor::Dir(d) => d.path.clone(),
+        };
+        let target = base.join(path);
+        let resolved = match (target.parent(), target.file_name()) {
+            (Some(parent), Some(name)) if !follow => match tokio::fs::canonicalize(parent).await {
+                Ok(parent) => parent.join(name),
+                Err(_) => return Ok(false),
+            },
+            _ => match tokio::fs::canonicalize(&target).await {
+                Ok(resolved) => resolved,
+                Err(_) => target,
+            },
+        };
+
+        Ok(self.read_only_paths.iter().any(|read_only| {
+            if include_parents {
+                read_only.starts_with(&resolved)
+            } else {
+                *read_only == resolved
+            }
+        }))
+    }
+}
+
 fn calculate_metadata_hash(meta: &DescriptorStat) -> MetadataHashValue {
     let mut hasher = MetroHash128::new();
 
@@ -14,7 +14,9 @@
 
 use anyhow::anyhow;
 use async_trait::async_trait;
+use chrono::Utc;
 use golem_common::config::RetryConfig;
+use std::ops::Add;
 use std::time::Duration;
 use tracing::debug;
 use uuid::Uuid;
@@ -32,10 +34,20 @@ use crate::preview2::golem;
 use crate::preview2::golem::api::host::{
     ComponentVersion, HostGetWorkers, PersistenceLevel, RetryPolicy, UpdateMode,
 };
+use crate::preview2::wasi::clocks::wall_clock::Datetime;
 use crate::workerctx::WorkerCtx;
 use golem_common::model::oplog::{OplogEntry, OplogIndex, WrappedFunctionType};
 use golem_common::model::regions::OplogRegion;
-use golem_common::model::{ComponentId, OwnedWorkerId, PromiseId, ScanCursor, WorkerId};
+use golem_common::model::{
+    ComponentId, OwnedWorkerId, PromiseId, ScanCursor, ScheduledAction, ScheduledInvocation,
+    ScheduledInvocationId, Timestamp, WorkerId,
+};
+use golem_wasm_rpc::golem::rpc::types::Uri;
+use golem_wasm_rpc::protobuf::{
+    r#type, NameOptionTypePair, NameTypePair, Type, TypeEnum, TypeFlags, TypeList, TypeOption,
+    TypePrimitive, TypeRecord, TypeResult, TypeTuple, TypeVariant,
+};
+use golem_wasm_rpc::{Value, WitValue};
 
 #[async_trait]
 impl<Ctx: WorkerCtx> HostGetWorkers for DurableWorkerCtx<Ctx> {
@@ -129,16 +141,21 @@ impl GetWorkersEntry {
 impl<Ctx: WorkerCtx> golem::api::host::Host for DurableWorkerCtx<Ctx> {
     async fn golem_create_promise(&mut self) -> Result<golem::api::host::PromiseId, anyhow::Error> {
         record_host_function_call("golem::api", "golem_create_promise");
-        let oplog_idx = self.get_oplog_index().await?;
-        Ok(self
-            .public_state
-            .promise_service
-            .create(
-                &self.owned_worker_id.worker_id,
-                OplogIndex::from_u64(oplog_idx),
-            )
+        self.create_promise(None, None).await
+    }
+
+    async fn golem_create_promise_with_options(
+        &mut self,
+        options: golem::api::host::PromiseOptions,
+    ) -> Result<golem::api::host::PromiseId, anyhow::Error> {
+        record_host_function_call("golem::api", "golem_create_promise_with_options");
+        let payload_type = options
+            .payload_type
+            .map(Type::try_from)
+            .transpose()
+            .map_err(|err| anyhow!("Invalid payload type: {err}"))?;
+        self.create_promise(options.expire_after.map(Duration::from_nanos), payload_type)
             .await
-            .into())
     }
 
     async fn golem_await_promise(
@@ -161,6 +178,30 @@ impl<Ctx: WorkerCtx> golem::api::host::Host for DurableWorkerCtx<Ctx> {
         }
     }
 
+    async fn golem_await_promise_result(
+        &mut self,
+        promise_id: golem::api::host::PromiseId,
+    ) -> Result<Result<Vec<u8>, golem::api::host::PromiseError>, anyhow::Error> {
+        record_host_function_call("golem::api", "golem_await_promise_result");
+        let promise_id: PromiseId = promise_id.into();
+        match self
+            .public_state
+            .promise_service
+            .poll(promise_id.clone())
+            .await
+        {
+            Ok(Some(result)) => Ok(Ok(result)),
+            Ok(None) => {
+                debug!("Suspending worker until {} gets completed", promise_id);
+                Err(InterruptKind::Suspend.into())
+            }
+            Err(GolemError::PromiseTimedOut { .. }) => {
+                Ok(Err(golem::api::host::PromiseError::TimedOut))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
     async fn golem_complete_promise(
         &mut self,
         promise_id: golem::api::host::PromiseId,
@@ -206,16 +247,12 @@ impl<Ctx: WorkerCtx> golem::api::host::Host for DurableWorkerCtx<Ctx> {
         .await
     }
 
-    async fn get_self_uri(
-        &mut self,
-        function_name: String,
-    ) -> Result<golem::rpc::types::Uri, anyhow::Error> {
+    async fn get_self_uri(&mut self, function_name: String) -> Result<Uri, anyhow::Error> {
         record_host_function_call("golem::api", "get_self_uri");
-        let uri = golem_wasm_rpc::golem::rpc::types::Uri::golem_uri(
+        Ok(Uri::golem_uri(
             &self.owned_worker_id.worker_id,
             Some(&function_name),
-        );
-        Ok(golem::rpc::types::Uri { value: uri.value })
+        ))
     }
 
     async fn get_oplog_index(&mut self) -> anyhow::Result<golem::api::host::OplogIndex> {
@@ -464,6 +501,148 @@ impl<Ctx: WorkerCtx> golem::api::host::Host for DurableWorkerCtx<Ctx> {
 
         Ok(())
     }
+
+    async fn schedule_invocation(
+        &mut self,
+        target: Uri,
+        function_name: String,
+        function_params: Vec<WitValue>,
+        scheduled_at: Datetime,
+    ) -> anyhow::Result<golem::api::host::ScheduledInvocationId> {
+        record_host_function_call("golem::api", "schedule_invocation");
+
+        let worker_id = match target.parse_as_golem_uri() {
+            Some((worker_id, _)) => worker_id,
+            None => return Err(anyhow!("Invalid URI: {}", target.value)),
+        };
+        let owned_worker_id = OwnedWorkerId::new(&self.owned_worker_id.account_id, &worker_id);
+        let scheduled_at = Timestamp::from(
+            scheduled_at.seconds * 1000 + (scheduled_at.nanoseconds / 1_000_000) as u64,
+        );
+        let function_input = function_params.into_iter().map(Value::from).collect();
+
+        let id = Durability::<Ctx, ScheduledInvocationId, SerializableError>::wrap(
+            self,
+            WrappedFunctionType::WriteRemote,
+            "golem::api::schedule-invocation",
+            |ctx| {
+                Box::pin(async move {
+                    let component_version = ctx
+                        .state
+                        .worker_service
+                        .get(&owned_worker_id)
+                        .await
+                        .map(|metadata| metadata.last_known_status.component_version);
+                    let metadata = ctx
+                        .state
+                        .component_service
+                        .get_metadata(&owned_worker_id.component_id(), component_version)
+                        .await?;
+                    if !metadata.exports_function(&function_name) {
+                        return Err(GolemError::invalid_request(format!(
+                            "Function {function_name} is not exported by component {} version {}",
+                            owned_worker_id.component_id(),
+                            metadata.version
+                        )));
+                    }
+
+                    // Invocations scheduled in the past are executed as soon as possible
+                    let invocation = ScheduledInvocation {
+                        id: ScheduledInvocationId::new_v4(),
+                        scheduled_at: scheduled_at.max(Timestamp::now_utc()),
+                        full_function_name: function_name,
+                        function_input,
+                        cron: None,
+                    };
+                    let id = invocation.id.clone();
+                    ctx.state
+                        .scheduler_service
+                        .schedule_invocation(&owned_worker_id, invocation)
+                        .await;
+                    Ok::<ScheduledInvocationId, GolemError>(id)
+                })
+            },
+        )
+        .await?;
+
+        Ok(golem::api::host::ScheduledInvocationId {
+            worker_id: worker_id.into(),
+            uuid: id.0.into(),
+        })
+    }
+
+    async fn cancel_scheduled_invocation(
+        &mut self,
+        id: golem::api::host::ScheduledInvocationId,
+    ) -> anyhow::Result<bool> {
+        record_host_function_call("golem::api", "cancel_scheduled_invocation");
+
+        let worker_id: WorkerId = id.worker_id.into();
+        let owned_worker_id = OwnedWorkerId::new(&self.owned_worker_id.account_id, &worker_id);
+        let id = ScheduledInvocationId(id.uuid.into());
+
+        let canceled = Durability::<Ctx, bool, SerializableError>::wrap(
+            self,
+            WrappedFunctionType::WriteRemote,
+            "golem::api::cancel-scheduled-invocation",
+            |ctx| {
+                Box::pin(async move {
+                    Ok::<bool, GolemError>(
+                        ctx.state
+                            .scheduler_service
+                            .cancel_scheduled_invocation(&owned_worker_id, &id)
+                            .await,
+                    )
+                })
+            },
+        )
+        .await?;
+
+        Ok(canceled)
+    }
+}
+
+impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
+    /// Creates a promise expiring after the given duration, or after the default one of the
+    /// executor if it is not given
+    async fn create_promise(
+        &mut self,
+        expire_after: Option<Duration>,
+        payload_type: Option<Type>,
+    ) -> Result<golem::api::host::PromiseId, anyhow::Error> {
+        let oplog_idx = golem::api::host::Host::get_oplog_index(self).await?;
+        let expires_at = expire_after
+            .or(self.state.config.promises.expire_after)
+            .map(|expire_after| Utc::now().add(expire_after));
+        let promise_id = self
+            .public_state
+            .promise_service
+            .create(
+                &self.owned_worker_id.worker_id,
+                OplogIndex::from_u64(oplog_idx),
+                expires_at.map(|expires_at| Timestamp::from(expires_at.timestamp_millis() as u64)),
+                payload_type,
+            )
+            .await;
+
+        // The expiration is only scheduled once, when the promise gets created by the live worker
+        if let Some(expires_at) = expires_at {
+            if self.state.is_live() {
+                self.state
+                    .scheduler_service
+                    .schedule(
+                        expires_at,
+                        ScheduledAction::ExpirePromise {
+                            account_id: self.owned_worker_id.account_id(),
+                            promise_id: promise_id.clone(),
+                        },
+                    )
+                    .await;
+            }
+        }
+
+        Ok(promise_id.into())
+    }
 }
 
 impl From<WorkerId> for golem::api::host::WorkerId {
@@ -524,6 +703,94 @@ impl From<golem::api::host::PromiseId> for PromiseId {
     }
 }
 
+impl TryFrom<golem::api::host::WitType> for Type {
+    type Error = String;
+
+    fn try_from(value: golem::api::host::WitType) -> Result<Self, Self::Error> {
+        wit_type_node_to_type(&value.nodes, 0, 0)
+    }
+}
+
+fn wit_type_node_to_type(
+    nodes: &[golem::api::host::WitTypeNode],
+    index: i32,
+    depth: usize,
+) -> Result<Type, String> {
+    use golem::api::host::WitTypeNode;
+
+    // A type can not be nested deeper than the number of its nodes, unless the nodes form a cycle
+    if depth > nodes.len() {
+        return Err("Type nodes must not form a cycle".to_string());
+    }
+    let node = usize::try_from(index)
+        .ok()
+        .and_then(|index| nodes.get(index))
+        .ok_or(format!("Invalid type node index {index}"))?;
+    let inner = |index: i32| wit_type_node_to_type(nodes, index, depth + 1);
+    let primitive = |primitive: i32| r#type::Type::Primitive(TypePrimitive { primitive });
+
+    let typ = match node {
+        WitTypeNode::RecordType(fields) => r#type::Type::Record(TypeRecord {
+            fields: fields
+                .iter()
+                .map(|(name, index)| {
+                    Ok(NameTypePair {
+                        name: name.clone(),
+                        typ: Some(inner(*index)?),
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+        }),
+        WitTypeNode::VariantType(cases) => r#type::Type::Variant(TypeVariant {
+            cases: cases
+                .iter()
+                .map(|(name, index)| {
+                    Ok(NameOptionTypePair {
+                        name: name.clone(),
+                        typ: index.map(inner).transpose()?,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+        }),
+        WitTypeNode::EnumType(names) => r#type::Type::Enum(TypeEnum {
+            names: names.clone(),
+        }),
+        WitTypeNode::FlagsType(names) => r#type::Type::Flags(TypeFlags {
+            names: names.clone(),
+        }),
+        WitTypeNode::TupleType(indices) => r#type::Type::Tuple(TypeTuple {
+            elems: indices
+                .iter()
+                .map(|index| inner(*index))
+                .collect::<Result<_, String>>()?,
+        }),
+        WitTypeNode::ListType(index) => r#type::Type::List(Box::new(TypeList {
+            elem: Some(Box::new(inner(*index)?)),
+        })),
+        WitTypeNode::OptionType(index) => r#type::Type::Option(Box::new(TypeOption {
+            elem: Some(Box::new(inner(*index)?)),
+        })),
+        WitTypeNode::ResultType((ok, err)) => r#type::Type::Result(Box::new(TypeResult {
+            ok: ok.map(inner).transpose()?.map(Box::new),
+            err: err.map(inner).transpose()?.map(Box::new),
+        })),
+        WitTypeNode::PrimBoolType => primitive(0),
+        WitTypeNode::PrimS8Type => primitive(1),
+        WitTypeNode::PrimU8Type => primitive(2),
+        WitTypeNode::PrimS16Type => primitive(3),
+        WitTypeNode::PrimU16Type => primitive(4),
+        WitTypeNode::PrimS32Type => primitive(5),
+        WitTypeNode::PrimU32Type => primitive(6),
+        WitTypeNode::PrimS64Type => primitive(7),
+        WitTypeNode::PrimU64Type => primitive(8),
+        WitTypeNode::PrimF32Type => primitive(9),
+        WitTypeNode::PrimF64Type => primitive(10),
+        WitTypeNode::PrimCharType => primitive(11),
+        WitTypeNode::PrimStringType => primitive(12),
+    };
+    Ok(Type { r#type: Some(typ) })
+}
+
 impl From<&RetryConfig> for RetryPolicy {
     fn from(value: &RetryConfig) -> Self {
         Self {
@@ -556,6 +823,12 @@ impl From<Uuid> for golem::api::host::Uuid {
     }
 }
 
+impl From<golem::api::host::Uuid> for Uuid {
+    fn from(uuid: golem::api::host::Uuid) -> Self {
+        Uuid::from_u64_pair(uuid.high_bits, uuid.low_bits)
+    }
+}
+
 impl From<golem::api::host::FilterComparator> for golem_common::model::FilterComparator {
     fn from(value: golem::api::host::FilterComparator) -> Self {
         match value {
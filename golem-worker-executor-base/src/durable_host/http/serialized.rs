@@ -19,7 +19,8 @@ use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
-use crate::durable_host::serialized::SerializableError;
+use crate::durable_host::serialized::{IntoValue, SerializableError};
+use golem_wasm_rpc::Value;
 use wasmtime_wasi_http::bindings::http::types::{
     DnsErrorPayload, ErrorCode, FieldSizePayload, TlsAlertReceivedPayload,
 };
@@ -394,6 +395,33 @@ impl From<SerializableErrorCode> for ErrorCode {
     }
 }
 
+impl IntoValue for SerializableResponse {
+    fn into_value(self) -> Value {
+        let (case_idx, case_value) = match self {
+            SerializableResponse::Pending => (0, None),
+            SerializableResponse::HeadersReceived(headers) => (1, Some(headers.into_value())),
+            SerializableResponse::HttpError(error_code) => (2, Some(error_code.into_value())),
+            SerializableResponse::InternalError(error) => (3, Some(error.into_value())),
+        };
+        Value::Variant {
+            case_idx,
+            case_value: case_value.map(Box::new),
+        }
+    }
+}
+
+impl IntoValue for SerializableResponseHeaders {
+    fn into_value(self) -> Value {
+        Value::Record(vec![self.status.into_value(), self.headers.into_value()])
+    }
+}
+
+impl IntoValue for SerializableErrorCode {
+    fn into_value(self) -> Value {
+        Value::String(format!("{self:?}"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::durable_host::http::serialized::{SerializableErrorCode, SerializedHttpVersion};
@@ -0,0 +1,151 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of the responses the durable host functions record in the oplog, used by the
+//! oplog inspection and search APIs.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bincode::Decode;
+use golem_common::model::oplog::OplogEntry;
+use golem_common::model::ScheduledInvocationId;
+use golem_wasm_rpc::{Value, WitValue};
+
+use crate::durable_host::http::serialized::{SerializableErrorCode, SerializableResponse};
+use crate::durable_host::serialized::{
+    IntoValue, SerializableDateTime, SerializableError, SerializableFileTimes,
+    SerializableIpAddresses, SerializableStreamError,
+};
+use crate::services::blob_store::ObjectMetadata;
+use crate::services::oplog::{Oplog, OplogOps};
+
+type HostResult<T> = Result<T, SerializableError>;
+
+type StreamResult<T> = Result<T, SerializableStreamError>;
+
+type FutureTrailers =
+    Option<Result<Result<Option<HashMap<String, Vec<u8>>>, SerializableErrorCode>, ()>>;
+
+/// Decodes the response recorded by an `ImportedFunctionInvoked` entry, based on the type the
+/// function with the entry's name records.
+///
+/// Returns `None` for entries of other types, and for functions whose response type is not known.
+pub async fn decode_imported_function_response(
+    oplog: &Arc<dyn Oplog + Send + Sync>,
+    entry: &OplogEntry,
+) -> Result<Option<Value>, String> {
+    let function_name = match entry {
+        OplogEntry::ImportedFunctionInvoked { function_name, .. } => function_name.as_str(),
+        _ => return Ok(None),
+    };
+
+    match function_name {
+        "golem blobstore::container::get_data"
+        | "golem random::get_random_bytes"
+        | "golem random::insecure::get_insecure_random_bytes" => {
+            decode::<HostResult<Vec<u8>>>(oplog, entry).await
+        }
+        "golem blobstore::container::write_data"
+        | "golem blobstore::container::delete_object"
+        | "golem blobstore::container::delete_objects"
+        | "golem blobstore::container::clear"
+        | "golem blobstore::blobstore::delete_container"
+        | "golem blobstore::blobstore::copy_object"
+        | "golem blobstore::blobstore::move_object"
+        | "golem keyvalue::eventual::set"
+        | "golem keyvalue::eventual::delete"
+        | "golem keyvalue::eventual_batch::set_many"
+        | "golem keyvalue::eventual_batch::delete_many"
+        | "golem_delete_promise"
+        | "golem::api::update-worker"
+        | "golem::rpc::wasm-rpc::invoke" => decode::<HostResult<()>>(oplog, entry).await,
+        "golem blobstore::container::list_objects"
+        | "golem keyvalue::eventual_batch::get_keys"
+        | "cli::preopens::get_directories" => decode::<HostResult<Vec<String>>>(oplog, entry).await,
+        "golem blobstore::container::has_object"
+        | "golem blobstore::blobstore::container_exists"
+        | "golem keyvalue::eventual::exists"
+        | "golem_complete_promise"
+        | "golem::api::cancel-scheduled-invocation" => {
+            decode::<HostResult<bool>>(oplog, entry).await
+        }
+        "golem blobstore::container::object_info" => {
+            decode::<HostResult<ObjectMetadata>>(oplog, entry).await
+        }
+        "golem blobstore::blobstore::create_container"
+        | "golem random::get_random_u64"
+        | "golem random::insecure::get_insecure_random_u64"
+        | "monotonic_clock::now"
+        | "monotonic_clock::resolution" => decode::<HostResult<u64>>(oplog, entry).await,
+        "golem blobstore::blobstore::get_container" => {
+            decode::<HostResult<Option<u64>>>(oplog, entry).await
+        }
+        "golem keyvalue::eventual::get" => {
+            decode::<HostResult<Option<Vec<u8>>>>(oplog, entry).await
+        }
+        "golem keyvalue::eventual_batch::get_many" => {
+            decode::<HostResult<Vec<Option<Vec<u8>>>>>(oplog, entry).await
+        }
+        "golem random::insecure_seed::insecure_seed"
+        | "golem api::generate_idempotency_key"
+        | "golem::rpc::wasm-rpc::invoke-and-await idempotency key" => {
+            decode::<HostResult<(u64, u64)>>(oplog, entry).await
+        }
+        "golem::api::schedule-invocation" => {
+            decode::<HostResult<ScheduledInvocationId>>(oplog, entry).await
+        }
+        "golem::rpc::wasm-rpc::invoke-and-await" => {
+            decode::<HostResult<WitValue>>(oplog, entry).await
+        }
+        "golem_environment::get_environment" => {
+            decode::<HostResult<Vec<(String, String)>>>(oplog, entry).await
+        }
+        "wall_clock::now" | "wall_clock::resolution" => {
+            decode::<HostResult<SerializableDateTime>>(oplog, entry).await
+        }
+        "filesystem::types::descriptor::stat" | "filesystem::types::descriptor::stat_at" => {
+            decode::<HostResult<SerializableFileTimes>>(oplog, entry).await
+        }
+        "sockets::ip_name_lookup::resolve_addresses" => {
+            decode::<HostResult<SerializableIpAddresses>>(oplog, entry).await
+        }
+        "golem http::types::future_trailers::get" => {
+            decode::<HostResult<FutureTrailers>>(oplog, entry).await
+        }
+        "http::types::future_incoming_response::get" => {
+            decode::<SerializableResponse>(oplog, entry).await
+        }
+        "http::types::incoming_body_stream::read"
+        | "http::types::incoming_body_stream::blocking_read" => {
+            decode::<StreamResult<Vec<u8>>>(oplog, entry).await
+        }
+        "http::types::incoming_body_stream::skip"
+        | "http::types::incoming_body_stream::blocking_skip" => {
+            decode::<StreamResult<u64>>(oplog, entry).await
+        }
+        // Also recorded by initial_cwd with a different response type, so the response of
+        // get_arguments cannot be told apart from it
+        "golem_environment::get_arguments" => Ok(None),
+        _ => Ok(None),
+    }
+}
+
+async fn decode<T: Decode + IntoValue>(
+    oplog: &Arc<dyn Oplog + Send + Sync>,
+    entry: &OplogEntry,
+) -> Result<Option<Value>, String> {
+    let response: Option<T> = oplog.get_payload_of_entry(entry).await?;
+    Ok(response.map(IntoValue::into_value))
+}
@@ -15,27 +15,31 @@
 // WASI Host implementation for Golem, delegating to the core WASI implementation (wasmtime_wasi)
 // implementing the Golem specific instrumentation on top of it.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::Add;
+use std::path::{Path, PathBuf};
 use std::string::FromUtf8Error;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
 use crate::error::GolemError;
 use crate::invocation::invoke_worker;
 use crate::model::{
-    CurrentResourceLimits, ExecutionStatus, InterruptKind, LastError, LookupResult,
+    CurrentResourceLimits, ExecutionStatus, InitialFile, InterruptKind, LastError, LookupResult,
     PersistenceLevel, TrapType, WorkerConfig,
 };
 use crate::services::active_workers::ActiveWorkers;
 use crate::services::blob_store::BlobStoreService;
-use crate::services::golem_config::GolemConfig;
+use crate::services::component::ComponentService;
+use crate::services::golem_config::{GolemConfig, RetentionPolicy, SnapshotPolicy};
 use crate::services::key_value::KeyValueService;
 use crate::services::promise::PromiseService;
 use crate::services::worker::WorkerService;
 use crate::services::worker_event::WorkerEventService;
+use crate::services::worker_file_system::WorkerFileSystemService;
 use crate::services::{worker_enumeration, HasAll, HasInvocationQueue, HasOplog};
 use crate::wasi_host::managed_stdio::ManagedStandardIo;
 use crate::workerctx::{
@@ -83,6 +87,7 @@ mod clocks;
 mod filesystem;
 pub mod golem;
 mod http;
+pub mod inspect;
 pub mod io;
 pub mod keyvalue;
 mod logging;
@@ -105,9 +110,66 @@ pub struct DurableWorkerCtx<Ctx: WorkerCtx> {
     pub owned_worker_id: OwnedWorkerId,
     pub public_state: PublicDurableWorkerState<Ctx>,
     state: PrivateDurableWorkerState<Ctx>,
-    #[allow(unused)] // note: need to keep reference to it to keep the temp dir alive
     temp_dir: Arc<TempDir>,
     execution_status: Arc<RwLock<ExecutionStatus>>,
+    file_system_synced: AtomicBool,
+    /// The canonical paths of the read-only initial files, which the worker cannot modify
+    read_only_paths: HashSet<PathBuf>,
+}
+
+// Copies the initial files of the component into the worker's file system. A file system restored
+// from a snapshot already contains them as they were when the snapshot was taken, so only the
+// read-only permissions, which are not part of the snapshot, are applied again.
+async fn write_initial_files(
+    root: &Path,
+    files: &[InitialFile],
+    restored: bool,
+) -> Result<(), GolemError> {
+    for file in files {
+        let path = root.join(&file.path);
+        let error = |e: std::io::Error| {
+            GolemError::runtime(format!(
+                "Failed to write the initial file {}: {e}",
+                file.path
+            ))
+        };
+
+        if !restored {
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await.map_err(error)?;
+            }
+            tokio::fs::write(&path, &file.content)
+                .await
+                .map_err(error)?;
+        }
+
+        if file.read_only && tokio::fs::try_exists(&path).await.map_err(error)? {
+            let mut permissions = tokio::fs::metadata(&path)
+                .await
+                .map_err(error)?
+                .permissions();
+            permissions.set_readonly(true);
+            tokio::fs::set_permissions(&path, permissions)
+                .await
+                .map_err(error)?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn read_only_paths(
+    root: &Path,
+    files: &[InitialFile],
+) -> Result<HashSet<PathBuf>, GolemError> {
+    let root = tokio::fs::canonicalize(root)
+        .await
+        .map_err(|e| GolemError::runtime(format!("Failed to resolve {root:?}: {e}")))?;
+    Ok(files
+        .iter()
+        .filter(|file| file.read_only)
+        .map(|file| root.join(&file.path))
+        .collect())
 }
 
 impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
@@ -121,6 +183,7 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
         >,
         key_value_service: Arc<dyn KeyValueService + Send + Sync>,
         blob_store_service: Arc<dyn BlobStoreService + Send + Sync>,
+        worker_file_system_service: Arc<dyn WorkerFileSystemService + Send + Sync>,
         event_service: Arc<dyn WorkerEventService + Send + Sync>,
         active_workers: Arc<ActiveWorkers<Ctx>>,
         oplog_service: Arc<dyn OplogService + Send + Sync>,
@@ -130,6 +193,7 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
         recovery_management: Arc<dyn RecoveryManagement + Send + Sync>,
         rpc: Arc<dyn Rpc + Send + Sync>,
         worker_proxy: Arc<dyn WorkerProxy + Send + Sync>,
+        component_service: Arc<dyn ComponentService + Send + Sync>,
         config: Arc<GolemConfig>,
         worker_config: WorkerConfig,
         execution_status: Arc<RwLock<ExecutionStatus>>,
@@ -141,6 +205,32 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
             "Created temporary file system root at {:?}",
             temp_dir.path()
         );
+
+        // Recovery replays the oplog entries following the last snapshot on top of the files
+        // synced when the snapshot was saved
+        let mut restored = false;
+        if let Some(last_snapshot) = worker_config.last_snapshot {
+            if !worker_config
+                .deleted_regions
+                .is_in_deleted_region(last_snapshot)
+            {
+                restored = worker_file_system_service
+                    .restore(&owned_worker_id, last_snapshot, temp_dir.path())
+                    .await
+                    .map_err(|e| {
+                        GolemError::runtime(format!("Failed to restore the file system: {e}"))
+                    })?;
+                if !restored {
+                    return Err(GolemError::runtime(format!(
+                        "No file system was synced with the snapshot at oplog index {last_snapshot}"
+                    )));
+                }
+            }
+        }
+        write_initial_files(temp_dir.path(), &worker_config.initial_files, restored).await?;
+        let read_only_paths =
+            read_only_paths(temp_dir.path(), &worker_config.initial_files).await?;
+
         let root_dir = cap_std::fs::Dir::open_ambient_dir(temp_dir.path(), ambient_authority())
             .map_err(|e| GolemError::runtime(format!("Failed to open temporary directory: {e}")))?;
 
@@ -179,6 +269,7 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
                         managed_stdio: stdio,
                         invocation_queue,
                         oplog: oplog.clone(),
+                        temp_dir: temp_dir.clone(),
                     },
                     state: PrivateDurableWorkerState::new(
                         oplog_service,
@@ -189,17 +280,22 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
                         worker_enumeration_service,
                         key_value_service,
                         blob_store_service,
+                        worker_file_system_service,
                         config.clone(),
                         owned_worker_id.clone(),
                         active_workers.clone(),
                         recovery_management,
                         rpc,
                         worker_proxy,
+                        component_service,
                         worker_config.deleted_regions.clone(),
+                        worker_config.last_snapshot,
                         last_oplog_index,
                     ),
                     temp_dir,
                     execution_status,
+                    file_system_synced: AtomicBool::new(false),
+                    read_only_paths,
                 }
             },
         )
@@ -210,6 +306,48 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
         &self.public_state
     }
 
+    /// Persists the worker's files when it leaves the memory, so they can be inspected without
+    /// activating the worker. The files of a worker still replaying its oplog are incomplete, so
+    /// they are not synced.
+    async fn sync_latest_file_system(&self) {
+        if self.state.is_replay() || self.file_system_synced.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let oplog_index = self.public_state.oplog.current_oplog_index().await;
+        if let Err(err) = self
+            .state
+            .worker_file_system_service
+            .sync_latest(&self.owned_worker_id, oplog_index, self.temp_dir.path())
+            .await
+        {
+            warn!("Failed to sync the worker file system: {err}");
+        }
+    }
+
+    /// Finds the index of the snapshot entry added when the last index was expected to be
+    /// `expected_idx`
+    async fn find_snapshot_entry(
+        oplog: &Arc<dyn Oplog + Send + Sync>,
+        expected_idx: OplogIndex,
+    ) -> Result<OplogIndex, String> {
+        let last_idx = oplog.current_oplog_index().await;
+        if last_idx == expected_idx {
+            return Ok(expected_idx);
+        }
+
+        let mut idx = expected_idx;
+        while idx <= last_idx {
+            if let OplogEntry::Snapshot { .. } = oplog.read(idx).await {
+                return Ok(idx);
+            }
+            idx = idx.next();
+        }
+        Err(format!(
+            "Snapshot entry not found between oplog index {expected_idx} and {last_idx}"
+        ))
+    }
+
     pub fn worker_id(&self) -> &WorkerId {
         &self.owned_worker_id.worker_id
     }
@@ -330,19 +468,79 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
             || status == WorkerStatus::Failed
             || status == WorkerStatus::Exited
         {
-            debug!("Scheduling oplog archive");
-            let at = Utc::now().add(self.state.config.oplog.archive_interval);
-            self.state
-                .scheduler_service
-                .schedule(
-                    at,
-                    ScheduledAction::ArchiveOplog {
-                        owned_worker_id: self.owned_worker_id.clone(),
-                        last_oplog_index: self.public_state.oplog.current_oplog_index().await,
-                        next_after: self.state.config.oplog.archive_interval,
-                    },
-                )
-                .await;
+            let last_oplog_index = self.public_state.oplog.current_oplog_index().await;
+            let retention = match status {
+                WorkerStatus::Failed | WorkerStatus::Exited => self.retention_policy().await,
+                _ => RetentionPolicy::default(),
+            };
+
+            match retention.archive_failed_after {
+                Some(archive_after) if status == WorkerStatus::Failed => {
+                    debug!("Scheduling oplog archive to the last layer after {archive_after:?}");
+                    self.state
+                        .scheduler_service
+                        .schedule(
+                            Utc::now().add(archive_after),
+                            ScheduledAction::ArchiveOplogToLastLayer {
+                                owned_worker_id: self.owned_worker_id.clone(),
+                                last_oplog_index,
+                            },
+                        )
+                        .await;
+                }
+                _ => {
+                    debug!("Scheduling oplog archive");
+                    self.state
+                        .scheduler_service
+                        .schedule(
+                            Utc::now().add(self.state.config.oplog.archive_interval),
+                            ScheduledAction::ArchiveOplog {
+                                owned_worker_id: self.owned_worker_id.clone(),
+                                last_oplog_index,
+                                next_after: self.state.config.oplog.archive_interval,
+                            },
+                        )
+                        .await;
+                }
+            }
+
+            let delete_after = match status {
+                WorkerStatus::Exited => retention.delete_exited_after,
+                WorkerStatus::Failed => retention.delete_failed_after,
+                _ => None,
+            };
+            if let Some(delete_after) = delete_after {
+                debug!("Scheduling worker deletion after {delete_after:?}");
+                self.state
+                    .scheduler_service
+                    .schedule(
+                        Utc::now().add(delete_after),
+                        ScheduledAction::DeleteWorker {
+                            owned_worker_id: self.owned_worker_id.clone(),
+                            last_oplog_index,
+                        },
+                    )
+                    .await;
+            }
+        }
+    }
+
+    /// Gets the retention policy stored with the worker's component, falling back to the
+    /// executor's default policy if it has none or it cannot be retrieved
+    async fn retention_policy(&self) -> RetentionPolicy {
+        let component_id = self.owned_worker_id.component_id();
+        match self
+            .state
+            .component_service
+            .get_retention_policy(&component_id)
+            .await
+        {
+            Ok(Some(policy)) => policy,
+            Ok(None) => self.state.config.retention.default.clone(),
+            Err(err) => {
+                warn!("Failed to get the retention policy of component {component_id}, using the default: {err}");
+                self.state.config.retention.default.clone()
+            }
         }
     }
 
@@ -384,6 +582,78 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
 }
 
 impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> DurableWorkerCtx<Ctx> {
+    /// Loads the worker's latest user-defined snapshot, if it has any, by calling its
+    /// `load-snapshot` export. The oplog entries preceding the snapshot are not replayed.
+    async fn load_last_snapshot(
+        instance: &Instance,
+        store: &mut (impl AsContextMut<Data = Ctx> + Send),
+    ) -> Result<(), GolemError> {
+        let (last_snapshot, oplog) = {
+            let state = &store.as_context().data().durable_ctx().state;
+            match state.last_snapshot {
+                // A snapshot in a deleted region is ignored, for example when it is superseded by a
+                // pending manual update
+                Some(last_snapshot)
+                    if !state.deleted_regions.is_in_deleted_region(last_snapshot) =>
+                {
+                    (last_snapshot, state.oplog.clone())
+                }
+                _ => return Ok(()),
+            }
+        };
+
+        debug!("Loading worker snapshot from oplog index {last_snapshot}");
+        let entry = oplog.read(last_snapshot).await;
+        let data = oplog
+            .get_snapshot_payload(&entry)
+            .await
+            .map_err(|err| {
+                GolemError::runtime(format!("Failed to read the worker snapshot: {err}"))
+            })?
+            .ok_or_else(|| GolemError::unexpected_oplog_entry("Snapshot", format!("{entry:?}")))?;
+
+        store
+            .as_context_mut()
+            .data_mut()
+            .begin_call_snapshotting_function();
+        let load_result = invoke_worker(
+            "golem:api/load-snapshot@0.2.0/load".to_string(),
+            vec![Value::List(data.iter().map(|b| Value::U8(*b)).collect())],
+            store,
+            instance,
+            CallingConvention::Component,
+            false,
+        )
+        .await;
+        store
+            .as_context_mut()
+            .data_mut()
+            .end_call_snapshotting_function();
+
+        match load_result {
+            Some(Ok(value)) => match value.as_slice() {
+                [Value::Result(Err(Some(error)))] => match &**error {
+                    Value::String(error) => Err(GolemError::runtime(format!(
+                        "Failed to load the worker snapshot: {error}"
+                    ))),
+                    _ => Err(GolemError::runtime(
+                        "Unexpected result value from the snapshot load function",
+                    )),
+                },
+                [Value::Result(_)] => Ok(()),
+                _ => Err(GolemError::runtime(
+                    "Unexpected result value from the snapshot load function",
+                )),
+            },
+            Some(Err(error)) => Err(GolemError::runtime(format!(
+                "Failed to load the worker snapshot: {error}"
+            ))),
+            None => Err(GolemError::runtime(
+                "The worker could not finish loading its snapshot",
+            )),
+        }
+    }
+
     /// Records the result of an automatic update, if any was active, and returns whether the worker
     /// should be restarted to retry recovering without the pending update.
     pub async fn finalize_pending_update(
@@ -599,12 +869,42 @@ impl<Ctx: WorkerCtx> StatusManagement for DurableWorkerCtx<Ctx> {
 
     async fn deactivate(&self) {
         debug!("deactivating worker");
+        self.sync_latest_file_system().await;
         self.state
             .active_workers
             .remove(&self.owned_worker_id.worker_id);
     }
 }
 
+// Idle workers leave the memory without getting deactivated, so their files are synced in the
+// background once they are dropped
+impl<Ctx: WorkerCtx> Drop for DurableWorkerCtx<Ctx> {
+    fn drop(&mut self) {
+        if self.state.is_replay() || self.file_system_synced.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let worker_file_system_service = self.state.worker_file_system_service.clone();
+            let oplog = self.public_state.oplog.clone();
+            let owned_worker_id = self.owned_worker_id.clone();
+            let temp_dir = self.temp_dir.clone();
+            handle.spawn(
+                async move {
+                    let oplog_index = oplog.current_oplog_index().await;
+                    if let Err(err) = worker_file_system_service
+                        .sync_latest(&owned_worker_id, oplog_index, temp_dir.path())
+                        .await
+                    {
+                        warn!("Failed to sync the worker file system: {err}");
+                    }
+                }
+                .in_current_span(),
+            );
+        }
+    }
+}
+
 #[async_trait]
 impl<Ctx: WorkerCtx> InvocationHooks for DurableWorkerCtx<Ctx> {
     type FailurePayload = Option<OplogIndex>;
@@ -752,7 +1052,7 @@ impl<Ctx: WorkerCtx> InvocationHooks for DurableWorkerCtx<Ctx> {
                         .await;
                 }
             }
-        } else {
+        } else if self.state.snapshotting_mode.is_none() {
             let response = self
                 .state
                 .get_oplog_entry_exported_function_completed()
@@ -854,6 +1154,82 @@ impl<Ctx: WorkerCtx> UpdateManagement for DurableWorkerCtx<Ctx> {
         })
         .await;
     }
+
+    async fn is_snapshot_due(&self) -> bool {
+        if !self.state.is_live() {
+            return false;
+        }
+        match &self.state.config.snapshot.policy {
+            SnapshotPolicy::Disabled => false,
+            SnapshotPolicy::OplogSize(config) => {
+                let last_snapshot: u64 = self
+                    .state
+                    .last_snapshot
+                    .unwrap_or(OplogIndex::INITIAL)
+                    .into();
+                let current: u64 = self.state.oplog.current_oplog_index().await.into();
+                current.saturating_sub(last_snapshot) >= config.entries
+            }
+            SnapshotPolicy::Periodic(config) => {
+                self.state.last_snapshot_at.elapsed() >= config.period
+            }
+        }
+    }
+
+    async fn on_snapshot_saved(&mut self, snapshot: &[u8]) -> Result<(), String> {
+        let oplog = self.state.oplog.clone();
+
+        // The entries creating the worker's files are not replayed anymore, so the files are
+        // persisted before the snapshot gets committed
+        let expected_idx = oplog.current_oplog_index().await.next();
+        self.state
+            .worker_file_system_service
+            .sync(&self.owned_worker_id, expected_idx, self.temp_dir.path())
+            .await?;
+
+        oplog.add_snapshot(snapshot).await?;
+        oplog.commit().await;
+        let snapshot_idx = Self::find_snapshot_entry(&oplog, expected_idx).await?;
+        if snapshot_idx != expected_idx {
+            // Invocations enqueued in the meantime moved the snapshot entry. The files did not
+            // change, as the worker is not running.
+            self.state
+                .worker_file_system_service
+                .sync(&self.owned_worker_id, snapshot_idx, self.temp_dir.path())
+                .await?;
+        }
+        debug!("Saved worker snapshot at oplog index {snapshot_idx}");
+
+        // Recovery loads the snapshot instead of replaying the entries preceding it
+        if snapshot_idx.previous() > OplogIndex::INITIAL {
+            self.state
+                .deleted_regions
+                .add(OplogRegion::from_index_range(
+                    OplogIndex::INITIAL.next()..=snapshot_idx.previous(),
+                ));
+        }
+        self.state.last_snapshot = Some(snapshot_idx);
+        self.state.last_snapshot_at = Instant::now();
+
+        if self.state.config.snapshot.compact_oplog {
+            self.state
+                .worker_service
+                .preserve_initial_entry(&self.owned_worker_id)
+                .await;
+            self.public_state
+                .invocation_queue
+                .forget_invocation_results_before(snapshot_idx);
+        }
+
+        self.update_worker_status(|status| status.last_snapshot = Some(snapshot_idx))
+            .await;
+
+        if self.state.config.snapshot.compact_oplog {
+            debug!("Compacting the oplog up to {}", snapshot_idx.previous());
+            oplog.drop_prefix(snapshot_idx.previous()).await;
+        }
+        Ok(())
+    }
 }
 
 pub trait DurableWorkerCtxView<Ctx: WorkerCtx> {
@@ -913,65 +1289,68 @@ impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> ExternalOperations<Ctx> for Dur
             .state
             .get_out_of_deleted_region();
 
-        let result = loop {
-            let cont = store.as_context().data().durable_ctx().state.is_replay();
-
-            if cont {
-                let oplog_entry = store
-                    .as_context_mut()
-                    .data_mut()
-                    .durable_ctx_mut()
-                    .state
-                    .get_oplog_entry_exported_function_invoked()
-                    .await;
-                match oplog_entry {
-                    Err(error) => break Err(error),
-                    Ok(None) => break Ok(()),
-                    Ok(Some((
-                        function_name,
-                        function_input,
-                        idempotency_key,
-                        calling_convention,
-                    ))) => {
-                        debug!("prepare_instance invoking function {function_name}");
-                        let span = span!(Level::INFO, "replaying", function = function_name);
-                        store
-                            .as_context_mut()
-                            .data_mut()
-                            .set_current_idempotency_key(idempotency_key)
-                            .await;
+        let result = match Self::load_last_snapshot(instance, store).await {
+            Ok(()) => loop {
+                let cont = store.as_context().data().durable_ctx().state.is_replay();
 
-                        let invoke_result = invoke_worker(
-                            function_name.to_string(),
-                            function_input,
-                            store,
-                            instance,
-                            calling_convention.unwrap_or(CallingConvention::Component),
-                            false, // we know it was not live before, because cont=true
-                        )
-                        .instrument(span)
+                if cont {
+                    let oplog_entry = store
+                        .as_context_mut()
+                        .data_mut()
+                        .durable_ctx_mut()
+                        .state
+                        .get_oplog_entry_exported_function_invoked()
                         .await;
+                    match oplog_entry {
+                        Err(error) => break Err(error),
+                        Ok(None) => break Ok(()),
+                        Ok(Some((
+                            function_name,
+                            function_input,
+                            idempotency_key,
+                            calling_convention,
+                        ))) => {
+                            debug!("prepare_instance invoking function {function_name}");
+                            let span = span!(Level::INFO, "replaying", function = function_name);
+                            store
+                                .as_context_mut()
+                                .data_mut()
+                                .set_current_idempotency_key(idempotency_key)
+                                .await;
+
+                            let invoke_result = invoke_worker(
+                                function_name.to_string(),
+                                function_input,
+                                store,
+                                instance,
+                                calling_convention.unwrap_or(CallingConvention::Component),
+                                false, // we know it was not live before, because cont=true
+                            )
+                            .instrument(span)
+                            .await;
 
-                        if let Some(invoke_result) = invoke_result {
-                            if let Err(error) = invoke_result {
-                                if let Some(error) =
-                                    TrapType::from_error::<Ctx>(&error).as_golem_error()
-                                {
-                                    break Err(error);
+                            if let Some(invoke_result) = invoke_result {
+                                if let Err(error) = invoke_result {
+                                    if let Some(error) =
+                                        TrapType::from_error::<Ctx>(&error).as_golem_error()
+                                    {
+                                        break Err(error);
+                                    }
                                 }
-                            }
-                        } else {
-                            break Err(GolemError::runtime(format!(
+                            } else {
+                                break Err(GolemError::runtime(format!(
                                 "The worker could not finish replaying a function {function_name}"
                             )));
-                        }
+                            }
 
-                        count += 1;
+                            count += 1;
+                        }
                     }
+                } else {
+                    break Ok(());
                 }
-            } else {
-                break Ok(());
-            }
+            },
+            Err(error) => Err(error),
         };
         record_resume_worker(start.elapsed());
         record_number_of_replayed_functions(count);
@@ -1092,6 +1471,7 @@ pub struct PrivateDurableWorkerState<Ctx: WorkerCtx> {
     worker_enumeration_service: Arc<dyn worker_enumeration::WorkerEnumerationService + Send + Sync>,
     key_value_service: Arc<dyn KeyValueService + Send + Sync>,
     blob_store_service: Arc<dyn BlobStoreService + Send + Sync>,
+    worker_file_system_service: Arc<dyn WorkerFileSystemService + Send + Sync>,
     config: Arc<GolemConfig>,
     owned_worker_id: OwnedWorkerId,
     current_idempotency_key: Option<IdempotencyKey>,
@@ -1099,6 +1479,7 @@ pub struct PrivateDurableWorkerState<Ctx: WorkerCtx> {
     recovery_management: Arc<dyn RecoveryManagement + Send + Sync>,
     rpc: Arc<dyn Rpc + Send + Sync>,
     worker_proxy: Arc<dyn WorkerProxy + Send + Sync>,
+    component_service: Arc<dyn ComponentService + Send + Sync>,
     resources: HashMap<u64, ResourceAny>,
     last_resource_id: u64,
     deleted_regions: DeletedRegions,
@@ -1112,6 +1493,11 @@ pub struct PrivateDurableWorkerState<Ctx: WorkerCtx> {
     /// The oplog index of the last replayed entry
     last_replayed_index: OplogIndex,
     snapshotting_mode: Option<PersistenceLevel>,
+
+    /// The oplog index of the latest user-defined snapshot of the worker
+    last_snapshot: Option<OplogIndex>,
+    /// The time the last snapshot was saved, or the worker got started if it was not saved since
+    last_snapshot_at: Instant,
 }
 
 impl<Ctx: WorkerCtx> PrivateDurableWorkerState<Ctx> {
@@ -1126,13 +1512,16 @@ impl<Ctx: WorkerCtx> PrivateDurableWorkerState<Ctx> {
         >,
         key_value_service: Arc<dyn KeyValueService + Send + Sync>,
         blob_store_service: Arc<dyn BlobStoreService + Send + Sync>,
+        worker_file_system_service: Arc<dyn WorkerFileSystemService + Send + Sync>,
         config: Arc<GolemConfig>,
         owned_worker_id: OwnedWorkerId,
         active_workers: Arc<ActiveWorkers<Ctx>>,
         recovery_management: Arc<dyn RecoveryManagement + Send + Sync>,
         rpc: Arc<dyn Rpc + Send + Sync>,
         worker_proxy: Arc<dyn WorkerProxy + Send + Sync>,
+        component_service: Arc<dyn ComponentService + Send + Sync>,
         deleted_regions: DeletedRegions,
+        last_snapshot: Option<OplogIndex>,
         last_oplog_index: OplogIndex,
     ) -> Self {
         let mut result = Self {
@@ -1144,6 +1533,7 @@ impl<Ctx: WorkerCtx> PrivateDurableWorkerState<Ctx> {
             worker_enumeration_service,
             key_value_service,
             blob_store_service,
+            worker_file_system_service,
             config,
             owned_worker_id,
             current_idempotency_key: None,
@@ -1151,6 +1541,7 @@ impl<Ctx: WorkerCtx> PrivateDurableWorkerState<Ctx> {
             recovery_management,
             rpc,
             worker_proxy,
+            component_service,
             resources: HashMap::new(),
             last_resource_id: 0,
             deleted_regions: deleted_regions.clone(),
@@ -1162,6 +1553,8 @@ impl<Ctx: WorkerCtx> PrivateDurableWorkerState<Ctx> {
             last_replayed_index: OplogIndex::NONE,
             replay_target: last_oplog_index,
             snapshotting_mode: None,
+            last_snapshot,
+            last_snapshot_at: Instant::now(),
         };
         result.move_replay_idx(OplogIndex::INITIAL); // By this we handle initial deleted regions applied by manual updates correctly
         result
@@ -1441,6 +1834,8 @@ impl<Ctx: WorkerCtx> PrivateDurableWorkerState<Ctx> {
             .create(
                 &self.owned_worker_id.worker_id,
                 self.current_oplog_index().await,
+                None,
+                None,
             )
             .await;
 
@@ -1541,6 +1936,7 @@ pub struct PublicDurableWorkerState<Ctx: WorkerCtx> {
     managed_stdio: ManagedStandardIo<Ctx>,
     invocation_queue: Arc<InvocationQueue<Ctx>>,
     oplog: Arc<dyn Oplog + Send + Sync>,
+    temp_dir: Arc<TempDir>,
 }
 
 impl<Ctx: WorkerCtx> Clone for PublicDurableWorkerState<Ctx> {
@@ -1551,6 +1947,7 @@ impl<Ctx: WorkerCtx> Clone for PublicDurableWorkerState<Ctx> {
             managed_stdio: self.managed_stdio.clone(),
             invocation_queue: self.invocation_queue.clone(),
             oplog: self.oplog.clone(),
+            temp_dir: self.temp_dir.clone(),
         }
     }
 }
@@ -1560,6 +1957,10 @@ impl<Ctx: WorkerCtx> PublicWorkerIo for PublicDurableWorkerState<Ctx> {
     fn event_service(&self) -> Arc<dyn WorkerEventService + Send + Sync> {
         self.event_service.clone()
     }
+
+    fn file_system_root(&self) -> PathBuf {
+        self.temp_dir.path().to_path_buf()
+    }
 }
 
 impl<Ctx: WorkerCtx> HasInvocationQueue<Ctx> for PublicDurableWorkerState<Ctx> {
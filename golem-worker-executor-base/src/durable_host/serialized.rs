@@ -13,10 +13,15 @@
 // limitations under the License.
 
 use crate::error::GolemError;
+use crate::services::blob_store::ObjectMetadata;
 use crate::services::rpc::RpcError;
 use crate::services::worker_proxy::WorkerProxyError;
 use anyhow::anyhow;
 use bincode::{Decode, Encode};
+use golem_common::model::ScheduledInvocationId;
+use golem_wasm_rpc::{Value, WitValue};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::ops::Add;
 use std::time::{Duration, SystemTime};
 use wasmtime_wasi::preview2::bindings::sockets::ip_name_lookup::IpAddress;
@@ -536,6 +541,164 @@ pub struct SerializableFileTimes {
     pub data_modification_timestamp: Option<SerializableDateTime>,
 }
 
+/// Converts the values serialized into the oplog by imported functions to generic values, so
+/// they can be inspected like the parameters and results of exported functions.
+/// Errors are converted to their messages.
+pub trait IntoValue {
+    fn into_value(self) -> Value;
+}
+
+impl IntoValue for () {
+    fn into_value(self) -> Value {
+        Value::Tuple(vec![])
+    }
+}
+
+impl IntoValue for bool {
+    fn into_value(self) -> Value {
+        Value::Bool(self)
+    }
+}
+
+impl IntoValue for u8 {
+    fn into_value(self) -> Value {
+        Value::U8(self)
+    }
+}
+
+impl IntoValue for u16 {
+    fn into_value(self) -> Value {
+        Value::U16(self)
+    }
+}
+
+impl IntoValue for u32 {
+    fn into_value(self) -> Value {
+        Value::U32(self)
+    }
+}
+
+impl IntoValue for u64 {
+    fn into_value(self) -> Value {
+        Value::U64(self)
+    }
+}
+
+impl IntoValue for String {
+    fn into_value(self) -> Value {
+        Value::String(self)
+    }
+}
+
+impl<T: IntoValue> IntoValue for Option<T> {
+    fn into_value(self) -> Value {
+        Value::Option(self.map(|value| Box::new(value.into_value())))
+    }
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+    fn into_value(self) -> Value {
+        Value::List(self.into_iter().map(IntoValue::into_value).collect())
+    }
+}
+
+impl<A: IntoValue, B: IntoValue> IntoValue for (A, B) {
+    fn into_value(self) -> Value {
+        Value::Tuple(vec![self.0.into_value(), self.1.into_value()])
+    }
+}
+
+impl<K: IntoValue, V: IntoValue> IntoValue for HashMap<K, V> {
+    fn into_value(self) -> Value {
+        Value::List(self.into_iter().map(|entry| entry.into_value()).collect())
+    }
+}
+
+impl<T: IntoValue, E: IntoValue> IntoValue for Result<T, E> {
+    fn into_value(self) -> Value {
+        Value::Result(
+            self.map(|value| Some(Box::new(value.into_value())))
+                .map_err(|err| Some(Box::new(err.into_value()))),
+        )
+    }
+}
+
+impl IntoValue for WitValue {
+    fn into_value(self) -> Value {
+        self.into()
+    }
+}
+
+impl IntoValue for ScheduledInvocationId {
+    fn into_value(self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+impl IntoValue for ObjectMetadata {
+    fn into_value(self) -> Value {
+        Value::Record(vec![
+            self.name.into_value(),
+            self.container.into_value(),
+            self.created_at.into_value(),
+            self.size.into_value(),
+        ])
+    }
+}
+
+impl IntoValue for SerializableDateTime {
+    fn into_value(self) -> Value {
+        Value::Record(vec![
+            self.seconds.into_value(),
+            self.nanoseconds.into_value(),
+        ])
+    }
+}
+
+impl IntoValue for SerializableError {
+    fn into_value(self) -> Value {
+        let error: anyhow::Error = self.into();
+        Value::String(error.to_string())
+    }
+}
+
+impl IntoValue for SerializableStreamError {
+    fn into_value(self) -> Value {
+        let error: StreamError = self.into();
+        Value::String(error.to_string())
+    }
+}
+
+impl IntoValue for SerializableIpAddresses {
+    fn into_value(self) -> Value {
+        Value::List(
+            self.0
+                .into_iter()
+                .map(|address| {
+                    let address = match address {
+                        SerializableIpAddress::IPv4 { address } => {
+                            IpAddr::V4(Ipv4Addr::from(address))
+                        }
+                        SerializableIpAddress::IPv6 { address } => {
+                            IpAddr::V6(Ipv6Addr::from(address))
+                        }
+                    };
+                    Value::String(address.to_string())
+                })
+                .collect(),
+        )
+    }
+}
+
+impl IntoValue for SerializableFileTimes {
+    fn into_value(self) -> Value {
+        Value::Record(vec![
+            self.data_access_timestamp.into_value(),
+            self.data_modification_timestamp.into_value(),
+        ])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::durable_host::serialized::{
@@ -676,6 +839,7 @@ mod tests {
             promiseid_strat().prop_map(|promise_id| GolemError::PromiseDropped { promise_id }),
             promiseid_strat().prop_map(|promise_id| GolemError::PromiseAlreadyCompleted { promise_id }),
             promiseid_strat().prop_map(|promise_id| GolemError::PromiseAlreadyCompleted { promise_id }),
+            promiseid_strat().prop_map(|promise_id| GolemError::PromiseTimedOut { promise_id }),
             interrupt_kind_strat().prop_map(|kind| GolemError::Interrupted { kind }),
             Just(GolemError::ParamTypeMismatch),
             Just(GolemError::NoValueInMessage),
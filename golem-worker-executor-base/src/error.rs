@@ -94,6 +94,9 @@ pub enum GolemError {
     Unknown {
         details: String,
     },
+    PromiseTimedOut {
+        promise_id: PromiseId,
+    },
 }
 
 impl GolemError {
@@ -174,6 +177,7 @@ impl GolemError {
             GolemError::PromiseNotFound { .. } => "PromiseNotFound",
             GolemError::PromiseDropped { .. } => "PromiseDropped",
             GolemError::PromiseAlreadyCompleted { .. } => "PromiseAlreadyCompleted",
+            GolemError::PromiseTimedOut { .. } => "PromiseTimedOut",
             GolemError::Interrupted { .. } => "Interrupted",
             GolemError::ParamTypeMismatch => "ParamTypeMismatch",
             GolemError::NoValueInMessage => "NoValueInMessage",
@@ -245,6 +249,9 @@ impl Display for GolemError {
             GolemError::PromiseAlreadyCompleted { promise_id } => {
                 write!(f, "Promise already completed: {promise_id}")
             }
+            GolemError::PromiseTimedOut { promise_id } => {
+                write!(f, "Promise timed out: {promise_id}")
+            }
             GolemError::Interrupted { kind } => {
                 write!(f, "{kind}")
             }
@@ -301,6 +308,7 @@ impl Error for GolemError {
             GolemError::PromiseNotFound { .. } => "Promise not found",
             GolemError::PromiseDropped { .. } => "Promise dropped",
             GolemError::PromiseAlreadyCompleted { .. } => "Promise already completed",
+            GolemError::PromiseTimedOut { .. } => "Promise timed out",
             GolemError::Interrupted { .. } => "Interrupted",
             GolemError::ParamTypeMismatch => "Parameter type mismatch",
             GolemError::NoValueInMessage => "No value in message",
@@ -482,6 +490,15 @@ impl From<GolemError> for golem::worker::WorkerExecutionError {
                     ),
                 }
             }
+            GolemError::PromiseTimedOut { promise_id } => golem::worker::WorkerExecutionError {
+                error: Some(
+                    golem::worker::worker_execution_error::Error::PromiseTimedOut(
+                        golem::worker::PromiseTimedOut {
+                            promise_id: Some(promise_id.into()),
+                        },
+                    ),
+                ),
+            },
             GolemError::Interrupted { kind } => golem::worker::WorkerExecutionError {
                 error: Some(golem::worker::worker_execution_error::Error::Interrupted(
                     golem::worker::Interrupted {
@@ -672,6 +689,14 @@ impl TryFrom<golem::worker::WorkerExecutionError> for GolemError {
                     .ok_or("Missing promise_id")?
                     .try_into()?,
             }),
+            Some(golem::worker::worker_execution_error::Error::PromiseTimedOut(
+                promise_timed_out,
+            )) => Ok(GolemError::PromiseTimedOut {
+                promise_id: promise_timed_out
+                    .promise_id
+                    .ok_or("Missing promise_id")?
+                    .try_into()?,
+            }),
             Some(golem::worker::worker_execution_error::Error::Interrupted(interrupted)) => {
                 Ok(GolemError::Interrupted {
                     kind: if interrupted.recover_immediately {
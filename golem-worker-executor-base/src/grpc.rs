@@ -16,9 +16,11 @@ use gethostname::gethostname;
 use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
+use std::path::PathBuf;
 
 use std::sync::Arc;
 
+use chrono::Utc;
 use golem_api_grpc::proto::golem;
 use golem_api_grpc::proto::golem::common::ResourceLimits as GrpcResourceLimits;
 use golem_api_grpc::proto::golem::worker::{Cursor, UpdateMode};
@@ -30,13 +32,15 @@ use golem_api_grpc::proto::golem::workerexecutor::{
 };
 use golem_common::cache::PendingOrFinal;
 use golem_common::model as common_model;
-use golem_common::model::oplog::UpdateDescription;
+use golem_common::model::oplog::{OplogEntry, OplogIndex, RevertWorkerTarget, UpdateDescription};
 use golem_common::model::{
-    AccountId, CallingConvention, ComponentId, IdempotencyKey, OwnedWorkerId, PromiseId,
-    ScanCursor, ShardId, TimestampedWorkerInvocation, WorkerFilter, WorkerId, WorkerInvocation,
-    WorkerMetadata, WorkerStatus, WorkerStatusRecord,
+    AccountId, CallingConvention, ComponentId, IdempotencyKey, InvocationSchedule, OwnedWorkerId,
+    PromiseId, ScanCursor, ScheduledInvocation, ScheduledInvocationId, ShardId, Timestamp,
+    TimestampedWorkerInvocation, WorkerFilter, WorkerId, WorkerInvocation, WorkerMetadata,
+    WorkerStatus, WorkerStatusRecord,
 };
 use golem_wasm_rpc::protobuf::Val;
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
@@ -48,14 +52,20 @@ use crate::error::*;
 use crate::metrics::grpc::{record_closed_grpc_active_stream, record_new_grpc_active_stream};
 use crate::model::{InterruptKind, LastError};
 use crate::recorded_grpc_request;
+use crate::services::oplog::inspect::{search_oplog, OplogPage};
+use crate::services::promise::PendingPromise;
 use crate::services::worker_activator::{DefaultWorkerActivator, LazyWorkerActivator};
 use crate::services::worker_event::LogLevel;
+use crate::services::worker_file_system::{list_directory, open_file, WorkerFileEntry};
 use crate::services::{
-    worker_event, All, HasActiveWorkers, HasAll, HasInvocationQueue, HasPromiseService,
-    HasRunningWorkerEnumerationService, HasShardManagerService, HasShardService,
-    HasWorkerEnumerationService, HasWorkerService, UsesAllDeps,
+    worker_event, All, HasActiveWorkers, HasAll, HasInvocationQueue, HasOplogService,
+    HasPromiseService, HasRunningWorkerEnumerationService, HasSchedulerService,
+    HasShardManagerService, HasShardService, HasWorkerEnumerationService,
+    HasWorkerFileSystemService, HasWorkerService, UsesAllDeps,
+};
+use crate::worker::{
+    delete_worker, fork_worker, invoke_and_await, revert_worker, PendingWorker, Worker,
 };
-use crate::worker::{invoke_and_await, PendingWorker, Worker};
 use crate::workerctx::{PublicWorkerIo, WorkerCtx};
 
 pub enum GrpcError<E> {
@@ -140,6 +150,8 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
 type ResponseResult<T> = Result<Response<T>, Status>;
 type ResponseStream = ReceiverStream<Result<golem::worker::LogEvent, Status>>;
 
+const READ_WORKER_FILE_CHUNK_SIZE: usize = 64 * 1024;
+
 impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync + 'static>
     WorkerExecutorImpl<Ctx, Svcs>
 {
@@ -341,41 +353,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
 
         self.validate_worker_id(&worker_id)?;
 
-        let metadata = self.worker_service().get(&owned_worker_id).await;
-        let worker_status =
-            Ctx::compute_latest_worker_status(self, &owned_worker_id, &metadata).await?;
-        let metadata = metadata.ok_or(GolemError::invalid_request("Worker not found"))?;
-
-        let should_interrupt = match &worker_status.status {
-            WorkerStatus::Running | WorkerStatus::Suspended | WorkerStatus::Retrying => true,
-            WorkerStatus::Exited
-            | WorkerStatus::Failed
-            | WorkerStatus::Idle
-            | WorkerStatus::Interrupted => false,
-        };
-
-        if should_interrupt {
-            let worker_details = Worker::get_or_create_with_config(
-                self,
-                &owned_worker_id,
-                metadata.args,
-                metadata.env,
-                Some(worker_status.component_version),
-            )
-            .await?;
-
-            if let Some(mut await_interrupted) =
-                worker_details.set_interrupting(InterruptKind::Interrupt)
-            {
-                await_interrupted.recv().await.unwrap();
-            }
-        }
-
-        Ctx::on_worker_deleted(self, &worker_id).await?;
-        self.worker_service().remove(&owned_worker_id).await;
-        self.active_workers().remove(&worker_id);
-
-        Ok(())
+        delete_worker(self, &owned_worker_id).await
     }
 
     async fn interrupt_worker_internal(
@@ -1005,6 +983,384 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         Ok(())
     }
 
+    async fn search_oplog_internal(
+        &self,
+        worker_id: Option<golem::worker::WorkerId>,
+        account_id: Option<golem::common::AccountId>,
+        start: OplogIndex,
+        count: u64,
+        filter: golem::worker::OplogFilter,
+    ) -> Result<OplogPage, GolemError> {
+        let worker_id = worker_id.ok_or(GolemError::invalid_request("worker_id not found"))?;
+        let worker_id: WorkerId = worker_id.try_into().map_err(GolemError::invalid_request)?;
+        let account_id = account_id.ok_or(GolemError::invalid_request("account_id not found"))?;
+        let account_id: AccountId = account_id.into();
+        let owned_worker_id = OwnedWorkerId::new(&account_id, &worker_id);
+
+        self.validate_worker_id(&worker_id)?;
+
+        if !self.oplog_service().exists(&owned_worker_id).await {
+            return Err(GolemError::worker_not_found(worker_id));
+        }
+
+        search_oplog(
+            self.oplog_service(),
+            self.worker_service(),
+            &owned_worker_id,
+            start,
+            count,
+            &filter,
+        )
+        .await
+    }
+
+    async fn fork_worker_internal(
+        &self,
+        request: golem::workerexecutor::ForkWorkerRequest,
+    ) -> Result<(), GolemError> {
+        let source_worker_id: WorkerId = request
+            .source_worker_id
+            .ok_or(GolemError::invalid_request("source_worker_id not found"))?
+            .try_into()
+            .map_err(GolemError::invalid_request)?;
+        let target_worker_id: WorkerId = request
+            .target_worker_id
+            .ok_or(GolemError::invalid_request("target_worker_id not found"))?
+            .try_into()
+            .map_err(GolemError::invalid_request)?;
+        let account_id: AccountId = request
+            .account_id
+            .ok_or(GolemError::invalid_request("account_id not found"))?
+            .into();
+
+        self.validate_worker_id(&target_worker_id)?;
+
+        fork_worker::<Ctx, _>(
+            self,
+            &OwnedWorkerId::new(&account_id, &source_worker_id),
+            &OwnedWorkerId::new(&account_id, &target_worker_id),
+            OplogIndex::from_u64(request.oplog_index_cutoff),
+        )
+        .await
+    }
+
+    async fn revert_worker_internal(
+        &self,
+        request: golem::workerexecutor::RevertWorkerRequest,
+    ) -> Result<(), GolemError> {
+        let worker_id: WorkerId = request
+            .worker_id
+            .ok_or(GolemError::invalid_request("worker_id not found"))?
+            .try_into()
+            .map_err(GolemError::invalid_request)?;
+        let account_id: AccountId = request
+            .account_id
+            .ok_or(GolemError::invalid_request("account_id not found"))?
+            .into();
+        let target: RevertWorkerTarget = request
+            .target
+            .ok_or(GolemError::invalid_request("target not found"))?
+            .try_into()
+            .map_err(GolemError::invalid_request)?;
+
+        self.validate_worker_id(&worker_id)?;
+
+        revert_worker::<Ctx, _>(self, &OwnedWorkerId::new(&account_id, &worker_id), target).await
+    }
+
+    async fn cancel_invocation_internal(
+        &self,
+        request: golem::workerexecutor::CancelInvocationRequest,
+    ) -> Result<golem::worker::CancelInvocationStatus, GolemError> {
+        let worker_id: WorkerId = request
+            .worker_id
+            .ok_or(GolemError::invalid_request("worker_id not found"))?
+            .try_into()
+            .map_err(GolemError::invalid_request)?;
+        let account_id: AccountId = request
+            .account_id
+            .ok_or(GolemError::invalid_request("account_id not found"))?
+            .into();
+        let idempotency_key: IdempotencyKey = request
+            .idempotency_key
+            .ok_or(GolemError::invalid_request("idempotency_key not found"))?
+            .into();
+
+        self.validate_worker_id(&worker_id)?;
+
+        let owned_worker_id = OwnedWorkerId::new(&account_id, &worker_id);
+        let metadata = self.worker_service().get(&owned_worker_id).await;
+        if metadata.is_none() {
+            return Err(GolemError::worker_not_found(worker_id));
+        }
+
+        let active_worker = self
+            .active_workers()
+            .enum_workers()
+            .into_iter()
+            .find(|(id, _)| *id == worker_id);
+
+        let canceled = match active_worker {
+            Some((_, worker)) => {
+                // The worker is in memory, so its invocation queue is the source of truth
+                worker
+                    .public_state
+                    .invocation_queue()
+                    .cancel_pending_invocation(&idempotency_key)
+                    .await
+            }
+            None => {
+                let worker_status =
+                    Ctx::compute_latest_worker_status(self, &owned_worker_id, &metadata).await?;
+                let is_pending = worker_status
+                    .pending_invocations
+                    .iter()
+                    .any(|pending| pending.invocation.is_idempotency_key(&idempotency_key));
+                if is_pending {
+                    self.oplog_service()
+                        .open(&owned_worker_id)
+                        .await
+                        .add_and_commit(OplogEntry::cancel_pending_invocation(
+                            idempotency_key.clone(),
+                        ))
+                        .await;
+                }
+                is_pending
+            }
+        };
+
+        let latest_status =
+            Ctx::compute_latest_worker_status(self, &owned_worker_id, &metadata).await?;
+        if canceled {
+            self.worker_service()
+                .update_status(&owned_worker_id, &latest_status)
+                .await;
+            Ok(golem::worker::CancelInvocationStatus::Canceled)
+        } else if latest_status
+            .invocation_results
+            .contains_key(&idempotency_key)
+        {
+            Ok(golem::worker::CancelInvocationStatus::AlreadyCompleted)
+        } else if latest_status.current_idempotency_key.as_ref() == Some(&idempotency_key) {
+            Ok(golem::worker::CancelInvocationStatus::AlreadyRunning)
+        } else {
+            Ok(golem::worker::CancelInvocationStatus::NotFound)
+        }
+    }
+
+    async fn schedule_invocation_internal(
+        &self,
+        request: golem::workerexecutor::ScheduleInvocationRequest,
+    ) -> Result<ScheduledInvocation, GolemError> {
+        let worker_id: WorkerId = request
+            .worker_id
+            .ok_or(GolemError::invalid_request("worker_id not found"))?
+            .try_into()
+            .map_err(GolemError::invalid_request)?;
+        let account_id: AccountId = request
+            .account_id
+            .ok_or(GolemError::invalid_request("account_id not found"))?
+            .into();
+        let schedule: InvocationSchedule = request
+            .schedule
+            .ok_or(GolemError::invalid_request("schedule not found"))?
+            .try_into()
+            .map_err(GolemError::invalid_request)?;
+        let function_input = request
+            .input
+            .into_iter()
+            .map(|val| val.try_into())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|msg| GolemError::ValueMismatch { details: msg })?;
+
+        self.validate_worker_id(&worker_id)?;
+
+        let (scheduled_at, cron) = match schedule {
+            // Invocations scheduled in the past are executed as soon as possible
+            InvocationSchedule::At(at) => (at.max(Timestamp::now_utc()), None),
+            InvocationSchedule::Cron(cron) => {
+                let next = cron
+                    .next_after(Utc::now())
+                    .ok_or(GolemError::invalid_request(
+                        "the cron schedule has no upcoming occurrence",
+                    ))?;
+                (Timestamp::from(next.timestamp_millis() as u64), Some(cron))
+            }
+        };
+
+        let invocation = ScheduledInvocation {
+            id: ScheduledInvocationId::new_v4(),
+            scheduled_at,
+            full_function_name: request.name,
+            function_input,
+            cron,
+        };
+
+        let owned_worker_id = OwnedWorkerId::new(&account_id, &worker_id);
+        self.scheduler_service()
+            .schedule_invocation(&owned_worker_id, invocation.clone())
+            .await;
+
+        Ok(invocation)
+    }
+
+    async fn get_scheduled_invocations_internal(
+        &self,
+        request: golem::workerexecutor::GetScheduledInvocationsRequest,
+    ) -> Result<Vec<ScheduledInvocation>, GolemError> {
+        let worker_id: WorkerId = request
+            .worker_id
+            .ok_or(GolemError::invalid_request("worker_id not found"))?
+            .try_into()
+            .map_err(GolemError::invalid_request)?;
+        let account_id: AccountId = request
+            .account_id
+            .ok_or(GolemError::invalid_request("account_id not found"))?
+            .into();
+
+        self.validate_worker_id(&worker_id)?;
+
+        let owned_worker_id = OwnedWorkerId::new(&account_id, &worker_id);
+        Ok(self
+            .scheduler_service()
+            .get_scheduled_invocations(&owned_worker_id)
+            .await)
+    }
+
+    async fn cancel_scheduled_invocation_internal(
+        &self,
+        request: golem::workerexecutor::CancelScheduledInvocationRequest,
+    ) -> Result<bool, GolemError> {
+        let worker_id: WorkerId = request
+            .worker_id
+            .ok_or(GolemError::invalid_request("worker_id not found"))?
+            .try_into()
+            .map_err(GolemError::invalid_request)?;
+        let account_id: AccountId = request
+            .account_id
+            .ok_or(GolemError::invalid_request("account_id not found"))?
+            .into();
+        let id: ScheduledInvocationId = request
+            .id
+            .ok_or(GolemError::invalid_request("id not found"))?
+            .try_into()
+            .map_err(GolemError::invalid_request)?;
+
+        self.validate_worker_id(&worker_id)?;
+
+        let owned_worker_id = OwnedWorkerId::new(&account_id, &worker_id);
+        Ok(self
+            .scheduler_service()
+            .cancel_scheduled_invocation(&owned_worker_id, &id)
+            .await)
+    }
+
+    async fn owned_worker_id_of(
+        &self,
+        worker_id: Option<golem::worker::WorkerId>,
+        account_id: Option<golem::common::AccountId>,
+    ) -> Result<OwnedWorkerId, GolemError> {
+        let worker_id: WorkerId = worker_id
+            .ok_or(GolemError::invalid_request("worker_id not found"))?
+            .try_into()
+            .map_err(GolemError::invalid_request)?;
+        let account_id: AccountId = account_id
+            .ok_or(GolemError::invalid_request("account_id not found"))?
+            .into();
+
+        self.validate_worker_id(&worker_id)?;
+
+        let owned_worker_id = OwnedWorkerId::new(&account_id, &worker_id);
+        if self.worker_service().get(&owned_worker_id).await.is_none() {
+            return Err(GolemError::worker_not_found(worker_id));
+        }
+
+        Ok(owned_worker_id)
+    }
+
+    // Inspecting the files of a worker must not activate it, as that would resume a suspended
+    // worker. Inactive workers are served from their file system synced when they were last
+    // deactivated
+    async fn active_worker_file_system_root(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Option<PathBuf> {
+        self.active_workers()
+            .get(&owned_worker_id.worker_id)
+            .await
+            .map(|worker| worker.public_state.file_system_root())
+    }
+
+    fn file_system_not_synced(owned_worker_id: &OwnedWorkerId) -> GolemError {
+        GolemError::invalid_request(format!(
+            "The file system of the inactive worker {} is not available",
+            owned_worker_id.worker_id
+        ))
+    }
+
+    async fn list_worker_directory_internal(
+        &self,
+        request: golem::workerexecutor::ListWorkerDirectoryRequest,
+    ) -> Result<Vec<WorkerFileEntry>, GolemError> {
+        let owned_worker_id = self
+            .owned_worker_id_of(request.worker_id, request.account_id)
+            .await?;
+
+        match self.active_worker_file_system_root(&owned_worker_id).await {
+            Some(root) => list_directory(&root, &request.path).await,
+            None => self
+                .worker_file_system_service()
+                .list_synced_directory(&owned_worker_id, &request.path)
+                .await?
+                .ok_or_else(|| Self::file_system_not_synced(&owned_worker_id)),
+        }
+    }
+
+    async fn read_worker_file_internal(
+        &self,
+        request: golem::workerexecutor::ReadWorkerFileRequest,
+    ) -> Result<Box<dyn AsyncRead + Send + Unpin>, GolemError> {
+        let owned_worker_id = self
+            .owned_worker_id_of(request.worker_id, request.account_id)
+            .await?;
+
+        match self.active_worker_file_system_root(&owned_worker_id).await {
+            Some(root) => Ok(Box::new(open_file(&root, &request.path).await?)),
+            None => {
+                let content = self
+                    .worker_file_system_service()
+                    .read_synced_file(&owned_worker_id, &request.path)
+                    .await?
+                    .ok_or_else(|| Self::file_system_not_synced(&owned_worker_id))?;
+                Ok(Box::new(std::io::Cursor::new(content)))
+            }
+        }
+    }
+
+    async fn get_pending_promises_internal(
+        &self,
+        request: golem::workerexecutor::GetPendingPromisesRequest,
+    ) -> Result<Vec<PendingPromise>, GolemError> {
+        let worker_id: WorkerId = request
+            .worker_id
+            .ok_or(GolemError::invalid_request("worker_id not found"))?
+            .try_into()
+            .map_err(GolemError::invalid_request)?;
+        let account_id: AccountId = request
+            .account_id
+            .ok_or(GolemError::invalid_request("account_id not found"))?
+            .into();
+
+        self.validate_worker_id(&worker_id)?;
+
+        let owned_worker_id = OwnedWorkerId::new(&account_id, &worker_id);
+        if self.worker_service().get(&owned_worker_id).await.is_none() {
+            return Err(GolemError::worker_not_found(worker_id));
+        }
+
+        Ok(self.promise_service().get_pending(&worker_id).await)
+    }
+
     fn create_proto_metadata(
         metadata: WorkerMetadata,
         latest_status: WorkerStatusRecord,
@@ -1798,6 +2154,512 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             ),
         }
     }
+
+    async fn get_oplog(
+        &self,
+        request: Request<golem::workerexecutor::GetOplogRequest>,
+    ) -> Result<Response<golem::workerexecutor::GetOplogResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_request!(
+            "get_oplog",
+            worker_id = proto_worker_id_string(&request.worker_id),
+            from_oplog_index = request.from_oplog_index,
+            count = request.count,
+        );
+
+        match self
+            .search_oplog_internal(
+                request.worker_id,
+                request.account_id,
+                OplogIndex::from_u64(request.from_oplog_index),
+                request.count,
+                golem::worker::OplogFilter::default(),
+            )
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(page) => {
+                record.succeed(Ok(Response::new(golem::workerexecutor::GetOplogResponse {
+                    result: Some(golem::workerexecutor::get_oplog_response::Result::Success(
+                        golem::workerexecutor::GetOplogSuccessResponse {
+                            entries: page.entries,
+                            next: page.next.map(|idx| idx.into()),
+                            last_index: page.last_index.into(),
+                        },
+                    )),
+                })))
+            }
+            Err(err) => record.fail(
+                Ok(Response::new(golem::workerexecutor::GetOplogResponse {
+                    result: Some(golem::workerexecutor::get_oplog_response::Result::Failure(
+                        err.clone().into(),
+                    )),
+                })),
+                &err,
+            ),
+        }
+    }
+
+    async fn search_oplog(
+        &self,
+        request: Request<golem::workerexecutor::SearchOplogRequest>,
+    ) -> Result<Response<golem::workerexecutor::SearchOplogResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_request!(
+            "search_oplog",
+            worker_id = proto_worker_id_string(&request.worker_id),
+            count = request.count,
+        );
+
+        match self
+            .search_oplog_internal(
+                request.worker_id,
+                request.account_id,
+                OplogIndex::from_u64(request.cursor.unwrap_or(OplogIndex::INITIAL.into())),
+                request.count,
+                request.filter.unwrap_or_default(),
+            )
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(page) => record.succeed(Ok(Response::new(
+                golem::workerexecutor::SearchOplogResponse {
+                    result: Some(
+                        golem::workerexecutor::search_oplog_response::Result::Success(
+                            golem::workerexecutor::SearchOplogSuccessResponse {
+                                entries: page.entries,
+                                next: page.next.map(|idx| idx.into()),
+                                last_index: page.last_index.into(),
+                            },
+                        ),
+                    ),
+                },
+            ))),
+            Err(err) => record.fail(
+                Ok(Response::new(golem::workerexecutor::SearchOplogResponse {
+                    result: Some(
+                        golem::workerexecutor::search_oplog_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &err,
+            ),
+        }
+    }
+
+    async fn fork_worker(
+        &self,
+        request: Request<golem::workerexecutor::ForkWorkerRequest>,
+    ) -> Result<Response<golem::workerexecutor::ForkWorkerResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_request!(
+            "fork_worker",
+            worker_id = proto_worker_id_string(&request.source_worker_id),
+            target_worker_id = proto_worker_id_string(&request.target_worker_id),
+            oplog_index_cutoff = request.oplog_index_cutoff,
+        );
+
+        match self
+            .fork_worker_internal(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(_) => record.succeed(Ok(Response::new(
+                golem::workerexecutor::ForkWorkerResponse {
+                    result: Some(
+                        golem::workerexecutor::fork_worker_response::Result::Success(
+                            golem::common::Empty {},
+                        ),
+                    ),
+                },
+            ))),
+            Err(err) => record.fail(
+                Ok(Response::new(golem::workerexecutor::ForkWorkerResponse {
+                    result: Some(
+                        golem::workerexecutor::fork_worker_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &err,
+            ),
+        }
+    }
+
+    async fn revert_worker(
+        &self,
+        request: Request<golem::workerexecutor::RevertWorkerRequest>,
+    ) -> Result<Response<golem::workerexecutor::RevertWorkerResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_request!(
+            "revert_worker",
+            worker_id = proto_worker_id_string(&request.worker_id),
+        );
+
+        match self
+            .revert_worker_internal(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(_) => record.succeed(Ok(Response::new(
+                golem::workerexecutor::RevertWorkerResponse {
+                    result: Some(
+                        golem::workerexecutor::revert_worker_response::Result::Success(
+                            golem::common::Empty {},
+                        ),
+                    ),
+                },
+            ))),
+            Err(err) => record.fail(
+                Ok(Response::new(golem::workerexecutor::RevertWorkerResponse {
+                    result: Some(
+                        golem::workerexecutor::revert_worker_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &err,
+            ),
+        }
+    }
+
+    async fn cancel_invocation(
+        &self,
+        request: Request<golem::workerexecutor::CancelInvocationRequest>,
+    ) -> Result<Response<golem::workerexecutor::CancelInvocationResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_request!(
+            "cancel_invocation",
+            worker_id = proto_worker_id_string(&request.worker_id),
+            idempotency_key = proto_idempotency_key_string(&request.idempotency_key),
+        );
+
+        match self
+            .cancel_invocation_internal(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(status) => record.succeed(Ok(Response::new(
+                golem::workerexecutor::CancelInvocationResponse {
+                    result: Some(
+                        golem::workerexecutor::cancel_invocation_response::Result::Success(
+                            status.into(),
+                        ),
+                    ),
+                },
+            ))),
+            Err(err) => record.fail(
+                Ok(Response::new(
+                    golem::workerexecutor::CancelInvocationResponse {
+                        result: Some(
+                            golem::workerexecutor::cancel_invocation_response::Result::Failure(
+                                err.clone().into(),
+                            ),
+                        ),
+                    },
+                )),
+                &err,
+            ),
+        }
+    }
+
+    async fn schedule_invocation(
+        &self,
+        request: Request<golem::workerexecutor::ScheduleInvocationRequest>,
+    ) -> Result<Response<golem::workerexecutor::ScheduleInvocationResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_request!(
+            "schedule_invocation",
+            worker_id = proto_worker_id_string(&request.worker_id),
+            function = request.name,
+        );
+
+        match self
+            .schedule_invocation_internal(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(invocation) => record.succeed(Ok(Response::new(
+                golem::workerexecutor::ScheduleInvocationResponse {
+                    result: Some(
+                        golem::workerexecutor::schedule_invocation_response::Result::Success(
+                            invocation.into(),
+                        ),
+                    ),
+                },
+            ))),
+            Err(err) => record.fail(
+                Ok(Response::new(
+                    golem::workerexecutor::ScheduleInvocationResponse {
+                        result: Some(
+                            golem::workerexecutor::schedule_invocation_response::Result::Failure(
+                                err.clone().into(),
+                            ),
+                        ),
+                    },
+                )),
+                &err,
+            ),
+        }
+    }
+
+    async fn get_scheduled_invocations(
+        &self,
+        request: Request<golem::workerexecutor::GetScheduledInvocationsRequest>,
+    ) -> Result<Response<golem::workerexecutor::GetScheduledInvocationsResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_request!(
+            "get_scheduled_invocations",
+            worker_id = proto_worker_id_string(&request.worker_id),
+        );
+
+        match self
+            .get_scheduled_invocations_internal(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(invocations) => record.succeed(Ok(Response::new(
+                golem::workerexecutor::GetScheduledInvocationsResponse {
+                    result: Some(
+                        golem::workerexecutor::get_scheduled_invocations_response::Result::Success(
+                            golem::workerexecutor::GetScheduledInvocationsSuccessResponse {
+                                invocations: invocations
+                                    .into_iter()
+                                    .map(|invocation| invocation.into())
+                                    .collect(),
+                            },
+                        ),
+                    ),
+                },
+            ))),
+            Err(err) => record.fail(
+                Ok(Response::new(
+                    golem::workerexecutor::GetScheduledInvocationsResponse {
+                        result: Some(
+                            golem::workerexecutor::get_scheduled_invocations_response::Result::Failure(
+                                err.clone().into(),
+                            ),
+                        ),
+                    },
+                )),
+                &err,
+            ),
+        }
+    }
+
+    async fn cancel_scheduled_invocation(
+        &self,
+        request: Request<golem::workerexecutor::CancelScheduledInvocationRequest>,
+    ) -> Result<Response<golem::workerexecutor::CancelScheduledInvocationResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_request!(
+            "cancel_scheduled_invocation",
+            worker_id = proto_worker_id_string(&request.worker_id),
+        );
+
+        match self
+            .cancel_scheduled_invocation_internal(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(canceled) => record.succeed(Ok(Response::new(
+                golem::workerexecutor::CancelScheduledInvocationResponse {
+                    result: Some(
+                        golem::workerexecutor::cancel_scheduled_invocation_response::Result::Success(
+                            canceled,
+                        ),
+                    ),
+                },
+            ))),
+            Err(err) => record.fail(
+                Ok(Response::new(
+                    golem::workerexecutor::CancelScheduledInvocationResponse {
+                        result: Some(
+                            golem::workerexecutor::cancel_scheduled_invocation_response::Result::Failure(
+                                err.clone().into(),
+                            ),
+                        ),
+                    },
+                )),
+                &err,
+            ),
+        }
+    }
+    async fn list_worker_directory(
+        &self,
+        request: Request<golem::workerexecutor::ListWorkerDirectoryRequest>,
+    ) -> Result<Response<golem::workerexecutor::ListWorkerDirectoryResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_request!(
+            "list_worker_directory",
+            worker_id = proto_worker_id_string(&request.worker_id),
+            path = request.path,
+        );
+
+        match self
+            .list_worker_directory_internal(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(entries) => record.succeed(Ok(Response::new(
+                golem::workerexecutor::ListWorkerDirectoryResponse {
+                    result: Some(
+                        golem::workerexecutor::list_worker_directory_response::Result::Success(
+                            golem::workerexecutor::ListWorkerDirectorySuccessResponse {
+                                entries: entries
+                                    .into_iter()
+                                    .map(|entry| golem::worker::WorkerFileEntry {
+                                        name: entry.name,
+                                        is_directory: entry.is_directory,
+                                        size: entry.size,
+                                        read_only: entry.read_only,
+                                    })
+                                    .collect(),
+                            },
+                        ),
+                    ),
+                },
+            ))),
+            Err(err) => record.fail(
+                Ok(Response::new(
+                    golem::workerexecutor::ListWorkerDirectoryResponse {
+                        result: Some(
+                            golem::workerexecutor::list_worker_directory_response::Result::Failure(
+                                err.clone().into(),
+                            ),
+                        ),
+                    },
+                )),
+                &err,
+            ),
+        }
+    }
+
+    type ReadWorkerFileStream =
+        ReceiverStream<Result<golem::workerexecutor::ReadWorkerFileResponse, Status>>;
+
+    async fn read_worker_file(
+        &self,
+        request: Request<golem::workerexecutor::ReadWorkerFileRequest>,
+    ) -> ResponseResult<Self::ReadWorkerFileStream> {
+        let request = request.into_inner();
+        let record = recorded_grpc_request!(
+            "read_worker_file",
+            worker_id = proto_worker_id_string(&request.worker_id),
+            path = request.path,
+        );
+
+        let (tx, rx) = mpsc::channel(16);
+
+        match self
+            .read_worker_file_internal(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(mut file) => {
+                tokio::spawn(
+                    async move {
+                        let mut buffer = vec![0u8; READ_WORKER_FILE_CHUNK_SIZE];
+                        loop {
+                            let message = match file.read(&mut buffer).await {
+                                Ok(0) => break,
+                                Ok(n) => golem::workerexecutor::ReadWorkerFileResponse {
+                                    result: Some(
+                                        golem::workerexecutor::read_worker_file_response::Result::SuccessChunk(
+                                            buffer[..n].to_vec(),
+                                        ),
+                                    ),
+                                },
+                                Err(err) => golem::workerexecutor::ReadWorkerFileResponse {
+                                    result: Some(
+                                        golem::workerexecutor::read_worker_file_response::Result::Failure(
+                                            GolemError::runtime(format!(
+                                                "Failed to read file: {err}"
+                                            ))
+                                            .into(),
+                                        ),
+                                    ),
+                                },
+                            };
+                            let failed = matches!(
+                                message.result,
+                                Some(golem::workerexecutor::read_worker_file_response::Result::Failure(_))
+                            );
+                            if tx.send(Ok(message)).await.is_err() || failed {
+                                break;
+                            }
+                        }
+                    }
+                    .in_current_span(),
+                );
+                record.succeed(Ok(Response::new(ReceiverStream::new(rx))))
+            }
+            Err(err) => {
+                // The failure is sent as the only message of the stream, so the caller can
+                // handle it the same way as for the other requests (for example retrying on
+                // an invalid shard id)
+                let _ = tx
+                    .send(Ok(golem::workerexecutor::ReadWorkerFileResponse {
+                        result: Some(
+                            golem::workerexecutor::read_worker_file_response::Result::Failure(
+                                err.clone().into(),
+                            ),
+                        ),
+                    }))
+                    .await;
+                record.fail(Ok(Response::new(ReceiverStream::new(rx))), &err)
+            }
+        }
+    }
+
+    async fn get_pending_promises(
+        &self,
+        request: Request<golem::workerexecutor::GetPendingPromisesRequest>,
+    ) -> Result<Response<golem::workerexecutor::GetPendingPromisesResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_request!(
+            "get_pending_promises",
+            worker_id = proto_worker_id_string(&request.worker_id),
+        );
+
+        match self
+            .get_pending_promises_internal(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(promises) => record.succeed(Ok(Response::new(
+                golem::workerexecutor::GetPendingPromisesResponse {
+                    result: Some(
+                        golem::workerexecutor::get_pending_promises_response::Result::Success(
+                            golem::workerexecutor::GetPendingPromisesSuccessResponse {
+                                promises: promises
+                                    .into_iter()
+                                    .map(|promise| golem::worker::PendingPromise {
+                                        promise_id: Some(promise.promise_id.into()),
+                                        expires_at: promise.expires_at.map(|at| at.into()),
+                                        payload_type: promise.payload_type,
+                                    })
+                                    .collect(),
+                            },
+                        ),
+                    ),
+                },
+            ))),
+            Err(err) => record.fail(
+                Ok(Response::new(
+                    golem::workerexecutor::GetPendingPromisesResponse {
+                        result: Some(
+                            golem::workerexecutor::get_pending_promises_response::Result::Failure(
+                                err.clone().into(),
+                            ),
+                        ),
+                    },
+                )),
+                &err,
+            ),
+        }
+    }
 }
 
 trait GrpcInvokeRequest {
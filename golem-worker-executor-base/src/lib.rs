@@ -65,15 +65,25 @@ use crate::services::worker_enumeration::{
     DefaultWorkerEnumerationService, RunningWorkerEnumerationService,
     RunningWorkerEnumerationServiceDefault, WorkerEnumerationService,
 };
+use crate::services::worker_file_system::{
+    DefaultWorkerFileSystemService, WorkerFileSystemService,
+};
 use crate::services::worker_proxy::{RemoteWorkerProxy, WorkerProxy};
 use crate::services::{component, shard_manager, All};
 use crate::storage::blob::s3::S3BlobStorage;
+use crate::storage::blob::sqlite::SqliteBlobStorage;
 use crate::storage::blob::BlobStorage;
+use crate::storage::indexed::postgres::PostgresIndexedStorage;
 use crate::storage::indexed::redis::RedisIndexedStorage;
+use crate::storage::indexed::sqlite::SqliteIndexedStorage;
 use crate::storage::indexed::IndexedStorage;
 use crate::storage::keyvalue::memory::InMemoryKeyValueStorage;
+use crate::storage::keyvalue::postgres::PostgresKeyValueStorage;
 use crate::storage::keyvalue::redis::RedisKeyValueStorage;
+use crate::storage::keyvalue::sqlite::SqliteKeyValueStorage;
 use crate::storage::keyvalue::KeyValueStorage;
+use crate::storage::postgres::PostgresPool;
+use crate::storage::sqlite::SqlitePool;
 use crate::workerctx::WorkerCtx;
 
 /// The Bootstrap trait should be implemented by all Worker Executors to customize the initialization
@@ -103,6 +113,7 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
         shard_service: Arc<dyn ShardService + Send + Sync>,
         key_value_service: Arc<dyn KeyValueService + Send + Sync>,
         blob_store_service: Arc<dyn BlobStoreService + Send + Sync>,
+        worker_file_system_service: Arc<dyn WorkerFileSystemService + Send + Sync>,
         worker_activator: Arc<dyn WorkerActivator + Send + Sync>,
         oplog_service: Arc<dyn OplogService + Send + Sync>,
         scheduler_service: Arc<dyn SchedulerService + Send + Sync>,
@@ -152,8 +163,10 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
             "Worker executor is running",
         );
 
-        let (redis, key_value_storage): (
+        let (redis, postgres, sqlite, key_value_storage): (
             Option<RedisPool>,
+            Option<PostgresPool>,
+            Option<SqlitePool>,
             Arc<dyn KeyValueStorage + Send + Sync>,
         ) = match &golem_config.key_value_storage {
             KeyValueStorageConfig::Redis(redis) => {
@@ -163,11 +176,29 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
                     .map_err(|err| anyhow!(err))?;
                 let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
                     Arc::new(RedisKeyValueStorage::new(pool.clone()));
-                (Some(pool), key_value_storage)
+                (Some(pool), None, None, key_value_storage)
+            }
+            KeyValueStorageConfig::Postgres(postgres) => {
+                info!("Using Postgres for key-value storage at {}", postgres.host);
+                let pool = PostgresPool::configured(postgres)
+                    .await
+                    .map_err(|err| anyhow!(err))?;
+                let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
+                    Arc::new(PostgresKeyValueStorage::new(pool.clone()));
+                (None, Some(pool), None, key_value_storage)
+            }
+            KeyValueStorageConfig::Sqlite(sqlite) => {
+                info!("Using Sqlite for key-value storage at {}", sqlite.database);
+                let pool = SqlitePool::configured(sqlite)
+                    .await
+                    .map_err(|err| anyhow!(err))?;
+                let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
+                    Arc::new(SqliteKeyValueStorage::new(pool.clone()));
+                (None, None, Some(pool), key_value_storage)
             }
             KeyValueStorageConfig::InMemory => {
                 info!("Using in-memory key-value storage");
-                (None, Arc::new(InMemoryKeyValueStorage::new()))
+                (None, None, None, Arc::new(InMemoryKeyValueStorage::new()))
             }
         };
 
@@ -185,6 +216,34 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
                 let pool = RedisPool::configured(redis).await?;
                 Arc::new(RedisIndexedStorage::new(pool.clone()))
             }
+            IndexedStorageConfig::KVStorePostgres => {
+                info!("Using the same Postgres for indexed-storage");
+                let postgres = postgres.expect(
+                    "Postgres must be configured key-value storage when using KVStorePostgres",
+                );
+                Arc::new(PostgresIndexedStorage::new(postgres))
+            }
+            IndexedStorageConfig::Postgres(postgres) => {
+                info!("Using Postgres for indexed-storage at {}", postgres.host);
+                let pool = PostgresPool::configured(postgres)
+                    .await
+                    .map_err(|err| anyhow!(err))?;
+                Arc::new(PostgresIndexedStorage::new(pool))
+            }
+            IndexedStorageConfig::KVStoreSqlite => {
+                info!("Using the same Sqlite for indexed-storage");
+                let sqlite = sqlite
+                    .clone()
+                    .expect("Sqlite must be configured key-value storage when using KVStoreSqlite");
+                Arc::new(SqliteIndexedStorage::new(sqlite))
+            }
+            IndexedStorageConfig::Sqlite(sqlite) => {
+                info!("Using Sqlite for indexed-storage at {}", sqlite.database);
+                let pool = SqlitePool::configured(sqlite)
+                    .await
+                    .map_err(|err| anyhow!(err))?;
+                Arc::new(SqliteIndexedStorage::new(pool))
+            }
             IndexedStorageConfig::InMemory => {
                 info!("Using in-memory indexed storage");
                 Arc::new(storage::indexed::memory::InMemoryIndexedStorage::new())
@@ -206,6 +265,19 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
                         .map_err(|err| anyhow!(err))?,
                 )
             }
+            BlobStorageConfig::KVStoreSqlite => {
+                info!("Using the same Sqlite for blob storage");
+                let sqlite = sqlite
+                    .expect("Sqlite must be configured key-value storage when using KVStoreSqlite");
+                Arc::new(SqliteBlobStorage::new(sqlite))
+            }
+            BlobStorageConfig::Sqlite(sqlite) => {
+                info!("Using Sqlite for blob storage at {}", sqlite.database);
+                let pool = SqlitePool::configured(sqlite)
+                    .await
+                    .map_err(|err| anyhow!(err))?;
+                Arc::new(SqliteBlobStorage::new(pool))
+            }
             BlobStorageConfig::InMemory => {
                 info!("Using in-memory blob storage");
                 Arc::new(storage::blob::memory::InMemoryBlobStorage::new())
@@ -269,10 +341,14 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
             }
         };
 
+        let worker_file_system_service =
+            Arc::new(DefaultWorkerFileSystemService::new(blob_storage.clone()));
+
         let worker_service = Arc::new(DefaultWorkerService::new(
             key_value_storage.clone(),
             shard_service.clone(),
             oplog_service.clone(),
+            worker_file_system_service.clone(),
         ));
         let worker_enumeration_service = Arc::new(DefaultWorkerEnumerationService::new(
             worker_service.clone(),
@@ -343,6 +419,7 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
                 shard_service,
                 key_value_service,
                 blob_store_service,
+                worker_file_system_service,
                 lazy_worker_activator.clone(),
                 oplog_service,
                 scheduler_service,
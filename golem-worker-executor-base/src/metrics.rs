@@ -282,6 +282,8 @@ pub mod promises {
             "Number of scheduled promise completions"
         )
         .unwrap();
+        static ref PROMISES_EXPIRED_TOTAL: Counter =
+            register_counter!("promises_expired_total", "Number of expired promises").unwrap();
     }
 
     pub fn record_promise_created() {
@@ -291,6 +293,42 @@ pub mod promises {
     pub fn record_scheduled_promise_completed() {
         PROMISES_SCHEDULED_COMPLETE_TOTAL.inc();
     }
+
+    pub fn record_promise_expired() {
+        PROMISES_EXPIRED_TOTAL.inc();
+    }
+}
+
+pub mod retention {
+    use lazy_static::lazy_static;
+    use prometheus::*;
+
+    lazy_static! {
+        static ref RETENTION_DELETED_WORKERS_TOTAL: Counter = register_counter!(
+            "retention_deleted_workers_total",
+            "Number of workers deleted by retention policies"
+        )
+        .unwrap();
+        static ref RETENTION_RECLAIMED_OPLOG_ENTRIES_TOTAL: Counter = register_counter!(
+            "retention_reclaimed_oplog_entries_total",
+            "Number of oplog entries deleted by retention policies"
+        )
+        .unwrap();
+        static ref OPLOG_ARCHIVE_TOTAL: Counter = register_counter!(
+            "oplog_archive_total",
+            "Number of scheduled transfers of oplog entries to a lower archive layer"
+        )
+        .unwrap();
+    }
+
+    pub fn record_worker_deleted_by_retention(oplog_entries: u64) {
+        RETENTION_DELETED_WORKERS_TOTAL.inc();
+        RETENTION_RECLAIMED_OPLOG_ENTRIES_TOTAL.inc_by(oplog_entries as f64);
+    }
+
+    pub fn record_oplog_archived() {
+        OPLOG_ARCHIVE_TOTAL.inc();
+    }
 }
 
 pub mod sharding {
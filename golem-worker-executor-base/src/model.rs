@@ -17,13 +17,16 @@ use std::fmt::{Debug, Display, Formatter};
 use std::sync::Arc;
 
 use bincode::{Decode, Encode};
+use bytes::Bytes;
 use golem_wasm_rpc::Value;
 use serde::{Deserialize, Serialize};
 use wasmtime::Trap;
 
-use golem_common::model::oplog::WorkerError;
+use golem_common::model::oplog::{OplogIndex, WorkerError};
 use golem_common::model::regions::DeletedRegions;
-use golem_common::model::{ShardAssignment, ShardId, WorkerId, WorkerStatusRecord};
+use golem_common::model::{
+    parse_function_name, ParsedFunctionName, ShardAssignment, ShardId, WorkerId, WorkerStatusRecord,
+};
 
 use crate::error::GolemError;
 use crate::workerctx::WorkerCtx;
@@ -74,6 +77,8 @@ pub struct WorkerConfig {
     pub args: Vec<String>,
     pub env: Vec<(String, String)>,
     pub deleted_regions: DeletedRegions,
+    pub last_snapshot: Option<OplogIndex>,
+    pub initial_files: Vec<InitialFile>,
 }
 
 impl WorkerConfig {
@@ -83,6 +88,8 @@ impl WorkerConfig {
         worker_args: Vec<String>,
         mut worker_env: Vec<(String, String)>,
         deleted_regions: DeletedRegions,
+        last_snapshot: Option<OplogIndex>,
+        initial_files: Vec<InitialFile>,
     ) -> WorkerConfig {
         let worker_name = worker_id.worker_name.clone();
         let component_id = worker_id.component_id;
@@ -94,10 +101,44 @@ impl WorkerConfig {
             args: worker_args,
             env: worker_env,
             deleted_regions,
+            last_snapshot,
+            initial_files,
         }
     }
 }
 
+/// A file of the component which is copied into the file system of the worker when it is created.
+/// The path is relative to the root of the worker's file system.
+#[derive(Clone, Debug)]
+pub struct InitialFile {
+    pub path: String,
+    pub read_only: bool,
+    pub content: Bytes,
+}
+
+/// The functions exported by a component version
+#[derive(Clone, Debug)]
+pub struct ComponentMetadata {
+    pub version: u64,
+    pub exported_functions: Vec<ParsedFunctionName>,
+}
+
+impl ComponentMetadata {
+    /// Checks whether the component exports the function with the given full name, using the
+    /// same name resolution as invocations do
+    pub fn exports_function(&self, full_function_name: &str) -> bool {
+        let parsed = parse_function_name(full_function_name);
+        let parsed_static = parsed.method_as_static();
+        self.exported_functions.iter().any(|function| {
+            *function == parsed
+                || Some(function) == parsed_static.as_ref()
+                || (parsed.interface.is_some()
+                    && function.interface == parsed.interface
+                    && parsed.function.starts_with("[drop]"))
+        })
+    }
+}
+
 /// Information about the available resources for the worker.
 #[derive(Debug, Clone)]
 pub struct CurrentResourceLimits {
@@ -284,4 +325,32 @@ mod tests {
         println!("hash: {:?}", hash);
         assert_eq!(hash, -6692039695739768661);
     }
+
+    #[test]
+    fn component_metadata_exports_function() {
+        let metadata = ComponentMetadata {
+            version: 0,
+            exported_functions: vec![
+                ParsedFunctionName::new(None, "run".to_string()),
+                ParsedFunctionName::new(Some("golem:it/api".to_string()), "bump".to_string()),
+                ParsedFunctionName::new(
+                    Some("golem:it/api".to_string()),
+                    "[constructor]cart".to_string(),
+                ),
+                ParsedFunctionName::new(
+                    Some("golem:it/api".to_string()),
+                    "[static]cart.merge".to_string(),
+                ),
+            ],
+        };
+
+        assert!(metadata.exports_function("run"));
+        assert!(metadata.exports_function("golem:it/api/bump"));
+        assert!(metadata.exports_function("golem:it/api/cart/new"));
+        assert!(metadata.exports_function("golem:it/api/cart/drop"));
+        assert!(metadata.exports_function("golem:it/api/cart/merge"));
+        assert!(!metadata.exports_function("golem:it/api/get-bumps"));
+        assert!(!metadata.exports_function("golem:it/other/bump"));
+        assert!(!metadata.exports_function("bump"));
+    }
 }
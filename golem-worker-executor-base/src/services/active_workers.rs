@@ -91,6 +91,11 @@ impl<Ctx: WorkerCtx> ActiveWorkers<Ctx> {
         self.cache.get_or_insert_pending(&worker_id, f1, f2).await
     }
 
+    /// Gets an active worker without activating it. A worker getting activated is awaited.
+    pub async fn get(&self, worker_id: &WorkerId) -> Option<Arc<Worker<Ctx>>> {
+        self.cache.get(worker_id).await
+    }
+
     pub fn remove(&self, worker_id: &WorkerId) {
         self.cache.remove(worker_id)
     }
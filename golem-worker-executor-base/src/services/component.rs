@@ -17,18 +17,22 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use bytes::Bytes;
 use futures_util::TryStreamExt;
 use golem_api_grpc::proto::golem::component::component_service_client::ComponentServiceClient;
 use golem_api_grpc::proto::golem::component::ComponentError;
 use golem_api_grpc::proto::golem::component::{
-    download_component_response, get_component_metadata_response, DownloadComponentRequest,
-    GetLatestComponentRequest,
+    download_component_response, export, get_component_metadata_response, ComponentFilePermissions,
+    DownloadComponentFileRequest, DownloadComponentRequest, GetLatestComponentRequest,
+    GetVersionedComponentRequest,
 };
 use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode, SimpleCache};
 use golem_common::config::RetryConfig;
 use golem_common::metrics::external_calls::record_external_call_response_size_bytes;
-use golem_common::model::ComponentId;
+use golem_common::model::{ComponentId, ParsedFunctionName};
 use golem_common::retries::with_retries;
+use golem_wasm_ast::analysis::{AnalysedExport, AnalysisContext, AnalysisFailure};
+use golem_wasm_ast::IgnoreAllButMetadata;
 use http::Uri;
 use prost::Message;
 use tracing::{debug, info, warn};
@@ -39,13 +43,17 @@ use wasmtime::Engine;
 use crate::error::GolemError;
 use crate::grpc::{authorised_grpc_request, is_grpc_retriable, GrpcError, UriBackConversion};
 use crate::metrics::component::record_compilation_time;
+use crate::model::{ComponentMetadata, InitialFile};
 use crate::services::compiled_component;
 use crate::services::compiled_component::CompiledComponentService;
 use crate::services::golem_config::{
-    CompiledComponentServiceConfig, ComponentCacheConfig, ComponentServiceConfig,
+    CompiledComponentServiceConfig, ComponentCacheConfig, ComponentServiceConfig, RetentionPolicy,
 };
 use crate::storage::blob::BlobStorage;
 
+/// How long a retention policy fetched from the Golem Component API is used before fetching it again
+const RETENTION_POLICY_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Service for downloading a specific Golem component from the Golem Component API
 #[async_trait]
 pub trait ComponentService {
@@ -63,6 +71,27 @@ pub trait ComponentService {
     ) -> Result<(u64, Component), GolemError>;
 
     async fn get_latest_version(&self, component_id: &ComponentId) -> Result<u64, GolemError>;
+
+    /// Gets the files to copy into the file system of new workers of the given component version
+    async fn get_initial_files(
+        &self,
+        component_id: &ComponentId,
+        component_version: u64,
+    ) -> Result<Vec<InitialFile>, GolemError>;
+
+    /// Gets the metadata of the given component version, or of the latest version if no version
+    /// is given
+    async fn get_metadata(
+        &self,
+        component_id: &ComponentId,
+        forced_version: Option<u64>,
+    ) -> Result<ComponentMetadata, GolemError>;
+
+    /// Gets the retention policy stored with the latest version of the given component, if any
+    async fn get_retention_policy(
+        &self,
+        component_id: &ComponentId,
+    ) -> Result<Option<RetentionPolicy>, GolemError>;
 }
 
 pub async fn configured(
@@ -106,6 +135,9 @@ struct ComponentKey {
 pub struct ComponentServiceGrpc {
     endpoint: Uri,
     component_cache: Cache<ComponentKey, (), Component, GolemError>,
+    initial_files_cache: Cache<ComponentKey, (), Vec<InitialFile>, GolemError>,
+    component_metadata_cache: Cache<ComponentKey, (), ComponentMetadata, GolemError>,
+    retention_policy_cache: Cache<ComponentId, (), (Instant, Option<RetentionPolicy>), GolemError>,
     access_token: Uuid,
     retry_config: RetryConfig,
     compiled_component_service: Arc<dyn CompiledComponentService + Send + Sync>,
@@ -125,6 +157,9 @@ impl ComponentServiceGrpc {
         Self {
             endpoint,
             component_cache: create_component_cache(max_capacity, time_to_idle),
+            initial_files_cache: create_initial_files_cache(max_capacity, time_to_idle),
+            component_metadata_cache: create_component_metadata_cache(max_capacity, time_to_idle),
+            retention_policy_cache: create_retention_policy_cache(max_capacity),
             access_token,
             retry_config,
             compiled_component_service,
@@ -242,6 +277,126 @@ impl ComponentService for ComponentServiceGrpc {
         )
         .await
     }
+
+    async fn get_initial_files(
+        &self,
+        component_id: &ComponentId,
+        component_version: u64,
+    ) -> Result<Vec<InitialFile>, GolemError> {
+        let key = ComponentKey {
+            component_id: component_id.clone(),
+            component_version,
+        };
+        let component_id = component_id.clone();
+        let endpoint = self.endpoint.clone();
+        let access_token = self.access_token;
+        let retry_config = self.retry_config.clone();
+        let max_component_size = self.max_component_size;
+        self.initial_files_cache
+            .get_or_insert_simple(&key, || {
+                Box::pin(async move {
+                    let files = get_initial_files_via_grpc(
+                        &endpoint,
+                        &access_token,
+                        &retry_config,
+                        &component_id,
+                        component_version,
+                    )
+                    .await?;
+
+                    let mut result = Vec::new();
+                    for file in files {
+                        let content = download_file_via_grpc(
+                            &endpoint,
+                            &access_token,
+                            &retry_config,
+                            &component_id,
+                            component_version,
+                            &file.key,
+                            max_component_size,
+                        )
+                        .await?;
+
+                        result.push(InitialFile {
+                            read_only: file.permissions() == ComponentFilePermissions::ReadOnly,
+                            path: file.path,
+                            content: Bytes::from(content),
+                        });
+                    }
+
+                    Ok(result)
+                })
+            })
+            .await
+    }
+
+    async fn get_metadata(
+        &self,
+        component_id: &ComponentId,
+        forced_version: Option<u64>,
+    ) -> Result<ComponentMetadata, GolemError> {
+        let version = match forced_version {
+            Some(version) => version,
+            None => self.get_latest_version(component_id).await?,
+        };
+        let key = ComponentKey {
+            component_id: component_id.clone(),
+            component_version: version,
+        };
+        let component_id = component_id.clone();
+        let endpoint = self.endpoint.clone();
+        let access_token = self.access_token;
+        let retry_config = self.retry_config.clone();
+        self.component_metadata_cache
+            .get_or_insert_simple(&key, || {
+                Box::pin(async move {
+                    get_metadata_via_grpc(
+                        &endpoint,
+                        &access_token,
+                        &retry_config,
+                        &component_id,
+                        version,
+                    )
+                    .await
+                })
+            })
+            .await
+    }
+
+    async fn get_retention_policy(
+        &self,
+        component_id: &ComponentId,
+    ) -> Result<Option<RetentionPolicy>, GolemError> {
+        // Cached policies are refreshed periodically, so changes to the policy apply to workers
+        // failing or exiting shortly afterwards
+        if let Some((fetched_at, policy)) = self.retention_policy_cache.get(component_id).await {
+            if fetched_at.elapsed() < RETENTION_POLICY_REFRESH_INTERVAL {
+                return Ok(policy);
+            }
+            self.retention_policy_cache.remove(component_id);
+        }
+
+        let component_id = component_id.clone();
+        let endpoint = self.endpoint.clone();
+        let access_token = self.access_token;
+        let retry_config = self.retry_config.clone();
+        let (_, policy) = self
+            .retention_policy_cache
+            .get_or_insert_simple(&component_id.clone(), || {
+                Box::pin(async move {
+                    let policy = get_retention_policy_via_grpc(
+                        &endpoint,
+                        &access_token,
+                        &retry_config,
+                        &component_id,
+                    )
+                    .await?;
+                    Ok((Instant::now(), policy))
+                })
+            })
+            .await?;
+        Ok(policy)
+    }
 }
 
 async fn download_via_grpc(
@@ -368,6 +523,273 @@ async fn get_latest_version_via_grpc(
     .map_err(|error| grpc_get_latest_version_error(error, component_id))
 }
 
+async fn get_initial_files_via_grpc(
+    endpoint: &Uri,
+    access_token: &Uuid,
+    retry_config: &RetryConfig,
+    component_id: &ComponentId,
+    component_version: u64,
+) -> Result<Vec<golem_api_grpc::proto::golem::component::InitialComponentFile>, GolemError> {
+    let desc = format!("Getting the initial files of {component_id}");
+    debug!("{}", &desc);
+    with_retries(
+        &desc,
+        "components",
+        "get_initial_files",
+        retry_config,
+        &(
+            endpoint.clone(),
+            component_id.clone(),
+            access_token.to_owned(),
+        ),
+        |(endpoint, component_id, access_token)| {
+            Box::pin(async move {
+                let mut client = ComponentServiceClient::connect(endpoint.as_http_02()).await?;
+
+                let request = authorised_grpc_request(
+                    GetVersionedComponentRequest {
+                        component_id: Some(component_id.clone().into()),
+                        version: component_version,
+                    },
+                    access_token,
+                );
+
+                let response = client.get_component_metadata(request).await?.into_inner();
+
+                let len = response.encoded_len();
+                let files = match response.result {
+                    None => Err("Empty response".to_string().into()),
+                    Some(get_component_metadata_response::Result::Success(response)) => response
+                        .component
+                        .map(|component| component.files)
+                        .ok_or(GrpcError::Unexpected("Undefined component".to_string())),
+                    Some(get_component_metadata_response::Result::Error(error)) => {
+                        Err(GrpcError::Domain(error))
+                    }
+                }?;
+
+                record_external_call_response_size_bytes("components", "get_initial_files", len);
+
+                Ok(files)
+            })
+        },
+        is_grpc_retriable::<ComponentError>,
+    )
+    .await
+    .map_err(|error| grpc_component_download_error(error, component_id, component_version))
+}
+
+async fn get_metadata_via_grpc(
+    endpoint: &Uri,
+    access_token: &Uuid,
+    retry_config: &RetryConfig,
+    component_id: &ComponentId,
+    component_version: u64,
+) -> Result<ComponentMetadata, GolemError> {
+    let desc = format!("Getting metadata of {component_id}");
+    debug!("{}", &desc);
+    with_retries(
+        &desc,
+        "components",
+        "get_metadata",
+        retry_config,
+        &(
+            endpoint.clone(),
+            component_id.clone(),
+            access_token.to_owned(),
+        ),
+        |(endpoint, component_id, access_token)| {
+            Box::pin(async move {
+                let mut client = ComponentServiceClient::connect(endpoint.as_http_02()).await?;
+
+                let request = authorised_grpc_request(
+                    GetVersionedComponentRequest {
+                        component_id: Some(component_id.clone().into()),
+                        version: component_version,
+                    },
+                    access_token,
+                );
+
+                let response = client.get_component_metadata(request).await?.into_inner();
+
+                let len = response.encoded_len();
+                let exports = match response.result {
+                    None => Err("Empty response".to_string().into()),
+                    Some(get_component_metadata_response::Result::Success(response)) => response
+                        .component
+                        .and_then(|component| component.metadata)
+                        .map(|metadata| metadata.exports)
+                        .ok_or(GrpcError::Unexpected("Undefined component".to_string())),
+                    Some(get_component_metadata_response::Result::Error(error)) => {
+                        Err(GrpcError::Domain(error))
+                    }
+                }?;
+
+                record_external_call_response_size_bytes("components", "get_metadata", len);
+
+                let exported_functions = exports
+                    .into_iter()
+                    .flat_map(|export| match export.export {
+                        Some(export::Export::Function(function)) => {
+                            vec![ParsedFunctionName::new(None, function.name)]
+                        }
+                        Some(export::Export::Instance(instance)) => instance
+                            .functions
+                            .into_iter()
+                            .map(|function| {
+                                ParsedFunctionName::new(Some(instance.name.clone()), function.name)
+                            })
+                            .collect(),
+                        None => vec![],
+                    })
+                    .collect();
+
+                Ok(ComponentMetadata {
+                    version: component_version,
+                    exported_functions,
+                })
+            })
+        },
+        is_grpc_retriable::<ComponentError>,
+    )
+    .await
+    .map_err(|error| grpc_component_download_error(error, component_id, component_version))
+}
+
+async fn get_retention_policy_via_grpc(
+    endpoint: &Uri,
+    access_token: &Uuid,
+    retry_config: &RetryConfig,
+    component_id: &ComponentId,
+) -> Result<Option<RetentionPolicy>, GolemError> {
+    let desc = format!("Getting the retention policy of {component_id}");
+    debug!("{}", &desc);
+    with_retries(
+        &desc,
+        "components",
+        "get_retention_policy",
+        retry_config,
+        &(
+            endpoint.clone(),
+            component_id.clone(),
+            access_token.to_owned(),
+        ),
+        |(endpoint, component_id, access_token)| {
+            Box::pin(async move {
+                let mut client = ComponentServiceClient::connect(endpoint.as_http_02()).await?;
+
+                let request = authorised_grpc_request(
+                    GetLatestComponentRequest {
+                        component_id: Some(component_id.clone().into()),
+                    },
+                    access_token,
+                );
+
+                let response = client
+                    .get_latest_component_metadata(request)
+                    .await?
+                    .into_inner();
+
+                let len = response.encoded_len();
+                let policy = match response.result {
+                    None => Err("Empty response".to_string().into()),
+                    Some(get_component_metadata_response::Result::Success(response)) => response
+                        .component
+                        .map(|component| component.retention_policy)
+                        .ok_or(GrpcError::Unexpected("Undefined component".to_string())),
+                    Some(get_component_metadata_response::Result::Error(error)) => {
+                        Err(GrpcError::Domain(error))
+                    }
+                }?;
+
+                record_external_call_response_size_bytes("components", "get_retention_policy", len);
+
+                Ok(policy.map(|policy| RetentionPolicy {
+                    delete_exited_after: policy
+                        .delete_exited_after_seconds
+                        .map(Duration::from_secs),
+                    delete_failed_after: policy
+                        .delete_failed_after_seconds
+                        .map(Duration::from_secs),
+                    archive_failed_after: policy
+                        .archive_failed_after_seconds
+                        .map(Duration::from_secs),
+                }))
+            })
+        },
+        is_grpc_retriable::<ComponentError>,
+    )
+    .await
+    .map_err(|error| grpc_get_retention_policy_error(error, component_id))
+}
+
+async fn download_file_via_grpc(
+    endpoint: &Uri,
+    access_token: &Uuid,
+    retry_config: &RetryConfig,
+    component_id: &ComponentId,
+    component_version: u64,
+    key: &str,
+    max_component_size: usize,
+) -> Result<Vec<u8>, GolemError> {
+    let desc = format!("Downloading file {key} of component {component_id}");
+    debug!("{}", &desc);
+    with_retries(
+        &desc,
+        "components",
+        "download_file",
+        retry_config,
+        &(
+            endpoint.clone(),
+            component_id.clone(),
+            access_token.to_owned(),
+            key.to_string(),
+        ),
+        |(endpoint, component_id, access_token, key)| {
+            Box::pin(async move {
+                let mut client = ComponentServiceClient::connect(endpoint.as_http_02())
+                    .await?
+                    .max_decoding_message_size(max_component_size);
+
+                let request = authorised_grpc_request(
+                    DownloadComponentFileRequest {
+                        component_id: Some(component_id.clone().into()),
+                        key: key.clone(),
+                    },
+                    access_token,
+                );
+
+                let response = client.download_component_file(request).await?.into_inner();
+
+                let chunks = response.into_stream().try_collect::<Vec<_>>().await?;
+                let bytes = chunks
+                    .into_iter()
+                    .map(|chunk| match chunk.result {
+                        None => Err("Empty response".to_string().into()),
+                        Some(download_component_response::Result::SuccessChunk(chunk)) => Ok(chunk),
+                        Some(download_component_response::Result::Error(error)) => {
+                            Err(GrpcError::Domain(error))
+                        }
+                    })
+                    .collect::<Result<Vec<Vec<u8>>, GrpcError<ComponentError>>>()?;
+
+                let bytes: Vec<u8> = bytes.into_iter().flatten().collect();
+
+                record_external_call_response_size_bytes(
+                    "components",
+                    "download_file",
+                    bytes.len(),
+                );
+
+                Ok(bytes)
+            })
+        },
+        is_grpc_retriable::<ComponentError>,
+    )
+    .await
+    .map_err(|error| grpc_component_download_error(error, component_id, component_version))
+}
+
 fn grpc_component_download_error(
     error: GrpcError<ComponentError>,
     component_id: &ComponentId,
@@ -390,6 +812,15 @@ fn grpc_get_latest_version_error(
     }
 }
 
+fn grpc_get_retention_policy_error(
+    error: GrpcError<ComponentError>,
+    component_id: &ComponentId,
+) -> GolemError {
+    GolemError::runtime(format!(
+        "Failed to get the retention policy of component {component_id}: {error}"
+    ))
+}
+
 fn create_component_cache(
     max_capacity: usize,
     time_to_idle: Duration,
@@ -405,6 +836,87 @@ fn create_component_cache(
     )
 }
 
+fn create_initial_files_cache(
+    max_capacity: usize,
+    time_to_idle: Duration,
+) -> Cache<ComponentKey, (), Vec<InitialFile>, GolemError> {
+    Cache::new(
+        Some(max_capacity),
+        FullCacheEvictionMode::LeastRecentlyUsed(1),
+        BackgroundEvictionMode::OlderThan {
+            ttl: time_to_idle,
+            period: Duration::from_secs(60),
+        },
+        "initial_files",
+    )
+}
+
+fn create_component_metadata_cache(
+    max_capacity: usize,
+    time_to_idle: Duration,
+) -> Cache<ComponentKey, (), ComponentMetadata, GolemError> {
+    Cache::new(
+        Some(max_capacity),
+        FullCacheEvictionMode::LeastRecentlyUsed(1),
+        BackgroundEvictionMode::OlderThan {
+            ttl: time_to_idle,
+            period: Duration::from_secs(60),
+        },
+        "component_metadata",
+    )
+}
+
+fn create_retention_policy_cache(
+    max_capacity: usize,
+) -> Cache<ComponentId, (), (Instant, Option<RetentionPolicy>), GolemError> {
+    Cache::new(
+        Some(max_capacity),
+        FullCacheEvictionMode::LeastRecentlyUsed(1),
+        BackgroundEvictionMode::OlderThan {
+            ttl: RETENTION_POLICY_REFRESH_INTERVAL,
+            period: Duration::from_secs(60),
+        },
+        "retention_policy",
+    )
+}
+
+/// Collects the names of the functions exported by a WASM component
+fn analyse_exported_functions(
+    component_id: &ComponentId,
+    component_version: u64,
+    bytes: &[u8],
+) -> Result<Vec<ParsedFunctionName>, GolemError> {
+    let component = golem_wasm_ast::component::Component::<IgnoreAllButMetadata>::from_bytes(bytes)
+        .map_err(|reason| GolemError::ComponentParseFailed {
+            component_id: component_id.clone(),
+            component_version,
+            reason,
+        })?;
+    let exports = AnalysisContext::new(component)
+        .get_top_level_exports()
+        .map_err(
+            |AnalysisFailure::Failed(reason)| GolemError::ComponentParseFailed {
+                component_id: component_id.clone(),
+                component_version,
+                reason: reason.to_string(),
+            },
+        )?;
+
+    Ok(exports
+        .into_iter()
+        .flat_map(|export| match export {
+            AnalysedExport::Function(function) => {
+                vec![ParsedFunctionName::new(None, function.name)]
+            }
+            AnalysedExport::Instance(instance) => instance
+                .funcs
+                .into_iter()
+                .map(|function| ParsedFunctionName::new(Some(instance.name.clone()), function.name))
+                .collect(),
+        })
+        .collect())
+}
+
 impl From<std::io::Error> for GolemError {
     fn from(value: std::io::Error) -> Self {
         GolemError::Unknown {
@@ -416,6 +928,7 @@ impl From<std::io::Error> for GolemError {
 pub struct ComponentServiceLocalFileSystem {
     root: PathBuf,
     component_cache: Cache<ComponentKey, (), Component, GolemError>,
+    component_metadata_cache: Cache<ComponentKey, (), ComponentMetadata, GolemError>,
     compiled_component_service: Arc<dyn CompiledComponentService + Send + Sync>,
 }
 
@@ -432,6 +945,7 @@ impl ComponentServiceLocalFileSystem {
         Self {
             root: root.to_path_buf(),
             component_cache: create_component_cache(max_capacity, time_to_idle),
+            component_metadata_cache: create_component_metadata_cache(max_capacity, time_to_idle),
             compiled_component_service,
         }
     }
@@ -592,6 +1106,90 @@ impl ComponentService for ComponentServiceLocalFileSystem {
             }),
         }
     }
+
+    async fn get_initial_files(
+        &self,
+        component_id: &ComponentId,
+        component_version: u64,
+    ) -> Result<Vec<InitialFile>, GolemError> {
+        // The initial files of a locally stored component are the files under the `read-only`
+        // and `read-write` subdirectories of the `<component-id>-<version>.files` directory
+        let files_root = self
+            .root
+            .join(format!("{component_id}-{component_version}.files"));
+        let mut result = Vec::new();
+        for (dir, read_only) in [("read-only", true), ("read-write", false)] {
+            let base = files_root.join(dir);
+            if !tokio::fs::try_exists(&base).await? {
+                continue;
+            }
+
+            let mut pending = vec![base.clone()];
+            while let Some(current) = pending.pop() {
+                let mut reader = tokio::fs::read_dir(&current).await?;
+                while let Some(entry) = reader.next_entry().await? {
+                    let path = entry.path();
+                    if entry.file_type().await?.is_dir() {
+                        pending.push(path);
+                    } else {
+                        let relative = path
+                            .strip_prefix(&base)
+                            .map_err(|err| GolemError::runtime(err.to_string()))?
+                            .components()
+                            .map(|component| component.as_os_str().to_string_lossy().to_string())
+                            .collect::<Vec<_>>()
+                            .join("/");
+                        result.push(InitialFile {
+                            path: relative,
+                            read_only,
+                            content: Bytes::from(tokio::fs::read(&path).await?),
+                        });
+                    }
+                }
+            }
+        }
+
+        result.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(result)
+    }
+
+    async fn get_metadata(
+        &self,
+        component_id: &ComponentId,
+        forced_version: Option<u64>,
+    ) -> Result<ComponentMetadata, GolemError> {
+        let version = match forced_version {
+            Some(version) => version,
+            None => self.get_latest_version(component_id).await?,
+        };
+        let key = ComponentKey {
+            component_id: component_id.clone(),
+            component_version: version,
+        };
+        let component_id = component_id.clone();
+        let path = self.root.join(format!("{}-{}.wasm", component_id, version));
+        self.component_metadata_cache
+            .get_or_insert_simple(&key, || {
+                Box::pin(async move {
+                    let bytes = tokio::fs::read(path).await?;
+                    let exported_functions =
+                        analyse_exported_functions(&component_id, version, &bytes)?;
+                    Ok(ComponentMetadata {
+                        version,
+                        exported_functions,
+                    })
+                })
+            })
+            .await
+    }
+
+    async fn get_retention_policy(
+        &self,
+        _component_id: &ComponentId,
+    ) -> Result<Option<RetentionPolicy>, GolemError> {
+        // Components stored on the local file system use the executor's default policy
+        Ok(None)
+    }
 }
 
 #[cfg(any(feature = "mocks", test))]
@@ -634,4 +1232,27 @@ impl ComponentService for ComponentServiceMock {
     async fn get_latest_version(&self, _component_id: &ComponentId) -> Result<u64, GolemError> {
         unimplemented!()
     }
+
+    async fn get_initial_files(
+        &self,
+        _component_id: &ComponentId,
+        _component_version: u64,
+    ) -> Result<Vec<InitialFile>, GolemError> {
+        unimplemented!()
+    }
+
+    async fn get_metadata(
+        &self,
+        _component_id: &ComponentId,
+        _forced_version: Option<u64>,
+    ) -> Result<ComponentMetadata, GolemError> {
+        unimplemented!()
+    }
+
+    async fn get_retention_policy(
+        &self,
+        _component_id: &ComponentId,
+    ) -> Result<Option<RetentionPolicy>, GolemError> {
+        unimplemented!()
+    }
 }
@@ -41,6 +41,9 @@ pub struct GolemConfig {
     pub suspend: SuspendConfig,
     pub active_workers: ActiveWorkersConfig,
     pub scheduler: SchedulerConfig,
+    pub snapshot: SnapshotConfig,
+    pub retention: RetentionConfig,
+    pub promises: PromisesConfig,
     pub public_worker_api: WorkerServiceGrpcConfig,
     pub enable_tracing_console: bool,
     pub enable_json_log: bool,
@@ -215,6 +218,70 @@ pub struct SchedulerConfig {
     pub refresh_interval: Duration,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+pub struct SnapshotConfig {
+    pub policy: SnapshotPolicy,
+    /// If enabled, the oplog entries preceding a saved snapshot are deleted from the oplog
+    pub compact_oplog: bool,
+}
+
+/// Determines when the executor saves a snapshot of workers exporting the
+/// `golem:api/save-snapshot` interface. Snapshots are only taken between invocations.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum SnapshotPolicy {
+    Disabled,
+    /// Take a snapshot when the given number of entries were added to the oplog since the last one
+    OplogSize(SnapshotOplogSizeConfig),
+    /// Take a snapshot when the given time elapsed since the last one
+    Periodic(SnapshotPeriodicConfig),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct SnapshotOplogSizeConfig {
+    pub entries: u64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct SnapshotPeriodicConfig {
+    #[serde(with = "humantime_serde")]
+    pub period: Duration,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct RetentionConfig {
+    /// The retention policy of components which have none stored with them in the component
+    /// service
+    #[serde(default)]
+    pub default: RetentionPolicy,
+}
+
+/// Determines how long workers that will never run again are kept in the storage layers.
+/// Every limit is optional, workers are kept forever by default.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct RetentionPolicy {
+    /// Workers are deleted this long after they exited
+    #[serde(default, with = "humantime_serde")]
+    pub delete_exited_after: Option<Duration>,
+    /// Workers are deleted this long after they failed
+    #[serde(default, with = "humantime_serde")]
+    pub delete_failed_after: Option<Duration>,
+    /// The oplog of failed workers is moved to the last (cheapest) archive layer this long
+    /// after they failed, instead of following `oplog.archive_interval`
+    #[serde(default, with = "humantime_serde")]
+    pub archive_failed_after: Option<Duration>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct PromisesConfig {
+    /// Promises created by workers fail with a timeout error if they are not completed
+    /// this long after their creation. This is only the default of the executor, workers can
+    /// override it per promise with `golem-create-promise-with-options`. Promises never expire
+    /// by default.
+    #[serde(default, with = "humantime_serde")]
+    pub expire_after: Option<Duration>,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct OplogConfig {
     pub max_operations_before_commit: u64,
@@ -230,6 +297,8 @@ pub struct OplogConfig {
 #[serde(tag = "type", content = "config")]
 pub enum KeyValueStorageConfig {
     Redis(RedisConfig),
+    Postgres(DbPostgresConfig),
+    Sqlite(DbSqliteConfig),
     InMemory,
 }
 
@@ -238,14 +307,37 @@ pub enum KeyValueStorageConfig {
 pub enum IndexedStorageConfig {
     KVStoreRedis,
     Redis(RedisConfig),
+    KVStorePostgres,
+    Postgres(DbPostgresConfig),
+    KVStoreSqlite,
+    Sqlite(DbSqliteConfig),
     InMemory,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+pub struct DbPostgresConfig {
+    pub host: String,
+    pub database: String,
+    pub username: String,
+    pub password: String,
+    pub port: u16,
+    pub max_connections: u32,
+    pub schema: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DbSqliteConfig {
+    pub database: String,
+    pub max_connections: u32,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(tag = "type", content = "config")]
 pub enum BlobStorageConfig {
     S3(S3BlobStorageConfig),
     LocalFileSystem(LocalFileSystemBlobStorageConfig),
+    KVStoreSqlite,
+    Sqlite(DbSqliteConfig),
     InMemory,
 }
 
@@ -259,6 +351,7 @@ pub struct S3BlobStorageConfig {
     pub custom_data_bucket: String,
     pub oplog_payload_bucket: String,
     pub compressed_oplog_buckets: Vec<String>,
+    pub worker_file_system_bucket: String,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -281,6 +374,9 @@ impl Default for GolemConfig {
             oplog: OplogConfig::default(),
             suspend: SuspendConfig::default(),
             scheduler: SchedulerConfig::default(),
+            snapshot: SnapshotConfig::default(),
+            retention: RetentionConfig::default(),
+            promises: PromisesConfig::default(),
             active_workers: ActiveWorkersConfig::default(),
             public_worker_api: WorkerServiceGrpcConfig::default(),
             enable_tracing_console: false,
@@ -352,6 +448,7 @@ impl Default for S3BlobStorageConfig {
             object_prefix: "".to_string(),
             aws_endpoint_url: None,
             compressed_oplog_buckets: vec!["oplog-archive-1".to_string()],
+            worker_file_system_bucket: "worker-file-system".to_string(),
         }
     }
 }
@@ -410,6 +507,15 @@ impl Default for SchedulerConfig {
     }
 }
 
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            policy: SnapshotPolicy::Disabled,
+            compact_oplog: true,
+        }
+    }
+}
+
 impl Default for WorkerServiceGrpcConfig {
     fn default() -> Self {
         Self {
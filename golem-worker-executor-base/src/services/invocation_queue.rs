@@ -21,6 +21,7 @@ use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use tracing::{debug, span, warn, Instrument, Level};
+use wasmtime::component::Instance;
 use wasmtime::Store;
 
 use crate::error::GolemError;
@@ -216,6 +217,28 @@ impl<Ctx: WorkerCtx> InvocationQueue<Ctx> {
         }
     }
 
+    /// Removes a pending invocation from the queue if it has not been started yet, and records
+    /// the cancellation in the oplog.
+    ///
+    /// Returns `true` if the invocation was found in the queue and got canceled.
+    pub async fn cancel_pending_invocation(&self, idempotency_key: &IdempotencyKey) -> bool {
+        let canceled = {
+            let mut queue = self.queue.write().unwrap();
+            let original_length = queue.len();
+            queue.retain(|item| !item.invocation.is_idempotency_key(idempotency_key));
+            queue.len() != original_length
+        };
+        if canceled {
+            debug!("Canceled pending invocation {idempotency_key}");
+            self.oplog
+                .add_and_commit(OplogEntry::cancel_pending_invocation(
+                    idempotency_key.clone(),
+                ))
+                .await;
+        }
+        canceled
+    }
+
     pub fn pending_invocations(&self) -> Vec<TimestampedWorkerInvocation> {
         self.queue.read().unwrap().iter().cloned().collect()
     }
@@ -251,6 +274,15 @@ impl<Ctx: WorkerCtx> InvocationQueue<Ctx> {
         )
     }
 
+    /// Forgets the results of invocations stored before the given oplog index. Used when the
+    /// beginning of the oplog gets dropped, as these results can no longer be read from it.
+    pub fn forget_invocation_results_before(&self, oplog_idx: OplogIndex) {
+        self.invocation_results
+            .write()
+            .unwrap()
+            .retain(|_, result| result.oplog_idx() >= oplog_idx);
+    }
+
     pub async fn store_invocation_success(
         &self,
         key: &IdempotencyKey,
@@ -467,11 +499,12 @@ impl<Ctx: WorkerCtx> RunningInvocationQueue<Ctx> {
         debug!("Invocation queue loop started");
 
         while receiver.recv().await.is_some() {
-            let message = active
-                .write()
-                .unwrap()
-                .pop_front()
-                .expect("Message should be present");
+            // The message may be missing if the pending invocation has been canceled
+            let message = active.write().unwrap().pop_front();
+            let message = match message {
+                Some(message) => message,
+                None => continue,
+            };
             if let Some(worker) = worker.upgrade() {
                 debug!("Invocation queue processing {message:?}");
 
@@ -524,6 +557,8 @@ impl<Ctx: WorkerCtx> RunningInvocationQueue<Ctx> {
                                 true, // Invocation queue is always initialized _after_ the worker recovery
                             )
                             .await;
+
+                            Self::save_snapshot_if_due(store, instance).await;
                         }
                         .instrument(span)
                         .await
@@ -628,6 +663,50 @@ impl<Ctx: WorkerCtx> RunningInvocationQueue<Ctx> {
             .await;
     }
 
+    /// Saves a snapshot of the worker if the configured snapshot policy requires it and the worker
+    /// exports both the `save-snapshot` and `load-snapshot` interfaces
+    async fn save_snapshot_if_due(store: &mut Store<Ctx>, instance: &Instance) {
+        if !store.data().is_snapshot_due().await || !Self::supports_snapshots(store, instance) {
+            return;
+        }
+
+        debug!("Saving worker snapshot");
+        store
+            .data_mut()
+            .set_current_idempotency_key(IdempotencyKey::fresh())
+            .await;
+        store.data_mut().begin_call_snapshotting_function();
+        let result = invoke_worker(
+            "golem:api/save-snapshot@0.2.0/save".to_string(),
+            vec![],
+            store,
+            instance,
+            CallingConvention::Component,
+            true,
+        )
+        .await;
+        store.data_mut().end_call_snapshotting_function();
+
+        match result {
+            Some(Ok(result)) => match Self::decode_snapshot_result(result) {
+                Some(bytes) => {
+                    if let Err(error) = store.data_mut().on_snapshot_saved(&bytes).await {
+                        warn!("Failed to store the worker snapshot: {error}");
+                    }
+                }
+                None => warn!("Failed to save a worker snapshot: invalid snapshot result"),
+            },
+            Some(Err(error)) => warn!("Failed to save a worker snapshot: {error}"),
+            None => {}
+        }
+    }
+
+    fn supports_snapshots(store: &mut Store<Ctx>, instance: &Instance) -> bool {
+        let mut exports = instance.exports(&mut *store);
+        exports.instance("golem:api/save-snapshot@0.2.0").is_some()
+            && exports.instance("golem:api/load-snapshot@0.2.0").is_some()
+    }
+
     /// Attempts to interpret the save snapshot result as a byte vector
     fn decode_snapshot_result(values: Vec<Value>) -> Option<Vec<u8>> {
         if values.len() == 1 {
@@ -42,6 +42,7 @@ pub mod worker;
 pub mod worker_activator;
 pub mod worker_enumeration;
 pub mod worker_event;
+pub mod worker_file_system;
 pub mod worker_proxy;
 
 // HasXXX traits for fine-grained control of which dependencies a function needs
@@ -100,6 +101,12 @@ pub trait HasBlobStoreService {
     fn blob_store_service(&self) -> Arc<dyn blob_store::BlobStoreService + Send + Sync>;
 }
 
+pub trait HasWorkerFileSystemService {
+    fn worker_file_system_service(
+        &self,
+    ) -> Arc<dyn worker_file_system::WorkerFileSystemService + Send + Sync>;
+}
+
 pub trait HasOplogService {
     fn oplog_service(&self) -> Arc<dyn oplog::OplogService + Send + Sync>;
 }
@@ -152,6 +159,7 @@ pub trait HasAll<Ctx: WorkerCtx>:
     + HasWasmtimeEngine<Ctx>
     + HasKeyValueService
     + HasBlobStoreService
+    + HasWorkerFileSystemService
     + HasOplogService
     + HasRecoveryManagement
     + HasRpc
@@ -176,6 +184,7 @@ impl<
             + HasWasmtimeEngine<Ctx>
             + HasKeyValueService
             + HasBlobStoreService
+            + HasWorkerFileSystemService
             + HasOplogService
             + HasRecoveryManagement
             + HasRpc
@@ -207,6 +216,7 @@ pub struct All<Ctx: WorkerCtx> {
     shard_service: Arc<dyn shard::ShardService + Send + Sync>,
     key_value_service: Arc<dyn key_value::KeyValueService + Send + Sync>,
     blob_store_service: Arc<dyn blob_store::BlobStoreService + Send + Sync>,
+    worker_file_system_service: Arc<dyn worker_file_system::WorkerFileSystemService + Send + Sync>,
     oplog_service: Arc<dyn oplog::OplogService + Send + Sync>,
     recovery_management: Arc<dyn recovery::RecoveryManagement + Send + Sync>,
     rpc: Arc<dyn rpc::Rpc + Send + Sync>,
@@ -234,6 +244,7 @@ impl<Ctx: WorkerCtx> Clone for All<Ctx> {
             shard_service: self.shard_service.clone(),
             key_value_service: self.key_value_service.clone(),
             blob_store_service: self.blob_store_service.clone(),
+            worker_file_system_service: self.worker_file_system_service.clone(),
             oplog_service: self.oplog_service.clone(),
             recovery_management: self.recovery_management.clone(),
             rpc: self.rpc.clone(),
@@ -267,6 +278,9 @@ impl<Ctx: WorkerCtx> All<Ctx> {
         shard_service: Arc<dyn shard::ShardService + Send + Sync>,
         key_value_service: Arc<dyn key_value::KeyValueService + Send + Sync>,
         blob_store_service: Arc<dyn blob_store::BlobStoreService + Send + Sync>,
+        worker_file_system_service: Arc<
+            dyn worker_file_system::WorkerFileSystemService + Send + Sync,
+        >,
         oplog_service: Arc<dyn oplog::OplogService + Send + Sync>,
         recovery_management: Arc<dyn recovery::RecoveryManagement + Send + Sync>,
         rpc: Arc<dyn rpc::Rpc + Send + Sync>,
@@ -291,6 +305,7 @@ impl<Ctx: WorkerCtx> All<Ctx> {
             shard_service,
             key_value_service,
             blob_store_service,
+            worker_file_system_service,
             oplog_service,
             recovery_management,
             rpc,
@@ -329,6 +344,9 @@ impl<Ctx: WorkerCtx> All<Ctx> {
         let blob_store_service = Arc::new(blob_store::DefaultBlobStoreService::new(
             blob_storage.clone(),
         ));
+        let worker_file_system_service = Arc::new(
+            worker_file_system::DefaultWorkerFileSystemService::new(blob_storage.clone()),
+        );
         let oplog_service = Arc::new(oplog::mock::OplogServiceMock::new());
         let recovery_management = Arc::new(recovery::RecoveryManagementMock::new());
         let rpc = Arc::new(rpc::RpcMock::new());
@@ -351,6 +369,7 @@ impl<Ctx: WorkerCtx> All<Ctx> {
             shard_service,
             key_value_service,
             blob_store_service,
+            worker_file_system_service,
             oplog_service,
             recovery_management,
             rpc,
@@ -461,6 +480,14 @@ impl<Ctx: WorkerCtx, T: UsesAllDeps<Ctx = Ctx>> HasBlobStoreService for T {
     }
 }
 
+impl<Ctx: WorkerCtx, T: UsesAllDeps<Ctx = Ctx>> HasWorkerFileSystemService for T {
+    fn worker_file_system_service(
+        &self,
+    ) -> Arc<dyn worker_file_system::WorkerFileSystemService + Send + Sync> {
+        self.all().worker_file_system_service.clone()
+    }
+}
+
 impl<Ctx: WorkerCtx, T: UsesAllDeps<Ctx = Ctx>> HasOplogService for T {
     fn oplog_service(&self) -> Arc<dyn oplog::OplogService + Send + Sync> {
         self.all().oplog_service.clone()
@@ -0,0 +1,405 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Read-only access to a worker's oplog in its public (protobuf) representation,
+//! used by the oplog inspection and search APIs.
+
+use std::sync::Arc;
+
+use golem_api_grpc::proto::golem::worker::{
+    OplogEntry as ProtoOplogEntry, OplogEntryType, OplogFilter,
+};
+use golem_common::model::oplog::{OplogEntry, OplogIndex, UpdateDescription};
+use golem_common::model::{
+    CallingConvention, IdempotencyKey, OwnedWorkerId, Timestamp, WorkerInvocation,
+};
+use golem_wasm_rpc::protobuf::Val;
+
+use crate::durable_host::inspect::decode_imported_function_response;
+use crate::error::GolemError;
+use crate::services::oplog::{Oplog, OplogOps, OplogService};
+use crate::services::worker::WorkerService;
+
+/// Number of entries read from the oplog service at once while scanning
+const SCAN_CHUNK_SIZE: u64 = 128;
+
+/// A page of oplog entries matching a filter
+pub struct OplogPage {
+    pub entries: Vec<ProtoOplogEntry>,
+    /// The oplog index to continue the search from, if there are more entries to scan
+    pub next: Option<OplogIndex>,
+    /// The last oplog index of the worker at the time of the scan
+    pub last_index: OplogIndex,
+}
+
+/// The exported function invocation an `ExportedFunctionCompleted` entry belongs to
+#[derive(Clone)]
+struct InvokedFunction {
+    function_name: String,
+    idempotency_key: IdempotencyKey,
+    calling_convention: Option<CallingConvention>,
+}
+
+/// Scans the oplog of the given worker starting from `start`, returning at most `count` entries
+/// matching `filter`, with their payloads decoded.
+///
+/// If the oplog has been compacted, the initial entry is taken from the worker service and the
+/// scan continues with the first entry kept after it.
+pub async fn search_oplog(
+    oplog_service: Arc<dyn OplogService + Send + Sync>,
+    worker_service: Arc<dyn WorkerService + Send + Sync>,
+    owned_worker_id: &OwnedWorkerId,
+    start: OplogIndex,
+    count: u64,
+    filter: &OplogFilter,
+) -> Result<OplogPage, GolemError> {
+    let last_index = oplog_service.get_last_index(owned_worker_id).await;
+    let start = if start < OplogIndex::INITIAL {
+        OplogIndex::INITIAL
+    } else {
+        start
+    };
+
+    let mut entries = Vec::new();
+    if count == 0 || start > last_index {
+        return Ok(OplogPage {
+            entries,
+            next: None,
+            last_index,
+        });
+    }
+
+    let oplog = oplog_service.open(owned_worker_id).await;
+    let mut current_function: Option<InvokedFunction> = None;
+    let mut idx = start;
+
+    while idx <= last_index {
+        let remaining = Into::<u64>::into(last_index) - Into::<u64>::into(idx) + 1;
+        let chunk_size = remaining.min(SCAN_CHUNK_SIZE);
+        let mut chunk = oplog_service.read(owned_worker_id, idx, chunk_size).await;
+        if idx == OplogIndex::INITIAL && !chunk.contains_key(&OplogIndex::INITIAL) {
+            if let Some(initial_entry) = worker_service.get_initial_entry(owned_worker_id).await {
+                chunk.insert(OplogIndex::INITIAL, initial_entry);
+            }
+        }
+        if chunk.is_empty() {
+            // Skipping the part of the oplog dropped by compaction
+            idx = OplogIndex::from_u64(Into::<u64>::into(idx) + chunk_size);
+            continue;
+        }
+
+        for (entry_idx, entry) in chunk {
+            let invoked_function = match &entry {
+                OplogEntry::ExportedFunctionInvoked {
+                    function_name,
+                    idempotency_key,
+                    calling_convention,
+                    ..
+                } => {
+                    current_function = Some(InvokedFunction {
+                        function_name: function_name.clone(),
+                        idempotency_key: idempotency_key.clone(),
+                        calling_convention: *calling_convention,
+                    });
+                    current_function.clone()
+                }
+                OplogEntry::ExportedFunctionCompleted { .. } => {
+                    if current_function.is_none() {
+                        current_function =
+                            find_invoked_function(&oplog_service, owned_worker_id, entry_idx).await;
+                    }
+                    current_function.take()
+                }
+                _ => None,
+            };
+
+            if matches_filter(filter, &entry, invoked_function.as_ref()) {
+                entries.push(
+                    to_proto_entry(&oplog, entry_idx, &entry, invoked_function.as_ref()).await?,
+                );
+
+                if entries.len() as u64 == count {
+                    let next = if entry_idx < last_index {
+                        Some(entry_idx.next())
+                    } else {
+                        None
+                    };
+                    return Ok(OplogPage {
+                        entries,
+                        next,
+                        last_index,
+                    });
+                }
+            }
+
+            idx = entry_idx.next();
+        }
+    }
+
+    Ok(OplogPage {
+        entries,
+        next: None,
+        last_index,
+    })
+}
+
+/// Looks backwards from `before` for the exported function invocation which is completed at `before`
+async fn find_invoked_function(
+    oplog_service: &Arc<dyn OplogService + Send + Sync>,
+    owned_worker_id: &OwnedWorkerId,
+    before: OplogIndex,
+) -> Option<InvokedFunction> {
+    let mut end = before;
+    while end > OplogIndex::INITIAL {
+        let last = end.previous();
+        let first = if Into::<u64>::into(last) > SCAN_CHUNK_SIZE {
+            OplogIndex::from_u64(Into::<u64>::into(last) - SCAN_CHUNK_SIZE + 1)
+        } else {
+            OplogIndex::INITIAL
+        };
+
+        let chunk = oplog_service.read_range(owned_worker_id, first, last).await;
+        for (_, entry) in chunk.into_iter().rev() {
+            match entry {
+                OplogEntry::ExportedFunctionInvoked {
+                    function_name,
+                    idempotency_key,
+                    calling_convention,
+                    ..
+                } => {
+                    return Some(InvokedFunction {
+                        function_name,
+                        idempotency_key,
+                        calling_convention,
+                    })
+                }
+                OplogEntry::ExportedFunctionCompleted { .. } => return None,
+                _ => {}
+            }
+        }
+
+        end = first;
+    }
+    None
+}
+
+fn matches_filter(
+    filter: &OplogFilter,
+    entry: &OplogEntry,
+    invoked_function: Option<&InvokedFunction>,
+) -> bool {
+    let entry_type = entry_type(entry) as i32;
+    let timestamp = entry.timestamp();
+
+    let type_matches = filter.entry_types.is_empty() || filter.entry_types.contains(&entry_type);
+    let after_matches = filter
+        .after
+        .clone()
+        .map(|after| timestamp >= Timestamp::from(after))
+        .unwrap_or(true);
+    let before_matches = filter
+        .before
+        .clone()
+        .map(|before| timestamp <= Timestamp::from(before))
+        .unwrap_or(true);
+    let function_matches = match &filter.function_name {
+        Some(name) => function_name(entry, invoked_function).as_deref() == Some(name.as_str()),
+        None => true,
+    };
+
+    type_matches && after_matches && before_matches && function_matches
+}
+
+fn entry_type(entry: &OplogEntry) -> OplogEntryType {
+    match entry {
+        OplogEntry::Create { .. } => OplogEntryType::Create,
+        OplogEntry::ImportedFunctionInvoked { .. } => OplogEntryType::ImportedFunctionInvoked,
+        OplogEntry::ExportedFunctionInvoked { .. } => OplogEntryType::ExportedFunctionInvoked,
+        OplogEntry::ExportedFunctionCompleted { .. } => OplogEntryType::ExportedFunctionCompleted,
+        OplogEntry::Suspend { .. } => OplogEntryType::Suspend,
+        OplogEntry::Error { .. } => OplogEntryType::Error,
+        OplogEntry::NoOp { .. } => OplogEntryType::NoOp,
+        OplogEntry::Jump { .. } => OplogEntryType::Jump,
+        OplogEntry::Interrupted { .. } => OplogEntryType::Interrupted,
+        OplogEntry::Exited { .. } => OplogEntryType::Exited,
+        OplogEntry::ChangeRetryPolicy { .. } => OplogEntryType::ChangeRetryPolicy,
+        OplogEntry::BeginAtomicRegion { .. } => OplogEntryType::BeginAtomicRegion,
+        OplogEntry::EndAtomicRegion { .. } => OplogEntryType::EndAtomicRegion,
+        OplogEntry::BeginRemoteWrite { .. } => OplogEntryType::BeginRemoteWrite,
+        OplogEntry::EndRemoteWrite { .. } => OplogEntryType::EndRemoteWrite,
+        OplogEntry::PendingWorkerInvocation { .. } => OplogEntryType::PendingWorkerInvocation,
+        OplogEntry::PendingUpdate { .. } => OplogEntryType::PendingUpdate,
+        OplogEntry::SuccessfulUpdate { .. } => OplogEntryType::SuccessfulUpdate,
+        OplogEntry::FailedUpdate { .. } => OplogEntryType::FailedUpdate,
+        OplogEntry::CancelPendingInvocation { .. } => OplogEntryType::CancelPendingInvocation,
+        OplogEntry::Snapshot { .. } => OplogEntryType::Snapshot,
+    }
+}
+
+fn function_name(entry: &OplogEntry, invoked_function: Option<&InvokedFunction>) -> Option<String> {
+    match entry {
+        OplogEntry::ImportedFunctionInvoked { function_name, .. } => Some(function_name.clone()),
+        OplogEntry::ExportedFunctionInvoked { function_name, .. } => Some(function_name.clone()),
+        OplogEntry::ExportedFunctionCompleted { .. } => {
+            invoked_function.map(|f| f.function_name.clone())
+        }
+        OplogEntry::PendingWorkerInvocation {
+            invocation:
+                WorkerInvocation::ExportedFunction {
+                    full_function_name, ..
+                },
+            ..
+        } => Some(full_function_name.clone()),
+        _ => None,
+    }
+}
+
+async fn to_proto_entry(
+    oplog: &Arc<dyn Oplog + Send + Sync>,
+    idx: OplogIndex,
+    entry: &OplogEntry,
+    invoked_function: Option<&InvokedFunction>,
+) -> Result<ProtoOplogEntry, GolemError> {
+    let mut result = ProtoOplogEntry {
+        oplog_index: idx.into(),
+        timestamp: Some(entry.timestamp().into()),
+        entry_type: entry_type(entry) as i32,
+        function_name: function_name(entry, invoked_function),
+        idempotency_key: None,
+        calling_convention: None,
+        request: vec![],
+        response: vec![],
+        details: None,
+    };
+
+    match entry {
+        OplogEntry::Create {
+            component_version,
+            args,
+            env,
+            ..
+        } => {
+            result.details = Some(format!(
+                "component version: {component_version}, args: {args:?}, env: {env:?}"
+            ));
+        }
+        OplogEntry::ImportedFunctionInvoked {
+            wrapped_function_type,
+            ..
+        } => {
+            result.response = decode_imported_function_response(oplog, entry)
+                .await
+                .map_err(|err| {
+                    GolemError::runtime(format!("failed to decode oplog payload: {err}"))
+                })?
+                .into_iter()
+                .map(|value| value.into())
+                .collect();
+            result.details = Some(format!("{wrapped_function_type:?}"));
+        }
+        OplogEntry::ExportedFunctionInvoked {
+            idempotency_key,
+            calling_convention,
+            ..
+        } => {
+            result.idempotency_key = Some(idempotency_key.clone().into());
+            result.calling_convention = calling_convention.map(|cc| cc.into());
+            result.request = decode_values(oplog, entry).await?;
+        }
+        OplogEntry::ExportedFunctionCompleted { consumed_fuel, .. } => {
+            if let Some(invoked_function) = invoked_function {
+                result.idempotency_key = Some(invoked_function.idempotency_key.clone().into());
+                result.calling_convention = invoked_function.calling_convention.map(|cc| cc.into());
+            }
+            result.response = decode_values(oplog, entry).await?;
+            result.details = Some(format!("consumed fuel: {consumed_fuel}"));
+        }
+        OplogEntry::Error { error, .. } => {
+            result.details = Some(error.to_string());
+        }
+        OplogEntry::Jump { jump, .. } => {
+            result.details = Some(format!("skipping {}..={}", jump.start, jump.end));
+        }
+        OplogEntry::ChangeRetryPolicy { new_policy, .. } => {
+            result.details = Some(format!("{new_policy:?}"));
+        }
+        OplogEntry::EndAtomicRegion { begin_index, .. }
+        | OplogEntry::EndRemoteWrite { begin_index, .. } => {
+            result.details = Some(format!("begin index: {begin_index}"));
+        }
+        OplogEntry::PendingWorkerInvocation { invocation, .. } => match invocation {
+            WorkerInvocation::ExportedFunction {
+                idempotency_key,
+                function_input,
+                calling_convention,
+                ..
+            } => {
+                result.idempotency_key = Some(idempotency_key.clone().into());
+                result.calling_convention = Some((*calling_convention).into());
+                result.request = function_input.iter().cloned().map(|v| v.into()).collect();
+            }
+            WorkerInvocation::ManualUpdate { target_version } => {
+                result.details = Some(format!("manual update to version {target_version}"));
+            }
+        },
+        OplogEntry::PendingUpdate { description, .. } => {
+            result.details = Some(match description {
+                UpdateDescription::Automatic { target_version } => {
+                    format!("automatic update to version {target_version}")
+                }
+                UpdateDescription::SnapshotBased { target_version, .. } => {
+                    format!("snapshot based update to version {target_version}")
+                }
+            });
+        }
+        OplogEntry::SuccessfulUpdate { target_version, .. } => {
+            result.details = Some(format!("target version: {target_version}"));
+        }
+        OplogEntry::FailedUpdate {
+            target_version,
+            details,
+            ..
+        } => {
+            result.details = Some(match details {
+                Some(details) => format!("target version: {target_version}, {details}"),
+                None => format!("target version: {target_version}"),
+            });
+        }
+        OplogEntry::CancelPendingInvocation {
+            idempotency_key, ..
+        } => {
+            result.idempotency_key = Some(idempotency_key.clone().into());
+        }
+        OplogEntry::Suspend { .. }
+        | OplogEntry::NoOp { .. }
+        | OplogEntry::Interrupted { .. }
+        | OplogEntry::Exited { .. }
+        | OplogEntry::BeginAtomicRegion { .. }
+        | OplogEntry::BeginRemoteWrite { .. }
+        | OplogEntry::Snapshot { .. } => {}
+    }
+
+    Ok(result)
+}
+
+async fn decode_values(
+    oplog: &Arc<dyn Oplog + Send + Sync>,
+    entry: &OplogEntry,
+) -> Result<Vec<Val>, GolemError> {
+    let values: Option<Vec<Val>> = oplog
+        .get_payload_of_entry(entry)
+        .await
+        .map_err(|err| GolemError::runtime(format!("failed to decode oplog payload: {err}")))?;
+    Ok(values.unwrap_or_default())
+}
@@ -42,6 +42,7 @@ use crate::error::GolemError;
 
 mod blob;
 mod compressed;
+pub mod inspect;
 mod multilayer;
 mod primary;
 
@@ -237,6 +238,16 @@ pub trait OplogOps: Oplog {
         Ok(entry)
     }
 
+    async fn add_snapshot(&self, data: &[u8]) -> Result<OplogEntry, String> {
+        let payload = self.upload_payload(data).await?;
+        let entry = OplogEntry::Snapshot {
+            timestamp: Timestamp::now_utc(),
+            data: payload,
+        };
+        self.add(entry.clone()).await;
+        Ok(entry)
+    }
+
     async fn create_snapshot_based_update_description(
         &self,
         target_version: ComponentVersion,
@@ -270,6 +281,87 @@ pub trait OplogOps: Oplog {
         }
     }
 
+    /// Adds an entry copied from another worker's oplog.
+    ///
+    /// Payloads are stored per worker, so every payload of the entry is downloaded from the
+    /// source oplog and uploaded again to this one.
+    async fn add_copied_entry(
+        &self,
+        source: &Arc<dyn Oplog + Send + Sync>,
+        entry: OplogEntry,
+    ) -> Result<(), String> {
+        let entry = match entry {
+            OplogEntry::ImportedFunctionInvoked {
+                timestamp,
+                function_name,
+                response,
+                wrapped_function_type,
+            } => OplogEntry::ImportedFunctionInvoked {
+                timestamp,
+                function_name,
+                response: self.copy_payload(source, &response).await?,
+                wrapped_function_type,
+            },
+            OplogEntry::ExportedFunctionInvoked {
+                timestamp,
+                function_name,
+                request,
+                idempotency_key,
+                calling_convention,
+            } => OplogEntry::ExportedFunctionInvoked {
+                timestamp,
+                function_name,
+                request: self.copy_payload(source, &request).await?,
+                idempotency_key,
+                calling_convention,
+            },
+            OplogEntry::ExportedFunctionCompleted {
+                timestamp,
+                response,
+                consumed_fuel,
+            } => OplogEntry::ExportedFunctionCompleted {
+                timestamp,
+                response: self.copy_payload(source, &response).await?,
+                consumed_fuel,
+            },
+            OplogEntry::PendingUpdate {
+                timestamp,
+                description:
+                    UpdateDescription::SnapshotBased {
+                        target_version,
+                        payload,
+                    },
+            } => OplogEntry::PendingUpdate {
+                timestamp,
+                description: UpdateDescription::SnapshotBased {
+                    target_version,
+                    payload: self.copy_payload(source, &payload).await?,
+                },
+            },
+            OplogEntry::Snapshot { timestamp, data } => OplogEntry::Snapshot {
+                timestamp,
+                data: self.copy_payload(source, &data).await?,
+            },
+            other => other,
+        };
+        self.add(entry).await;
+        Ok(())
+    }
+
+    async fn copy_payload(
+        &self,
+        source: &Arc<dyn Oplog + Send + Sync>,
+        payload: &OplogPayload,
+    ) -> Result<OplogPayload, String> {
+        match payload {
+            OplogPayload::Inline(_) => Ok(payload.clone()),
+            OplogPayload::External { .. } => {
+                let bytes = source.download_payload(payload).await?;
+                self.upload_payload(&bytes).await
+            }
+        }
+    }
+
     async fn get_upload_description_payload(
         &self,
         description: &UpdateDescription,
@@ -282,6 +374,16 @@ pub trait OplogOps: Oplog {
             UpdateDescription::Automatic { .. } => Ok(None),
         }
     }
+
+    async fn get_snapshot_payload(&self, entry: &OplogEntry) -> Result<Option<Bytes>, String> {
+        match entry {
+            OplogEntry::Snapshot { data, .. } => {
+                let bytes: Bytes = self.download_payload(data).await?;
+                Ok(Some(bytes))
+            }
+            _ => Ok(None),
+        }
+    }
 }
 
 #[async_trait]
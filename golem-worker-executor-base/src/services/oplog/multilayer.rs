@@ -514,6 +514,56 @@ impl MultiLayerOplog {
             }
         }
     }
+
+    /// Archives all entries of the oplog down to the last layer. Returns false if there was
+    /// nothing to archive.
+    pub async fn try_archive_to_last_layer(this: &Arc<dyn Oplog + Send + Sync>) -> Option<bool> {
+        let this = downcast_oplog::<MultiLayerOplog>(this)?;
+        Some(Self::archive_to_last_layer(this).await)
+    }
+
+    async fn archive_to_last_layer(this: Arc<Self>) -> bool {
+        let last_idx = this.primary.current_oplog_index().await;
+        let last_layer = this.lower.len().get() - 1;
+
+        let first_source = if this.primary_length.get() > 0 {
+            this.transfer
+                .send(TransferFromPrimary {
+                    last_transferred_idx: last_idx,
+                    keep_alive: Some(this.clone()),
+                })
+                .expect("Failed to enqueue transfer of primary oplog entries");
+            this.primary_length.set(0);
+            Some(0)
+        } else {
+            let mut first_non_empty = None;
+            for n in 0..last_layer {
+                if this.lower[n].length().await > 0 {
+                    first_non_empty = Some(n);
+                    break;
+                }
+            }
+            first_non_empty
+        };
+
+        match first_source {
+            Some(first_source) => {
+                // The transfers are processed in order, so each layer receives all the entries
+                // of the previous ones before it gets transferred to the next layer
+                for source in first_source..last_layer {
+                    this.transfer
+                        .send(TransferFromLower {
+                            source,
+                            last_transferred_idx: last_idx,
+                            keep_alive: Some(this.clone()),
+                        })
+                        .expect("Failed to enqueue transfer of lower oplog entries");
+                }
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 impl Drop for MultiLayerOplog {
@@ -544,6 +594,11 @@ impl Oplog for MultiLayerOplog {
         self.primary.drop_prefix(last_dropped_id).await;
         let new_length = self.primary.length().await;
         self.primary_length.set(new_length);
+
+        // Archived chunks are only dropped if they are fully covered by the dropped prefix
+        for layer in &self.lower {
+            layer.drop_prefix(last_dropped_id).await;
+        }
     }
 
     async fn commit(&self) {
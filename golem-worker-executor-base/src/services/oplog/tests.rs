@@ -163,6 +163,17 @@ fn rounded(entry: OplogEntry) -> OplogEntry {
             timestamp: rounded_ts(timestamp),
             invocation,
         },
+        OplogEntry::CancelPendingInvocation {
+            timestamp,
+            idempotency_key,
+        } => OplogEntry::CancelPendingInvocation {
+            timestamp: rounded_ts(timestamp),
+            idempotency_key,
+        },
+        OplogEntry::Snapshot { timestamp, data } => OplogEntry::Snapshot {
+            timestamp: rounded_ts(timestamp),
+            data,
+        },
     }
 }
 
@@ -756,6 +767,92 @@ async fn empty_layer_gets_deleted_impl(use_blob: bool) {
     assert!(tertiary_exists);
 }
 
+#[tokio::test]
+async fn multilayer_drop_prefix() {
+    multilayer_drop_prefix_impl(false).await;
+}
+
+#[tokio::test]
+async fn blob_multilayer_drop_prefix() {
+    multilayer_drop_prefix_impl(true).await;
+}
+
+async fn multilayer_drop_prefix_impl(use_blob: bool) {
+    init_logging();
+
+    let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
+    let blob_storage = Arc::new(InMemoryBlobStorage::new());
+    let primary_oplog_service = Arc::new(
+        PrimaryOplogService::new(indexed_storage.clone(), blob_storage.clone(), 1, 100).await,
+    );
+    let secondary_layer: Arc<dyn OplogArchiveService + Send + Sync> = if use_blob {
+        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 1))
+    } else {
+        Arc::new(CompressedOplogArchiveService::new(
+            indexed_storage.clone(),
+            1,
+        ))
+    };
+    let tertiary_layer: Arc<dyn OplogArchiveService + Send + Sync> = if use_blob {
+        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 2))
+    } else {
+        Arc::new(CompressedOplogArchiveService::new(
+            indexed_storage.clone(),
+            2,
+        ))
+    };
+    let oplog_service = Arc::new(MultiLayerOplogService::new(
+        primary_oplog_service.clone(),
+        nev![secondary_layer.clone(), tertiary_layer.clone()],
+        10,
+    ));
+    let account_id = AccountId {
+        value: "user1".to_string(),
+    };
+    let worker_id = WorkerId {
+        component_id: ComponentId(Uuid::new_v4()),
+        worker_name: "test".to_string(),
+    };
+    let owned_worker_id = OwnedWorkerId::new(&account_id, &worker_id);
+
+    let oplog = oplog_service.open(&owned_worker_id).await;
+
+    // Adding 100 entries at once exceeds the limit, so all of them get moved to the lower layers
+    let timestamp = Timestamp::now_utc();
+    for i in 0..100 {
+        oplog
+            .add(rounded(OplogEntry::Error {
+                timestamp,
+                error: WorkerError::Unknown(i.to_string()),
+            }))
+            .await;
+    }
+    oplog.commit().await;
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    let last_archived_idx = oplog.current_oplog_index().await;
+    let snapshot = rounded(
+        oplog
+            .add_snapshot(&[1, 2, 3])
+            .await
+            .expect("failed to add snapshot"),
+    );
+    oplog.commit().await;
+    let snapshot_idx = oplog.current_oplog_index().await;
+
+    oplog.drop_prefix(last_archived_idx).await;
+
+    let secondary_length = secondary_layer.open(&owned_worker_id).await.length().await;
+    let tertiary_length = tertiary_layer.open(&owned_worker_id).await.length().await;
+    let remaining = oplog_service
+        .read_range(&owned_worker_id, OplogIndex::INITIAL, snapshot_idx)
+        .await;
+
+    check!(secondary_length == 0);
+    check!(tertiary_length == 0);
+    check!(remaining.into_iter().collect::<Vec<_>>() == vec![(snapshot_idx, snapshot)]);
+}
+
 #[tokio::test]
 async fn scheduled_archive() {
     scheduled_archive_impl(false).await;
@@ -873,3 +970,108 @@ async fn scheduled_archive_impl(use_blob: bool) {
     assert_eq!(tertiary_length, 1);
     assert_eq!(archive_result2, Some(false));
 }
+
+#[tokio::test]
+async fn archive_to_last_layer() {
+    archive_to_last_layer_impl(false).await;
+}
+
+#[tokio::test]
+async fn blob_archive_to_last_layer() {
+    archive_to_last_layer_impl(true).await;
+}
+
+async fn archive_to_last_layer_impl(use_blob: bool) {
+    init_logging();
+
+    let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
+    let blob_storage = Arc::new(InMemoryBlobStorage::new());
+    let primary_oplog_service = Arc::new(
+        PrimaryOplogService::new(indexed_storage.clone(), blob_storage.clone(), 1, 100).await,
+    );
+    let secondary_layer: Arc<dyn OplogArchiveService + Send + Sync> = if use_blob {
+        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 1))
+    } else {
+        Arc::new(CompressedOplogArchiveService::new(
+            indexed_storage.clone(),
+            1,
+        ))
+    };
+    let tertiary_layer: Arc<dyn OplogArchiveService + Send + Sync> = if use_blob {
+        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 2))
+    } else {
+        Arc::new(CompressedOplogArchiveService::new(
+            indexed_storage.clone(),
+            2,
+        ))
+    };
+    let oplog_service = Arc::new(MultiLayerOplogService::new(
+        primary_oplog_service.clone(),
+        nev![secondary_layer.clone(), tertiary_layer.clone()],
+        1000, // no transfer will occur by reaching limit in this test
+    ));
+    let account_id = AccountId {
+        value: "user1".to_string(),
+    };
+    let worker_id = WorkerId {
+        component_id: ComponentId(Uuid::new_v4()),
+        worker_name: "test".to_string(),
+    };
+    let owned_worker_id = OwnedWorkerId::new(&account_id, &worker_id);
+
+    let timestamp = Timestamp::now_utc();
+    let entries: Vec<OplogEntry> = (0..100)
+        .map(|i| {
+            rounded(OplogEntry::Error {
+                timestamp,
+                error: WorkerError::Unknown(i.to_string()),
+            })
+        })
+        .collect();
+
+    // Adding 100 entries to the primary oplog, archive them to the last layer and immediately drop the oplog
+    let archive_result = {
+        let oplog = oplog_service.open(&owned_worker_id).await;
+        for entry in &entries {
+            oplog.add(entry.clone()).await;
+        }
+        oplog.commit().await;
+
+        let result = MultiLayerOplog::try_archive_to_last_layer(&oplog).await;
+        drop(oplog);
+        result
+    };
+
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    let primary_length = primary_oplog_service
+        .open(&owned_worker_id)
+        .await
+        .length()
+        .await;
+    let secondary_length = secondary_layer.open(&owned_worker_id).await.length().await;
+    let tertiary_length = tertiary_layer.open(&owned_worker_id).await.length().await;
+    let all_entries = oplog_service
+        .read_range(
+            &owned_worker_id,
+            OplogIndex::INITIAL,
+            OplogIndex::from_u64(100),
+        )
+        .await;
+
+    assert_eq!(primary_length, 0);
+    assert_eq!(secondary_length, 0);
+    assert_eq!(tertiary_length, 1);
+    assert_eq!(archive_result, Some(true));
+    assert_eq!(all_entries.into_values().collect::<Vec<_>>(), entries);
+
+    // Nothing is left to archive
+    let archive_result2 = {
+        let oplog = oplog_service.open(&owned_worker_id).await;
+        let result = MultiLayerOplog::try_archive_to_last_layer(&oplog).await;
+        drop(oplog);
+        result
+    };
+
+    assert_eq!(archive_result2, Some(false));
+}
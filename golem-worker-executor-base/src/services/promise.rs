@@ -22,7 +22,11 @@ use async_trait::async_trait;
 use bincode::{Decode, Encode};
 use dashmap::DashMap;
 use golem_common::model::oplog::OplogIndex;
-use golem_common::model::{PromiseId, WorkerId};
+use golem_common::model::{PromiseId, Timestamp, WorkerId};
+use golem_wasm_ast::analysis::AnalysedType;
+use golem_wasm_rpc::json::get_typed_value_from_json;
+use golem_wasm_rpc::protobuf::{r#type, Type, TypePrimitive};
+use prost::Message;
 use serde::{Deserialize, Serialize};
 use tokio::sync::oneshot;
 use tracing::debug;
@@ -36,15 +40,40 @@ use crate::storage::keyvalue::{
 /// Service implementing creation, completion and polling of promises
 #[async_trait]
 pub trait PromiseService {
-    async fn create(&self, worker_id: &WorkerId, oplog_idx: OplogIndex) -> PromiseId;
+    /// Creates a promise, which fails with [GolemError::PromiseTimedOut] once it gets
+    /// expired if it was created with an expiration time. If a payload type is given, the
+    /// promise can only be completed with a JSON encoded value of that type.
+    async fn create(
+        &self,
+        worker_id: &WorkerId,
+        oplog_idx: OplogIndex,
+        expires_at: Option<Timestamp>,
+        payload_type: Option<Type>,
+    ) -> PromiseId;
 
     async fn wait_for(&self, promise_id: PromiseId) -> Result<Vec<u8>, GolemError>;
 
     async fn poll(&self, promise_id: PromiseId) -> Result<Option<Vec<u8>>, GolemError>;
 
+    /// Completes the promise with the given payload. Fails with [GolemError::ValueMismatch] if
+    /// the promise has a payload type which the payload does not match.
     async fn complete(&self, promise_id: PromiseId, data: Vec<u8>) -> Result<bool, GolemError>;
 
+    /// Fails the promise with [GolemError::PromiseTimedOut] if it is not completed yet.
+    /// Returns true if the promise got expired by this call.
+    async fn expire(&self, promise_id: PromiseId) -> Result<bool, GolemError>;
+
     async fn delete(&self, promise_id: PromiseId);
+
+    /// Gets the promises created by the given worker which are not completed yet
+    async fn get_pending(&self, worker_id: &WorkerId) -> Vec<PendingPromise>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingPromise {
+    pub promise_id: PromiseId,
+    pub expires_at: Option<Timestamp>,
+    pub payload_type: Option<Type>,
 }
 
 #[derive(Clone, Debug)]
@@ -88,11 +117,103 @@ impl DefaultPromiseService {
                 panic!("failed to check if promise {promise_id} exists in Redis: {err}")
             })
     }
+
+    async fn get_payload_type(&self, promise_id: &PromiseId) -> Option<Type> {
+        let encoded: Option<Vec<u8>> = self
+            .key_value_storage
+            .with_entity("promise", "get_pending", "promise")
+            .get(
+                KeyValueStorageNamespace::Promise,
+                &get_promise_type_redis_key(promise_id),
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to get the payload type of promise {promise_id} from Redis: {err}")
+            });
+
+        encoded.map(|encoded| {
+            Type::decode(encoded.as_slice()).unwrap_or_else(|err| {
+                panic!("failed to decode the payload type of promise {promise_id}: {err}")
+            })
+        })
+    }
+
+    async fn remove_from_pending(&self, promise_id: &PromiseId) {
+        self.key_value_storage
+            .with_entity("promise", "remove_from_pending", "promise")
+            .remove_from_set(
+                KeyValueStorageNamespace::Promise,
+                &get_pending_promises_redis_key(&promise_id.worker_id),
+                &promise_id.oplog_idx,
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to remove promise {promise_id} from the pending promises in Redis: {err}")
+            });
+    }
+
+    // Stores the final state of the promise and wakes up the ones waiting for it. Returns false
+    // if the promise was already completed or expired.
+    async fn finish(
+        &self,
+        promise_id: PromiseId,
+        state: RedisPromiseState,
+        result: Result<Vec<u8>, GolemError>,
+    ) -> Result<bool, GolemError> {
+        let key = get_promise_result_redis_key(&promise_id);
+
+        let written: bool = self
+            .key_value_storage
+            .with_entity("promise", "complete", "promise")
+            .set_if_not_exists(KeyValueStorageNamespace::Promise, &key, &state)
+            .await
+            .unwrap_or_else(|err| panic!("failed to set promise {promise_id} in Redis: {err}"));
+
+        if !self.exists(&promise_id).await {
+            Err(GolemError::PromiseNotFound { promise_id })
+        } else if written {
+            self.remove_from_pending(&promise_id).await;
+
+            let complete = PromiseState::Complete(result.clone());
+            self.insert_if_empty(promise_id.clone(), complete);
+            let entry = self.promises.get(&promise_id).unwrap_or_else(|| {
+                panic!(
+                    "Promise {:?} not found after inserting it into the map!",
+                    promise_id.clone()
+                )
+            });
+            let promise_state = entry.value();
+            match promise_state {
+                PromiseState::Pending(sender, _) => {
+                    let mut mutex_guard = sender.lock().await;
+                    let owned_sender =
+                        mutex_guard
+                            .take()
+                            .ok_or(GolemError::PromiseAlreadyCompleted {
+                                promise_id: promise_id.clone(),
+                            })?;
+                    owned_sender
+                        .send(result)
+                        .map_err(|_| GolemError::PromiseDropped { promise_id })?;
+                    Ok(true)
+                }
+                _ => Ok(true),
+            }
+        } else {
+            Ok(false)
+        }
+    }
 }
 
 #[async_trait]
 impl PromiseService for DefaultPromiseService {
-    async fn create(&self, worker_id: &WorkerId, oplog_idx: OplogIndex) -> PromiseId {
+    async fn create(
+        &self,
+        worker_id: &WorkerId,
+        oplog_idx: OplogIndex,
+        expires_at: Option<Timestamp>,
+        payload_type: Option<Type>,
+    ) -> PromiseId {
         let promise_id = PromiseId {
             worker_id: worker_id.clone(),
             oplog_idx,
@@ -100,16 +221,46 @@ impl PromiseService for DefaultPromiseService {
         debug!("Created promise {promise_id}");
 
         let key = get_promise_redis_key(&promise_id);
-        self.key_value_storage
+        let state = match expires_at {
+            Some(expires_at) => RedisPromiseState::PendingUntil(expires_at),
+            None => RedisPromiseState::Pending,
+        };
+        let created = self
+            .key_value_storage
             .with_entity("promise", "create", "promise")
-            .set_if_not_exists(
-                KeyValueStorageNamespace::Promise,
-                &key,
-                &RedisPromiseState::Pending,
-            )
+            .set_if_not_exists(KeyValueStorageNamespace::Promise, &key, &state)
             .await
             .unwrap_or_else(|err| panic!("failed to set promise {promise_id} in Redis: {err}"));
 
+        if created {
+            // The type is stored before the promise becomes listed as pending
+            if let Some(payload_type) = payload_type {
+                self.key_value_storage
+                    .with_entity("promise", "create", "promise")
+                    .set(
+                        KeyValueStorageNamespace::Promise,
+                        &get_promise_type_redis_key(&promise_id),
+                        &payload_type.encode_to_vec(),
+                    )
+                    .await
+                    .unwrap_or_else(|err| {
+                        panic!("failed to set the payload type of promise {promise_id} in Redis: {err}")
+                    });
+            }
+
+            self.key_value_storage
+                .with_entity("promise", "create", "promise")
+                .add_to_set(
+                    KeyValueStorageNamespace::Promise,
+                    &get_pending_promises_redis_key(worker_id),
+                    &oplog_idx,
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to add promise {promise_id} to the pending promises in Redis: {err}")
+                });
+        }
+
         record_promise_created();
         promise_id
     }
@@ -132,8 +283,11 @@ impl PromiseService for DefaultPromiseService {
 
             match response {
                 Some(RedisPromiseState::Complete(data)) => Ok(data),
+                Some(RedisPromiseState::TimedOut) => {
+                    Err(GolemError::PromiseTimedOut { promise_id })
+                }
                 _ => {
-                    let (sender, receiver) = oneshot::channel::<Vec<u8>>();
+                    let (sender, receiver) = oneshot::channel::<Result<Vec<u8>, GolemError>>();
 
                     let pending = PromiseState::Pending(
                         Arc::new(Mutex::new(Some(sender))),
@@ -155,12 +309,11 @@ impl PromiseService for DefaultPromiseService {
                         PromiseState::Pending(_, receiver) => {
                             let mut mutex_guard = receiver.lock().await;
                             let receiver = mutex_guard.deref_mut();
-                            let data = receiver
+                            receiver
                                 .await
-                                .map_err(|_| GolemError::PromiseDropped { promise_id })?;
-                            Ok(data)
+                                .map_err(|_| GolemError::PromiseDropped { promise_id })?
                         }
-                        PromiseState::Complete(data) => Ok(data.clone()),
+                        PromiseState::Complete(result) => result.clone(),
                     }
                 }
             }
@@ -185,68 +338,189 @@ impl PromiseService for DefaultPromiseService {
 
             match response {
                 Some(RedisPromiseState::Complete(data)) => Ok(Some(data)),
+                Some(RedisPromiseState::TimedOut) => {
+                    Err(GolemError::PromiseTimedOut { promise_id })
+                }
                 _ => Ok(None),
             }
         }
     }
 
     async fn complete(&self, promise_id: PromiseId, data: Vec<u8>) -> Result<bool, GolemError> {
-        let key = get_promise_result_redis_key(&promise_id);
+        if let Some(payload_type) = self.get_payload_type(&promise_id).await {
+            check_payload(&payload_type, &data)
+                .map_err(|details| GolemError::ValueMismatch { details })?;
+        }
 
-        let written: bool = self
-            .key_value_storage
-            .with_entity("promise", "complete", "promise")
-            .set_if_not_exists(
-                KeyValueStorageNamespace::Promise,
-                &key,
-                &RedisPromiseState::Complete(data.clone()),
-            )
-            .await
-            .unwrap_or_else(|err| panic!("failed to set promise {promise_id} in Redis: {err}"));
+        self.finish(
+            promise_id,
+            RedisPromiseState::Complete(data.clone()),
+            Ok(data),
+        )
+        .await
+    }
 
+    async fn expire(&self, promise_id: PromiseId) -> Result<bool, GolemError> {
         if !self.exists(&promise_id).await {
-            Err(GolemError::PromiseNotFound { promise_id })
-        } else if written {
-            let complete = PromiseState::Complete(data.clone());
-            self.insert_if_empty(promise_id.clone(), complete);
-            let entry = self.promises.get(&promise_id).unwrap_or_else(|| {
-                panic!(
-                    "Promise {:?} not found after inserting it into the map!",
-                    promise_id.clone()
-                )
-            });
-            let promise_state = entry.value();
-            match promise_state {
-                PromiseState::Pending(sender, _) => {
-                    let mut mutex_guard = sender.lock().await;
-                    let owned_sender =
-                        mutex_guard
-                            .take()
-                            .ok_or(GolemError::PromiseAlreadyCompleted {
-                                promise_id: promise_id.clone(),
-                            })?;
-                    owned_sender
-                        .send(data)
-                        .map_err(|_| GolemError::PromiseDropped { promise_id })?;
-                    Ok(true)
-                }
-                _ => Ok(true),
-            }
-        } else {
+            // The promise has been deleted since its expiration got scheduled
             Ok(false)
+        } else {
+            debug!("Expiring promise {promise_id}");
+            let error = GolemError::PromiseTimedOut {
+                promise_id: promise_id.clone(),
+            };
+            self.finish(promise_id, RedisPromiseState::TimedOut, Err(error))
+                .await
         }
     }
 
     async fn delete(&self, promise_id: PromiseId) {
         let key1 = get_promise_redis_key(&promise_id);
         let key2 = get_promise_result_redis_key(&promise_id);
+        let key3 = get_promise_type_redis_key(&promise_id);
         self.key_value_storage
             .with("promise", "delete")
-            .del_many(KeyValueStorageNamespace::Promise, vec![key1, key2])
+            .del_many(KeyValueStorageNamespace::Promise, vec![key1, key2, key3])
             .await
             .unwrap_or_else(|err| {
                 panic!("failed to delete promise {promise_id} from Redis: {err}")
             });
+        self.remove_from_pending(&promise_id).await;
+    }
+
+    async fn get_pending(&self, worker_id: &WorkerId) -> Vec<PendingPromise> {
+        let oplog_indices: Vec<OplogIndex> = self
+            .key_value_storage
+            .with_entity("promise", "get_pending", "promise")
+            .members_of_set(
+                KeyValueStorageNamespace::Promise,
+                &get_pending_promises_redis_key(worker_id),
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to get the pending promises of {worker_id} from Redis: {err}")
+            });
+
+        let mut result = Vec::new();
+        for oplog_idx in oplog_indices {
+            let promise_id = PromiseId {
+                worker_id: worker_id.clone(),
+                oplog_idx,
+            };
+            let state: Option<RedisPromiseState> = self
+                .key_value_storage
+                .with_entity("promise", "get_pending", "promise")
+                .get(
+                    KeyValueStorageNamespace::Promise,
+                    &get_promise_redis_key(&promise_id),
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to get promise {promise_id} from Redis: {err}")
+                });
+
+            let expires_at = match state {
+                Some(RedisPromiseState::PendingUntil(expires_at)) => Some(expires_at),
+                Some(_) => None,
+                None => continue,
+            };
+            let payload_type = self.get_payload_type(&promise_id).await;
+            result.push(PendingPromise {
+                promise_id,
+                expires_at,
+                payload_type,
+            });
+        }
+
+        result.sort_by_key(|pending| pending.promise_id.oplog_idx);
+        result
+    }
+}
+
+/// Checks that the payload is a JSON encoded value of the payload type
+fn check_payload(payload_type: &Type, data: &[u8]) -> Result<(), String> {
+    let analysed_type = to_analysed_type(payload_type)?;
+    let json: serde_json::Value = serde_json::from_slice(data)
+        .map_err(|err| format!("The payload of a typed promise must be a JSON value: {err}"))?;
+    get_typed_value_from_json(&json, &analysed_type)
+        .map(|_| ())
+        .map_err(|errors| errors.join(", "))
+}
+
+fn to_analysed_type(typ: &Type) -> Result<AnalysedType, String> {
+    let inner = |typ: &Option<Box<Type>>| match typ {
+        Some(typ) => to_analysed_type(typ),
+        None => Err("Missing inner type".to_string()),
+    };
+
+    match &typ.r#type {
+        None => Err("Missing type".to_string()),
+        Some(r#type::Type::Primitive(TypePrimitive { primitive })) => match primitive {
+            0 => Ok(AnalysedType::Bool),
+            1 => Ok(AnalysedType::S8),
+            2 => Ok(AnalysedType::U8),
+            3 => Ok(AnalysedType::S16),
+            4 => Ok(AnalysedType::U16),
+            5 => Ok(AnalysedType::S32),
+            6 => Ok(AnalysedType::U32),
+            7 => Ok(AnalysedType::S64),
+            8 => Ok(AnalysedType::U64),
+            9 => Ok(AnalysedType::F32),
+            10 => Ok(AnalysedType::F64),
+            11 => Ok(AnalysedType::Chr),
+            12 => Ok(AnalysedType::Str),
+            _ => Err(format!("Invalid primitive: {primitive}")),
+        },
+        Some(r#type::Type::Record(record)) => Ok(AnalysedType::Record(
+            record
+                .fields
+                .iter()
+                .map(|field| match &field.typ {
+                    Some(typ) => Ok((field.name.clone(), to_analysed_type(typ)?)),
+                    None => Err("Missing field type".to_string()),
+                })
+                .collect::<Result<_, _>>()?,
+        )),
+        Some(r#type::Type::Variant(variant)) => Ok(AnalysedType::Variant(
+            variant
+                .cases
+                .iter()
+                .map(|case| {
+                    Ok((
+                        case.name.clone(),
+                        case.typ.as_ref().map(to_analysed_type).transpose()?,
+                    ))
+                })
+                .collect::<Result<_, String>>()?,
+        )),
+        Some(r#type::Type::Enum(r#enum)) => Ok(AnalysedType::Enum(r#enum.names.clone())),
+        Some(r#type::Type::Flags(flags)) => Ok(AnalysedType::Flags(flags.names.clone())),
+        Some(r#type::Type::Tuple(tuple)) => Ok(AnalysedType::Tuple(
+            tuple
+                .elems
+                .iter()
+                .map(to_analysed_type)
+                .collect::<Result<_, _>>()?,
+        )),
+        Some(r#type::Type::List(list)) => Ok(AnalysedType::List(Box::new(inner(&list.elem)?))),
+        Some(r#type::Type::Option(option)) => {
+            Ok(AnalysedType::Option(Box::new(inner(&option.elem)?)))
+        }
+        Some(r#type::Type::Result(result)) => Ok(AnalysedType::Result {
+            ok: result
+                .ok
+                .as_ref()
+                .map(|ok| to_analysed_type(ok).map(Box::new))
+                .transpose()?,
+            error: result
+                .err
+                .as_ref()
+                .map(|err| to_analysed_type(err).map(Box::new))
+                .transpose()?,
+        }),
+        Some(r#type::Type::Handle(_)) => {
+            Err("Resource handles can not be passed to promises".to_string())
+        }
     }
 }
 
@@ -258,24 +532,35 @@ fn get_promise_result_redis_key(promise_id: &PromiseId) -> String {
     format!("{}:completed", promise_id.to_redis_key())
 }
 
+fn get_promise_type_redis_key(promise_id: &PromiseId) -> String {
+    format!("{}:type", promise_id.to_redis_key())
+}
+
+fn get_pending_promises_redis_key(worker_id: &WorkerId) -> String {
+    format!("worker:pending-promises:{}", worker_id.to_redis_key())
+}
+
 #[derive(Debug)]
 enum PromiseState {
     Pending(
-        Arc<Mutex<Option<oneshot::Sender<Vec<u8>>>>>,
-        Mutex<oneshot::Receiver<Vec<u8>>>,
+        Arc<Mutex<Option<oneshot::Sender<Result<Vec<u8>, GolemError>>>>>,
+        Mutex<oneshot::Receiver<Result<Vec<u8>, GolemError>>>,
     ),
-    Complete(Vec<u8>),
+    Complete(Result<Vec<u8>, GolemError>),
 }
 
 #[derive(Debug, Serialize, Deserialize, Encode, Decode)]
 enum RedisPromiseState {
     Pending,
     Complete(Vec<u8>),
+    TimedOut,
+    PendingUntil(Timestamp),
 }
 
 #[cfg(any(feature = "mocks", test))]
 pub struct PromiseServiceMock {
     completed: Arc<Mutex<HashSet<PromiseId>>>,
+    expired: Arc<Mutex<HashSet<PromiseId>>>,
 }
 
 #[cfg(any(feature = "mocks", test))]
@@ -290,18 +575,29 @@ impl PromiseServiceMock {
     pub fn new() -> Self {
         Self {
             completed: Arc::new(Mutex::new(HashSet::new())),
+            expired: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
     pub async fn all_completed(&self) -> HashSet<PromiseId> {
         self.completed.lock().await.clone()
     }
+
+    pub async fn all_expired(&self) -> HashSet<PromiseId> {
+        self.expired.lock().await.clone()
+    }
 }
 
 #[cfg(any(feature = "mocks", test))]
 #[async_trait]
 impl PromiseService for PromiseServiceMock {
-    async fn create(&self, _worker_id: &WorkerId, _oplog_idx: OplogIndex) -> PromiseId {
+    async fn create(
+        &self,
+        _worker_id: &WorkerId,
+        _oplog_idx: OplogIndex,
+        _expires_at: Option<Timestamp>,
+        _payload_type: Option<Type>,
+    ) -> PromiseId {
         unimplemented!()
     }
 
@@ -318,7 +614,96 @@ impl PromiseService for PromiseServiceMock {
         Ok(true)
     }
 
+    async fn expire(&self, promise_id: PromiseId) -> Result<bool, GolemError> {
+        self.expired.lock().await.insert(promise_id);
+        Ok(true)
+    }
+
     async fn delete(&self, _promise_id: PromiseId) {
         unimplemented!()
     }
+
+    async fn get_pending(&self, _worker_id: &WorkerId) -> Vec<PendingPromise> {
+        unimplemented!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use uuid::Uuid;
+
+    use golem_common::model::oplog::OplogIndex;
+    use golem_common::model::{ComponentId, PromiseId, WorkerId};
+    use golem_wasm_rpc::protobuf::{r#type, NameTypePair, Type, TypePrimitive, TypeRecord};
+
+    use crate::error::GolemError;
+    use crate::services::promise::{DefaultPromiseService, PromiseService};
+    use crate::storage::keyvalue::memory::InMemoryKeyValueStorage;
+
+    fn primitive(primitive: i32) -> Type {
+        Type {
+            r#type: Some(r#type::Type::Primitive(TypePrimitive { primitive })),
+        }
+    }
+
+    fn record_type() -> Type {
+        Type {
+            r#type: Some(r#type::Type::Record(TypeRecord {
+                fields: vec![
+                    NameTypePair {
+                        name: "name".to_string(),
+                        typ: Some(primitive(12)),
+                    },
+                    NameTypePair {
+                        name: "count".to_string(),
+                        typ: Some(primitive(6)),
+                    },
+                ],
+            })),
+        }
+    }
+
+    async fn create(service: &DefaultPromiseService, payload_type: Option<Type>) -> PromiseId {
+        let worker_id = WorkerId {
+            component_id: ComponentId(Uuid::new_v4()),
+            worker_name: "worker1".to_string(),
+        };
+        service
+            .create(&worker_id, OplogIndex::from_u64(3), None, payload_type)
+            .await
+    }
+
+    #[tokio::test]
+    async fn typed_promises_are_type_checked() {
+        let service = DefaultPromiseService::new(Arc::new(InMemoryKeyValueStorage::new()));
+        let promise_id = create(&service, Some(record_type())).await;
+
+        let raw = service.complete(promise_id.clone(), vec![1, 2, 3]).await;
+        let invalid = service
+            .complete(promise_id.clone(), br#"{"name": 1}"#.to_vec())
+            .await;
+        let valid = service
+            .complete(promise_id.clone(), br#"{"name": "x", "count": 2}"#.to_vec())
+            .await;
+        let result = service.poll(promise_id).await;
+
+        assert!(matches!(raw, Err(GolemError::ValueMismatch { .. })));
+        assert!(matches!(invalid, Err(GolemError::ValueMismatch { .. })));
+        assert!(matches!(valid, Ok(true)));
+        assert!(matches!(result, Ok(Some(data)) if data == br#"{"name": "x", "count": 2}"#));
+    }
+
+    #[tokio::test]
+    async fn untyped_promises_accept_any_bytes() {
+        let service = DefaultPromiseService::new(Arc::new(InMemoryKeyValueStorage::new()));
+        let promise_id = create(&service, None).await;
+
+        let completed = service.complete(promise_id.clone(), vec![1, 2, 3]).await;
+        let result = service.poll(promise_id).await;
+
+        assert!(matches!(completed, Ok(true)));
+        assert!(matches!(result, Ok(Some(data)) if data == vec![1, 2, 3]));
+    }
 }
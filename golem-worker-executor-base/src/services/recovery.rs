@@ -32,11 +32,12 @@ use crate::services::events::Events;
 use crate::services::rpc::Rpc;
 use crate::services::{
     active_workers, blob_store, component, golem_config, key_value, oplog, promise, scheduler,
-    worker, worker_activator, worker_enumeration, worker_proxy, HasActiveWorkers, HasAll,
-    HasBlobStoreService, HasComponentService, HasConfig, HasEvents, HasExtraDeps,
-    HasKeyValueService, HasOplogService, HasPromiseService, HasRecoveryManagement, HasRpc,
-    HasRunningWorkerEnumerationService, HasSchedulerService, HasWasmtimeEngine, HasWorkerActivator,
-    HasWorkerEnumerationService, HasWorkerProxy, HasWorkerService,
+    worker, worker_activator, worker_enumeration, worker_file_system, worker_proxy,
+    HasActiveWorkers, HasAll, HasBlobStoreService, HasComponentService, HasConfig, HasEvents,
+    HasExtraDeps, HasKeyValueService, HasOplogService, HasPromiseService, HasRecoveryManagement,
+    HasRpc, HasRunningWorkerEnumerationService, HasSchedulerService, HasWasmtimeEngine,
+    HasWorkerActivator, HasWorkerEnumerationService, HasWorkerFileSystemService, HasWorkerProxy,
+    HasWorkerService,
 };
 use crate::worker::Worker;
 use crate::workerctx::WorkerCtx;
@@ -90,6 +91,7 @@ pub struct RecoveryManagementDefault<Ctx: WorkerCtx> {
     recovery_override: Option<Arc<dyn Fn(WorkerId) + Send + Sync>>,
     key_value_service: Arc<dyn key_value::KeyValueService + Send + Sync>,
     blob_store_service: Arc<dyn blob_store::BlobStoreService + Send + Sync>,
+    worker_file_system_service: Arc<dyn worker_file_system::WorkerFileSystemService + Send + Sync>,
     rpc: Arc<dyn Rpc + Send + Sync>,
     worker_activator: Arc<dyn worker_activator::WorkerActivator + Send + Sync>,
     worker_proxy: Arc<dyn worker_proxy::WorkerProxy + Send + Sync>,
@@ -116,6 +118,7 @@ impl<Ctx: WorkerCtx> Clone for RecoveryManagementDefault<Ctx> {
             recovery_override: self.recovery_override.clone(),
             key_value_service: self.key_value_service.clone(),
             blob_store_service: self.blob_store_service.clone(),
+            worker_file_system_service: self.worker_file_system_service.clone(),
             rpc: self.rpc.clone(),
             worker_activator: self.worker_activator.clone(),
             worker_proxy: self.worker_proxy.clone(),
@@ -197,6 +200,14 @@ impl<Ctx: WorkerCtx> HasBlobStoreService for RecoveryManagementDefault<Ctx> {
     }
 }
 
+impl<Ctx: WorkerCtx> HasWorkerFileSystemService for RecoveryManagementDefault<Ctx> {
+    fn worker_file_system_service(
+        &self,
+    ) -> Arc<dyn worker_file_system::WorkerFileSystemService + Send + Sync> {
+        self.worker_file_system_service.clone()
+    }
+}
+
 impl<Ctx: WorkerCtx> HasSchedulerService for RecoveryManagementDefault<Ctx> {
     fn scheduler_service(&self) -> Arc<dyn scheduler::SchedulerService + Send + Sync> {
         self.scheduler_service.clone()
@@ -264,6 +275,9 @@ impl<Ctx: WorkerCtx> RecoveryManagementDefault<Ctx> {
         scheduler_service: Arc<dyn scheduler::SchedulerService + Send + Sync>,
         key_value_service: Arc<dyn key_value::KeyValueService + Send + Sync>,
         blob_store_service: Arc<dyn blob_store::BlobStoreService + Send + Sync>,
+        worker_file_system_service: Arc<
+            dyn worker_file_system::WorkerFileSystemService + Send + Sync,
+        >,
         rpc: Arc<dyn Rpc + Send + Sync>,
         worker_activator: Arc<dyn worker_activator::WorkerActivator + Send + Sync>,
         worker_proxy: Arc<dyn worker_proxy::WorkerProxy + Send + Sync>,
@@ -286,6 +300,7 @@ impl<Ctx: WorkerCtx> RecoveryManagementDefault<Ctx> {
             scheduler_service,
             key_value_service,
             blob_store_service,
+            worker_file_system_service,
             golem_config,
             recovery_override: None,
             rpc,
@@ -315,6 +330,9 @@ impl<Ctx: WorkerCtx> RecoveryManagementDefault<Ctx> {
         scheduler_service: Arc<dyn scheduler::SchedulerService + Send + Sync>,
         key_value_service: Arc<dyn key_value::KeyValueService + Send + Sync>,
         blob_store_service: Arc<dyn blob_store::BlobStoreService + Send + Sync>,
+        worker_file_system_service: Arc<
+            dyn worker_file_system::WorkerFileSystemService + Send + Sync,
+        >,
         golem_config: Arc<golem_config::GolemConfig>,
         rpc: Arc<dyn Rpc + Send + Sync>,
         worker_activator: Arc<dyn worker_activator::WorkerActivator + Send + Sync>,
@@ -341,6 +359,7 @@ impl<Ctx: WorkerCtx> RecoveryManagementDefault<Ctx> {
             scheduler_service,
             key_value_service,
             blob_store_service,
+            worker_file_system_service,
             golem_config,
             recovery_override: Some(Arc::new(recovery_override)),
             rpc,
@@ -607,6 +626,7 @@ impl RecoveryManagement for RecoveryManagementMock {
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
     use std::string::FromUtf8Error;
     use std::sync::{Arc, RwLock};
     use std::time::Duration;
@@ -646,13 +666,14 @@ mod tests {
     use crate::services::scheduler::SchedulerService;
     use crate::services::worker::WorkerService;
     use crate::services::worker_event::WorkerEventService;
+    use crate::services::worker_file_system::WorkerFileSystemService;
     use crate::services::worker_proxy::WorkerProxy;
     use crate::services::{scheduler, HasEvents};
     use crate::services::{
         worker_enumeration, All, HasAll, HasBlobStoreService, HasComponentService, HasConfig,
         HasExtraDeps, HasInvocationQueue, HasKeyValueService, HasOplog, HasPromiseService, HasRpc,
         HasRunningWorkerEnumerationService, HasWasmtimeEngine, HasWorkerActivator,
-        HasWorkerEnumerationService, HasWorkerProxy, HasWorkerService,
+        HasWorkerEnumerationService, HasWorkerFileSystemService, HasWorkerProxy, HasWorkerService,
     };
     use crate::workerctx::{
         ExternalOperations, FuelManagement, InvocationHooks, InvocationManagement, IoCapturing,
@@ -674,6 +695,10 @@ mod tests {
         fn event_service(&self) -> Arc<dyn WorkerEventService + Send + Sync> {
             unimplemented!()
         }
+
+        fn file_system_root(&self) -> PathBuf {
+            unimplemented!()
+        }
     }
 
     impl HasInvocationQueue<EmptyContext> for EmptyPublicState {
@@ -836,6 +861,14 @@ mod tests {
         async fn on_worker_update_succeeded(&self, _target_version: ComponentVersion) {
             unimplemented!()
         }
+
+        async fn is_snapshot_due(&self) -> bool {
+            unimplemented!()
+        }
+
+        async fn on_snapshot_saved(&mut self, _snapshot: &[u8]) -> Result<(), String> {
+            unimplemented!()
+        }
     }
 
     #[async_trait]
@@ -909,6 +942,7 @@ mod tests {
             >,
             _key_value_service: Arc<dyn KeyValueService + Send + Sync>,
             _blob_store_service: Arc<dyn BlobStoreService + Send + Sync>,
+            _worker_file_system_service: Arc<dyn WorkerFileSystemService + Send + Sync>,
             _event_service: Arc<dyn WorkerEventService + Send + Sync>,
             _active_workers: Arc<ActiveWorkers<Self>>,
             _oplog_service: Arc<dyn OplogService + Send + Sync>,
@@ -1023,6 +1057,7 @@ mod tests {
             scheduler,
             deps.key_value_service(),
             deps.blob_store_service(),
+            deps.worker_file_system_service(),
             deps.config(),
             deps.rpc(),
             deps.worker_activator(),
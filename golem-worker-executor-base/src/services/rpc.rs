@@ -30,10 +30,11 @@ use crate::services::worker_proxy::{WorkerProxy, WorkerProxyError};
 use crate::services::{
     active_workers, blob_store, component, golem_config, key_value, oplog, promise, recovery,
     scheduler, shard, shard_manager, worker, worker_activator, worker_enumeration,
-    HasActiveWorkers, HasBlobStoreService, HasComponentService, HasConfig, HasEvents, HasExtraDeps,
-    HasKeyValueService, HasOplogService, HasPromiseService, HasRecoveryManagement, HasRpc,
-    HasRunningWorkerEnumerationService, HasSchedulerService, HasShardService, HasWasmtimeEngine,
-    HasWorkerActivator, HasWorkerEnumerationService, HasWorkerProxy, HasWorkerService,
+    worker_file_system, HasActiveWorkers, HasBlobStoreService, HasComponentService, HasConfig,
+    HasEvents, HasExtraDeps, HasKeyValueService, HasOplogService, HasPromiseService,
+    HasRecoveryManagement, HasRpc, HasRunningWorkerEnumerationService, HasSchedulerService,
+    HasShardService, HasWasmtimeEngine, HasWorkerActivator, HasWorkerEnumerationService,
+    HasWorkerFileSystemService, HasWorkerProxy, HasWorkerService,
 };
 use crate::worker::{invoke, invoke_and_await, Worker};
 use crate::workerctx::WorkerCtx;
@@ -225,6 +226,7 @@ pub struct DirectWorkerInvocationRpc<Ctx: WorkerCtx> {
     shard_service: Arc<dyn shard::ShardService + Send + Sync>,
     key_value_service: Arc<dyn key_value::KeyValueService + Send + Sync>,
     blob_store_service: Arc<dyn blob_store::BlobStoreService + Send + Sync>,
+    worker_file_system_service: Arc<dyn worker_file_system::WorkerFileSystemService + Send + Sync>,
     oplog_service: Arc<dyn oplog::OplogService + Send + Sync>,
     recovery_management: Arc<Mutex<Option<Arc<dyn recovery::RecoveryManagement + Send + Sync>>>>,
     scheduler_service: Arc<dyn scheduler::SchedulerService + Send + Sync>,
@@ -251,6 +253,7 @@ impl<Ctx: WorkerCtx> Clone for DirectWorkerInvocationRpc<Ctx> {
             shard_service: self.shard_service.clone(),
             key_value_service: self.key_value_service.clone(),
             blob_store_service: self.blob_store_service.clone(),
+            worker_file_system_service: self.worker_file_system_service.clone(),
             oplog_service: self.oplog_service.clone(),
             recovery_management: self.recovery_management.clone(),
             scheduler_service: self.scheduler_service.clone(),
@@ -339,6 +342,14 @@ impl<Ctx: WorkerCtx> HasBlobStoreService for DirectWorkerInvocationRpc<Ctx> {
     }
 }
 
+impl<Ctx: WorkerCtx> HasWorkerFileSystemService for DirectWorkerInvocationRpc<Ctx> {
+    fn worker_file_system_service(
+        &self,
+    ) -> Arc<dyn worker_file_system::WorkerFileSystemService + Send + Sync> {
+        self.worker_file_system_service.clone()
+    }
+}
+
 impl<Ctx: WorkerCtx> HasSchedulerService for DirectWorkerInvocationRpc<Ctx> {
     fn scheduler_service(&self) -> Arc<dyn scheduler::SchedulerService + Send + Sync> {
         self.scheduler_service.clone()
@@ -413,6 +424,9 @@ impl<Ctx: WorkerCtx> DirectWorkerInvocationRpc<Ctx> {
         shard_manager_service: Arc<dyn shard_manager::ShardManagerService + Send + Sync>,
         key_value_service: Arc<dyn key_value::KeyValueService + Send + Sync>,
         blob_store_service: Arc<dyn blob_store::BlobStoreService + Send + Sync>,
+        worker_file_system_service: Arc<
+            dyn worker_file_system::WorkerFileSystemService + Send + Sync,
+        >,
         oplog_service: Arc<dyn oplog::OplogService + Send + Sync>,
         scheduler_service: Arc<dyn scheduler::SchedulerService + Send + Sync>,
         worker_activator: Arc<dyn worker_activator::WorkerActivator + Send + Sync>,
@@ -435,6 +449,7 @@ impl<Ctx: WorkerCtx> DirectWorkerInvocationRpc<Ctx> {
             shard_service,
             key_value_service,
             blob_store_service,
+            worker_file_system_service,
             oplog_service,
             recovery_management: Arc::new(Mutex::new(None)),
             scheduler_service,
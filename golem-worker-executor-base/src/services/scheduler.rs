@@ -22,9 +22,12 @@ use chrono::{DateTime, TimeZone, Utc};
 use tokio::task::JoinHandle;
 use tracing::{error, span, Instrument, Level};
 
-use golem_common::model::{ScheduleId, ScheduledAction};
+use golem_common::model::{
+    OwnedWorkerId, ScheduleId, ScheduledAction, ScheduledInvocation, ScheduledInvocationId,
+};
 
-use crate::metrics::promises::record_scheduled_promise_completed;
+use crate::metrics::promises::{record_promise_expired, record_scheduled_promise_completed};
+use crate::metrics::retention::{record_oplog_archived, record_worker_deleted_by_retention};
 use crate::services::oplog::{MultiLayerOplog, OplogService};
 use crate::services::promise::PromiseService;
 use crate::services::shard::ShardService;
@@ -38,6 +41,28 @@ pub trait SchedulerService {
     async fn schedule(&self, time: DateTime<Utc>, action: ScheduledAction) -> ScheduleId;
 
     async fn cancel(&self, id: ScheduleId);
+
+    /// Schedules an invocation and registers it in the list of the worker's pending
+    /// scheduled invocations
+    async fn schedule_invocation(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        invocation: ScheduledInvocation,
+    ) -> ScheduleId;
+
+    /// Gets the scheduled invocations of a worker which were not enqueued yet
+    async fn get_scheduled_invocations(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Vec<ScheduledInvocation>;
+
+    /// Cancels a scheduled invocation, including all its future occurrences. Returns false
+    /// if there was no such scheduled invocation.
+    async fn cancel_scheduled_invocation(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        id: &ScheduledInvocationId,
+    ) -> bool;
 }
 
 #[derive(Clone)]
@@ -129,7 +154,10 @@ impl SchedulerServiceDefault {
 
         let mut owned_worker_ids = HashSet::new();
         for (key, action) in matching {
-            owned_worker_ids.insert(action.owned_worker_id().clone());
+            // Deleted workers must not be activated again
+            if !matches!(action, ScheduledAction::DeleteWorker { .. }) {
+                owned_worker_ids.insert(action.owned_worker_id().clone());
+            }
             self.key_value_storage
                 .with_entity("scheduler", "process", "scheduled_action")
                 .remove_from_sorted_set(KeyValueStorageNamespace::Schedule, key, &action)
@@ -144,6 +172,17 @@ impl SchedulerServiceDefault {
 
                     record_scheduled_promise_completed();
                 }
+                ScheduledAction::ExpirePromise { promise_id, .. } => {
+                    let expired = self
+                        .promise_service
+                        .expire(promise_id)
+                        .await
+                        .map_err(|golem_err| format!("{golem_err}"))?;
+
+                    if expired {
+                        record_promise_expired();
+                    }
+                }
                 ScheduledAction::ArchiveOplog {
                     owned_worker_id,
                     last_oplog_index,
@@ -155,6 +194,7 @@ impl SchedulerServiceDefault {
                         if current_last_index == last_oplog_index {
                             let oplog = self.oplog_service.open(&owned_worker_id).await;
                             if let Some(more) = MultiLayerOplog::try_archive(&oplog).await {
+                                record_oplog_archived();
                                 if more {
                                     self.schedule(
                                         now.add(next_after),
@@ -168,8 +208,70 @@ impl SchedulerServiceDefault {
                                 }
                             }
                         }
+                    }
+                }
+                ScheduledAction::ArchiveOplogToLastLayer {
+                    owned_worker_id,
+                    last_oplog_index,
+                } => {
+                    if self.oplog_service.exists(&owned_worker_id).await {
+                        let current_last_index =
+                            self.oplog_service.get_last_index(&owned_worker_id).await;
+                        if current_last_index == last_oplog_index {
+                            let oplog = self.oplog_service.open(&owned_worker_id).await;
+                            if let Some(true) =
+                                MultiLayerOplog::try_archive_to_last_layer(&oplog).await
+                            {
+                                record_oplog_archived();
+                            }
+                        }
+                    }
+                }
+                ScheduledAction::DeleteWorker {
+                    owned_worker_id,
+                    last_oplog_index,
+                } => {
+                    // If the worker was used again since the deletion was scheduled, a new
+                    // deletion gets scheduled when it reaches a final status again
+                    if self.oplog_service.exists(&owned_worker_id).await {
+                        let current_last_index =
+                            self.oplog_service.get_last_index(&owned_worker_id).await;
+                        if current_last_index == last_oplog_index {
+                            let first_index =
+                                self.oplog_service.get_first_index(&owned_worker_id).await;
+                            let reclaimed_entries =
+                                u64::from(current_last_index) + 1 - u64::from(first_index);
+
+                            self.worker_activator.delete_worker(&owned_worker_id).await;
 
-                        // TODO: metrics
+                            record_worker_deleted_by_retention(reclaimed_entries);
+                        }
+                    }
+                }
+                ScheduledAction::Invoke {
+                    owned_worker_id,
+                    invocation,
+                } => {
+                    self.key_value_storage
+                        .with_entity("scheduler", "process", "scheduled_invocation")
+                        .remove_from_set(
+                            KeyValueStorageNamespace::Schedule,
+                            &Self::scheduled_invocations_key(&owned_worker_id),
+                            &invocation,
+                        )
+                        .await?;
+
+                    self.worker_activator
+                        .enqueue_invocation(
+                            &owned_worker_id,
+                            invocation.idempotency_key(),
+                            invocation.full_function_name.clone(),
+                            invocation.function_input.clone(),
+                        )
+                        .await;
+
+                    if let Some(next) = invocation.next_occurrence(now) {
+                        self.schedule_invocation(&owned_worker_id, next).await;
                     }
                 }
             }
@@ -206,6 +308,15 @@ impl SchedulerServiceDefault {
     fn schedule_key_from_timestamp(timestamp: i64) -> String {
         format!("worker:schedule:{}", timestamp)
     }
+
+    fn scheduled_invocations_key(owned_worker_id: &OwnedWorkerId) -> String {
+        format!("worker:scheduled-invocations:{}", owned_worker_id.worker_id)
+    }
+
+    fn scheduled_invocation_time(invocation: &ScheduledInvocation) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(invocation.scheduled_at.to_millis() as i64)
+            .unwrap()
+    }
 }
 
 impl Drop for SchedulerServiceDefault {
@@ -258,6 +369,102 @@ impl SchedulerService for SchedulerServiceDefault {
                 )
             });
     }
+
+    async fn schedule_invocation(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        invocation: ScheduledInvocation,
+    ) -> ScheduleId {
+        self.key_value_storage
+            .with_entity("scheduler", "schedule_invocation", "scheduled_invocation")
+            .add_to_set(
+                KeyValueStorageNamespace::Schedule,
+                &Self::scheduled_invocations_key(owned_worker_id),
+                &invocation,
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!(
+                    "failed to add scheduled invocation {} of {owned_worker_id}: {err}",
+                    invocation.id
+                )
+            });
+
+        self.schedule(
+            Self::scheduled_invocation_time(&invocation),
+            ScheduledAction::Invoke {
+                owned_worker_id: owned_worker_id.clone(),
+                invocation,
+            },
+        )
+        .await
+    }
+
+    async fn get_scheduled_invocations(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Vec<ScheduledInvocation> {
+        let mut invocations: Vec<ScheduledInvocation> = self
+            .key_value_storage
+            .with_entity(
+                "scheduler",
+                "get_scheduled_invocations",
+                "scheduled_invocation",
+            )
+            .members_of_set(
+                KeyValueStorageNamespace::Schedule,
+                &Self::scheduled_invocations_key(owned_worker_id),
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to get scheduled invocations of {owned_worker_id}: {err}")
+            });
+        invocations.sort_by_key(|invocation| invocation.scheduled_at);
+        invocations
+    }
+
+    async fn cancel_scheduled_invocation(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        id: &ScheduledInvocationId,
+    ) -> bool {
+        let invocations = self.get_scheduled_invocations(owned_worker_id).await;
+        match invocations
+            .into_iter()
+            .find(|invocation| &invocation.id == id)
+        {
+            Some(invocation) => {
+                self.key_value_storage
+                    .with_entity(
+                        "scheduler",
+                        "cancel_scheduled_invocation",
+                        "scheduled_invocation",
+                    )
+                    .remove_from_set(
+                        KeyValueStorageNamespace::Schedule,
+                        &Self::scheduled_invocations_key(owned_worker_id),
+                        &invocation,
+                    )
+                    .await
+                    .unwrap_or_else(|err| {
+                        panic!("failed to remove scheduled invocation {id}: {err}")
+                    });
+
+                let (hours_since_epoch, _) =
+                    Self::split_time(Self::scheduled_invocation_time(&invocation));
+                self.cancel(ScheduleId {
+                    timestamp: hours_since_epoch,
+                    action: ScheduledAction::Invoke {
+                        owned_worker_id: owned_worker_id.clone(),
+                        invocation,
+                    },
+                })
+                .await;
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 #[cfg(any(feature = "mocks", test))]
@@ -287,6 +494,29 @@ impl SchedulerService for SchedulerServiceMock {
     async fn cancel(&self, _id: ScheduleId) {
         unimplemented!()
     }
+
+    async fn schedule_invocation(
+        &self,
+        _owned_worker_id: &OwnedWorkerId,
+        _invocation: ScheduledInvocation,
+    ) -> ScheduleId {
+        unimplemented!()
+    }
+
+    async fn get_scheduled_invocations(
+        &self,
+        _owned_worker_id: &OwnedWorkerId,
+    ) -> Vec<ScheduledInvocation> {
+        unimplemented!()
+    }
+
+    async fn cancel_scheduled_invocation(
+        &self,
+        _owned_worker_id: &OwnedWorkerId,
+        _id: &ScheduledInvocationId,
+    ) -> bool {
+        unimplemented!()
+    }
 }
 
 #[cfg(test)]
@@ -303,8 +533,12 @@ mod tests {
     use uuid::Uuid;
 
     use crate::services::oplog::mock::OplogServiceMock;
+    use golem_common::model::cron::CronSchedule;
     use golem_common::model::oplog::OplogIndex;
-    use golem_common::model::{AccountId, ComponentId, PromiseId, ScheduledAction, WorkerId};
+    use golem_common::model::{
+        AccountId, ComponentId, OwnedWorkerId, PromiseId, ScheduledAction, ScheduledInvocation,
+        ScheduledInvocationId, Timestamp, WorkerId,
+    };
 
     use crate::services::promise::PromiseServiceMock;
     use crate::services::scheduler::{SchedulerService, SchedulerServiceDefault};
@@ -636,6 +870,74 @@ mod tests {
         assert!(!completed_promises.contains(&p2));
     }
 
+    #[tokio::test]
+    pub async fn process_expire_promise_schedules() {
+        let c1: ComponentId = ComponentId(Uuid::new_v4());
+        let i1: WorkerId = WorkerId {
+            component_id: c1.clone(),
+            worker_name: "inst1".to_string(),
+        };
+
+        let account_id = AccountId {
+            value: "test-account".to_string(),
+        };
+
+        let p1: PromiseId = PromiseId {
+            worker_id: i1.clone(),
+            oplog_idx: OplogIndex::from_u64(101),
+        };
+        let p2: PromiseId = PromiseId {
+            worker_id: i1.clone(),
+            oplog_idx: OplogIndex::from_u64(123),
+        };
+
+        let kvs = Arc::new(InMemoryKeyValueStorage::new());
+
+        let shard_service = Arc::new(ShardServiceMock::new());
+        let promise_service = Arc::new(PromiseServiceMock::new());
+        let worker_activator = Arc::new(WorkerActivatorMock::new());
+        let oplog_service = Arc::new(OplogServiceMock::new());
+
+        let svc = SchedulerServiceDefault::new(
+            kvs.clone(),
+            shard_service,
+            promise_service.clone(),
+            worker_activator,
+            oplog_service,
+            Duration::from_secs(1000), // explicitly calling process for testing
+        );
+
+        let _s1 = svc
+            .schedule(
+                DateTime::from_str("2023-07-17T10:05:00Z").unwrap(),
+                ScheduledAction::ExpirePromise {
+                    promise_id: p1.clone(),
+                    account_id: account_id.clone(),
+                },
+            )
+            .await;
+        let _s2 = svc
+            .schedule(
+                DateTime::from_str("2023-07-17T10:59:00Z").unwrap(),
+                ScheduledAction::ExpirePromise {
+                    promise_id: p2.clone(),
+                    account_id: account_id.clone(),
+                },
+            )
+            .await;
+
+        svc.process(DateTime::from_str("2023-07-17T10:15:00Z").unwrap())
+            .await
+            .unwrap();
+
+        let expired_promises = promise_service.all_expired().await;
+        let completed_promises = promise_service.all_completed().await;
+
+        assert!(expired_promises.contains(&p1));
+        assert!(!expired_promises.contains(&p2));
+        assert!(completed_promises.is_empty());
+    }
+
     #[tokio::test]
     pub async fn process_past_and_current_hours_past_schedules() {
         let c1: ComponentId = ComponentId(Uuid::new_v4());
@@ -943,4 +1245,72 @@ mod tests {
         assert!(completed_promises.contains(&p3));
         assert!(completed_promises.contains(&p2));
     }
+
+    #[tokio::test]
+    pub async fn scheduled_invocations_listed_canceled_and_rescheduled() {
+        let worker_id = WorkerId {
+            component_id: ComponentId(Uuid::new_v4()),
+            worker_name: "inst1".to_string(),
+        };
+        let account_id = AccountId {
+            value: "test-account".to_string(),
+        };
+        let owned_worker_id = OwnedWorkerId::new(&account_id, &worker_id);
+
+        let kvs = Arc::new(InMemoryKeyValueStorage::new());
+
+        let svc = SchedulerServiceDefault::new(
+            kvs.clone(),
+            Arc::new(ShardServiceMock::new()),
+            Arc::new(PromiseServiceMock::new()),
+            Arc::new(WorkerActivatorMock::new()),
+            Arc::new(OplogServiceMock::new()),
+            Duration::from_secs(1000), // process() is called manually
+        );
+
+        let once = ScheduledInvocation {
+            id: ScheduledInvocationId::new_v4(),
+            scheduled_at: Timestamp::from_str("2023-07-17T10:05:00Z").unwrap(),
+            full_function_name: "golem:it/api.{f1}".to_string(),
+            function_input: vec![],
+            cron: None,
+        };
+        let recurring = ScheduledInvocation {
+            id: ScheduledInvocationId::new_v4(),
+            scheduled_at: Timestamp::from_str("2023-07-17T10:00:00Z").unwrap(),
+            full_function_name: "golem:it/api.{f2}".to_string(),
+            function_input: vec![],
+            cron: Some(CronSchedule::parse("0 * * * *").unwrap()),
+        };
+
+        svc.schedule_invocation(&owned_worker_id, once.clone())
+            .await;
+        svc.schedule_invocation(&owned_worker_id, recurring.clone())
+            .await;
+
+        let scheduled = svc.get_scheduled_invocations(&owned_worker_id).await;
+        assert_eq!(scheduled, vec![recurring.clone(), once.clone()]);
+
+        assert!(
+            svc.cancel_scheduled_invocation(&owned_worker_id, &once.id)
+                .await
+        );
+        assert!(
+            !svc.cancel_scheduled_invocation(&owned_worker_id, &once.id)
+                .await
+        );
+
+        svc.process(DateTime::from_str("2023-07-17T10:30:00Z").unwrap())
+            .await
+            .unwrap();
+
+        let scheduled = svc.get_scheduled_invocations(&owned_worker_id).await;
+        assert_eq!(
+            scheduled,
+            vec![ScheduledInvocation {
+                scheduled_at: Timestamp::from_str("2023-07-17T11:00:00Z").unwrap(),
+                ..recurring
+            }]
+        );
+    }
 }
@@ -19,13 +19,15 @@ use golem_common::model::oplog::{OplogEntry, OplogIndex};
 use golem_common::model::{
     OwnedWorkerId, ShardId, WorkerId, WorkerMetadata, WorkerStatus, WorkerStatusRecord,
 };
-use tracing::debug;
+use golem_common::serialization::deserialize;
+use tracing::{debug, warn};
 
 use crate::error::GolemError;
 use crate::metrics::workers::record_worker_call;
 
 use crate::services::oplog::OplogService;
 use crate::services::shard::ShardService;
+use crate::services::worker_file_system::WorkerFileSystemService;
 use crate::storage::keyvalue::{
     KeyValueStorage, KeyValueStorageLabelledApi, KeyValueStorageNamespace,
 };
@@ -41,6 +43,14 @@ pub trait WorkerService {
 
     async fn remove(&self, owned_worker_id: &OwnedWorkerId);
 
+    /// Gets the worker's initial (`Create`) oplog entry, either from the oplog or, if it has been
+    /// compacted, from the copy stored by `preserve_initial_entry`
+    async fn get_initial_entry(&self, owned_worker_id: &OwnedWorkerId) -> Option<OplogEntry>;
+
+    /// Stores a copy of the worker's initial oplog entry, so its metadata remains available after
+    /// the beginning of the oplog gets dropped by compaction
+    async fn preserve_initial_entry(&self, owned_worker_id: &OwnedWorkerId);
+
     async fn update_status(
         &self,
         owned_worker_id: &OwnedWorkerId,
@@ -53,6 +63,7 @@ pub struct DefaultWorkerService {
     key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
     shard_service: Arc<dyn ShardService + Send + Sync>,
     oplog_service: Arc<dyn OplogService + Send + Sync>,
+    worker_file_system_service: Arc<dyn WorkerFileSystemService + Send + Sync>,
 }
 
 impl DefaultWorkerService {
@@ -60,11 +71,13 @@ impl DefaultWorkerService {
         key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
         shard_service: Arc<dyn ShardService + Send + Sync>,
         oplog_service: Arc<dyn OplogService + Send + Sync>,
+        worker_file_system_service: Arc<dyn WorkerFileSystemService + Send + Sync>,
     ) -> Self {
         Self {
             key_value_storage,
             shard_service,
             oplog_service,
+            worker_file_system_service,
         }
     }
 
@@ -95,6 +108,10 @@ impl DefaultWorkerService {
         format!("worker:status:{}", worker_id.to_redis_key())
     }
 
+    fn initial_entry_key(worker_id: &WorkerId) -> String {
+        format!("worker:initial_entry:{}", worker_id.to_redis_key())
+    }
+
     fn running_in_shard_key(shard_id: &ShardId) -> String {
         format!("worker:running_in_shard:{shard_id}")
     }
@@ -155,26 +172,18 @@ impl WorkerService for DefaultWorkerService {
     async fn get(&self, owned_worker_id: &OwnedWorkerId) -> Option<WorkerMetadata> {
         record_worker_call("get");
 
-        let initial_oplog_entry = self
-            .oplog_service
-            .read(owned_worker_id, OplogIndex::INITIAL, 1)
-            .await
-            .into_iter()
-            .next();
+        let initial_oplog_entry = self.get_initial_entry(owned_worker_id).await;
 
         match initial_oplog_entry {
             None => None,
-            Some((
-                _,
-                OplogEntry::Create {
-                    worker_id,
-                    component_version,
-                    args,
-                    env,
-                    account_id,
-                    timestamp,
-                },
-            )) => {
+            Some(OplogEntry::Create {
+                worker_id,
+                component_version,
+                args,
+                env,
+                account_id,
+                timestamp,
+            }) => {
                 let mut details = WorkerMetadata {
                     worker_id,
                     args,
@@ -187,10 +196,10 @@ impl WorkerService for DefaultWorkerService {
                     },
                 };
 
-                let status_value: Option<WorkerStatusRecord> = self
+                let status_bytes = self
                     .key_value_storage
                     .with_entity("worker", "get", "worker_status")
-                    .get(
+                    .get_raw(
                         KeyValueStorageNamespace::Worker,
                         &Self::status_key(&owned_worker_id.worker_id),
                     )
@@ -199,13 +208,20 @@ impl WorkerService for DefaultWorkerService {
                         panic!("failed to get worker status for {owned_worker_id} from KV storage: {err}")
                     });
 
-                if let Some(status) = status_value {
-                    details.last_known_status = status;
+                // The status record is only a cache of what can be calculated from the oplog, so a
+                // record stored with an older layout is dropped and the status gets recalculated
+                if let Some(bytes) = status_bytes {
+                    match deserialize::<WorkerStatusRecord>(&bytes) {
+                        Ok(status) => details.last_known_status = status,
+                        Err(err) => warn!(
+                            "Ignoring cached worker status of {owned_worker_id} that could not be decoded: {err}"
+                        ),
+                    }
                 }
 
                 Some(details)
             }
-            Some((_, entry)) => {
+            Some(entry) => {
                 panic!("Unexpected initial oplog entry for worker: {entry:?}")
             }
         }
@@ -227,6 +243,11 @@ impl WorkerService for DefaultWorkerService {
 
         self.oplog_service.delete(owned_worker_id).await;
 
+        self.worker_file_system_service
+            .delete(owned_worker_id)
+            .await
+            .unwrap_or_else(|err| panic!("failed to remove the worker's file system: {err}"));
+
         self.key_value_storage
             .with("worker", "remove")
             .del(
@@ -238,6 +259,17 @@ impl WorkerService for DefaultWorkerService {
                 panic!("failed to remove worker status in the KV storage: {err}")
             });
 
+        self.key_value_storage
+            .with("worker", "remove")
+            .del(
+                KeyValueStorageNamespace::Worker,
+                &Self::initial_entry_key(&owned_worker_id.worker_id),
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to remove initial oplog entry in the KV storage: {err}")
+            });
+
         let shard_assignment = self.shard_service.current_assignment();
         let shard_id = ShardId::from_worker_id(
             &owned_worker_id.worker_id,
@@ -256,6 +288,47 @@ impl WorkerService for DefaultWorkerService {
             });
     }
 
+    async fn get_initial_entry(&self, owned_worker_id: &OwnedWorkerId) -> Option<OplogEntry> {
+        let initial_oplog_entry = self
+            .oplog_service
+            .read(owned_worker_id, OplogIndex::INITIAL, 1)
+            .await
+            .remove(&OplogIndex::INITIAL);
+
+        match initial_oplog_entry {
+            Some(entry) => Some(entry),
+            None => self
+                .key_value_storage
+                .with_entity("worker", "get", "initial_entry")
+                .get(
+                    KeyValueStorageNamespace::Worker,
+                    &Self::initial_entry_key(&owned_worker_id.worker_id),
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to get initial oplog entry for {owned_worker_id} from KV storage: {err}")
+                }),
+        }
+    }
+
+    async fn preserve_initial_entry(&self, owned_worker_id: &OwnedWorkerId) {
+        record_worker_call("preserve_initial_entry");
+
+        if let Some(initial_oplog_entry) = self.get_initial_entry(owned_worker_id).await {
+            self.key_value_storage
+                .with_entity("worker", "preserve_initial_entry", "initial_entry")
+                .set(
+                    KeyValueStorageNamespace::Worker,
+                    &Self::initial_entry_key(&owned_worker_id.worker_id),
+                    &initial_oplog_entry,
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to set initial oplog entry in KV storage: {err}")
+                });
+        }
+    }
+
     async fn update_status(
         &self,
         owned_worker_id: &OwnedWorkerId,
@@ -346,6 +419,14 @@ impl WorkerService for WorkerServiceMock {
         unimplemented!()
     }
 
+    async fn get_initial_entry(&self, _owned_worker_id: &OwnedWorkerId) -> Option<OplogEntry> {
+        unimplemented!()
+    }
+
+    async fn preserve_initial_entry(&self, _owned_worker_id: &OwnedWorkerId) {
+        unimplemented!()
+    }
+
     async fn update_status(
         &self,
         _owned_worker_id: &OwnedWorkerId,
@@ -16,13 +16,15 @@ use std::marker::PhantomData;
 use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
-use golem_common::model::OwnedWorkerId;
+use golem_common::cache::PendingOrFinal;
+use golem_common::model::{CallingConvention, IdempotencyKey, OwnedWorkerId};
+use golem_wasm_rpc::Value;
 #[cfg(any(feature = "mocks", test))]
 use tracing::info;
 use tracing::{error, warn};
 
-use crate::services::HasAll;
-use crate::worker::Worker;
+use crate::services::{HasAll, HasInvocationQueue};
+use crate::worker::{delete_worker, Worker};
 use crate::workerctx::WorkerCtx;
 
 /// Service for activating workers in the background
@@ -35,6 +37,19 @@ pub trait WorkerActivator {
     /// it was already active, it deactivates it first, so it is guaranteed that its recovery
     /// runs. Returns immediately
     async fn reactivate_worker(&self, owned_worker_id: &OwnedWorkerId);
+
+    /// Enqueues an invocation of an exported function on a worker, creating the worker
+    /// if it does not exist yet. Returns as soon as the invocation is persisted in the queue
+    async fn enqueue_invocation(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        idempotency_key: IdempotencyKey,
+        full_function_name: String,
+        function_input: Vec<Value>,
+    );
+
+    /// Deletes a worker, interrupting it first if it is running
+    async fn delete_worker(&self, owned_worker_id: &OwnedWorkerId);
 }
 
 pub struct LazyWorkerActivator {
@@ -76,6 +91,37 @@ impl WorkerActivator for LazyWorkerActivator {
             None => warn!("WorkerActivator is disabled, not reactivating instance"),
         }
     }
+
+    async fn enqueue_invocation(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        idempotency_key: IdempotencyKey,
+        full_function_name: String,
+        function_input: Vec<Value>,
+    ) {
+        let maybe_worker_activator = self.worker_activator.lock().unwrap().clone();
+        match maybe_worker_activator {
+            Some(worker_activator) => {
+                worker_activator
+                    .enqueue_invocation(
+                        owned_worker_id,
+                        idempotency_key,
+                        full_function_name,
+                        function_input,
+                    )
+                    .await
+            }
+            None => warn!("WorkerActivator is disabled, not enqueuing invocation"),
+        }
+    }
+
+    async fn delete_worker(&self, owned_worker_id: &OwnedWorkerId) {
+        let maybe_worker_activator = self.worker_activator.lock().unwrap().clone();
+        match maybe_worker_activator {
+            Some(worker_activator) => worker_activator.delete_worker(owned_worker_id).await,
+            None => warn!("WorkerActivator is disabled, not deleting instance"),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -135,6 +181,56 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + Send + Sync + 'static> WorkerActivator
             }
         }
     }
+
+    async fn enqueue_invocation(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        idempotency_key: IdempotencyKey,
+        full_function_name: String,
+        function_input: Vec<Value>,
+    ) {
+        let metadata = self.all.worker_service().get(owned_worker_id).await;
+        let (worker_args, worker_env, component_version) = match metadata {
+            Some(metadata) => (
+                metadata.args,
+                metadata.env,
+                Some(metadata.last_known_status.component_version),
+            ),
+            None => (vec![], vec![], None),
+        };
+
+        let invocation_queue = match Worker::get_or_create_pending(
+            &self.all,
+            owned_worker_id,
+            worker_args,
+            worker_env,
+            component_version,
+        )
+        .await
+        {
+            Ok(PendingOrFinal::Pending(pending_worker)) => pending_worker.invocation_queue.clone(),
+            Ok(PendingOrFinal::Final(worker)) => worker.public_state.invocation_queue(),
+            Err(err) => {
+                error!("WorkerActivator::enqueue_invocation: failed to get worker: {err}");
+                return;
+            }
+        };
+
+        invocation_queue
+            .enqueue(
+                idempotency_key,
+                full_function_name,
+                function_input,
+                CallingConvention::Component,
+            )
+            .await;
+    }
+
+    async fn delete_worker(&self, owned_worker_id: &OwnedWorkerId) {
+        if let Err(err) = delete_worker(&self.all, owned_worker_id).await {
+            error!("WorkerActivator::delete_worker: failed to delete worker: {err}");
+        }
+    }
 }
 
 #[cfg(any(feature = "mocks", test))]
@@ -164,4 +260,18 @@ impl WorkerActivator for WorkerActivatorMock {
     async fn reactivate_worker(&self, _owned_worker_id: &OwnedWorkerId) {
         info!("WorkerActivatorMock::reactivate_worker");
     }
+
+    async fn enqueue_invocation(
+        &self,
+        _owned_worker_id: &OwnedWorkerId,
+        _idempotency_key: IdempotencyKey,
+        _full_function_name: String,
+        _function_input: Vec<Value>,
+    ) {
+        info!("WorkerActivatorMock::enqueue_invocation");
+    }
+
+    async fn delete_worker(&self, _owned_worker_id: &OwnedWorkerId) {
+        info!("WorkerActivatorMock::delete_worker");
+    }
 }
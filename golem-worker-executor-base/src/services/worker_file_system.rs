@@ -0,0 +1,1169 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bincode::{Decode, Encode};
+use dashmap::DashMap;
+use sha2::{Digest, Sha256};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+use tracing::debug;
+
+use golem_common::model::oplog::OplogIndex;
+use golem_common::model::OwnedWorkerId;
+
+use crate::error::GolemError;
+use crate::storage::blob::{BlobStorage, BlobStorageLabelledApi, BlobStorageNamespace};
+
+/// Persists the preopened directory of workers, so their files survive the worker being
+/// recovered on another executor.
+///
+/// A synced file system belongs to an oplog index. Restoring it is only valid when the worker's
+/// recovery starts replaying from that same index, as the entries after it are replayed on top
+/// of the restored files.
+#[async_trait]
+pub trait WorkerFileSystemService {
+    /// Uploads the current content of the worker's root directory before the snapshot at the
+    /// given oplog index gets committed. The previously synced one is kept until the next sync,
+    /// in case the snapshot never gets committed.
+    async fn sync(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        oplog_index: OplogIndex,
+        root: &Path,
+    ) -> Result<(), String>;
+
+    /// Downloads the worker's file system into the root directory if it was synced at the given
+    /// oplog index. Returns whether anything was restored.
+    async fn restore(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        oplog_index: OplogIndex,
+        root: &Path,
+    ) -> Result<bool, String>;
+
+    /// Copies the file system synced at the given oplog index to another worker, so a worker
+    /// forked from a snapshot can restore it. Returns whether it was found.
+    async fn copy_synced(
+        &self,
+        source_worker_id: &OwnedWorkerId,
+        target_worker_id: &OwnedWorkerId,
+        oplog_index: OplogIndex,
+    ) -> Result<bool, String>;
+
+    /// Uploads the current content of the worker's root directory when the worker leaves the
+    /// memory, so it can be inspected without activating the worker. Unlike `sync`, this is never
+    /// used for restoring the file system. Nothing is uploaded if the file system was already
+    /// synced at the given oplog index.
+    async fn sync_latest(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        oplog_index: OplogIndex,
+        root: &Path,
+    ) -> Result<(), String>;
+
+    /// Returns the oplog index the worker's file system was last synced at
+    async fn synced_at(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Result<Option<OplogIndex>, String>;
+
+    /// Lists a directory of the most recently synced file system of an inactive worker. Returns
+    /// `None` if the worker's file system was never synced.
+    async fn list_synced_directory(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        path: &str,
+    ) -> Result<Option<Vec<WorkerFileEntry>>, GolemError>;
+
+    /// Reads a file of the most recently synced file system of an inactive worker. Returns
+    /// `None` if the worker's file system was never synced.
+    async fn read_synced_file(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        path: &str,
+    ) -> Result<Option<Vec<u8>>, GolemError>;
+
+    async fn delete(&self, owned_worker_id: &OwnedWorkerId) -> Result<(), String>;
+}
+
+/// Lists the files and directories of a synced worker file system. The content of the files is
+/// stored separately, addressed by its hash, so unchanged files are not uploaded again.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+struct Manifest {
+    oplog_index: OplogIndex,
+    directories: Vec<String>,
+    files: Vec<ManifestFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+struct ManifestFile {
+    path: String,
+    size: u64,
+    hash: String,
+}
+
+impl Manifest {
+    fn hashes(&self) -> HashSet<&str> {
+        self.files.iter().map(|file| file.hash.as_str()).collect()
+    }
+
+    fn entries(&self, path: &str) -> Result<Vec<WorkerFileEntry>, GolemError> {
+        let dir = normalize_path(path)?;
+        if !dir.is_empty() && !self.directories.contains(&dir) {
+            return Err(GolemError::invalid_request(format!(
+                "{path} does not exist"
+            )));
+        }
+
+        let is_child = |entry: &str| parent_of(entry) == dir;
+        let mut result: Vec<WorkerFileEntry> = self
+            .directories
+            .iter()
+            .filter(|directory| is_child(directory))
+            .map(|directory| WorkerFileEntry {
+                name: name_of(directory).to_string(),
+                is_directory: true,
+                size: 0,
+                read_only: false,
+            })
+            .chain(
+                self.files
+                    .iter()
+                    .filter(|file| is_child(&file.path))
+                    .map(|file| WorkerFileEntry {
+                        name: name_of(&file.path).to_string(),
+                        is_directory: false,
+                        size: file.size,
+                        read_only: false,
+                    }),
+            )
+            .collect();
+
+        result.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(result)
+    }
+
+    fn file(&self, path: &str) -> Result<&ManifestFile, GolemError> {
+        let file_path = normalize_path(path)?;
+        match self.files.iter().find(|file| file.path == file_path) {
+            Some(file) => Ok(file),
+            None if self.directories.contains(&file_path) => Err(GolemError::invalid_request(
+                format!("{path} is a directory"),
+            )),
+            None => Err(GolemError::invalid_request(format!(
+                "{path} does not exist"
+            ))),
+        }
+    }
+}
+
+// Manifest paths are relative to the root and use `/` as the separator
+fn normalize_path(path: &str) -> Result<String, GolemError> {
+    let relative = Path::new(path.trim_start_matches('/'));
+    let mut components = Vec::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(name) => components.push(name.to_string_lossy().to_string()),
+            Component::CurDir => {}
+            _ => return Err(GolemError::invalid_request(format!("Invalid path {path}"))),
+        }
+    }
+    Ok(components.join("/"))
+}
+
+fn parent_of(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(parent, _)| parent)
+}
+
+fn name_of(path: &str) -> &str {
+    path.rsplit_once('/').map_or(path, |(_, name)| name)
+}
+
+/// The snapshot manifest is only replaced when a snapshot is saved, and is used for restoring the
+/// file system. The previous snapshot manifest is kept until the next snapshot is saved, as the
+/// file system is synced before the snapshot entry is committed. The latest manifest is replaced
+/// whenever the worker leaves the memory.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ManifestKind {
+    Snapshot,
+    PreviousSnapshot,
+    Latest,
+}
+
+impl ManifestKind {
+    const ALL: [ManifestKind; 3] = [
+        ManifestKind::Snapshot,
+        ManifestKind::PreviousSnapshot,
+        ManifestKind::Latest,
+    ];
+}
+
+/// Holds the lock of a worker's synced file system, and forgets the lock once nobody else is
+/// waiting for it
+struct WorkerFileSystemLock<'a> {
+    locks: &'a DashMap<OwnedWorkerId, Arc<Mutex<()>>>,
+    owned_worker_id: OwnedWorkerId,
+    guard: Option<OwnedMutexGuard<()>>,
+}
+
+impl Drop for WorkerFileSystemLock<'_> {
+    fn drop(&mut self) {
+        drop(self.guard.take());
+        self.locks.remove_if(&self.owned_worker_id, |_, lock| {
+            Arc::strong_count(lock) == 1
+        });
+    }
+}
+
+pub struct DefaultWorkerFileSystemService {
+    blob_storage: Arc<dyn BlobStorage + Send + Sync>,
+    // Operations on the same worker's file system are serialized, so the content deleted by one
+    // of them is never referenced by a manifest stored by another
+    locks: DashMap<OwnedWorkerId, Arc<Mutex<()>>>,
+}
+
+impl DefaultWorkerFileSystemService {
+    pub fn new(blob_storage: Arc<dyn BlobStorage + Send + Sync>) -> Self {
+        Self {
+            blob_storage,
+            locks: DashMap::new(),
+        }
+    }
+
+    async fn lock(&self, owned_worker_id: &OwnedWorkerId) -> WorkerFileSystemLock<'_> {
+        let lock = self
+            .locks
+            .entry(owned_worker_id.clone())
+            .or_default()
+            .clone();
+        WorkerFileSystemLock {
+            locks: &self.locks,
+            owned_worker_id: owned_worker_id.clone(),
+            guard: Some(lock.lock_owned().await),
+        }
+    }
+
+    fn namespace(owned_worker_id: &OwnedWorkerId) -> BlobStorageNamespace {
+        BlobStorageNamespace::WorkerFileSystem {
+            account_id: owned_worker_id.account_id(),
+            worker_id: owned_worker_id.worker_id(),
+        }
+    }
+
+    fn manifest_path(kind: ManifestKind) -> PathBuf {
+        match kind {
+            ManifestKind::Snapshot => Path::new("manifest").to_path_buf(),
+            ManifestKind::PreviousSnapshot => Path::new("previous-manifest").to_path_buf(),
+            ManifestKind::Latest => Path::new("latest-manifest").to_path_buf(),
+        }
+    }
+
+    fn content_path(hash: &str) -> PathBuf {
+        Path::new("content").join(hash)
+    }
+
+    async fn get_manifest(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        kind: ManifestKind,
+    ) -> Result<Option<Manifest>, String> {
+        self.blob_storage
+            .with("worker_file_system", "get_manifest")
+            .get(Self::namespace(owned_worker_id), &Self::manifest_path(kind))
+            .await
+    }
+
+    async fn get_manifests(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Result<Vec<(ManifestKind, Manifest)>, String> {
+        let mut result = Vec::new();
+        for kind in ManifestKind::ALL {
+            if let Some(manifest) = self.get_manifest(owned_worker_id, kind).await? {
+                result.push((kind, manifest));
+            }
+        }
+        Ok(result)
+    }
+
+    /// The snapshot manifest synced at the given oplog index, if it is still stored
+    async fn get_snapshot_manifest(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        oplog_index: OplogIndex,
+    ) -> Result<Option<Manifest>, String> {
+        for kind in [ManifestKind::Snapshot, ManifestKind::PreviousSnapshot] {
+            match self.get_manifest(owned_worker_id, kind).await? {
+                Some(manifest) if manifest.oplog_index == oplog_index => return Ok(Some(manifest)),
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    /// The manifest describing the most recent state of the worker's file system
+    async fn get_most_recent_manifest(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Result<Option<Manifest>, GolemError> {
+        let error = |err: String| {
+            GolemError::runtime(format!("Failed to get the synced file system: {err}"))
+        };
+        let snapshot = self
+            .get_manifest(owned_worker_id, ManifestKind::Snapshot)
+            .await
+            .map_err(error)?;
+        let latest = self
+            .get_manifest(owned_worker_id, ManifestKind::Latest)
+            .await
+            .map_err(error)?;
+
+        Ok(match (snapshot, latest) {
+            (Some(snapshot), Some(latest)) if snapshot.oplog_index > latest.oplog_index => {
+                Some(snapshot)
+            }
+            (snapshot, latest) => latest.or(snapshot),
+        })
+    }
+
+    /// Stores a manifest of the root directory, uploading the content not referenced by any of
+    /// the manifests yet, and deleting the content no longer referenced by any of the manifests
+    /// once it is stored. Must be called while holding the worker's lock.
+    async fn store_manifest(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        kind: ManifestKind,
+        oplog_index: OplogIndex,
+        root: &Path,
+    ) -> Result<Manifest, String> {
+        let namespace = Self::namespace(owned_worker_id);
+        let manifests = self.get_manifests(owned_worker_id).await?;
+        let mut uploaded: HashSet<String> = manifests
+            .iter()
+            .flat_map(|(_, manifest)| manifest.hashes().into_iter().map(|h| h.to_string()))
+            .collect();
+        let previously_referenced = uploaded.clone();
+
+        let (directories, paths) = Self::scan(root).await?;
+        let mut files = Vec::new();
+        for (relative, path) in paths {
+            let data = tokio::fs::read(&path)
+                .await
+                .map_err(|err| format!("Failed to read file {path:?}: {err}"))?;
+            let hash = Self::hash(&data);
+            if !uploaded.contains(&hash) {
+                self.blob_storage
+                    .with("worker_file_system", "sync")
+                    .put_raw(namespace.clone(), &Self::content_path(&hash), &data)
+                    .await?;
+                uploaded.insert(hash.clone());
+            }
+            files.push(ManifestFile {
+                path: relative,
+                size: data.len() as u64,
+                hash,
+            });
+        }
+
+        let manifest = Manifest {
+            oplog_index,
+            directories,
+            files,
+        };
+
+        let previous_snapshot = manifests
+            .iter()
+            .find(|(existing, _)| *existing == ManifestKind::Snapshot)
+            .map(|(_, manifest)| manifest.clone());
+        if let (ManifestKind::Snapshot, Some(previous)) = (kind, previous_snapshot) {
+            self.blob_storage
+                .with("worker_file_system", "sync")
+                .put(
+                    namespace.clone(),
+                    &Self::manifest_path(ManifestKind::PreviousSnapshot),
+                    &previous,
+                )
+                .await?;
+        }
+        self.blob_storage
+            .with("worker_file_system", "sync")
+            .put(namespace.clone(), &Self::manifest_path(kind), &manifest)
+            .await?;
+
+        // The manifests are read again, so content referenced by a manifest stored in the
+        // meantime is kept
+        let stored = self.get_manifests(owned_worker_id).await?;
+        let referenced: HashSet<&str> = stored
+            .iter()
+            .flat_map(|(_, manifest)| manifest.hashes())
+            .collect();
+        let unused: Vec<PathBuf> = previously_referenced
+            .iter()
+            .filter(|hash| !referenced.contains(hash.as_str()))
+            .map(|hash| Self::content_path(hash))
+            .collect();
+        if !unused.is_empty() {
+            self.blob_storage
+                .with("worker_file_system", "sync")
+                .delete_many(namespace, &unused)
+                .await?;
+        }
+
+        Ok(manifest)
+    }
+
+    /// Collects the directories and files under the root, with paths relative to it using `/`
+    /// as the separator. Symbolic links are not followed.
+    async fn scan(root: &Path) -> Result<(Vec<String>, Vec<(String, PathBuf)>), String> {
+        let mut directories = Vec::new();
+        let mut files = Vec::new();
+        let mut pending = vec![root.to_path_buf()];
+
+        while let Some(dir) = pending.pop() {
+            let mut entries = tokio::fs::read_dir(&dir)
+                .await
+                .map_err(|err| format!("Failed to read directory {dir:?}: {err}"))?;
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .map_err(|err| format!("Failed to read directory {dir:?}: {err}"))?
+            {
+                let path = entry.path();
+                let relative = Self::relative_path(root, &path)?;
+                let file_type = entry
+                    .file_type()
+                    .await
+                    .map_err(|err| format!("Failed to get the type of {path:?}: {err}"))?;
+                if file_type.is_dir() {
+                    directories.push(relative);
+                    pending.push(path);
+                } else if file_type.is_file() {
+                    files.push((relative, path));
+                }
+            }
+        }
+
+        directories.sort();
+        files.sort();
+        Ok((directories, files))
+    }
+
+    fn relative_path(root: &Path, path: &Path) -> Result<String, String> {
+        let relative = path
+            .strip_prefix(root)
+            .map_err(|err| format!("Path {path:?} is not within {root:?}: {err}"))?;
+        Ok(relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/"))
+    }
+
+    fn hash(data: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(data))
+    }
+}
+
+#[async_trait]
+impl WorkerFileSystemService for DefaultWorkerFileSystemService {
+    async fn sync(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        oplog_index: OplogIndex,
+        root: &Path,
+    ) -> Result<(), String> {
+        let _lock = self.lock(owned_worker_id).await;
+        let manifest = self
+            .store_manifest(owned_worker_id, ManifestKind::Snapshot, oplog_index, root)
+            .await?;
+
+        debug!(
+            "Synced {} files of the worker file system at oplog index {oplog_index}",
+            manifest.files.len()
+        );
+        Ok(())
+    }
+
+    async fn sync_latest(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        oplog_index: OplogIndex,
+        root: &Path,
+    ) -> Result<(), String> {
+        let _lock = self.lock(owned_worker_id).await;
+
+        // The files only change while the worker runs, which always adds entries to its oplog
+        let up_to_date =
+            self.get_manifests(owned_worker_id)
+                .await?
+                .iter()
+                .any(|(kind, manifest)| {
+                    *kind != ManifestKind::PreviousSnapshot && manifest.oplog_index == oplog_index
+                });
+        if up_to_date {
+            debug!("The worker file system is already synced at oplog index {oplog_index}");
+            return Ok(());
+        }
+
+        let manifest = self
+            .store_manifest(owned_worker_id, ManifestKind::Latest, oplog_index, root)
+            .await?;
+
+        debug!(
+            "Synced {} files of the latest worker file system at oplog index {oplog_index}",
+            manifest.files.len()
+        );
+        Ok(())
+    }
+
+    async fn restore(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        oplog_index: OplogIndex,
+        root: &Path,
+    ) -> Result<bool, String> {
+        let _lock = self.lock(owned_worker_id).await;
+        let Some(manifest) = self
+            .get_snapshot_manifest(owned_worker_id, oplog_index)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        for directory in &manifest.directories {
+            let path = root.join(directory);
+            tokio::fs::create_dir_all(&path)
+                .await
+                .map_err(|err| format!("Failed to create directory {path:?}: {err}"))?;
+        }
+
+        for file in &manifest.files {
+            let data = self
+                .blob_storage
+                .with("worker_file_system", "restore")
+                .get_raw(
+                    Self::namespace(owned_worker_id),
+                    &Self::content_path(&file.hash),
+                )
+                .await?
+                .ok_or(format!("Content of file {} is missing", file.path))?;
+            let path = root.join(&file.path);
+            tokio::fs::write(&path, &data)
+                .await
+                .map_err(|err| format!("Failed to write file {path:?}: {err}"))?;
+        }
+
+        debug!(
+            "Restored {} files of the worker file system from oplog index {oplog_index}",
+            manifest.files.len()
+        );
+        Ok(true)
+    }
+
+    async fn copy_synced(
+        &self,
+        source_worker_id: &OwnedWorkerId,
+        target_worker_id: &OwnedWorkerId,
+        oplog_index: OplogIndex,
+    ) -> Result<bool, String> {
+        let _lock = self.lock(source_worker_id).await;
+        let Some(manifest) = self
+            .get_snapshot_manifest(source_worker_id, oplog_index)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        let source_namespace = Self::namespace(source_worker_id);
+        let target_namespace = Self::namespace(target_worker_id);
+        for hash in manifest.hashes() {
+            let path = Self::content_path(hash);
+            let data = self
+                .blob_storage
+                .with("worker_file_system", "copy_synced")
+                .get_raw(source_namespace.clone(), &path)
+                .await?
+                .ok_or(format!("Content {hash} is missing"))?;
+            self.blob_storage
+                .with("worker_file_system", "copy_synced")
+                .put_raw(target_namespace.clone(), &path, &data)
+                .await?;
+        }
+        self.blob_storage
+            .with("worker_file_system", "copy_synced")
+            .put(
+                target_namespace,
+                &Self::manifest_path(ManifestKind::Snapshot),
+                &manifest,
+            )
+            .await?;
+
+        Ok(true)
+    }
+
+    async fn synced_at(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Result<Option<OplogIndex>, String> {
+        Ok(self
+            .get_manifest(owned_worker_id, ManifestKind::Snapshot)
+            .await?
+            .map(|manifest| manifest.oplog_index))
+    }
+
+    async fn list_synced_directory(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        path: &str,
+    ) -> Result<Option<Vec<WorkerFileEntry>>, GolemError> {
+        match self.get_most_recent_manifest(owned_worker_id).await? {
+            Some(manifest) => manifest.entries(path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn read_synced_file(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        path: &str,
+    ) -> Result<Option<Vec<u8>>, GolemError> {
+        let _lock = self.lock(owned_worker_id).await;
+        let Some(manifest) = self.get_most_recent_manifest(owned_worker_id).await? else {
+            return Ok(None);
+        };
+        let file = manifest.file(path)?;
+
+        let data = self
+            .blob_storage
+            .with("worker_file_system", "read_synced_file")
+            .get_raw(
+                Self::namespace(owned_worker_id),
+                &Self::content_path(&file.hash),
+            )
+            .await
+            .map_err(|err| GolemError::runtime(format!("Failed to read {path}: {err}")))?
+            .ok_or(GolemError::runtime(format!(
+                "Content of file {path} is missing"
+            )))?;
+        Ok(Some(data.to_vec()))
+    }
+
+    async fn delete(&self, owned_worker_id: &OwnedWorkerId) -> Result<(), String> {
+        let _lock = self.lock(owned_worker_id).await;
+        let namespace = Self::namespace(owned_worker_id);
+        let mut paths = Vec::new();
+        for kind in ManifestKind::ALL {
+            if let Some(manifest) = self.get_manifest(owned_worker_id, kind).await? {
+                paths.extend(manifest.hashes().into_iter().map(Self::content_path));
+                paths.push(Self::manifest_path(kind));
+            }
+        }
+        paths.sort();
+        paths.dedup();
+
+        if !paths.is_empty() {
+            self.blob_storage
+                .with("worker_file_system", "delete")
+                .delete_many(namespace, &paths)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// A file or directory in a worker's file system, as seen through the worker inspection API
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerFileEntry {
+    pub name: String,
+    pub is_directory: bool,
+    pub size: u64,
+    pub read_only: bool,
+}
+
+/// Lists a directory of the worker's file system. The path is relative to the worker's root,
+/// and cannot point outside of it.
+pub async fn list_directory(root: &Path, path: &str) -> Result<Vec<WorkerFileEntry>, GolemError> {
+    let dir = resolve_path(root, path).await?;
+    let mut entries = tokio::fs::read_dir(&dir)
+        .await
+        .map_err(|err| GolemError::invalid_request(format!("Failed to list {path}: {err}")))?;
+
+    let mut result = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|err| GolemError::invalid_request(format!("Failed to list {path}: {err}")))?
+    {
+        let metadata = entry.metadata().await.map_err(|err| {
+            GolemError::runtime(format!(
+                "Failed to get the metadata of {:?}: {err}",
+                entry.path()
+            ))
+        })?;
+        result.push(WorkerFileEntry {
+            name: entry.file_name().to_string_lossy().to_string(),
+            is_directory: metadata.is_dir(),
+            size: if metadata.is_dir() { 0 } else { metadata.len() },
+            read_only: metadata.permissions().readonly(),
+        });
+    }
+
+    result.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(result)
+}
+
+/// Opens a file of the worker's file system for reading. The path is relative to the worker's
+/// root, and cannot point outside of it.
+pub async fn open_file(root: &Path, path: &str) -> Result<tokio::fs::File, GolemError> {
+    let file = resolve_path(root, path).await?;
+    if file.is_dir() {
+        return Err(GolemError::invalid_request(format!(
+            "{path} is a directory"
+        )));
+    }
+
+    tokio::fs::File::open(&file)
+        .await
+        .map_err(|err| GolemError::invalid_request(format!("Failed to open {path}: {err}")))
+}
+
+async fn resolve_path(root: &Path, path: &str) -> Result<PathBuf, GolemError> {
+    let relative = Path::new(path.trim_start_matches('/'));
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(GolemError::invalid_request(format!("Invalid path {path}")));
+    }
+
+    let root = tokio::fs::canonicalize(root)
+        .await
+        .map_err(|err| GolemError::runtime(format!("Failed to resolve {root:?}: {err}")))?;
+    let resolved = tokio::fs::canonicalize(root.join(relative))
+        .await
+        .map_err(|_| GolemError::invalid_request(format!("{path} does not exist")))?;
+
+    // Symbolic links created by the worker could otherwise point outside of its root
+    if resolved.starts_with(&root) {
+        Ok(resolved)
+    } else {
+        Err(GolemError::invalid_request(format!("Invalid path {path}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    use golem_common::model::oplog::OplogIndex;
+    use golem_common::model::{AccountId, ComponentId, OwnedWorkerId, WorkerId};
+
+    use crate::services::worker_file_system::{
+        list_directory, open_file, DefaultWorkerFileSystemService, WorkerFileEntry,
+        WorkerFileSystemService,
+    };
+    use crate::storage::blob::memory::InMemoryBlobStorage;
+
+    fn owned_worker_id() -> OwnedWorkerId {
+        OwnedWorkerId::new(
+            &AccountId {
+                value: "account1".to_string(),
+            },
+            &WorkerId {
+                component_id: ComponentId(Uuid::new_v4()),
+                worker_name: "worker1".to_string(),
+            },
+        )
+    }
+
+    fn service() -> DefaultWorkerFileSystemService {
+        DefaultWorkerFileSystemService::new(Arc::new(InMemoryBlobStorage::new()))
+    }
+
+    async fn write(root: &TempDir, path: &str, content: &str) {
+        let path = root.path().join(path);
+        tokio::fs::create_dir_all(path.parent().unwrap())
+            .await
+            .unwrap();
+        tokio::fs::write(path, content).await.unwrap();
+    }
+
+    async fn read(root: &TempDir, path: &str) -> Option<String> {
+        tokio::fs::read_to_string(root.path().join(path)).await.ok()
+    }
+
+    #[tokio::test]
+    async fn sync_and_restore() {
+        let service = service();
+        let owned_worker_id = owned_worker_id();
+
+        let source = TempDir::new().unwrap();
+        write(&source, "a.txt", "hello").await;
+        write(&source, "dir/b.txt", "world").await;
+        write(&source, "dir/copy-of-a.txt", "hello").await;
+        tokio::fs::create_dir_all(source.path().join("empty"))
+            .await
+            .unwrap();
+
+        service
+            .sync(&owned_worker_id, OplogIndex::from_u64(5), source.path())
+            .await
+            .unwrap();
+
+        let target = TempDir::new().unwrap();
+        let restored = service
+            .restore(&owned_worker_id, OplogIndex::from_u64(5), target.path())
+            .await
+            .unwrap();
+
+        assert!(restored);
+        assert_eq!(read(&target, "a.txt").await, Some("hello".to_string()));
+        assert_eq!(read(&target, "dir/b.txt").await, Some("world".to_string()));
+        assert_eq!(
+            read(&target, "dir/copy-of-a.txt").await,
+            Some("hello".to_string())
+        );
+        assert!(target.path().join("empty").is_dir());
+    }
+
+    #[tokio::test]
+    async fn restore_only_at_the_synced_index() {
+        let service = service();
+        let owned_worker_id = owned_worker_id();
+
+        let source = TempDir::new().unwrap();
+        write(&source, "a.txt", "hello").await;
+        service
+            .sync(&owned_worker_id, OplogIndex::from_u64(5), source.path())
+            .await
+            .unwrap();
+
+        let target = TempDir::new().unwrap();
+        let restored = service
+            .restore(&owned_worker_id, OplogIndex::from_u64(7), target.path())
+            .await
+            .unwrap();
+
+        assert!(!restored);
+        assert_eq!(read(&target, "a.txt").await, None);
+        assert_eq!(
+            service.synced_at(&owned_worker_id).await.unwrap(),
+            Some(OplogIndex::from_u64(5))
+        );
+    }
+
+    #[tokio::test]
+    async fn sync_replaces_previous_files() {
+        let service = service();
+        let owned_worker_id = owned_worker_id();
+
+        let source = TempDir::new().unwrap();
+        write(&source, "a.txt", "hello").await;
+        write(&source, "b.txt", "world").await;
+        service
+            .sync(&owned_worker_id, OplogIndex::from_u64(5), source.path())
+            .await
+            .unwrap();
+
+        tokio::fs::remove_file(source.path().join("b.txt"))
+            .await
+            .unwrap();
+        write(&source, "a.txt", "changed").await;
+        service
+            .sync(&owned_worker_id, OplogIndex::from_u64(9), source.path())
+            .await
+            .unwrap();
+
+        let target = TempDir::new().unwrap();
+        service
+            .restore(&owned_worker_id, OplogIndex::from_u64(9), target.path())
+            .await
+            .unwrap();
+
+        assert_eq!(read(&target, "a.txt").await, Some("changed".to_string()));
+        assert_eq!(read(&target, "b.txt").await, None);
+    }
+
+    #[tokio::test]
+    async fn restore_previous_snapshot() {
+        let service = service();
+        let owned_worker_id = owned_worker_id();
+
+        let source = TempDir::new().unwrap();
+        write(&source, "a.txt", "hello").await;
+        service
+            .sync(&owned_worker_id, OplogIndex::from_u64(5), source.path())
+            .await
+            .unwrap();
+        write(&source, "a.txt", "changed").await;
+        service
+            .sync(&owned_worker_id, OplogIndex::from_u64(9), source.path())
+            .await
+            .unwrap();
+        write(&source, "a.txt", "changed again").await;
+        service
+            .sync(&owned_worker_id, OplogIndex::from_u64(12), source.path())
+            .await
+            .unwrap();
+
+        // The snapshot entry of the last sync may not have been committed
+        let target = TempDir::new().unwrap();
+        let restored_previous = service
+            .restore(&owned_worker_id, OplogIndex::from_u64(9), target.path())
+            .await
+            .unwrap();
+        let restored_dropped = service
+            .restore(
+                &owned_worker_id,
+                OplogIndex::from_u64(5),
+                TempDir::new().unwrap().path(),
+            )
+            .await
+            .unwrap();
+
+        assert!(restored_previous);
+        assert_eq!(read(&target, "a.txt").await, Some("changed".to_string()));
+        assert!(!restored_dropped);
+    }
+
+    #[tokio::test]
+    async fn copy_synced() {
+        let service = service();
+        let source_worker_id = owned_worker_id();
+        let target_worker_id = owned_worker_id();
+
+        let source = TempDir::new().unwrap();
+        write(&source, "dir/a.txt", "hello").await;
+        service
+            .sync(&source_worker_id, OplogIndex::from_u64(5), source.path())
+            .await
+            .unwrap();
+
+        let copied = service
+            .copy_synced(
+                &source_worker_id,
+                &target_worker_id,
+                OplogIndex::from_u64(5),
+            )
+            .await
+            .unwrap();
+        let not_synced = service
+            .copy_synced(
+                &source_worker_id,
+                &target_worker_id,
+                OplogIndex::from_u64(7),
+            )
+            .await
+            .unwrap();
+        service.delete(&source_worker_id).await.unwrap();
+
+        let target = TempDir::new().unwrap();
+        let restored = service
+            .restore(&target_worker_id, OplogIndex::from_u64(5), target.path())
+            .await
+            .unwrap();
+
+        assert!(copied);
+        assert!(!not_synced);
+        assert!(restored);
+        assert_eq!(read(&target, "dir/a.txt").await, Some("hello".to_string()));
+    }
+
+    #[tokio::test]
+    async fn concurrent_syncs_keep_referenced_content() {
+        let service = service();
+        let owned_worker_id = owned_worker_id();
+
+        let snapshot = TempDir::new().unwrap();
+        let latest = TempDir::new().unwrap();
+        for i in 0..10u64 {
+            write(&snapshot, "a.txt", &format!("snapshot {i}")).await;
+            write(&latest, "a.txt", &format!("latest {i}")).await;
+            let (synced, synced_latest) = tokio::join!(
+                service.sync(
+                    &owned_worker_id,
+                    OplogIndex::from_u64(i * 2 + 1),
+                    snapshot.path()
+                ),
+                service.sync_latest(
+                    &owned_worker_id,
+                    OplogIndex::from_u64(i * 2 + 2),
+                    latest.path()
+                )
+            );
+            synced.unwrap();
+            synced_latest.unwrap();
+        }
+
+        let target = TempDir::new().unwrap();
+        service
+            .restore(&owned_worker_id, OplogIndex::from_u64(19), target.path())
+            .await
+            .unwrap();
+        let content = service
+            .read_synced_file(&owned_worker_id, "a.txt")
+            .await
+            .unwrap();
+
+        assert_eq!(read(&target, "a.txt").await, Some("snapshot 9".to_string()));
+        assert_eq!(content, Some(b"latest 9".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn sync_latest_skips_unchanged_worker() {
+        let service = service();
+        let owned_worker_id = owned_worker_id();
+
+        let source = TempDir::new().unwrap();
+        write(&source, "a.txt", "hello").await;
+        service
+            .sync_latest(&owned_worker_id, OplogIndex::from_u64(5), source.path())
+            .await
+            .unwrap();
+        write(&source, "a.txt", "changed").await;
+        service
+            .sync_latest(&owned_worker_id, OplogIndex::from_u64(5), source.path())
+            .await
+            .unwrap();
+
+        let content = service
+            .read_synced_file(&owned_worker_id, "a.txt")
+            .await
+            .unwrap();
+        assert_eq!(content, Some(b"hello".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn delete() {
+        let service = service();
+        let owned_worker_id = owned_worker_id();
+
+        let source = TempDir::new().unwrap();
+        write(&source, "a.txt", "hello").await;
+        service
+            .sync(&owned_worker_id, OplogIndex::from_u64(5), source.path())
+            .await
+            .unwrap();
+        service.delete(&owned_worker_id).await.unwrap();
+
+        assert_eq!(service.synced_at(&owned_worker_id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn inspect_synced_file_system() {
+        let service = service();
+        let owned_worker_id = owned_worker_id();
+
+        assert_eq!(
+            service
+                .list_synced_directory(&owned_worker_id, "/")
+                .await
+                .unwrap(),
+            None
+        );
+
+        let source = TempDir::new().unwrap();
+        write(&source, "a.txt", "hello").await;
+        service
+            .sync(&owned_worker_id, OplogIndex::from_u64(5), source.path())
+            .await
+            .unwrap();
+        write(&source, "dir/b.txt", "world").await;
+        service
+            .sync_latest(&owned_worker_id, OplogIndex::from_u64(9), source.path())
+            .await
+            .unwrap();
+
+        let root = service
+            .list_synced_directory(&owned_worker_id, "/")
+            .await
+            .unwrap()
+            .unwrap();
+        let names: Vec<&str> = root.iter().map(|entry| entry.name.as_str()).collect();
+        let content = service
+            .read_synced_file(&owned_worker_id, "/dir/b.txt")
+            .await
+            .unwrap();
+
+        assert_eq!(names, vec!["a.txt", "dir"]);
+        assert_eq!(content, Some(b"world".to_vec()));
+        assert!(service
+            .read_synced_file(&owned_worker_id, "dir")
+            .await
+            .is_err());
+        assert!(service
+            .list_synced_directory(&owned_worker_id, "../dir")
+            .await
+            .is_err());
+
+        // The snapshot manifest is still restored, even though the latest one is newer
+        let target = TempDir::new().unwrap();
+        service
+            .restore(&owned_worker_id, OplogIndex::from_u64(5), target.path())
+            .await
+            .unwrap();
+        assert_eq!(read(&target, "a.txt").await, Some("hello".to_string()));
+        assert_eq!(read(&target, "dir/b.txt").await, None);
+    }
+
+    #[tokio::test]
+    async fn list_worker_directory() {
+        let root = TempDir::new().unwrap();
+        write(&root, "a.txt", "hello").await;
+        write(&root, "dir/b.txt", "world").await;
+
+        let entries = list_directory(root.path(), "/").await.unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                WorkerFileEntry {
+                    name: "a.txt".to_string(),
+                    is_directory: false,
+                    size: 5,
+                    read_only: false,
+                },
+                WorkerFileEntry {
+                    name: "dir".to_string(),
+                    is_directory: true,
+                    size: 0,
+                    read_only: false,
+                }
+            ]
+        );
+
+        let entries = list_directory(root.path(), "dir").await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "b.txt");
+    }
+
+    #[tokio::test]
+    async fn paths_outside_of_the_root_are_rejected() {
+        let parent = TempDir::new().unwrap();
+        write(&parent, "secret.txt", "secret").await;
+        write(&parent, "root/a.txt", "hello").await;
+        let root = parent.path().join("root");
+
+        assert!(open_file(&root, "a.txt").await.is_ok());
+        assert!(open_file(&root, "../secret.txt").await.is_err());
+        assert!(list_directory(&root, "..").await.is_err());
+        assert!(open_file(&root, "missing.txt").await.is_err());
+    }
+}
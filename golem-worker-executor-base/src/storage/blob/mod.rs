@@ -25,6 +25,9 @@ use golem_common::serialization::{deserialize, serialize};
 pub mod fs;
 pub mod memory;
 pub mod s3;
+pub mod sqlite;
+#[cfg(test)]
+mod tests;
 
 #[async_trait]
 pub trait BlobStorage: Debug {
@@ -336,6 +339,10 @@ pub enum BlobStorageNamespace {
         component_id: ComponentId,
         level: usize,
     },
+    WorkerFileSystem {
+        account_id: AccountId,
+        worker_id: WorkerId,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
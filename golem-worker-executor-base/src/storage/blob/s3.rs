@@ -65,6 +65,7 @@ impl S3BlobStorage {
             BlobStorageNamespace::CompressedOplog { level, .. } => {
                 &self.config.compressed_oplog_buckets[*level]
             }
+            BlobStorageNamespace::WorkerFileSystem { .. } => &self.config.worker_file_system_bucket,
         }
     }
 
@@ -86,6 +87,10 @@ impl S3BlobStorage {
             BlobStorageNamespace::OplogPayload {
                 account_id,
                 worker_id,
+            }
+            | BlobStorageNamespace::WorkerFileSystem {
+                account_id,
+                worker_id,
             } => {
                 let account_id_string = account_id.to_string();
                 let worker_id_string = worker_id.to_string();
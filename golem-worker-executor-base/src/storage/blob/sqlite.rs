@@ -0,0 +1,305 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use golem_common::model::Timestamp;
+
+use crate::storage::blob::{BlobMetadata, BlobStorage, BlobStorageNamespace, ExistsResult};
+use crate::storage::sqlite::SqlitePool;
+
+/// Blob storage keeping the blobs in the same SQLite database as the other storages, so a
+/// single file holds all the persistent state of the executor
+#[derive(Debug)]
+pub struct SqliteBlobStorage {
+    sqlite: SqlitePool,
+}
+
+impl SqliteBlobStorage {
+    pub fn new(sqlite: SqlitePool) -> Self {
+        Self { sqlite }
+    }
+
+    fn namespace(namespace: &BlobStorageNamespace) -> String {
+        match namespace {
+            BlobStorageNamespace::CompilationCache => "compilation_cache".to_string(),
+            BlobStorageNamespace::CustomStorage(account_id) => {
+                format!("custom_data/{account_id}")
+            }
+            BlobStorageNamespace::OplogPayload {
+                account_id,
+                worker_id,
+            } => format!("oplog_payload/{account_id}/{worker_id}"),
+            BlobStorageNamespace::CompressedOplog {
+                account_id,
+                component_id,
+                level,
+            } => format!("compressed_oplog/{account_id}/{component_id}/{level}"),
+            BlobStorageNamespace::WorkerFileSystem {
+                account_id,
+                worker_id,
+            } => format!("worker_file_system/{account_id}/{worker_id}"),
+        }
+    }
+
+    fn dir_and_name(path: &Path) -> (String, String) {
+        let dir = path
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let name = path
+            .file_name()
+            .expect("Path must have a file name")
+            .to_string_lossy()
+            .to_string();
+        (dir, name)
+    }
+}
+
+#[async_trait]
+impl BlobStorage for SqliteBlobStorage {
+    async fn get_raw(
+        &self,
+        _target_label: &'static str,
+        _op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<Option<Bytes>, String> {
+        let (dir, name) = Self::dir_and_name(path);
+        sqlx::query_as::<_, (Vec<u8>,)>(
+            "SELECT value FROM blob_storage WHERE namespace = ? AND parent = ? AND name = ?",
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(dir)
+        .bind(name)
+        .fetch_optional(self.sqlite.pool())
+        .await
+        .map(|row| row.map(|(value,)| Bytes::from(value)))
+        .map_err(|err| err.to_string())
+    }
+
+    async fn get_metadata(
+        &self,
+        _target_label: &'static str,
+        _op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<Option<BlobMetadata>, String> {
+        let (dir, name) = Self::dir_and_name(path);
+        sqlx::query_as::<_, (i64, i64)>(
+            r#"
+              SELECT size, last_modified_at FROM blob_storage
+              WHERE namespace = ? AND parent = ? AND name = ?
+            "#,
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(dir)
+        .bind(name)
+        .fetch_optional(self.sqlite.pool())
+        .await
+        .map(|row| {
+            row.map(|(size, last_modified_at)| BlobMetadata {
+                last_modified_at: Timestamp::from(last_modified_at as u64),
+                size: size as u64,
+            })
+        })
+        .map_err(|err| err.to_string())
+    }
+
+    async fn put_raw(
+        &self,
+        _target_label: &'static str,
+        _op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+        data: &[u8],
+    ) -> Result<(), String> {
+        let namespace = Self::namespace(&namespace);
+        let (dir, name) = Self::dir_and_name(path);
+        let mut tx = self
+            .sqlite
+            .pool()
+            .begin()
+            .await
+            .map_err(|err| err.to_string())?;
+        sqlx::query(
+            r#"
+              INSERT INTO blob_storage (namespace, parent, name, value, size, last_modified_at)
+              VALUES (?, ?, ?, ?, ?, ?)
+              ON CONFLICT (namespace, parent, name) DO UPDATE
+              SET value = excluded.value, size = excluded.size,
+                  last_modified_at = excluded.last_modified_at
+            "#,
+        )
+        .bind(&namespace)
+        .bind(&dir)
+        .bind(name)
+        .bind(data)
+        .bind(data.len() as i64)
+        .bind(Timestamp::now_utc().to_millis() as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| err.to_string())?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO blob_storage_directories (namespace, path) VALUES (?, ?)",
+        )
+        .bind(&namespace)
+        .bind(&dir)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| err.to_string())?;
+        tx.commit().await.map_err(|err| err.to_string())
+    }
+
+    async fn delete(
+        &self,
+        _target_label: &'static str,
+        _op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<(), String> {
+        let (dir, name) = Self::dir_and_name(path);
+        sqlx::query("DELETE FROM blob_storage WHERE namespace = ? AND parent = ? AND name = ?")
+            .bind(Self::namespace(&namespace))
+            .bind(dir)
+            .bind(name)
+            .execute(self.sqlite.pool())
+            .await
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+
+    async fn create_dir(
+        &self,
+        _target_label: &'static str,
+        _op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<(), String> {
+        sqlx::query(
+            "INSERT OR IGNORE INTO blob_storage_directories (namespace, path) VALUES (?, ?)",
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(path.to_string_lossy().to_string())
+        .execute(self.sqlite.pool())
+        .await
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+    }
+
+    async fn list_dir(
+        &self,
+        _target_label: &'static str,
+        _op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<Vec<PathBuf>, String> {
+        sqlx::query_as::<_, (String,)>(
+            "SELECT name FROM blob_storage WHERE namespace = ? AND parent = ? ORDER BY name",
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(path.to_string_lossy().to_string())
+        .fetch_all(self.sqlite.pool())
+        .await
+        .map(|rows| rows.into_iter().map(|(name,)| path.join(name)).collect())
+        .map_err(|err| err.to_string())
+    }
+
+    async fn delete_dir(
+        &self,
+        _target_label: &'static str,
+        _op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<(), String> {
+        let namespace = Self::namespace(&namespace);
+        let dir = path.to_string_lossy().to_string();
+        let mut tx = self
+            .sqlite
+            .pool()
+            .begin()
+            .await
+            .map_err(|err| err.to_string())?;
+        // Deleting the directory recursively, like the file system based implementation
+        sqlx::query(
+            r#"
+              DELETE FROM blob_storage
+              WHERE namespace = ?1 AND (parent = ?2 OR substr(parent, 1, length(?2) + 1) = ?2 || '/')
+            "#,
+        )
+        .bind(&namespace)
+        .bind(&dir)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| err.to_string())?;
+        sqlx::query(
+            r#"
+              DELETE FROM blob_storage_directories
+              WHERE namespace = ?1 AND (path = ?2 OR substr(path, 1, length(?2) + 1) = ?2 || '/')
+            "#,
+        )
+        .bind(&namespace)
+        .bind(&dir)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| err.to_string())?;
+        tx.commit().await.map_err(|err| err.to_string())
+    }
+
+    async fn exists(
+        &self,
+        _target_label: &'static str,
+        _op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<ExistsResult, String> {
+        let namespace = Self::namespace(&namespace);
+        let (is_directory,) = sqlx::query_as::<_, (bool,)>(
+            "SELECT EXISTS(SELECT 1 FROM blob_storage_directories WHERE namespace = ? AND path = ?)",
+        )
+        .bind(&namespace)
+        .bind(path.to_string_lossy().to_string())
+        .fetch_one(self.sqlite.pool())
+        .await
+        .map_err(|err| err.to_string())?;
+
+        if is_directory {
+            Ok(ExistsResult::Directory)
+        } else {
+            let (dir, name) = Self::dir_and_name(path);
+            let (is_file,) = sqlx::query_as::<_, (bool,)>(
+                r#"
+                  SELECT EXISTS(
+                    SELECT 1 FROM blob_storage WHERE namespace = ? AND parent = ? AND name = ?
+                  )
+                "#,
+            )
+            .bind(&namespace)
+            .bind(dir)
+            .bind(name)
+            .fetch_one(self.sqlite.pool())
+            .await
+            .map_err(|err| err.to_string())?;
+
+            if is_file {
+                Ok(ExistsResult::File)
+            } else {
+                Ok(ExistsResult::DoesNotExist)
+            }
+        }
+    }
+}
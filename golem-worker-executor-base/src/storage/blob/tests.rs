@@ -0,0 +1,137 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::{Path, PathBuf};
+
+use assert2::check;
+use bytes::Bytes;
+
+use golem_common::model::AccountId;
+
+use crate::storage::blob::{BlobStorage, BlobStorageNamespace, ExistsResult};
+
+fn namespace() -> BlobStorageNamespace {
+    BlobStorageNamespace::CustomStorage(AccountId {
+        value: "test-account".to_string(),
+    })
+}
+
+async fn put_get_delete(storage: &(dyn BlobStorage + Send + Sync)) {
+    let path = Path::new("dir/file1");
+
+    let before = storage.get_raw("test", "get", namespace(), path).await;
+    storage
+        .put_raw("test", "put", namespace(), path, b"hello")
+        .await
+        .unwrap();
+    let after_put = storage.get_raw("test", "get", namespace(), path).await;
+    let metadata = storage
+        .get_metadata("test", "get_metadata", namespace(), path)
+        .await
+        .unwrap();
+    let slice = storage
+        .get_raw_slice("test", "get", namespace(), path, 1, 3)
+        .await;
+    storage
+        .delete("test", "delete", namespace(), path)
+        .await
+        .unwrap();
+    let after_delete = storage.get_raw("test", "get", namespace(), path).await;
+
+    check!(before == Ok(None));
+    check!(after_put == Ok(Some(Bytes::from_static(b"hello"))));
+    check!(metadata.map(|metadata| metadata.size) == Some(5));
+    check!(slice == Ok(Some(Bytes::from_static(b"el"))));
+    check!(after_delete == Ok(None));
+}
+
+async fn directories(storage: &(dyn BlobStorage + Send + Sync)) {
+    storage
+        .create_dir("test", "create_dir", namespace(), Path::new("dir"))
+        .await
+        .unwrap();
+    storage
+        .put_raw("test", "put", namespace(), Path::new("dir/a"), b"a")
+        .await
+        .unwrap();
+    storage
+        .put_raw("test", "put", namespace(), Path::new("dir/b"), b"b")
+        .await
+        .unwrap();
+
+    let dir_exists = storage
+        .exists("test", "exists", namespace(), Path::new("dir"))
+        .await;
+    let file_exists = storage
+        .exists("test", "exists", namespace(), Path::new("dir/a"))
+        .await;
+    let mut entries = storage
+        .list_dir("test", "list_dir", namespace(), Path::new("dir"))
+        .await
+        .unwrap();
+    entries.sort();
+    storage
+        .delete_dir("test", "delete_dir", namespace(), Path::new("dir"))
+        .await
+        .unwrap();
+    let after_delete = storage
+        .exists("test", "exists", namespace(), Path::new("dir/a"))
+        .await;
+
+    check!(dir_exists == Ok(ExistsResult::Directory));
+    check!(file_exists == Ok(ExistsResult::File));
+    check!(entries == vec![PathBuf::from("dir/a"), PathBuf::from("dir/b")]);
+    check!(after_delete == Ok(ExistsResult::DoesNotExist));
+}
+
+macro_rules! blob_storage_tests {
+    ($name:ident, $create:path) => {
+        mod $name {
+            #[tokio::test]
+            async fn put_get_delete() {
+                let (_guard, storage) = $create().await;
+                super::put_get_delete(&*storage).await;
+            }
+
+            #[tokio::test]
+            async fn directories() {
+                let (_guard, storage) = $create().await;
+                super::directories(&*storage).await;
+            }
+        }
+    };
+}
+
+mod storages {
+    use std::sync::Arc;
+
+    use tempfile::TempDir;
+
+    use crate::storage::blob::memory::InMemoryBlobStorage;
+    use crate::storage::blob::sqlite::SqliteBlobStorage;
+    use crate::storage::blob::BlobStorage;
+    use crate::storage::sqlite::test_utils::open_sqlite;
+
+    pub async fn in_memory() -> ((), Arc<dyn BlobStorage + Send + Sync>) {
+        ((), Arc::new(InMemoryBlobStorage::new()))
+    }
+
+    pub async fn sqlite() -> (TempDir, Arc<dyn BlobStorage + Send + Sync>) {
+        let (dir, pool) = open_sqlite().await;
+        (dir, Arc::new(SqliteBlobStorage::new(pool)))
+    }
+}
+
+blob_storage_tests!(in_memory, super::storages::in_memory);
+blob_storage_tests!(sqlite, super::storages::sqlite);
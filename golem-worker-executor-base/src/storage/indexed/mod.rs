@@ -22,7 +22,11 @@ use bytes::Bytes;
 use golem_common::serialization::{deserialize, serialize};
 
 pub mod memory;
+pub mod postgres;
 pub mod redis;
+pub mod sqlite;
+#[cfg(test)]
+mod tests;
 
 pub type ScanCursor = u64;
 
@@ -0,0 +1,299 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::storage::indexed::{IndexedStorage, IndexedStorageNamespace, ScanCursor};
+use crate::storage::sqlite::SqlitePool;
+
+#[derive(Debug)]
+pub struct SqliteIndexedStorage {
+    sqlite: SqlitePool,
+}
+
+impl SqliteIndexedStorage {
+    pub fn new(sqlite: SqlitePool) -> Self {
+        Self { sqlite }
+    }
+
+    fn namespace(namespace: &IndexedStorageNamespace) -> String {
+        match namespace {
+            IndexedStorageNamespace::OpLog => "worker:oplog".to_string(),
+            IndexedStorageNamespace::CompressedOpLog { level } => {
+                format!("worker:c{level}-oplog")
+            }
+        }
+    }
+
+    fn to_row(row: (i64, Vec<u8>)) -> (u64, Bytes) {
+        (row.0 as u64, Bytes::from(row.1))
+    }
+}
+
+#[async_trait]
+impl IndexedStorage for SqliteIndexedStorage {
+    async fn number_of_replicas(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+    ) -> Result<u8, String> {
+        // A local database file has no replicas, committed writes are already durable
+        Ok(1)
+    }
+
+    async fn wait_for_replicas(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _replicas: u8,
+        _timeout: Duration,
+    ) -> Result<u8, String> {
+        Ok(1)
+    }
+
+    async fn exists(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<bool, String> {
+        sqlx::query_as::<_, (bool,)>(
+            "SELECT EXISTS(SELECT 1 FROM index_storage WHERE namespace = ? AND key = ?)",
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .fetch_one(self.sqlite.pool())
+        .await
+        .map(|(exists,)| exists)
+        .map_err(|err| err.to_string())
+    }
+
+    async fn scan(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        pattern: &str,
+        cursor: ScanCursor,
+        count: u64,
+    ) -> Result<(ScanCursor, Vec<String>), String> {
+        let namespace = Self::namespace(&namespace);
+        let keys: Vec<(String,)> = sqlx::query_as(
+            r#"
+              SELECT DISTINCT key FROM index_storage
+              WHERE namespace = ? AND key GLOB ?
+              ORDER BY key
+              LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(&namespace)
+        .bind(pattern)
+        .bind(count as i64)
+        .bind(cursor as i64)
+        .fetch_all(self.sqlite.pool())
+        .await
+        .map_err(|err| err.to_string())?;
+
+        // Following Redis, a zero cursor means the scan is finished
+        let new_cursor = if (keys.len() as u64) < count {
+            0
+        } else {
+            cursor + keys.len() as u64
+        };
+        // Returning the same composite keys as the Redis implementation
+        let keys = keys
+            .into_iter()
+            .map(|(key,)| format!("{namespace}:{key}"))
+            .collect();
+        Ok((new_cursor, keys))
+    }
+
+    async fn append(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        id: u64,
+        value: &[u8],
+    ) -> Result<(), String> {
+        sqlx::query("INSERT INTO index_storage (namespace, key, id, value) VALUES (?, ?, ?, ?)")
+            .bind(Self::namespace(&namespace))
+            .bind(key)
+            .bind(id as i64)
+            .bind(value)
+            .execute(self.sqlite.pool())
+            .await
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+
+    async fn length(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<u64, String> {
+        sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM index_storage WHERE namespace = ? AND key = ?",
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .fetch_one(self.sqlite.pool())
+        .await
+        .map(|(count,)| count as u64)
+        .map_err(|err| err.to_string())
+    }
+
+    async fn delete(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<(), String> {
+        sqlx::query("DELETE FROM index_storage WHERE namespace = ? AND key = ?")
+            .bind(Self::namespace(&namespace))
+            .bind(key)
+            .execute(self.sqlite.pool())
+            .await
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+
+    async fn read(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        start_id: u64,
+        end_id: u64,
+    ) -> Result<Vec<(u64, Bytes)>, String> {
+        sqlx::query_as::<_, (i64, Vec<u8>)>(
+            r#"
+              SELECT id, value FROM index_storage
+              WHERE namespace = ? AND key = ? AND id >= ? AND id <= ?
+              ORDER BY id
+            "#,
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .bind(start_id as i64)
+        .bind(end_id.min(i64::MAX as u64) as i64)
+        .fetch_all(self.sqlite.pool())
+        .await
+        .map(|rows| rows.into_iter().map(Self::to_row).collect())
+        .map_err(|err| err.to_string())
+    }
+
+    async fn first(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<Option<(u64, Bytes)>, String> {
+        sqlx::query_as::<_, (i64, Vec<u8>)>(
+            r#"
+              SELECT id, value FROM index_storage
+              WHERE namespace = ? AND key = ?
+              ORDER BY id ASC
+              LIMIT 1
+            "#,
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .fetch_optional(self.sqlite.pool())
+        .await
+        .map(|row| row.map(Self::to_row))
+        .map_err(|err| err.to_string())
+    }
+
+    async fn last(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<Option<(u64, Bytes)>, String> {
+        sqlx::query_as::<_, (i64, Vec<u8>)>(
+            r#"
+              SELECT id, value FROM index_storage
+              WHERE namespace = ? AND key = ?
+              ORDER BY id DESC
+              LIMIT 1
+            "#,
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .fetch_optional(self.sqlite.pool())
+        .await
+        .map(|row| row.map(Self::to_row))
+        .map_err(|err| err.to_string())
+    }
+
+    async fn closest(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        id: u64,
+    ) -> Result<Option<(u64, Bytes)>, String> {
+        sqlx::query_as::<_, (i64, Vec<u8>)>(
+            r#"
+              SELECT id, value FROM index_storage
+              WHERE namespace = ? AND key = ? AND id >= ?
+              ORDER BY id ASC
+              LIMIT 1
+            "#,
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .bind(id as i64)
+        .fetch_optional(self.sqlite.pool())
+        .await
+        .map(|row| row.map(Self::to_row))
+        .map_err(|err| err.to_string())
+    }
+
+    async fn drop_prefix(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        last_dropped_id: u64,
+    ) -> Result<(), String> {
+        sqlx::query("DELETE FROM index_storage WHERE namespace = ? AND key = ? AND id <= ?")
+            .bind(Self::namespace(&namespace))
+            .bind(key)
+            .bind(last_dropped_id as i64)
+            .execute(self.sqlite.pool())
+            .await
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use assert2::check;
+use bytes::Bytes;
+
+use crate::storage::indexed::{IndexedStorage, IndexedStorageNamespace};
+
+async fn append(
+    storage: &(dyn IndexedStorage + Send + Sync),
+    namespace: IndexedStorageNamespace,
+    key: &str,
+    ids: impl IntoIterator<Item = u64>,
+) {
+    for id in ids {
+        storage
+            .append(
+                "test",
+                "append",
+                "entity",
+                namespace.clone(),
+                key,
+                id,
+                format!("value{id}").as_bytes(),
+            )
+            .await
+            .unwrap();
+    }
+}
+
+fn entry(id: u64) -> (u64, Bytes) {
+    (id, Bytes::from(format!("value{id}")))
+}
+
+async fn append_and_read(storage: &(dyn IndexedStorage + Send + Sync)) {
+    let ns = IndexedStorageNamespace::OpLog;
+
+    let exists1 = storage.exists("test", "exists", ns.clone(), "key1").await;
+    append(storage, ns.clone(), "key1", [1, 2, 3, 5, 8]).await;
+    let exists2 = storage.exists("test", "exists", ns.clone(), "key1").await;
+    let length = storage.length("test", "length", ns.clone(), "key1").await;
+    let range = storage
+        .read("test", "read", "entity", ns.clone(), "key1", 2, 5)
+        .await;
+    let empty_range = storage
+        .read("test", "read", "entity", ns.clone(), "key1", 9, 20)
+        .await;
+
+    check!(exists1 == Ok(false));
+    check!(exists2 == Ok(true));
+    check!(length == Ok(5));
+    check!(range == Ok(vec![entry(2), entry(3), entry(5)]));
+    check!(empty_range == Ok(vec![]));
+}
+
+async fn first_last_closest(storage: &(dyn IndexedStorage + Send + Sync)) {
+    let ns = IndexedStorageNamespace::OpLog;
+
+    let first1 = storage
+        .first("test", "first", "entity", ns.clone(), "key1")
+        .await;
+    append(storage, ns.clone(), "key1", [2, 4, 6, 8]).await;
+    let first2 = storage
+        .first("test", "first", "entity", ns.clone(), "key1")
+        .await;
+    let last = storage
+        .last("test", "last", "entity", ns.clone(), "key1")
+        .await;
+    let closest1 = storage
+        .closest("test", "closest", "entity", ns.clone(), "key1", 5)
+        .await;
+    let closest2 = storage
+        .closest("test", "closest", "entity", ns.clone(), "key1", 6)
+        .await;
+    let closest3 = storage
+        .closest("test", "closest", "entity", ns.clone(), "key1", 9)
+        .await;
+
+    check!(first1 == Ok(None));
+    check!(first2 == Ok(Some(entry(2))));
+    check!(last == Ok(Some(entry(8))));
+    check!(closest1 == Ok(Some(entry(6))));
+    check!(closest2 == Ok(Some(entry(6))));
+    check!(closest3 == Ok(None));
+}
+
+async fn drop_prefix_and_delete(storage: &(dyn IndexedStorage + Send + Sync)) {
+    let ns = IndexedStorageNamespace::CompressedOpLog { level: 1 };
+
+    append(storage, ns.clone(), "key1", 1..=10).await;
+    storage
+        .drop_prefix("test", "drop_prefix", ns.clone(), "key1", 4)
+        .await
+        .unwrap();
+    let length1 = storage.length("test", "length", ns.clone(), "key1").await;
+    let first = storage
+        .first("test", "first", "entity", ns.clone(), "key1")
+        .await;
+    storage
+        .delete("test", "delete", ns.clone(), "key1")
+        .await
+        .unwrap();
+    let length2 = storage.length("test", "length", ns.clone(), "key1").await;
+    let exists = storage.exists("test", "exists", ns.clone(), "key1").await;
+
+    check!(length1 == Ok(6));
+    check!(first == Ok(Some(entry(5))));
+    check!(length2 == Ok(0));
+    check!(exists == Ok(false));
+}
+
+async fn namespaces_are_isolated(storage: &(dyn IndexedStorage + Send + Sync)) {
+    let ns1 = IndexedStorageNamespace::OpLog;
+    let ns2 = IndexedStorageNamespace::CompressedOpLog { level: 1 };
+
+    append(storage, ns1.clone(), "key1", 1..=3).await;
+    let exists = storage.exists("test", "exists", ns2.clone(), "key1").await;
+    let length = storage.length("test", "length", ns2.clone(), "key1").await;
+
+    check!(exists == Ok(false));
+    check!(length == Ok(0));
+}
+
+async fn scan(storage: &(dyn IndexedStorage + Send + Sync)) {
+    let ns = IndexedStorageNamespace::OpLog;
+
+    append(storage, ns.clone(), "component1:worker1", [1]).await;
+    append(storage, ns.clone(), "component1:worker2", [1]).await;
+    append(storage, ns.clone(), "component2:worker1", [1]).await;
+
+    let (_, mut keys) = storage
+        .scan("test", "scan", ns.clone(), "component1:*", 0, 100)
+        .await
+        .unwrap();
+    keys.sort();
+
+    check!(keys.len() == 2);
+    check!(keys[0].ends_with("component1:worker1"));
+    check!(keys[1].ends_with("component1:worker2"));
+}
+
+macro_rules! indexed_storage_tests {
+    ($name:ident, $create:path) => {
+        mod $name {
+            #[tokio::test]
+            async fn append_and_read() {
+                let (_guard, storage) = $create().await;
+                super::append_and_read(&*storage).await;
+            }
+
+            #[tokio::test]
+            async fn first_last_closest() {
+                let (_guard, storage) = $create().await;
+                super::first_last_closest(&*storage).await;
+            }
+
+            #[tokio::test]
+            async fn drop_prefix_and_delete() {
+                let (_guard, storage) = $create().await;
+                super::drop_prefix_and_delete(&*storage).await;
+            }
+
+            #[tokio::test]
+            async fn namespaces_are_isolated() {
+                let (_guard, storage) = $create().await;
+                super::namespaces_are_isolated(&*storage).await;
+            }
+
+            #[tokio::test]
+            async fn scan() {
+                let (_guard, storage) = $create().await;
+                super::scan(&*storage).await;
+            }
+        }
+    };
+}
+
+mod storages {
+    use std::sync::Arc;
+
+    use golem_test_framework::components::rdb::docker_postgres::DockerPostgresRdb;
+    use tempfile::TempDir;
+
+    use crate::storage::indexed::memory::InMemoryIndexedStorage;
+    use crate::storage::indexed::postgres::PostgresIndexedStorage;
+    use crate::storage::indexed::sqlite::SqliteIndexedStorage;
+    use crate::storage::indexed::IndexedStorage;
+    use crate::storage::postgres::test_utils::start_postgres;
+    use crate::storage::sqlite::test_utils::open_sqlite;
+
+    pub async fn in_memory() -> ((), Arc<dyn IndexedStorage + Send + Sync>) {
+        ((), Arc::new(InMemoryIndexedStorage::new()))
+    }
+
+    pub async fn postgres() -> (DockerPostgresRdb, Arc<dyn IndexedStorage + Send + Sync>) {
+        let (rdb, pool) = start_postgres().await;
+        (rdb, Arc::new(PostgresIndexedStorage::new(pool)))
+    }
+
+    pub async fn sqlite() -> (TempDir, Arc<dyn IndexedStorage + Send + Sync>) {
+        let (dir, pool) = open_sqlite().await;
+        (dir, Arc::new(SqliteIndexedStorage::new(pool)))
+    }
+}
+
+indexed_storage_tests!(in_memory, super::storages::in_memory);
+indexed_storage_tests!(postgres, super::storages::postgres);
+indexed_storage_tests!(sqlite, super::storages::sqlite);
@@ -13,7 +13,11 @@
 // limitations under the License.
 
 pub mod memory;
+pub mod postgres;
 pub mod redis;
+pub mod sqlite;
+#[cfg(test)]
+mod tests;
 
 use async_trait::async_trait;
 use bincode::{Decode, Encode};
@@ -0,0 +1,410 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::storage::keyvalue::{KeyValueStorage, KeyValueStorageNamespace};
+use crate::storage::sqlite::SqlitePool;
+
+#[derive(Debug)]
+pub struct SqliteKeyValueStorage {
+    sqlite: SqlitePool,
+}
+
+impl SqliteKeyValueStorage {
+    pub fn new(sqlite: SqlitePool) -> Self {
+        Self { sqlite }
+    }
+
+    fn namespace(namespace: &KeyValueStorageNamespace) -> String {
+        match namespace {
+            KeyValueStorageNamespace::Worker => "worker".to_string(),
+            KeyValueStorageNamespace::Promise => "promise".to_string(),
+            KeyValueStorageNamespace::Schedule => "schedule".to_string(),
+            KeyValueStorageNamespace::UserDefined { account_id, bucket } => {
+                format!("user-defined:{account_id}:{bucket}")
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl KeyValueStorage for SqliteKeyValueStorage {
+    async fn set(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+              INSERT INTO kv_storage (namespace, key, value) VALUES (?, ?, ?)
+              ON CONFLICT (namespace, key) DO UPDATE SET value = excluded.value
+            "#,
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .bind(value)
+        .execute(self.sqlite.pool())
+        .await
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+    }
+
+    async fn set_many(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        pairs: &[(&str, &[u8])],
+    ) -> Result<(), String> {
+        // Later pairs override earlier ones with the same key, like in Redis' MSET
+        let pairs: HashMap<&str, &[u8]> = pairs.iter().cloned().collect();
+        let mut tx = self
+            .sqlite
+            .pool()
+            .begin()
+            .await
+            .map_err(|err| err.to_string())?;
+        for (key, value) in pairs {
+            sqlx::query(
+                r#"
+                  INSERT INTO kv_storage (namespace, key, value) VALUES (?, ?, ?)
+                  ON CONFLICT (namespace, key) DO UPDATE SET value = excluded.value
+                "#,
+            )
+            .bind(Self::namespace(&namespace))
+            .bind(key)
+            .bind(value)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| err.to_string())?;
+        }
+        tx.commit().await.map_err(|err| err.to_string())
+    }
+
+    async fn set_if_not_exists(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<bool, String> {
+        sqlx::query(
+            r#"
+              INSERT INTO kv_storage (namespace, key, value) VALUES (?, ?, ?)
+              ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .bind(value)
+        .execute(self.sqlite.pool())
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .map_err(|err| err.to_string())
+    }
+
+    async fn get(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<Option<Bytes>, String> {
+        sqlx::query_as::<_, (Vec<u8>,)>(
+            "SELECT value FROM kv_storage WHERE namespace = ? AND key = ?",
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .fetch_optional(self.sqlite.pool())
+        .await
+        .map(|row| row.map(|(value,)| Bytes::from(value)))
+        .map_err(|err| err.to_string())
+    }
+
+    async fn get_many(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: Vec<String>,
+    ) -> Result<Vec<Option<Bytes>>, String> {
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys {
+            let value = sqlx::query_as::<_, (Vec<u8>,)>(
+                "SELECT value FROM kv_storage WHERE namespace = ? AND key = ?",
+            )
+            .bind(Self::namespace(&namespace))
+            .bind(key)
+            .fetch_optional(self.sqlite.pool())
+            .await
+            .map_err(|err| err.to_string())?;
+            values.push(value.map(|(value,)| Bytes::from(value)));
+        }
+        Ok(values)
+    }
+
+    async fn del(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<(), String> {
+        sqlx::query("DELETE FROM kv_storage WHERE namespace = ? AND key = ?")
+            .bind(Self::namespace(&namespace))
+            .bind(key)
+            .execute(self.sqlite.pool())
+            .await
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+
+    async fn del_many(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: Vec<String>,
+    ) -> Result<(), String> {
+        let mut tx = self
+            .sqlite
+            .pool()
+            .begin()
+            .await
+            .map_err(|err| err.to_string())?;
+        for key in keys {
+            sqlx::query("DELETE FROM kv_storage WHERE namespace = ? AND key = ?")
+                .bind(Self::namespace(&namespace))
+                .bind(key)
+                .execute(&mut *tx)
+                .await
+                .map_err(|err| err.to_string())?;
+        }
+        tx.commit().await.map_err(|err| err.to_string())
+    }
+
+    async fn exists(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<bool, String> {
+        sqlx::query_as::<_, (bool,)>(
+            "SELECT EXISTS(SELECT 1 FROM kv_storage WHERE namespace = ? AND key = ?)",
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .fetch_one(self.sqlite.pool())
+        .await
+        .map(|(exists,)| exists)
+        .map_err(|err| err.to_string())
+    }
+
+    async fn keys(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<String>, String> {
+        sqlx::query_as::<_, (String,)>("SELECT key FROM kv_storage WHERE namespace = ?")
+            .bind(Self::namespace(&namespace))
+            .fetch_all(self.sqlite.pool())
+            .await
+            .map(|rows| rows.into_iter().map(|(key,)| key).collect())
+            .map_err(|err| err.to_string())
+    }
+
+    async fn add_to_set(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+              INSERT INTO set_storage (namespace, key, value) VALUES (?, ?, ?)
+              ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .bind(value)
+        .execute(self.sqlite.pool())
+        .await
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+    }
+
+    async fn remove_from_set(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        sqlx::query("DELETE FROM set_storage WHERE namespace = ? AND key = ? AND value = ?")
+            .bind(Self::namespace(&namespace))
+            .bind(key)
+            .bind(value)
+            .execute(self.sqlite.pool())
+            .await
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+
+    async fn members_of_set(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<Vec<Bytes>, String> {
+        sqlx::query_as::<_, (Vec<u8>,)>(
+            "SELECT value FROM set_storage WHERE namespace = ? AND key = ?",
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .fetch_all(self.sqlite.pool())
+        .await
+        .map(|rows| {
+            rows.into_iter()
+                .map(|(value,)| Bytes::from(value))
+                .collect()
+        })
+        .map_err(|err| err.to_string())
+    }
+
+    async fn add_to_sorted_set(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        score: f64,
+        value: &[u8],
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+              INSERT INTO sorted_set_storage (namespace, key, value, score) VALUES (?, ?, ?, ?)
+              ON CONFLICT (namespace, key, value) DO UPDATE SET score = excluded.score
+            "#,
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .bind(value)
+        .bind(score)
+        .execute(self.sqlite.pool())
+        .await
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+    }
+
+    async fn remove_from_sorted_set(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        sqlx::query("DELETE FROM sorted_set_storage WHERE namespace = ? AND key = ? AND value = ?")
+            .bind(Self::namespace(&namespace))
+            .bind(key)
+            .bind(value)
+            .execute(self.sqlite.pool())
+            .await
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+
+    async fn get_sorted_set(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<Vec<(f64, Bytes)>, String> {
+        sqlx::query_as::<_, (f64, Vec<u8>)>(
+            r#"
+              SELECT score, value FROM sorted_set_storage
+              WHERE namespace = ? AND key = ?
+              ORDER BY score, value
+            "#,
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .fetch_all(self.sqlite.pool())
+        .await
+        .map(|rows| {
+            rows.into_iter()
+                .map(|(score, value)| (score, Bytes::from(value)))
+                .collect()
+        })
+        .map_err(|err| err.to_string())
+    }
+
+    async fn query_sorted_set(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<Vec<(f64, Bytes)>, String> {
+        sqlx::query_as::<_, (f64, Vec<u8>)>(
+            r#"
+              SELECT score, value FROM sorted_set_storage
+              WHERE namespace = ? AND key = ? AND score >= ? AND score <= ?
+              ORDER BY score, value
+            "#,
+        )
+        .bind(Self::namespace(&namespace))
+        .bind(key)
+        .bind(min)
+        .bind(max)
+        .fetch_all(self.sqlite.pool())
+        .await
+        .map(|rows| {
+            rows.into_iter()
+                .map(|(score, value)| (score, Bytes::from(value)))
+                .collect()
+        })
+        .map_err(|err| err.to_string())
+    }
+}
@@ -0,0 +1,335 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use assert2::check;
+use bytes::Bytes;
+
+use golem_common::model::AccountId;
+
+use crate::storage::keyvalue::{KeyValueStorage, KeyValueStorageNamespace};
+
+fn user_defined(bucket: &str) -> KeyValueStorageNamespace {
+    KeyValueStorageNamespace::UserDefined {
+        account_id: AccountId {
+            value: "test-account".to_string(),
+        },
+        bucket: bucket.to_string(),
+    }
+}
+
+async fn get_set_del(storage: &(dyn KeyValueStorage + Send + Sync)) {
+    let ns = KeyValueStorageNamespace::Worker;
+
+    let before = storage
+        .get("test", "get", "entity", ns.clone(), "key1")
+        .await;
+    storage
+        .set("test", "set", "entity", ns.clone(), "key1", b"value1")
+        .await
+        .unwrap();
+    let after_set = storage
+        .get("test", "get", "entity", ns.clone(), "key1")
+        .await;
+    let exists1 = storage.exists("test", "exists", ns.clone(), "key1").await;
+    storage
+        .set("test", "set", "entity", ns.clone(), "key1", b"value2")
+        .await
+        .unwrap();
+    let after_overwrite = storage
+        .get("test", "get", "entity", ns.clone(), "key1")
+        .await;
+    storage
+        .del("test", "del", ns.clone(), "key1")
+        .await
+        .unwrap();
+    let after_del = storage
+        .get("test", "get", "entity", ns.clone(), "key1")
+        .await;
+    let exists2 = storage.exists("test", "exists", ns.clone(), "key1").await;
+
+    check!(before == Ok(None));
+    check!(after_set == Ok(Some(Bytes::from_static(b"value1"))));
+    check!(exists1 == Ok(true));
+    check!(after_overwrite == Ok(Some(Bytes::from_static(b"value2"))));
+    check!(after_del == Ok(None));
+    check!(exists2 == Ok(false));
+}
+
+async fn set_if_not_exists(storage: &(dyn KeyValueStorage + Send + Sync)) {
+    let ns = KeyValueStorageNamespace::Promise;
+
+    let first = storage
+        .set_if_not_exists("test", "set", "entity", ns.clone(), "key1", b"value1")
+        .await;
+    let second = storage
+        .set_if_not_exists("test", "set", "entity", ns.clone(), "key1", b"value2")
+        .await;
+    let value = storage
+        .get("test", "get", "entity", ns.clone(), "key1")
+        .await;
+
+    check!(first == Ok(true));
+    check!(second == Ok(false));
+    check!(value == Ok(Some(Bytes::from_static(b"value1"))));
+}
+
+async fn many(storage: &(dyn KeyValueStorage + Send + Sync)) {
+    let ns = user_defined("many");
+
+    storage
+        .set_many(
+            "test",
+            "set_many",
+            "entity",
+            ns.clone(),
+            &[
+                ("key1", b"value1".as_slice()),
+                ("key2", b"value2".as_slice()),
+                ("key3", b"value3".as_slice()),
+            ],
+        )
+        .await
+        .unwrap();
+    let values1 = storage
+        .get_many(
+            "test",
+            "get_many",
+            "entity",
+            ns.clone(),
+            vec!["key3".to_string(), "key4".to_string(), "key1".to_string()],
+        )
+        .await;
+    storage
+        .del_many(
+            "test",
+            "del_many",
+            ns.clone(),
+            vec!["key1".to_string(), "key2".to_string()],
+        )
+        .await
+        .unwrap();
+    let values2 = storage
+        .get_many(
+            "test",
+            "get_many",
+            "entity",
+            ns.clone(),
+            vec!["key1".to_string(), "key2".to_string(), "key3".to_string()],
+        )
+        .await;
+
+    check!(
+        values1
+            == Ok(vec![
+                Some(Bytes::from_static(b"value3")),
+                None,
+                Some(Bytes::from_static(b"value1"))
+            ])
+    );
+    check!(values2 == Ok(vec![None, None, Some(Bytes::from_static(b"value3"))]));
+}
+
+async fn namespaces_are_isolated(storage: &(dyn KeyValueStorage + Send + Sync)) {
+    let ns1 = user_defined("bucket1");
+    let ns2 = user_defined("bucket2");
+
+    storage
+        .set("test", "set", "entity", ns1.clone(), "key1", b"value1")
+        .await
+        .unwrap();
+    storage
+        .set("test", "set", "entity", ns1.clone(), "key2", b"value2")
+        .await
+        .unwrap();
+    storage
+        .set("test", "set", "entity", ns2.clone(), "key3", b"value3")
+        .await
+        .unwrap();
+
+    let value = storage
+        .get("test", "get", "entity", ns2.clone(), "key1")
+        .await;
+    let mut keys1 = storage.keys("test", "keys", ns1).await.unwrap();
+    keys1.sort();
+    let keys2 = storage.keys("test", "keys", ns2).await.unwrap();
+
+    check!(value == Ok(None));
+    check!(keys1.len() == 2);
+    check!(keys1[0].ends_with("key1"));
+    check!(keys1[1].ends_with("key2"));
+    check!(keys2.len() == 1);
+    check!(keys2[0].ends_with("key3"));
+}
+
+async fn sets(storage: &(dyn KeyValueStorage + Send + Sync)) {
+    let ns = KeyValueStorageNamespace::Schedule;
+
+    storage
+        .add_to_set("test", "add", "entity", ns.clone(), "set1", b"a")
+        .await
+        .unwrap();
+    storage
+        .add_to_set("test", "add", "entity", ns.clone(), "set1", b"b")
+        .await
+        .unwrap();
+    storage
+        .add_to_set("test", "add", "entity", ns.clone(), "set1", b"a")
+        .await
+        .unwrap();
+    let mut members1 = storage
+        .members_of_set("test", "members", "entity", ns.clone(), "set1")
+        .await
+        .unwrap();
+    members1.sort();
+    storage
+        .remove_from_set("test", "remove", "entity", ns.clone(), "set1", b"a")
+        .await
+        .unwrap();
+    let members2 = storage
+        .members_of_set("test", "members", "entity", ns.clone(), "set1")
+        .await;
+    let members3 = storage
+        .members_of_set("test", "members", "entity", ns.clone(), "set2")
+        .await;
+
+    check!(members1 == vec![Bytes::from_static(b"a"), Bytes::from_static(b"b")]);
+    check!(members2 == Ok(vec![Bytes::from_static(b"b")]));
+    check!(members3 == Ok(vec![]));
+}
+
+async fn sorted_sets(storage: &(dyn KeyValueStorage + Send + Sync)) {
+    let ns = KeyValueStorageNamespace::Schedule;
+
+    storage
+        .add_to_sorted_set("test", "add", "entity", ns.clone(), "zset1", 3.0, b"c")
+        .await
+        .unwrap();
+    storage
+        .add_to_sorted_set("test", "add", "entity", ns.clone(), "zset1", 1.0, b"a")
+        .await
+        .unwrap();
+    storage
+        .add_to_sorted_set("test", "add", "entity", ns.clone(), "zset1", 2.0, b"b")
+        .await
+        .unwrap();
+    let all = storage
+        .get_sorted_set("test", "get", "entity", ns.clone(), "zset1")
+        .await;
+    let range = storage
+        .query_sorted_set("test", "query", "entity", ns.clone(), "zset1", 1.5, 3.0)
+        .await;
+    storage
+        .remove_from_sorted_set("test", "remove", "entity", ns.clone(), "zset1", b"b")
+        .await
+        .unwrap();
+    let after_remove = storage
+        .get_sorted_set("test", "get", "entity", ns.clone(), "zset1")
+        .await;
+
+    check!(
+        all == Ok(vec![
+            (1.0, Bytes::from_static(b"a")),
+            (2.0, Bytes::from_static(b"b")),
+            (3.0, Bytes::from_static(b"c"))
+        ])
+    );
+    check!(
+        range
+            == Ok(vec![
+                (2.0, Bytes::from_static(b"b")),
+                (3.0, Bytes::from_static(b"c"))
+            ])
+    );
+    check!(
+        after_remove
+            == Ok(vec![
+                (1.0, Bytes::from_static(b"a")),
+                (3.0, Bytes::from_static(b"c"))
+            ])
+    );
+}
+
+macro_rules! key_value_storage_tests {
+    ($name:ident, $create:path) => {
+        mod $name {
+            #[tokio::test]
+            async fn get_set_del() {
+                let (_guard, storage) = $create().await;
+                super::get_set_del(&*storage).await;
+            }
+
+            #[tokio::test]
+            async fn set_if_not_exists() {
+                let (_guard, storage) = $create().await;
+                super::set_if_not_exists(&*storage).await;
+            }
+
+            #[tokio::test]
+            async fn many() {
+                let (_guard, storage) = $create().await;
+                super::many(&*storage).await;
+            }
+
+            #[tokio::test]
+            async fn namespaces_are_isolated() {
+                let (_guard, storage) = $create().await;
+                super::namespaces_are_isolated(&*storage).await;
+            }
+
+            #[tokio::test]
+            async fn sets() {
+                let (_guard, storage) = $create().await;
+                super::sets(&*storage).await;
+            }
+
+            #[tokio::test]
+            async fn sorted_sets() {
+                let (_guard, storage) = $create().await;
+                super::sorted_sets(&*storage).await;
+            }
+        }
+    };
+}
+
+mod storages {
+    use std::sync::Arc;
+
+    use golem_test_framework::components::rdb::docker_postgres::DockerPostgresRdb;
+    use tempfile::TempDir;
+
+    use crate::storage::keyvalue::memory::InMemoryKeyValueStorage;
+    use crate::storage::keyvalue::postgres::PostgresKeyValueStorage;
+    use crate::storage::keyvalue::sqlite::SqliteKeyValueStorage;
+    use crate::storage::keyvalue::KeyValueStorage;
+    use crate::storage::postgres::test_utils::start_postgres;
+    use crate::storage::sqlite::test_utils::open_sqlite;
+
+    pub async fn in_memory() -> ((), Arc<dyn KeyValueStorage + Send + Sync>) {
+        ((), Arc::new(InMemoryKeyValueStorage::new()))
+    }
+
+    pub async fn postgres() -> (DockerPostgresRdb, Arc<dyn KeyValueStorage + Send + Sync>) {
+        let (rdb, pool) = start_postgres().await;
+        (rdb, Arc::new(PostgresKeyValueStorage::new(pool)))
+    }
+
+    pub async fn sqlite() -> (TempDir, Arc<dyn KeyValueStorage + Send + Sync>) {
+        let (dir, pool) = open_sqlite().await;
+        (dir, Arc::new(SqliteKeyValueStorage::new(pool)))
+    }
+}
+
+key_value_storage_tests!(in_memory, super::storages::in_memory);
+key_value_storage_tests!(postgres, super::storages::postgres);
+key_value_storage_tests!(sqlite, super::storages::sqlite);
@@ -15,3 +15,5 @@
 pub mod blob;
 pub mod indexed;
 pub mod keyvalue;
+pub mod postgres;
+pub mod sqlite;
@@ -0,0 +1,113 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{Connection, Executor, PgConnection, Pool, Postgres};
+use tracing::info;
+
+use crate::services::golem_config::DbPostgresConfig;
+
+/// Connection pool to a PostgreSQL database, shared by the storage implementations using it
+#[derive(Clone, Debug)]
+pub struct PostgresPool {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresPool {
+    /// Creates the configured schema if necessary, runs the migrations and connects the pool
+    pub async fn configured(config: &DbPostgresConfig) -> Result<Self, String> {
+        let schema = config.schema.clone().unwrap_or("public".to_string());
+        info!(
+            "DB Pool: postgresql://{}:{}/{}?currentSchema={}",
+            config.host, config.port, config.database, schema
+        );
+
+        Self::migrate(config, &schema)
+            .await
+            .map_err(|err| format!("Failed to migrate the Postgres database: {err}"))?;
+
+        let pool = PgPoolOptions::new()
+            .max_connections(config.max_connections)
+            .after_connect(move |conn, _meta| {
+                let s = schema.clone();
+                Box::pin(async move {
+                    let sql = format!("SET SCHEMA '{}';", s);
+                    conn.execute(sqlx::query(&sql)).await?;
+                    Ok(())
+                })
+            })
+            .connect_with(Self::connect_options(config))
+            .await
+            .map_err(|err| format!("Failed to connect to the Postgres database: {err}"))?;
+
+        Ok(Self { pool })
+    }
+
+    pub fn pool(&self) -> &Pool<Postgres> {
+        &self.pool
+    }
+
+    fn connect_options(config: &DbPostgresConfig) -> PgConnectOptions {
+        PgConnectOptions::new()
+            .host(config.host.as_str())
+            .port(config.port)
+            .database(config.database.as_str())
+            .username(config.username.as_str())
+            .password(config.password.as_str())
+    }
+
+    async fn migrate(config: &DbPostgresConfig, schema: &str) -> Result<(), sqlx::Error> {
+        let mut conn = PgConnection::connect_with(&Self::connect_options(config)).await?;
+        let sql = format!("CREATE SCHEMA IF NOT EXISTS {};", schema);
+        conn.execute(sqlx::query(&sql)).await?;
+        let sql = format!("SET SCHEMA '{}';", schema);
+        conn.execute(sqlx::query(&sql)).await?;
+
+        sqlx::migrate!("./db/migration/postgres")
+            .run(&mut conn)
+            .await?;
+
+        let _ = conn.close().await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use golem_test_framework::components::rdb::docker_postgres::DockerPostgresRdb;
+    use golem_test_framework::components::rdb::{DbInfo, Rdb};
+
+    use crate::services::golem_config::DbPostgresConfig;
+    use crate::storage::postgres::PostgresPool;
+
+    /// Starts a Postgres container and connects to it. The container is stopped when the
+    /// returned `DockerPostgresRdb` is dropped.
+    pub async fn start_postgres() -> (DockerPostgresRdb, PostgresPool) {
+        let rdb = DockerPostgresRdb::new(true).await;
+        let config = match rdb.info() {
+            DbInfo::Postgres(info) => DbPostgresConfig {
+                host: "localhost".to_string(),
+                database: info.database_name,
+                username: info.username,
+                password: info.password,
+                port: info.host_port,
+                max_connections: 10,
+                schema: None,
+            },
+            other => panic!("Unexpected database info: {other:?}"),
+        };
+        let pool = PostgresPool::configured(&config).await.unwrap();
+        (rdb, pool)
+    }
+}
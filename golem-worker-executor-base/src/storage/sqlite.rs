@@ -0,0 +1,75 @@
+// Copyright 2024 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::{Pool, Sqlite};
+use tracing::info;
+
+use crate::services::golem_config::DbSqliteConfig;
+
+/// Connection pool to a SQLite database file, shared by the storage implementations using it
+#[derive(Clone, Debug)]
+pub struct SqlitePool {
+    pool: Pool<Sqlite>,
+}
+
+impl SqlitePool {
+    /// Creates the database file if necessary, connects the pool and runs the migrations
+    pub async fn configured(config: &DbSqliteConfig) -> Result<Self, String> {
+        info!("DB Pool: sqlite://{}", config.database);
+        let conn_options = SqliteConnectOptions::new()
+            .filename(Path::new(config.database.as_str()))
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(config.max_connections)
+            .connect_with(conn_options)
+            .await
+            .map_err(|err| format!("Failed to open the Sqlite database: {err}"))?;
+
+        sqlx::migrate!("./db/migration/sqlite")
+            .run(&pool)
+            .await
+            .map_err(|err| format!("Failed to migrate the Sqlite database: {err}"))?;
+
+        Ok(Self { pool })
+    }
+
+    pub fn pool(&self) -> &Pool<Sqlite> {
+        &self.pool
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use tempfile::TempDir;
+
+    use crate::services::golem_config::DbSqliteConfig;
+    use crate::storage::sqlite::SqlitePool;
+
+    /// Opens a new SQLite database in a temporary directory, which is deleted when the returned
+    /// `TempDir` is dropped.
+    pub async fn open_sqlite() -> (TempDir, SqlitePool) {
+        let dir = tempfile::tempdir().unwrap();
+        let config = DbSqliteConfig {
+            database: dir.path().join("golem.db").to_string_lossy().to_string(),
+            max_connections: 4,
+        };
+        let pool = SqlitePool::configured(&config).await.unwrap();
+        (dir, pool)
+    }
+}
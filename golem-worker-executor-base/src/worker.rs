@@ -21,7 +21,7 @@ use async_mutex::Mutex;
 use golem_common::cache::PendingOrFinal;
 use golem_common::config::RetryConfig;
 use golem_common::model::oplog::{
-    OplogEntry, OplogIndex, TimestampedUpdateDescription, UpdateDescription,
+    OplogEntry, OplogIndex, RevertWorkerTarget, TimestampedUpdateDescription, UpdateDescription,
 };
 use golem_common::model::regions::{DeletedRegions, DeletedRegionsBuilder, OplogRegion};
 use golem_common::model::{
@@ -40,7 +40,7 @@ use crate::model::{ExecutionStatus, InterruptKind, LookupResult, TrapType, Worke
 use crate::services::events::Events;
 use crate::services::golem_config::GolemConfig;
 use crate::services::invocation_queue::InvocationQueue;
-use crate::services::oplog::Oplog;
+use crate::services::oplog::{Oplog, OplogOps};
 use crate::services::recovery::is_worker_error_retriable;
 use crate::services::worker_activator::WorkerActivator;
 use crate::services::worker_event::{WorkerEventService, WorkerEventServiceDefault};
@@ -123,6 +123,10 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                     .component_service()
                     .get(&this.engine(), &component_id, component_version)
                     .await?;
+                let initial_files = this
+                    .component_service()
+                    .get_initial_files(&component_id, component_version)
+                    .await?;
 
                 let execution_status = Arc::new(RwLock::new(ExecutionStatus::Suspended {
                     last_known_status: worker_metadata.last_known_status.clone(),
@@ -136,6 +140,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                     this.worker_enumeration_service(),
                     this.key_value_service(),
                     this.blob_store_service(),
+                    this.worker_file_system_service(),
                     pending_worker.event_service.clone(),
                     this.active_workers(),
                     this.oplog_service(),
@@ -145,6 +150,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                     this.recovery_management(),
                     this.rpc(),
                     this.worker_proxy(),
+                    this.component_service(),
                     this.extra_deps(),
                     this.config(),
                     WorkerConfig::new(
@@ -153,6 +159,8 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                         worker_args.clone(),
                         worker_env.clone(),
                         worker_metadata.last_known_status.deleted_regions.clone(),
+                        worker_metadata.last_known_status.last_snapshot,
+                        initial_files,
                     ),
                     execution_status.clone(),
                 )
@@ -848,6 +856,370 @@ pub async fn invoke_and_await<Ctx: WorkerCtx>(
     }
 }
 
+/// Creates a new worker with a copy of the source worker's oplog up to (and including) the given
+/// oplog index, and activates it. The new worker recovers by replaying the copied oplog and then
+/// continues running live from the cut-off point.
+///
+/// The target worker must not exist yet and must belong to the same component as the source worker.
+pub async fn fork_worker<Ctx, T>(
+    this: &T,
+    source_worker_id: &OwnedWorkerId,
+    target_worker_id: &OwnedWorkerId,
+    oplog_index_cutoff: OplogIndex,
+) -> Result<(), GolemError>
+where
+    Ctx: WorkerCtx,
+    T: HasAll<Ctx> + Send + Sync + Clone + 'static,
+{
+    if source_worker_id.component_id() != target_worker_id.component_id() {
+        return Err(GolemError::invalid_request(
+            "The forked worker must belong to the same component as the source worker",
+        ));
+    }
+
+    if !this.oplog_service().exists(source_worker_id).await {
+        return Err(GolemError::worker_not_found(source_worker_id.worker_id()));
+    }
+
+    if this.oplog_service().exists(target_worker_id).await
+        || this.worker_service().get(target_worker_id).await.is_some()
+    {
+        return Err(GolemError::WorkerAlreadyExists {
+            worker_id: target_worker_id.worker_id(),
+        });
+    }
+
+    let last_index = this.oplog_service().get_last_index(source_worker_id).await;
+    if oplog_index_cutoff < OplogIndex::INITIAL || oplog_index_cutoff > last_index {
+        return Err(GolemError::invalid_request(format!(
+            "Oplog index cut-off {oplog_index_cutoff} is out of range (1..={last_index})"
+        )));
+    }
+
+    let mut entries = this
+        .oplog_service()
+        .read_prefix(source_worker_id, oplog_index_cutoff)
+        .await;
+
+    // If the source oplog has been compacted, its initial entry is only kept by the worker
+    // service, and the remaining entries start with the snapshot which compacted it
+    let initial_entry = match entries.remove(&OplogIndex::INITIAL) {
+        Some(entry) => Some(entry),
+        None => {
+            if entries.is_empty() {
+                return Err(GolemError::invalid_request(format!(
+                    "Oplog index cut-off {oplog_index_cutoff} precedes the last snapshot of the compacted oplog"
+                )));
+            }
+            this.worker_service()
+                .get_initial_entry(source_worker_id)
+                .await
+        }
+    };
+
+    let (args, env, component_version) = match initial_entry {
+        Some(OplogEntry::Create {
+            args,
+            env,
+            component_version,
+            ..
+        }) => (args, env, component_version),
+        Some(other) => {
+            return Err(GolemError::unexpected_oplog_entry(
+                "Create",
+                format!("{other:?}"),
+            ))
+        }
+        None => return Err(GolemError::worker_not_found(source_worker_id.worker_id())),
+    };
+
+    // The forked worker gets its own initial entry, the rest of the prefix is copied as is so the
+    // oplog indices of the two workers match. The entries dropped by compaction are not copied,
+    // so in that case the forked worker's oplog continues with the snapshot right after its
+    // initial entry.
+    let source_oplog = this.oplog_service().open(source_worker_id).await;
+    let target_oplog = this
+        .oplog_service()
+        .create(
+            target_worker_id,
+            OplogEntry::create(
+                target_worker_id.worker_id(),
+                component_version,
+                args.clone(),
+                env.clone(),
+                target_worker_id.account_id(),
+            ),
+        )
+        .await;
+    for entry in entries.into_values() {
+        if let Err(err) = target_oplog.add_copied_entry(&source_oplog, entry).await {
+            drop(target_oplog);
+            this.oplog_service().delete(target_worker_id).await;
+            return Err(GolemError::unknown(format!(
+                "Failed to copy oplog entry while forking: {err}"
+            )));
+        }
+    }
+    target_oplog.commit().await;
+    drop(target_oplog);
+
+    // The metadata of the forked worker only gets stored once its oplog is complete
+    let metadata = this
+        .worker_service()
+        .get(target_worker_id)
+        .await
+        .ok_or(GolemError::worker_not_found(target_worker_id.worker_id()))?;
+    let status = calculate_last_known_status(this, target_worker_id, &Some(metadata)).await?;
+
+    // Recovering from a copied snapshot requires the files synced along with it
+    if let Some(last_snapshot) = status.last_snapshot {
+        if !status.deleted_regions.is_in_deleted_region(last_snapshot) {
+            let copied = this
+                .worker_file_system_service()
+                .copy_synced(source_worker_id, target_worker_id, last_snapshot)
+                .await
+                .map_err(|err| {
+                    GolemError::runtime(format!("Failed to copy the worker file system: {err}"))
+                });
+            match copied {
+                Ok(true) => {}
+                Ok(false) => {
+                    this.worker_service().remove(target_worker_id).await;
+                    return Err(GolemError::runtime(format!(
+                        "No file system was synced with the snapshot at oplog index {last_snapshot}"
+                    )));
+                }
+                Err(err) => {
+                    this.worker_service().remove(target_worker_id).await;
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    this.worker_service()
+        .update_status(target_worker_id, &status)
+        .await;
+
+    Worker::<Ctx>::activate(this, target_worker_id, args, env, Some(component_version)).await;
+
+    Ok(())
+}
+
+/// Reverts a worker to an earlier state by marking the end of its oplog as a deleted region, and
+/// restarts it so it recovers to the state it had at the target oplog index.
+///
+/// If the worker is currently running it gets interrupted first.
+pub async fn revert_worker<Ctx, T>(
+    this: &T,
+    owned_worker_id: &OwnedWorkerId,
+    target: RevertWorkerTarget,
+) -> Result<(), GolemError>
+where
+    Ctx: WorkerCtx,
+    T: HasAll<Ctx> + Send + Sync + Clone + 'static,
+{
+    let metadata = this
+        .worker_service()
+        .get(owned_worker_id)
+        .await
+        .ok_or(GolemError::worker_not_found(owned_worker_id.worker_id()))?;
+
+    if let Some((_, worker)) = this
+        .active_workers()
+        .enum_workers()
+        .into_iter()
+        .find(|(id, _)| *id == owned_worker_id.worker_id())
+    {
+        debug!("Interrupting worker before reverting it");
+        if let Some(mut await_interrupted) = worker.set_interrupting(InterruptKind::Interrupt) {
+            let _ = await_interrupted.recv().await;
+        }
+    }
+    // Ensuring the previous instance is dropped
+    this.active_workers().remove(&owned_worker_id.worker_id());
+
+    let last_known_status =
+        calculate_last_known_status(this, owned_worker_id, &Some(metadata.clone())).await?;
+    let last_index = last_known_status.oplog_idx;
+
+    let target_index = match target {
+        RevertWorkerTarget::RevertToOplogIndex(target_index) => target_index,
+        RevertWorkerTarget::RevertLastInvocations(count) => find_nth_last_invocation(
+            this,
+            owned_worker_id,
+            last_index,
+            &last_known_status.deleted_regions,
+            count,
+        )
+        .await?
+        .previous(),
+    };
+
+    if target_index < OplogIndex::INITIAL || target_index >= last_index {
+        return Err(GolemError::invalid_request(format!(
+            "Cannot revert worker to oplog index {target_index}, it must be in the range 1..{last_index}"
+        )));
+    }
+    if let Some(last_snapshot) = last_known_status.last_snapshot {
+        if target_index < last_snapshot {
+            return Err(GolemError::invalid_request(format!(
+                "Cannot revert worker to oplog index {target_index} because it precedes its last snapshot at {last_snapshot}"
+            )));
+        }
+    }
+    if last_known_status
+        .deleted_regions
+        .is_in_deleted_region(target_index)
+    {
+        return Err(GolemError::invalid_request(format!(
+            "Cannot revert worker to oplog index {target_index} because it is in a deleted region"
+        )));
+    }
+
+    // The deleted region covers the Jump entry itself too
+    let deleted_region = OplogRegion {
+        start: target_index.next(),
+        end: last_index.next(),
+    };
+    debug!("Reverting worker by deleting oplog region {deleted_region:?}");
+    this.oplog_service()
+        .open(owned_worker_id)
+        .await
+        .add_and_commit(OplogEntry::jump(deleted_region))
+        .await;
+
+    let last_known_status = calculate_last_known_status(
+        this,
+        owned_worker_id,
+        &Some(WorkerMetadata {
+            last_known_status,
+            ..metadata.clone()
+        }),
+    )
+    .await?;
+    this.worker_service()
+        .update_status(owned_worker_id, &last_known_status)
+        .await;
+
+    Worker::<Ctx>::activate(
+        this,
+        owned_worker_id,
+        metadata.args,
+        metadata.env,
+        Some(last_known_status.component_version),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Finds the oplog index of the `n`th exported function invocation counted from the end of the
+/// oplog, ignoring the deleted regions.
+/// Deletes a worker. If the worker is currently running it gets interrupted first, and its
+/// scheduled invocations are canceled.
+pub async fn delete_worker<Ctx, T>(
+    this: &T,
+    owned_worker_id: &OwnedWorkerId,
+) -> Result<(), GolemError>
+where
+    Ctx: WorkerCtx,
+    T: HasAll<Ctx> + Send + Sync + Clone + 'static,
+{
+    let worker_id = owned_worker_id.worker_id();
+    let metadata = this.worker_service().get(owned_worker_id).await;
+    let worker_status = Ctx::compute_latest_worker_status(this, owned_worker_id, &metadata).await?;
+    let metadata = metadata.ok_or(GolemError::invalid_request("Worker not found"))?;
+
+    let should_interrupt = match &worker_status.status {
+        WorkerStatus::Running | WorkerStatus::Suspended | WorkerStatus::Retrying => true,
+        WorkerStatus::Exited
+        | WorkerStatus::Failed
+        | WorkerStatus::Idle
+        | WorkerStatus::Interrupted => false,
+    };
+
+    if should_interrupt {
+        let worker_details = Worker::get_or_create_with_config(
+            this,
+            owned_worker_id,
+            metadata.args,
+            metadata.env,
+            Some(worker_status.component_version),
+        )
+        .await?;
+
+        if let Some(mut await_interrupted) =
+            worker_details.set_interrupting(InterruptKind::Interrupt)
+        {
+            await_interrupted.recv().await.unwrap();
+        }
+    }
+
+    Ctx::on_worker_deleted(this, &worker_id).await?;
+    this.worker_service().remove(owned_worker_id).await;
+    this.active_workers().remove(&worker_id);
+
+    let scheduler_service = this.scheduler_service();
+    for invocation in scheduler_service
+        .get_scheduled_invocations(owned_worker_id)
+        .await
+    {
+        scheduler_service
+            .cancel_scheduled_invocation(owned_worker_id, &invocation.id)
+            .await;
+    }
+
+    Ok(())
+}
+
+async fn find_nth_last_invocation<T: HasOplogService>(
+    this: &T,
+    owned_worker_id: &OwnedWorkerId,
+    last_index: OplogIndex,
+    deleted_regions: &DeletedRegions,
+    n: u64,
+) -> Result<OplogIndex, GolemError> {
+    const CHUNK_SIZE: u64 = 100;
+
+    if n == 0 {
+        return Err(GolemError::invalid_request(
+            "The number of invocations to revert must be positive",
+        ));
+    }
+
+    let first: u64 = OplogIndex::INITIAL.into();
+    let mut remaining = n;
+    let mut end: u64 = last_index.into();
+    while end >= first {
+        let start = end.saturating_sub(CHUNK_SIZE - 1).max(first);
+        let entries = this
+            .oplog_service()
+            .read_range(
+                owned_worker_id,
+                OplogIndex::from_u64(start),
+                OplogIndex::from_u64(end),
+            )
+            .await;
+        for (idx, entry) in entries.iter().rev() {
+            if matches!(entry, OplogEntry::ExportedFunctionInvoked { .. })
+                && !deleted_regions.is_in_deleted_region(*idx)
+            {
+                remaining -= 1;
+                if remaining == 0 {
+                    return Ok(*idx);
+                }
+            }
+        }
+        end = start - 1;
+    }
+
+    Err(GolemError::invalid_request(format!(
+        "Cannot revert the last {n} invocations, the worker only has {} invocations",
+        n - remaining
+    )))
+}
+
 /// Gets the last cached worker status record and the new oplog entries and calculates the new worker status.
 pub async fn calculate_last_known_status<T>(
     this: &T,
@@ -896,6 +1268,7 @@ where
         }
 
         let mut deleted_regions = calculate_deleted_regions(initial_deleted_regions, &new_entries);
+        let last_snapshot = calculate_last_snapshot(last_known.last_snapshot, &new_entries);
         let pending_invocations =
             calculate_pending_invocations(last_known.pending_invocations, &new_entries);
         let (pending_updates, failed_updates, successful_updates, component_version) =
@@ -939,6 +1312,7 @@ where
             invocation_results,
             current_idempotency_key,
             component_version,
+            last_snapshot,
         })
     }
 }
@@ -1024,6 +1398,8 @@ fn calculate_latest_worker_status(
             }
             OplogEntry::FailedUpdate { .. } => {}
             OplogEntry::SuccessfulUpdate { .. } => {}
+            OplogEntry::CancelPendingInvocation { .. } => {}
+            OplogEntry::Snapshot { .. } => {}
         }
     }
     result
@@ -1034,14 +1410,38 @@ fn calculate_deleted_regions(
     entries: &BTreeMap<OplogIndex, OplogEntry>,
 ) -> DeletedRegions {
     let mut builder = DeletedRegionsBuilder::from_regions(initial.into_regions());
-    for entry in entries.values() {
-        if let OplogEntry::Jump { jump, .. } = entry {
-            builder.add(jump.clone());
+    for (idx, entry) in entries {
+        match entry {
+            OplogEntry::Jump { jump, .. } => {
+                builder.add(jump.clone());
+            }
+            OplogEntry::Snapshot { .. } => {
+                // Everything between the initial entry and the snapshot is restored by loading
+                // the snapshot, so it must not be replayed
+                if idx.previous() > OplogIndex::INITIAL {
+                    builder.add(OplogRegion::from_index_range(
+                        OplogIndex::INITIAL.next()..=idx.previous(),
+                    ));
+                }
+            }
+            _ => {}
         }
     }
     builder.build()
 }
 
+fn calculate_last_snapshot(
+    initial: Option<OplogIndex>,
+    entries: &BTreeMap<OplogIndex, OplogEntry>,
+) -> Option<OplogIndex> {
+    entries
+        .iter()
+        .rev()
+        .find(|(_, entry)| matches!(entry, OplogEntry::Snapshot { .. }))
+        .map(|(idx, _)| *idx)
+        .or(initial)
+}
+
 pub fn calculate_worker_status(
     retry_config: &RetryConfig,
     trap_type: &TrapType,
@@ -1095,6 +1495,9 @@ fn calculate_pending_invocations(
             }
             OplogEntry::ExportedFunctionInvoked {
                 idempotency_key, ..
+            }
+            | OplogEntry::CancelPendingInvocation {
+                idempotency_key, ..
             } => {
                 result.retain(|invocation| match invocation {
                     TimestampedWorkerInvocation {
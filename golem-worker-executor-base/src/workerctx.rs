@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::path::PathBuf;
 use std::string::FromUtf8Error;
 use std::sync::{Arc, RwLock};
 
@@ -32,6 +33,7 @@ use crate::model::{
 };
 use crate::services::active_workers::ActiveWorkers;
 use crate::services::blob_store::BlobStoreService;
+use crate::services::component::ComponentService;
 use crate::services::events::Events;
 use crate::services::golem_config::GolemConfig;
 use crate::services::invocation_queue::InvocationQueue;
@@ -43,6 +45,7 @@ use crate::services::rpc::Rpc;
 use crate::services::scheduler::SchedulerService;
 use crate::services::worker::WorkerService;
 use crate::services::worker_event::WorkerEventService;
+use crate::services::worker_file_system::WorkerFileSystemService;
 use crate::services::worker_proxy::WorkerProxy;
 use crate::services::{worker_enumeration, HasAll, HasInvocationQueue, HasOplog};
 
@@ -78,6 +81,7 @@ pub trait WorkerCtx:
     /// - `worker_service`: The service for managing workers
     /// - `key_value_service`: The service for storing key-value pairs
     /// - `blob_store_service`: The service for storing arbitrary blobs
+    /// - `worker_file_system_service`: The service for persisting the worker's file system
     /// - `event_service`: The service for publishing worker events
     /// - `active_workers`: The service for managing active workers
     /// - `oplog_service`: The service for reading and writing the oplog
@@ -85,6 +89,7 @@ pub trait WorkerCtx:
     /// - `recovery_management`: The service for deciding if a worker should be recovered
     /// - `rpc`: The RPC implementation used for worker to worker communication
     /// - `worker_proyx`: Access to the worker proxy above the worker executor cluster
+    /// - `component_service`: The service for downloading components and their metadata
     /// - `extra_deps`: Extra dependencies that are required by this specific worker context
     /// - `config`: The shared worker configuration
     /// - `worker_config`: Configuration for this specific worker
@@ -100,6 +105,7 @@ pub trait WorkerCtx:
         >,
         key_value_service: Arc<dyn KeyValueService + Send + Sync>,
         blob_store_service: Arc<dyn BlobStoreService + Send + Sync>,
+        worker_file_system_service: Arc<dyn WorkerFileSystemService + Send + Sync>,
         event_service: Arc<dyn WorkerEventService + Send + Sync>,
         active_workers: Arc<ActiveWorkers<Self>>,
         oplog_service: Arc<dyn OplogService + Send + Sync>,
@@ -109,6 +115,7 @@ pub trait WorkerCtx:
         recovery_management: Arc<dyn RecoveryManagement + Send + Sync>,
         rpc: Arc<dyn Rpc + Send + Sync>,
         worker_proxy: Arc<dyn WorkerProxy + Send + Sync>,
+        component_service: Arc<dyn ComponentService + Send + Sync>,
         extra_deps: Self::ExtraDeps,
         config: Arc<GolemConfig>,
         worker_config: WorkerConfig,
@@ -314,6 +321,12 @@ pub trait UpdateManagement {
 
     /// Called when an update attempt succeeded
     async fn on_worker_update_succeeded(&self, target_version: ComponentVersion);
+
+    /// Checks whether the configured snapshot policy requires saving a snapshot of the worker
+    async fn is_snapshot_due(&self) -> bool;
+
+    /// Called when the worker's `save-snapshot` export returned a new snapshot of its state
+    async fn on_snapshot_saved(&mut self, snapshot: &[u8]) -> Result<(), String>;
 }
 
 /// Operations not requiring an active worker context, but still depending on the
@@ -376,10 +389,14 @@ pub trait ExternalOperations<Ctx: WorkerCtx> {
 
 /// A required interface to be implemented by the worker context's public state.
 ///
-/// It is used to "connect" to a worker's event stream
+/// It is used to "connect" to a worker's event stream and to inspect its file system
 #[async_trait]
 pub trait PublicWorkerIo {
     /// Gets the event service created for the worker, which can be used to
     /// subscribe to worker events.
     fn event_service(&self) -> Arc<dyn WorkerEventService + Send + Sync>;
+
+    /// Gets the directory on the host which is pre-opened as the root of the worker's
+    /// file system.
+    fn file_system_root(&self) -> PathBuf;
 }
@@ -12,22 +12,30 @@ use http_02::{Response, StatusCode};
 use redis::Commands;
 
 use golem_api_grpc::proto::golem::worker::{
-    worker_execution_error, ComponentParseFailed, LogEvent,
+    worker_execution_error, CancelInvocationStatus, ComponentParseFailed, LogEvent, OplogEntryType,
+    OplogFilter,
 };
-use golem_api_grpc::proto::golem::workerexecutor::CompletePromiseRequest;
+use golem_api_grpc::proto::golem::workerexecutor::{
+    complete_promise_response, CompletePromiseRequest,
+};
+use golem_common::model::cron::CronSchedule;
 use golem_common::model::{
-    AccountId, ComponentId, FilterComparator, IdempotencyKey, PromiseId, ScanCursor,
-    StringFilterComparator, WorkerFilter, WorkerId, WorkerMetadata, WorkerStatus,
+    AccountId, ComponentId, FilterComparator, IdempotencyKey, InvocationSchedule, PromiseId,
+    ScanCursor, StringFilterComparator, Timestamp, WorkerFilter, WorkerId, WorkerMetadata,
+    WorkerStatus,
 };
 use golem_wasm_rpc::Value;
 
-use crate::common::{start, TestContext, TestWorkerExecutor};
-use golem_common::model::oplog::OplogIndex;
+use crate::common::{start, start_customized, TestContext, TestWorkerExecutor};
+use golem_common::model::oplog::{OplogIndex, RevertWorkerTarget};
 use golem_test_framework::config::TestDependencies;
 use golem_test_framework::dsl::{
     drain_connection, is_worker_execution_error, stdout_event, worker_error_message, TestDsl,
 };
-use tokio::time::sleep;
+use golem_worker_executor_base::services::golem_config::{
+    PromisesConfig, RetentionPolicy, SnapshotConfig, SnapshotOplogSizeConfig, SnapshotPolicy,
+};
+use tokio::time::{sleep, Instant};
 use tonic::transport::Body;
 use tracing::debug;
 use warp::Filter;
@@ -317,6 +325,182 @@ async fn promise() {
     check!(result == Ok(vec![Value::List(vec![Value::U8(42)])]));
 }
 
+#[tokio::test]
+#[tracing::instrument]
+async fn pending_promises() {
+    let context = TestContext::new();
+    let executor = start(&context).await.unwrap();
+
+    let component_id = executor.store_component("promise").await;
+    let worker_id = executor.start_worker(&component_id, "promise-2").await;
+
+    let executor_clone = executor.clone();
+    let worker_id_clone = worker_id.clone();
+    let fiber = tokio::spawn(async move {
+        executor_clone
+            .invoke_and_await(&worker_id_clone, "run", vec![])
+            .await
+    });
+
+    sleep(Duration::from_secs(10)).await;
+
+    let pending1 = executor.get_pending_promises(&worker_id).await.unwrap();
+
+    let promise_id = PromiseId {
+        worker_id: worker_id.clone(),
+        oplog_idx: OplogIndex::from_u64(3),
+    };
+    executor
+        .client()
+        .await
+        .complete_promise(CompletePromiseRequest {
+            promise_id: Some(promise_id.clone().into()),
+            data: vec![42],
+            account_id: Some(
+                AccountId {
+                    value: "test-account".to_string(),
+                }
+                .into(),
+            ),
+        })
+        .await
+        .unwrap();
+
+    let result = fiber.await.unwrap();
+    let pending2 = executor.get_pending_promises(&worker_id).await.unwrap();
+
+    drop(executor);
+
+    check!(result.is_ok());
+    check!(pending1.len() == 1);
+    check!(pending1[0].promise_id == Some(promise_id.into()));
+    check!(pending1[0].expires_at.is_none());
+    check!(pending1[0].payload_type.is_none());
+    check!(pending2.is_empty());
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn promise_expiry() {
+    let context = TestContext::new();
+    let executor = start_customized(&context, |config| {
+        config.promises = PromisesConfig {
+            expire_after: Some(Duration::from_secs(2)),
+        };
+    })
+    .await
+    .unwrap();
+
+    let component_id = executor.store_component("promise").await;
+    let worker_id = executor.start_worker(&component_id, "promise-3").await;
+
+    let result = executor.invoke_and_await(&worker_id, "run", vec![]).await;
+    let pending = executor.get_pending_promises(&worker_id).await.unwrap();
+
+    drop(executor);
+
+    check!(result.is_err());
+    check!(worker_error_message(&result.err().unwrap()).contains("Promise timed out"));
+    check!(pending.is_empty());
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn promise_expiry_per_promise() {
+    let context = TestContext::new();
+    let executor = start_customized(&context, |config| {
+        config.promises = PromisesConfig {
+            expire_after: Some(Duration::from_secs(3600)),
+        };
+    })
+    .await
+    .unwrap();
+
+    let component_id = executor.store_component("promise").await;
+    let worker_id = executor.start_worker(&component_id, "promise-4").await;
+
+    let start = Instant::now();
+    let result = executor
+        .invoke_and_await(&worker_id, "run-with-expiry", vec![Value::U64(2)])
+        .await;
+    let elapsed = start.elapsed();
+
+    drop(executor);
+
+    // The expiration given by the worker overrides the default one of the executor, and the
+    // worker handles it instead of failing
+    check!(
+        result
+            == Ok(vec![Value::Result(Err(Some(Box::new(Value::String(
+                "timed out".to_string()
+            )))))])
+    );
+    check!(elapsed < Duration::from_secs(60));
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn typed_promise() {
+    let context = TestContext::new();
+    let executor = start(&context).await.unwrap();
+
+    let component_id = executor.store_component("promise").await;
+    let worker_id = executor.start_worker(&component_id, "promise-5").await;
+
+    let executor_clone = executor.clone();
+    let worker_id_clone = worker_id.clone();
+    let fiber = tokio::spawn(async move {
+        executor_clone
+            .invoke_and_await(&worker_id_clone, "run-typed", vec![])
+            .await
+    });
+
+    sleep(Duration::from_secs(10)).await;
+
+    let pending = executor.get_pending_promises(&worker_id).await.unwrap();
+    let complete = |data: &[u8]| CompletePromiseRequest {
+        promise_id: pending[0].promise_id.clone(),
+        data: data.to_vec(),
+        account_id: Some(
+            AccountId {
+                value: "test-account".to_string(),
+            }
+            .into(),
+        ),
+    };
+
+    let invalid = executor
+        .client()
+        .await
+        .complete_promise(complete(&[42]))
+        .await
+        .unwrap()
+        .into_inner();
+    let valid = executor
+        .client()
+        .await
+        .complete_promise(complete(br#"{"name":"x","count":2}"#))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let result = fiber.await.unwrap();
+
+    drop(executor);
+
+    check!(pending.len() == 1);
+    check!(pending[0].payload_type.is_some());
+    check!(matches!(
+        invalid.result,
+        Some(complete_promise_response::Result::Failure(_))
+    ));
+    check!(matches!(
+        valid.result,
+        Some(complete_promise_response::Result::Success(_))
+    ));
+    check!(result == Ok(vec![Value::String(r#"{"name":"x","count":2}"#.to_string())]));
+}
+
 #[tokio::test]
 #[tracing::instrument]
 async fn get_self_uri() {
@@ -2049,3 +2233,739 @@ async fn invocation_queue_is_persistent() {
 
     check!(result == vec![Value::U64(4)]);
 }
+
+#[tokio::test]
+#[tracing::instrument]
+async fn get_oplog() {
+    let context = TestContext::new();
+    let executor = start(&context).await.unwrap();
+
+    let component_id = executor.store_component("shopping-cart").await;
+    let worker_id = executor.start_worker(&component_id, "get-oplog-1").await;
+
+    let _ = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/initialize-cart",
+            vec![Value::String("test-user-1".to_string())],
+        )
+        .await;
+
+    let _ = executor
+        .invoke_and_await(&worker_id, "golem:it/api/get-cart-contents", vec![])
+        .await;
+
+    let oplog = executor.get_oplog(&worker_id, OplogIndex::INITIAL).await;
+
+    drop(executor);
+
+    let invoked_functions = oplog
+        .iter()
+        .filter(|entry| entry.entry_type() == OplogEntryType::ExportedFunctionInvoked)
+        .map(|entry| entry.function_name.clone().unwrap_or_default())
+        .collect::<Vec<_>>();
+
+    check!(oplog[0].entry_type() == OplogEntryType::Create);
+    check!(oplog[0].oplog_index == 1);
+    check!(
+        invoked_functions
+            == vec![
+                "golem:it/api/initialize-cart".to_string(),
+                "golem:it/api/get-cart-contents".to_string()
+            ]
+    );
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn search_oplog() {
+    let context = TestContext::new();
+    let executor = start(&context).await.unwrap();
+
+    let component_id = executor.store_component("shopping-cart").await;
+    let worker_id = executor.start_worker(&component_id, "search-oplog-1").await;
+
+    let _ = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/initialize-cart",
+            vec![Value::String("test-user-1".to_string())],
+        )
+        .await;
+
+    let _ = executor
+        .invoke_and_await(&worker_id, "golem:it/api/get-cart-contents", vec![])
+        .await;
+
+    let completed = executor
+        .search_oplog(
+            &worker_id,
+            OplogFilter {
+                entry_types: vec![OplogEntryType::ExportedFunctionCompleted.into()],
+                after: None,
+                before: None,
+                function_name: Some("golem:it/api/get-cart-contents".to_string()),
+            },
+        )
+        .await;
+
+    drop(executor);
+
+    check!(completed.len() == 1);
+    check!(completed[0].entry_type() == OplogEntryType::ExportedFunctionCompleted);
+    check!(completed[0].function_name == Some("golem:it/api/get-cart-contents".to_string()));
+    check!(completed[0].response.len() == 1);
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn search_oplog_decodes_imported_function_responses() {
+    let context = TestContext::new();
+    let executor = start(&context).await.unwrap();
+
+    let component_id = executor.store_component("clocks").await;
+    let worker_id = executor.start_worker(&component_id, "search-oplog-2").await;
+
+    let _ = executor.invoke_and_await(&worker_id, "run", vec![]).await;
+
+    let imported = executor
+        .search_oplog(
+            &worker_id,
+            OplogFilter {
+                entry_types: vec![OplogEntryType::ImportedFunctionInvoked.into()],
+                after: None,
+                before: None,
+                function_name: Some("wall_clock::now".to_string()),
+            },
+        )
+        .await;
+
+    drop(executor);
+
+    check!(!imported.is_empty());
+    check!(imported
+        .iter()
+        .all(|entry| entry.entry_type() == OplogEntryType::ImportedFunctionInvoked));
+    check!(imported.iter().all(|entry| entry.response.len() == 1));
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn fork_worker() {
+    let context = TestContext::new();
+    let executor = start(&context).await.unwrap();
+
+    let component_id = executor.store_component("shopping-cart").await;
+    let worker_id = executor.start_worker(&component_id, "fork-worker-1").await;
+
+    let _ = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/initialize-cart",
+            vec![Value::String("test-user-1".to_string())],
+        )
+        .await;
+
+    let _ = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/add-item",
+            vec![Value::Record(vec![
+                Value::String("G1000".to_string()),
+                Value::String("Golem T-Shirt M".to_string()),
+                Value::F32(100.0),
+                Value::U32(5),
+            ])],
+        )
+        .await;
+
+    let _ = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/add-item",
+            vec![Value::Record(vec![
+                Value::String("G1001".to_string()),
+                Value::String("Golem Cloud Subscription 1y".to_string()),
+                Value::F32(999999.0),
+                Value::U32(1),
+            ])],
+        )
+        .await;
+
+    let first_add_item = executor
+        .search_oplog(
+            &worker_id,
+            OplogFilter {
+                entry_types: vec![OplogEntryType::ExportedFunctionCompleted.into()],
+                after: None,
+                before: None,
+                function_name: Some("golem:it/api/add-item".to_string()),
+            },
+        )
+        .await[0]
+        .oplog_index;
+
+    let forked_worker_id = executor
+        .fork_worker(
+            &worker_id,
+            "fork-worker-2",
+            OplogIndex::from_u64(first_add_item),
+        )
+        .await
+        .unwrap();
+
+    let original_contents = executor
+        .invoke_and_await(&worker_id, "golem:it/api/get-cart-contents", vec![])
+        .await;
+    let forked_contents = executor
+        .invoke_and_await(&forked_worker_id, "golem:it/api/get-cart-contents", vec![])
+        .await;
+
+    let forking_again = executor
+        .fork_worker(
+            &worker_id,
+            "fork-worker-2",
+            OplogIndex::from_u64(first_add_item),
+        )
+        .await;
+
+    drop(executor);
+
+    check!(
+        forked_contents
+            == Ok(vec![Value::List(vec![Value::Record(vec![
+                Value::String("G1000".to_string()),
+                Value::String("Golem T-Shirt M".to_string()),
+                Value::F32(100.0),
+                Value::U32(5),
+            ])])])
+    );
+    check!(original_contents.unwrap()[0] != forked_contents.unwrap()[0]);
+    check!(forking_again.is_err());
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn revert_worker() {
+    let context = TestContext::new();
+    let executor = start(&context).await.unwrap();
+
+    let component_id = executor.store_component("shopping-cart").await;
+    let worker_id = executor.start_worker(&component_id, "revert-worker").await;
+
+    let _ = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/initialize-cart",
+            vec![Value::String("test-user-1".to_string())],
+        )
+        .await;
+
+    let _ = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/add-item",
+            vec![Value::Record(vec![
+                Value::String("G1000".to_string()),
+                Value::String("Golem T-Shirt M".to_string()),
+                Value::F32(100.0),
+                Value::U32(5),
+            ])],
+        )
+        .await;
+
+    let _ = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/add-item",
+            vec![Value::Record(vec![
+                Value::String("G1001".to_string()),
+                Value::String("Golem Cloud Subscription 1y".to_string()),
+                Value::F32(999999.0),
+                Value::U32(1),
+            ])],
+        )
+        .await;
+
+    let reverting_too_much = executor
+        .revert_worker(&worker_id, RevertWorkerTarget::RevertLastInvocations(10))
+        .await;
+
+    executor
+        .revert_worker(&worker_id, RevertWorkerTarget::RevertLastInvocations(1))
+        .await
+        .unwrap();
+
+    let contents = executor
+        .invoke_and_await(&worker_id, "golem:it/api/get-cart-contents", vec![])
+        .await;
+
+    drop(executor);
+
+    check!(reverting_too_much.is_err());
+    check!(
+        contents
+            == Ok(vec![Value::List(vec![Value::Record(vec![
+                Value::String("G1000".to_string()),
+                Value::String("Golem T-Shirt M".to_string()),
+                Value::F32(100.0),
+                Value::U32(5),
+            ])])])
+    );
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn cancel_pending_invocation() {
+    let context = TestContext::new();
+    let executor = start(&context).await.unwrap();
+
+    let component_id = executor.store_component("clock-service").await;
+    let worker_id = executor
+        .start_worker(&component_id, "cancel-pending-invocation-1")
+        .await;
+
+    let running_key = IdempotencyKey::fresh();
+    executor
+        .invoke_with_key(
+            &worker_id,
+            &running_key,
+            "golem:it/api/sleep",
+            vec![Value::U64(5)],
+        )
+        .await
+        .unwrap();
+
+    sleep(Duration::from_secs(1)).await;
+
+    let idempotency_key = IdempotencyKey::fresh();
+    executor
+        .invoke_with_key(
+            &worker_id,
+            &idempotency_key,
+            "golem:it/api/sleep",
+            vec![Value::U64(1)],
+        )
+        .await
+        .unwrap();
+
+    let canceled = executor
+        .cancel_invocation(&worker_id, &idempotency_key)
+        .await
+        .unwrap();
+    let canceled_again = executor
+        .cancel_invocation(&worker_id, &idempotency_key)
+        .await
+        .unwrap();
+    let canceled_running = executor
+        .cancel_invocation(&worker_id, &running_key)
+        .await
+        .unwrap();
+
+    sleep(Duration::from_secs(6)).await;
+
+    let canceled_completed = executor
+        .cancel_invocation(&worker_id, &running_key)
+        .await
+        .unwrap();
+
+    let invocations = executor
+        .search_oplog(
+            &worker_id,
+            OplogFilter {
+                entry_types: vec![OplogEntryType::ExportedFunctionInvoked.into()],
+                after: None,
+                before: None,
+                function_name: None,
+            },
+        )
+        .await;
+    let cancellations = executor
+        .search_oplog(
+            &worker_id,
+            OplogFilter {
+                entry_types: vec![OplogEntryType::CancelPendingInvocation.into()],
+                after: None,
+                before: None,
+                function_name: None,
+            },
+        )
+        .await;
+    let metadata = executor.get_worker_metadata(&worker_id).await.unwrap();
+
+    drop(executor);
+
+    check!(canceled == CancelInvocationStatus::Canceled);
+    check!(canceled_again == CancelInvocationStatus::NotFound);
+    check!(canceled_running == CancelInvocationStatus::AlreadyRunning);
+    check!(canceled_completed == CancelInvocationStatus::AlreadyCompleted);
+    check!(invocations.len() == 1);
+    check!(cancellations.len() == 1);
+    check!(metadata.last_known_status.pending_invocations.is_empty());
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn scheduled_invocations() {
+    let context = TestContext::new();
+    let executor = start(&context).await.unwrap();
+
+    let component_id = executor.store_component("shopping-cart").await;
+    let worker_id = executor
+        .start_worker(&component_id, "scheduled-invocations-1")
+        .await;
+
+    let _ = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/initialize-cart",
+            vec![Value::String("test-user-1".to_string())],
+        )
+        .await;
+
+    let at = Timestamp::from(Timestamp::now_utc().to_millis() + 2000);
+    let one_off = executor
+        .schedule_invocation(
+            &worker_id,
+            "golem:it/api/add-item",
+            vec![Value::Record(vec![
+                Value::String("G1000".to_string()),
+                Value::String("Golem T-Shirt M".to_string()),
+                Value::F32(100.0),
+                Value::U32(5),
+            ])],
+            InvocationSchedule::At(at),
+        )
+        .await
+        .unwrap();
+    let recurring = executor
+        .schedule_invocation(
+            &worker_id,
+            "golem:it/api/add-item",
+            vec![Value::Record(vec![
+                Value::String("G1001".to_string()),
+                Value::String("Golem Cloud Subscription 1y".to_string()),
+                Value::F32(999999.0),
+                Value::U32(1),
+            ])],
+            InvocationSchedule::Cron(CronSchedule::parse("0 0 1 1 *").unwrap()),
+        )
+        .await
+        .unwrap();
+
+    let scheduled = executor
+        .get_scheduled_invocations(&worker_id)
+        .await
+        .unwrap();
+    let canceled = executor
+        .cancel_scheduled_invocation(&worker_id, &recurring.id)
+        .await
+        .unwrap();
+
+    sleep(Duration::from_secs(6)).await;
+
+    let contents = executor
+        .invoke_and_await(&worker_id, "golem:it/api/get-cart-contents", vec![])
+        .await
+        .unwrap();
+    let remaining = executor
+        .get_scheduled_invocations(&worker_id)
+        .await
+        .unwrap();
+
+    drop(executor);
+
+    check!(scheduled.len() == 2);
+    check!(scheduled[0].id == one_off.id);
+    check!(canceled);
+    check!(remaining.is_empty());
+    check!(
+        contents
+            == vec![Value::List(vec![Value::Record(vec![
+                Value::String("G1000".to_string()),
+                Value::String("Golem T-Shirt M".to_string()),
+                Value::F32(100.0),
+                Value::U32(5),
+            ])])]
+    );
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn scheduled_invocations_from_worker() {
+    let context = TestContext::new();
+    let executor = start(&context).await.unwrap();
+
+    let component_id = executor.store_component("runtime-service").await;
+    let worker_id = executor
+        .start_worker(&component_id, "scheduled-invocations-from-worker-1")
+        .await;
+
+    let first = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/schedule-bump",
+            vec![Value::U64(2)],
+        )
+        .await
+        .unwrap();
+    let second = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/schedule-bump",
+            vec![Value::U64(2)],
+        )
+        .await
+        .unwrap();
+
+    let scheduled = executor
+        .get_scheduled_invocations(&worker_id)
+        .await
+        .unwrap();
+
+    let canceled = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/cancel-scheduled-bump",
+            vec![second[0].clone()],
+        )
+        .await
+        .unwrap();
+
+    sleep(Duration::from_secs(6)).await;
+
+    let bumps = executor
+        .invoke_and_await(&worker_id, "golem:it/api/get-bumps", vec![])
+        .await
+        .unwrap();
+    let canceled_after_performed = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/cancel-scheduled-bump",
+            vec![first[0].clone()],
+        )
+        .await
+        .unwrap();
+    let remaining = executor
+        .get_scheduled_invocations(&worker_id)
+        .await
+        .unwrap();
+
+    drop(executor);
+
+    check!(scheduled.len() == 2);
+    check!(canceled == vec![Value::Bool(true)]);
+    check!(bumps == vec![Value::U64(1)]);
+    check!(canceled_after_performed == vec![Value::Bool(false)]);
+    check!(remaining.is_empty());
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn snapshot_and_oplog_compaction() {
+    let context = TestContext::new();
+    let executor = start_customized(&context, |config| {
+        config.snapshot = SnapshotConfig {
+            policy: SnapshotPolicy::OplogSize(SnapshotOplogSizeConfig { entries: 4 }),
+            compact_oplog: true,
+        };
+    })
+    .await
+    .unwrap();
+
+    let component_id = executor.store_component("update-test-v3").await;
+    let worker_id = executor
+        .start_worker(&component_id, "snapshot-compaction-1")
+        .await;
+
+    for value in 1..=5 {
+        let _ = executor
+            .invoke_and_await(
+                &worker_id,
+                "golem:component/api/set",
+                vec![Value::U64(value)],
+            )
+            .await
+            .unwrap();
+    }
+
+    let oplog = executor.get_oplog(&worker_id, OplogIndex::INITIAL).await;
+
+    executor.simulated_crash(&worker_id).await;
+    let result = executor
+        .invoke_and_await(&worker_id, "golem:component/api/get", vec![])
+        .await
+        .unwrap();
+    let metadata = executor.get_worker_metadata(&worker_id).await;
+
+    drop(executor);
+
+    check!(oplog
+        .iter()
+        .any(|entry| entry.entry_type() == OplogEntryType::Snapshot));
+    check!(oplog[0].oplog_index == 1);
+    check!(oplog[0].entry_type() == OplogEntryType::Create);
+    check!(oplog[1].oplog_index > 2);
+    check!(result == vec![Value::U64(5)]);
+    check!(metadata.is_some());
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn recover_files_from_snapshot() {
+    let context = TestContext::new();
+    let executor = start_customized(&context, |config| {
+        config.snapshot = SnapshotConfig {
+            policy: SnapshotPolicy::OplogSize(SnapshotOplogSizeConfig { entries: 4 }),
+            compact_oplog: true,
+        };
+    })
+    .await
+    .unwrap();
+
+    let component_id = executor.store_component("update-test-v3").await;
+    let initial_files = Path::new("data/components").join(format!("{component_id}-0.files"));
+    std::fs::create_dir_all(initial_files.join("read-write")).unwrap();
+    std::fs::write(initial_files.join("read-write/hello.txt"), "hello world").unwrap();
+
+    let worker_id = executor
+        .start_worker(&component_id, "recover-files-from-snapshot-1")
+        .await;
+
+    for value in 1..=5 {
+        let _ = executor
+            .invoke_and_await(
+                &worker_id,
+                "golem:component/api/set",
+                vec![Value::U64(value)],
+            )
+            .await
+            .unwrap();
+    }
+
+    // The initial files are not written again when recovering from a snapshot, so the file can
+    // only come from the file system synced along with it
+    std::fs::remove_dir_all(&initial_files).unwrap();
+    executor.simulated_crash(&worker_id).await;
+    let result = executor
+        .invoke_and_await(&worker_id, "golem:component/api/get", vec![])
+        .await
+        .unwrap();
+    let entries = executor.list_worker_directory(&worker_id, "/").await;
+
+    drop(executor);
+
+    let entries = entries.unwrap();
+    let hello = entries.iter().find(|entry| entry.name == "hello.txt");
+    check!(result == vec![Value::U64(5)]);
+    check!(hello.is_some());
+    check!(hello.unwrap().size == 11);
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn fork_and_inspect_compacted_worker() {
+    let context = TestContext::new();
+    let executor = start_customized(&context, |config| {
+        config.snapshot = SnapshotConfig {
+            policy: SnapshotPolicy::OplogSize(SnapshotOplogSizeConfig { entries: 4 }),
+            compact_oplog: true,
+        };
+    })
+    .await
+    .unwrap();
+
+    let component_id = executor.store_component("update-test-v3").await;
+    let worker_id = executor
+        .start_worker(&component_id, "fork-compacted-1")
+        .await;
+
+    for value in 1..=5 {
+        let _ = executor
+            .invoke_and_await(
+                &worker_id,
+                "golem:component/api/set",
+                vec![Value::U64(value)],
+            )
+            .await
+            .unwrap();
+    }
+
+    let oplog = executor.get_oplog(&worker_id, OplogIndex::INITIAL).await;
+    let creates = executor
+        .search_oplog(
+            &worker_id,
+            OplogFilter {
+                entry_types: vec![OplogEntryType::Create.into()],
+                ..Default::default()
+            },
+        )
+        .await;
+    let last_index = OplogIndex::from_u64(oplog.last().unwrap().oplog_index);
+
+    let forked_worker_id = executor
+        .fork_worker(&worker_id, "fork-compacted-2", last_index)
+        .await
+        .unwrap();
+    let forked_result = executor
+        .invoke_and_await(&forked_worker_id, "golem:component/api/get", vec![])
+        .await
+        .unwrap();
+    let forked_oplog = executor
+        .get_oplog(&forked_worker_id, OplogIndex::INITIAL)
+        .await;
+
+    let forking_compacted_part = executor
+        .fork_worker(&worker_id, "fork-compacted-3", OplogIndex::INITIAL.next())
+        .await;
+
+    drop(executor);
+
+    check!(oplog[0].entry_type() == OplogEntryType::Create);
+    check!(oplog[1].oplog_index > 2);
+    check!(creates.len() == 1);
+    check!(creates[0].oplog_index == 1);
+    check!(forked_result == vec![Value::U64(5)]);
+    check!(forked_oplog[0].entry_type() == OplogEntryType::Create);
+    check!(forked_oplog
+        .iter()
+        .any(|entry| entry.entry_type() == OplogEntryType::Snapshot));
+    check!(forking_compacted_part.is_err());
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn failed_worker_deleted_by_retention_policy() {
+    let context = TestContext::new();
+    let executor = start_customized(&context, |config| {
+        config.retention.default = RetentionPolicy {
+            delete_failed_after: Some(Duration::from_secs(1)),
+            ..Default::default()
+        };
+    })
+    .await
+    .unwrap();
+
+    let component_id = executor.store_component("failing-component").await;
+    let worker_id = executor
+        .start_worker(&component_id, "retention-failed-1")
+        .await;
+
+    let result1 = executor
+        .invoke_and_await(&worker_id, "golem:component/api/add", vec![Value::U64(5)])
+        .await;
+    let result2 = executor
+        .invoke_and_await(&worker_id, "golem:component/api/add", vec![Value::U64(50)])
+        .await;
+
+    let metadata1 = executor.get_worker_metadata(&worker_id).await;
+    sleep(Duration::from_secs(6)).await;
+    let metadata2 = executor.get_worker_metadata(&worker_id).await;
+
+    drop(executor);
+
+    check!(result1.is_ok());
+    check!(result2.is_err());
+    check!(metadata1.is_some());
+    check!(metadata2.is_none());
+}
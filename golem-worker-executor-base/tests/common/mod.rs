@@ -49,6 +49,7 @@ use golem_worker_executor_base::services::shard_manager::ShardManagerService;
 use golem_worker_executor_base::services::worker::WorkerService;
 use golem_worker_executor_base::services::worker_activator::WorkerActivator;
 use golem_worker_executor_base::services::worker_event::WorkerEventService;
+use golem_worker_executor_base::services::worker_file_system::WorkerFileSystemService;
 use golem_worker_executor_base::services::{All, HasAll};
 use golem_worker_executor_base::wasi_host::create_linker;
 use golem_worker_executor_base::workerctx::{
@@ -276,6 +277,13 @@ impl TestContext {
 }
 
 pub async fn start(context: &TestContext) -> anyhow::Result<TestWorkerExecutor> {
+    start_customized(context, |_| {}).await
+}
+
+pub async fn start_customized(
+    context: &TestContext,
+    customize: impl FnOnce(&mut GolemConfig),
+) -> anyhow::Result<TestWorkerExecutor> {
     let redis = BASE_DEPS.redis();
     let redis_monitor = BASE_DEPS.redis_monitor();
     redis.assert_valid();
@@ -283,7 +291,7 @@ pub async fn start(context: &TestContext) -> anyhow::Result<TestWorkerExecutor>
     println!("Using Redis on port {}", redis.public_port());
 
     let prometheus = golem_worker_executor_base::metrics::register_all();
-    let config = GolemConfig {
+    let mut config = GolemConfig {
         key_value_storage: KeyValueStorageConfig::Redis(RedisConfig {
             port: redis.public_port(),
             key_prefix: context.redis_prefix(),
@@ -309,6 +317,7 @@ pub async fn start(context: &TestContext) -> anyhow::Result<TestWorkerExecutor>
         },
         ..Default::default()
     };
+    customize(&mut config);
 
     let handle = Handle::current();
 
@@ -616,6 +625,14 @@ impl UpdateManagement for TestWorkerCtx {
             .on_worker_update_succeeded(target_version)
             .await
     }
+
+    async fn is_snapshot_due(&self) -> bool {
+        self.durable_ctx.is_snapshot_due().await
+    }
+
+    async fn on_snapshot_saved(&mut self, snapshot: &[u8]) -> Result<(), String> {
+        self.durable_ctx.on_snapshot_saved(snapshot).await
+    }
 }
 
 struct ServerBootstrap {}
@@ -632,6 +649,7 @@ impl WorkerCtx for TestWorkerCtx {
         worker_enumeration_service: Arc<dyn WorkerEnumerationService + Send + Sync>,
         key_value_service: Arc<dyn KeyValueService + Send + Sync>,
         blob_store_service: Arc<dyn BlobStoreService + Send + Sync>,
+        worker_file_system_service: Arc<dyn WorkerFileSystemService + Send + Sync>,
         event_service: Arc<dyn WorkerEventService + Send + Sync>,
         active_workers: Arc<ActiveWorkers<TestWorkerCtx>>,
         oplog_service: Arc<dyn OplogService + Send + Sync>,
@@ -641,6 +659,7 @@ impl WorkerCtx for TestWorkerCtx {
         recovery_management: Arc<dyn RecoveryManagement + Send + Sync>,
         rpc: Arc<dyn Rpc + Send + Sync>,
         worker_proxy: Arc<dyn WorkerProxy + Send + Sync>,
+        component_service: Arc<dyn ComponentService + Send + Sync>,
         _extra_deps: Self::ExtraDeps,
         config: Arc<GolemConfig>,
         worker_config: WorkerConfig,
@@ -654,6 +673,7 @@ impl WorkerCtx for TestWorkerCtx {
             worker_enumeration_service,
             key_value_service,
             blob_store_service,
+            worker_file_system_service,
             event_service,
             active_workers,
             oplog_service,
@@ -663,6 +683,7 @@ impl WorkerCtx for TestWorkerCtx {
             recovery_management,
             rpc,
             worker_proxy,
+            component_service,
             config,
             worker_config,
             execution_status,
@@ -746,6 +767,7 @@ impl Bootstrap<TestWorkerCtx> for ServerBootstrap {
         shard_service: Arc<dyn ShardService + Send + Sync>,
         key_value_service: Arc<dyn KeyValueService + Send + Sync>,
         blob_store_service: Arc<dyn BlobStoreService + Send + Sync>,
+        worker_file_system_service: Arc<dyn WorkerFileSystemService + Send + Sync>,
         worker_activator: Arc<dyn WorkerActivator + Send + Sync>,
         oplog_service: Arc<dyn OplogService + Send + Sync>,
         scheduler_service: Arc<dyn SchedulerService + Send + Sync>,
@@ -768,6 +790,7 @@ impl Bootstrap<TestWorkerCtx> for ServerBootstrap {
             shard_manager_service.clone(),
             key_value_service.clone(),
             blob_store_service.clone(),
+            worker_file_system_service.clone(),
             oplog_service.clone(),
             scheduler_service.clone(),
             worker_activator.clone(),
@@ -788,6 +811,7 @@ impl Bootstrap<TestWorkerCtx> for ServerBootstrap {
             scheduler_service.clone(),
             key_value_service.clone(),
             blob_store_service.clone(),
+            worker_file_system_service.clone(),
             rpc.clone(),
             worker_activator.clone(),
             worker_proxy.clone(),
@@ -812,6 +836,7 @@ impl Bootstrap<TestWorkerCtx> for ServerBootstrap {
             shard_service,
             key_value_service,
             blob_store_service,
+            worker_file_system_service,
             oplog_service,
             recovery_management,
             rpc,
@@ -4,7 +4,7 @@ use std::sync::atomic::AtomicU8;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 
-use crate::common::{start, TestContext};
+use crate::common::{start, start_customized, TestContext};
 use assert2::{assert, check};
 use golem_common::model::WorkerStatus;
 use golem_test_framework::dsl::{stderr_event, stdout_event, worker_error_message, TestDsl};
@@ -1364,3 +1364,154 @@ async fn ip_address_resolve() {
     check!(result1.len() > 0);
     check!(result2.len() > 0);
 }
+
+#[tokio::test]
+#[tracing::instrument]
+async fn list_worker_directory() {
+    let context = TestContext::new();
+    let executor = start(&context).await.unwrap();
+
+    let component_id = executor.store_component("file-service").await;
+    let worker_id = executor
+        .start_worker(&component_id, "file-service-11")
+        .await;
+
+    let _ = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/write-file",
+            vec![
+                Value::String("/testfile.txt".to_string()),
+                Value::String("hello world".to_string()),
+            ],
+        )
+        .await
+        .unwrap();
+
+    let entries = executor.list_worker_directory(&worker_id, "/").await;
+    let invalid = executor.list_worker_directory(&worker_id, "/..").await;
+
+    drop(executor);
+
+    let entries = entries.unwrap();
+    let testfile = entries.iter().find(|entry| entry.name == "testfile.txt");
+    check!(testfile.is_some());
+    check!(!testfile.unwrap().is_directory);
+    check!(testfile.unwrap().size == 11);
+    check!(invalid.is_err());
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn list_suspended_worker_directory() {
+    let context = TestContext::new();
+    let executor = start_customized(&context, |config| {
+        config.suspend.suspend_after = Duration::from_secs(1);
+    })
+    .await
+    .unwrap();
+
+    let component_id = executor.store_component("clock-service").await;
+    let worker_id = executor
+        .start_worker(&component_id, "list-suspended-worker-directory-1")
+        .await;
+
+    let executor_clone = executor.clone();
+    let worker_id_clone = worker_id.clone();
+    let fiber = spawn(async move {
+        executor_clone
+            .invoke_and_await(&worker_id_clone, "golem:it/api/sleep", vec![Value::U64(10)])
+            .await
+    });
+
+    tokio::time::sleep(Duration::from_secs(3)).await;
+
+    let before = executor.get_worker_metadata(&worker_id).await.unwrap();
+    let entries = executor.list_worker_directory(&worker_id, "/").await;
+    let after = executor.get_worker_metadata(&worker_id).await.unwrap();
+
+    let _ = fiber.await;
+    drop(executor);
+
+    // Listing the files of the suspended worker does not resume it
+    check!(before.last_known_status.status == WorkerStatus::Suspended);
+    check!(entries.is_ok());
+    check!(after.last_known_status.status == WorkerStatus::Suspended);
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn read_only_initial_files_cannot_be_modified() {
+    let context = TestContext::new();
+    let executor = start(&context).await.unwrap();
+
+    let component_id = executor.store_component("file-service").await;
+    let initial_files =
+        std::path::Path::new("data/components").join(format!("{component_id}-0.files"));
+    std::fs::create_dir_all(initial_files.join("read-only/dir")).unwrap();
+    std::fs::write(initial_files.join("read-only/dir/config.txt"), "original").unwrap();
+
+    let worker_id = executor
+        .start_worker(&component_id, "read-only-initial-files-1")
+        .await;
+
+    let mut attempts = Vec::new();
+    for (function, params) in [
+        (
+            "golem:it/api/write-file",
+            vec!["/dir/config.txt".to_string(), "changed".to_string()],
+        ),
+        (
+            "golem:it/api/write-file-direct",
+            vec!["dir/config.txt".to_string(), "changed".to_string()],
+        ),
+        (
+            "golem:it/api/delete-file",
+            vec!["/dir/config.txt".to_string()],
+        ),
+        (
+            "golem:it/api/rename-file",
+            vec!["/dir/config.txt".to_string(), "/moved.txt".to_string()],
+        ),
+        (
+            "golem:it/api/rename-file",
+            vec!["/dir".to_string(), "/moved".to_string()],
+        ),
+        (
+            "golem:it/api/create-link",
+            vec!["/dir/config.txt".to_string(), "/link.txt".to_string()],
+        ),
+    ] {
+        let result = executor
+            .invoke_and_await(
+                &worker_id,
+                function,
+                params.into_iter().map(Value::String).collect(),
+            )
+            .await
+            .unwrap();
+        attempts.push(result);
+    }
+
+    let content = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api/read-file",
+            vec![Value::String("/dir/config.txt".to_string())],
+        )
+        .await
+        .unwrap();
+
+    drop(executor);
+    std::fs::remove_dir_all(&initial_files).unwrap();
+
+    for attempt in attempts {
+        check!(matches!(attempt.as_slice(), [Value::Result(Err(_))]));
+    }
+    check!(
+        content
+            == vec![Value::Result(Ok(Some(Box::new(Value::String(
+                "original".to_string()
+            )))))]
+    );
+}
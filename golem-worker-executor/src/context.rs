@@ -34,6 +34,7 @@ use golem_worker_executor_base::model::{
 };
 use golem_worker_executor_base::services::active_workers::ActiveWorkers;
 use golem_worker_executor_base::services::blob_store::BlobStoreService;
+use golem_worker_executor_base::services::component::ComponentService;
 use golem_worker_executor_base::services::events::Events;
 use golem_worker_executor_base::services::golem_config::GolemConfig;
 use golem_worker_executor_base::services::invocation_queue::InvocationQueue;
@@ -45,6 +46,7 @@ use golem_worker_executor_base::services::rpc::Rpc;
 use golem_worker_executor_base::services::scheduler::SchedulerService;
 use golem_worker_executor_base::services::worker::WorkerService;
 use golem_worker_executor_base::services::worker_event::WorkerEventService;
+use golem_worker_executor_base::services::worker_file_system::WorkerFileSystemService;
 use golem_worker_executor_base::services::worker_proxy::WorkerProxy;
 use golem_worker_executor_base::services::{worker_enumeration, HasAll};
 use golem_worker_executor_base::workerctx::{
@@ -288,6 +290,14 @@ impl UpdateManagement for Context {
             .on_worker_update_succeeded(target_version)
             .await
     }
+
+    async fn is_snapshot_due(&self) -> bool {
+        self.durable_ctx.is_snapshot_due().await
+    }
+
+    async fn on_snapshot_saved(&mut self, snapshot: &[u8]) -> Result<(), String> {
+        self.durable_ctx.on_snapshot_saved(snapshot).await
+    }
 }
 
 #[async_trait]
@@ -304,6 +314,7 @@ impl WorkerCtx for Context {
         >,
         key_value_service: Arc<dyn KeyValueService + Send + Sync>,
         blob_store_service: Arc<dyn BlobStoreService + Send + Sync>,
+        worker_file_system_service: Arc<dyn WorkerFileSystemService + Send + Sync>,
         event_service: Arc<dyn WorkerEventService + Send + Sync>,
         active_workers: Arc<ActiveWorkers<Context>>,
         oplog_service: Arc<dyn OplogService + Send + Sync>,
@@ -313,6 +324,7 @@ impl WorkerCtx for Context {
         recovery_management: Arc<dyn RecoveryManagement + Send + Sync>,
         rpc: Arc<dyn Rpc + Send + Sync>,
         worker_proxy: Arc<dyn WorkerProxy + Send + Sync>,
+        component_service: Arc<dyn ComponentService + Send + Sync>,
         _extra_deps: Self::ExtraDeps,
         config: Arc<GolemConfig>,
         worker_config: WorkerConfig,
@@ -326,6 +338,7 @@ impl WorkerCtx for Context {
             worker_enumeration_service,
             key_value_service,
             blob_store_service,
+            worker_file_system_service,
             event_service,
             active_workers,
             oplog_service,
@@ -335,6 +348,7 @@ impl WorkerCtx for Context {
             recovery_management,
             rpc,
             worker_proxy,
+            component_service,
             config,
             worker_config,
             execution_status,
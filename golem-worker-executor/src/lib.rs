@@ -38,6 +38,7 @@ use golem_worker_executor_base::services::worker_activator::WorkerActivator;
 use golem_worker_executor_base::services::worker_enumeration::{
     RunningWorkerEnumerationService, WorkerEnumerationService,
 };
+use golem_worker_executor_base::services::worker_file_system::WorkerFileSystemService;
 use golem_worker_executor_base::services::worker_proxy::WorkerProxy;
 use golem_worker_executor_base::services::All;
 use golem_worker_executor_base::wasi_host::create_linker;
@@ -75,6 +76,7 @@ impl Bootstrap<Context> for ServerBootstrap {
         shard_service: Arc<dyn ShardService + Send + Sync>,
         key_value_service: Arc<dyn KeyValueService + Send + Sync>,
         blob_store_service: Arc<dyn BlobStoreService + Send + Sync>,
+        worker_file_system_service: Arc<dyn WorkerFileSystemService + Send + Sync>,
         worker_activator: Arc<dyn WorkerActivator + Send + Sync>,
         oplog_service: Arc<dyn OplogService + Send + Sync>,
         scheduler_service: Arc<dyn SchedulerService + Send + Sync>,
@@ -99,6 +101,7 @@ impl Bootstrap<Context> for ServerBootstrap {
             shard_manager_service.clone(),
             key_value_service.clone(),
             blob_store_service.clone(),
+            worker_file_system_service.clone(),
             oplog_service.clone(),
             scheduler_service.clone(),
             worker_activator.clone(),
@@ -119,6 +122,7 @@ impl Bootstrap<Context> for ServerBootstrap {
             scheduler_service.clone(),
             key_value_service.clone(),
             blob_store_service.clone(),
+            worker_file_system_service.clone(),
             rpc.clone(),
             worker_activator.clone(),
             worker_proxy.clone(),
@@ -143,6 +147,7 @@ impl Bootstrap<Context> for ServerBootstrap {
             shard_service,
             key_value_service,
             blob_store_service,
+            worker_file_system_service,
             oplog_service,
             recovery_management,
             rpc,
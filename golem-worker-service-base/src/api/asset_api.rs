@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use poem::Body;
+use poem_openapi::param::Query;
+use poem_openapi::payload::{Binary, Json};
+use poem_openapi::*;
+use tracing::{error, info};
+
+use golem_service_base::api_tags::ApiTags;
+
+use crate::api::ApiEndpointError;
+use crate::api_definition::ApiDefinitionId;
+use crate::http::RequestBody;
+use crate::service::asset_store::{api_asset_path, content_type, AssetStore, AssetUploadLimits};
+
+// Uploads the assets served by the file server routes of api definitions
+pub struct AssetApi {
+    asset_store: Arc<dyn AssetStore + Sync + Send>,
+    limits: AssetUploadLimits,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiAsset {
+    pub api_definition_id: ApiDefinitionId,
+    pub path: String,
+    pub content_type: String,
+    pub size: u64,
+}
+
+#[OpenApi(prefix_path = "/v1/api/assets", tag = ApiTags::ApiAsset)]
+impl AssetApi {
+    pub fn new(asset_store: Arc<dyn AssetStore + Sync + Send>, limits: AssetUploadLimits) -> Self {
+        Self {
+            asset_store,
+            limits,
+        }
+    }
+
+    #[oai(path = "/", method = "put", operation_id = "upload_asset")]
+    async fn upload(
+        &self,
+        #[oai(name = "api-definition-id")] api_definition_id: Query<ApiDefinitionId>,
+        path: Query<String>,
+        content: Binary<Body>,
+    ) -> Result<Json<ApiAsset>, ApiEndpointError> {
+        let api_definition_id = api_definition_id.0;
+        let path = path.0;
+
+        info!(
+            "Upload asset - api definition: {}, path: {}",
+            api_definition_id, path
+        );
+
+        let asset_path = api_asset_path(&api_definition_id, &path).ok_or_else(|| {
+            ApiEndpointError::bad_request(format!(
+                "Invalid asset path {}, it has to be relative and cannot refer to parent directories",
+                path
+            ))
+        })?;
+
+        let content = RequestBody::read_body(content.0, self.limits.max_asset_size)
+            .await
+            .map_err(ApiEndpointError::bad_request)?;
+        let size = content.len() as u64;
+
+        self.asset_store
+            .put(&asset_path, content.into())
+            .await
+            .map_err(|err| {
+                error!("Upload asset: {} - error: {}", asset_path, err);
+                ApiEndpointError::internal(err)
+            })?;
+
+        Ok(Json(ApiAsset {
+            api_definition_id,
+            content_type: content_type(&path),
+            path,
+            size,
+        }))
+    }
+}
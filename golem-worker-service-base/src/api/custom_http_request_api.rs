@@ -1,18 +1,35 @@
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::api_definition::http::HttpApiDefinition;
+use crate::api_definition::http::{HttpApiDefinition, StreamBinding, StreamProtocol, StreamSource};
+use crate::api_definition::ApiDefinitionId;
 use crate::evaluator::{DefaultEvaluator, Evaluator, WorkerMetadataFetcher};
 use async_trait::async_trait;
-use hyper::header::HOST;
+use futures::{future, StreamExt};
+use golem_service_base::model::WorkerId;
+use hyper::header::{HOST, RETRY_AFTER, WWW_AUTHENTICATE};
 use poem::http::StatusCode;
-use poem::{Body, Endpoint, Request, Response};
+use poem::web::sse::{Event, SSE};
+use poem::web::websocket::{Message, WebSocket};
+use poem::{Body, Endpoint, FromRequest, IntoResponse, Request, Response};
+use serde_json::Value;
 use tracing::{error, info};
 
-use crate::http::{ApiInputPath, InputHttpRequest};
+use crate::http::cors;
+use crate::http::security::AuthError;
+use crate::http::{ApiInputPath, InputHttpRequest, RequestBody, RequestBodyLimits};
 use crate::service::api_definition_lookup::ApiDefinitionLookup;
+use crate::service::asset_store::{api_asset_path, content_type, AssetStore};
+use crate::service::jwks::JwksProvider;
+use crate::service::rate_limit::{RateLimitDecision, RateLimiter};
+use crate::service::worker::{proxy_worker_connection_with, ConnectWorkerStream};
 
-use crate::worker_binding::WorkerBindingResolver;
-use crate::worker_bridge_execution::WorkerRequestExecutor;
+use crate::worker_binding::{
+    ResolvedBinding, ResolvedRateLimit, ResolvedStaticBinding, ResolvedWorkerBinding,
+    WorkerBindingResolutionError,
+};
+use crate::worker_bridge_execution::to_response::StreamMessage;
+use crate::worker_bridge_execution::{WorkerOutputConnector, WorkerRequestExecutor};
 
 // Executes custom request with the help of worker_request_executor and definition_service
 // This is a common API projects can make use of, similar to healthcheck service
@@ -22,6 +39,11 @@ pub struct CustomHttpRequestApi {
     pub worker_metadata_fetcher: Arc<dyn WorkerMetadataFetcher + Sync + Send>,
     pub api_definition_lookup_service:
         Arc<dyn ApiDefinitionLookup<InputHttpRequest, HttpApiDefinition> + Sync + Send>,
+    pub rate_limiter: Arc<dyn RateLimiter + Sync + Send>,
+    pub worker_output_connector: Arc<dyn WorkerOutputConnector + Sync + Send>,
+    pub asset_store: Arc<dyn AssetStore + Sync + Send>,
+    pub request_body_limits: RequestBodyLimits,
+    pub jwks_provider: Arc<dyn JwksProvider + Sync + Send>,
 }
 
 impl CustomHttpRequestApi {
@@ -31,6 +53,11 @@ impl CustomHttpRequestApi {
         api_definition_lookup_service: Arc<
             dyn ApiDefinitionLookup<InputHttpRequest, HttpApiDefinition> + Sync + Send,
         >,
+        rate_limiter: Arc<dyn RateLimiter + Sync + Send>,
+        worker_output_connector: Arc<dyn WorkerOutputConnector + Sync + Send>,
+        asset_store: Arc<dyn AssetStore + Sync + Send>,
+        request_body_limits: RequestBodyLimits,
+        jwks_provider: Arc<dyn JwksProvider + Sync + Send>,
     ) -> Self {
         let evaluator = Arc::new(DefaultEvaluator::from_worker_request_executor(
             worker_request_executor_service.clone(),
@@ -40,13 +67,22 @@ impl CustomHttpRequestApi {
             evaluator,
             worker_metadata_fetcher,
             api_definition_lookup_service,
+            rate_limiter,
+            worker_output_connector,
+            asset_store,
+            request_body_limits,
+            jwks_provider,
         }
     }
 
     pub async fn execute(&self, request: Request) -> Response {
-        let (req_parts, body) = request.into_parts();
-        let headers = req_parts.headers;
-        let uri = req_parts.uri;
+        let (request, body) = request.split();
+        let client_ip = request
+            .remote_addr()
+            .as_socket_addr()
+            .map(|addr| addr.ip().to_string());
+        let headers = request.headers().clone();
+        let uri = request.uri().clone();
 
         let host = match headers.get(HOST).and_then(|h| h.to_str().ok()) {
             Some(host) => host.to_string(),
@@ -59,28 +95,16 @@ impl CustomHttpRequestApi {
 
         info!("API request host: {}", host);
 
-        let json_request_body: serde_json::Value = if body.is_empty() {
-            serde_json::Value::Null
-        } else {
-            match body.into_json().await {
-                Ok(json_request_body) => json_request_body,
-                Err(err) => {
-                    error!("API request host: {} - error: {}", host, err);
-                    return Response::builder()
-                        .status(StatusCode::BAD_REQUEST)
-                        .body(Body::from_string("Request body parse error".to_string()));
-                }
-            }
-        };
-
-        let api_request = InputHttpRequest {
+        // The body is only read once the route is known and the caller is authenticated and
+        // within the rate limit, until then the request is resolved without it
+        let mut api_request = InputHttpRequest {
             input_path: ApiInputPath {
                 base_path: uri.path().to_string(),
                 query_path: uri.query().map(|x| x.to_string()),
             },
             headers,
-            req_method: req_parts.method,
-            req_body: json_request_body,
+            req_method: request.method().clone(),
+            req_body: RequestBody::Json(Value::Null),
         };
 
         let api_definition = match self
@@ -97,14 +121,159 @@ impl CustomHttpRequestApi {
             }
         };
 
-        match api_request.resolve(&api_definition).await {
-            Ok(resolved_worker_request) => {
-                resolved_worker_request
-                    .execute_with::<poem::Response>(&self.evaluator, &self.worker_metadata_fetcher)
-                    .await
+        if let Some(preflight) = cors::preflight(&api_definition, &api_request) {
+            return match preflight {
+                Ok(headers) => {
+                    let mut response = Response::builder().status(StatusCode::NO_CONTENT).finish();
+                    response.headers_mut().extend(headers);
+                    response
+                }
+                Err(err) => {
+                    error!(
+                        "API request id: {} - preflight error: {}",
+                        &api_definition.id, err
+                    );
+
+                    Response::builder()
+                        .status(StatusCode::FORBIDDEN)
+                        .body(Body::from_string(err))
+                }
+            };
+        }
+
+        let cors_headers = api_definition
+            .cors
+            .as_ref()
+            .map(|policy| cors::response_headers(policy, &api_request.headers))
+            .unwrap_or_default();
+
+        let mut response = match self
+            .resolve(
+                &api_definition,
+                &mut api_request,
+                &request,
+                body,
+                client_ip.as_deref(),
+            )
+            .await
+        {
+            Ok(resolved_binding) => match resolved_binding {
+                ResolvedBinding::Worker(binding) => match binding.stream.clone() {
+                    Some(stream) => {
+                        self.stream(&request, binding, stream, client_ip.as_deref())
+                            .await
+                    }
+                    None => {
+                        binding
+                            .execute_with::<poem::Response>(
+                                &self.evaluator,
+                                &self.worker_metadata_fetcher,
+                            )
+                            .await
+                    }
+                },
+                ResolvedBinding::Static(binding) => binding.execute::<poem::Response>().await,
+                ResolvedBinding::FileServer(binding) => {
+                    self.serve_file(&api_definition.id, &binding).await
+                }
+            },
+            Err(response) => response,
+        };
+
+        response.headers_mut().extend(cors_headers);
+        response
+    }
+
+    // Resolves the binding of the request, returning the response to send back if it cannot
+    // be resolved. Callers of routes limited by their IP address are limited before they are
+    // authenticated, and failed authentications of routes limited by a key count against the
+    // IP address of the caller, so credentials cannot be guessed at an unlimited rate
+    async fn resolve(
+        &self,
+        api_definition: &HttpApiDefinition,
+        api_request: &mut InputHttpRequest,
+        request: &Request,
+        body: Body,
+        client_ip: Option<&str>,
+    ) -> Result<ResolvedBinding, Response> {
+        let route = api_request
+            .route(api_definition)
+            .map_err(|err| self.resolution_error_response(api_definition, err))?;
+
+        // Bucket of the caller's IP address, None if the route is not rate limited
+        let ip_rate_limit = route.rate_limit.as_ref().map(|rate_limit| {
+            ResolvedRateLimit::new(api_definition, &route.route, rate_limit, None)
+        });
+        let limited_by_key = |rate_limit: &&ResolvedRateLimit| rate_limit.limit.key.is_some();
+
+        if let Some(rate_limit) = ip_rate_limit.as_ref().filter(|r| !limited_by_key(r)) {
+            if let Some(response) = self.rate_limit(rate_limit, client_ip).await {
+                return Err(response);
             }
+        }
+
+        let auth_claims = match api_request
+            .authenticate_caller(api_definition, &route, self.jwks_provider.as_ref())
+            .await
+        {
+            Ok(auth_claims) => auth_claims,
+            Err(err) => {
+                if let Some(rate_limit) = ip_rate_limit.as_ref().filter(limited_by_key) {
+                    if let Some(response) = self.rate_limit(rate_limit, client_ip).await {
+                        return Err(response);
+                    }
+                }
+                return Err(self.resolution_error_response(api_definition, err));
+            }
+        };
 
-            Err(msg) => {
+        api_request.req_body =
+            match RequestBody::from_request(request, body, &self.request_body_limits).await {
+                Ok(request_body) => request_body,
+                Err(err) if err.is_too_large() => {
+                    error!("API request id: {} - error: {}", &api_definition.id, err);
+                    return Err(Response::builder()
+                        .status(StatusCode::PAYLOAD_TOO_LARGE)
+                        .body(Body::from_string(err.to_string())));
+                }
+                Err(err) => {
+                    error!("API request id: {} - error: {}", &api_definition.id, err);
+                    return Err(Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from_string("Request body parse error".to_string())));
+                }
+            };
+
+        let resolved_binding = api_request
+            .resolve_route(api_definition, &route, auth_claims.as_ref())
+            .await
+            .map_err(|err| self.resolution_error_response(api_definition, err))?;
+
+        // Routes limited by a key are only limited once the key is evaluated
+        if let Some(rate_limit) = resolved_binding.rate_limit().filter(limited_by_key) {
+            if let Some(response) = self.rate_limit(rate_limit, client_ip).await {
+                return Err(response);
+            }
+        }
+
+        Ok(resolved_binding)
+    }
+
+    fn resolution_error_response(
+        &self,
+        api_definition: &HttpApiDefinition,
+        error: WorkerBindingResolutionError,
+    ) -> Response {
+        match error {
+            WorkerBindingResolutionError::Auth(err) => {
+                error!(
+                    "API request id: {} - auth error: {}",
+                    &api_definition.id, err
+                );
+
+                auth_error_response(err)
+            }
+            msg => {
                 error!(
                     "API request id: {} - request error: {}",
                     &api_definition.id, msg
@@ -116,6 +285,324 @@ impl CustomHttpRequestApi {
             }
         }
     }
+
+    // Serves the asset at the path the response mapping evaluates to, among the assets uploaded
+    // for the api definition
+    async fn serve_file(
+        &self,
+        api_definition_id: &ApiDefinitionId,
+        binding: &ResolvedStaticBinding,
+    ) -> Response {
+        let path = match binding.asset_path().await {
+            Ok(path) => path,
+            Err(err) => {
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::from_string(err));
+            }
+        };
+
+        let asset_path = match api_asset_path(api_definition_id, &path) {
+            Some(asset_path) => asset_path,
+            None => {
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::from_string(format!("Invalid asset path {}", path)));
+            }
+        };
+
+        match self.asset_store.get(&asset_path).await {
+            Ok(Some(content)) => Response::builder()
+                .status(StatusCode::OK)
+                .content_type(content_type(&path))
+                .body(Body::from_bytes(content)),
+            Ok(None) => Response::builder().status(StatusCode::NOT_FOUND).finish(),
+            Err(err) => {
+                error!("API request asset: {} - error: {}", path, err);
+                Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from_string("Internal error".to_string()))
+            }
+        }
+    }
+
+    // Keeps the connection open, evaluating the binding for each message of a WebSocket, or
+    // periodically for SSE, and streaming either the results or the worker output back
+    async fn stream(
+        &self,
+        request: &Request,
+        mut binding: ResolvedWorkerBinding,
+        stream: StreamBinding,
+        client_ip: Option<&str>,
+    ) -> Response {
+        let worker_id = match binding.worker_detail.worker_id() {
+            Ok(worker_id) => worker_id,
+            Err(err) => {
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::from_string(err));
+            }
+        };
+
+        // Every message is a separate invocation, which must not be deduplicated
+        binding.worker_detail.idempotency_key = None;
+
+        let worker_stream = match stream.source {
+            StreamSource::Output => match self.worker_output_connector.connect(&worker_id).await {
+                Ok(worker_stream) => Some(worker_stream),
+                Err(err) => {
+                    error!("API request worker: {} - connect error: {}", worker_id, err);
+                    return Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::from_string("Internal error".to_string()));
+                }
+            },
+            StreamSource::Results => None,
+        };
+
+        match stream.protocol {
+            StreamProtocol::WebSocket => {
+                self.websocket(request, worker_id, binding, stream.source, worker_stream)
+                    .await
+            }
+            StreamProtocol::Sse => self.sse(binding, &stream, worker_stream, client_ip),
+        }
+    }
+
+    async fn websocket(
+        &self,
+        request: &Request,
+        worker_id: WorkerId,
+        binding: ResolvedWorkerBinding,
+        source: StreamSource,
+        worker_stream: Option<ConnectWorkerStream>,
+    ) -> Response {
+        let websocket = match WebSocket::from_request_without_body(request).await {
+            Ok(websocket) => websocket,
+            Err(err) => return err.into_response(),
+        };
+
+        let evaluator = self.evaluator.clone();
+        let worker_metadata_fetcher = self.worker_metadata_fetcher.clone();
+
+        websocket
+            .on_upgrade(move |socket| async move {
+                let (sink, socket_stream) = socket.split();
+
+                // Only the results are sent back if the worker output is not streamed
+                let worker_stream = match worker_stream {
+                    Some(worker_stream) => worker_stream.left_stream(),
+                    None => futures::stream::pending().right_stream(),
+                };
+
+                let on_message = move |message| {
+                    let evaluator = evaluator.clone();
+                    let worker_metadata_fetcher = worker_metadata_fetcher.clone();
+                    let binding = binding.clone();
+
+                    async move {
+                        let body = match message_body(message) {
+                            Some(body) => body,
+                            None => return None,
+                        };
+
+                        let result = match binding.with_message(&body) {
+                            Ok(binding) => {
+                                binding
+                                    .execute_with::<StreamMessage>(
+                                        &evaluator,
+                                        &worker_metadata_fetcher,
+                                    )
+                                    .await
+                            }
+                            Err(err) => StreamMessage::Error(err),
+                        };
+
+                        match (source, result) {
+                            (StreamSource::Results, result)
+                            | (StreamSource::Output, result @ StreamMessage::Error(_)) => {
+                                Some(Message::Text(result.to_json().to_string()))
+                            }
+                            (StreamSource::Output, StreamMessage::Result(_)) => None,
+                        }
+                    }
+                };
+
+                let _ = proxy_worker_connection_with(
+                    worker_id,
+                    worker_stream,
+                    sink,
+                    socket_stream,
+                    PING_INTERVAL,
+                    PING_TIMEOUT,
+                    on_message,
+                )
+                .await;
+            })
+            .into_response()
+    }
+
+    // Results are polled on behalf of the client, so each poll takes a token from the bucket of
+    // the client like a request would, and the stream is closed once the client runs out of them
+    fn sse(
+        &self,
+        binding: ResolvedWorkerBinding,
+        stream: &StreamBinding,
+        worker_stream: Option<ConnectWorkerStream>,
+        client_ip: Option<&str>,
+    ) -> Response {
+        match worker_stream {
+            Some(worker_stream) => {
+                let events = worker_stream
+                    .take_while(|event| future::ready(event.is_ok()))
+                    .filter_map(|event| {
+                        future::ready(
+                            event
+                                .ok()
+                                .and_then(|event| serde_json::to_string(&event).ok())
+                                .map(Event::message),
+                        )
+                    });
+
+                SSE::new(events).keep_alive(PING_INTERVAL).into_response()
+            }
+            None => {
+                let evaluator = self.evaluator.clone();
+                let worker_metadata_fetcher = self.worker_metadata_fetcher.clone();
+                let rate_limiter = self.rate_limiter.clone();
+                let client_ip = client_ip.map(|client_ip| client_ip.to_string());
+                let interval =
+                    tokio::time::interval(Duration::from_secs(stream.poll_interval_seconds()));
+
+                let events = futures::stream::unfold(Some((interval, 0u64)), move |state| {
+                    let evaluator = evaluator.clone();
+                    let worker_metadata_fetcher = worker_metadata_fetcher.clone();
+                    let rate_limiter = rate_limiter.clone();
+                    let client_ip = client_ip.clone();
+                    let binding = binding.clone();
+
+                    async move {
+                        let (mut interval, poll) = state?;
+                        interval.tick().await;
+
+                        // The first poll is covered by the token taken when connecting, and the
+                        // stream ends with an error once a poll is limited
+                        if let Some(rate_limit) = binding.rate_limit.as_ref().filter(|_| poll > 0) {
+                            if let Some(retry_after) = acquire_token(
+                                rate_limiter.as_ref(),
+                                rate_limit,
+                                client_ip.as_deref(),
+                            )
+                            .await
+                            {
+                                let message = format!(
+                                    "Rate limited, retry after {} seconds",
+                                    retry_after.as_millis().div_ceil(1000)
+                                );
+                                return Some((sse_event(StreamMessage::Error(message)), None));
+                            }
+                        }
+
+                        let result = binding
+                            .execute_with::<StreamMessage>(&evaluator, &worker_metadata_fetcher)
+                            .await;
+
+                        Some((sse_event(result), Some((interval, poll + 1))))
+                    }
+                });
+
+                SSE::new(events).keep_alive(PING_INTERVAL).into_response()
+            }
+        }
+    }
+
+    // Takes a token from the bucket of the caller, returning the response to send back if
+    // the caller ran out of them
+    async fn rate_limit(
+        &self,
+        rate_limit: &ResolvedRateLimit,
+        client_ip: Option<&str>,
+    ) -> Option<Response> {
+        let retry_after = acquire_token(self.rate_limiter.as_ref(), rate_limit, client_ip).await?;
+        let retry_after_seconds = retry_after.as_millis().div_ceil(1000);
+
+        Some(
+            Response::builder()
+                .status(StatusCode::TOO_MANY_REQUESTS)
+                .header(RETRY_AFTER, retry_after_seconds.to_string())
+                .finish(),
+        )
+    }
+}
+
+// Takes a token from the bucket of the caller, returning how long to wait for the next one if
+// the caller ran out of them
+async fn acquire_token(
+    rate_limiter: &(dyn RateLimiter + Sync + Send),
+    rate_limit: &ResolvedRateLimit,
+    client_ip: Option<&str>,
+) -> Option<Duration> {
+    let caller = rate_limit
+        .caller
+        .as_deref()
+        .or(client_ip)
+        .unwrap_or("unknown");
+    let bucket = format!("{}:{}", rate_limit.route, caller);
+
+    match rate_limiter.acquire(&bucket, &rate_limit.limit).await {
+        Ok(RateLimitDecision::Allowed) => None,
+        Ok(RateLimitDecision::Limited { retry_after }) => {
+            info!("API request rate limited: {}", bucket);
+            Some(retry_after)
+        }
+        // Requests are let through rather than failed while the rate limit store is unavailable
+        Err(err) => {
+            error!("API request rate limit error: {}", err);
+            None
+        }
+    }
+}
+
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+const PING_TIMEOUT: Duration = Duration::from_secs(15);
+
+// Text messages holding JSON are passed to the worker as a JSON request body
+fn message_body(message: Message) -> Option<RequestBody> {
+    match message {
+        Message::Text(text) => Some(
+            serde_json::from_str(&text)
+                .map(RequestBody::Json)
+                .unwrap_or(RequestBody::Text(text)),
+        ),
+        Message::Binary(bytes) => Some(RequestBody::Binary(bytes)),
+        _ => None,
+    }
+}
+
+fn sse_event(message: StreamMessage) -> Event {
+    match message {
+        StreamMessage::Result(value) => Event::message(value.to_string()),
+        StreamMessage::Error(error) => Event::message(error).event_type("error"),
+    }
+}
+
+fn auth_error_response(error: AuthError) -> Response {
+    match error {
+        AuthError::Unauthorized { challenge, message } => {
+            let response = Response::builder().status(StatusCode::UNAUTHORIZED);
+            let response = match challenge {
+                Some(challenge) => response.header(WWW_AUTHENTICATE, challenge),
+                None => response,
+            };
+            response.body(Body::from_string(message))
+        }
+        AuthError::Forbidden(message) => Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Body::from_string(message)),
+        AuthError::InvalidConfiguration(_) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from_string("Internal error".to_string())),
+    }
 }
 
 #[async_trait]
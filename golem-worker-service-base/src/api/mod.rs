@@ -1,3 +1,4 @@
+pub use asset_api::*;
 pub use common::*;
 pub use custom_http_request_api::*;
 pub use error::*;
@@ -5,6 +6,7 @@ pub use healthcheck::*;
 pub use register_api_definition_api::*;
 
 // Components and request data that can be reused for implementing server API endpoints
+mod asset_api;
 mod common;
 mod custom_http_request_api;
 mod error;
@@ -6,11 +6,16 @@ use serde::{Deserialize, Serialize};
 use golem_api_grpc::proto::golem::apidefinition as grpc_apidefinition;
 use golem_common::model::ComponentId;
 
-use crate::api_definition::http::MethodPattern;
+use crate::api_definition::http::{
+    ApiKey, ApiKeyLocation, ApiKeySecurityScheme, BasicAuthUser, BasicSecurityScheme, CorsPolicy,
+    JwtBearerSecurityScheme, MethodPattern, RouteSecurity, SecurityScheme, SecuritySchemeKind,
+    StreamBinding, StreamProtocol, StreamSource,
+};
 use crate::api_definition::{ApiDefinitionId, ApiSite, ApiVersion};
 use crate::expression;
 use crate::expression::Expr;
 use crate::parser::ParseError;
+use crate::worker_binding::{RouteBinding, StaticBinding};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
 #[serde(rename_all = "camelCase")]
@@ -32,24 +37,82 @@ pub struct HttpApiDefinition {
     pub version: ApiVersion,
     pub routes: Vec<Route>,
     #[serde(default)]
+    #[oai(default)]
+    pub security_schemes: Vec<SecurityScheme>,
+    pub cors: Option<CorsPolicy>,
+    #[serde(default)]
+    #[oai(default)]
+    pub functions: Vec<FunctionDefinition>,
+    #[serde(default)]
     pub draft: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
 pub struct Route {
     pub method: MethodPattern,
     pub path: String,
     pub binding: GolemWorkerBinding,
+    pub security: Option<RouteSecurity>,
+    pub rate_limit: Option<RateLimit>,
+    pub stream: Option<StreamBinding>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct RateLimit {
+    pub requests: u64,
+    pub period_seconds: u64,
+    pub burst: Option<u64>,
+    pub key: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
 #[serde(rename_all = "camelCase")]
 #[oai(rename_all = "camelCase")]
 pub struct GolemWorkerBinding {
-    pub component_id: ComponentId,
-    pub worker_name: String,
+    // Defaults to a worker binding, the only one with a component and a worker name
+    pub binding_type: Option<BindingType>,
+    pub component_id: Option<ComponentId>,
+    pub worker_name: Option<String>,
     pub idempotency_key: Option<String>,
     pub response: String,
+    #[serde(default)]
+    #[oai(default)]
+    pub workers: Vec<WorkerReference>,
+}
+
+// Another worker of a worker binding, whose functions are called as `<name>.<function>(..)`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct WorkerReference {
+    pub name: String,
+    pub component_id: ComponentId,
+    pub worker_name: String,
+}
+
+// A function callable from the expressions of every route, with its body as an expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct FunctionDefinition {
+    pub name: String,
+    #[serde(default)]
+    #[oai(default)]
+    pub params: Vec<String>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "kebab-case")]
+#[oai(rename_all = "kebab-case")]
+pub enum BindingType {
+    WitWorker,
+    Static,
+    FileServer,
 }
 
 impl<N> From<crate::api_definition::ApiDeployment<N>> for ApiDeployment {
@@ -74,10 +137,19 @@ impl TryFrom<crate::api_definition::http::HttpApiDefinition> for HttpApiDefiniti
             routes.push(v);
         }
 
+        let functions = value
+            .functions
+            .into_iter()
+            .map(FunctionDefinition::try_from)
+            .collect::<Result<Vec<FunctionDefinition>, String>>()?;
+
         Ok(Self {
             id: value.id,
             version: value.version,
             routes,
+            security_schemes: value.security_schemes,
+            cors: value.cors,
+            functions,
             draft: value.draft,
         })
     }
@@ -94,10 +166,23 @@ impl TryInto<crate::api_definition::http::HttpApiDefinition> for HttpApiDefiniti
             routes.push(v);
         }
 
+        let functions = self
+            .functions
+            .into_iter()
+            .map(|function| function.try_into())
+            .collect::<Result<Vec<crate::api_definition::http::FunctionDefinition>, String>>()?;
+
         Ok(crate::api_definition::http::HttpApiDefinition {
             id: self.id,
             version: self.version,
             routes,
+            security_schemes: self
+                .security_schemes
+                .into_iter()
+                .map(SecurityScheme::with_hashed_secrets)
+                .collect(),
+            cors: self.cors,
+            functions,
             draft: self.draft,
         })
     }
@@ -109,11 +194,15 @@ impl TryFrom<crate::api_definition::http::Route> for Route {
     fn try_from(value: crate::api_definition::http::Route) -> Result<Self, Self::Error> {
         let path = value.path.to_string();
         let binding = GolemWorkerBinding::try_from(value.binding)?;
+        let rate_limit = value.rate_limit.map(RateLimit::try_from).transpose()?;
 
         Ok(Self {
             method: value.method,
             path,
             binding,
+            security: value.security,
+            rate_limit,
+            stream: value.stream,
         })
     }
 }
@@ -125,66 +214,214 @@ impl TryInto<crate::api_definition::http::Route> for Route {
         let path = crate::api_definition::http::AllPathPatterns::parse(self.path.as_str())
             .map_err(|e| e.to_string())?;
         let binding = self.binding.try_into()?;
+        let rate_limit = self.rate_limit.map(|limit| limit.try_into()).transpose()?;
 
         Ok(crate::api_definition::http::Route {
             method: self.method,
             path,
             binding,
+            security: self.security,
+            rate_limit,
+            stream: self.stream,
         })
     }
 }
 
-impl TryFrom<crate::worker_binding::GolemWorkerBinding> for GolemWorkerBinding {
+impl TryFrom<crate::api_definition::http::RateLimit> for RateLimit {
     type Error = String;
 
-    fn try_from(value: crate::worker_binding::GolemWorkerBinding) -> Result<Self, Self::Error> {
-        let response: String =
-            expression::to_string(&value.response.0).map_err(|e| e.to_string())?;
-
-        let worker_id = expression::to_string(&value.worker_name).map_err(|e| e.to_string())?;
-
-        let idempotency_key = if let Some(key) = &value.idempotency_key {
+    fn try_from(value: crate::api_definition::http::RateLimit) -> Result<Self, Self::Error> {
+        let key = if let Some(key) = &value.key {
             Some(expression::to_string(key).map_err(|e| e.to_string())?)
         } else {
             None
         };
 
         Ok(Self {
-            component_id: value.component_id,
-            worker_name: worker_id,
-            idempotency_key,
-            response,
+            requests: value.requests,
+            period_seconds: value.period_seconds,
+            burst: value.burst,
+            key,
         })
     }
 }
 
-impl TryInto<crate::worker_binding::GolemWorkerBinding> for GolemWorkerBinding {
+impl TryInto<crate::api_definition::http::RateLimit> for RateLimit {
     type Error = String;
 
-    fn try_into(self) -> Result<crate::worker_binding::GolemWorkerBinding, Self::Error> {
-        let response: crate::worker_binding::ResponseMapping = {
-            let r = expression::from_string(self.response).map_err(|e| e.to_string())?;
-            crate::worker_binding::ResponseMapping(r)
-        };
-
-        let worker_name: Expr =
-            expression::from_string(self.worker_name).map_err(|e| e.to_string())?;
-
-        let idempotency_key = if let Some(key) = &self.idempotency_key {
+    fn try_into(self) -> Result<crate::api_definition::http::RateLimit, Self::Error> {
+        let key = if let Some(key) = &self.key {
             Some(expression::from_string(key).map_err(|e| e.to_string())?)
         } else {
             None
         };
 
-        Ok(crate::worker_binding::GolemWorkerBinding {
+        Ok(crate::api_definition::http::RateLimit {
+            requests: self.requests,
+            period_seconds: self.period_seconds,
+            burst: self.burst,
+            key,
+        })
+    }
+}
+
+impl TryFrom<RouteBinding> for GolemWorkerBinding {
+    type Error = String;
+
+    fn try_from(value: RouteBinding) -> Result<Self, Self::Error> {
+        let response: String =
+            expression::to_string(&value.response().0).map_err(|e| e.to_string())?;
+
+        match value {
+            RouteBinding::WitWorker(value) => {
+                let worker_id =
+                    expression::to_string(&value.worker_name).map_err(|e| e.to_string())?;
+
+                let idempotency_key = if let Some(key) = &value.idempotency_key {
+                    Some(expression::to_string(key).map_err(|e| e.to_string())?)
+                } else {
+                    None
+                };
+
+                let workers = value
+                    .workers
+                    .into_iter()
+                    .map(WorkerReference::try_from)
+                    .collect::<Result<Vec<WorkerReference>, String>>()?;
+
+                Ok(Self {
+                    binding_type: Some(BindingType::WitWorker),
+                    component_id: Some(value.component_id),
+                    worker_name: Some(worker_id),
+                    idempotency_key,
+                    response,
+                    workers,
+                })
+            }
+            RouteBinding::Static(_) => Ok(Self::without_worker(BindingType::Static, response)),
+            RouteBinding::FileServer(_) => {
+                Ok(Self::without_worker(BindingType::FileServer, response))
+            }
+        }
+    }
+}
+
+impl GolemWorkerBinding {
+    fn without_worker(binding_type: BindingType, response: String) -> Self {
+        Self {
+            binding_type: Some(binding_type),
+            component_id: None,
+            worker_name: None,
+            idempotency_key: None,
+            response,
+            workers: vec![],
+        }
+    }
+}
+
+impl TryFrom<crate::worker_binding::WorkerReference> for WorkerReference {
+    type Error = String;
+
+    fn try_from(value: crate::worker_binding::WorkerReference) -> Result<Self, Self::Error> {
+        let worker_name = expression::to_string(&value.worker_name).map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            name: value.name,
+            component_id: value.component_id,
+            worker_name,
+        })
+    }
+}
+
+impl TryInto<crate::worker_binding::WorkerReference> for WorkerReference {
+    type Error = String;
+
+    fn try_into(self) -> Result<crate::worker_binding::WorkerReference, Self::Error> {
+        let worker_name = expression::from_string(self.worker_name).map_err(|e| e.to_string())?;
+
+        Ok(crate::worker_binding::WorkerReference {
+            name: self.name,
             component_id: self.component_id,
             worker_name,
-            idempotency_key,
-            response,
         })
     }
 }
 
+impl TryFrom<crate::api_definition::http::FunctionDefinition> for FunctionDefinition {
+    type Error = String;
+
+    fn try_from(
+        value: crate::api_definition::http::FunctionDefinition,
+    ) -> Result<Self, Self::Error> {
+        let body = expression::to_string(&value.body).map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            name: value.name,
+            params: value.params,
+            body,
+        })
+    }
+}
+
+impl TryInto<crate::api_definition::http::FunctionDefinition> for FunctionDefinition {
+    type Error = String;
+
+    fn try_into(self) -> Result<crate::api_definition::http::FunctionDefinition, Self::Error> {
+        let body = expression::from_string(self.body).map_err(|e| e.to_string())?;
+
+        Ok(crate::api_definition::http::FunctionDefinition {
+            name: self.name,
+            params: self.params,
+            body,
+        })
+    }
+}
+
+impl TryInto<RouteBinding> for GolemWorkerBinding {
+    type Error = String;
+
+    fn try_into(self) -> Result<RouteBinding, Self::Error> {
+        let response: crate::worker_binding::ResponseMapping = {
+            let r = expression::from_string(self.response).map_err(|e| e.to_string())?;
+            crate::worker_binding::ResponseMapping(r)
+        };
+
+        match self.binding_type.unwrap_or(BindingType::WitWorker) {
+            BindingType::WitWorker => {
+                let component_id = self.component_id.ok_or("componentId is missing")?;
+
+                let worker_name: Expr =
+                    expression::from_string(self.worker_name.ok_or("workerName is missing")?)
+                        .map_err(|e| e.to_string())?;
+
+                let idempotency_key = if let Some(key) = &self.idempotency_key {
+                    Some(expression::from_string(key).map_err(|e| e.to_string())?)
+                } else {
+                    None
+                };
+
+                let workers = self
+                    .workers
+                    .into_iter()
+                    .map(|worker| worker.try_into())
+                    .collect::<Result<Vec<crate::worker_binding::WorkerReference>, String>>()?;
+
+                Ok(RouteBinding::WitWorker(
+                    crate::worker_binding::GolemWorkerBinding {
+                        component_id,
+                        worker_name,
+                        idempotency_key,
+                        response,
+                        workers,
+                    },
+                ))
+            }
+            BindingType::Static => Ok(RouteBinding::Static(StaticBinding { response })),
+            BindingType::FileServer => Ok(RouteBinding::FileServer(StaticBinding { response })),
+        }
+    }
+}
+
 impl TryFrom<crate::api_definition::http::HttpApiDefinition> for grpc_apidefinition::ApiDefinition {
     type Error = String;
 
@@ -197,9 +434,26 @@ impl TryFrom<crate::api_definition::http::HttpApiDefinition> for grpc_apidefinit
             .map(grpc_apidefinition::HttpRoute::try_from)
             .collect::<Result<Vec<grpc_apidefinition::HttpRoute>, String>>()?;
 
+        let security_schemes = value
+            .security_schemes
+            .into_iter()
+            .map(grpc_apidefinition::SecurityScheme::from)
+            .collect();
+
+        let functions = value
+            .functions
+            .into_iter()
+            .map(grpc_apidefinition::FunctionDefinition::try_from)
+            .collect::<Result<Vec<grpc_apidefinition::FunctionDefinition>, String>>()?;
+
         let id = value.id.0;
 
-        let definition = grpc_apidefinition::HttpApiDefinition { routes };
+        let definition = grpc_apidefinition::HttpApiDefinition {
+            routes,
+            security_schemes,
+            cors: value.cors.map(grpc_apidefinition::CorsPolicy::from),
+            functions,
+        };
 
         let result = grpc_apidefinition::ApiDefinition {
             id: Some(grpc_apidefinition::ApiDefinitionId { value: id }),
@@ -218,20 +472,39 @@ impl TryFrom<grpc_apidefinition::ApiDefinition> for crate::api_definition::http:
     type Error = String;
 
     fn try_from(value: grpc_apidefinition::ApiDefinition) -> Result<Self, Self::Error> {
-        let routes = match value.definition.ok_or("definition is missing")? {
-            grpc_apidefinition::api_definition::Definition::Http(http) => http
-                .routes
-                .into_iter()
-                .map(crate::api_definition::http::Route::try_from)
-                .collect::<Result<Vec<crate::api_definition::http::Route>, String>>()?,
+        let http = match value.definition.ok_or("definition is missing")? {
+            grpc_apidefinition::api_definition::Definition::Http(http) => http,
         };
 
+        let routes = http
+            .routes
+            .into_iter()
+            .map(crate::api_definition::http::Route::try_from)
+            .collect::<Result<Vec<crate::api_definition::http::Route>, String>>()?;
+
+        let security_schemes = http
+            .security_schemes
+            .into_iter()
+            .map(SecurityScheme::try_from)
+            .collect::<Result<Vec<SecurityScheme>, String>>()?;
+
+        let cors = http.cors.map(CorsPolicy::try_from).transpose()?;
+
+        let functions = http
+            .functions
+            .into_iter()
+            .map(crate::api_definition::http::FunctionDefinition::try_from)
+            .collect::<Result<Vec<crate::api_definition::http::FunctionDefinition>, String>>()?;
+
         let id = value.id.ok_or("Api Definition ID is missing")?;
 
         let result = crate::api_definition::http::HttpApiDefinition {
             id: crate::api_definition::ApiDefinitionId(id.value),
             version: crate::api_definition::ApiVersion(value.version),
             routes,
+            security_schemes,
+            cors,
+            functions,
             draft: value.draft,
         };
 
@@ -246,11 +519,18 @@ impl TryFrom<crate::api_definition::http::Route> for grpc_apidefinition::HttpRou
         let path = value.path.to_string();
         let binding = grpc_apidefinition::WorkerBinding::try_from(value.binding)?;
         let method: grpc_apidefinition::HttpMethod = value.method.into();
+        let rate_limit = value
+            .rate_limit
+            .map(grpc_apidefinition::RateLimit::try_from)
+            .transpose()?;
 
         let result = grpc_apidefinition::HttpRoute {
             method: method as i32,
             path,
             binding: Some(binding),
+            security: value.security.map(grpc_apidefinition::RouteSecurity::from),
+            rate_limit,
+            stream: value.stream.map(grpc_apidefinition::StreamBinding::from),
         };
 
         Ok(result)
@@ -283,42 +563,81 @@ impl TryFrom<grpc_apidefinition::HttpRoute> for crate::api_definition::http::Rou
 
         let method: MethodPattern = value.method.try_into()?;
 
+        let rate_limit = value
+            .rate_limit
+            .map(crate::api_definition::http::RateLimit::try_from)
+            .transpose()?;
+
+        let stream = value.stream.map(StreamBinding::try_from).transpose()?;
+
         let result = crate::api_definition::http::Route {
             method,
             path,
             binding,
+            security: value.security.map(RouteSecurity::from),
+            rate_limit,
+            stream,
         };
 
         Ok(result)
     }
 }
 
-impl TryFrom<crate::worker_binding::GolemWorkerBinding> for grpc_apidefinition::WorkerBinding {
+impl TryFrom<RouteBinding> for grpc_apidefinition::WorkerBinding {
     type Error = String;
 
-    fn try_from(value: crate::worker_binding::GolemWorkerBinding) -> Result<Self, Self::Error> {
-        let response: String = value.response.0.to_string();
-
-        let worker_id = expression::to_string(&value.worker_name).map_err(|e| e.to_string())?;
-
-        let idempotency_key = if let Some(key) = &value.idempotency_key {
-            Some(expression::to_string(key).map_err(|e| e.to_string())?)
-        } else {
-            None
-        };
-
-        let result = grpc_apidefinition::WorkerBinding {
-            component: Some(value.component_id.into()),
-            worker_id,
-            idempotency_key,
-            response,
+    fn try_from(value: RouteBinding) -> Result<Self, Self::Error> {
+        let response: String = value.response().0.to_string();
+
+        let result = match value {
+            RouteBinding::WitWorker(value) => {
+                let worker_id =
+                    expression::to_string(&value.worker_name).map_err(|e| e.to_string())?;
+
+                let idempotency_key = if let Some(key) = &value.idempotency_key {
+                    Some(expression::to_string(key).map_err(|e| e.to_string())?)
+                } else {
+                    None
+                };
+
+                let workers = value
+                    .workers
+                    .into_iter()
+                    .map(grpc_apidefinition::WorkerReference::try_from)
+                    .collect::<Result<Vec<grpc_apidefinition::WorkerReference>, String>>()?;
+
+                grpc_apidefinition::WorkerBinding {
+                    component: Some(value.component_id.into()),
+                    worker_id: Some(worker_id),
+                    idempotency_key,
+                    response,
+                    binding_type: Some(grpc_apidefinition::BindingType::WitWorker as i32),
+                    workers,
+                }
+            }
+            RouteBinding::Static(_) => grpc_apidefinition::WorkerBinding {
+                component: None,
+                worker_id: None,
+                idempotency_key: None,
+                response,
+                binding_type: Some(grpc_apidefinition::BindingType::Static as i32),
+                workers: vec![],
+            },
+            RouteBinding::FileServer(_) => grpc_apidefinition::WorkerBinding {
+                component: None,
+                worker_id: None,
+                idempotency_key: None,
+                response,
+                binding_type: Some(grpc_apidefinition::BindingType::FileServer as i32),
+                workers: vec![],
+            },
         };
 
         Ok(result)
     }
 }
 
-impl TryFrom<grpc_apidefinition::WorkerBinding> for crate::worker_binding::GolemWorkerBinding {
+impl TryFrom<grpc_apidefinition::WorkerBinding> for RouteBinding {
     type Error = String;
 
     fn try_from(value: grpc_apidefinition::WorkerBinding) -> Result<Self, Self::Error> {
@@ -330,27 +649,381 @@ impl TryFrom<grpc_apidefinition::WorkerBinding> for crate::worker_binding::Golem
             crate::worker_binding::ResponseMapping(r)
         };
 
+        let binding_type = match value.binding_type {
+            Some(binding_type) => grpc_apidefinition::BindingType::try_from(binding_type)
+                .map_err(|_| format!("Invalid binding type: {}", binding_type))?,
+            None => grpc_apidefinition::BindingType::WitWorker,
+        };
+
+        match binding_type {
+            grpc_apidefinition::BindingType::WitWorker => {
+                let worker_name = value
+                    .worker_id
+                    .ok_or("worker_id is missing")?
+                    .parse()
+                    .map_err(|e: ParseError| e.to_string())?;
+
+                let component_id = value.component.ok_or("component is missing")?.try_into()?;
+
+                let idempotency_key = if let Some(key) = &value.idempotency_key {
+                    Some(key.parse().map_err(|e: ParseError| e.to_string())?)
+                } else {
+                    None
+                };
+
+                let workers = value
+                    .workers
+                    .into_iter()
+                    .map(crate::worker_binding::WorkerReference::try_from)
+                    .collect::<Result<Vec<crate::worker_binding::WorkerReference>, String>>()?;
+
+                Ok(RouteBinding::WitWorker(
+                    crate::worker_binding::GolemWorkerBinding {
+                        component_id,
+                        worker_name,
+                        idempotency_key,
+                        response,
+                        workers,
+                    },
+                ))
+            }
+            grpc_apidefinition::BindingType::Static => {
+                Ok(RouteBinding::Static(StaticBinding { response }))
+            }
+            grpc_apidefinition::BindingType::FileServer => {
+                Ok(RouteBinding::FileServer(StaticBinding { response }))
+            }
+        }
+    }
+}
+
+impl TryFrom<crate::worker_binding::WorkerReference> for grpc_apidefinition::WorkerReference {
+    type Error = String;
+
+    fn try_from(value: crate::worker_binding::WorkerReference) -> Result<Self, Self::Error> {
+        let worker_name = expression::to_string(&value.worker_name).map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            name: value.name,
+            component: Some(value.component_id.into()),
+            worker_name,
+        })
+    }
+}
+
+impl TryFrom<grpc_apidefinition::WorkerReference> for crate::worker_binding::WorkerReference {
+    type Error = String;
+
+    fn try_from(value: grpc_apidefinition::WorkerReference) -> Result<Self, Self::Error> {
         let worker_name = value
-            .worker_id
+            .worker_name
             .parse()
             .map_err(|e: ParseError| e.to_string())?;
 
         let component_id = value.component.ok_or("component is missing")?.try_into()?;
 
-        let idempotency_key = if let Some(key) = &value.idempotency_key {
+        Ok(Self {
+            name: value.name,
+            component_id,
+            worker_name,
+        })
+    }
+}
+
+impl TryFrom<crate::api_definition::http::FunctionDefinition>
+    for grpc_apidefinition::FunctionDefinition
+{
+    type Error = String;
+
+    fn try_from(
+        value: crate::api_definition::http::FunctionDefinition,
+    ) -> Result<Self, Self::Error> {
+        let body = expression::to_string(&value.body).map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            name: value.name,
+            params: value.params,
+            body,
+        })
+    }
+}
+
+impl TryFrom<grpc_apidefinition::FunctionDefinition>
+    for crate::api_definition::http::FunctionDefinition
+{
+    type Error = String;
+
+    fn try_from(value: grpc_apidefinition::FunctionDefinition) -> Result<Self, Self::Error> {
+        let body = value.body.parse().map_err(|e: ParseError| e.to_string())?;
+
+        Ok(Self {
+            name: value.name,
+            params: value.params,
+            body,
+        })
+    }
+}
+
+impl TryFrom<crate::api_definition::http::RateLimit> for grpc_apidefinition::RateLimit {
+    type Error = String;
+
+    fn try_from(value: crate::api_definition::http::RateLimit) -> Result<Self, Self::Error> {
+        let key = if let Some(key) = &value.key {
+            Some(expression::to_string(key).map_err(|e| e.to_string())?)
+        } else {
+            None
+        };
+
+        Ok(grpc_apidefinition::RateLimit {
+            requests: value.requests,
+            period_seconds: value.period_seconds,
+            burst: value.burst,
+            key,
+        })
+    }
+}
+
+impl TryFrom<grpc_apidefinition::RateLimit> for crate::api_definition::http::RateLimit {
+    type Error = String;
+
+    fn try_from(value: grpc_apidefinition::RateLimit) -> Result<Self, Self::Error> {
+        let key = if let Some(key) = &value.key {
             Some(key.parse().map_err(|e: ParseError| e.to_string())?)
         } else {
             None
         };
 
-        let result = crate::worker_binding::GolemWorkerBinding {
-            component_id,
-            worker_name,
-            idempotency_key,
-            response,
+        Ok(crate::api_definition::http::RateLimit {
+            requests: value.requests,
+            period_seconds: value.period_seconds,
+            burst: value.burst,
+            key,
+        })
+    }
+}
+
+impl From<RouteSecurity> for grpc_apidefinition::RouteSecurity {
+    fn from(value: RouteSecurity) -> Self {
+        grpc_apidefinition::RouteSecurity {
+            scheme: value.scheme,
+            scopes: value.scopes,
+        }
+    }
+}
+
+impl From<grpc_apidefinition::RouteSecurity> for RouteSecurity {
+    fn from(value: grpc_apidefinition::RouteSecurity) -> Self {
+        RouteSecurity {
+            scheme: value.scheme,
+            scopes: value.scopes,
+        }
+    }
+}
+
+impl From<StreamBinding> for grpc_apidefinition::StreamBinding {
+    fn from(value: StreamBinding) -> Self {
+        let protocol = match value.protocol {
+            StreamProtocol::WebSocket => grpc_apidefinition::StreamProtocol::WebSocket,
+            StreamProtocol::Sse => grpc_apidefinition::StreamProtocol::Sse,
         };
 
-        Ok(result)
+        let source = match value.source {
+            StreamSource::Output => grpc_apidefinition::StreamSource::Output,
+            StreamSource::Results => grpc_apidefinition::StreamSource::Results,
+        };
+
+        grpc_apidefinition::StreamBinding {
+            protocol: protocol as i32,
+            source: source as i32,
+            poll_interval_seconds: value.poll_interval_seconds,
+        }
+    }
+}
+
+impl TryFrom<grpc_apidefinition::StreamBinding> for StreamBinding {
+    type Error = String;
+
+    fn try_from(value: grpc_apidefinition::StreamBinding) -> Result<Self, Self::Error> {
+        let protocol = match grpc_apidefinition::StreamProtocol::try_from(value.protocol) {
+            Ok(grpc_apidefinition::StreamProtocol::WebSocket) => StreamProtocol::WebSocket,
+            Ok(grpc_apidefinition::StreamProtocol::Sse) => StreamProtocol::Sse,
+            Err(_) => return Err(format!("Invalid stream protocol: {}", value.protocol)),
+        };
+
+        let source = match grpc_apidefinition::StreamSource::try_from(value.source) {
+            Ok(grpc_apidefinition::StreamSource::Output) => StreamSource::Output,
+            Ok(grpc_apidefinition::StreamSource::Results) => StreamSource::Results,
+            Err(_) => return Err(format!("Invalid stream source: {}", value.source)),
+        };
+
+        Ok(StreamBinding {
+            protocol,
+            source,
+            poll_interval_seconds: value.poll_interval_seconds,
+        })
+    }
+}
+
+impl From<SecurityScheme> for grpc_apidefinition::SecurityScheme {
+    fn from(value: SecurityScheme) -> Self {
+        let scheme = match value.scheme {
+            SecuritySchemeKind::ApiKey(api_key) => {
+                let location: grpc_apidefinition::ApiKeyLocation = api_key.location.into();
+                grpc_apidefinition::security_scheme::Scheme::ApiKey(
+                    grpc_apidefinition::ApiKeySecurityScheme {
+                        location: location as i32,
+                        param_name: api_key.param_name,
+                        keys: api_key
+                            .keys
+                            .into_iter()
+                            .map(|key| grpc_apidefinition::ApiKey {
+                                name: key.name,
+                                key: key.key,
+                                scopes: key.scopes,
+                                key_hash: key.key_hash,
+                            })
+                            .collect(),
+                    },
+                )
+            }
+            SecuritySchemeKind::JwtBearer(jwt_bearer) => {
+                grpc_apidefinition::security_scheme::Scheme::JwtBearer(
+                    grpc_apidefinition::JwtBearerSecurityScheme {
+                        jwks: jwt_bearer.jwks,
+                        issuer: jwt_bearer.issuer,
+                        audience: jwt_bearer.audience,
+                    },
+                )
+            }
+            SecuritySchemeKind::Basic(basic) => grpc_apidefinition::security_scheme::Scheme::Basic(
+                grpc_apidefinition::BasicSecurityScheme {
+                    users: basic
+                        .users
+                        .into_iter()
+                        .map(|user| grpc_apidefinition::BasicAuthUser {
+                            username: user.username,
+                            password: user.password,
+                            scopes: user.scopes,
+                            password_hash: user.password_hash,
+                        })
+                        .collect(),
+                },
+            ),
+        };
+
+        grpc_apidefinition::SecurityScheme {
+            name: value.name,
+            scheme: Some(scheme),
+        }
+    }
+}
+
+impl TryFrom<grpc_apidefinition::SecurityScheme> for SecurityScheme {
+    type Error = String;
+
+    fn try_from(value: grpc_apidefinition::SecurityScheme) -> Result<Self, Self::Error> {
+        let scheme = match value.scheme.ok_or("security scheme is missing")? {
+            grpc_apidefinition::security_scheme::Scheme::ApiKey(api_key) => {
+                SecuritySchemeKind::ApiKey(ApiKeySecurityScheme {
+                    location: api_key.location.try_into()?,
+                    param_name: api_key.param_name,
+                    keys: api_key
+                        .keys
+                        .into_iter()
+                        .map(|key| ApiKey {
+                            name: key.name,
+                            key: key.key,
+                            key_hash: key.key_hash,
+                            scopes: key.scopes,
+                        })
+                        .collect(),
+                })
+            }
+            grpc_apidefinition::security_scheme::Scheme::JwtBearer(jwt_bearer) => {
+                SecuritySchemeKind::JwtBearer(JwtBearerSecurityScheme {
+                    jwks: jwt_bearer.jwks,
+                    issuer: jwt_bearer.issuer,
+                    audience: jwt_bearer.audience,
+                })
+            }
+            grpc_apidefinition::security_scheme::Scheme::Basic(basic) => {
+                SecuritySchemeKind::Basic(BasicSecurityScheme {
+                    users: basic
+                        .users
+                        .into_iter()
+                        .map(|user| BasicAuthUser {
+                            username: user.username,
+                            password: user.password,
+                            password_hash: user.password_hash,
+                            scopes: user.scopes,
+                        })
+                        .collect(),
+                })
+            }
+        };
+
+        Ok(SecurityScheme {
+            name: value.name,
+            scheme,
+        }
+        .with_hashed_secrets())
+    }
+}
+
+impl From<CorsPolicy> for grpc_apidefinition::CorsPolicy {
+    fn from(value: CorsPolicy) -> Self {
+        grpc_apidefinition::CorsPolicy {
+            allowed_origins: value.allowed_origins,
+            allowed_methods: value
+                .allowed_methods
+                .into_iter()
+                .map(|method| grpc_apidefinition::HttpMethod::from(method) as i32)
+                .collect(),
+            allowed_headers: value.allowed_headers,
+            allow_credentials: value.allow_credentials,
+            max_age: value.max_age,
+        }
+    }
+}
+
+impl TryFrom<grpc_apidefinition::CorsPolicy> for CorsPolicy {
+    type Error = String;
+
+    fn try_from(value: grpc_apidefinition::CorsPolicy) -> Result<Self, Self::Error> {
+        let allowed_methods = value
+            .allowed_methods
+            .into_iter()
+            .map(MethodPattern::try_from)
+            .collect::<Result<Vec<MethodPattern>, &'static str>>()?;
+
+        Ok(CorsPolicy {
+            allowed_origins: value.allowed_origins,
+            allowed_methods,
+            allowed_headers: value.allowed_headers,
+            allow_credentials: value.allow_credentials,
+            max_age: value.max_age,
+        })
+    }
+}
+
+impl From<ApiKeyLocation> for grpc_apidefinition::ApiKeyLocation {
+    fn from(value: ApiKeyLocation) -> Self {
+        match value {
+            ApiKeyLocation::Header => grpc_apidefinition::ApiKeyLocation::Header,
+            ApiKeyLocation::Query => grpc_apidefinition::ApiKeyLocation::Query,
+        }
+    }
+}
+
+impl TryFrom<i32> for ApiKeyLocation {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match grpc_apidefinition::ApiKeyLocation::try_from(value) {
+            Ok(grpc_apidefinition::ApiKeyLocation::Header) => Ok(ApiKeyLocation::Header),
+            Ok(grpc_apidefinition::ApiKeyLocation::Query) => Ok(ApiKeyLocation::Query),
+            Err(_) => Err(format!("Invalid api key location: {value}")),
+        }
     }
 }
 
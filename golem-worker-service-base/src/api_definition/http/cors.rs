@@ -0,0 +1,42 @@
+use bincode::{Decode, Encode};
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+
+use crate::api_definition::http::MethodPattern;
+
+// Cross-origin resource sharing policy applied to every route of an api definition.
+// Preflight requests are answered by the gateway itself, unless an OPTIONS route is bound
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct CorsPolicy {
+    // Origins allowed to call the api, `*` allows any origin
+    pub allowed_origins: Vec<String>,
+    // Methods allowed in cross-origin requests, all the methods bound to a path if empty
+    #[serde(default)]
+    #[oai(default)]
+    pub allowed_methods: Vec<MethodPattern>,
+    // Request headers allowed in cross-origin requests, `*` allows any header
+    #[serde(default)]
+    #[oai(default)]
+    pub allowed_headers: Vec<String>,
+    #[serde(default)]
+    #[oai(default)]
+    pub allow_credentials: bool,
+    // Number of seconds the result of a preflight request can be cached
+    pub max_age: Option<u64>,
+}
+
+impl CorsPolicy {
+    pub fn is_origin_allowed(&self, origin: &str) -> bool {
+        self.allowed_origins
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.eq_ignore_ascii_case(origin))
+    }
+
+    pub fn is_header_allowed(&self, header: &str) -> bool {
+        self.allowed_headers
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.eq_ignore_ascii_case(header))
+    }
+}
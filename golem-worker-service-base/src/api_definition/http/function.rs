@@ -0,0 +1,27 @@
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+use crate::expression::Expr;
+
+// A named function of the API definition, callable from any expression of its routes as
+// `name(arg1, arg2)`, or passed to map, filter and fold by its name. Functions are pure: the body
+// only sees its parameters, and can only call the functions defined before it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub params: Vec<String>,
+    pub body: Expr,
+}
+
+impl FunctionDefinition {
+    // The functions the body of the named function can call
+    pub fn visible_from(functions: &[FunctionDefinition], name: &str) -> Vec<FunctionDefinition> {
+        functions
+            .iter()
+            .take_while(|function| function.name != name)
+            .cloned()
+            .collect()
+    }
+}
@@ -9,12 +9,15 @@ use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value;
 
 use crate::api_definition::api_common::HasIsDraft;
+use crate::api_definition::http::{
+    CorsPolicy, FunctionDefinition, RateLimit, RouteSecurity, SecurityScheme, StreamBinding,
+};
 use crate::api_definition::{
     ApiDefinitionId, ApiVersion, HasApiDefinitionId, HasGolemWorkerBindings, HasVersion,
 };
 use crate::parser::path_pattern_parser::PathPatternParser;
 use crate::parser::{GolemParser, ParseError};
-use crate::worker_binding::GolemWorkerBinding;
+use crate::worker_binding::{deserialize_route_binding, GolemWorkerBinding, RouteBinding};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 #[serde(rename_all = "camelCase")]
@@ -23,14 +26,28 @@ pub struct HttpApiDefinition {
     pub version: ApiVersion,
     pub routes: Vec<Route>,
     #[serde(default)]
+    pub security_schemes: Vec<SecurityScheme>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cors: Option<CorsPolicy>,
+    #[serde(default)]
+    pub functions: Vec<FunctionDefinition>,
+    #[serde(default)]
     pub draft: bool,
 }
 
+impl HttpApiDefinition {
+    pub fn security_scheme(&self, name: &str) -> Option<&SecurityScheme> {
+        self.security_schemes
+            .iter()
+            .find(|scheme| scheme.name == name)
+    }
+}
+
 impl HasGolemWorkerBindings for HttpApiDefinition {
     fn get_golem_worker_bindings(&self) -> Vec<GolemWorkerBinding> {
         self.routes
             .iter()
-            .map(|route| route.binding.clone())
+            .filter_map(|route| route.binding.worker_binding().cloned())
             .collect()
     }
 }
@@ -279,10 +296,18 @@ impl Display for PathPattern {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
+#[serde(rename_all = "camelCase")]
 pub struct Route {
     pub method: MethodPattern,
     pub path: AllPathPatterns,
-    pub binding: GolemWorkerBinding,
+    #[serde(deserialize_with = "deserialize_route_binding")]
+    pub binding: RouteBinding,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<RouteSecurity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream: Option<StreamBinding>,
 }
 
 #[cfg(test)]
@@ -556,6 +581,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_static_bindings_serde() {
+        let yaml = r#"
+          id: website
+          version: 0.0.1
+          routes:
+          - method: Get
+            path: old
+            binding:
+              type: static
+              response: '${{status: 302, headers: {location: "/new"}}}'
+          - method: Get
+            path: assets/{file}
+            binding:
+              type: file-server
+              response: 'website/${request.path.file}'
+        "#;
+
+        let result: HttpApiDefinition = serde_yaml::from_str(yaml).unwrap();
+
+        assert!(matches!(result.routes[0].binding, RouteBinding::Static(_)));
+        assert!(matches!(
+            result.routes[1].binding,
+            RouteBinding::FileServer(_)
+        ));
+        assert!(result.get_golem_worker_bindings().is_empty());
+
+        let yaml2 = serde_yaml::to_string(&result).unwrap();
+        let result2: HttpApiDefinition = serde_yaml::from_str(&yaml2).unwrap();
+
+        assert_eq!(result, result2);
+    }
+
     #[test]
     fn test_api_spec_encode_decode() {
         fn test_encode_decode(
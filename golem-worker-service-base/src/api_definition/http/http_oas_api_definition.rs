@@ -1,7 +1,9 @@
 use async_trait::async_trait;
+use golem_service_base::model::Component;
 use openapiv3::OpenAPI;
-use poem_openapi::types::ParseFromJSON;
+use poem_openapi::types::{ParseFromJSON, ToJSON};
 use poem_openapi::{registry, types};
+use serde_json::{json, Map, Value};
 
 use crate::api_definition::http::HttpApiDefinition;
 use crate::api_definition::{ApiDefinitionId, ApiVersion};
@@ -16,16 +18,90 @@ pub fn get_api_definition(openapi: OpenAPI) -> Result<HttpApiDefinition, String>
     let api_definition_version =
         ApiVersion(get_root_extension(&openapi, GOLEM_API_DEFINITION_VERSION)?);
 
+    let functions = get_functions(&openapi)?;
+
+    let security_schemes = get_security_schemes(&openapi)?;
+
+    let cors = get_root_extension_value(&openapi, GOLEM_CORS_EXTENSION)?;
+
+    if openapi
+        .security
+        .as_ref()
+        .is_some_and(|requirements| !requirements.is_empty())
+    {
+        return Err("Security requirements have to be declared on the operations".to_string());
+    }
+
     let routes = get_routes(openapi.paths)?;
 
     Ok(HttpApiDefinition {
         id: api_definition_id,
         version: api_definition_version,
         routes,
+        security_schemes,
+        cors,
+        functions,
         draft: true,
     })
 }
 
+// Renders the api definition as an OpenAPI spec, which can be imported back with
+// get_api_definition. The schemas of the requests and responses are derived from the functions
+// of the components the routes are bound to, and are left open where the type is only known
+// at runtime.
+pub fn get_openapi(
+    api_definition: &HttpApiDefinition,
+    components: &[Component],
+) -> Result<OpenAPI, String> {
+    let mut paths = Map::new();
+
+    for route in &api_definition.routes {
+        let component = components
+            .iter()
+            .find(|component| {
+                Some(&component.versioned_component_id.component_id) == route.binding.component_id()
+            })
+            .map(|component| &component.metadata);
+
+        let path_item = paths
+            .entry(export::get_path(&route.path))
+            .or_insert_with(|| Value::Object(Map::new()));
+
+        path_item[export::get_method(&route.method)?] =
+            export::get_operation(route, component, &api_definition.functions)?;
+    }
+
+    let mut openapi = json!({
+        "openapi": "3.0.0",
+        "info": {
+            "title": api_definition.id.0,
+            "version": api_definition.version.0,
+        },
+        "paths": paths,
+    });
+
+    if !api_definition.security_schemes.is_empty() {
+        openapi["components"] = json!({
+            "securitySchemes": export::get_security_schemes(&api_definition.security_schemes),
+        });
+        openapi[GOLEM_SECURITY_SCHEMES_EXTENSION] =
+            export::get_golem_security_schemes(&api_definition.security_schemes)?;
+    }
+
+    openapi[GOLEM_API_DEFINITION_ID_EXTENSION] = json!(api_definition.id.0);
+    openapi[GOLEM_API_DEFINITION_VERSION] = json!(api_definition.version.0);
+
+    if let Some(cors) = &api_definition.cors {
+        openapi[GOLEM_CORS_EXTENSION] = export::to_extension(GOLEM_CORS_EXTENSION, cors)?;
+    }
+
+    if !api_definition.functions.is_empty() {
+        openapi[GOLEM_FUNCTIONS_EXTENSION] = export::get_functions(&api_definition.functions)?;
+    }
+
+    serde_json::from_value(openapi).map_err(|err| format!("Invalid OpenAPI spec: {err}"))
+}
+
 // Used to extract the OpenAPI spec from JSON Body in Poem OpenAPI endpoints.
 pub struct JsonOpenApiDefinition(pub openapiv3::OpenAPI);
 
@@ -74,12 +150,24 @@ impl ParseFromJSON for JsonOpenApiDefinition {
     }
 }
 
+impl ToJSON for JsonOpenApiDefinition {
+    fn to_json(&self) -> Option<Value> {
+        serde_json::to_value(&self.0).ok()
+    }
+}
+
 mod internal {
-    use crate::api_definition::http::{AllPathPatterns, MethodPattern, Route};
+    use crate::api_definition::http::QueryInfo;
+    use crate::api_definition::http::{
+        AllPathPatterns, FunctionDefinition, MethodPattern, Route, RouteSecurity, SecurityScheme,
+    };
     use crate::expression::Expr;
-    use crate::worker_binding::{GolemWorkerBinding, ResponseMapping};
+    use crate::worker_binding::{
+        GolemWorkerBinding, ResponseMapping, RouteBinding, StaticBinding, WorkerReference,
+    };
     use golem_common::model::ComponentId;
-    use openapiv3::{OpenAPI, PathItem, Paths, ReferenceOr};
+    use openapiv3::{OpenAPI, Operation, Parameter, PathItem, Paths, ReferenceOr};
+    use serde::de::DeserializeOwned;
     use serde_json::Value;
 
     use crate::expression;
@@ -88,6 +176,11 @@ mod internal {
     pub(crate) const GOLEM_API_DEFINITION_ID_EXTENSION: &str = "x-golem-api-definition-id";
     pub(crate) const GOLEM_API_DEFINITION_VERSION: &str = "x-golem-api-definition-version";
     pub(crate) const GOLEM_WORKER_BRIDGE_EXTENSION: &str = "x-golem-worker-bridge";
+    pub(crate) const GOLEM_FUNCTIONS_EXTENSION: &str = "x-golem-functions";
+    pub(crate) const GOLEM_SECURITY_SCHEMES_EXTENSION: &str = "x-golem-security-schemes";
+    pub(crate) const GOLEM_CORS_EXTENSION: &str = "x-golem-cors";
+    pub(crate) const GOLEM_RATE_LIMIT_EXTENSION: &str = "x-golem-rate-limit";
+    pub(crate) const GOLEM_STREAM_EXTENSION: &str = "x-golem-stream";
 
     pub(crate) fn get_root_extension(open_api: &OpenAPI, key_name: &str) -> Result<String, String> {
         open_api
@@ -101,8 +194,132 @@ mod internal {
             .map(|x| x.to_string())
     }
 
+    // Extensions holding parts of the api definition as they are serialized in the definition
+    // itself, such as the CORS policy
+    pub(crate) fn get_root_extension_value<T: DeserializeOwned>(
+        open_api: &OpenAPI,
+        key_name: &str,
+    ) -> Result<Option<T>, String> {
+        parse_extension(open_api.extensions.get(key_name), key_name)
+    }
+
+    fn get_operation_extension_value<T: DeserializeOwned>(
+        operation: Option<&Operation>,
+        key_name: &str,
+    ) -> Result<Option<T>, String> {
+        parse_extension(
+            operation.and_then(|operation| operation.extensions.get(key_name)),
+            key_name,
+        )
+    }
+
+    fn parse_extension<T: DeserializeOwned>(
+        value: Option<&Value>,
+        key_name: &str,
+    ) -> Result<Option<T>, String> {
+        value
+            .map(|value| {
+                serde_json::from_value(value.clone())
+                    .map_err(|err| format!("Invalid value for {}: {}", key_name, err))
+            })
+            .transpose()
+    }
+
+    // The standard security schemes only say how callers authenticate. The keys, users and JWKS
+    // they are checked against are in the extension, which every declared scheme has to be in
+    pub(crate) fn get_security_schemes(open_api: &OpenAPI) -> Result<Vec<SecurityScheme>, String> {
+        let security_schemes: Vec<SecurityScheme> =
+            get_root_extension_value(open_api, GOLEM_SECURITY_SCHEMES_EXTENSION)?
+                .unwrap_or_default();
+
+        if let Some(components) = &open_api.components {
+            for name in components.security_schemes.keys() {
+                if !security_schemes.iter().any(|scheme| &scheme.name == name) {
+                    return Err(format!(
+                        "Security scheme {} is not described in {}",
+                        name, GOLEM_SECURITY_SCHEMES_EXTENSION
+                    ));
+                }
+            }
+        }
+
+        Ok(security_schemes
+            .into_iter()
+            .map(SecurityScheme::with_hashed_secrets)
+            .collect())
+    }
+
+    // A route is secured by a single scheme, so only operations with at most one requirement
+    // naming a single scheme can be imported
+    fn get_route_security(operation: Option<&Operation>) -> Result<Option<RouteSecurity>, String> {
+        let requirements = match operation.and_then(|operation| operation.security.as_ref()) {
+            Some(requirements) => requirements,
+            None => return Ok(None),
+        };
+
+        match requirements.as_slice() {
+            [] => Ok(None),
+            [requirement] if requirement.len() == 1 => {
+                Ok(requirement
+                    .iter()
+                    .next()
+                    .map(|(scheme, scopes)| RouteSecurity {
+                        scheme: scheme.clone(),
+                        scopes: scopes.clone(),
+                    }))
+            }
+            _ => Err("Only a single security scheme per operation is supported".to_string()),
+        }
+    }
+
+    // Functions of the api definition, as a list of name, params and body
+    pub(crate) fn get_functions(open_api: &OpenAPI) -> Result<Vec<FunctionDefinition>, String> {
+        let Some(functions) = open_api.extensions.get(GOLEM_FUNCTIONS_EXTENSION) else {
+            return Ok(vec![]);
+        };
+
+        functions
+            .as_array()
+            .ok_or(format!("{} is not an array", GOLEM_FUNCTIONS_EXTENSION))?
+            .iter()
+            .map(|function| {
+                let name = function
+                    .get("name")
+                    .ok_or("No name found for a function")?
+                    .as_str()
+                    .ok_or("name of a function is not a string")?;
+
+                let params = match function.get("params") {
+                    Some(params) => params
+                        .as_array()
+                        .ok_or(format!("params of function {} is not an array", name))?
+                        .iter()
+                        .map(|param| {
+                            param
+                                .as_str()
+                                .map(|param| param.to_string())
+                                .ok_or(format!("params of function {} are not strings", name))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                    None => vec![],
+                };
+
+                let body = function
+                    .get("body")
+                    .ok_or(format!("No body found for function {}", name))?
+                    .as_str()
+                    .ok_or(format!("body of function {} is not a string", name))?;
+
+                Ok(FunctionDefinition {
+                    name: name.to_string(),
+                    params,
+                    body: expression::from_string(body).map_err(|err| err.to_string())?,
+                })
+            })
+            .collect()
+    }
+
     pub(crate) fn get_routes(paths: Paths) -> Result<Vec<Route>, String> {
-        dbg!("here???");
         let mut routes: Vec<Route> = vec![];
 
         for (path, path_item) in paths.iter() {
@@ -110,8 +327,27 @@ mod internal {
                 ReferenceOr::Item(item) => {
                     let path_pattern = get_path_pattern(path)?;
 
-                    for (str, _) in item.iter() {
-                        let route = get_route_from_path_item(str, item, &path_pattern)?;
+                    for (str, operation) in item.iter() {
+                        let mut route = get_route_from_path_item(str, item, &path_pattern)?;
+
+                        // Query parameters are not part of the path in the exported specs
+                        for parameter in operation.parameters.iter() {
+                            if let ReferenceOr::Item(Parameter::Query { parameter_data, .. }) =
+                                parameter
+                            {
+                                if !route
+                                    .path
+                                    .query_params
+                                    .iter()
+                                    .any(|query| query.key_name == parameter_data.name)
+                                {
+                                    route.path.query_params.push(QueryInfo {
+                                        key_name: parameter_data.name.clone(),
+                                    });
+                                }
+                            }
+                        }
+
                         routes.push(route);
                     }
                 }
@@ -144,30 +380,61 @@ mod internal {
             _ => Err("Other methods not supported".to_string()),
         };
 
-        let method = method_res?;
+        let operation = path_item
+            .iter()
+            .find(|(operation_method, _)| *operation_method == method)
+            .map(|(_, operation)| operation);
 
-        let worker_bridge_info = path_item
-            .extensions
-            .get(GOLEM_WORKER_BRIDGE_EXTENSION)
+        // Exported specs have the extension on each operation, as a path can have several routes
+        let worker_bridge_info = operation
+            .and_then(|operation| operation.extensions.get(GOLEM_WORKER_BRIDGE_EXTENSION))
+            .or_else(|| path_item.extensions.get(GOLEM_WORKER_BRIDGE_EXTENSION))
             .ok_or(format!(
                 "No {} extension found",
                 GOLEM_WORKER_BRIDGE_EXTENSION
             ))?;
 
-        let binding = GolemWorkerBinding {
-            worker_name: get_worker_id_expr(worker_bridge_info)?,
-            component_id: get_component_id(worker_bridge_info)?,
-            idempotency_key: get_idempotency_key(worker_bridge_info)?,
-            response: get_response_mapping(worker_bridge_info)?,
-        };
+        let method = method_res?;
+
+        let binding = get_binding(worker_bridge_info)?;
 
         Ok(Route {
             path: path_pattern.clone(),
             method,
             binding,
+            security: get_route_security(operation)?,
+            rate_limit: get_operation_extension_value(operation, GOLEM_RATE_LIMIT_EXTENSION)?,
+            stream: get_operation_extension_value(operation, GOLEM_STREAM_EXTENSION)?,
         })
     }
 
+    // Routes are bound to a worker unless the extension says otherwise
+    pub(crate) fn get_binding(worker_bridge_info: &Value) -> Result<RouteBinding, String> {
+        let binding_type = match worker_bridge_info.get("binding-type") {
+            Some(binding_type) => binding_type
+                .as_str()
+                .ok_or("binding-type is not a string")?,
+            None => "wit-worker",
+        };
+
+        match binding_type {
+            "wit-worker" => Ok(RouteBinding::WitWorker(GolemWorkerBinding {
+                worker_name: get_worker_id_expr(worker_bridge_info)?,
+                component_id: get_component_id(worker_bridge_info)?,
+                idempotency_key: get_idempotency_key(worker_bridge_info)?,
+                response: get_response_mapping(worker_bridge_info)?,
+                workers: get_worker_references(worker_bridge_info)?,
+            })),
+            "static" => Ok(RouteBinding::Static(StaticBinding {
+                response: get_response_mapping(worker_bridge_info)?,
+            })),
+            "file-server" => Ok(RouteBinding::FileServer(StaticBinding {
+                response: get_response_mapping(worker_bridge_info)?,
+            })),
+            other => Err(format!("Unknown binding-type {}", other)),
+        }
+    }
+
     pub(crate) fn get_component_id(worker_bridge_info: &Value) -> Result<ComponentId, String> {
         let component_id = worker_bridge_info
             .get("component-id")
@@ -221,18 +488,645 @@ mod internal {
         }
     }
 
+    pub(crate) fn get_worker_references(
+        worker_bridge_info: &Value,
+    ) -> Result<Vec<WorkerReference>, String> {
+        let Some(workers) = worker_bridge_info.get("workers") else {
+            return Ok(vec![]);
+        };
+
+        workers
+            .as_array()
+            .ok_or("workers is not an array")?
+            .iter()
+            .map(|worker| {
+                let name = worker
+                    .get("name")
+                    .ok_or("No name found for a worker")?
+                    .as_str()
+                    .ok_or("name of a worker is not a string")?;
+
+                Ok(WorkerReference {
+                    name: name.to_string(),
+                    component_id: get_component_id(worker)?,
+                    worker_name: get_worker_id_expr(worker)?,
+                })
+            })
+            .collect()
+    }
+
     pub(crate) fn get_path_pattern(path: &str) -> Result<AllPathPatterns, String> {
         AllPathPatterns::parse(path).map_err(|err| err.to_string())
     }
 }
 
+mod export {
+    use golem_service_base::model::ComponentMetadata;
+    use golem_wasm_ast::analysis::AnalysedType;
+    use serde::Serialize;
+    use serde_json::{json, Map, Value};
+
+    use crate::api_definition::http::{
+        AllPathPatterns, ApiKeyLocation, FunctionDefinition, MethodPattern, PathPattern, Route,
+        SecurityScheme, SecuritySchemeKind,
+    };
+    use crate::expression::{self, Expr, InnerNumber};
+    use crate::service::http::http_api_definition_validator::{request_scope, response_scope};
+    use crate::type_checker::{type_check, FunctionScope, InferredType, NumberLiteral};
+    use crate::worker_binding::RouteBinding;
+
+    use super::internal::{
+        GOLEM_RATE_LIMIT_EXTENSION, GOLEM_SECURITY_SCHEMES_EXTENSION, GOLEM_STREAM_EXTENSION,
+        GOLEM_WORKER_BRIDGE_EXTENSION,
+    };
+
+    // Query parameters are described by the parameters of the operation instead
+    pub(crate) fn get_path(path: &AllPathPatterns) -> String {
+        let path: String = path
+            .path_patterns
+            .iter()
+            .map(|pattern| format!("/{pattern}"))
+            .collect();
+
+        if path.is_empty() {
+            "/".to_string()
+        } else {
+            path
+        }
+    }
+
+    pub(crate) fn get_method(method: &MethodPattern) -> Result<&'static str, String> {
+        match method {
+            MethodPattern::Get => Ok("get"),
+            MethodPattern::Post => Ok("post"),
+            MethodPattern::Put => Ok("put"),
+            MethodPattern::Delete => Ok("delete"),
+            MethodPattern::Options => Ok("options"),
+            MethodPattern::Head => Ok("head"),
+            MethodPattern::Patch => Ok("patch"),
+            MethodPattern::Trace => Ok("trace"),
+            MethodPattern::Connect => Err("Connect routes cannot be described in OpenAPI".into()),
+        }
+    }
+
+    pub(crate) fn get_operation(
+        route: &Route,
+        component: Option<&ComponentMetadata>,
+        functions: &[FunctionDefinition],
+    ) -> Result<Value, String> {
+        let mut request = RequestSchema::default();
+
+        for expr in binding_expressions(route) {
+            request.add_references(expr);
+        }
+
+        if let Some(component) = component {
+            request.add_call_arguments(&route.binding.response().0, component);
+        }
+
+        let mut operation = json!({
+            "parameters": request.parameters(&route.path),
+            "responses": get_responses(route, component, functions),
+        });
+
+        if let Some(body) = request.body() {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": {
+                    "application/json": { "schema": body },
+                },
+            });
+        }
+
+        if let Some(security) = &route.security {
+            let mut requirement = Map::new();
+            requirement.insert(security.scheme.clone(), json!(security.scopes));
+            operation["security"] = json!([requirement]);
+        }
+
+        operation[GOLEM_WORKER_BRIDGE_EXTENSION] = get_worker_bridge_info(&route.binding)?;
+
+        if let Some(rate_limit) = &route.rate_limit {
+            operation[GOLEM_RATE_LIMIT_EXTENSION] =
+                to_extension(GOLEM_RATE_LIMIT_EXTENSION, rate_limit)?;
+        }
+
+        if let Some(stream) = &route.stream {
+            operation[GOLEM_STREAM_EXTENSION] = to_extension(GOLEM_STREAM_EXTENSION, stream)?;
+        }
+
+        Ok(operation)
+    }
+
+    pub(crate) fn get_functions(functions: &[FunctionDefinition]) -> Result<Value, String> {
+        let functions = functions
+            .iter()
+            .map(|function| {
+                Ok(json!({
+                    "name": function.name,
+                    "params": function.params,
+                    "body": expression_text(&function.body)?,
+                }))
+            })
+            .collect::<Result<Vec<Value>, String>>()?;
+
+        Ok(json!(functions))
+    }
+
+    pub(crate) fn to_extension<T: Serialize>(key_name: &str, value: &T) -> Result<Value, String> {
+        serde_json::to_value(value).map_err(|err| format!("Failed to export {key_name}: {err}"))
+    }
+
+    // The schemes as they are imported back. Keys and passwords are only exported as their
+    // hashes, plaintext ones of definitions not registered yet are hashed first
+    pub(crate) fn get_golem_security_schemes(schemes: &[SecurityScheme]) -> Result<Value, String> {
+        let schemes: Vec<SecurityScheme> = schemes
+            .iter()
+            .cloned()
+            .map(SecurityScheme::with_hashed_secrets)
+            .collect();
+
+        to_extension(GOLEM_SECURITY_SCHEMES_EXTENSION, &schemes)
+    }
+
+    // Only the kind of the scheme is described in the standard security schemes, never the keys
+    // or the users it accepts
+    pub(crate) fn get_security_schemes(schemes: &[SecurityScheme]) -> Value {
+        let schemes: Map<String, Value> = schemes
+            .iter()
+            .map(|scheme| {
+                let value = match &scheme.scheme {
+                    SecuritySchemeKind::ApiKey(api_key) => json!({
+                        "type": "apiKey",
+                        "in": match api_key.location {
+                            ApiKeyLocation::Header => "header",
+                            ApiKeyLocation::Query => "query",
+                        },
+                        "name": api_key.param_name,
+                    }),
+                    SecuritySchemeKind::JwtBearer(_) => json!({
+                        "type": "http",
+                        "scheme": "bearer",
+                        "bearerFormat": "JWT",
+                    }),
+                    SecuritySchemeKind::Basic(_) => json!({
+                        "type": "http",
+                        "scheme": "basic",
+                    }),
+                };
+
+                (scheme.name.clone(), value)
+            })
+            .collect();
+
+        Value::Object(schemes)
+    }
+
+    // The binding type is left out for worker bindings, which is the default when importing
+    fn get_worker_bridge_info(binding: &RouteBinding) -> Result<Value, String> {
+        match binding {
+            RouteBinding::WitWorker(binding) => {
+                let mut info = json!({
+                    "worker-name": expression_text(&binding.worker_name)?,
+                    "component-id": binding.component_id.0.to_string(),
+                    "response": expression_text(&binding.response.0)?,
+                });
+
+                if let Some(idempotency_key) = &binding.idempotency_key {
+                    info["idempotency-key"] = json!(expression_text(idempotency_key)?);
+                }
+
+                if !binding.workers.is_empty() {
+                    let workers = binding
+                        .workers
+                        .iter()
+                        .map(|worker| {
+                            Ok(json!({
+                                "name": worker.name,
+                                "component-id": worker.component_id.0.to_string(),
+                                "worker-name": expression_text(&worker.worker_name)?,
+                            }))
+                        })
+                        .collect::<Result<Vec<Value>, String>>()?;
+
+                    info["workers"] = json!(workers);
+                }
+
+                Ok(info)
+            }
+            RouteBinding::Static(binding) => Ok(json!({
+                "binding-type": "static",
+                "response": expression_text(&binding.response.0)?,
+            })),
+            RouteBinding::FileServer(binding) => Ok(json!({
+                "binding-type": "file-server",
+                "response": expression_text(&binding.response.0)?,
+            })),
+        }
+    }
+
+    fn expression_text(expr: &Expr) -> Result<String, String> {
+        expression::to_string(expr).map_err(|err| err.to_string())
+    }
+
+    // Every route responds with the same body, which is the `body` of the response mapping.
+    // File server routes respond with the asset instead, of a type only known at runtime
+    fn get_responses(
+        route: &Route,
+        component: Option<&ComponentMetadata>,
+        functions: &[FunctionDefinition],
+    ) -> Value {
+        let response = &route.binding.response().0;
+
+        let (description, response_type) = match &route.binding {
+            RouteBinding::WitWorker(_) => (
+                "Response of the worker",
+                component.and_then(|component| {
+                    let functions = FunctionScope {
+                        component: Some(component),
+                        functions,
+                        ..FunctionScope::default()
+                    };
+                    type_check(response, &response_scope(route), functions).ok()
+                }),
+            ),
+            RouteBinding::Static(_) => (
+                "Response of the gateway",
+                type_check(
+                    response,
+                    &request_scope(route),
+                    FunctionScope {
+                        functions,
+                        ..FunctionScope::default()
+                    },
+                )
+                .ok(),
+            ),
+            RouteBinding::FileServer(_) => {
+                return json!({
+                    "200": {
+                        "description": "Asset served by the gateway",
+                        "content": {
+                            "*/*": { "schema": { "type": "string", "format": "binary" } },
+                        },
+                    },
+                    "404": { "description": "Asset not found" },
+                })
+            }
+        };
+
+        let body_type = response_type.and_then(|typ| match typ {
+            InferredType::Record(fields) => fields
+                .into_iter()
+                .find(|(name, _)| name == "body")
+                .map(|(_, typ)| typ),
+            _ => None,
+        });
+
+        let mut content = json!({ "description": description });
+
+        match body_type {
+            Some(InferredType::Unit) => {}
+            Some(typ) => {
+                content["content"] = json!({
+                    "application/json": { "schema": type_schema(&typ) },
+                })
+            }
+            None => {
+                content["content"] = json!({
+                    "application/json": { "schema": {} },
+                })
+            }
+        }
+
+        let mut status_codes = vec![];
+        for status in response_status_codes(response) {
+            if !status_codes.contains(&status) {
+                status_codes.push(status);
+            }
+        }
+
+        let responses: Map<String, Value> = if status_codes.is_empty() {
+            vec![("default".to_string(), content)].into_iter().collect()
+        } else {
+            status_codes
+                .into_iter()
+                .map(|status| (status.to_string(), content.clone()))
+                .collect()
+        };
+
+        Value::Object(responses)
+    }
+
+    // The status codes of the records the response mapping can result in, if they are literals
+    fn response_status_codes(expr: &Expr) -> Vec<u64> {
+        match expr {
+            Expr::Record(fields) => fields
+                .iter()
+                .filter_map(|(name, value)| match value.as_ref() {
+                    Expr::Number(InnerNumber::UnsignedInteger(status)) if name == "status" => {
+                        Some(*status)
+                    }
+                    _ => None,
+                })
+                .collect(),
+            Expr::Cond(_, left, right) => {
+                let mut status_codes = response_status_codes(left);
+                status_codes.extend(response_status_codes(right));
+                status_codes
+            }
+            Expr::PatternMatch(_, arms) => arms
+                .iter()
+                .flat_map(|arm| response_status_codes(&arm.0 .1))
+                .collect(),
+            Expr::Multiple(exprs) => exprs.last().map(response_status_codes).unwrap_or_default(),
+            _ => vec![],
+        }
+    }
+
+    fn binding_expressions(route: &Route) -> Vec<&Expr> {
+        let mut exprs = vec![&route.binding.response().0];
+
+        if let Some(binding) = route.binding.worker_binding() {
+            exprs.push(&binding.worker_name);
+
+            if let Some(idempotency_key) = &binding.idempotency_key {
+                exprs.push(idempotency_key);
+            }
+
+            exprs.extend(binding.workers.iter().map(|worker| &worker.worker_name));
+        }
+
+        if let Some(key) = route
+            .rate_limit
+            .as_ref()
+            .and_then(|rate_limit| rate_limit.key.as_ref())
+        {
+            exprs.push(key);
+        }
+
+        exprs
+    }
+
+    // The parts of the request the bindings of a route read, along with their schema where they
+    // are passed to a function of the component
+    #[derive(Default)]
+    struct RequestSchema {
+        path_variables: Map<String, Value>,
+        headers: Vec<String>,
+        body: Option<Value>,
+        body_fields: Map<String, Value>,
+    }
+
+    impl RequestSchema {
+        fn add_references(&mut self, expr: &Expr) {
+            match request_field(expr) {
+                Some(("headers", Some(name))) => {
+                    if !self.headers.iter().any(|header| header == name) {
+                        self.headers.push(name.to_string());
+                    }
+                }
+                Some(("body", Some(field))) => {
+                    self.body_fields
+                        .entry(field.to_string())
+                        .or_insert_with(|| json!({}));
+                }
+                Some(("body", None)) => {
+                    self.body.get_or_insert_with(|| json!({}));
+                }
+                Some(_) => {}
+                None => {
+                    for sub_expr in sub_expressions(expr) {
+                        self.add_references(sub_expr);
+                    }
+                }
+            }
+        }
+
+        fn add_call_arguments(&mut self, expr: &Expr, component: &ComponentMetadata) {
+            if let Expr::Call(function_name, args) = expr {
+                if let Some(function) = component.function_by_name(function_name) {
+                    for (arg, parameter) in args.iter().zip(function.parameters.iter()) {
+                        let typ: AnalysedType = parameter.typ.clone().into();
+                        let schema = type_schema(&InferredType::from(&typ));
+
+                        match request_field(arg) {
+                            Some(("body", None)) => self.body = Some(schema),
+                            Some(("body", Some(field))) => {
+                                self.body_fields.insert(field.to_string(), schema);
+                            }
+                            Some(("path", Some(name))) => {
+                                self.path_variables.insert(name.to_string(), schema);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            for sub_expr in sub_expressions(expr) {
+                self.add_call_arguments(sub_expr, component);
+            }
+        }
+
+        fn body(&self) -> Option<Value> {
+            match &self.body {
+                Some(body) => Some(body.clone()),
+                None if self.body_fields.is_empty() => None,
+                None => Some(json!({
+                    "type": "object",
+                    "properties": self.body_fields,
+                    "required": self.body_fields.keys().collect::<Vec<_>>(),
+                })),
+            }
+        }
+
+        fn parameters(&self, path: &AllPathPatterns) -> Vec<Value> {
+            let path_variables = path
+                .path_patterns
+                .iter()
+                .filter_map(|pattern| match pattern {
+                    PathPattern::Var(var_info) => Some((&var_info.key_name, "path")),
+                    PathPattern::Literal(_) => None,
+                });
+
+            let query_params = path
+                .query_params
+                .iter()
+                .map(|query_info| (&query_info.key_name, "query"));
+
+            let mut parameters: Vec<Value> = path_variables
+                .chain(query_params)
+                .map(|(name, location)| {
+                    json!({
+                        "name": name,
+                        "in": location,
+                        "required": true,
+                        "schema": self
+                            .path_variables
+                            .get(name)
+                            .cloned()
+                            .unwrap_or_else(|| json!({ "type": "string" })),
+                    })
+                })
+                .collect();
+
+            parameters.extend(self.headers.iter().map(|name| {
+                json!({
+                    "name": name,
+                    "in": "header",
+                    "required": true,
+                    "schema": { "type": "string" },
+                })
+            }));
+
+            parameters
+        }
+    }
+
+    // Matches `request.<section>` and `request.<section>.<name>`
+    fn request_field(expr: &Expr) -> Option<(&str, Option<&str>)> {
+        match expr {
+            Expr::SelectField(inner, section) if is_request(inner) => {
+                Some((section.as_str(), None))
+            }
+            Expr::SelectField(inner, name) => match inner.as_ref() {
+                Expr::SelectField(request, section) if is_request(request) => {
+                    Some((section.as_str(), Some(name.as_str())))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn is_request(expr: &Expr) -> bool {
+        matches!(expr, Expr::Identifier(name) if name == "request")
+    }
+
+    fn sub_expressions(expr: &Expr) -> Vec<&Expr> {
+        match expr {
+            Expr::Let(_, inner)
+            | Expr::SelectField(inner, _)
+            | Expr::SelectIndex(inner, _)
+            | Expr::Not(inner)
+            | Expr::Lambda(_, inner) => vec![inner.as_ref()],
+            Expr::Sequence(exprs)
+            | Expr::Tuple(exprs)
+            | Expr::Concat(exprs)
+            | Expr::Multiple(exprs)
+            | Expr::Call(_, exprs) => exprs.iter().collect(),
+            Expr::Record(fields) => fields.iter().map(|(_, value)| value.as_ref()).collect(),
+            Expr::GreaterThan(left, right)
+            | Expr::GreaterThanOrEqualTo(left, right)
+            | Expr::LessThanOrEqualTo(left, right)
+            | Expr::EqualTo(left, right)
+            | Expr::LessThan(left, right) => vec![left.as_ref(), right.as_ref()],
+            Expr::Cond(predicate, left, right) => {
+                vec![predicate.as_ref(), left.as_ref(), right.as_ref()]
+            }
+            Expr::PatternMatch(match_expr, arms) => std::iter::once(match_expr.as_ref())
+                .chain(arms.iter().map(|arm| arm.0 .1.as_ref()))
+                .collect(),
+            Expr::Option(Some(inner)) | Expr::Result(Ok(inner)) | Expr::Result(Err(inner)) => {
+                vec![inner.as_ref()]
+            }
+            Expr::Option(None)
+            | Expr::Literal(_)
+            | Expr::Number(_)
+            | Expr::Flags(_)
+            | Expr::Identifier(_)
+            | Expr::Boolean(_) => vec![],
+        }
+    }
+
+    // Mirrors how the values of a component are rendered as JSON
+    fn type_schema(typ: &InferredType) -> Value {
+        match typ {
+            InferredType::Bool => json!({ "type": "boolean" }),
+            InferredType::S8 | InferredType::S16 | InferredType::S32 => {
+                json!({ "type": "integer", "format": "int32" })
+            }
+            InferredType::U8 | InferredType::U16 | InferredType::U32 => {
+                json!({ "type": "integer", "format": "int32", "minimum": 0 })
+            }
+            InferredType::S64 => json!({ "type": "integer", "format": "int64" }),
+            InferredType::U64 => json!({ "type": "integer", "format": "int64", "minimum": 0 }),
+            InferredType::F32 => json!({ "type": "number", "format": "float" }),
+            InferredType::F64 => json!({ "type": "number", "format": "double" }),
+            InferredType::Number(NumberLiteral::Unsigned | NumberLiteral::Signed) => {
+                json!({ "type": "integer" })
+            }
+            InferredType::Number(NumberLiteral::Float) => json!({ "type": "number" }),
+            InferredType::Chr | InferredType::Str | InferredType::Resource => {
+                json!({ "type": "string" })
+            }
+            InferredType::List(inner) => json!({ "type": "array", "items": type_schema(inner) }),
+            InferredType::Tuple(items) => json!({
+                "type": "array",
+                "items": {},
+                "minItems": items.len(),
+                "maxItems": items.len(),
+            }),
+            InferredType::Record(fields) => object_schema(
+                fields
+                    .iter()
+                    .map(|(name, typ)| (name.clone(), type_schema(typ)))
+                    .collect(),
+            ),
+            InferredType::Flags(flags) => json!({
+                "type": "array",
+                "items": { "type": "string", "enum": flags },
+            }),
+            InferredType::Enum(cases) => json!({ "type": "string", "enum": cases }),
+            InferredType::Option(inner) => {
+                let mut schema = type_schema(inner);
+                schema["nullable"] = json!(true);
+                schema
+            }
+            InferredType::Result { ok, error } => json!({
+                "oneOf": [case_schema("ok", ok.as_deref()), case_schema("err", error.as_deref())],
+            }),
+            InferredType::Variant(cases) => json!({
+                "oneOf": cases
+                    .iter()
+                    .map(|(name, typ)| case_schema(name, typ.as_ref()))
+                    .collect::<Vec<_>>(),
+            }),
+            InferredType::OpenRecord(_) => json!({ "type": "object" }),
+            InferredType::Primitive | InferredType::Unknown | InferredType::Unit => json!({}),
+        }
+    }
+
+    // Cases are rendered as an object with a single field named after the case
+    fn case_schema(name: &str, typ: Option<&InferredType>) -> Value {
+        let schema = typ.map(type_schema).unwrap_or_else(|| json!({}));
+        object_schema(vec![(name.to_string(), schema)])
+    }
+
+    fn object_schema(properties: Vec<(String, Value)>) -> Value {
+        let required: Vec<&String> = properties.iter().map(|(name, _)| name).collect();
+
+        json!({
+            "type": "object",
+            "required": required,
+            "properties": properties.iter().cloned().collect::<Map<String, Value>>(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api_definition::http::{AllPathPatterns, MethodPattern, Route};
+    use crate::api_definition::http::{AllPathPatterns, MethodPattern, Route, SecurityScheme};
     use crate::expression::{Expr, InnerNumber};
-    use crate::worker_binding::{GolemWorkerBinding, ResponseMapping};
+    use crate::worker_binding::{GolemWorkerBinding, ResponseMapping, RouteBinding};
     use golem_common::model::ComponentId;
+    use golem_service_base::model::{
+        ComponentMetadata, ComponentName, Export, ExportFunction, FunctionParameter,
+        FunctionResult, ProtectedComponentId, UserComponentId, VersionedComponentId,
+    };
+    use golem_wasm_ast::analysis::AnalysedType;
     use openapiv3::PathItem;
     use serde_json::json;
     use uuid::Uuid;
@@ -259,7 +1153,7 @@ mod tests {
             Ok(Route {
                 path: path_pattern,
                 method: MethodPattern::Get,
-                binding: GolemWorkerBinding {
+                binding: RouteBinding::WitWorker(GolemWorkerBinding {
                     worker_name: Expr::Concat(vec![
                         Expr::Literal("worker-".to_string()),
                         Expr::SelectField(
@@ -272,6 +1166,7 @@ mod tests {
                     ]),
                     component_id: ComponentId(Uuid::nil()),
                     idempotency_key: Some(Expr::Literal("test-key".to_string())),
+                    workers: vec![],
                     response: ResponseMapping(Expr::Record(
                         vec![
                             (
@@ -302,8 +1197,273 @@ mod tests {
                         .into_iter()
                         .collect()
                     ))
-                }
+                }),
+                security: None,
+                rate_limit: None,
+                stream: None,
             })
         );
     }
+
+    fn shopping_cart_component(component_id: ComponentId) -> Component {
+        let item = AnalysedType::Record(vec![
+            ("product-id".to_string(), AnalysedType::Str),
+            ("quantity".to_string(), AnalysedType::U32),
+        ]);
+
+        let versioned_component_id = VersionedComponentId {
+            component_id,
+            version: 0,
+        };
+
+        Component {
+            versioned_component_id: versioned_component_id.clone(),
+            user_component_id: UserComponentId {
+                versioned_component_id: versioned_component_id.clone(),
+            },
+            protected_component_id: ProtectedComponentId {
+                versioned_component_id,
+            },
+            component_name: ComponentName("shopping-cart".to_string()),
+            component_size: 0,
+            metadata: ComponentMetadata {
+                exports: vec![
+                    Export::Function(ExportFunction {
+                        name: "get-cart-contents".to_string(),
+                        parameters: vec![FunctionParameter {
+                            name: "user-id".to_string(),
+                            typ: AnalysedType::Str.into(),
+                        }],
+                        results: vec![FunctionResult {
+                            name: None,
+                            typ: AnalysedType::List(Box::new(item.clone())).into(),
+                        }],
+                    }),
+                    Export::Function(ExportFunction {
+                        name: "add-item".to_string(),
+                        parameters: vec![FunctionParameter {
+                            name: "item".to_string(),
+                            typ: item.into(),
+                        }],
+                        results: vec![FunctionResult {
+                            name: None,
+                            typ: AnalysedType::Bool.into(),
+                        }],
+                    }),
+                ],
+                producers: vec![],
+            },
+            files: vec![],
+            retention_policy: None,
+        }
+    }
+
+    fn shopping_cart_api() -> HttpApiDefinition {
+        serde_yaml::from_str(
+            r#"
+          id: cart-api
+          version: 0.0.1
+          securitySchemes:
+          - name: service-keys
+            scheme:
+              type: apiKey
+              location: Header
+              paramName: x-api-key
+              keys:
+              - name: service-a
+                key: secret
+          cors:
+            allowedOrigins: ['https://shop.example']
+            maxAge: 600
+          functions:
+          - name: cart-name
+            params: [id]
+            body: 'cart-${lower(id)}'
+          routes:
+          - method: Get
+            path: cart/{user-id}?{currency}
+            binding:
+              componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+              workerName: '${cart-name(request.path.user-id)}'
+              response: '${let result = get-cart-contents(request.path.user-id); {status: 200, body: result}}'
+          - method: Post
+            path: cart/{user-id}
+            security:
+              scheme: service-keys
+              scopes: []
+            rateLimit:
+              requests: 10
+              periodSeconds: 60
+              key: '${request.auth.claims.sub}'
+            binding:
+              componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+              workerName: 'cart-${request.path.user-id}'
+              idempotencyKey: '${request.headers.x-request-id}'
+              response: '${let result = add-item(request.body.item); {status: 201, body: result}}'
+        "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_get_openapi() {
+        let api_definition = shopping_cart_api();
+        let component = shopping_cart_component(
+            api_definition.routes[0]
+                .binding
+                .component_id()
+                .cloned()
+                .unwrap(),
+        );
+
+        let openapi =
+            serde_json::to_value(get_openapi(&api_definition, &[component]).unwrap()).unwrap();
+
+        assert_eq!(openapi["x-golem-api-definition-id"], json!("cart-api"));
+        assert_eq!(openapi["x-golem-api-definition-version"], json!("0.0.1"));
+        assert_eq!(
+            openapi["x-golem-functions"],
+            json!([{ "name": "cart-name", "params": ["id"], "body": "cart-${lower(id)}" }])
+        );
+        assert_eq!(
+            openapi["components"]["securitySchemes"]["service-keys"],
+            json!({ "type": "apiKey", "in": "header", "name": "x-api-key" })
+        );
+        let exported_key = &openapi["x-golem-security-schemes"][0]["scheme"]["keys"][0];
+        assert_eq!(exported_key["name"], json!("service-a"));
+        assert_eq!(exported_key["key"], Value::Null);
+        assert!(exported_key["keyHash"].is_string());
+        assert_eq!(
+            openapi["x-golem-cors"]["allowedOrigins"],
+            json!(["https://shop.example"])
+        );
+
+        let get = &openapi["paths"]["/cart/{user-id}"]["get"];
+        assert_eq!(
+            get["parameters"],
+            json!([
+                { "name": "user-id", "in": "path", "required": true, "schema": { "type": "string" } },
+                { "name": "currency", "in": "query", "required": true, "schema": { "type": "string" } },
+            ])
+        );
+        assert_eq!(
+            get["responses"]["200"]["content"]["application/json"]["schema"]["items"]["properties"]
+                ["quantity"],
+            json!({ "type": "integer", "format": "int32", "minimum": 0 })
+        );
+
+        let post = &openapi["paths"]["/cart/{user-id}"]["post"];
+        assert_eq!(post["security"], json!([{ "service-keys": [] }]));
+        assert_eq!(
+            post["x-golem-rate-limit"],
+            json!({ "requests": 10, "periodSeconds": 60, "key": "${request.auth.claims.sub}" })
+        );
+        assert_eq!(post["parameters"][1]["name"], json!("x-request-id"));
+        assert_eq!(post["parameters"][1]["in"], json!("header"));
+        assert_eq!(
+            post["requestBody"]["content"]["application/json"]["schema"]["properties"]["item"]
+                ["required"],
+            json!(["product-id", "quantity"])
+        );
+        assert_eq!(
+            post["responses"]["201"]["content"]["application/json"]["schema"],
+            json!({ "type": "boolean" })
+        );
+    }
+
+    #[test]
+    fn test_get_openapi_round_trip() {
+        // Secrets are hashed when the api definition is registered
+        let api_definition = shopping_cart_api();
+        let api_definition = HttpApiDefinition {
+            security_schemes: api_definition
+                .security_schemes
+                .into_iter()
+                .map(SecurityScheme::with_hashed_secrets)
+                .collect(),
+            ..api_definition
+        };
+
+        let openapi = get_openapi(&api_definition, &[]).unwrap();
+        let imported = get_api_definition(openapi).unwrap();
+
+        assert_eq!(imported.id, api_definition.id);
+        assert_eq!(imported.version, api_definition.version);
+        assert_eq!(imported.functions, api_definition.functions);
+        assert_eq!(imported.security_schemes, api_definition.security_schemes);
+        assert_eq!(imported.cors, api_definition.cors);
+        assert_eq!(imported.routes, api_definition.routes);
+    }
+
+    #[test]
+    fn test_get_api_definition_with_undescribed_security_scheme() {
+        let openapi: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "cart-api", "version": "0.0.1" },
+            "paths": {},
+            "components": {
+                "securitySchemes": {
+                    "bearer": { "type": "http", "scheme": "bearer" },
+                },
+            },
+            "x-golem-api-definition-id": "cart-api",
+            "x-golem-api-definition-version": "0.0.1",
+        }))
+        .unwrap();
+
+        assert_eq!(
+            get_api_definition(openapi),
+            Err("Security scheme bearer is not described in x-golem-security-schemes".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_openapi_static_routes() {
+        let api_definition: HttpApiDefinition = serde_yaml::from_str(
+            r#"
+          id: website
+          version: 0.0.1
+          routes:
+          - method: Get
+            path: old
+            binding:
+              type: static
+              response: '${{status: 302, body: "moved"}}'
+          - method: Get
+            path: assets/{file}
+            binding:
+              type: file-server
+              response: 'website/${request.path.file}'
+        "#,
+        )
+        .unwrap();
+
+        let openapi = get_openapi(&api_definition, &[]).unwrap();
+        let exported = serde_json::to_value(&openapi).unwrap();
+
+        assert_eq!(
+            exported["paths"]["/old"]["get"]["responses"]["302"]["content"]["application/json"]
+                ["schema"],
+            json!({ "type": "string" })
+        );
+        assert_eq!(
+            exported["paths"]["/assets/{file}"]["get"]["x-golem-worker-bridge"]["binding-type"],
+            json!("file-server")
+        );
+
+        let imported = get_api_definition(openapi).unwrap();
+
+        assert_eq!(
+            imported
+                .routes
+                .iter()
+                .map(|route| &route.binding)
+                .collect::<Vec<_>>(),
+            api_definition
+                .routes
+                .iter()
+                .map(|route| &route.binding)
+                .collect::<Vec<_>>()
+        );
+    }
 }
@@ -1,5 +1,15 @@
+pub use cors::*;
+pub use function::*;
 pub use http_api_definition::*;
 pub use http_oas_api_definition::*;
+pub use rate_limit::*;
+pub use security::*;
+pub use stream::*;
 
+mod cors;
+mod function;
 mod http_api_definition;
 mod http_oas_api_definition;
+mod rate_limit;
+mod security;
+mod stream;
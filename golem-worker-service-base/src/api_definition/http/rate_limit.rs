@@ -0,0 +1,31 @@
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+use crate::expression::Expr;
+
+// Token bucket limiting the requests made to a route, kept separately for each caller.
+// The bucket holds up to `burst` tokens, and is refilled with `requests` tokens every period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimit {
+    pub requests: u64,
+    pub period_seconds: u64,
+    // Defaults to `requests`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u64>,
+    // Expression identifying the caller, such as `${request.headers.x-api-key}` or
+    // `${request.auth.claims.sub}`. Callers are identified by their IP address if missing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<Expr>,
+}
+
+impl RateLimit {
+    pub fn capacity(&self) -> u64 {
+        self.burst.unwrap_or(self.requests)
+    }
+
+    // Number of tokens added back to the bucket every millisecond
+    pub fn refill_rate(&self) -> f64 {
+        self.requests.max(1) as f64 / (self.period_seconds.max(1) * 1000) as f64
+    }
+}
@@ -0,0 +1,221 @@
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::SaltString;
+use argon2::{Argon2, PasswordHasher, PasswordVerifier};
+use base64::Engine;
+use bincode::{Decode, Encode};
+use poem_openapi::{Enum, Object, Union};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+// A security scheme declared once in the api definition, and referred to by name from the
+// routes it protects
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, Object)]
+pub struct SecurityScheme {
+    pub name: String,
+    pub scheme: SecuritySchemeKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, Union)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[oai(discriminator_name = "type", one_of = true, rename_all = "camelCase")]
+pub enum SecuritySchemeKind {
+    ApiKey(ApiKeySecurityScheme),
+    JwtBearer(JwtBearerSecurityScheme),
+    Basic(BasicSecurityScheme),
+}
+
+impl SecurityScheme {
+    // Replaces the plaintext api keys and passwords given when registering an api definition
+    // with their hashes, so the secrets are neither stored nor returned
+    pub fn with_hashed_secrets(self) -> Self {
+        let scheme = match self.scheme {
+            SecuritySchemeKind::ApiKey(api_key) => {
+                SecuritySchemeKind::ApiKey(ApiKeySecurityScheme {
+                    keys: api_key
+                        .keys
+                        .into_iter()
+                        .map(|key| match key.key {
+                            Some(plaintext) => ApiKey {
+                                key: None,
+                                key_hash: Some(SecretHash::new(&plaintext).0),
+                                ..key
+                            },
+                            None => key,
+                        })
+                        .collect(),
+                    ..api_key
+                })
+            }
+            SecuritySchemeKind::Basic(basic) => SecuritySchemeKind::Basic(BasicSecurityScheme {
+                users: basic
+                    .users
+                    .into_iter()
+                    .map(|user| match user.password {
+                        Some(plaintext) => BasicAuthUser {
+                            password: None,
+                            password_hash: Some(PasswordHash::new(&plaintext).0),
+                            ..user
+                        },
+                        None => user,
+                    })
+                    .collect(),
+            }),
+            scheme => scheme,
+        };
+
+        SecurityScheme {
+            name: self.name,
+            scheme,
+        }
+    }
+}
+
+impl SecuritySchemeKind {
+    // The value of the WWW-Authenticate header sent back along with a 401 response
+    pub fn challenge(&self) -> Option<&'static str> {
+        match self {
+            SecuritySchemeKind::ApiKey(_) => None,
+            SecuritySchemeKind::JwtBearer(_) => Some("Bearer"),
+            SecuritySchemeKind::Basic(_) => Some("Basic"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiKeySecurityScheme {
+    pub location: ApiKeyLocation,
+    // Name of the header or query parameter holding the key
+    pub param_name: String,
+    pub keys: Vec<ApiKey>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode, Enum)]
+pub enum ApiKeyLocation {
+    Header,
+    Query,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiKey {
+    // Identifies the owner of the key, available as the `sub` claim
+    pub name: String,
+    // The plaintext key is only accepted when registering the api definition, it is replaced
+    // by its hash before the definition is stored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub key_hash: Option<String>,
+    #[serde(default)]
+    #[oai(default)]
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct JwtBearerSecurityScheme {
+    // Name of a JSON Web Key Set source configured in the worker service
+    pub jwks: String,
+    pub issuer: Option<String>,
+    pub audience: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, Object)]
+pub struct BasicSecurityScheme {
+    pub users: Vec<BasicAuthUser>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct BasicAuthUser {
+    pub username: String,
+    // Like api keys, the plaintext password is replaced by its hash on registration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    pub password_hash: Option<String>,
+    #[serde(default)]
+    #[oai(default)]
+    pub scopes: Vec<String>,
+}
+
+// Security requirement of a single route: the name of the scheme authenticating the caller,
+// and the scopes the caller has to be granted
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode, Object)]
+pub struct RouteSecurity {
+    pub scheme: String,
+    #[serde(default)]
+    #[oai(default)]
+    pub scopes: Vec<String>,
+}
+
+// A salted SHA-256 hash of an api key, formatted as `<salt>:<hash>` with both parts base64
+// encoded. Api keys are random, so a fast hash is enough to check them on every request
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecretHash(pub String);
+
+impl SecretHash {
+    pub fn new(secret: &str) -> Self {
+        let salt = Uuid::new_v4().into_bytes();
+        Self::with_salt(&salt, secret)
+    }
+
+    fn with_salt(salt: &[u8], secret: &str) -> Self {
+        let hash = Self::digest(salt, secret);
+        let engine = base64::engine::general_purpose::STANDARD;
+        SecretHash(format!("{}:{}", engine.encode(salt), engine.encode(hash)))
+    }
+
+    fn digest(salt: &[u8], secret: &str) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(salt);
+        hasher.update(secret.as_bytes());
+        hasher.finalize().to_vec()
+    }
+
+    // Checks the secret in constant time, malformed hashes never match
+    pub fn verify(&self, secret: &str) -> bool {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let parsed = self
+            .0
+            .split_once(':')
+            .and_then(|(salt, hash)| Some((engine.decode(salt).ok()?, engine.decode(hash).ok()?)));
+
+        match parsed {
+            Some((salt, expected)) => constant_time_eq(&Self::digest(&salt, secret), &expected),
+            None => false,
+        }
+    }
+}
+
+// An argon2 hash of a password in the PHC string format. Passwords are chosen by people, so
+// unlike api keys they are hashed with a key derivation function that is slow to brute force
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasswordHash(pub String);
+
+impl PasswordHash {
+    pub fn new(password: &str) -> Self {
+        let salt = SaltString::generate(&mut OsRng);
+        let hash = Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .expect("Failed to hash password");
+        PasswordHash(hash.to_string())
+    }
+
+    // Malformed hashes never match
+    pub fn verify(&self, password: &str) -> bool {
+        argon2::PasswordHash::new(&self.0).is_ok_and(|hash| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok()
+        })
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
@@ -0,0 +1,37 @@
+use bincode::{Decode, Encode};
+use poem_openapi::{Enum, Object};
+use serde::{Deserialize, Serialize};
+
+// Turns a route into a long lived connection. Every message sent by the client on a WebSocket is
+// evaluated as a separate request to the worker, with the message as the request body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct StreamBinding {
+    pub protocol: StreamProtocol,
+    pub source: StreamSource,
+    // How often the response is re-evaluated for SSE routes streaming results. Defaults to 1 second
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poll_interval_seconds: Option<u64>,
+}
+
+impl StreamBinding {
+    pub fn poll_interval_seconds(&self) -> u64 {
+        self.poll_interval_seconds.unwrap_or(1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Enum)]
+pub enum StreamProtocol {
+    WebSocket,
+    Sse,
+}
+
+// What is sent back to the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Enum)]
+pub enum StreamSource {
+    // The log events (stdout, stderr and log calls) emitted by the worker
+    Output,
+    // The response mapping evaluated against the result of the export called by the binding
+    Results,
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use figment::providers::{Env, Format, Toml};
@@ -10,6 +12,9 @@ use uuid::Uuid;
 use golem_common::config::{RedisConfig, RetryConfig};
 use golem_service_base::routing_table::RoutingTableConfig;
 
+use crate::http::RequestBodyLimits;
+use crate::service::asset_store::AssetUploadLimits;
+
 // The base configuration for the worker service
 // If there are extra cofigurations for custom services,
 // its preferred to reuse base config.
@@ -25,6 +30,91 @@ pub struct WorkerServiceBaseConfig {
     pub worker_grpc_port: u16,
     pub routing_table: RoutingTableConfig,
     pub worker_executor_client_cache: WorkerExecutorClientCacheConfig,
+    pub rate_limit_store: RateLimitStoreConfig,
+    pub asset_store: AssetStoreConfig,
+    #[serde(default)]
+    pub asset_upload_limits: AssetUploadLimits,
+    #[serde(default)]
+    pub request_body_limits: RequestBodyLimits,
+    #[serde(default)]
+    pub jwks: JwksConfig,
+}
+
+// The JSON Web Key Sets of JWT bearer security schemes, by the name api definitions refer to them
+// with. Loaded key sets are read again after the refresh interval to pick up rotated keys
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct JwksConfig {
+    #[serde(with = "humantime_serde")]
+    pub refresh_interval: Duration,
+    pub sources: HashMap<String, PathBuf>,
+}
+
+impl Default for JwksConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval: Duration::from_secs(60 * 5),
+            sources: HashMap::new(),
+        }
+    }
+}
+
+// Where the token buckets of rate limited routes are kept. Gateways running several
+// worker service instances need to share them through redis
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum RateLimitStoreConfig {
+    #[default]
+    Redis,
+    InMemory(InMemoryRateLimitStoreConfig),
+}
+
+// Buckets idle for longer than the time it takes to refill them are full again, so evicting them
+// only relaxes the limit if the time to idle is shorter than that
+#[derive(Clone, Debug, Deserialize)]
+pub struct InMemoryRateLimitStoreConfig {
+    #[serde(with = "humantime_serde")]
+    pub bucket_time_to_idle: Duration,
+    #[serde(with = "humantime_serde")]
+    pub eviction_period: Duration,
+}
+
+impl Default for InMemoryRateLimitStoreConfig {
+    fn default() -> Self {
+        Self {
+            bucket_time_to_idle: Duration::from_secs(60 * 60 * 24),
+            eviction_period: Duration::from_secs(60),
+        }
+    }
+}
+
+// Where the assets served by file server routes are read from and uploaded to. Gateways running
+// several worker service instances need to share them through S3
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum AssetStoreConfig {
+    S3(S3AssetStoreConfig),
+    LocalFileSystem(LocalFileSystemAssetStoreConfig),
+    InMemory,
+}
+
+impl Default for AssetStoreConfig {
+    fn default() -> Self {
+        AssetStoreConfig::LocalFileSystem(LocalFileSystemAssetStoreConfig {
+            root: PathBuf::from("../data/assets"),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct S3AssetStoreConfig {
+    pub bucket_name: String,
+    pub object_prefix: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct LocalFileSystemAssetStoreConfig {
+    pub root: PathBuf,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -70,6 +160,11 @@ impl Default for WorkerServiceBaseConfig {
             worker_grpc_port: 9092,
             routing_table: RoutingTableConfig::default(),
             worker_executor_client_cache: WorkerExecutorClientCacheConfig::default(),
+            rate_limit_store: RateLimitStoreConfig::default(),
+            asset_store: AssetStoreConfig::default(),
+            asset_upload_limits: AssetUploadLimits::default(),
+            request_body_limits: RequestBodyLimits::default(),
+            jwks: JwksConfig::default(),
         }
     }
 }
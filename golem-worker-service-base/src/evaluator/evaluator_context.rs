@@ -1,3 +1,4 @@
+use crate::api_definition::http::FunctionDefinition;
 use crate::evaluator::evaluator_context::internal::create_record;
 use crate::evaluator::getter::GetError;
 use crate::evaluator::path::Path;
@@ -16,6 +17,17 @@ use std::fmt::Display;
 pub struct EvaluationContext {
     pub variables: Option<TypeAnnotatedValue>,
     pub analysed_functions: Vec<AnalysedFunction>,
+    // The other workers of the binding, whose functions are called as `<name>.<function>(..)`
+    pub workers: Vec<NamedWorker>,
+    // The functions defined in the API definition
+    pub functions: Vec<FunctionDefinition>,
+}
+
+#[derive(Clone)]
+pub struct NamedWorker {
+    pub name: String,
+    pub worker_detail: WorkerDetail,
+    pub analysed_functions: Vec<AnalysedFunction>,
 }
 
 #[async_trait]
@@ -51,6 +63,8 @@ impl EvaluationContext {
         EvaluationContext {
             variables: None,
             analysed_functions: vec![],
+            workers: vec![],
+            functions: vec![],
         }
     }
 
@@ -73,6 +87,36 @@ impl EvaluationContext {
         }
     }
 
+    // The variables merged into this context since it was cloned from the original one
+    pub fn variables_added_since(
+        &self,
+        original: &EvaluationContext,
+    ) -> Option<TypeAnnotatedValue> {
+        match (&self.variables, &original.variables) {
+            (
+                Some(TypeAnnotatedValue::Record { value, typ }),
+                Some(TypeAnnotatedValue::Record {
+                    value: original_value,
+                    typ: original_typ,
+                }),
+            ) => {
+                let value = value.get(original_value.len()..).unwrap_or_default();
+                let typ = typ.get(original_typ.len()..).unwrap_or_default();
+
+                if value.is_empty() {
+                    None
+                } else {
+                    Some(TypeAnnotatedValue::Record {
+                        value: value.to_vec(),
+                        typ: typ.to_vec(),
+                    })
+                }
+            }
+            (Some(variables), None) => Some(variables.clone()),
+            _ => None,
+        }
+    }
+
     pub fn get_variable_value(&self, variable_name: &str) -> Result<TypeAnnotatedValue, GetError> {
         match &self.variables {
             Some(variables) => variables.get(&Path::from_key(variable_name)),
@@ -92,9 +136,36 @@ impl EvaluationContext {
         EvaluationContext {
             variables: Some(merged.clone()),
             analysed_functions: functions,
+            workers: vec![],
+            functions: vec![],
         }
     }
 
+    pub fn with_workers(self, workers: Vec<NamedWorker>) -> Self {
+        EvaluationContext { workers, ..self }
+    }
+
+    pub fn with_functions(self, functions: Vec<FunctionDefinition>) -> Self {
+        EvaluationContext { functions, ..self }
+    }
+
+    pub fn function(&self, name: &str) -> Option<&FunctionDefinition> {
+        self.functions.iter().find(|function| function.name == name)
+    }
+
+    // Splits a call such as users.get-user into the named worker and its function
+    pub fn named_worker_function<'a>(
+        &self,
+        function_name: &'a str,
+    ) -> Option<(&NamedWorker, &'a str)> {
+        let (name, function_name) = function_name.split_once('.')?;
+
+        self.workers
+            .iter()
+            .find(|worker| worker.name == name)
+            .map(|worker| (worker, function_name))
+    }
+
     pub fn from_worker_detail(worker_detail: &WorkerDetail) -> Self {
         let typed_value = worker_detail.clone().to_type_annotated_value();
         let worker_data = create_record("worker", typed_value);
@@ -102,6 +173,8 @@ impl EvaluationContext {
         EvaluationContext {
             variables: Some(worker_data),
             analysed_functions: vec![],
+            workers: vec![],
+            functions: vec![],
         }
     }
 
@@ -111,6 +184,8 @@ impl EvaluationContext {
         EvaluationContext {
             variables: Some(variables),
             analysed_functions: vec![],
+            workers: vec![],
+            functions: vec![],
         }
     }
 
@@ -125,6 +200,8 @@ impl EvaluationContext {
             EvaluationContext {
                 variables: Some(worker_data),
                 analysed_functions: vec![],
+                workers: vec![],
+                functions: vec![],
             }
         } else {
             EvaluationContext::empty()
@@ -0,0 +1,254 @@
+use std::sync::Arc;
+
+use golem_wasm_ast::analysis::AnalysedType;
+use golem_wasm_rpc::TypeAnnotatedValue;
+
+use crate::api_definition::http::FunctionDefinition;
+use crate::evaluator::evaluator_context::EvaluationContext;
+use crate::evaluator::stdlib::Builtin;
+use crate::evaluator::{DefaultEvaluator, Evaluator};
+use crate::evaluator::{EvaluationError, EvaluationResult};
+use crate::expression::Expr;
+use crate::worker_bridge_execution::WorkerRequestExecutor;
+
+// A function passed to map, filter or fold
+enum FunctionArg<'a> {
+    Lambda(&'a [String], &'a Expr),
+    Defined(&'a FunctionDefinition),
+}
+
+// Evaluates a call to a function of the API definition or of the standard library. Returns None
+// when the call is to a worker function, which is left to the caller.
+pub(crate) async fn evaluate_function_call(
+    worker_executor: &Arc<dyn WorkerRequestExecutor + Sync + Send>,
+    function_name: &str,
+    args: &[Expr],
+    input: &EvaluationContext,
+) -> Option<Result<EvaluationResult, EvaluationError>> {
+    if input.named_worker_function(function_name).is_some() {
+        return None;
+    }
+
+    if let Some(function) = input.function(function_name) {
+        let result = match evaluate_args(worker_executor, args, input).await {
+            Ok(values) => call_defined_function(worker_executor, function, values, input).await,
+            Err(err) => Err(err),
+        };
+
+        return Some(result.map(|value| value.into()));
+    }
+
+    if input
+        .analysed_functions
+        .iter()
+        .any(|function| function.name == function_name)
+    {
+        return None;
+    }
+
+    let builtin = Builtin::from_name(function_name)?;
+
+    let result = if builtin.is_higher_order() {
+        call_higher_order(worker_executor, builtin, args, input).await
+    } else {
+        match evaluate_args(worker_executor, args, input).await {
+            Ok(values) => builtin.call(values).map_err(EvaluationError::Message),
+            Err(err) => Err(err),
+        }
+    };
+
+    Some(result.map(|value| value.into()))
+}
+
+async fn evaluate_args(
+    worker_executor: &Arc<dyn WorkerRequestExecutor + Sync + Send>,
+    args: &[Expr],
+    input: &EvaluationContext,
+) -> Result<Vec<TypeAnnotatedValue>, EvaluationError> {
+    let mut values = vec![];
+
+    for arg in args {
+        values.push(evaluate_value(worker_executor, arg, input).await?);
+    }
+
+    Ok(values)
+}
+
+async fn evaluate_value(
+    worker_executor: &Arc<dyn WorkerRequestExecutor + Sync + Send>,
+    expr: &Expr,
+    input: &EvaluationContext,
+) -> Result<TypeAnnotatedValue, EvaluationError> {
+    let evaluator = DefaultEvaluator::from_worker_request_executor(worker_executor.clone());
+
+    evaluator
+        .evaluate(expr, input)
+        .await?
+        .get_value()
+        .ok_or(EvaluationError::Message(
+            "Function parameter is evaluated to unit".to_string(),
+        ))
+}
+
+// The body of a function only sees its parameters and the functions defined before it
+async fn call_defined_function(
+    worker_executor: &Arc<dyn WorkerRequestExecutor + Sync + Send>,
+    function: &FunctionDefinition,
+    values: Vec<TypeAnnotatedValue>,
+    input: &EvaluationContext,
+) -> Result<TypeAnnotatedValue, EvaluationError> {
+    if function.params.len() != values.len() {
+        return Err(EvaluationError::Message(format!(
+            "Function {} expects {} arguments, but {} were given",
+            function.name,
+            function.params.len(),
+            values.len()
+        )));
+    }
+
+    let mut function_input = EvaluationContext::empty().with_functions(
+        FunctionDefinition::visible_from(&input.functions, &function.name),
+    );
+    function_input.merge_variables(&bind(&function.params, values));
+
+    evaluate_value(worker_executor, &function.body, &function_input).await
+}
+
+async fn call_higher_order(
+    worker_executor: &Arc<dyn WorkerRequestExecutor + Sync + Send>,
+    builtin: Builtin,
+    args: &[Expr],
+    input: &EvaluationContext,
+) -> Result<TypeAnnotatedValue, EvaluationError> {
+    if args.len() != builtin.arity() {
+        return Err(EvaluationError::Message(format!(
+            "Function {} expects {} arguments, but {} were given",
+            builtin.name(),
+            builtin.arity(),
+            args.len()
+        )));
+    }
+
+    let (typ, values) = match evaluate_value(worker_executor, &args[0], input).await? {
+        TypeAnnotatedValue::List { typ, values } => (typ, values),
+        _ => {
+            return Err(EvaluationError::Message(format!(
+                "The first argument of {} is not a list",
+                builtin.name()
+            )))
+        }
+    };
+
+    let function = function_arg(builtin, &args[args.len() - 1], input)?;
+
+    match builtin {
+        Builtin::Map => {
+            let mut result = vec![];
+            for value in values {
+                result.push(apply(worker_executor, &function, vec![value], input).await?);
+            }
+
+            Ok(TypeAnnotatedValue::List {
+                typ: result.first().map(AnalysedType::from).unwrap_or(typ),
+                values: result,
+            })
+        }
+
+        Builtin::Filter => {
+            let mut result = vec![];
+            for value in values {
+                match apply(worker_executor, &function, vec![value.clone()], input).await? {
+                    TypeAnnotatedValue::Bool(true) => result.push(value),
+                    TypeAnnotatedValue::Bool(false) => {}
+                    _ => {
+                        return Err(EvaluationError::Message(
+                            "The function passed to filter should return a bool".to_string(),
+                        ))
+                    }
+                }
+            }
+
+            Ok(TypeAnnotatedValue::List {
+                typ,
+                values: result,
+            })
+        }
+
+        // fold
+        _ => {
+            let mut acc = evaluate_value(worker_executor, &args[1], input).await?;
+            for value in values {
+                acc = apply(worker_executor, &function, vec![acc, value], input).await?;
+            }
+
+            Ok(acc)
+        }
+    }
+}
+
+fn function_arg<'a>(
+    builtin: Builtin,
+    expr: &'a Expr,
+    input: &'a EvaluationContext,
+) -> Result<FunctionArg<'a>, EvaluationError> {
+    match expr {
+        Expr::Lambda(params, body) => Ok(FunctionArg::Lambda(params, body)),
+        Expr::Identifier(name) => {
+            input
+                .function(name)
+                .map(FunctionArg::Defined)
+                .ok_or(EvaluationError::Message(format!(
+                    "{} is not a function defined in the API definition",
+                    name
+                )))
+        }
+        _ => Err(EvaluationError::Message(format!(
+            "The last argument of {} should be a function",
+            builtin.name()
+        ))),
+    }
+}
+
+async fn apply(
+    worker_executor: &Arc<dyn WorkerRequestExecutor + Sync + Send>,
+    function: &FunctionArg<'_>,
+    values: Vec<TypeAnnotatedValue>,
+    input: &EvaluationContext,
+) -> Result<TypeAnnotatedValue, EvaluationError> {
+    match function {
+        FunctionArg::Lambda(params, body) => {
+            if params.len() != values.len() {
+                return Err(EvaluationError::Message(format!(
+                    "The function expects {} arguments, but {} were given",
+                    params.len(),
+                    values.len()
+                )));
+            }
+
+            // The parameters shadow the variables of the expression the lambda is defined in
+            let mut lambda_input = EvaluationContext {
+                variables: Some(bind(params, values)),
+                ..input.clone()
+            };
+            if let Some(variables) = &input.variables {
+                lambda_input.merge_variables(variables);
+            }
+
+            evaluate_value(worker_executor, body, &lambda_input).await
+        }
+        FunctionArg::Defined(function) => {
+            call_defined_function(worker_executor, function, values, input).await
+        }
+    }
+}
+
+fn bind(params: &[String], values: Vec<TypeAnnotatedValue>) -> TypeAnnotatedValue {
+    TypeAnnotatedValue::Record {
+        typ: params
+            .iter()
+            .zip(&values)
+            .map(|(param, value)| (param.clone(), AnalysedType::from(value)))
+            .collect(),
+        value: params.iter().cloned().zip(values).collect(),
+    }
+}
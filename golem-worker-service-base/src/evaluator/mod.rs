@@ -1,11 +1,14 @@
 use async_trait::async_trait;
 pub use evaluator_context::*;
+use futures::future::join_all;
 use std::sync::Arc;
 mod evaluator_context;
+mod function_evaluator;
 pub(crate) mod getter;
 mod math_op_evaluator;
 pub(crate) mod path;
 mod pattern_match_evaluator;
+pub(crate) mod stdlib;
 
 use golem_wasm_ast::analysis::AnalysedType;
 use golem_wasm_rpc::json::get_json_from_typed_value;
@@ -133,6 +136,14 @@ impl Evaluator for DefaultEvaluator {
                     .map_err(|err| err.into()),
 
                 Expr::Call(name, params) => {
+                    // Functions of the API definition and of the standard library don't call the worker
+                    if let Some(result) =
+                        function_evaluator::evaluate_function_call(executor, name, params, input)
+                            .await
+                    {
+                        return result;
+                    }
+
                     let mut function_params = vec![];
 
                     for param in params {
@@ -147,9 +158,13 @@ impl Evaluator for DefaultEvaluator {
                         internal::call_worker_function(input, name, function_params, executor)
                             .await?;
 
-                    let response_context = EvaluationContext::from_refined_worker_response(&result);
+                    // Only the response of the worker of the binding is available as worker.response
+                    if input.named_worker_function(name).is_none() {
+                        let response_context =
+                            EvaluationContext::from_refined_worker_response(&result);
 
-                    input.merge(&response_context);
+                        input.merge(&response_context);
+                    }
 
                     Ok(EvaluationResult::from(&result))
                 }
@@ -297,16 +312,13 @@ impl Evaluator for DefaultEvaluator {
                 Expr::Sequence(exprs) => {
                     let mut result: Vec<TypeAnnotatedValue> = vec![];
 
-                    for expr in exprs {
-                        match Box::pin(go(expr, input, executor)).await {
-                            Ok(eval_result) => {
-                                if let Some(value) = eval_result.get_value() {
-                                    result.push(value);
-                                } else {
-                                    return Err(format!("The expression {} is evaluated to unit and cannot be part of a record", expression::to_string(expr).unwrap()).into());
-                                }
-                            }
-                            Err(result) => return Err(result),
+                    let eval_results = go_all(exprs.iter().collect(), input, executor).await?;
+
+                    for (expr, eval_result) in exprs.iter().zip(eval_results) {
+                        if let Some(value) = eval_result.get_value() {
+                            result.push(value);
+                        } else {
+                            return Err(format!("The expression {} is evaluated to unit and cannot be part of a record", expression::to_string(expr).unwrap()).into());
                         }
                     }
 
@@ -327,17 +339,18 @@ impl Evaluator for DefaultEvaluator {
                 Expr::Record(tuples) => {
                     let mut values: Vec<(String, TypeAnnotatedValue)> = vec![];
 
-                    for (key, expr) in tuples {
-                        match Box::pin(go(expr, input, executor)).await {
-                            Ok(expr_result) => {
-                                if let Some(value) = expr_result.get_value() {
-                                    values.push((key.to_string(), value));
-                                } else {
-                                    return Err(format!("The expression for key {} is evaluated to unit and cannot be part of a record", key).into());
-                                }
-                            }
+                    let eval_results = go_all(
+                        tuples.iter().map(|(_, expr)| expr.as_ref()).collect(),
+                        input,
+                        executor,
+                    )
+                    .await?;
 
-                            Err(result) => return Err(result),
+                    for ((key, _), expr_result) in tuples.iter().zip(eval_results) {
+                        if let Some(value) = expr_result.get_value() {
+                            values.push((key.to_string(), value));
+                        } else {
+                            return Err(format!("The expression for key {} is evaluated to unit and cannot be part of a record", key).into());
                         }
                     }
 
@@ -451,9 +464,10 @@ impl Evaluator for DefaultEvaluator {
                 Expr::Tuple(tuple_exprs) => {
                     let mut result: Vec<TypeAnnotatedValue> = vec![];
 
-                    for expr in tuple_exprs {
-                        let eval_result = Box::pin(go(expr, input, executor)).await?;
+                    let eval_results =
+                        go_all(tuple_exprs.iter().collect(), input, executor).await?;
 
+                    for (expr, eval_result) in tuple_exprs.iter().zip(eval_results) {
                         if let Some(value) = eval_result.get_value() {
                             result.push(value);
                         } else {
@@ -474,6 +488,51 @@ impl Evaluator for DefaultEvaluator {
                     values: flags.clone(),
                     typ: flags.clone(),
                 })),
+
+                Expr::Lambda(_, _) => Err(EvaluationError::Message(
+                    "A function can only be passed as an argument of map, filter or fold"
+                        .to_string(),
+                )),
+            }
+        }
+
+        // Worker calls made by different elements of a record, tuple or sequence don't depend
+        // on each other, and are made concurrently. The variables each element adds to its copy
+        // of the context, such as the worker.response of a call to the worker of the binding,
+        // are merged back in the order of the elements, as if they were evaluated one by one
+        async fn go_all(
+            exprs: Vec<&Expr>,
+            input: &mut EvaluationContext,
+            executor: &Arc<dyn WorkerRequestExecutor + Sync + Send>,
+        ) -> Result<Vec<EvaluationResult>, EvaluationError> {
+            if exprs.iter().filter(|expr| expr.has_call()).count() > 1 {
+                let original = input.clone();
+                let evaluations = exprs.into_iter().map(|expr| {
+                    let mut input = original.clone();
+                    async move {
+                        let result = Box::pin(go(expr, &mut input, executor)).await;
+                        (result, input)
+                    }
+                });
+
+                let mut results = vec![];
+
+                for (result, context) in join_all(evaluations).await {
+                    if let Some(added) = context.variables_added_since(&original) {
+                        input.merge_variables(&added);
+                    }
+                    results.push(result?);
+                }
+
+                Ok(results)
+            } else {
+                let mut results = vec![];
+
+                for expr in exprs {
+                    results.push(Box::pin(go(expr, input, executor)).await?);
+                }
+
+                Ok(results)
             }
         }
 
@@ -502,6 +561,27 @@ mod internal {
         json_params: Vec<TypeAnnotatedValue>,
         executor: &Arc<dyn WorkerRequestExecutor + Sync + Send>,
     ) -> Result<RefinedWorkerResponse, EvaluationError> {
+        if let Some((worker, function_name)) = runtime.named_worker_function(function_name) {
+            let analysed_function = worker
+                .analysed_functions
+                .iter()
+                .find(|f| f.name == function_name)
+                .ok_or(EvaluationError::Message(format!(
+                    "The function {} is not found in worker {} at Runtime",
+                    function_name, worker.name
+                )))?;
+
+            let worker_request = WorkerRequest {
+                component_id: worker.worker_detail.component_id.clone(),
+                worker_name: worker.worker_detail.worker_name.clone(),
+                function_name: analysed_function.name.clone(),
+                function_params: json_params,
+                idempotency_key: worker.worker_detail.idempotency_key.clone(),
+            };
+
+            return execute(worker_request, executor).await;
+        }
+
         let variables = runtime.clone().variables.ok_or(EvaluationError::Message(
             "No variables found in the context".to_string(),
         ))?;
@@ -560,6 +640,13 @@ mod internal {
             idempotency_key,
         };
 
+        execute(worker_request, executor).await
+    }
+
+    async fn execute(
+        worker_request: WorkerRequest,
+        executor: &Arc<dyn WorkerRequestExecutor + Sync + Send>,
+    ) -> Result<RefinedWorkerResponse, EvaluationError> {
         let worker_response = executor.execute(worker_request).await.map_err(|err| {
             EvaluationError::Message(format!("Failed to execute worker function: {}", err))
         })?;
@@ -585,7 +672,7 @@ mod tests {
     use http::{HeaderMap, Uri};
     use serde_json::{json, Value};
 
-    use crate::api_definition::http::AllPathPatterns;
+    use crate::api_definition::http::{AllPathPatterns, FunctionDefinition};
     use crate::evaluator::evaluator_context::EvaluationContext;
     use crate::evaluator::getter::GetError;
     use crate::evaluator::{DefaultEvaluator, EvaluationError, EvaluationResult, Evaluator};
@@ -1808,12 +1895,118 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[tokio::test]
+    async fn test_evaluation_with_builtin_functions() {
+        let noop_executor = DefaultEvaluator::noop();
+
+        let program = r#"
+            let names = split("ann,bob", ",");
+            let uppercased = map(names, name => upper(name));
+            join(uppercased, "-")
+          "#;
+
+        let expr = expression::from_string(format!("${{{}}}", program)).unwrap();
+
+        let result = noop_executor
+            .evaluate(&expr, &EvaluationContext::empty())
+            .await;
+
+        let expected = Ok(EvaluationResult::Value(TypeAnnotatedValue::Str(
+            "ANN-BOB".to_string(),
+        )));
+
+        assert_eq!(result, expected);
+    }
+
+    #[tokio::test]
+    async fn test_evaluation_with_filter_and_fold() {
+        let noop_executor = DefaultEvaluator::noop();
+
+        let program = r"
+            let large = filter([1, 5, 2, 4], n => n > 2);
+            fold(large, 0, (max, n) => if n > max then n else max)
+          ";
+
+        let expr = expression::from_string(format!("${{{}}}", program)).unwrap();
+
+        let result = noop_executor
+            .evaluate(&expr, &EvaluationContext::empty())
+            .await;
+
+        let expected = Ok(EvaluationResult::Value(TypeAnnotatedValue::U64(5)));
+
+        assert_eq!(result, expected);
+    }
+
+    #[tokio::test]
+    async fn test_evaluation_with_defined_functions() {
+        let noop_executor = DefaultEvaluator::noop();
+
+        let function = |name: &str, body: &str| FunctionDefinition {
+            name: name.to_string(),
+            params: vec!["name".to_string()],
+            body: expression::from_string(body).unwrap(),
+        };
+
+        let context = EvaluationContext::empty().with_functions(vec![
+            function("greet", "Hello ${upper(name)}"),
+            function("long", "${length(name) > 3}"),
+        ]);
+
+        let expr =
+            expression::from_string(r#"${map(filter(["ann", "carla"], long), greet)}"#).unwrap();
+
+        let result = noop_executor.evaluate(&expr, &context).await;
+
+        let expected = Ok(EvaluationResult::Value(TypeAnnotatedValue::List {
+            typ: AnalysedType::Str,
+            values: vec![TypeAnnotatedValue::Str("Hello CARLA".to_string())],
+        }));
+
+        assert_eq!(result, expected);
+    }
+
+    #[tokio::test]
+    async fn test_evaluation_of_defined_function_without_request() {
+        let noop_executor = DefaultEvaluator::noop();
+
+        let context = EvaluationContext::empty().with_functions(vec![FunctionDefinition {
+            name: "user".to_string(),
+            params: vec![],
+            body: expression::from_string("${request.path.user}").unwrap(),
+        }]);
+
+        let expr = expression::from_string("${user()}").unwrap();
+
+        let result = noop_executor.evaluate(&expr, &context).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_evaluation_of_lambda_outside_function_call() {
+        let noop_executor = DefaultEvaluator::noop();
+
+        let expr = expression::from_string("${x => x}").unwrap();
+
+        let result = noop_executor
+            .evaluate(&expr, &EvaluationContext::empty())
+            .await;
+
+        let expected = Err(EvaluationError::Message(
+            "A function can only be passed as an argument of map, filter or fold".to_string(),
+        ));
+
+        assert_eq!(result, expected);
+    }
+
     mod test_utils {
         use crate::api_definition::http::{AllPathPatterns, PathPattern, VarInfo};
         use crate::evaluator::tests::{EvaluatorTestExt, WorkerBridgeExt};
         use crate::evaluator::DefaultEvaluator;
         use crate::expression;
         use crate::http::router::RouterPattern;
+        use crate::http::RequestBody;
         use crate::worker_binding::RequestDetails;
         use crate::worker_bridge_execution::WorkerResponse;
         use golem_service_base::type_inference::infer_analysed_type;
@@ -1909,8 +2102,9 @@ mod tests {
                 &HashMap::new(),
                 &HashMap::new(),
                 &[],
-                &request_body,
+                &RequestBody::Json(request_body),
                 header_map,
+                None,
             )
             .unwrap()
         }
@@ -1935,8 +2129,9 @@ mod tests {
                 &path_params,
                 &HashMap::new(),
                 &path_pattern.query_params,
-                &Value::Null,
+                &RequestBody::Json(Value::Null),
                 &HeaderMap::new(),
+                None,
             )
             .unwrap()
         }
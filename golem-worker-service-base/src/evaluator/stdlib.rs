@@ -0,0 +1,454 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use base64::Engine;
+use golem_service_base::type_inference::infer_analysed_type;
+use golem_wasm_ast::analysis::AnalysedType;
+use golem_wasm_rpc::json::{get_json_from_typed_value, get_typed_value_from_json};
+use golem_wasm_rpc::TypeAnnotatedValue;
+use sha2::{Digest, Sha256};
+use strum_macros::{EnumString, IntoStaticStr};
+
+use crate::primitive::GetPrimitive;
+use crate::type_checker::InferredType;
+
+// The functions available to every expression, such as `lower(request.path.name)`.
+// A function of the worker or of the API definition with the same name takes precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Builtin {
+    Lower,
+    Upper,
+    Trim,
+    // Number of characters of a string, or of elements of a list
+    Length,
+    Split,
+    Join,
+    Replace,
+    // Whether a list has the element, or a string has the substring
+    Contains,
+    StartsWith,
+    EndsWith,
+    ToJson,
+    FromJson,
+    // The current time in RFC 3339
+    Now,
+    // Formats an RFC 3339 date with a strftime pattern, as in format-date(now(), "%Y-%m-%d")
+    FormatDate,
+    #[strum(to_string = "base64-encode")]
+    Base64Encode,
+    #[strum(to_string = "base64-decode")]
+    Base64Decode,
+    // Hex encoded SHA-256 of a string
+    #[strum(to_string = "sha256")]
+    Sha256,
+    // A random v4 UUID
+    Uuid,
+    // map(list, item => ..), filter(list, item => ..) and fold(list, init, (acc, item) => ..)
+    // take a lambda, or the name of a function of the API definition
+    Map,
+    Filter,
+    Fold,
+}
+
+impl Builtin {
+    pub fn from_name(name: &str) -> Option<Builtin> {
+        Builtin::from_str(name).ok()
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.into()
+    }
+
+    pub fn is_higher_order(&self) -> bool {
+        matches!(self, Builtin::Map | Builtin::Filter | Builtin::Fold)
+    }
+
+    pub fn arity(&self) -> usize {
+        match self {
+            Builtin::Now | Builtin::Uuid => 0,
+            Builtin::Lower
+            | Builtin::Upper
+            | Builtin::Trim
+            | Builtin::Length
+            | Builtin::ToJson
+            | Builtin::FromJson
+            | Builtin::Base64Encode
+            | Builtin::Base64Decode
+            | Builtin::Sha256 => 1,
+            Builtin::Split
+            | Builtin::Join
+            | Builtin::Contains
+            | Builtin::StartsWith
+            | Builtin::EndsWith
+            | Builtin::FormatDate
+            | Builtin::Map
+            | Builtin::Filter => 2,
+            Builtin::Replace | Builtin::Fold => 3,
+        }
+    }
+
+    fn arity_error(&self, given: usize) -> String {
+        format!(
+            "Function `{}` expects {} arguments, but {} were given",
+            self.name(),
+            self.arity(),
+            given
+        )
+    }
+
+    // Calls a function which doesn't take a function as an argument
+    pub fn call(&self, args: Vec<TypeAnnotatedValue>) -> Result<TypeAnnotatedValue, String> {
+        if args.len() != self.arity() {
+            return Err(self.arity_error(args.len()));
+        }
+
+        match self {
+            Builtin::Lower => Ok(TypeAnnotatedValue::Str(
+                self.string_arg(&args, 0)?.to_lowercase(),
+            )),
+            Builtin::Upper => Ok(TypeAnnotatedValue::Str(
+                self.string_arg(&args, 0)?.to_uppercase(),
+            )),
+            Builtin::Trim => Ok(TypeAnnotatedValue::Str(
+                self.string_arg(&args, 0)?.trim().to_string(),
+            )),
+            Builtin::Length => match &args[0] {
+                TypeAnnotatedValue::List { values, .. } => {
+                    Ok(TypeAnnotatedValue::U64(values.len() as u64))
+                }
+                _ => Ok(TypeAnnotatedValue::U64(
+                    self.string_arg(&args, 0)?.chars().count() as u64,
+                )),
+            },
+            Builtin::Split => {
+                let text = self.string_arg(&args, 0)?;
+                let separator = self.string_arg(&args, 1)?;
+
+                Ok(TypeAnnotatedValue::List {
+                    typ: AnalysedType::Str,
+                    values: text
+                        .split(separator.as_str())
+                        .map(|part| TypeAnnotatedValue::Str(part.to_string()))
+                        .collect(),
+                })
+            }
+            Builtin::Join => {
+                let separator = self.string_arg(&args, 1)?;
+                let parts = self
+                    .list_arg(&args, 0)?
+                    .iter()
+                    .map(|value| {
+                        value.get_primitive().map(|p| p.as_string()).ok_or(format!(
+                            "Function `{}` can only join primitive values, found {}",
+                            self.name(),
+                            get_json_from_typed_value(value)
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+
+                Ok(TypeAnnotatedValue::Str(parts.join(separator.as_str())))
+            }
+            Builtin::Replace => {
+                let text = self.string_arg(&args, 0)?;
+                let from = self.string_arg(&args, 1)?;
+                let to = self.string_arg(&args, 2)?;
+
+                Ok(TypeAnnotatedValue::Str(
+                    text.replace(from.as_str(), to.as_str()),
+                ))
+            }
+            Builtin::Contains => match &args[0] {
+                TypeAnnotatedValue::List { values, .. } => {
+                    let element = get_json_from_typed_value(&args[1]);
+                    Ok(TypeAnnotatedValue::Bool(
+                        values
+                            .iter()
+                            .any(|value| get_json_from_typed_value(value) == element),
+                    ))
+                }
+                _ => Ok(TypeAnnotatedValue::Bool(
+                    self.string_arg(&args, 0)?
+                        .contains(self.string_arg(&args, 1)?.as_str()),
+                )),
+            },
+            Builtin::StartsWith => Ok(TypeAnnotatedValue::Bool(
+                self.string_arg(&args, 0)?
+                    .starts_with(self.string_arg(&args, 1)?.as_str()),
+            )),
+            Builtin::EndsWith => Ok(TypeAnnotatedValue::Bool(
+                self.string_arg(&args, 0)?
+                    .ends_with(self.string_arg(&args, 1)?.as_str()),
+            )),
+            Builtin::ToJson => Ok(TypeAnnotatedValue::Str(
+                get_json_from_typed_value(&args[0]).to_string(),
+            )),
+            Builtin::FromJson => {
+                let json: serde_json::Value =
+                    serde_json::from_str(self.string_arg(&args, 0)?.as_str())
+                        .map_err(|err| format!("Invalid JSON: {}", err))?;
+
+                get_typed_value_from_json(&json, &infer_analysed_type(&json))
+                    .map_err(|errors| format!("Invalid JSON: {}", errors.join(", ")))
+            }
+            Builtin::Now => Ok(TypeAnnotatedValue::Str(chrono::Utc::now().to_rfc3339())),
+            Builtin::FormatDate => {
+                let date =
+                    chrono::DateTime::parse_from_rfc3339(self.string_arg(&args, 0)?.as_str())
+                        .map_err(|err| format!("Invalid RFC 3339 date: {}", err))?;
+                let pattern = self.string_arg(&args, 1)?;
+
+                // An invalid pattern makes the formatting fail, rather than to_string panic
+                let mut formatted = String::new();
+                write!(formatted, "{}", date.format(pattern.as_str()))
+                    .map_err(|_| format!("Invalid date format: {}", pattern))?;
+
+                Ok(TypeAnnotatedValue::Str(formatted))
+            }
+            Builtin::Base64Encode => Ok(TypeAnnotatedValue::Str(
+                base64::engine::general_purpose::STANDARD.encode(self.string_arg(&args, 0)?),
+            )),
+            Builtin::Base64Decode => {
+                let bytes = base64::engine::general_purpose::STANDARD
+                    .decode(self.string_arg(&args, 0)?)
+                    .map_err(|err| format!("Invalid base64: {}", err))?;
+
+                String::from_utf8(bytes)
+                    .map(TypeAnnotatedValue::Str)
+                    .map_err(|_| "The decoded base64 is not a valid UTF-8 string".to_string())
+            }
+            Builtin::Sha256 => Ok(TypeAnnotatedValue::Str(format!(
+                "{:x}",
+                Sha256::digest(self.string_arg(&args, 0)?.as_bytes())
+            ))),
+            Builtin::Uuid => Ok(TypeAnnotatedValue::Str(uuid::Uuid::new_v4().to_string())),
+            Builtin::Map | Builtin::Filter | Builtin::Fold => Err(format!(
+                "Function `{}` needs a function as an argument",
+                self.name()
+            )),
+        }
+    }
+
+    // The type of the result of a function which doesn't take a function as an argument
+    pub fn result_type(&self, args: &[InferredType]) -> Result<InferredType, String> {
+        if args.len() != self.arity() {
+            return Err(self.arity_error(args.len()));
+        }
+
+        let expect_primitive = |index: usize| {
+            if args[index].is_primitive() {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Argument {} of function `{}` expects a primitive value, found {}",
+                    index + 1,
+                    self.name(),
+                    args[index]
+                ))
+            }
+        };
+
+        let expect_list_or_primitive = |index: usize| match &args[index] {
+            InferredType::List(_) => Ok(()),
+            _ => expect_primitive(index),
+        };
+
+        match self {
+            Builtin::Lower
+            | Builtin::Upper
+            | Builtin::Trim
+            | Builtin::Base64Encode
+            | Builtin::Base64Decode
+            | Builtin::Sha256 => {
+                expect_primitive(0)?;
+                Ok(InferredType::Str)
+            }
+            Builtin::Length => {
+                expect_list_or_primitive(0)?;
+                Ok(InferredType::U64)
+            }
+            Builtin::Split => {
+                expect_primitive(0)?;
+                expect_primitive(1)?;
+                Ok(InferredType::List(Box::new(InferredType::Str)))
+            }
+            Builtin::Join => {
+                match &args[0] {
+                    InferredType::List(_) | InferredType::Unknown => {}
+                    other => {
+                        return Err(format!(
+                            "Argument 1 of function `join` expects a list, found {other}"
+                        ))
+                    }
+                }
+                expect_primitive(1)?;
+                Ok(InferredType::Str)
+            }
+            Builtin::Replace => {
+                for index in 0..3 {
+                    expect_primitive(index)?;
+                }
+                Ok(InferredType::Str)
+            }
+            Builtin::Contains => {
+                expect_list_or_primitive(0)?;
+                Ok(InferredType::Bool)
+            }
+            Builtin::StartsWith | Builtin::EndsWith => {
+                expect_primitive(0)?;
+                expect_primitive(1)?;
+                Ok(InferredType::Bool)
+            }
+            Builtin::ToJson => Ok(InferredType::Str),
+            Builtin::FromJson => {
+                expect_primitive(0)?;
+                Ok(InferredType::Unknown)
+            }
+            Builtin::FormatDate => {
+                expect_primitive(0)?;
+                expect_primitive(1)?;
+                Ok(InferredType::Str)
+            }
+            Builtin::Now | Builtin::Uuid => Ok(InferredType::Str),
+            Builtin::Map | Builtin::Filter | Builtin::Fold => Err(format!(
+                "Function `{}` needs a function as an argument",
+                self.name()
+            )),
+        }
+    }
+
+    fn string_arg(&self, args: &[TypeAnnotatedValue], index: usize) -> Result<String, String> {
+        args[index]
+            .get_primitive()
+            .map(|primitive| primitive.as_string())
+            .ok_or(format!(
+                "Argument {} of function `{}` expects a primitive value, found {}",
+                index + 1,
+                self.name(),
+                get_json_from_typed_value(&args[index])
+            ))
+    }
+
+    fn list_arg<'a>(
+        &self,
+        args: &'a [TypeAnnotatedValue],
+        index: usize,
+    ) -> Result<&'a Vec<TypeAnnotatedValue>, String> {
+        match &args[index] {
+            TypeAnnotatedValue::List { values, .. } => Ok(values),
+            other => Err(format!(
+                "Argument {} of function `{}` expects a list, found {}",
+                index + 1,
+                self.name(),
+                get_json_from_typed_value(other)
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use golem_wasm_ast::analysis::AnalysedType;
+    use golem_wasm_rpc::TypeAnnotatedValue;
+
+    use crate::evaluator::stdlib::Builtin;
+    use crate::type_checker::InferredType;
+
+    fn str(value: &str) -> TypeAnnotatedValue {
+        TypeAnnotatedValue::Str(value.to_string())
+    }
+
+    fn strings(values: &[&str]) -> TypeAnnotatedValue {
+        TypeAnnotatedValue::List {
+            typ: AnalysedType::Str,
+            values: values.iter().map(|value| str(value)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_builtin_names() {
+        assert_eq!(Builtin::from_name("starts-with"), Some(Builtin::StartsWith));
+        assert_eq!(
+            Builtin::from_name("base64-encode"),
+            Some(Builtin::Base64Encode)
+        );
+        assert_eq!(Builtin::from_name("sha256"), Some(Builtin::Sha256));
+        assert_eq!(Builtin::Base64Decode.name(), "base64-decode");
+        assert_eq!(Builtin::from_name("get-cart-contents"), None);
+    }
+
+    #[test]
+    fn test_string_functions() {
+        assert_eq!(Builtin::Lower.call(vec![str("ABC")]), Ok(str("abc")));
+        assert_eq!(Builtin::Upper.call(vec![str("abc")]), Ok(str("ABC")));
+        assert_eq!(Builtin::Trim.call(vec![str(" abc ")]), Ok(str("abc")));
+        assert_eq!(
+            Builtin::Replace.call(vec![str("a-b-c"), str("-"), str("+")]),
+            Ok(str("a+b+c"))
+        );
+        assert_eq!(
+            Builtin::Split.call(vec![str("a,b,c"), str(",")]),
+            Ok(strings(&["a", "b", "c"]))
+        );
+        assert_eq!(
+            Builtin::Join.call(vec![strings(&["a", "b"]), str("/")]),
+            Ok(str("a/b"))
+        );
+        assert_eq!(
+            Builtin::Length.call(vec![strings(&["a", "b"])]),
+            Ok(TypeAnnotatedValue::U64(2))
+        );
+        assert_eq!(
+            Builtin::Contains.call(vec![strings(&["a", "b"]), str("b")]),
+            Ok(TypeAnnotatedValue::Bool(true))
+        );
+        assert_eq!(
+            Builtin::StartsWith.call(vec![str("golem"), str("go")]),
+            Ok(TypeAnnotatedValue::Bool(true))
+        );
+    }
+
+    #[test]
+    fn test_encoding_functions() {
+        assert_eq!(
+            Builtin::Base64Encode.call(vec![str("golem")]),
+            Ok(str("Z29sZW0="))
+        );
+        assert_eq!(
+            Builtin::Base64Decode.call(vec![str("Z29sZW0=")]),
+            Ok(str("golem"))
+        );
+        assert_eq!(
+            Builtin::Sha256.call(vec![str("abc")]),
+            Ok(str(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_json_and_date_functions() {
+        let value = Builtin::FromJson.call(vec![str(r#"{"id": 1}"#)]).unwrap();
+        assert_eq!(Builtin::ToJson.call(vec![value]), Ok(str(r#"{"id":1}"#)));
+
+        assert_eq!(
+            Builtin::FormatDate.call(vec![str("2024-03-01T10:00:00Z"), str("%Y/%m/%d")]),
+            Ok(str("2024/03/01"))
+        );
+        assert!(Builtin::FormatDate
+            .call(vec![str("yesterday"), str("%Y")])
+            .is_err());
+    }
+
+    #[test]
+    fn test_arity() {
+        assert_eq!(
+            Builtin::Lower.call(vec![str("a"), str("b")]),
+            Err("Function `lower` expects 1 arguments, but 2 were given".to_string())
+        );
+        assert_eq!(
+            Builtin::Split.result_type(&[InferredType::Str]),
+            Err("Function `split` expects 2 arguments, but 1 were given".to_string())
+        );
+    }
+}
@@ -35,6 +35,8 @@ pub enum Expr {
     Option(Option<Box<Expr>>),
     Result(Result<Box<Expr>, Box<Expr>>),
     Call(String, Vec<Expr>), // Upto the evaluator to find from the context what String represents
+    // Ex: (acc, item) => acc, passed to the functions of the standard library such as map
+    Lambda(Vec<String>, Box<Expr>),
 }
 
 impl Expr {
@@ -203,6 +205,41 @@ impl Expr {
             _ => false,
         }
     }
+
+    // Whether evaluating the expression calls a worker function
+    pub fn has_call(&self) -> bool {
+        match self {
+            Expr::Call(_, _) => true,
+            Expr::Let(_, expr)
+            | Expr::SelectField(expr, _)
+            | Expr::SelectIndex(expr, _)
+            | Expr::Not(expr)
+            | Expr::Option(Some(expr))
+            | Expr::Result(Ok(expr))
+            | Expr::Result(Err(expr))
+            | Expr::Lambda(_, expr) => expr.has_call(),
+            Expr::Sequence(exprs)
+            | Expr::Tuple(exprs)
+            | Expr::Concat(exprs)
+            | Expr::Multiple(exprs) => exprs.iter().any(|expr| expr.has_call()),
+            Expr::Record(fields) => fields.iter().any(|(_, expr)| expr.has_call()),
+            Expr::GreaterThan(left, right)
+            | Expr::GreaterThanOrEqualTo(left, right)
+            | Expr::LessThanOrEqualTo(left, right)
+            | Expr::EqualTo(left, right)
+            | Expr::LessThan(left, right) => left.has_call() || right.has_call(),
+            Expr::Cond(pred, left, right) => pred.has_call() || left.has_call() || right.has_call(),
+            Expr::PatternMatch(expr, arms) => {
+                expr.has_call() || arms.iter().any(|MatchArm((_, expr))| expr.has_call())
+            }
+            Expr::Literal(_)
+            | Expr::Number(_)
+            | Expr::Flags(_)
+            | Expr::Identifier(_)
+            | Expr::Boolean(_)
+            | Expr::Option(None) => false,
+        }
+    }
 }
 
 impl FromStr for Expr {
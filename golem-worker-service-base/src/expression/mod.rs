@@ -1,5 +1,6 @@
 pub(crate) use expr::*;
 pub(crate) use text::*;
+pub(crate) use writer::{ExprSpans, SourceSpan};
 mod expr;
 mod text;
 mod writer;
@@ -1,4 +1,4 @@
-use crate::expression::writer::WriterError;
+use crate::expression::writer::{ExprSpans, WriterError};
 use crate::expression::{writer, Expr};
 use crate::parser::expr_parser::ExprParser;
 use crate::parser::GolemParser;
@@ -13,6 +13,10 @@ pub fn to_string(expr: &Expr) -> Result<String, WriterError> {
     writer::write_expr(expr)
 }
 
+pub fn to_string_with_spans(expr: &Expr) -> Result<(String, ExprSpans), WriterError> {
+    writer::write_expr_with_spans(expr)
+}
+
 #[cfg(test)]
 mod record_tests {
     use crate::expression::{from_string, to_string, ArmPattern, Expr, InnerNumber, MatchArm};
@@ -3,6 +3,12 @@ use crate::tokeniser::tokenizer::{MultiCharTokens, Token};
 use std::io::Write;
 
 pub fn write_expr(expr: &Expr) -> Result<String, WriterError> {
+    write_expr_with_spans(expr).map(|(text, _)| text)
+}
+
+// Writes the expression, and keeps track of where each sub-expression ended up in the text,
+// so that errors found in an expression tree can be reported against the source
+pub fn write_expr_with_spans(expr: &Expr) -> Result<(String, ExprSpans), WriterError> {
     let mut buf = vec![];
     let mut writer = Writer::new(&mut buf);
 
@@ -23,11 +29,56 @@ pub fn write_expr(expr: &Expr) -> Result<String, WriterError> {
         }
     }
 
-    Ok(String::from_utf8(buf).unwrap_or_else(|err| panic!("invalid UTF-8: {err:?}")))
+    let spans = ExprSpans {
+        spans: writer.spans,
+        length: writer.position,
+    };
+
+    Ok((
+        String::from_utf8(buf).unwrap_or_else(|err| panic!("invalid UTF-8: {err:?}")),
+        spans,
+    ))
+}
+
+// Byte offsets of a sub-expression in the written text of an expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl std::fmt::Display for SourceSpan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+// The sub-expressions are identified by their address, so the spans are only meaningful
+// for the same expression tree that was written
+#[derive(Debug, Clone)]
+pub struct ExprSpans {
+    spans: Vec<(usize, SourceSpan)>,
+    length: usize,
+}
+
+impl ExprSpans {
+    pub fn span_of(&self, expr: &Expr) -> SourceSpan {
+        let address = expr as *const Expr as usize;
+        self.spans
+            .iter()
+            .find(|(expr_address, _)| *expr_address == address)
+            .map(|(_, span)| *span)
+            .unwrap_or(SourceSpan {
+                start: 0,
+                end: self.length,
+            })
+    }
 }
 
 struct Writer<W> {
     inner: W,
+    position: usize,
+    spans: Vec<(usize, SourceSpan)>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -39,7 +90,11 @@ pub enum WriterError {
 
 impl<W: Write> Writer<W> {
     fn new(w: W) -> Self {
-        Self { inner: w }
+        Self {
+            inner: w,
+            position: 0,
+            spans: vec![],
+        }
     }
 
     fn write_code_start(&mut self) -> Result<(), WriterError> {
@@ -51,6 +106,19 @@ impl<W: Write> Writer<W> {
     }
 
     fn write_expr(&mut self, expr: &Expr) -> Result<(), WriterError> {
+        let start = self.position;
+        self.write_expr_inner(expr)?;
+        self.spans.push((
+            expr as *const Expr as usize,
+            SourceSpan {
+                start,
+                end: self.position,
+            },
+        ));
+        Ok(())
+    }
+
+    fn write_expr_inner(&mut self, expr: &Expr) -> Result<(), WriterError> {
         match expr {
             Expr::Literal(string) => {
                 self.write_display(Token::Quote)?;
@@ -226,17 +294,30 @@ impl<W: Write> Writer<W> {
                 }
                 self.write_display(Token::RParen)
             }
+            Expr::Lambda(params, body) => {
+                match params.as_slice() {
+                    [param] => self.write_str(param)?,
+                    params => {
+                        self.write_display(Token::LParen)?;
+                        self.write_str(params.join(", "))?;
+                        self.write_display(Token::RParen)?;
+                    }
+                }
+                self.write_str(" => ")?;
+                self.write_expr(body)
+            }
         }
     }
 
     fn write_str(&mut self, s: impl AsRef<str>) -> Result<(), WriterError> {
-        self.inner.write_all(s.as_ref().as_bytes())?;
+        let bytes = s.as_ref().as_bytes();
+        self.inner.write_all(bytes)?;
+        self.position += bytes.len();
         Ok(())
     }
 
     fn write_display(&mut self, d: impl std::fmt::Display) -> Result<(), WriterError> {
-        write!(self.inner, "{d}")?;
-        Ok(())
+        self.write_str(d.to_string())
     }
 }
 
@@ -0,0 +1,339 @@
+use http::header::{
+    ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+    ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_HEADERS,
+    ACCESS_CONTROL_REQUEST_METHOD, ORIGIN, VARY,
+};
+use http::{HeaderMap, HeaderValue, Method};
+
+use crate::api_definition::http::{CorsPolicy, HttpApiDefinition};
+use crate::http::http_request::router;
+use crate::http::router::RouterPattern;
+use crate::http::InputHttpRequest;
+
+// Answers a preflight request on behalf of the api definition, returning the headers of the
+// response, or the reason of rejecting it.
+// Returns None if the request is not a preflight request, the api definition has no cors policy,
+// or the path has an OPTIONS route bound, in which case the request is routed as usual
+pub fn preflight(
+    api_definition: &HttpApiDefinition,
+    request: &InputHttpRequest,
+) -> Option<Result<HeaderMap, String>> {
+    let policy = api_definition.cors.as_ref()?;
+
+    if request.req_method != Method::OPTIONS {
+        return None;
+    }
+
+    let origin = header(&request.headers, ORIGIN)?;
+    let requested_method = header(&request.headers, ACCESS_CONTROL_REQUEST_METHOD)?;
+
+    let router = router::build(api_definition.routes.clone());
+    let path: Vec<&str> = RouterPattern::split(&request.input_path.base_path).collect();
+    let route_methods = router.allowed_methods(&path);
+
+    if route_methods.contains(&Method::OPTIONS) {
+        return None;
+    }
+
+    Some(preflight_headers(
+        policy,
+        origin,
+        requested_method,
+        header(&request.headers, ACCESS_CONTROL_REQUEST_HEADERS),
+        &route_methods,
+    ))
+}
+
+// Headers added to the response of a cross-origin request, empty if the origin is not allowed
+pub fn response_headers(policy: &CorsPolicy, request_headers: &HeaderMap) -> HeaderMap {
+    match header(request_headers, ORIGIN) {
+        Some(origin) if policy.is_origin_allowed(origin) => origin_headers(policy, origin),
+        _ => HeaderMap::new(),
+    }
+}
+
+fn preflight_headers(
+    policy: &CorsPolicy,
+    origin: &str,
+    requested_method: &str,
+    requested_headers: Option<&str>,
+    route_methods: &[Method],
+) -> Result<HeaderMap, String> {
+    if !policy.is_origin_allowed(origin) {
+        return Err(format!("Origin {origin} is not allowed"));
+    }
+
+    let allowed_methods: Vec<&Method> = route_methods
+        .iter()
+        .filter(|method| {
+            policy.allowed_methods.is_empty()
+                || policy
+                    .allowed_methods
+                    .iter()
+                    .any(|allowed| Method::from(allowed.clone()) == **method)
+        })
+        .collect();
+
+    if !allowed_methods
+        .iter()
+        .any(|method| method.as_str().eq_ignore_ascii_case(requested_method))
+    {
+        return Err(format!("Method {requested_method} is not allowed"));
+    }
+
+    let requested_headers: Vec<&str> = requested_headers
+        .map(|headers| {
+            headers
+                .split(',')
+                .map(|header| header.trim())
+                .filter(|header| !header.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if let Some(header) = requested_headers
+        .iter()
+        .find(|header| !policy.is_header_allowed(header))
+    {
+        return Err(format!("Header {header} is not allowed"));
+    }
+
+    let mut headers = origin_headers(policy, origin);
+
+    let methods = allowed_methods
+        .iter()
+        .map(|method| method.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    insert(&mut headers, ACCESS_CONTROL_ALLOW_METHODS, &methods);
+
+    if !requested_headers.is_empty() {
+        insert(
+            &mut headers,
+            ACCESS_CONTROL_ALLOW_HEADERS,
+            &requested_headers.join(", "),
+        );
+    }
+
+    if let Some(max_age) = policy.max_age {
+        insert(&mut headers, ACCESS_CONTROL_MAX_AGE, &max_age.to_string());
+    }
+
+    Ok(headers)
+}
+
+fn origin_headers(policy: &CorsPolicy, origin: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+
+    let any_origin = policy.allowed_origins.iter().any(|allowed| allowed == "*");
+    if any_origin {
+        insert(&mut headers, ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+    } else {
+        insert(&mut headers, ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+        insert(&mut headers, VARY, "Origin");
+    }
+
+    // Credentials are never allowed for any origin, as that would let every site make
+    // authenticated requests. Such policies are rejected when the api definition is validated
+    if policy.allow_credentials && !any_origin {
+        insert(&mut headers, ACCESS_CONTROL_ALLOW_CREDENTIALS, "true");
+    }
+
+    headers
+}
+
+fn header(headers: &HeaderMap, name: http::header::HeaderName) -> Option<&str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+fn insert(headers: &mut HeaderMap, name: http::header::HeaderName, value: &str) {
+    if let Ok(value) = HeaderValue::from_str(value) {
+        headers.insert(name, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::header::{
+        ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS,
+        ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_MAX_AGE,
+    };
+    use http::{HeaderMap, HeaderValue, Method};
+    use serde_json::Value;
+
+    use crate::api_definition::http::HttpApiDefinition;
+    use crate::http::cors::{preflight, response_headers};
+    use crate::http::{ApiInputPath, InputHttpRequest, RequestBody};
+
+    fn api_definition(cors: &str, options_route: bool) -> HttpApiDefinition {
+        let options_route = if options_route {
+            r#"
+          - method: Options
+            path: cart/{user-id}
+            binding:
+              componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+              workerName: 'cart'
+              response: 'sample'"#
+        } else {
+            ""
+        };
+
+        let yaml = format!(
+            r#"
+          id: cart-api
+          version: 0.0.1
+          cors:
+            {cors}
+          routes:
+          - method: Get
+            path: cart/{{user-id}}
+            binding:
+              componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+              workerName: 'cart'
+              response: 'sample'
+          - method: Put
+            path: cart/{{user-id}}
+            binding:
+              componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+              workerName: 'cart'
+              response: 'sample'{options_route}
+        "#
+        );
+
+        serde_yaml::from_str(&yaml).unwrap()
+    }
+
+    fn request(method: Method, headers: Vec<(&'static str, &'static str)>) -> InputHttpRequest {
+        let mut header_map = HeaderMap::new();
+        for (name, value) in headers {
+            header_map.insert(name, HeaderValue::from_static(value));
+        }
+
+        InputHttpRequest {
+            input_path: ApiInputPath {
+                base_path: "cart/1".to_string(),
+                query_path: None,
+            },
+            headers: header_map,
+            req_method: method,
+            req_body: RequestBody::Json(Value::Null),
+        }
+    }
+
+    fn preflight_request(method: &'static str) -> InputHttpRequest {
+        request(
+            Method::OPTIONS,
+            vec![
+                ("origin", "https://app.example.com"),
+                ("access-control-request-method", method),
+                ("access-control-request-headers", "content-type"),
+            ],
+        )
+    }
+
+    #[test]
+    fn preflight_allowed() {
+        let api_definition = api_definition(
+            "{allowedOrigins: ['https://app.example.com'], allowedHeaders: [content-type], allowCredentials: true, maxAge: 600}",
+            false,
+        );
+
+        let headers = preflight(&api_definition, &preflight_request("PUT"))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            headers.get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(),
+            "https://app.example.com"
+        );
+        assert_eq!(
+            headers.get(ACCESS_CONTROL_ALLOW_METHODS).unwrap(),
+            "GET, PUT"
+        );
+        assert_eq!(
+            headers.get(ACCESS_CONTROL_ALLOW_HEADERS).unwrap(),
+            "content-type"
+        );
+        assert_eq!(
+            headers.get(ACCESS_CONTROL_ALLOW_CREDENTIALS).unwrap(),
+            "true"
+        );
+        assert_eq!(headers.get(ACCESS_CONTROL_MAX_AGE).unwrap(), "600");
+    }
+
+    #[test]
+    fn preflight_rejected() {
+        let other_origin = api_definition(
+            "{allowedOrigins: ['https://other.example.com'], allowedHeaders: ['*']}",
+            false,
+        );
+        let get_only = api_definition(
+            "{allowedOrigins: ['*'], allowedMethods: [Get], allowedHeaders: ['*']}",
+            false,
+        );
+        let no_headers = api_definition("{allowedOrigins: ['*']}", false);
+
+        assert_eq!(
+            preflight(&other_origin, &preflight_request("PUT")),
+            Some(Err(
+                "Origin https://app.example.com is not allowed".to_string()
+            ))
+        );
+        assert_eq!(
+            preflight(&get_only, &preflight_request("PUT")),
+            Some(Err("Method PUT is not allowed".to_string()))
+        );
+        assert_eq!(
+            preflight(&no_headers, &preflight_request("GET")),
+            Some(Err("Header content-type is not allowed".to_string()))
+        );
+    }
+
+    #[test]
+    fn preflight_not_handled() {
+        let with_options_route = api_definition("{allowedOrigins: ['*']}", true);
+        let without_options_route = api_definition("{allowedOrigins: ['*']}", false);
+
+        assert_eq!(
+            preflight(&with_options_route, &preflight_request("GET")),
+            None
+        );
+        assert_eq!(
+            preflight(
+                &without_options_route,
+                &request(Method::OPTIONS, vec![("origin", "https://app.example.com")])
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn cross_origin_response_headers() {
+        let api_definition = api_definition("{allowedOrigins: ['*']}", false);
+        let policy = api_definition.cors.unwrap();
+
+        let allowed = response_headers(
+            &policy,
+            &request(Method::GET, vec![("origin", "https://app.example.com")]).headers,
+        );
+        let same_origin = response_headers(&policy, &request(Method::GET, vec![]).headers);
+
+        assert_eq!(allowed.get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(), "*");
+        assert!(same_origin.is_empty());
+    }
+
+    #[test]
+    fn any_origin_without_credentials() {
+        let api_definition =
+            api_definition("{allowedOrigins: ['*'], allowCredentials: true}", false);
+        let policy = api_definition.cors.unwrap();
+
+        let headers = response_headers(
+            &policy,
+            &request(Method::GET, vec![("origin", "https://app.example.com")]).headers,
+        );
+
+        assert_eq!(headers.get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(), "*");
+        assert!(headers.get(ACCESS_CONTROL_ALLOW_CREDENTIALS).is_none());
+    }
+}
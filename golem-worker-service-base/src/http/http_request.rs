@@ -1,7 +1,12 @@
 use std::collections::HashMap;
 
 use crate::api_definition::ApiSiteString;
+use futures::StreamExt;
+use hyper::http::header::CONTENT_LENGTH;
 use hyper::http::{HeaderMap, Method};
+use poem::web::Multipart;
+use poem::FromRequest;
+use serde::Deserialize;
 use serde_json::Value;
 
 #[derive(Clone)]
@@ -9,7 +14,181 @@ pub struct InputHttpRequest {
     pub input_path: ApiInputPath,
     pub headers: HeaderMap,
     pub req_method: Method,
-    pub req_body: Value,
+    pub req_body: RequestBody,
+}
+
+// The body of a request, decoded based on its content type. A body without a content type
+// is decoded as JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RequestBody {
+    Json(Value),
+    FormUrlEncoded(Vec<(String, String)>),
+    Multipart(Vec<MultipartField>),
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultipartField {
+    pub name: String,
+    pub file_name: Option<String>,
+    pub content_type: Option<String>,
+    pub data: Vec<u8>,
+}
+
+// Limits of the request bodies buffered before the request is resolved. Parts of multipart
+// bodies are limited separately as they are buffered as well.
+#[derive(Clone, Debug, Deserialize)]
+pub struct RequestBodyLimits {
+    pub max_body_size: usize,
+    pub max_part_size: usize,
+}
+
+impl Default for RequestBodyLimits {
+    fn default() -> Self {
+        Self {
+            max_body_size: 10 * 1024 * 1024,
+            max_part_size: 5 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum RequestBodyError {
+    #[error("Request body exceeds the limit of {0} bytes")]
+    BodyTooLarge(usize),
+    #[error("Part {name} of the request body exceeds the limit of {limit} bytes")]
+    PartTooLarge { name: String, limit: usize },
+    #[error("Invalid request body: {0}")]
+    Invalid(String),
+}
+
+impl RequestBodyError {
+    pub fn is_too_large(&self) -> bool {
+        matches!(
+            self,
+            RequestBodyError::BodyTooLarge(_) | RequestBodyError::PartTooLarge { .. }
+        )
+    }
+}
+
+impl RequestBody {
+    pub async fn from_request(
+        request: &poem::Request,
+        body: poem::Body,
+        limits: &RequestBodyLimits,
+    ) -> Result<Self, RequestBodyError> {
+        let content_type = request
+            .content_type()
+            .map(|content_type| {
+                content_type
+                    .split(';')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_ascii_lowercase()
+            })
+            .unwrap_or_default();
+
+        let data = Self::read_limited(request, body, limits.max_body_size).await?;
+
+        match content_type.as_str() {
+            "" | "application/json" => {
+                if data.is_empty() {
+                    Ok(RequestBody::Json(Value::Null))
+                } else {
+                    Self::parse_json(&data)
+                }
+            }
+            "application/x-www-form-urlencoded" => Ok(RequestBody::FormUrlEncoded(
+                url::form_urlencoded::parse(&data).into_owned().collect(),
+            )),
+            "multipart/form-data" => {
+                let mut multipart = Multipart::from_request(
+                    request,
+                    &mut poem::RequestBody::new(poem::Body::from(data)),
+                )
+                .await
+                .map_err(|err| RequestBodyError::Invalid(err.to_string()))?;
+
+                let mut fields = vec![];
+                while let Some(field) = multipart
+                    .next_field()
+                    .await
+                    .map_err(|err| RequestBodyError::Invalid(err.to_string()))?
+                {
+                    let name = field.name().unwrap_or_default().to_string();
+                    let file_name = field.file_name().map(|name| name.to_string());
+                    let content_type = field
+                        .content_type()
+                        .map(|content_type| content_type.to_string());
+                    let data = field
+                        .bytes()
+                        .await
+                        .map_err(|err| RequestBodyError::Invalid(err.to_string()))?;
+
+                    if data.len() > limits.max_part_size {
+                        return Err(RequestBodyError::PartTooLarge {
+                            name,
+                            limit: limits.max_part_size,
+                        });
+                    }
+
+                    fields.push(MultipartField {
+                        name,
+                        file_name,
+                        content_type,
+                        data,
+                    });
+                }
+
+                Ok(RequestBody::Multipart(fields))
+            }
+            content_type if content_type.starts_with("text/") => String::from_utf8(data)
+                .map(RequestBody::Text)
+                .map_err(|err| RequestBodyError::Invalid(err.to_string())),
+            content_type if content_type.ends_with("+json") => Self::parse_json(&data),
+            _ => Ok(RequestBody::Binary(data)),
+        }
+    }
+
+    // Buffers the body, failing as soon as it exceeds the limit. Requests declaring a larger
+    // content length are rejected without reading the body.
+    async fn read_limited(
+        request: &poem::Request,
+        body: poem::Body,
+        limit: usize,
+    ) -> Result<Vec<u8>, RequestBodyError> {
+        let content_length = request
+            .header(CONTENT_LENGTH)
+            .and_then(|length| length.parse::<usize>().ok());
+        if content_length.is_some_and(|length| length > limit) {
+            return Err(RequestBodyError::BodyTooLarge(limit));
+        }
+
+        Self::read_body(body, limit).await
+    }
+
+    // Buffers the body, failing as soon as it exceeds the limit
+    pub async fn read_body(body: poem::Body, limit: usize) -> Result<Vec<u8>, RequestBodyError> {
+        let mut stream = body.into_bytes_stream();
+        let mut data = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|err| RequestBodyError::Invalid(err.to_string()))?;
+            if data.len() + chunk.len() > limit {
+                return Err(RequestBodyError::BodyTooLarge(limit));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        Ok(data)
+    }
+
+    fn parse_json(data: &[u8]) -> Result<Self, RequestBodyError> {
+        serde_json::from_slice(data)
+            .map(RequestBody::Json)
+            .map_err(|err| RequestBodyError::Invalid(err.to_string()))
+    }
 }
 
 impl InputHttpRequest {
@@ -50,9 +229,11 @@ impl ApiInputPath {
 
 pub mod router {
     use crate::{
-        api_definition::http::{PathPattern, QueryInfo, Route, VarInfo},
+        api_definition::http::{
+            PathPattern, QueryInfo, RateLimit, Route, RouteSecurity, StreamBinding, VarInfo,
+        },
         http::router::{Router, RouterPattern},
-        worker_binding::GolemWorkerBinding,
+        worker_binding::RouteBinding,
     };
 
     #[derive(Debug, Clone)]
@@ -60,16 +241,25 @@ pub mod router {
         // size is the index of all path patterns.
         pub path_params: Vec<(VarInfo, usize)>,
         pub query_params: Vec<QueryInfo>,
-        pub binding: GolemWorkerBinding,
+        pub binding: RouteBinding,
+        pub security: Option<RouteSecurity>,
+        pub rate_limit: Option<RateLimit>,
+        pub stream: Option<StreamBinding>,
+        // Method and path of the route, as declared in the api definition
+        pub route: String,
     }
 
     pub fn build(routes: Vec<Route>) -> Router<RouteEntry> {
         let mut router = Router::new();
 
         for route in routes {
+            let route_name = format!("{} {}", route.method, route.path);
             let method = route.method.into();
             let path = route.path;
             let binding = route.binding;
+            let security = route.security;
+            let rate_limit = route.rate_limit;
+            let stream = route.stream;
 
             let path_params = path
                 .path_patterns
@@ -85,6 +275,10 @@ pub mod router {
                 path_params,
                 query_params: path.query_params,
                 binding,
+                security,
+                rate_limit,
+                stream,
+                route: route_name,
             };
 
             let path: Vec<RouterPattern> = path
@@ -113,17 +307,25 @@ mod tests {
     use golem_common::model::IdempotencyKey;
     use golem_service_base::model::{FunctionResult, WorkerId};
 
-    use crate::api_definition::http::HttpApiDefinition;
+    use crate::api_definition::http::{HttpApiDefinition, SecurityScheme, StreamProtocol};
     use crate::evaluator::getter::Getter;
     use crate::evaluator::path::Path;
     use crate::evaluator::{
         DefaultEvaluator, EvaluationError, EvaluationResult, Evaluator, MetadataFetchError,
         WorkerMetadataFetcher,
     };
-    use crate::http::http_request::{ApiInputPath, InputHttpRequest};
+    use crate::http::http_request::{
+        ApiInputPath, InputHttpRequest, MultipartField, RequestBody, RequestBodyError,
+        RequestBodyLimits,
+    };
+    use crate::http::security::AuthError;
     use crate::merge::Merge;
     use crate::primitive::GetPrimitive;
-    use crate::worker_binding::{RequestDetails, WorkerBindingResolver};
+    use crate::service::jwks::ConfiguredJwksProvider;
+    use crate::worker_binding::{
+        RequestDetails, ResolvedBinding, ResolvedWorkerBinding, WorkerBindingResolutionError,
+        WorkerBindingResolver,
+    };
     use crate::worker_bridge_execution::to_response::ToResponse;
     use crate::worker_bridge_execution::{
         WorkerRequest, WorkerRequestExecutor, WorkerRequestExecutorError, WorkerResponse,
@@ -298,6 +500,11 @@ mod tests {
         }
     }
 
+    // Api definitions of these tests do not use JWT bearer security schemes
+    fn no_jwks() -> ConfiguredJwksProvider {
+        ConfiguredJwksProvider::new(&Default::default())
+    }
+
     async fn execute(
         api_request: &InputHttpRequest,
         api_specification: &HttpApiDefinition,
@@ -305,7 +512,12 @@ mod tests {
         let evaluator = get_test_evaluator();
         let worker_metadata_fetcher = get_test_metadata_fetcher("golem:it/api/get-cart-contents");
 
-        let resolved_route = api_request.resolve(api_specification).await.unwrap();
+        let resolved_route = worker_binding(
+            api_request
+                .resolve(api_specification, &no_jwks())
+                .await
+                .unwrap(),
+        );
 
         resolved_route
             .execute_with(&evaluator, &worker_metadata_fetcher)
@@ -943,9 +1155,9 @@ mod tests {
                 function_params,
             );
 
-            let resolved_route = api_request.resolve(&api_specification).await;
+            let resolved_route = api_request.resolve(&api_specification, &no_jwks()).await;
 
-            let result = resolved_route.map(|x| x.worker_detail);
+            let result = resolved_route.map(|x| worker_binding(x).worker_detail);
 
             assert_eq!(result.is_ok(), ok);
         }
@@ -977,7 +1189,12 @@ mod tests {
                 expression,
             );
 
-            let resolved_route = api_request.resolve(&api_specification).await.unwrap();
+            let resolved_route = worker_binding(
+                api_request
+                    .resolve(&api_specification, &no_jwks())
+                    .await
+                    .unwrap(),
+            );
 
             assert_eq!(
                 resolved_route.worker_detail.idempotency_key,
@@ -994,6 +1211,583 @@ mod tests {
         test_key(&headers, Some(IdempotencyKey::new("bar".to_string()))).await;
     }
 
+    #[tokio::test]
+    async fn test_request_body_form_url_encoded() {
+        let request_body = parse_request_body(
+            "application/x-www-form-urlencoded",
+            "name=golem&count=2&message=hello+world",
+        )
+        .await;
+
+        let expected = RequestBody::FormUrlEncoded(vec![
+            ("name".to_string(), "golem".to_string()),
+            ("count".to_string(), "2".to_string()),
+            ("message".to_string(), "hello world".to_string()),
+        ]);
+
+        assert_eq!(request_body, Ok(expected));
+    }
+
+    #[tokio::test]
+    async fn test_request_body_text_and_binary() {
+        let text = parse_request_body("text/plain; charset=utf-8", "hello").await;
+        let binary = parse_request_body("application/octet-stream", vec![1u8, 2, 3]).await;
+        let json = parse_request_body("application/vnd.api+json", r#"{"id": 1}"#).await;
+
+        assert_eq!(text, Ok(RequestBody::Text("hello".to_string())));
+        assert_eq!(binary, Ok(RequestBody::Binary(vec![1, 2, 3])));
+        assert_eq!(json, Ok(RequestBody::Json(serde_json::json!({"id": 1}))));
+    }
+
+    #[tokio::test]
+    async fn test_request_body_invalid_json() {
+        let request_body = parse_request_body("application/json", "{").await;
+
+        assert!(request_body.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_request_body_multipart() {
+        let body = "--boundary\r\n\
+            Content-Disposition: form-data; name=\"title\"\r\n\r\n\
+            report\r\n\
+            --boundary\r\n\
+            Content-Disposition: form-data; name=\"file\"; filename=\"report.txt\"\r\n\
+            Content-Type: text/plain\r\n\r\n\
+            contents\r\n\
+            --boundary--\r\n";
+
+        let request_body = parse_request_body("multipart/form-data; boundary=boundary", body).await;
+
+        let expected = RequestBody::Multipart(vec![
+            MultipartField {
+                name: "title".to_string(),
+                file_name: None,
+                content_type: None,
+                data: b"report".to_vec(),
+            },
+            MultipartField {
+                name: "file".to_string(),
+                file_name: Some("report.txt".to_string()),
+                content_type: Some("text/plain".to_string()),
+                data: b"contents".to_vec(),
+            },
+        ]);
+
+        assert_eq!(request_body, Ok(expected));
+    }
+
+    #[tokio::test]
+    async fn test_request_body_limits() {
+        let limits = RequestBodyLimits {
+            max_body_size: 16,
+            max_part_size: 4,
+        };
+        let body = "--boundary\r\n\
+            Content-Disposition: form-data; name=\"title\"\r\n\r\n\
+            report\r\n\
+            --boundary--\r\n";
+        let large_part_limits = RequestBodyLimits {
+            max_body_size: 1024,
+            max_part_size: 4,
+        };
+
+        let small = parse_limited_request_body("text/plain", "hello", &limits).await;
+        let large =
+            parse_limited_request_body("application/octet-stream", vec![0u8; 17], &limits).await;
+        let large_part = parse_limited_request_body(
+            "multipart/form-data; boundary=boundary",
+            body,
+            &large_part_limits,
+        )
+        .await;
+
+        assert_eq!(small, Ok(RequestBody::Text("hello".to_string())));
+        assert_eq!(large, Err(RequestBodyError::BodyTooLarge(16)));
+        assert_eq!(
+            large_part,
+            Err(RequestBodyError::PartTooLarge {
+                name: "title".to_string(),
+                limit: 4
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_worker_request_form_body_resolution() {
+        let empty_headers = HeaderMap::new();
+
+        let api_request = InputHttpRequest {
+            req_body: RequestBody::FormUrlEncoded(vec![
+                ("address".to_string(), "london".to_string()),
+                ("quantity".to_string(), "2".to_string()),
+            ]),
+            ..get_api_request("foo/2", None, &empty_headers, Value::Null)
+        };
+
+        let expression = r#"let response = golem:it/api/get-cart-contents(request.body.address, request.body.quantity); response"#;
+
+        let api_specification: HttpApiDefinition = get_api_spec(
+            "foo/{user-id}",
+            "shopping-cart-${request.path.user-id}",
+            expression,
+        );
+
+        let test_response = execute(&api_request, &api_specification).await;
+
+        assert_eq!(
+            test_response.function_params,
+            serde_json::json!(["london", 2])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_worker_request_multipart_file_resolution() {
+        let empty_headers = HeaderMap::new();
+
+        let api_request = InputHttpRequest {
+            req_body: RequestBody::Multipart(vec![MultipartField {
+                name: "upload".to_string(),
+                file_name: Some("data.bin".to_string()),
+                content_type: Some("application/octet-stream".to_string()),
+                data: vec![1, 2],
+            }]),
+            ..get_api_request("foo/2", None, &empty_headers, Value::Null)
+        };
+
+        let expression = r#"let response = golem:it/api/get-cart-contents(request.body.upload.file-name, request.body.upload.data); response"#;
+
+        let api_specification: HttpApiDefinition = get_api_spec(
+            "foo/{user-id}",
+            "shopping-cart-${request.path.user-id}",
+            expression,
+        );
+
+        let test_response = execute(&api_request, &api_specification).await;
+
+        assert_eq!(
+            test_response.function_params,
+            serde_json::json!(["data.bin", [1, 2]])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_worker_request_with_api_key_auth() {
+        let api_specification: HttpApiDefinition = serde_yaml::from_str(
+            r#"
+          id: users-api
+          version: 0.0.1
+          securitySchemes:
+          - name: service-keys
+            scheme:
+              type: apiKey
+              location: Header
+              paramName: x-api-key
+              keys:
+              - name: service-a
+                key: secret
+                scopes: [carts]
+          routes:
+          - method: Get
+            path: foo/{user-id}
+            security:
+              scheme: service-keys
+              scopes: [carts]
+            binding:
+              type: wit-worker
+              componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+              workerName: 'shopping-cart-${request.auth.claims.sub}'
+              response: '${let response = golem:it/api/get-cart-contents(request.path.user-id); response}'
+        "#,
+        )
+        .unwrap();
+        // Keys are hashed when the api definition is registered
+        let api_specification = HttpApiDefinition {
+            security_schemes: api_specification
+                .security_schemes
+                .into_iter()
+                .map(SecurityScheme::with_hashed_secrets)
+                .collect(),
+            ..api_specification
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", HeaderValue::from_static("secret"));
+        let api_request = get_api_request("foo/1", None, &headers, Value::Null);
+
+        let test_response = execute(&api_request, &api_specification).await;
+
+        let unauthenticated = get_api_request("foo/1", None, &HeaderMap::new(), Value::Null)
+            .resolve(&api_specification, &no_jwks())
+            .await;
+
+        assert_eq!(test_response.worker_name, "shopping-cart-service-a");
+        assert!(matches!(
+            unauthenticated,
+            Err(WorkerBindingResolutionError::Auth(
+                AuthError::Unauthorized { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_secured_route_is_resolved_only_for_authenticated_callers() {
+        let api_specification: HttpApiDefinition = serde_yaml::from_str(
+            r#"
+          id: users-api
+          version: 0.0.1
+          securitySchemes:
+          - name: service-keys
+            scheme:
+              type: apiKey
+              location: Header
+              paramName: x-api-key
+              keys: []
+          routes:
+          - method: Post
+            path: foo/{user-id}
+            security:
+              scheme: service-keys
+            binding:
+              type: wit-worker
+              componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+              workerName: 'shopping-cart-${request.path.user-id}'
+              response: '${let response = golem:it/api/get-cart-contents(request.body.item); response}'
+        "#,
+        )
+        .unwrap();
+
+        // The route and the caller are checked before the body is read
+        let api_request = InputHttpRequest {
+            req_method: Method::POST,
+            ..get_api_request("foo/1", None, &HeaderMap::new(), Value::Null)
+        };
+        let route = api_request.route(&api_specification).unwrap();
+        let unauthenticated = api_request
+            .authenticate_caller(&api_specification, &route, &no_jwks())
+            .await;
+        let without_claims = api_request
+            .resolve_route(&api_specification, &route, None)
+            .await;
+
+        assert!(matches!(
+            unauthenticated,
+            Err(WorkerBindingResolutionError::Auth(
+                AuthError::Unauthorized { .. }
+            ))
+        ));
+        assert!(matches!(
+            without_claims,
+            Err(WorkerBindingResolutionError::Auth(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_worker_request_with_rate_limit() {
+        let api_specification: HttpApiDefinition = serde_yaml::from_str(
+            r#"
+          id: users-api
+          version: 0.0.1
+          routes:
+          - method: Get
+            path: foo/{user-id}
+            rateLimit:
+              requests: 10
+              periodSeconds: 60
+              key: '${request.headers.x-tenant}'
+            binding:
+              type: wit-worker
+              componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+              workerName: 'shopping-cart-${request.path.user-id}'
+              response: '${let response = golem:it/api/get-cart-contents(request.path.user-id); response}'
+        "#,
+        )
+        .unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-tenant", HeaderValue::from_static("acme"));
+
+        let resolved = get_api_request("foo/1", None, &headers, Value::Null)
+            .resolve(&api_specification, &no_jwks())
+            .await
+            .unwrap();
+
+        let rate_limit = resolved.rate_limit().cloned().unwrap();
+
+        assert_eq!(rate_limit.route, "users-api:0.0.1:Get /foo/{user-id}");
+        assert_eq!(rate_limit.caller, Some("acme".to_string()));
+        assert_eq!(rate_limit.limit.capacity(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_worker_request_with_stream_message() {
+        let api_specification: HttpApiDefinition = serde_yaml::from_str(
+            r#"
+          id: users-api
+          version: 0.0.1
+          routes:
+          - method: Get
+            path: foo/{user-id}
+            stream:
+              protocol: WebSocket
+              source: Results
+            binding:
+              type: wit-worker
+              componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+              workerName: 'shopping-cart-${request.path.user-id}'
+              response: '${let response = golem:it/api/get-cart-contents(request.body.item); response}'
+        "#,
+        )
+        .unwrap();
+
+        let resolved = worker_binding(
+            get_api_request("foo/1", None, &HeaderMap::new(), Value::Null)
+                .resolve(&api_specification, &no_jwks())
+                .await
+                .unwrap(),
+        );
+
+        assert_eq!(
+            resolved.stream.as_ref().map(|stream| stream.protocol),
+            Some(StreamProtocol::WebSocket)
+        );
+
+        // Each message is evaluated in place of the request body
+        let message = RequestBody::Json(serde_json::json!({ "item": "apple" }));
+
+        let test_response: TestResponse = resolved
+            .with_message(&message)
+            .unwrap()
+            .execute_with(
+                &get_test_evaluator(),
+                &get_test_metadata_fetcher("golem:it/api/get-cart-contents"),
+            )
+            .await;
+
+        assert_eq!(test_response.worker_name, "shopping-cart-1");
+        assert_eq!(
+            test_response.function_params,
+            Value::Array(vec![Value::String("apple".to_string())])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_static_and_file_server_bindings() {
+        let api_specification: HttpApiDefinition = serde_yaml::from_str(
+            r#"
+          id: website
+          version: 0.0.1
+          routes:
+          - method: Get
+            path: old/{page}
+            binding:
+              type: static
+              response: '${{status: 302, headers: {location: request.path.page}}}'
+          - method: Get
+            path: assets/{file}
+            binding:
+              type: file-server
+              response: 'website/${request.path.file}'
+        "#,
+        )
+        .unwrap();
+
+        let redirect = get_api_request("old/about", None, &HeaderMap::new(), Value::Null)
+            .resolve(&api_specification, &no_jwks())
+            .await
+            .unwrap();
+
+        let asset = get_api_request("assets/index.html", None, &HeaderMap::new(), Value::Null)
+            .resolve(&api_specification, &no_jwks())
+            .await
+            .unwrap();
+
+        match redirect {
+            ResolvedBinding::Static(binding) => {
+                let result = binding.evaluate().await.unwrap();
+                let location = result
+                    .get_value()
+                    .unwrap()
+                    .get(&Path::from_key("headers"))
+                    .and_then(|headers| headers.get(&Path::from_key("location")))
+                    .unwrap();
+
+                assert_eq!(location, TypeAnnotatedValue::Str("about".to_string()));
+            }
+            other => panic!("Expected a static binding, found {:?}", other),
+        }
+
+        match asset {
+            ResolvedBinding::FileServer(binding) => {
+                assert_eq!(binding.asset_path().await.unwrap(), "website/index.html");
+            }
+            other => panic!("Expected a file server binding, found {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_worker_request_with_named_workers() {
+        let api_specification: HttpApiDefinition = serde_yaml::from_str(
+            r#"
+          id: users-api
+          version: 0.0.1
+          routes:
+          - method: Get
+            path: foo/{user-id}
+            binding:
+              componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+              workerName: 'shopping-cart-${request.path.user-id}'
+              workers:
+              - name: users
+                componentId: 3d2fd1b3-5d89-4a2f-8d0a-6b3f2a4c1e7b
+                workerName: 'user-${request.path.user-id}'
+              response: '${let result = {cart: get-details(request.path.user-id), user: users.get-details(request.path.user-id)}; result.user}'
+        "#,
+        )
+        .unwrap();
+
+        let resolved = worker_binding(
+            get_api_request("foo/1", None, &HeaderMap::new(), Value::Null)
+                .resolve(&api_specification, &no_jwks())
+                .await
+                .unwrap(),
+        );
+
+        let named_workers: Vec<(&str, &str)> = resolved
+            .workers
+            .iter()
+            .map(|(name, worker_detail)| (name.as_str(), worker_detail.worker_name.as_str()))
+            .collect();
+
+        assert_eq!(named_workers, vec![("users", "user-1")]);
+
+        // Both workers are called, and the result of each call is kept apart
+        let test_response: TestResponse = resolved
+            .execute_with(
+                &get_test_evaluator(),
+                &get_test_metadata_fetcher("get-details"),
+            )
+            .await;
+
+        assert_eq!(test_response.worker_name, "user-1");
+        assert_eq!(test_response.function_name, "get-details");
+        assert_eq!(
+            test_response.function_params,
+            Value::Array(vec![Value::String("1".to_string())])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_worker_response_of_concurrent_calls() {
+        let api_specification: HttpApiDefinition = serde_yaml::from_str(
+            r#"
+          id: users-api
+          version: 0.0.1
+          routes:
+          - method: Get
+            path: foo/{user-id}
+            binding:
+              componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+              workerName: 'shopping-cart-${request.path.user-id}'
+              workers:
+              - name: users
+                componentId: 3d2fd1b3-5d89-4a2f-8d0a-6b3f2a4c1e7b
+                workerName: 'user-${request.path.user-id}'
+              response: '${let result = {cart: get-details(request.path.user-id), user: users.get-details(request.path.user-id)}; worker.response}'
+        "#,
+        )
+        .unwrap();
+
+        let resolved = worker_binding(
+            get_api_request("foo/1", None, &HeaderMap::new(), Value::Null)
+                .resolve(&api_specification, &no_jwks())
+                .await
+                .unwrap(),
+        );
+
+        // The calls are made concurrently, and the response of the worker of the binding is
+        // still available afterwards
+        let test_response: TestResponse = resolved
+            .execute_with(
+                &get_test_evaluator(),
+                &get_test_metadata_fetcher("get-details"),
+            )
+            .await;
+
+        assert_eq!(test_response.worker_name, "shopping-cart-1");
+        assert_eq!(test_response.function_name, "get-details");
+    }
+
+    #[tokio::test]
+    async fn test_worker_request_with_functions() {
+        let api_specification: HttpApiDefinition = serde_yaml::from_str(
+            r#"
+          id: users-api
+          version: 0.0.1
+          functions:
+          - name: cart-name
+            params: [id]
+            body: 'shopping-cart-${lower(id)}'
+          routes:
+          - method: Get
+            path: foo/{user-id}
+            binding:
+              componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+              workerName: '${cart-name(request.path.user-id)}'
+              response: '${get-details(upper(request.path.user-id))}'
+        "#,
+        )
+        .unwrap();
+
+        let resolved = worker_binding(
+            get_api_request("foo/Ann", None, &HeaderMap::new(), Value::Null)
+                .resolve(&api_specification, &no_jwks())
+                .await
+                .unwrap(),
+        );
+
+        assert_eq!(resolved.worker_detail.worker_name, "shopping-cart-ann");
+
+        let test_response: TestResponse = resolved
+            .execute_with(
+                &get_test_evaluator(),
+                &get_test_metadata_fetcher("get-details"),
+            )
+            .await;
+
+        assert_eq!(
+            test_response.function_params,
+            Value::Array(vec![Value::String("ANN".to_string())])
+        );
+    }
+
+    fn worker_binding(resolved: ResolvedBinding) -> ResolvedWorkerBinding {
+        match resolved {
+            ResolvedBinding::Worker(binding) => binding,
+            other => panic!("Expected a worker binding, found {:?}", other),
+        }
+    }
+
+    async fn parse_request_body(
+        content_type: &str,
+        body: impl Into<poem::Body>,
+    ) -> Result<RequestBody, RequestBodyError> {
+        parse_limited_request_body(content_type, body, &RequestBodyLimits::default()).await
+    }
+
+    async fn parse_limited_request_body(
+        content_type: &str,
+        body: impl Into<poem::Body>,
+        limits: &RequestBodyLimits,
+    ) -> Result<RequestBody, RequestBodyError> {
+        let (request, body) = poem::Request::builder()
+            .content_type(content_type)
+            .body(body)
+            .split();
+
+        RequestBody::from_request(&request, body, limits).await
+    }
+
     fn get_api_request(
         base_path: &str,
         query_path: Option<&str>,
@@ -1007,7 +1801,7 @@ mod tests {
             },
             headers: headers.clone(),
             req_method: Method::GET,
-            req_body,
+            req_body: RequestBody::Json(req_body),
         }
     }
 
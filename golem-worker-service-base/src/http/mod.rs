@@ -1,5 +1,9 @@
 pub use http_request::*;
 
+pub mod cors;
+
 pub mod http_request;
 
 pub mod router;
+
+pub mod security;
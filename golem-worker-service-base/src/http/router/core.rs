@@ -33,6 +33,19 @@ impl<T> Router<T> {
         let result = node.matches(path)?;
         Some(result)
     }
+
+    /// Returns the methods having a route matching the path.
+    pub fn allowed_methods(&self, path: &[&str]) -> Vec<Method> {
+        let mut methods: Vec<Method> = self
+            .tree
+            .iter()
+            .filter(|(_, node)| node.matches(path).is_some())
+            .map(|(method, _)| method.clone())
+            .collect();
+
+        methods.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        methods
+    }
 }
 
 #[test]
@@ -54,3 +67,27 @@ fn test_router() {
 
     assert_eq!(router.check_path(&Method::POST, &["api"]), Some(&1));
 }
+
+#[test]
+fn test_allowed_methods() {
+    let mut router = Router::new();
+
+    router.add_route(
+        Method::GET,
+        vec![RouterPattern::literal("api"), RouterPattern::Variable],
+        1,
+    );
+    router.add_route(
+        Method::PUT,
+        vec![RouterPattern::literal("api"), RouterPattern::Variable],
+        2,
+    );
+    router.add_route(Method::POST, vec![RouterPattern::literal("api")], 3);
+
+    assert_eq!(
+        router.allowed_methods(&["api", "123"]),
+        vec![Method::GET, Method::PUT]
+    );
+    assert_eq!(router.allowed_methods(&["api"]), vec![Method::POST]);
+    assert_eq!(router.allowed_methods(&["other"]), Vec::<Method>::new());
+}
@@ -0,0 +1,674 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use base64::Engine;
+use http::header::AUTHORIZATION;
+use jsonwebtoken::jwk::{AlgorithmParameters, EllipticCurve, Jwk};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde_json::{json, Value};
+
+use crate::api_definition::http::{
+    ApiKeyLocation, ApiKeySecurityScheme, BasicSecurityScheme, JwtBearerSecurityScheme,
+    PasswordHash, RouteSecurity, SecretHash, SecurityScheme, SecuritySchemeKind,
+};
+use crate::http::InputHttpRequest;
+use crate::service::jwks::JwksProvider;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthError {
+    // The caller could not be authenticated, answered with 401
+    Unauthorized {
+        challenge: Option<&'static str>,
+        message: String,
+    },
+    // The caller is authenticated but not granted all the scopes of the route, answered with 403
+    Forbidden(String),
+    // The security scheme cannot be used, for example because its JWKS is not configured
+    InvalidConfiguration(String),
+}
+
+impl AuthError {
+    fn unauthorized(scheme: &SecurityScheme, message: impl Into<String>) -> Self {
+        AuthError::Unauthorized {
+            challenge: scheme.scheme.challenge(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for AuthError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthError::Unauthorized { message, .. } => write!(f, "Unauthorized: {message}"),
+            AuthError::Forbidden(message) => write!(f, "Forbidden: {message}"),
+            AuthError::InvalidConfiguration(message) => {
+                write!(f, "Invalid security configuration: {message}")
+            }
+        }
+    }
+}
+
+// Claims of an authenticated caller, available in binding expressions as `request.auth.claims`.
+// API keys and basic credentials get a `sub` and a `scope` claim, mirroring the standard JWT ones
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthClaims {
+    pub claims: Value,
+    pub scopes: Vec<String>,
+}
+
+pub async fn authenticate(
+    request: &InputHttpRequest,
+    scheme: &SecurityScheme,
+    route_security: &RouteSecurity,
+    jwks_provider: &(dyn JwksProvider + Sync + Send),
+) -> Result<AuthClaims, AuthError> {
+    let claims = match &scheme.scheme {
+        SecuritySchemeKind::ApiKey(api_key) => authenticate_api_key(request, scheme, api_key)?,
+        SecuritySchemeKind::JwtBearer(jwt_bearer) => {
+            authenticate_jwt_bearer(request, scheme, jwt_bearer, jwks_provider).await?
+        }
+        SecuritySchemeKind::Basic(basic) => authenticate_basic(request, scheme, basic).await?,
+    };
+
+    let missing_scopes: Vec<&str> = route_security
+        .scopes
+        .iter()
+        .filter(|scope| !claims.scopes.contains(scope))
+        .map(|scope| scope.as_str())
+        .collect();
+
+    if missing_scopes.is_empty() {
+        Ok(claims)
+    } else {
+        Err(AuthError::Forbidden(format!(
+            "Missing required scopes: {}",
+            missing_scopes.join(", ")
+        )))
+    }
+}
+
+fn authenticate_api_key(
+    request: &InputHttpRequest,
+    scheme: &SecurityScheme,
+    api_key: &ApiKeySecurityScheme,
+) -> Result<AuthClaims, AuthError> {
+    let provided_key = match api_key.location {
+        ApiKeyLocation::Header => request
+            .headers
+            .get(api_key.param_name.as_str())
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string()),
+        ApiKeyLocation::Query => request
+            .input_path
+            .query_components()
+            .and_then(|query| query.get(&api_key.param_name).cloned()),
+    }
+    .ok_or_else(|| AuthError::unauthorized(scheme, "Missing api key"))?;
+
+    let key = api_key
+        .keys
+        .iter()
+        .find(|key| verify_secret(key.key_hash.as_deref(), &provided_key))
+        .ok_or_else(|| AuthError::unauthorized(scheme, "Invalid api key"))?;
+
+    Ok(claims_of(&key.name, &key.scopes))
+}
+
+async fn authenticate_basic(
+    request: &InputHttpRequest,
+    scheme: &SecurityScheme,
+    basic: &BasicSecurityScheme,
+) -> Result<AuthClaims, AuthError> {
+    let encoded = authorization(request, "Basic")
+        .ok_or_else(|| AuthError::unauthorized(scheme, "Missing basic credentials"))?;
+
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(|| AuthError::unauthorized(scheme, "Malformed basic credentials"))?;
+
+    let (username, password) = decoded
+        .split_once(':')
+        .ok_or_else(|| AuthError::unauthorized(scheme, "Malformed basic credentials"))?;
+
+    let user = basic.users.iter().find(|user| user.username == username);
+
+    // Verifying a password hash is deliberately expensive, so it does not block the runtime
+    let password_hash = user.and_then(|user| user.password_hash.clone());
+    let password = password.to_string();
+    let verified = tokio::task::spawn_blocking(move || {
+        password_hash.is_some_and(|hash| PasswordHash(hash).verify(&password))
+    })
+    .await
+    .unwrap_or(false);
+
+    match user {
+        Some(user) if verified => Ok(claims_of(&user.username, &user.scopes)),
+        _ => Err(AuthError::unauthorized(
+            scheme,
+            "Invalid username or password",
+        )),
+    }
+}
+
+async fn authenticate_jwt_bearer(
+    request: &InputHttpRequest,
+    scheme: &SecurityScheme,
+    jwt_bearer: &JwtBearerSecurityScheme,
+    jwks_provider: &(dyn JwksProvider + Sync + Send),
+) -> Result<AuthClaims, AuthError> {
+    let token = authorization(request, "Bearer")
+        .ok_or_else(|| AuthError::unauthorized(scheme, "Missing bearer token"))?;
+
+    let header = jsonwebtoken::decode_header(token)
+        .map_err(|err| AuthError::unauthorized(scheme, format!("Invalid bearer token: {err}")))?;
+
+    let jwks = jwks_provider
+        .get(&jwt_bearer.jwks)
+        .await
+        .map_err(AuthError::InvalidConfiguration)?;
+
+    let jwk = match &header.kid {
+        Some(kid) => jwks.find(kid),
+        None => jwks.keys.first(),
+    }
+    .ok_or_else(|| AuthError::unauthorized(scheme, "Unknown signing key"))?;
+
+    let decoding_key = DecodingKey::from_jwk(jwk).map_err(|err| {
+        AuthError::InvalidConfiguration(format!("Invalid key in JWKS {}: {err}", jwt_bearer.jwks))
+    })?;
+
+    // The algorithm named in the token header is only accepted if the signing key allows it,
+    // otherwise a token could pick a weaker algorithm than the key is meant for
+    let algorithms = jwk_algorithms(jwk)?;
+    if !algorithms.contains(&header.alg) {
+        return Err(AuthError::unauthorized(
+            scheme,
+            format!(
+                "Algorithm {:?} is not allowed for the signing key",
+                header.alg
+            ),
+        ));
+    }
+
+    let mut validation = Validation::new(header.alg);
+    validation.algorithms = algorithms;
+    if let Some(issuer) = &jwt_bearer.issuer {
+        validation.set_issuer(&[issuer]);
+    }
+    match &jwt_bearer.audience {
+        Some(audience) => validation.set_audience(&[audience]),
+        None => validation.validate_aud = false,
+    }
+
+    let token_data = jsonwebtoken::decode::<Value>(token, &decoding_key, &validation)
+        .map_err(|err| AuthError::unauthorized(scheme, format!("Invalid bearer token: {err}")))?;
+
+    let scopes = jwt_scopes(&token_data.claims);
+
+    Ok(AuthClaims {
+        claims: token_data.claims,
+        scopes,
+    })
+}
+
+// Only the stored hash of a key is compared with the provided one, keys without a hash never
+// match
+fn verify_secret(hash: Option<&str>, provided: &str) -> bool {
+    hash.is_some_and(|hash| SecretHash(hash.to_string()).verify(provided))
+}
+
+// The algorithms a key can be used with, either the one pinned by its `alg` parameter, or the
+// ones matching its key type
+fn jwk_algorithms(jwk: &Jwk) -> Result<Vec<Algorithm>, AuthError> {
+    if let Some(key_algorithm) = &jwk.common.key_algorithm {
+        return Algorithm::from_str(&key_algorithm.to_string())
+            .map(|algorithm| vec![algorithm])
+            .map_err(|_| {
+                AuthError::InvalidConfiguration(format!(
+                    "Key algorithm {key_algorithm} in JWKS file cannot be used for signatures"
+                ))
+            });
+    }
+
+    let algorithms = match &jwk.algorithm {
+        AlgorithmParameters::RSA(_) => vec![
+            Algorithm::RS256,
+            Algorithm::RS384,
+            Algorithm::RS512,
+            Algorithm::PS256,
+            Algorithm::PS384,
+            Algorithm::PS512,
+        ],
+        AlgorithmParameters::EllipticCurve(params) => match params.curve {
+            EllipticCurve::P256 => vec![Algorithm::ES256],
+            EllipticCurve::P384 => vec![Algorithm::ES384],
+            _ => {
+                return Err(AuthError::InvalidConfiguration(format!(
+                    "Unsupported elliptic curve {:?} in JWKS file",
+                    params.curve
+                )))
+            }
+        },
+        AlgorithmParameters::OctetKeyPair(_) => vec![Algorithm::EdDSA],
+        AlgorithmParameters::OctetKey(_) => {
+            vec![Algorithm::HS256, Algorithm::HS384, Algorithm::HS512]
+        }
+    };
+
+    Ok(algorithms)
+}
+
+fn authorization<'a>(request: &'a InputHttpRequest, auth_scheme: &str) -> Option<&'a str> {
+    let value = request.headers.get(AUTHORIZATION)?.to_str().ok()?;
+    let (name, credentials) = value.split_once(' ')?;
+
+    if name.eq_ignore_ascii_case(auth_scheme) {
+        Some(credentials.trim())
+    } else {
+        None
+    }
+}
+
+fn claims_of(subject: &str, scopes: &[String]) -> AuthClaims {
+    AuthClaims {
+        claims: json!({ "sub": subject, "scope": scopes.join(" ") }),
+        scopes: scopes.to_vec(),
+    }
+}
+
+// Scopes are either a space separated `scope` claim, or an `scp` claim holding a list or a
+// space separated string
+fn jwt_scopes(claims: &Value) -> Vec<String> {
+    let split = |scopes: &str| {
+        scopes
+            .split_whitespace()
+            .map(|scope| scope.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    match (claims.get("scope"), claims.get("scp")) {
+        (Some(Value::String(scopes)), _) => split(scopes),
+        (_, Some(Value::String(scopes))) => split(scopes),
+        (_, Some(Value::Array(scopes))) => scopes
+            .iter()
+            .filter_map(|scope| scope.as_str().map(|scope| scope.to_string()))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io::Write;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use base64::Engine;
+    use http::{HeaderMap, HeaderValue, Method};
+    use jsonwebtoken::{Algorithm, EncodingKey, Header};
+    use serde_json::{json, Value};
+
+    use crate::api_definition::http::{
+        ApiKey, ApiKeyLocation, ApiKeySecurityScheme, BasicAuthUser, BasicSecurityScheme,
+        JwtBearerSecurityScheme, RouteSecurity, SecurityScheme, SecuritySchemeKind,
+    };
+    use crate::app_config::JwksConfig;
+    use crate::http::security::{authenticate, jwt_scopes, AuthError};
+    use crate::http::{ApiInputPath, InputHttpRequest, RequestBody};
+    use crate::service::jwks::ConfiguredJwksProvider;
+
+    fn request(query_path: Option<&str>, headers: Vec<(&'static str, &str)>) -> InputHttpRequest {
+        let mut header_map = HeaderMap::new();
+        for (name, value) in headers {
+            header_map.insert(name, HeaderValue::from_str(value).unwrap());
+        }
+
+        InputHttpRequest {
+            input_path: ApiInputPath {
+                base_path: "/foo".to_string(),
+                query_path: query_path.map(|query| query.to_string()),
+            },
+            headers: header_map,
+            req_method: Method::GET,
+            req_body: RequestBody::Json(Value::Null),
+        }
+    }
+
+    fn api_key_scheme(location: ApiKeyLocation) -> SecurityScheme {
+        SecurityScheme {
+            name: "keys".to_string(),
+            scheme: SecuritySchemeKind::ApiKey(ApiKeySecurityScheme {
+                location,
+                param_name: "x-api-key".to_string(),
+                keys: vec![ApiKey {
+                    name: "service-a".to_string(),
+                    key: Some("secret".to_string()),
+                    key_hash: None,
+                    scopes: vec!["read".to_string()],
+                }],
+            }),
+        }
+        .with_hashed_secrets()
+    }
+
+    fn basic_scheme() -> SecurityScheme {
+        SecurityScheme {
+            name: "users".to_string(),
+            scheme: SecuritySchemeKind::Basic(BasicSecurityScheme {
+                users: vec![BasicAuthUser {
+                    username: "alice".to_string(),
+                    password: Some("wonderland".to_string()),
+                    password_hash: None,
+                    scopes: vec![],
+                }],
+            }),
+        }
+        .with_hashed_secrets()
+    }
+
+    fn jwks() -> ConfiguredJwksProvider {
+        ConfiguredJwksProvider::new(&JwksConfig::default())
+    }
+
+    fn route_security(scheme: &SecurityScheme, scopes: Vec<&str>) -> RouteSecurity {
+        RouteSecurity {
+            scheme: scheme.name.clone(),
+            scopes: scopes.into_iter().map(|scope| scope.to_string()).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn api_key_in_header() {
+        let scheme = api_key_scheme(ApiKeyLocation::Header);
+        let security = route_security(&scheme, vec!["read"]);
+
+        let result = authenticate(
+            &request(None, vec![("x-api-key", "secret")]),
+            &scheme,
+            &security,
+            &jwks(),
+        )
+        .await
+        .map(|claims| claims.claims);
+
+        assert_eq!(result, Ok(json!({"sub": "service-a", "scope": "read"})));
+    }
+
+    #[tokio::test]
+    async fn api_key_in_query() {
+        let scheme = api_key_scheme(ApiKeyLocation::Query);
+        let security = route_security(&scheme, vec![]);
+
+        let valid = authenticate(
+            &request(Some("x-api-key=secret"), vec![]),
+            &scheme,
+            &security,
+            &jwks(),
+        )
+        .await
+        .is_ok();
+        let invalid = authenticate(
+            &request(Some("x-api-key=other"), vec![]),
+            &scheme,
+            &security,
+            &jwks(),
+        )
+        .await;
+
+        assert!(valid);
+        assert_eq!(
+            invalid,
+            Err(AuthError::Unauthorized {
+                challenge: None,
+                message: "Invalid api key".to_string()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_scope_is_forbidden() {
+        let scheme = api_key_scheme(ApiKeyLocation::Header);
+        let security = route_security(&scheme, vec!["read", "write"]);
+
+        let result = authenticate(
+            &request(None, vec![("x-api-key", "secret")]),
+            &scheme,
+            &security,
+            &jwks(),
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            Err(AuthError::Forbidden(
+                "Missing required scopes: write".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn basic_credentials() {
+        let scheme = basic_scheme();
+        let security = route_security(&scheme, vec![]);
+
+        let encode = |credentials: &str| {
+            format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(credentials)
+            )
+        };
+
+        let valid = authenticate(
+            &request(
+                None,
+                vec![("authorization", encode("alice:wonderland").as_str())],
+            ),
+            &scheme,
+            &security,
+            &jwks(),
+        )
+        .await
+        .map(|claims| claims.claims);
+        let invalid = authenticate(
+            &request(
+                None,
+                vec![("authorization", encode("alice:looking-glass").as_str())],
+            ),
+            &scheme,
+            &security,
+            &jwks(),
+        )
+        .await;
+        let missing = authenticate(&request(None, vec![]), &scheme, &security, &jwks()).await;
+
+        assert_eq!(valid, Ok(json!({"sub": "alice", "scope": ""})));
+        assert_eq!(
+            invalid,
+            Err(AuthError::Unauthorized {
+                challenge: Some("Basic"),
+                message: "Invalid username or password".to_string()
+            })
+        );
+        assert_eq!(
+            missing,
+            Err(AuthError::Unauthorized {
+                challenge: Some("Basic"),
+                message: "Missing basic credentials".to_string()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn jwt_bearer_without_valid_token() {
+        let scheme = SecurityScheme {
+            name: "jwt".to_string(),
+            scheme: SecuritySchemeKind::JwtBearer(JwtBearerSecurityScheme {
+                jwks: "unknown".to_string(),
+                issuer: None,
+                audience: None,
+            }),
+        };
+        let security = route_security(&scheme, vec![]);
+
+        let missing_token = authenticate(&request(None, vec![]), &scheme, &security, &jwks()).await;
+        let invalid_token = authenticate(
+            &request(None, vec![("authorization", "Bearer not-a-token")]),
+            &scheme,
+            &security,
+            &jwks(),
+        )
+        .await;
+
+        assert_eq!(
+            missing_token,
+            Err(AuthError::Unauthorized {
+                challenge: Some("Bearer"),
+                message: "Missing bearer token".to_string()
+            })
+        );
+        assert!(matches!(
+            invalid_token,
+            Err(AuthError::Unauthorized {
+                challenge: Some("Bearer"),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn secrets_are_stored_hashed() {
+        let scheme = api_key_scheme(ApiKeyLocation::Header);
+
+        match scheme.scheme {
+            SecuritySchemeKind::ApiKey(api_key) => {
+                let key = &api_key.keys[0];
+                assert_eq!(key.key, None);
+                assert!(key
+                    .key_hash
+                    .as_ref()
+                    .is_some_and(|hash| !hash.contains("secret")));
+            }
+            _ => panic!("Expected an api key scheme"),
+        }
+
+        match basic_scheme().scheme {
+            SecuritySchemeKind::Basic(basic) => {
+                let user = &basic.users[0];
+                assert_eq!(user.password, None);
+                assert!(user
+                    .password_hash
+                    .as_ref()
+                    .is_some_and(|hash| hash.starts_with("$argon2")));
+            }
+            _ => panic!("Expected a basic scheme"),
+        }
+    }
+
+    #[tokio::test]
+    async fn jwt_bearer_signed_with_key_from_configured_jwks() {
+        let secret = b"a-shared-secret-of-at-least-32-bytes";
+        let jwks = json!({
+            "keys": [{
+                "kty": "oct",
+                "kid": "test",
+                "alg": "HS256",
+                "k": base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(secret),
+            }]
+        });
+        let mut jwks_file = tempfile::NamedTempFile::new().unwrap();
+        jwks_file.write_all(jwks.to_string().as_bytes()).unwrap();
+        let jwks_provider = Arc::new(ConfiguredJwksProvider::new(&JwksConfig {
+            refresh_interval: Duration::from_secs(60),
+            sources: HashMap::from([("issuer".to_string(), jwks_file.path().to_path_buf())]),
+        }));
+
+        let scheme = SecurityScheme {
+            name: "jwt".to_string(),
+            scheme: SecuritySchemeKind::JwtBearer(JwtBearerSecurityScheme {
+                jwks: "issuer".to_string(),
+                issuer: Some("https://issuer.example".to_string()),
+                audience: Some("cart-api".to_string()),
+            }),
+        };
+        let security = route_security(&scheme, vec!["read"]);
+
+        let token = |algorithm: Algorithm, issuer: &str, audience: &str| {
+            let mut header = Header::new(algorithm);
+            header.kid = Some("test".to_string());
+            let claims = json!({
+                "sub": "alice",
+                "iss": issuer,
+                "aud": audience,
+                "exp": 4102444800u64,
+                "scope": "read write",
+            });
+            let token =
+                jsonwebtoken::encode(&header, &claims, &EncodingKey::from_secret(secret)).unwrap();
+            format!("Bearer {token}")
+        };
+        let authenticate_with = |authorization: String| {
+            let scheme = scheme.clone();
+            let security = security.clone();
+            let jwks_provider = jwks_provider.clone();
+            async move {
+                authenticate(
+                    &request(None, vec![("authorization", authorization.as_str())]),
+                    &scheme,
+                    &security,
+                    jwks_provider.as_ref(),
+                )
+                .await
+            }
+        };
+
+        let valid = authenticate_with(token(
+            Algorithm::HS256,
+            "https://issuer.example",
+            "cart-api",
+        ))
+        .await
+        .unwrap();
+        let wrong_issuer =
+            authenticate_with(token(Algorithm::HS256, "https://other.example", "cart-api")).await;
+        let wrong_audience = authenticate_with(token(
+            Algorithm::HS256,
+            "https://issuer.example",
+            "other-api",
+        ))
+        .await;
+        let other_algorithm = authenticate_with(token(
+            Algorithm::HS384,
+            "https://issuer.example",
+            "cart-api",
+        ))
+        .await;
+
+        assert_eq!(valid.claims["sub"], json!("alice"));
+        assert_eq!(valid.scopes, vec!["read".to_string(), "write".to_string()]);
+        for rejected in [wrong_issuer, wrong_audience, other_algorithm] {
+            assert!(matches!(
+                rejected,
+                Err(AuthError::Unauthorized {
+                    challenge: Some("Bearer"),
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn scopes_of_jwt_claims() {
+        assert_eq!(
+            jwt_scopes(&json!({"scope": "read write"})),
+            vec!["read".to_string(), "write".to_string()]
+        );
+        assert_eq!(
+            jwt_scopes(&json!({"scp": ["read"]})),
+            vec!["read".to_string()]
+        );
+        assert_eq!(jwt_scopes(&json!({"sub": "alice"})), Vec::<String>::new());
+    }
+}
@@ -13,6 +13,7 @@ mod primitive;
 pub mod repo;
 pub mod service;
 pub mod tokeniser;
+mod type_checker;
 pub mod wasm_wave_read;
 mod worker_binding;
 pub mod worker_bridge_execution;
@@ -2,8 +2,8 @@ use crate::expression::Expr;
 use crate::tokeniser::tokenizer::{MultiCharTokens, Token, Tokenizer};
 
 use crate::parser::expr::{
-    code_block, constructor, flags, if_condition, let_statement, math_op, params, pattern_match,
-    record, selection, sequence, tuple, util,
+    code_block, constructor, flags, if_condition, lambda, let_statement, math_op, params,
+    pattern_match, record, selection, sequence, tuple, util,
 };
 use crate::parser::{GolemParser, ParseError};
 use internal::*;
@@ -267,9 +267,12 @@ pub(crate) fn parse_code(input: impl AsRef<str>) -> Result<Expr, ParseError> {
             }
 
             Token::MultiChar(MultiCharTokens::Arrow) => {
-                return Err(
+                let params = previous_expression.final_expr()?.ok_or::<ParseError>(
                     format!("Arrow at {} is not a valid expression", tokenizer.pos()).into(),
-                )
+                )?;
+
+                let lambda = lambda::create_lambda(&mut tokenizer, params)?;
+                previous_expression.reset_and_build(lambda);
             }
             Token::Escape => {}
             Token::RCurly => {}
@@ -333,6 +336,14 @@ mod internal {
                     let str = match i {
                         Expr::Identifier(str) => Ok(str.to_string()),
                         Expr::Literal(str) => Ok(str.to_string()),
+                        // A function of another worker of the binding, such as users.get-user
+                        Expr::SelectField(worker, function_name) => match worker.as_ref() {
+                            Expr::Identifier(worker) => Ok(format!("{}.{}", worker, function_name)),
+                            expr => Err(ParseError::Message(format!(
+                                "Invalid expression: {}",
+                                expression::to_string(expr).unwrap()
+                            ))),
+                        },
                         expr => Err(ParseError::Message(format!(
                             "Invalid expression: {}",
                             expression::to_string(expr).unwrap()
@@ -1240,4 +1251,72 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_function_call_on_named_worker() {
+        let expression_parser = ExprParser {};
+
+        let result = expression_parser
+            .parse("${users.get-user(request.path.user-id)}")
+            .unwrap();
+
+        let expected = Expr::Call(
+            "users.get-user".to_string(),
+            vec![Expr::SelectField(
+                Box::new(Expr::SelectField(
+                    Box::new(Expr::Identifier("request".to_string())),
+                    "path".to_string(),
+                )),
+                "user-id".to_string(),
+            )],
+        );
+
+        assert_eq!(result, expected);
+        assert_eq!(
+            crate::expression::to_string(&result).unwrap(),
+            "${users.get-user(request.path.user-id)}".to_string()
+        );
+    }
+
+    #[test]
+    fn test_function_call_with_lambda() {
+        let expression_parser = ExprParser {};
+
+        let result = expression_parser
+            .parse("${fold(request.body, 0, (acc, item) => item.price)}")
+            .unwrap();
+
+        let expected = Expr::Call(
+            "fold".to_string(),
+            vec![
+                Expr::SelectField(
+                    Box::new(Expr::Identifier("request".to_string())),
+                    "body".to_string(),
+                ),
+                Expr::Number(InnerNumber::UnsignedInteger(0)),
+                Expr::Lambda(
+                    vec!["acc".to_string(), "item".to_string()],
+                    Box::new(Expr::SelectField(
+                        Box::new(Expr::Identifier("item".to_string())),
+                        "price".to_string(),
+                    )),
+                ),
+            ],
+        );
+
+        assert_eq!(result, expected);
+        assert_eq!(
+            crate::expression::to_string(&result).unwrap(),
+            "${fold(request.body, 0, (acc, item) => item.price)}".to_string()
+        );
+    }
+
+    #[test]
+    fn test_lambda_with_invalid_parameter() {
+        let expression_parser = ExprParser {};
+
+        let result = expression_parser.parse("${map(request.body, 1 => 2)}");
+
+        assert!(result.is_err());
+    }
 }
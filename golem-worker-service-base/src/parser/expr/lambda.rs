@@ -0,0 +1,40 @@
+use crate::expression;
+use crate::expression::Expr;
+use crate::parser::expr_parser::parse_code;
+use crate::parser::ParseError;
+use crate::tokeniser::tokenizer::{Token, Tokenizer};
+
+// Assuming the tokenizer already consumed `=>`, and the parameters are parsed as the
+// previous expression, such as `x` or `(acc, x)`
+pub(crate) fn create_lambda(tokenizer: &mut Tokenizer, params: Expr) -> Result<Expr, ParseError> {
+    let params = match params {
+        Expr::Identifier(param) => vec![param],
+        Expr::Tuple(params) => params
+            .into_iter()
+            .map(|param| match param {
+                Expr::Identifier(param) => Ok(param),
+                expr => Err(invalid_param(&expr)),
+            })
+            .collect::<Result<Vec<String>, ParseError>>()?,
+        expr => return Err(invalid_param(&expr)),
+    };
+
+    // The body ends with the expression the lambda is part of
+    let body = match tokenizer.capture_string_until(&Token::SemiColon) {
+        Some(body) => body,
+        None => tokenizer.consume_rest().to_string(),
+    };
+
+    if body.trim().is_empty() {
+        return Err(ParseError::Message("Expecting a body after =>".to_string()));
+    }
+
+    Ok(Expr::Lambda(params, Box::new(parse_code(body)?)))
+}
+
+fn invalid_param(expr: &Expr) -> ParseError {
+    ParseError::Message(format!(
+        "Expecting a parameter name before =>. But found {}",
+        expression::to_string(expr).unwrap()
+    ))
+}
@@ -3,6 +3,7 @@ mod constructor;
 pub(crate) mod expr_parser;
 mod flags;
 mod if_condition;
+mod lambda;
 mod let_statement;
 mod math_op;
 mod pattern_match;
@@ -67,6 +67,7 @@ fn is_valid_tuple_element(expr: &Expr) -> bool {
     match expr {
         Expr::Identifier(_) => true,
         Expr::Call(_, _) => false,
+        Expr::Lambda(_, _) => false,
         Expr::Number(_) => true,
         Expr::Boolean(_) => true,
         Expr::Flags(_) => true,
@@ -65,6 +65,13 @@ pub trait ApiDefinitionService<AuthCtx, Namespace, ApiDefinition, ValidationErro
         namespace: Namespace,
         auth_ctx: &AuthCtx,
     ) -> ApiResult<Vec<ApiDefinition>, ValidationError>;
+
+    // The latest versions of the components the definition is bound to
+    async fn get_components(
+        &self,
+        definition: &ApiDefinition,
+        auth_ctx: &AuthCtx,
+    ) -> ApiResult<Vec<Component>, ValidationError>;
 }
 
 // An ApiDefinitionKey is just the original ApiDefinitionId with additional information of version and a possibility of namespace.
@@ -144,14 +151,21 @@ where
         definition: &ApiDefinition,
         auth_ctx: &AuthCtx,
     ) -> Result<Vec<Component>, ApiRegistrationError<ValidationError>> {
-        let get_components = definition
-            .get_golem_worker_bindings()
-            .iter()
-            .cloned()
-            .map(|binding| async move {
-                let id = &binding.component_id;
+        // A binding can call the workers of several components
+        let mut component_ids: Vec<ComponentId> = vec![];
+        for binding in definition.get_golem_worker_bindings() {
+            for component_id in binding.component_ids() {
+                if !component_ids.contains(component_id) {
+                    component_ids.push(component_id.clone());
+                }
+            }
+        }
+
+        let get_components = component_ids
+            .into_iter()
+            .map(|id| async move {
                 self.component_service
-                    .get_latest(id, auth_ctx)
+                    .get_latest(&id, auth_ctx)
                     .await
                     .map_err(|e| {
                         tracing::error!("Error getting latest component: {:?}", e);
@@ -296,6 +310,14 @@ where
 
         Ok(value)
     }
+
+    async fn get_components(
+        &self,
+        definition: &ApiDefinition,
+        auth_ctx: &AuthCtx,
+    ) -> ApiResult<Vec<Component>, ValidationError> {
+        self.get_all_components(definition, auth_ctx).await
+    }
 }
 
 pub struct RegisterApiDefinitionNoop {}
@@ -361,4 +383,12 @@ where
     ) -> ApiResult<Vec<ApiDefinition>, ValidationError> {
         Ok(vec![])
     }
+
+    async fn get_components(
+        &self,
+        _definition: &ApiDefinition,
+        _auth_ctx: &AuthCtx,
+    ) -> ApiResult<Vec<Component>, ValidationError> {
+        Ok(vec![])
+    }
 }
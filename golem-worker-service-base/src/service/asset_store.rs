@@ -0,0 +1,347 @@
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use aws_config::BehaviorVersion;
+use bytes::Bytes;
+use serde::Deserialize;
+use tracing::info;
+
+use crate::api_definition::ApiDefinitionId;
+use crate::app_config::S3AssetStoreConfig;
+
+// Limit of the assets buffered when they are uploaded
+#[derive(Clone, Debug, Deserialize)]
+pub struct AssetUploadLimits {
+    pub max_asset_size: usize,
+}
+
+impl Default for AssetUploadLimits {
+    fn default() -> Self {
+        Self {
+            max_asset_size: 50 * 1024 * 1024,
+        }
+    }
+}
+
+// Static assets served by the file server routes, addressed by a relative path
+#[async_trait]
+pub trait AssetStore {
+    async fn get(&self, path: &str) -> Result<Option<Bytes>, String>;
+
+    // Uploads an asset, replacing the existing one. Callers are expected to check the path
+    // with `is_valid_asset_path` first
+    async fn put(&self, path: &str, content: Bytes) -> Result<(), String>;
+}
+
+// Content type of an asset, guessed from the extension of its path
+pub fn content_type(path: &str) -> String {
+    mime_guess::from_path(path)
+        .first_or_octet_stream()
+        .to_string()
+}
+
+// Assets are kept apart by the api definition serving them, so the file server routes of an
+// api definition can only serve the assets uploaded for it
+pub fn api_asset_path(api_definition_id: &ApiDefinitionId, path: &str) -> Option<String> {
+    let id = api_definition_id.0.as_str();
+    let is_single_component = is_valid_asset_path(id) && !id.contains(['/', '\\']);
+
+    if is_single_component && is_valid_asset_path(path) {
+        Some(format!("{}/{}", id, path))
+    } else {
+        None
+    }
+}
+
+// Asset paths are relative, and cannot refer to parent directories
+pub fn is_valid_asset_path(path: &str) -> bool {
+    let path = Path::new(path);
+
+    path.components().next().is_some()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+// Assets of a single worker service instance, only meant for local development and tests
+#[derive(Default)]
+pub struct InMemoryAssetStore {
+    assets: Mutex<HashMap<String, Bytes>>,
+}
+
+#[async_trait]
+impl AssetStore for InMemoryAssetStore {
+    async fn get(&self, path: &str) -> Result<Option<Bytes>, String> {
+        Ok(self.assets.lock().unwrap().get(path).cloned())
+    }
+
+    async fn put(&self, path: &str, content: Bytes) -> Result<(), String> {
+        self.assets
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), content);
+        Ok(())
+    }
+}
+
+pub struct FileSystemAssetStore {
+    root: PathBuf,
+}
+
+impl FileSystemAssetStore {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+        }
+    }
+
+    // Paths come from the request, so anything escaping the root is treated as missing
+    fn resolve(&self, path: &str) -> Option<PathBuf> {
+        if is_valid_asset_path(path) {
+            Some(self.root.join(path))
+        } else {
+            None
+        }
+    }
+
+    // Symlinks under the root may still point outside of it, so the resolved path is checked
+    // once they are followed
+    async fn is_under_root(&self, path: &Path) -> std::io::Result<bool> {
+        let root = tokio::fs::canonicalize(&self.root).await?;
+        let path = tokio::fs::canonicalize(path).await?;
+        Ok(path.starts_with(root))
+    }
+}
+
+#[async_trait]
+impl AssetStore for FileSystemAssetStore {
+    async fn get(&self, path: &str) -> Result<Option<Bytes>, String> {
+        let Some(full_path) = self.resolve(path) else {
+            return Ok(None);
+        };
+
+        match self.is_under_root(&full_path).await {
+            Ok(true) => {}
+            Ok(false) => return Ok(None),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(format!("Failed to resolve asset {}: {}", path, err)),
+        }
+
+        match tokio::fs::read(&full_path).await {
+            Ok(content) => Ok(Some(Bytes::from(content))),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(_) if full_path.is_dir() => Ok(None),
+            Err(err) => Err(format!("Failed to read asset {}: {}", path, err)),
+        }
+    }
+
+    async fn put(&self, path: &str, content: Bytes) -> Result<(), String> {
+        let full_path = self
+            .resolve(path)
+            .ok_or_else(|| format!("Invalid asset path {}", path))?;
+        let parent = full_path
+            .parent()
+            .ok_or_else(|| format!("Invalid asset path {}", path))?;
+
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|err| format!("Failed to create directory of asset {}: {}", path, err))?;
+
+        let target = if tokio::fs::try_exists(&full_path).await.unwrap_or(false) {
+            full_path.as_path()
+        } else {
+            parent
+        };
+        match self.is_under_root(target).await {
+            Ok(true) => {}
+            Ok(false) => return Err(format!("Asset {} is outside of the asset root", path)),
+            Err(err) => return Err(format!("Failed to resolve asset {}: {}", path, err)),
+        }
+
+        tokio::fs::write(&full_path, content)
+            .await
+            .map_err(|err| format!("Failed to write asset {}: {}", path, err))
+    }
+}
+
+// Assets shared by all the worker service instances behind the same gateway
+pub struct S3AssetStore {
+    client: aws_sdk_s3::Client,
+    bucket_name: String,
+    object_prefix: String,
+}
+
+impl S3AssetStore {
+    pub async fn new(config: &S3AssetStoreConfig) -> Self {
+        info!(
+            "S3 Asset Store bucket: {}, prefix: {}",
+            config.bucket_name, config.object_prefix
+        );
+        let sdk_config = aws_config::load_defaults(BehaviorVersion::v2023_11_09()).await;
+        let client = aws_sdk_s3::Client::new(&sdk_config);
+        Self {
+            client,
+            bucket_name: config.bucket_name.clone(),
+            object_prefix: config.object_prefix.clone(),
+        }
+    }
+
+    fn get_key(&self, path: &str) -> String {
+        if self.object_prefix.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}/{}", self.object_prefix, path)
+        }
+    }
+}
+
+#[async_trait]
+impl AssetStore for S3AssetStore {
+    async fn get(&self, path: &str) -> Result<Option<Bytes>, String> {
+        if !is_valid_asset_path(path) {
+            return Ok(None);
+        }
+
+        let response = self
+            .client
+            .get_object()
+            .bucket(&self.bucket_name)
+            .key(self.get_key(path))
+            .send()
+            .await;
+
+        match response {
+            Ok(response) => {
+                let data = response
+                    .body
+                    .collect()
+                    .await
+                    .map_err(|err| format!("Failed to read asset {}: {}", path, err))?;
+                Ok(Some(data.into_bytes()))
+            }
+            Err(err)
+                if err
+                    .as_service_error()
+                    .is_some_and(|err| err.is_no_such_key()) =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(format!("Failed to get asset {}: {}", path, err)),
+        }
+    }
+
+    async fn put(&self, path: &str, content: Bytes) -> Result<(), String> {
+        if !is_valid_asset_path(path) {
+            return Err(format!("Invalid asset path {}", path));
+        }
+
+        self.client
+            .put_object()
+            .bucket(&self.bucket_name)
+            .key(self.get_key(path))
+            .body(aws_sdk_s3::primitives::ByteStream::from(content))
+            .send()
+            .await
+            .map_err(|err| format!("Failed to put asset {}: {}", path, err))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use bytes::Bytes;
+
+    use crate::api_definition::ApiDefinitionId;
+    use crate::service::asset_store::{
+        api_asset_path, content_type, is_valid_asset_path, AssetStore, FileSystemAssetStore,
+    };
+
+    #[test]
+    fn file_system_paths_stay_under_root() {
+        let store = FileSystemAssetStore::new(Path::new("/srv/assets"));
+
+        assert_eq!(
+            store.resolve("website/index.html"),
+            Some(Path::new("/srv/assets/website/index.html").to_path_buf())
+        );
+        assert_eq!(store.resolve("../secrets"), None);
+        assert_eq!(store.resolve("/etc/passwd"), None);
+        assert_eq!(store.resolve("website/../../secrets"), None);
+    }
+
+    #[test]
+    fn asset_paths_are_relative() {
+        assert!(is_valid_asset_path("website/index.html"));
+        assert!(!is_valid_asset_path(""));
+        assert!(!is_valid_asset_path("./index.html"));
+        assert!(!is_valid_asset_path("/index.html"));
+    }
+
+    #[test]
+    fn asset_paths_are_namespaced_by_api_definition() {
+        let api_definition_id = |id: &str| ApiDefinitionId(id.to_string());
+
+        assert_eq!(
+            api_asset_path(&api_definition_id("shop"), "website/index.html"),
+            Some("shop/website/index.html".to_string())
+        );
+        assert_eq!(
+            api_asset_path(&api_definition_id("shop"), "../other/index.html"),
+            None
+        );
+        assert_eq!(
+            api_asset_path(&api_definition_id("shop/../other"), "index.html"),
+            None
+        );
+        assert_eq!(api_asset_path(&api_definition_id(".."), "index.html"), None);
+    }
+
+    #[tokio::test]
+    async fn file_system_uploads_are_served() {
+        let root = tempfile::tempdir().unwrap();
+        let store = FileSystemAssetStore::new(root.path());
+
+        store
+            .put("website/index.html", Bytes::from("<html></html>"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.get("website/index.html").await,
+            Ok(Some(Bytes::from("<html></html>")))
+        );
+        assert_eq!(store.get("website/missing.html").await, Ok(None));
+        assert!(store.put("../index.html", Bytes::new()).await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn file_system_symlinks_stay_under_root() {
+        let root = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret"), "secret").unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.path().join("link")).unwrap();
+
+        let store = FileSystemAssetStore::new(root.path());
+
+        assert_eq!(store.get("link/secret").await, Ok(None));
+        assert!(store.put("link/secret", Bytes::from("x")).await.is_err());
+        assert!(store.put("link/other", Bytes::from("x")).await.is_err());
+        assert_eq!(
+            std::fs::read_to_string(outside.path().join("secret")).unwrap(),
+            "secret"
+        );
+    }
+
+    #[test]
+    fn content_type_from_extension() {
+        assert_eq!(content_type("website/index.html"), "text/html");
+        assert_eq!(content_type("website/style.css"), "text/css");
+        assert_eq!(content_type("website/data"), "application/octet-stream");
+    }
+}
@@ -212,6 +212,8 @@ impl ComponentServiceNoop {
                 exports: vec![],
                 producers: vec![],
             },
+            files: vec![],
+            retention_policy: None,
         }
     }
 }
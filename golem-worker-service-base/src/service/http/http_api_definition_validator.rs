@@ -3,19 +3,25 @@ use poem_openapi::Object;
 use serde::{Deserialize, Serialize};
 
 use golem_common::model::ComponentId;
-use golem_service_base::model::Component;
+use golem_service_base::model::{Component, ComponentMetadata};
 
-use crate::api_definition::http::{HttpApiDefinition, MethodPattern, Route};
+use crate::api_definition::http::{
+    FunctionDefinition, HttpApiDefinition, MethodPattern, PathPattern, Route,
+};
 
+use crate::expression::{self, Expr};
 use crate::http::router::{Router, RouterPattern};
 use crate::service::api_definition_validator::{ApiDefinitionValidatorService, ValidationErrors};
+use crate::type_checker::{type_check, FunctionScope, InferredType, TypeScope};
+use crate::worker_binding::RouteBinding;
 
 // Http Api Definition Validator
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
 pub struct RouteValidationError {
     pub method: MethodPattern,
     pub path: String,
-    pub component: ComponentId,
+    // Not set for the routes served by the gateway itself
+    pub component: Option<ComponentId>,
     pub detail: String,
 }
 
@@ -24,7 +30,7 @@ impl RouteValidationError {
         Self {
             method: route.method,
             path: route.path.to_string(),
-            component: route.binding.component_id,
+            component: route.binding.component_id().cloned(),
             detail,
         }
     }
@@ -39,9 +45,54 @@ impl ApiDefinitionValidatorService<HttpApiDefinition, RouteValidationError>
     fn validate(
         &self,
         api: &HttpApiDefinition,
-        _components: &[Component],
+        components: &[Component],
     ) -> Result<(), ValidationErrors<RouteValidationError>> {
-        let errors = unique_routes(api.routes.as_slice());
+        let mut errors = unique_routes(api.routes.as_slice());
+
+        errors.extend(unknown_security_schemes(api));
+
+        errors.extend(invalid_rate_limits(api));
+
+        errors.extend(invalid_streams(api));
+
+        errors.extend(invalid_cors_policy(api));
+
+        errors.extend(duplicate_worker_names(api));
+
+        let find_component = |component_id: &ComponentId| {
+            components
+                .iter()
+                .find(|component| &component.versioned_component_id.component_id == component_id)
+                .map(|component| &component.metadata)
+        };
+
+        for route in &api.routes {
+            match route.binding.worker_binding() {
+                Some(binding) => {
+                    let workers: Option<Vec<(&str, &ComponentMetadata)>> = binding
+                        .workers
+                        .iter()
+                        .map(|worker| {
+                            find_component(&worker.component_id)
+                                .map(|component| (worker.name.as_str(), component))
+                        })
+                        .collect();
+
+                    // Components which could not be fetched are reported by the api definition service
+                    if let (Some(component), Some(workers)) =
+                        (find_component(&binding.component_id), workers)
+                    {
+                        errors.extend(type_check_route(
+                            route,
+                            Some(component),
+                            &workers,
+                            &api.functions,
+                        ));
+                    }
+                }
+                None => errors.extend(type_check_route(route, None, &[], &api.functions)),
+            }
+        }
 
         if errors.is_empty() {
             Ok(())
@@ -74,7 +125,7 @@ fn unique_routes(routes: &[Route]) -> Vec<RouteValidationError> {
             errors.push(RouteValidationError {
                 method: route.method.clone(),
                 path: route.path.to_string(),
-                component: route.binding.component_id.clone(),
+                component: route.binding.component_id().cloned(),
                 detail,
             });
         }
@@ -83,13 +134,308 @@ fn unique_routes(routes: &[Route]) -> Vec<RouteValidationError> {
     errors
 }
 
+fn unknown_security_schemes(api: &HttpApiDefinition) -> Vec<RouteValidationError> {
+    api.routes
+        .iter()
+        .filter_map(|route| {
+            let security = route.security.as_ref()?;
+
+            if api.security_scheme(&security.scheme).is_none() {
+                Some(RouteValidationError::from_route(
+                    route.clone(),
+                    format!("Unknown security scheme: {}", security.scheme),
+                ))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn invalid_rate_limits(api: &HttpApiDefinition) -> Vec<RouteValidationError> {
+    api.routes
+        .iter()
+        .filter_map(|route| {
+            let rate_limit = route.rate_limit.as_ref()?;
+
+            let detail = if rate_limit.requests == 0 {
+                "Rate limit requests must be greater than 0"
+            } else if rate_limit.period_seconds == 0 {
+                "Rate limit period must be greater than 0"
+            } else if rate_limit.burst == Some(0) {
+                "Rate limit burst must be greater than 0"
+            } else {
+                return None;
+            };
+
+            Some(RouteValidationError::from_route(
+                route.clone(),
+                detail.to_string(),
+            ))
+        })
+        .collect()
+}
+
+// Functions of the other workers of a binding are called by the name of the worker
+fn duplicate_worker_names(api: &HttpApiDefinition) -> Vec<RouteValidationError> {
+    let mut errors = vec![];
+
+    for route in &api.routes {
+        let Some(binding) = route.binding.worker_binding() else {
+            continue;
+        };
+
+        for (index, worker) in binding.workers.iter().enumerate() {
+            if binding.workers[..index]
+                .iter()
+                .any(|previous| previous.name == worker.name)
+            {
+                errors.push(RouteValidationError::from_route(
+                    route.clone(),
+                    format!("Duplicate worker name: {}", worker.name),
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+fn invalid_streams(api: &HttpApiDefinition) -> Vec<RouteValidationError> {
+    api.routes
+        .iter()
+        .filter_map(|route| {
+            let stream = route.stream.as_ref()?;
+
+            // Both WebSocket handshakes and SSE subscriptions are GET requests
+            let detail = if route.binding.worker_binding().is_none() {
+                "Stream routes must be bound to a worker"
+            } else if !route.method.is_get() {
+                "Stream routes must use the GET method"
+            } else if stream.poll_interval_seconds == Some(0) {
+                "Stream poll interval must be greater than 0"
+            } else {
+                return None;
+            };
+
+            Some(RouteValidationError::from_route(
+                route.clone(),
+                detail.to_string(),
+            ))
+        })
+        .collect()
+}
+
+// Browsers refuse credentials along with a wildcard origin, and echoing the origin back instead
+// would let any site make authenticated requests. The policy applies to every route, which are
+// all reported
+fn invalid_cors_policy(api: &HttpApiDefinition) -> Vec<RouteValidationError> {
+    let Some(cors) = &api.cors else {
+        return vec![];
+    };
+
+    let any_origin = cors.allowed_origins.iter().any(|origin| origin == "*");
+    if !(any_origin && cors.allow_credentials) {
+        return vec![];
+    }
+
+    api.routes
+        .iter()
+        .map(|route| {
+            RouteValidationError::from_route(
+                route.clone(),
+                "CORS policy cannot allow credentials for any origin".to_string(),
+            )
+        })
+        .collect()
+}
+
+// Checks the binding expressions of the route against the exports of the component, using the
+// same variables the evaluator makes available when the route is invoked. Routes served by the
+// gateway itself have no component, and only the request in scope. The functions of the api
+// definition can be called from any expression of the route
+fn type_check_route(
+    route: &Route,
+    component: Option<&ComponentMetadata>,
+    workers: &[(&str, &ComponentMetadata)],
+    functions: &[FunctionDefinition],
+) -> Vec<RouteValidationError> {
+    let mut errors = vec![];
+
+    let request_scope = request_scope(route);
+
+    // The worker names, idempotency key and rate limit key are resolved before the workers
+    // are known
+    let mut key_expressions = vec![];
+    if let Some(binding) = route.binding.worker_binding() {
+        key_expressions.push(("worker name".to_string(), &binding.worker_name));
+        if let Some(idempotency_key) = &binding.idempotency_key {
+            key_expressions.push(("idempotency key".to_string(), idempotency_key));
+        }
+        for worker in &binding.workers {
+            key_expressions.push((
+                format!("worker name of {}", worker.name),
+                &worker.worker_name,
+            ));
+        }
+    }
+    if let Some(rate_limit_key) = route
+        .rate_limit
+        .as_ref()
+        .and_then(|limit| limit.key.as_ref())
+    {
+        key_expressions.push(("rate limit key".to_string(), rate_limit_key));
+    }
+
+    for (name, expr) in key_expressions {
+        let key_functions = FunctionScope {
+            functions,
+            ..FunctionScope::default()
+        };
+
+        match type_check(expr, &request_scope, key_functions) {
+            Ok(typ) if !typ.is_primitive() => errors.push(RouteValidationError::from_route(
+                route.clone(),
+                format!(
+                    "Invalid {name} expression {}: expected a string, found {typ}",
+                    expression_text(expr)
+                ),
+            )),
+            Ok(_) => {}
+            Err(type_errors) => errors.extend(type_errors.into_iter().map(|error| {
+                RouteValidationError::from_route(
+                    route.clone(),
+                    format!(
+                        "Invalid {name} expression {}: {error}",
+                        expression_text(expr)
+                    ),
+                )
+            })),
+        }
+    }
+
+    let response_scope = match &route.binding {
+        RouteBinding::WitWorker(_) => response_scope(route),
+        RouteBinding::Static(_) | RouteBinding::FileServer(_) => request_scope,
+    };
+
+    let response = &route.binding.response().0;
+    let response_functions = FunctionScope {
+        component,
+        workers,
+        functions,
+    };
+
+    match type_check(response, &response_scope, response_functions) {
+        // File server routes respond with the asset at the path their response evaluates to
+        Ok(typ) if matches!(route.binding, RouteBinding::FileServer(_)) && !typ.is_primitive() => {
+            errors.push(RouteValidationError::from_route(
+                route.clone(),
+                format!(
+                    "Invalid response expression {}: expected an asset path, found {typ}",
+                    expression_text(response)
+                ),
+            ))
+        }
+        Ok(_) => {}
+        Err(type_errors) => errors.extend(type_errors.into_iter().map(|error| {
+            RouteValidationError::from_route(
+                route.clone(),
+                format!(
+                    "Invalid response expression {}: {error}",
+                    expression_text(response)
+                ),
+            )
+        })),
+    }
+
+    errors
+}
+
+// Path variables and query parameters are both available as `request.path`, and the claims of
+// the caller as `request.auth.claims` on routes with a security requirement
+pub(crate) fn request_scope(route: &Route) -> TypeScope {
+    let path_variables = route
+        .path
+        .path_patterns
+        .iter()
+        .filter_map(|pattern| match pattern {
+            PathPattern::Var(var_info) => Some(var_info.key_name.clone()),
+            PathPattern::Literal(_) => None,
+        })
+        .chain(
+            route
+                .path
+                .query_params
+                .iter()
+                .map(|query_info| query_info.key_name.clone()),
+        )
+        .map(|name| (name, InferredType::Primitive))
+        .collect();
+
+    let mut request = vec![
+        ("path".to_string(), InferredType::Record(path_variables)),
+        ("body".to_string(), InferredType::Unknown),
+        (
+            "headers".to_string(),
+            InferredType::OpenRecord(Box::new(InferredType::Primitive)),
+        ),
+    ];
+
+    // Claims are only known once the caller is authenticated
+    if route.security.is_some() {
+        request.push((
+            "auth".to_string(),
+            InferredType::Record(vec![("claims".to_string(), InferredType::Unknown)]),
+        ));
+    }
+
+    let mut scope = TypeScope::new();
+    scope.insert("request", InferredType::Record(request));
+    scope
+}
+
+// The response mapping can refer to the worker as well, along with the result of its functions
+pub(crate) fn response_scope(route: &Route) -> TypeScope {
+    let mut scope = request_scope(route);
+    scope.insert(
+        "worker",
+        InferredType::Record(vec![
+            ("component_id".to_string(), InferredType::Str),
+            ("name".to_string(), InferredType::Str),
+            ("idempotency-key".to_string(), InferredType::Str),
+        ]),
+    );
+    scope
+}
+
+fn expression_text(expr: &Expr) -> String {
+    expression::to_string(expr)
+        .map(|text| format!("`{text}`"))
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::api_definition::http::{MethodPattern, Route};
-    use crate::expression::Expr;
-    use crate::service::http::http_api_definition_validator::unique_routes;
-    use crate::worker_binding::ResponseMapping;
+    use crate::api_definition::http::{
+        AllPathPatterns, BasicSecurityScheme, CorsPolicy, FunctionDefinition, HttpApiDefinition,
+        MethodPattern, RateLimit, Route, RouteSecurity, SecurityScheme, SecuritySchemeKind,
+        StreamBinding, StreamProtocol, StreamSource,
+    };
+    use crate::api_definition::{ApiDefinitionId, ApiVersion};
+    use crate::expression::{from_string, Expr};
+    use crate::service::http::http_api_definition_validator::{
+        duplicate_worker_names, invalid_rate_limits, invalid_streams, type_check_route,
+        unique_routes, unknown_security_schemes,
+    };
+    use crate::worker_binding::{
+        GolemWorkerBinding, ResponseMapping, RouteBinding, StaticBinding, WorkerReference,
+    };
     use golem_common::model::ComponentId;
+    use golem_service_base::model::{
+        ComponentMetadata, Export, ExportFunction, FunctionParameter, FunctionResult,
+    };
+    use golem_wasm_ast::analysis::AnalysedType;
 
     #[test]
     fn test_unique_routes() {
@@ -97,12 +443,16 @@ mod tests {
             Route {
                 method,
                 path: crate::api_definition::http::AllPathPatterns::parse(path).unwrap(),
-                binding: crate::worker_binding::GolemWorkerBinding {
+                binding: RouteBinding::WitWorker(GolemWorkerBinding {
                     component_id: ComponentId::new_v4(),
                     worker_name: crate::expression::Expr::Identifier("request".to_string()),
                     idempotency_key: None,
                     response: ResponseMapping(Expr::Literal("sample".to_string())),
-                },
+                    workers: vec![],
+                }),
+                security: None,
+                rate_limit: None,
+                stream: None,
             }
         }
 
@@ -135,4 +485,608 @@ mod tests {
         assert!(errors.len() == 1);
         assert!(errors[0].detail.contains(paths[0]), "Received: {errors:?}");
     }
+
+    fn shopping_cart_component() -> ComponentMetadata {
+        let item = AnalysedType::Record(vec![
+            ("product-id".to_string(), AnalysedType::Str),
+            ("quantity".to_string(), AnalysedType::U32),
+        ]);
+
+        ComponentMetadata {
+            exports: vec![
+                Export::Function(ExportFunction {
+                    name: "get-cart-contents".to_string(),
+                    parameters: vec![FunctionParameter {
+                        name: "user-id".to_string(),
+                        typ: AnalysedType::Str.into(),
+                    }],
+                    results: vec![FunctionResult {
+                        name: None,
+                        typ: AnalysedType::List(Box::new(item.clone())).into(),
+                    }],
+                }),
+                Export::Function(ExportFunction {
+                    name: "add-item".to_string(),
+                    parameters: vec![FunctionParameter {
+                        name: "item".to_string(),
+                        typ: item.into(),
+                    }],
+                    results: vec![],
+                }),
+            ],
+            producers: vec![],
+        }
+    }
+
+    fn make_route(response: Expr) -> Route {
+        Route {
+            method: MethodPattern::Get,
+            path: crate::api_definition::http::AllPathPatterns::parse("/{user-id}/cart").unwrap(),
+            binding: RouteBinding::WitWorker(GolemWorkerBinding {
+                component_id: ComponentId::new_v4(),
+                worker_name: Expr::Concat(vec![
+                    Expr::Literal("worker-".to_string()),
+                    Expr::SelectField(
+                        Box::new(Expr::SelectField(
+                            Box::new(Expr::Identifier("request".to_string())),
+                            "path".to_string(),
+                        )),
+                        "user-id".to_string(),
+                    ),
+                ]),
+                idempotency_key: None,
+                response: ResponseMapping(response),
+                workers: vec![],
+            }),
+            security: None,
+            rate_limit: None,
+            stream: None,
+        }
+    }
+
+    fn worker_binding_mut(route: &mut Route) -> &mut GolemWorkerBinding {
+        match &mut route.binding {
+            RouteBinding::WitWorker(binding) => binding,
+            _ => panic!("Expected a worker binding"),
+        }
+    }
+
+    fn select(expr: Expr, fields: &[&str]) -> Expr {
+        fields.iter().fold(expr, |expr, field| {
+            Expr::SelectField(Box::new(expr), field.to_string())
+        })
+    }
+
+    fn identifier(name: &str) -> Expr {
+        Expr::Identifier(name.to_string())
+    }
+
+    fn get_cart_contents() -> Expr {
+        Expr::Call(
+            "get-cart-contents".to_string(),
+            vec![select(identifier("request"), &["path", "user-id"])],
+        )
+    }
+
+    #[test]
+    fn test_type_check_valid_response() {
+        let response = Expr::Multiple(vec![
+            Expr::Let("contents".to_string(), Box::new(get_cart_contents())),
+            Expr::Record(vec![
+                ("status".to_string(), Box::new(Expr::unsigned_integer(200))),
+                (
+                    "body".to_string(),
+                    Box::new(select(
+                        Expr::SelectIndex(Box::new(select(identifier("worker"), &["response"])), 0),
+                        &["product-id"],
+                    )),
+                ),
+            ]),
+        ]);
+
+        let errors = type_check_route(
+            &make_route(response),
+            Some(&shopping_cart_component()),
+            &[],
+            &[],
+        );
+
+        assert!(errors.is_empty(), "Received: {errors:?}");
+    }
+
+    #[test]
+    fn test_type_check_unknown_function() {
+        let response = Expr::Call(
+            "get-cart".to_string(),
+            vec![select(identifier("request"), &["path", "user-id"])],
+        );
+
+        let errors = type_check_route(
+            &make_route(response),
+            Some(&shopping_cart_component()),
+            &[],
+            &[],
+        );
+
+        assert!(errors.len() == 1, "Received: {errors:?}");
+        assert!(errors[0]
+            .detail
+            .contains("Function `get-cart` is not exported by the component"));
+    }
+
+    #[test]
+    fn test_type_check_wrong_argument_type() {
+        let response = Expr::Call(
+            "add-item".to_string(),
+            vec![Expr::Record(vec![
+                (
+                    "product-id".to_string(),
+                    Box::new(Expr::Literal("p1".to_string())),
+                ),
+                ("quantity".to_string(), Box::new(Expr::Boolean(true))),
+            ])],
+        );
+
+        let errors = type_check_route(
+            &make_route(response),
+            Some(&shopping_cart_component()),
+            &[],
+            &[],
+        );
+
+        assert!(errors.len() == 1, "Received: {errors:?}");
+        assert!(
+            errors[0]
+                .detail
+                .contains("Argument `item` of function `add-item` expects"),
+            "Received: {errors:?}"
+        );
+    }
+
+    #[test]
+    fn test_type_check_invalid_field_selection_with_span() {
+        let response = Expr::Record(vec![(
+            "body".to_string(),
+            Box::new(select(identifier("request"), &["bdy"])),
+        )]);
+
+        let errors = type_check_route(
+            &make_route(response),
+            Some(&shopping_cart_component()),
+            &[],
+            &[],
+        );
+
+        // ${{body: request.bdy}}
+        assert!(errors.len() == 1, "Received: {errors:?}");
+        assert!(
+            errors[0].detail.contains("Field `bdy` does not exist")
+                && errors[0].detail.contains("(at 9..20)"),
+            "Received: {errors:?}"
+        );
+    }
+
+    #[test]
+    fn test_type_check_response_without_call() {
+        let response = select(identifier("worker"), &["response"]);
+
+        let errors = type_check_route(
+            &make_route(response),
+            Some(&shopping_cart_component()),
+            &[],
+            &[],
+        );
+
+        assert!(errors.len() == 1, "Received: {errors:?}");
+        assert!(errors[0].detail.contains("Field `response` does not exist"));
+    }
+
+    #[test]
+    fn test_type_check_unknown_path_variable_in_worker_name() {
+        let mut route = make_route(get_cart_contents());
+        worker_binding_mut(&mut route).worker_name =
+            select(identifier("request"), &["path", "user"]);
+
+        let errors = type_check_route(&route, Some(&shopping_cart_component()), &[], &[]);
+
+        assert!(errors.len() == 1, "Received: {errors:?}");
+        assert!(errors[0]
+            .detail
+            .starts_with("Invalid worker name expression"));
+    }
+
+    #[test]
+    fn test_type_check_auth_claims_in_worker_name() {
+        let mut route = make_route(get_cart_contents());
+        worker_binding_mut(&mut route).worker_name =
+            select(identifier("request"), &["auth", "claims", "sub"]);
+
+        let errors_without_security =
+            type_check_route(&route, Some(&shopping_cart_component()), &[], &[]);
+
+        route.security = Some(RouteSecurity {
+            scheme: "users".to_string(),
+            scopes: vec![],
+        });
+        let errors_with_security =
+            type_check_route(&route, Some(&shopping_cart_component()), &[], &[]);
+
+        assert!(
+            errors_with_security.is_empty(),
+            "Received: {errors_with_security:?}"
+        );
+        assert!(
+            errors_without_security.len() == 1
+                && errors_without_security[0]
+                    .detail
+                    .contains("Field `auth` does not exist"),
+            "Received: {errors_without_security:?}"
+        );
+    }
+
+    #[test]
+    fn test_unknown_security_scheme() {
+        let mut secured_route = make_route(get_cart_contents());
+        secured_route.security = Some(RouteSecurity {
+            scheme: "admins".to_string(),
+            scopes: vec![],
+        });
+
+        let api = HttpApiDefinition {
+            id: ApiDefinitionId("shopping-cart".to_string()),
+            version: ApiVersion("0.0.1".to_string()),
+            routes: vec![make_route(get_cart_contents()), secured_route],
+            security_schemes: vec![SecurityScheme {
+                name: "users".to_string(),
+                scheme: SecuritySchemeKind::Basic(BasicSecurityScheme { users: vec![] }),
+            }],
+            cors: None,
+            functions: vec![],
+            draft: true,
+        };
+
+        let errors = unknown_security_schemes(&api);
+
+        assert!(errors.len() == 1, "Received: {errors:?}");
+        assert_eq!(errors[0].detail, "Unknown security scheme: admins");
+    }
+
+    #[test]
+    fn test_type_check_rate_limit_key() {
+        let mut route = make_route(get_cart_contents());
+        route.rate_limit = Some(RateLimit {
+            requests: 10,
+            period_seconds: 60,
+            burst: None,
+            key: Some(select(identifier("request"), &["headers", "x-api-key"])),
+        });
+
+        let errors_with_header =
+            type_check_route(&route, Some(&shopping_cart_component()), &[], &[]);
+
+        route.rate_limit.as_mut().unwrap().key =
+            Some(select(identifier("request"), &["auth", "claims", "sub"]));
+        let errors_with_claims =
+            type_check_route(&route, Some(&shopping_cart_component()), &[], &[]);
+
+        assert!(
+            errors_with_header.is_empty(),
+            "Received: {errors_with_header:?}"
+        );
+        assert!(
+            errors_with_claims.len() == 1
+                && errors_with_claims[0]
+                    .detail
+                    .starts_with("Invalid rate limit key expression"),
+            "Received: {errors_with_claims:?}"
+        );
+    }
+
+    #[test]
+    fn test_invalid_rate_limit() {
+        let mut limited_route = make_route(get_cart_contents());
+        limited_route.rate_limit = Some(RateLimit {
+            requests: 10,
+            period_seconds: 0,
+            burst: None,
+            key: None,
+        });
+
+        let api = HttpApiDefinition {
+            id: ApiDefinitionId("shopping-cart".to_string()),
+            version: ApiVersion("0.0.1".to_string()),
+            routes: vec![make_route(get_cart_contents()), limited_route],
+            security_schemes: vec![],
+            cors: None,
+            functions: vec![],
+            draft: true,
+        };
+
+        let errors = invalid_rate_limits(&api);
+
+        assert!(errors.len() == 1, "Received: {errors:?}");
+        assert_eq!(errors[0].detail, "Rate limit period must be greater than 0");
+    }
+
+    #[test]
+    fn test_invalid_stream() {
+        let mut websocket_route = make_route(get_cart_contents());
+        websocket_route.stream = Some(StreamBinding {
+            protocol: StreamProtocol::WebSocket,
+            source: StreamSource::Results,
+            poll_interval_seconds: None,
+        });
+
+        let mut post_route = websocket_route.clone();
+        post_route.method = MethodPattern::Post;
+
+        let mut sse_route = make_route(get_cart_contents());
+        sse_route.path = AllPathPatterns::parse("/{user-id}/cart/events").unwrap();
+        sse_route.stream = Some(StreamBinding {
+            protocol: StreamProtocol::Sse,
+            source: StreamSource::Results,
+            poll_interval_seconds: Some(0),
+        });
+
+        let api = HttpApiDefinition {
+            id: ApiDefinitionId("shopping-cart".to_string()),
+            version: ApiVersion("0.0.1".to_string()),
+            routes: vec![websocket_route, post_route, sse_route],
+            security_schemes: vec![],
+            cors: None,
+            functions: vec![],
+            draft: true,
+        };
+
+        let errors = invalid_streams(&api);
+
+        assert!(errors.len() == 2, "Received: {errors:?}");
+        assert_eq!(errors[0].detail, "Stream routes must use the GET method");
+        assert_eq!(
+            errors[1].detail,
+            "Stream poll interval must be greater than 0"
+        );
+    }
+
+    #[test]
+    fn test_invalid_cors_policy() {
+        let cors = |allowed_origin: &str| CorsPolicy {
+            allowed_origins: vec![allowed_origin.to_string()],
+            allowed_methods: vec![],
+            allowed_headers: vec![],
+            allow_credentials: true,
+            max_age: None,
+        };
+        let api = |cors: CorsPolicy| HttpApiDefinition {
+            id: ApiDefinitionId("shopping-cart".to_string()),
+            version: ApiVersion("0.0.1".to_string()),
+            routes: vec![make_route(get_cart_contents())],
+            security_schemes: vec![],
+            cors: Some(cors),
+            functions: vec![],
+            draft: true,
+        };
+
+        let any_origin_errors = invalid_cors_policy(&api(cors("*")));
+        let single_origin_errors = invalid_cors_policy(&api(cors("https://shop.example")));
+
+        assert!(
+            any_origin_errors.len() == 1,
+            "Received: {any_origin_errors:?}"
+        );
+        assert_eq!(
+            any_origin_errors[0].detail,
+            "CORS policy cannot allow credentials for any origin"
+        );
+        assert!(single_origin_errors.is_empty());
+    }
+
+    #[test]
+    fn test_type_check_static_routes() {
+        let mut redirect = make_route(Expr::Record(vec![
+            ("status".to_string(), Box::new(Expr::unsigned_integer(302))),
+            (
+                "body".to_string(),
+                Box::new(select(identifier("worker"), &["name"])),
+            ),
+        ]));
+        redirect.binding = RouteBinding::Static(StaticBinding {
+            response: redirect.binding.response().clone(),
+        });
+
+        let mut asset = make_route(Expr::Concat(vec![
+            Expr::Literal("website/".to_string()),
+            select(identifier("request"), &["path", "user-id"]),
+        ]));
+        asset.binding = RouteBinding::FileServer(StaticBinding {
+            response: asset.binding.response().clone(),
+        });
+
+        let mut asset_record = make_route(Expr::Record(vec![(
+            "path".to_string(),
+            Box::new(select(identifier("request"), &["path", "user-id"])),
+        )]));
+        asset_record.binding = RouteBinding::FileServer(StaticBinding {
+            response: asset_record.binding.response().clone(),
+        });
+
+        let redirect_errors = type_check_route(&redirect, None, &[], &[]);
+        let asset_errors = type_check_route(&asset, None, &[], &[]);
+        let asset_record_errors = type_check_route(&asset_record, None, &[], &[]);
+
+        // The worker is not in scope of the routes served by the gateway
+        assert!(
+            redirect_errors.len() == 1
+                && redirect_errors[0]
+                    .detail
+                    .contains("Unknown variable `worker`"),
+            "Received: {redirect_errors:?}"
+        );
+        assert!(asset_errors.is_empty(), "Received: {asset_errors:?}");
+        assert!(
+            asset_record_errors.len() == 1
+                && asset_record_errors[0]
+                    .detail
+                    .contains("expected an asset path"),
+            "Received: {asset_record_errors:?}"
+        );
+    }
+
+    fn users_component() -> ComponentMetadata {
+        ComponentMetadata {
+            exports: vec![Export::Function(ExportFunction {
+                name: "get-user".to_string(),
+                parameters: vec![FunctionParameter {
+                    name: "user-id".to_string(),
+                    typ: AnalysedType::Str.into(),
+                }],
+                results: vec![FunctionResult {
+                    name: None,
+                    typ: AnalysedType::Record(vec![("name".to_string(), AnalysedType::Str)]).into(),
+                }],
+            })],
+            producers: vec![],
+        }
+    }
+
+    fn users_worker(worker_name: Expr) -> WorkerReference {
+        WorkerReference {
+            name: "users".to_string(),
+            component_id: ComponentId::new_v4(),
+            worker_name,
+        }
+    }
+
+    #[test]
+    fn test_type_check_named_worker_calls() {
+        let get_user = |function_name: &str| {
+            Expr::Call(
+                function_name.to_string(),
+                vec![select(identifier("request"), &["path", "user-id"])],
+            )
+        };
+
+        let response = Expr::Record(vec![
+            ("cart".to_string(), Box::new(get_cart_contents())),
+            (
+                "user".to_string(),
+                Box::new(select(get_user("users.get-user"), &["name"])),
+            ),
+        ]);
+        let mut route = make_route(response);
+        worker_binding_mut(&mut route)
+            .workers
+            .push(users_worker(select(
+                identifier("request"),
+                &["path", "user"],
+            )));
+
+        let users = users_component();
+        let errors = type_check_route(
+            &route,
+            Some(&shopping_cart_component()),
+            &[("users", &users)],
+            &[],
+        );
+
+        let mut unknown_worker_route = make_route(get_user("orders.get-user"));
+        worker_binding_mut(&mut unknown_worker_route)
+            .workers
+            .push(users_worker(Expr::Literal("users".to_string())));
+        let unknown_worker_errors = type_check_route(
+            &unknown_worker_route,
+            Some(&shopping_cart_component()),
+            &[("users", &users)],
+            &[],
+        );
+
+        assert!(
+            errors.len() == 1
+                && errors[0]
+                    .detail
+                    .starts_with("Invalid worker name of users expression"),
+            "Received: {errors:?}"
+        );
+        assert!(
+            unknown_worker_errors.len() == 1
+                && unknown_worker_errors[0]
+                    .detail
+                    .contains("Function `orders.get-user` is not exported by the component"),
+            "Received: {unknown_worker_errors:?}"
+        );
+    }
+
+    #[test]
+    fn test_type_check_functions() {
+        let function = |name: &str, body: &str| FunctionDefinition {
+            name: name.to_string(),
+            params: vec!["id".to_string()],
+            body: from_string(body).unwrap(),
+        };
+        let functions = vec![
+            function("product-key", "product-${lower(id)}"),
+            function("broken", "${id.name}"),
+        ];
+
+        let valid_response = from_string(
+            "${{ids: map(get-cart-contents(request.path.user-id), item => product-key(item.product-id)), count: fold(get-cart-contents(request.path.user-id), 0, (acc, item) => acc), user: upper(request.path.user-id)}}",
+        )
+        .unwrap();
+        let errors = type_check_route(
+            &make_route(valid_response),
+            Some(&shopping_cart_component()),
+            &[],
+            &functions,
+        );
+        assert!(errors.is_empty(), "Received: {errors:?}");
+
+        let invalid_response = from_string(
+            "${{a: lower(request.path.user-id, \"b\"), b: filter(get-cart-contents(request.path.user-id), item => item.quantity), c: broken(request.path.user-id)}}",
+        )
+        .unwrap();
+        let errors = type_check_route(
+            &make_route(invalid_response),
+            Some(&shopping_cart_component()),
+            &[],
+            &functions,
+        );
+
+        assert!(
+            errors.len() == 3
+                && errors[0]
+                    .detail
+                    .contains("Function `lower` expects 1 arguments, but 2 were given")
+                && errors[1]
+                    .detail
+                    .contains("The function passed to `filter` should return a bool")
+                && errors[2]
+                    .detail
+                    .contains("Invalid call of function `broken`"),
+            "Received: {errors:?}"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_worker_names() {
+        let mut route = make_route(get_cart_contents());
+        worker_binding_mut(&mut route).workers = vec![
+            users_worker(Expr::Literal("users-1".to_string())),
+            users_worker(Expr::Literal("users-2".to_string())),
+        ];
+
+        let api = HttpApiDefinition {
+            id: ApiDefinitionId("shopping-cart".to_string()),
+            version: ApiVersion("0.0.1".to_string()),
+            routes: vec![route],
+            security_schemes: vec![],
+            cors: None,
+            functions: vec![],
+            draft: true,
+        };
+
+        let errors = duplicate_worker_names(&api);
+
+        assert!(errors.len() == 1, "Received: {errors:?}");
+        assert_eq!(errors[0].detail, "Duplicate worker name: users");
+    }
 }
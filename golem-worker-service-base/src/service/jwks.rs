@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode, SimpleCache};
+use jsonwebtoken::jwk::JwkSet;
+
+use crate::app_config::JwksConfig;
+
+#[async_trait]
+pub trait JwksProvider {
+    // The key set of the source with the given name
+    async fn get(&self, name: &str) -> Result<Arc<JwkSet>, String>;
+}
+
+// Serves the key sets of the sources configured in the worker service. Api definitions only
+// refer to them by name, so the files read are chosen by the operator and not by the tenants.
+// Loaded key sets are kept for the refresh interval, so rotated keys are picked up without
+// reading the files on every request
+pub struct ConfiguredJwksProvider {
+    sources: HashMap<String, PathBuf>,
+    refresh_interval: Duration,
+    key_sets: Cache<String, (), (Instant, Arc<JwkSet>), String>,
+}
+
+impl ConfiguredJwksProvider {
+    pub fn new(config: &JwksConfig) -> Self {
+        Self {
+            sources: config.sources.clone(),
+            refresh_interval: config.refresh_interval,
+            key_sets: Cache::new(
+                None,
+                FullCacheEvictionMode::None,
+                BackgroundEvictionMode::None,
+                "jwks",
+            ),
+        }
+    }
+
+    async fn load(name: &str, path: PathBuf) -> Result<(Instant, Arc<JwkSet>), String> {
+        let bytes = tokio::fs::read(&path)
+            .await
+            .map_err(|err| format!("Failed to read JWKS {name}: {err}"))?;
+        let key_set = serde_json::from_slice::<JwkSet>(&bytes)
+            .map_err(|err| format!("Failed to parse JWKS {name}: {err}"))?;
+
+        Ok((Instant::now(), Arc::new(key_set)))
+    }
+}
+
+#[async_trait]
+impl JwksProvider for ConfiguredJwksProvider {
+    async fn get(&self, name: &str) -> Result<Arc<JwkSet>, String> {
+        let path = self
+            .sources
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("JWKS {name} is not configured"))?;
+
+        let key = name.to_string();
+        if let Some((loaded_at, _)) = self.key_sets.get(&key).await {
+            if loaded_at.elapsed() >= self.refresh_interval {
+                self.key_sets.remove(&key);
+            }
+        }
+
+        let (_, key_set) = self
+            .key_sets
+            .get_or_insert_simple(&key, || {
+                let name = name.to_string();
+                Box::pin(async move { Self::load(&name, path).await })
+            })
+            .await?;
+
+        Ok(key_set)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn key_sets_are_read_from_configured_sources_only() {
+        let jwks = json!({
+            "keys": [{"kty": "oct", "kid": "test", "alg": "HS256", "k": "c2VjcmV0"}]
+        });
+        let mut jwks_file = tempfile::NamedTempFile::new().unwrap();
+        jwks_file.write_all(jwks.to_string().as_bytes()).unwrap();
+
+        let provider = ConfiguredJwksProvider::new(&JwksConfig {
+            refresh_interval: Duration::from_secs(60),
+            sources: HashMap::from([("issuer".to_string(), jwks_file.path().to_path_buf())]),
+        });
+
+        let configured = provider.get("issuer").await.unwrap();
+        let path = provider
+            .get(jwks_file.path().to_string_lossy().as_ref())
+            .await;
+
+        assert!(configured.find("test").is_some());
+        assert!(path.is_err());
+    }
+}
@@ -3,7 +3,10 @@ pub mod api_definition;
 pub mod api_definition_lookup;
 pub mod api_definition_validator;
 pub mod api_deployment;
+pub mod asset_store;
 pub mod component;
+pub mod jwks;
+pub mod rate_limit;
 pub mod worker;
 
 pub mod http;
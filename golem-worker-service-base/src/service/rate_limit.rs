@@ -0,0 +1,277 @@
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode, SimpleCache};
+use golem_common::config::RedisConfig;
+use golem_common::redis::RedisPool;
+
+use crate::api_definition::http::RateLimit;
+use crate::app_config::InMemoryRateLimitStoreConfig;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RateLimitDecision {
+    Allowed,
+    // The bucket is empty, and the next token is added back after the given duration
+    Limited { retry_after: Duration },
+}
+
+#[async_trait]
+pub trait RateLimiter {
+    // Takes a token from the bucket, creating a full one if it does not exist yet
+    async fn acquire(&self, bucket: &str, limit: &RateLimit) -> Result<RateLimitDecision, String>;
+}
+
+// Keeps the buckets of a single worker service instance. Buckets not used for the configured
+// time are dropped, like the expiring keys of the redis rate limiter
+pub struct InMemoryRateLimiter {
+    buckets: Cache<String, (), Arc<Mutex<TokenBucket>>, String>,
+}
+
+impl InMemoryRateLimiter {
+    pub fn new(config: &InMemoryRateLimitStoreConfig) -> Self {
+        Self {
+            buckets: Cache::new(
+                None,
+                FullCacheEvictionMode::None,
+                BackgroundEvictionMode::OlderThan {
+                    ttl: config.bucket_time_to_idle,
+                    period: config.eviction_period,
+                },
+                "rate_limit_buckets",
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl RateLimiter for InMemoryRateLimiter {
+    async fn acquire(&self, bucket: &str, limit: &RateLimit) -> Result<RateLimitDecision, String> {
+        let full = TokenBucket::full(limit, now_millis());
+        let token_bucket = self
+            .buckets
+            .get_or_insert_simple(&bucket.to_string(), || {
+                Box::pin(async move { Ok(Arc::new(Mutex::new(full))) })
+            })
+            .await?;
+
+        let decision = token_bucket.lock().unwrap().acquire(limit, now_millis());
+
+        Ok(decision)
+    }
+}
+
+// Shares the buckets between the worker service instances behind the same gateway
+pub struct RedisRateLimiter {
+    pool: RedisPool,
+}
+
+impl RedisRateLimiter {
+    pub async fn new(config: &RedisConfig) -> Result<RedisRateLimiter, Box<dyn Error>> {
+        let pool = RedisPool::configured(config).await?;
+        Ok(Self { pool })
+    }
+}
+
+// Same as TokenBucket::acquire, executed atomically by redis. Returns the number of milliseconds
+// to wait for the next token, or 0 if a token was taken. Idle buckets expire once they are full
+const ACQUIRE_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local rate = tonumber(ARGV[2])
+local now = tonumber(ARGV[3])
+
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'updated_at')
+local tokens = tonumber(bucket[1]) or capacity
+local updated_at = tonumber(bucket[2]) or now
+
+tokens = math.min(capacity, tokens + math.max(0, now - updated_at) * rate)
+
+local wait = 0
+if tokens >= 1 then
+  tokens = tokens - 1
+else
+  wait = math.ceil((1 - tokens) / rate)
+end
+
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'updated_at', now)
+redis.call('PEXPIRE', KEYS[1], math.ceil(capacity / rate))
+
+return wait
+"#;
+
+#[async_trait]
+impl RateLimiter for RedisRateLimiter {
+    async fn acquire(&self, bucket: &str, limit: &RateLimit) -> Result<RateLimitDecision, String> {
+        let wait: u64 = self
+            .pool
+            .with("rate_limit", "acquire")
+            .eval(
+                ACQUIRE_SCRIPT,
+                vec![redis_keys::bucket_key(bucket)],
+                vec![
+                    limit.capacity().to_string(),
+                    limit.refill_rate().to_string(),
+                    now_millis().to_string(),
+                ],
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if wait == 0 {
+            Ok(RateLimitDecision::Allowed)
+        } else {
+            Ok(RateLimitDecision::Limited {
+                retry_after: Duration::from_millis(wait),
+            })
+        }
+    }
+}
+
+// Tokens are refilled continuously, hence the fractional number of tokens
+#[derive(Debug, Clone, PartialEq)]
+struct TokenBucket {
+    tokens: f64,
+    updated_at: u64,
+}
+
+impl TokenBucket {
+    fn full(limit: &RateLimit, now: u64) -> Self {
+        TokenBucket {
+            tokens: limit.capacity() as f64,
+            updated_at: now,
+        }
+    }
+
+    fn acquire(&mut self, limit: &RateLimit, now: u64) -> RateLimitDecision {
+        let rate = limit.refill_rate();
+        let elapsed = now.saturating_sub(self.updated_at) as f64;
+
+        self.tokens = (self.tokens + elapsed * rate).min(limit.capacity() as f64);
+        self.updated_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            RateLimitDecision::Allowed
+        } else {
+            let wait = ((1.0 - self.tokens) / rate).ceil() as u64;
+            RateLimitDecision::Limited {
+                retry_after: Duration::from_millis(wait),
+            }
+        }
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+mod redis_keys {
+    pub(crate) fn bucket_key(bucket: &str) -> String {
+        format!("rate-limit:{}", bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::api_definition::http::RateLimit;
+    use crate::app_config::InMemoryRateLimitStoreConfig;
+    use crate::service::rate_limit::{
+        InMemoryRateLimiter, RateLimitDecision, RateLimiter, TokenBucket,
+    };
+
+    fn limit(requests: u64, period_seconds: u64, burst: Option<u64>) -> RateLimit {
+        RateLimit {
+            requests,
+            period_seconds,
+            burst,
+            key: None,
+        }
+    }
+
+    #[test]
+    fn token_bucket_refills_over_time() {
+        let limit = limit(2, 1, None);
+        let mut bucket = TokenBucket::full(&limit, 0);
+
+        assert_eq!(bucket.acquire(&limit, 0), RateLimitDecision::Allowed);
+        assert_eq!(bucket.acquire(&limit, 0), RateLimitDecision::Allowed);
+        assert_eq!(
+            bucket.acquire(&limit, 100),
+            RateLimitDecision::Limited {
+                retry_after: Duration::from_millis(400)
+            }
+        );
+        assert_eq!(bucket.acquire(&limit, 500), RateLimitDecision::Allowed);
+        assert_eq!(
+            bucket.acquire(&limit, 500),
+            RateLimitDecision::Limited {
+                retry_after: Duration::from_millis(500)
+            }
+        );
+    }
+
+    #[test]
+    fn token_bucket_holds_burst_tokens() {
+        let limit = limit(1, 1, Some(3));
+        let mut bucket = TokenBucket::full(&limit, 0);
+
+        // Idle time does not add tokens above the burst size
+        for _ in 0..3 {
+            assert_eq!(
+                bucket.acquire(&limit, 3_600_000),
+                RateLimitDecision::Allowed
+            );
+        }
+
+        assert_eq!(
+            bucket.acquire(&limit, 3_600_000),
+            RateLimitDecision::Limited {
+                retry_after: Duration::from_secs(1)
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn in_memory_buckets_are_separate() {
+        let rate_limiter = InMemoryRateLimiter::new(&InMemoryRateLimitStoreConfig::default());
+        let limit = limit(1, 60, None);
+
+        assert_eq!(
+            rate_limiter.acquire("route:alice", &limit).await,
+            Ok(RateLimitDecision::Allowed)
+        );
+        assert!(matches!(
+            rate_limiter.acquire("route:alice", &limit).await,
+            Ok(RateLimitDecision::Limited { .. })
+        ));
+        assert_eq!(
+            rate_limiter.acquire("route:bob", &limit).await,
+            Ok(RateLimitDecision::Allowed)
+        );
+    }
+
+    #[tokio::test]
+    async fn idle_in_memory_buckets_are_evicted() {
+        let rate_limiter = InMemoryRateLimiter::new(&InMemoryRateLimitStoreConfig {
+            bucket_time_to_idle: Duration::from_millis(50),
+            eviction_period: Duration::from_millis(10),
+        });
+        let limit = limit(1, 60, None);
+
+        assert_eq!(
+            rate_limiter.acquire("route:alice", &limit).await,
+            Ok(RateLimitDecision::Allowed)
+        );
+        assert_eq!(rate_limiter.buckets.iter().count(), 1);
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert_eq!(rate_limiter.buckets.iter().count(), 0);
+    }
+}
@@ -3,7 +3,7 @@ use std::{
     time::Duration,
 };
 
-use futures::{Sink, SinkExt, Stream, StreamExt};
+use futures::{future, Future, Sink, SinkExt, Stream, StreamExt};
 use poem::web::websocket::Message;
 use tonic::Status;
 
@@ -14,15 +14,42 @@ use golem_service_base::model::WorkerId;
 ///
 /// keep_alive_interval: Interval at which Ping messages are sent
 /// max_pong_timeout: Maximum time to wait for a Pong message before considering the connection dead
-#[tracing::instrument(skip_all, fields(worker_id = worker_id.to_string()))]
 pub async fn proxy_worker_connection(
     worker_id: WorkerId,
-    mut worker_stream: impl Stream<Item = Result<LogEvent, Status>> + Unpin,
+    worker_stream: impl Stream<Item = Result<LogEvent, Status>> + Unpin,
     websocket_sender: impl Sink<Message, Error = IoError> + Unpin,
     websocket_receiver: impl Stream<Item = IoResult<Message>> + Unpin,
     keep_alive_interval: Duration,
     max_pong_timeout: Duration,
 ) -> Result<(), ConnectProxyError> {
+    proxy_worker_connection_with(
+        worker_id,
+        worker_stream,
+        websocket_sender,
+        websocket_receiver,
+        keep_alive_interval,
+        max_pong_timeout,
+        |_| future::ready(None),
+    )
+    .await
+}
+
+/// Same as `proxy_worker_connection`, passing the Text and Binary messages of the client to `on_message`.
+/// The message returned by `on_message`, if any, is sent back to the client.
+#[tracing::instrument(skip_all, fields(worker_id = worker_id.to_string()))]
+pub async fn proxy_worker_connection_with<F, Fut>(
+    worker_id: WorkerId,
+    mut worker_stream: impl Stream<Item = Result<LogEvent, Status>> + Unpin,
+    websocket_sender: impl Sink<Message, Error = IoError> + Unpin,
+    websocket_receiver: impl Stream<Item = IoResult<Message>> + Unpin,
+    keep_alive_interval: Duration,
+    max_pong_timeout: Duration,
+    mut on_message: F,
+) -> Result<(), ConnectProxyError>
+where
+    F: FnMut(Message) -> Fut,
+    Fut: Future<Output = Option<Message>>,
+{
     tracing::info!("Proxying worker connection {worker_id}");
 
     let mut websocket = keep_alive::WebSocketKeepAlive::from_sink_and_stream(
@@ -47,6 +74,15 @@ pub async fn proxy_worker_connection(
                         tracing::info!("Received WebSocket Error: {error}");
                         break Err(error);
                     },
+                    Some(Ok(message @ (Message::Text(_) | Message::Binary(_)))) => {
+                        if let Some(reply) = on_message(message).await {
+                            if let Err(error) = websocket.send(reply).await {
+                                let error: ConnectProxyError = error.into();
+                                tracing::info!("Error sending reply to WebSocket client: {error}");
+                                break Err(error);
+                            }
+                        }
+                    }
                     Some(Ok(_)) => {}
                     None => {
                         tracing::info!("WebSocket connection closed");
@@ -3,7 +3,7 @@ use std::pin::Pin;
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
-use golem_wasm_ast::analysis::AnalysedFunctionResult;
+use golem_wasm_ast::analysis::{AnalysedFunctionResult, AnalysedType};
 use golem_wasm_rpc::json::get_json_from_typed_value;
 use golem_wasm_rpc::protobuf::Val as ProtoVal;
 use golem_wasm_rpc::TypeAnnotatedValue;
@@ -14,19 +14,28 @@ use tonic::transport::Channel;
 use tracing::{debug, info};
 
 use golem_api_grpc::proto::golem::worker::IdempotencyKey as ProtoIdempotencyKey;
-use golem_api_grpc::proto::golem::worker::{InvokeResult as ProtoInvokeResult, UpdateMode};
+use golem_api_grpc::proto::golem::worker::{
+    CancelInvocationStatus as ProtoCancelInvocationStatus, InvokeResult as ProtoInvokeResult,
+    OplogFilter as ProtoOplogFilter, ScheduledInvocation as ProtoScheduledInvocation, UpdateMode,
+};
 use golem_api_grpc::proto::golem::workerexecutor::worker_executor_client::WorkerExecutorClient;
 use golem_api_grpc::proto::golem::workerexecutor::{
-    self, CompletePromiseRequest, ConnectWorkerRequest, CreateWorkerRequest,
-    InterruptWorkerRequest, InvokeAndAwaitWorkerRequest, ResumeWorkerRequest, UpdateWorkerRequest,
+    self, CancelInvocationRequest, CancelScheduledInvocationRequest, CompletePromiseRequest,
+    ConnectWorkerRequest, CreateWorkerRequest, ForkWorkerRequest, GetOplogRequest,
+    GetPendingPromisesRequest, GetScheduledInvocationsRequest, InterruptWorkerRequest,
+    InvokeAndAwaitWorkerRequest, ListWorkerDirectoryRequest, ReadWorkerFileRequest,
+    ResumeWorkerRequest, RevertWorkerRequest, ScheduleInvocationRequest, SearchOplogRequest,
+    UpdateWorkerRequest,
 };
 
+use golem_common::model::oplog::RevertWorkerTarget;
 use golem_common::model::{
     AccountId, CallingConvention, ComponentId, ComponentVersion, FilterComparator, IdempotencyKey,
-    ScanCursor, Timestamp, WorkerFilter, WorkerStatus,
+    InvocationSchedule, ScanCursor, ScheduledInvocationId, Timestamp, WorkerFilter, WorkerStatus,
 };
 use golem_service_base::model::{
-    FunctionResult, GolemErrorUnknown, PromiseId, ResourceLimits, WorkerId, WorkerMetadata,
+    CancelInvocationStatus, FunctionResult, GetOplogResponse, GolemErrorUnknown, PendingPromise,
+    PromiseId, ResourceLimits, ScheduledInvocation, WorkerFileEntry, WorkerId, WorkerMetadata,
 };
 use golem_service_base::typechecker::{TypeCheckIn, TypeCheckOut};
 use golem_service_base::{
@@ -37,7 +46,10 @@ use golem_service_base::{
 
 use crate::service::component::ComponentService;
 
-use super::{ConnectWorkerStream, WorkerServiceError};
+use super::{
+    to_public_oplog_entry, to_public_scheduled_invocation, ConnectWorkerStream, OplogPage,
+    PromisePayload, WorkerFileStream, WorkerServiceError,
+};
 
 pub type WorkerResult<T> = Result<T, WorkerServiceError>;
 
@@ -124,7 +136,7 @@ pub trait WorkerService<AuthCtx> {
         &self,
         worker_id: &WorkerId,
         oplog_id: u64,
-        data: Vec<u8>,
+        payload: PromisePayload,
         metadata: WorkerRequestMetadata,
         auth_ctx: &AuthCtx,
     ) -> WorkerResult<bool>;
@@ -177,6 +189,134 @@ pub trait WorkerService<AuthCtx> {
         metadata: WorkerRequestMetadata,
         auth_ctx: &AuthCtx,
     ) -> Result<Component, WorkerServiceError>;
+
+    async fn get_oplog(
+        &self,
+        worker_id: &WorkerId,
+        from_oplog_index: u64,
+        count: u64,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<GetOplogResponse>;
+
+    async fn get_oplog_proto(
+        &self,
+        worker_id: &WorkerId,
+        from_oplog_index: u64,
+        count: u64,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<OplogPage>;
+
+    async fn search_oplog(
+        &self,
+        worker_id: &WorkerId,
+        filter: ProtoOplogFilter,
+        cursor: Option<u64>,
+        count: u64,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<GetOplogResponse>;
+
+    async fn search_oplog_proto(
+        &self,
+        worker_id: &WorkerId,
+        filter: ProtoOplogFilter,
+        cursor: Option<u64>,
+        count: u64,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<OplogPage>;
+
+    async fn fork(
+        &self,
+        source_worker_id: &WorkerId,
+        target_worker_id: &WorkerId,
+        oplog_index_cutoff: u64,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<()>;
+
+    async fn revert(
+        &self,
+        worker_id: &WorkerId,
+        target: RevertWorkerTarget,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<()>;
+
+    async fn cancel_invocation(
+        &self,
+        worker_id: &WorkerId,
+        idempotency_key: &IdempotencyKey,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<CancelInvocationStatus>;
+
+    async fn schedule_invocation(
+        &self,
+        worker_id: &WorkerId,
+        function_name: String,
+        params: Value,
+        schedule: InvocationSchedule,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<ScheduledInvocation>;
+
+    async fn schedule_invocation_proto(
+        &self,
+        worker_id: &WorkerId,
+        function_name: String,
+        params: Vec<ProtoVal>,
+        schedule: InvocationSchedule,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<ProtoScheduledInvocation>;
+
+    async fn get_scheduled_invocations(
+        &self,
+        worker_id: &WorkerId,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<Vec<ScheduledInvocation>>;
+
+    async fn get_scheduled_invocations_proto(
+        &self,
+        worker_id: &WorkerId,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<Vec<ProtoScheduledInvocation>>;
+
+    async fn cancel_scheduled_invocation(
+        &self,
+        worker_id: &WorkerId,
+        id: &ScheduledInvocationId,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<bool>;
+
+    async fn list_directory(
+        &self,
+        worker_id: &WorkerId,
+        path: &str,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<Vec<WorkerFileEntry>>;
+
+    async fn read_file(
+        &self,
+        worker_id: &WorkerId,
+        path: &str,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<WorkerFileStream>;
+
+    async fn get_pending_promises(
+        &self,
+        worker_id: &WorkerId,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<Vec<PendingPromise>>;
 }
 
 pub struct TypedResult {
@@ -639,15 +779,35 @@ where
         &self,
         worker_id: &WorkerId,
         oplog_id: u64,
-        data: Vec<u8>,
+        payload: PromisePayload,
         metadata: WorkerRequestMetadata,
-        _auth_ctx: &AuthCtx,
+        auth_ctx: &AuthCtx,
     ) -> WorkerResult<bool> {
         let promise_id = PromiseId {
             worker_id: worker_id.clone(),
             oplog_idx: oplog_id,
         };
 
+        // The payload is type checked by the executor, the payload type is only needed here to
+        // parse WAVE values
+        let payload_type = if payload.needs_payload_type() {
+            let pending = self
+                .get_pending_promises(worker_id, metadata.clone(), auth_ctx)
+                .await?
+                .into_iter()
+                .find(|pending| pending.promise_id == promise_id)
+                .ok_or(WorkerServiceError::TypeChecker(format!(
+                    "Promise {} of worker {} is not pending",
+                    oplog_id, worker_id
+                )))?;
+            pending.payload_type.map(AnalysedType::from)
+        } else {
+            None
+        };
+        let data = payload
+            .encode(payload_type.as_ref())
+            .map_err(WorkerServiceError::TypeChecker)?;
+
         let result = self
             .retry_on_invalid_shard_id(
                 worker_id,
@@ -688,7 +848,13 @@ where
                     })
                 },
             )
-            .await?;
+            .await
+            .map_err(|err| match err {
+                WorkerServiceError::Golem(GolemError::ValueMismatch(mismatch)) => {
+                    WorkerServiceError::TypeChecker(mismatch.details)
+                }
+                err => err,
+            })?;
         Ok(result)
     }
 
@@ -906,120 +1072,788 @@ where
         self.try_get_component_for_worker(worker_id, metadata, auth_ctx)
             .await
     }
-}
 
-impl<AuthCtx> WorkerServiceDefault<AuthCtx>
-where
-    AuthCtx: Send + Sync,
-{
-    async fn try_get_component_for_worker(
+    async fn get_oplog(
         &self,
         worker_id: &WorkerId,
-        request_metadata: WorkerRequestMetadata,
+        from_oplog_index: u64,
+        count: u64,
+        metadata: WorkerRequestMetadata,
         auth_ctx: &AuthCtx,
-    ) -> Result<Component, WorkerServiceError> {
-        match self
-            .get_metadata(worker_id, request_metadata, auth_ctx)
+    ) -> WorkerResult<GetOplogResponse> {
+        let page = self
+            .get_oplog_proto(
+                worker_id,
+                from_oplog_index,
+                count,
+                metadata.clone(),
+                auth_ctx,
+            )
+            .await?;
+        self.to_oplog_response(worker_id, page, metadata, auth_ctx)
             .await
-        {
-            Ok(metadata) => {
-                let component_version = metadata.component_version;
-                let component_details = self
-                    .component_service
-                    .get_by_version(&worker_id.component_id, component_version, auth_ctx)
-                    .await?;
+    }
 
-                Ok(component_details)
-            }
-            Err(WorkerServiceError::WorkerNotFound(_)) => Ok(self
-                .component_service
-                .get_latest(&worker_id.component_id, auth_ctx)
-                .await?),
-            Err(WorkerServiceError::Golem(GolemError::WorkerNotFound(_))) => Ok(self
-                .component_service
-                .get_latest(&worker_id.component_id, auth_ctx)
-                .await?),
-            Err(other) => Err(other),
-        }
+    async fn get_oplog_proto(
+        &self,
+        worker_id: &WorkerId,
+        from_oplog_index: u64,
+        count: u64,
+        metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<OplogPage> {
+        let page = self.retry_on_invalid_shard_id(
+            worker_id,
+            &(worker_id, metadata),
+            |worker_executor_client, (worker_id, metadata)| {
+                Box::pin(async move {
+                    let response = worker_executor_client
+                        .get_oplog(GetOplogRequest {
+                            worker_id: Some((*worker_id).clone().into()),
+                            from_oplog_index,
+                            count,
+                            account_id: metadata.account_id.clone().map(|id| id.into()),
+                        })
+                        .await
+                        .map_err(|err| {
+                            GolemError::RuntimeError(GolemErrorRuntimeError {
+                                details: err.to_string(),
+                            })
+                        })?;
+                    match response.into_inner() {
+                        workerexecutor::GetOplogResponse {
+                            result: Some(workerexecutor::get_oplog_response::Result::Success(success)),
+                        } => Ok(OplogPage {
+                            entries: success.entries,
+                            next: success.next,
+                            last_index: success.last_index,
+                        }),
+                        workerexecutor::GetOplogResponse {
+                            result: Some(workerexecutor::get_oplog_response::Result::Failure(err)),
+                        } => Err(err.try_into().unwrap()),
+                        workerexecutor::GetOplogResponse { .. } => {
+                            Err(GolemError::Unknown(GolemErrorUnknown {
+                                details: "Empty response".to_string(),
+                            }))
+                        }
+                    }
+                })
+            },
+        )
+            .await?;
+
+        Ok(page)
     }
 
-    async fn get_worker_executor_client(
+    async fn search_oplog(
         &self,
         worker_id: &WorkerId,
-    ) -> Result<Option<WorkerExecutorClient<Channel>>, GetWorkerExecutorClientError> {
-        let routing_table = self
-            .routing_table_service
-            .get_routing_table()
+        filter: ProtoOplogFilter,
+        cursor: Option<u64>,
+        count: u64,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<GetOplogResponse> {
+        let page = self
+            .search_oplog_proto(worker_id, filter, cursor, count, metadata.clone(), auth_ctx)
+            .await?;
+        self.to_oplog_response(worker_id, page, metadata, auth_ctx)
             .await
-            .map_err(GetWorkerExecutorClientError::FailedToGetRoutingTable)?;
-        match routing_table.lookup(worker_id) {
-            None => Ok(None),
-            Some(pod) => {
-                let worker_executor_client = self
-                    .worker_executor_clients
-                    .lookup(pod.clone())
-                    .await
-                    .map_err(GetWorkerExecutorClientError::FailedToConnectToPod)?;
-                Ok(Some(worker_executor_client))
-            }
-        }
     }
 
-    async fn retry_on_invalid_shard_id<F, In, Out>(
+    async fn search_oplog_proto(
         &self,
         worker_id: &WorkerId,
-        i: &In,
-        f: F,
-    ) -> Result<Out, WorkerServiceError>
-    where
-        F: for<'b> Fn(
-            &'b mut WorkerExecutorClient<Channel>,
-            &'b In,
-        )
-            -> Pin<Box<dyn Future<Output = Result<Out, GolemError>> + 'b + Send>>,
-    {
-        loop {
-            match self.get_worker_executor_client(worker_id).await {
-                Ok(Some(mut worker_executor_client)) => {
-                    match f(&mut worker_executor_client, i).await {
-                        Ok(result) => return Ok(result),
-                        Err(GolemError::InvalidShardId(GolemErrorInvalidShardId {
-                            shard_id,
-                            shard_ids,
-                        })) => {
-                            info!("InvalidShardId: {} not in {:?}", shard_id, shard_ids);
-                            info!("Invalidating routing table");
-                            self.routing_table_service.invalidate_routing_table().await;
-                            sleep(Duration::from_secs(1)).await;
-                        }
-                        Err(GolemError::RuntimeError(GolemErrorRuntimeError { details }))
-                            if is_connection_failure(&details) =>
-                        {
-                            info!("Worker executor unavailable");
-                            info!("Invalidating routing table and retrying immediately");
-                            self.routing_table_service.invalidate_routing_table().await;
-                        }
-                        Err(other) => {
-                            debug!("Got {:?}, not retrying", other);
-                            return Err(WorkerServiceError::Golem(other));
+        filter: ProtoOplogFilter,
+        cursor: Option<u64>,
+        count: u64,
+        metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<OplogPage> {
+        let page = self.retry_on_invalid_shard_id(
+            worker_id,
+            &(worker_id, filter, metadata),
+            |worker_executor_client, (worker_id, filter, metadata)| {
+                Box::pin(async move {
+                    let response = worker_executor_client
+                        .search_oplog(SearchOplogRequest {
+                            worker_id: Some((*worker_id).clone().into()),
+                            filter: Some(filter.clone()),
+                            cursor,
+                            count,
+                            account_id: metadata.account_id.clone().map(|id| id.into()),
+                        })
+                        .await
+                        .map_err(|err| {
+                            GolemError::RuntimeError(GolemErrorRuntimeError {
+                                details: err.to_string(),
+                            })
+                        })?;
+                    match response.into_inner() {
+                        workerexecutor::SearchOplogResponse {
+                            result: Some(workerexecutor::search_oplog_response::Result::Success(success)),
+                        } => Ok(OplogPage {
+                            entries: success.entries,
+                            next: success.next,
+                            last_index: success.last_index,
+                        }),
+                        workerexecutor::SearchOplogResponse {
+                            result: Some(workerexecutor::search_oplog_response::Result::Failure(err)),
+                        } => Err(err.try_into().unwrap()),
+                        workerexecutor::SearchOplogResponse { .. } => {
+                            Err(GolemError::Unknown(GolemErrorUnknown {
+                                details: "Empty response".to_string(),
+                            }))
                         }
                     }
-                }
-                Ok(None) => {
-                    info!("No active shards");
-                    info!("Invalidating routing table");
-                    self.routing_table_service.invalidate_routing_table().await;
-                    sleep(Duration::from_secs(1)).await;
-                }
-                Err(GetWorkerExecutorClientError::FailedToGetRoutingTable(
-                    RoutingTableError::Unexpected(details),
-                )) if is_connection_failure(&details) => {
-                    info!("Shard manager unavailable");
-                    info!("Invalidating routing table and retrying in 1 seconds");
-                    self.routing_table_service.invalidate_routing_table().await;
-                    sleep(Duration::from_secs(1)).await;
-                }
-                Err(GetWorkerExecutorClientError::FailedToConnectToPod(details))
+                })
+            },
+        )
+            .await?;
+
+        Ok(page)
+    }
+
+    async fn fork(
+        &self,
+        source_worker_id: &WorkerId,
+        target_worker_id: &WorkerId,
+        oplog_index_cutoff: u64,
+        metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<()> {
+        // The fork is executed by the worker executor owning the newly created worker
+        self.retry_on_invalid_shard_id(
+            target_worker_id,
+            &(source_worker_id, target_worker_id, metadata),
+            |worker_executor_client, (source_worker_id, target_worker_id, metadata)| {
+                Box::pin(async move {
+                    let response = worker_executor_client
+                        .fork_worker(ForkWorkerRequest {
+                            source_worker_id: Some((*source_worker_id).clone().into()),
+                            target_worker_id: Some((*target_worker_id).clone().into()),
+                            oplog_index_cutoff,
+                            account_id: metadata.account_id.clone().map(|id| id.into()),
+                        })
+                        .await
+                        .map_err(|err| {
+                            GolemError::RuntimeError(GolemErrorRuntimeError {
+                                details: err.to_string(),
+                            })
+                        })?;
+                    match response.into_inner() {
+                        workerexecutor::ForkWorkerResponse {
+                            result: Some(workerexecutor::fork_worker_response::Result::Success(_)),
+                        } => Ok(()),
+                        workerexecutor::ForkWorkerResponse {
+                            result: Some(workerexecutor::fork_worker_response::Result::Failure(err)),
+                        } => Err(err.try_into().unwrap()),
+                        workerexecutor::ForkWorkerResponse { .. } => {
+                            Err(GolemError::Unknown(GolemErrorUnknown {
+                                details: "Empty response".to_string(),
+                            }))
+                        }
+                    }
+                })
+            },
+        )
+            .await?;
+        Ok(())
+    }
+
+    async fn revert(
+        &self,
+        worker_id: &WorkerId,
+        target: RevertWorkerTarget,
+        metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<()> {
+        self.retry_on_invalid_shard_id(
+            worker_id,
+            &(worker_id.clone(), target, metadata),
+            |worker_executor_client, (worker_id, target, metadata)| {
+                Box::pin(async move {
+                    let response = worker_executor_client
+                        .revert_worker(RevertWorkerRequest {
+                            worker_id: Some(worker_id.clone().into()),
+                            target: Some(target.clone().into()),
+                            account_id: metadata.account_id.clone().map(|id| id.into()),
+                        })
+                        .await
+                        .map_err(|err| {
+                            GolemError::RuntimeError(GolemErrorRuntimeError {
+                                details: err.to_string(),
+                            })
+                        })?;
+                    match response.into_inner() {
+                        workerexecutor::RevertWorkerResponse {
+                            result: Some(workerexecutor::revert_worker_response::Result::Success(_)),
+                        } => Ok(()),
+                        workerexecutor::RevertWorkerResponse {
+                            result: Some(workerexecutor::revert_worker_response::Result::Failure(err)),
+                        } => Err(err.try_into().unwrap()),
+                        workerexecutor::RevertWorkerResponse { .. } => {
+                            Err(GolemError::Unknown(GolemErrorUnknown {
+                                details: "Empty response".to_string(),
+                            }))
+                        }
+                    }
+                })
+            },
+        )
+            .await?;
+        Ok(())
+    }
+
+    async fn cancel_invocation(
+        &self,
+        worker_id: &WorkerId,
+        idempotency_key: &IdempotencyKey,
+        metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<CancelInvocationStatus> {
+        let status = self.retry_on_invalid_shard_id(
+            worker_id,
+            &(worker_id.clone(), idempotency_key.clone(), metadata),
+            |worker_executor_client, (worker_id, idempotency_key, metadata)| {
+                Box::pin(async move {
+                    let response = worker_executor_client
+                        .cancel_invocation(CancelInvocationRequest {
+                            worker_id: Some(worker_id.clone().into()),
+                            idempotency_key: Some(idempotency_key.clone().into()),
+                            account_id: metadata.account_id.clone().map(|id| id.into()),
+                        })
+                        .await
+                        .map_err(|err| {
+                            GolemError::RuntimeError(GolemErrorRuntimeError {
+                                details: err.to_string(),
+                            })
+                        })?;
+                    match response.into_inner() {
+                        workerexecutor::CancelInvocationResponse {
+                            result: Some(workerexecutor::cancel_invocation_response::Result::Success(status)),
+                        } => ProtoCancelInvocationStatus::try_from(status)
+                            .map_err(|err| err.to_string())
+                            .and_then(CancelInvocationStatus::try_from)
+                            .map_err(|err| {
+                                GolemError::Unknown(GolemErrorUnknown {
+                                    details: format!("Invalid cancel invocation status: {err}"),
+                                })
+                            }),
+                        workerexecutor::CancelInvocationResponse {
+                            result: Some(workerexecutor::cancel_invocation_response::Result::Failure(err)),
+                        } => Err(err.try_into().unwrap()),
+                        workerexecutor::CancelInvocationResponse { .. } => {
+                            Err(GolemError::Unknown(GolemErrorUnknown {
+                                details: "Empty response".to_string(),
+                            }))
+                        }
+                    }
+                })
+            },
+        )
+            .await?;
+        Ok(status)
+    }
+
+    async fn schedule_invocation(
+        &self,
+        worker_id: &WorkerId,
+        function_name: String,
+        params: Value,
+        schedule: InvocationSchedule,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<ScheduledInvocation> {
+        let component_details = self
+            .try_get_component_for_worker(worker_id, metadata.clone(), auth_ctx)
+            .await?;
+        let function_type = component_details
+            .metadata
+            .function_by_name(&function_name)
+            .ok_or_else(|| {
+                WorkerServiceError::TypeChecker("Failed to find the function".to_string())
+            })?;
+        let params_val = params
+            .validate_function_parameters(
+                function_type
+                    .parameters
+                    .into_iter()
+                    .map(|parameter| parameter.into())
+                    .collect(),
+                CallingConvention::Component,
+            )
+            .map_err(|err| WorkerServiceError::TypeChecker(err.join(", ")))?;
+        let invocation = self
+            .schedule_invocation_proto(
+                worker_id,
+                function_name,
+                params_val,
+                schedule,
+                metadata,
+                auth_ctx,
+            )
+            .await?;
+
+        to_public_scheduled_invocation(invocation, &component_details)
+            .map_err(|err| WorkerServiceError::Internal(anyhow::Error::msg(err)))
+    }
+
+    async fn schedule_invocation_proto(
+        &self,
+        worker_id: &WorkerId,
+        function_name: String,
+        params: Vec<ProtoVal>,
+        schedule: InvocationSchedule,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<ProtoScheduledInvocation> {
+        let component_details = self
+            .try_get_component_for_worker(worker_id, metadata.clone(), auth_ctx)
+            .await?;
+        let function_type = component_details
+            .metadata
+            .function_by_name(&function_name)
+            .ok_or_else(|| {
+                WorkerServiceError::TypeChecker("Failed to find the function".to_string())
+            })?;
+        let params_val = params
+            .validate_function_parameters(
+                function_type
+                    .parameters
+                    .into_iter()
+                    .map(|parameter| parameter.into())
+                    .collect(),
+                CallingConvention::Component,
+            )
+            .map_err(|err| WorkerServiceError::TypeChecker(err.join(", ")))?;
+
+        let invocation = self.retry_on_invalid_shard_id(
+            worker_id,
+            &(worker_id.clone(), function_name, params_val, schedule, metadata),
+            |worker_executor_client, (worker_id, function_name, params_val, schedule, metadata)| {
+                Box::pin(async move {
+                    let response = worker_executor_client
+                        .schedule_invocation(ScheduleInvocationRequest {
+                            worker_id: Some(worker_id.clone().into()),
+                            name: function_name.clone(),
+                            input: params_val.clone(),
+                            schedule: Some(schedule.clone().into()),
+                            account_id: metadata.account_id.clone().map(|id| id.into()),
+                        })
+                        .await
+                        .map_err(|err| {
+                            GolemError::RuntimeError(GolemErrorRuntimeError {
+                                details: err.to_string(),
+                            })
+                        })?;
+                    match response.into_inner() {
+                        workerexecutor::ScheduleInvocationResponse {
+                            result: Some(workerexecutor::schedule_invocation_response::Result::Success(invocation)),
+                        } => Ok(invocation),
+                        workerexecutor::ScheduleInvocationResponse {
+                            result: Some(workerexecutor::schedule_invocation_response::Result::Failure(err)),
+                        } => Err(err.try_into().unwrap()),
+                        workerexecutor::ScheduleInvocationResponse { .. } => {
+                            Err(GolemError::Unknown(GolemErrorUnknown {
+                                details: "Empty response".to_string(),
+                            }))
+                        }
+                    }
+                })
+            },
+        )
+            .await?;
+        Ok(invocation)
+    }
+
+    async fn get_scheduled_invocations(
+        &self,
+        worker_id: &WorkerId,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<Vec<ScheduledInvocation>> {
+        let invocations = self
+            .get_scheduled_invocations_proto(worker_id, metadata.clone(), auth_ctx)
+            .await?;
+        let component = self
+            .try_get_component_for_worker(worker_id, metadata, auth_ctx)
+            .await?;
+
+        invocations
+            .into_iter()
+            .map(|invocation| to_public_scheduled_invocation(invocation, &component))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| WorkerServiceError::Internal(anyhow::Error::msg(err)))
+    }
+
+    async fn get_scheduled_invocations_proto(
+        &self,
+        worker_id: &WorkerId,
+        metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<Vec<ProtoScheduledInvocation>> {
+        let invocations = self.retry_on_invalid_shard_id(
+            worker_id,
+            &(worker_id.clone(), metadata),
+            |worker_executor_client, (worker_id, metadata)| {
+                Box::pin(async move {
+                    let response = worker_executor_client
+                        .get_scheduled_invocations(GetScheduledInvocationsRequest {
+                            worker_id: Some(worker_id.clone().into()),
+                            account_id: metadata.account_id.clone().map(|id| id.into()),
+                        })
+                        .await
+                        .map_err(|err| {
+                            GolemError::RuntimeError(GolemErrorRuntimeError {
+                                details: err.to_string(),
+                            })
+                        })?;
+                    match response.into_inner() {
+                        workerexecutor::GetScheduledInvocationsResponse {
+                            result: Some(workerexecutor::get_scheduled_invocations_response::Result::Success(success)),
+                        } => Ok(success.invocations),
+                        workerexecutor::GetScheduledInvocationsResponse {
+                            result: Some(workerexecutor::get_scheduled_invocations_response::Result::Failure(err)),
+                        } => Err(err.try_into().unwrap()),
+                        workerexecutor::GetScheduledInvocationsResponse { .. } => {
+                            Err(GolemError::Unknown(GolemErrorUnknown {
+                                details: "Empty response".to_string(),
+                            }))
+                        }
+                    }
+                })
+            },
+        )
+            .await?;
+        Ok(invocations)
+    }
+
+    async fn cancel_scheduled_invocation(
+        &self,
+        worker_id: &WorkerId,
+        id: &ScheduledInvocationId,
+        metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<bool> {
+        let canceled = self.retry_on_invalid_shard_id(
+            worker_id,
+            &(worker_id.clone(), id.clone(), metadata),
+            |worker_executor_client, (worker_id, id, metadata)| {
+                Box::pin(async move {
+                    let response = worker_executor_client
+                        .cancel_scheduled_invocation(CancelScheduledInvocationRequest {
+                            worker_id: Some(worker_id.clone().into()),
+                            id: Some(id.clone().into()),
+                            account_id: metadata.account_id.clone().map(|id| id.into()),
+                        })
+                        .await
+                        .map_err(|err| {
+                            GolemError::RuntimeError(GolemErrorRuntimeError {
+                                details: err.to_string(),
+                            })
+                        })?;
+                    match response.into_inner() {
+                        workerexecutor::CancelScheduledInvocationResponse {
+                            result: Some(workerexecutor::cancel_scheduled_invocation_response::Result::Success(canceled)),
+                        } => Ok(canceled),
+                        workerexecutor::CancelScheduledInvocationResponse {
+                            result: Some(workerexecutor::cancel_scheduled_invocation_response::Result::Failure(err)),
+                        } => Err(err.try_into().unwrap()),
+                        workerexecutor::CancelScheduledInvocationResponse { .. } => {
+                            Err(GolemError::Unknown(GolemErrorUnknown {
+                                details: "Empty response".to_string(),
+                            }))
+                        }
+                    }
+                })
+            },
+        )
+            .await?;
+        Ok(canceled)
+    }
+
+    async fn list_directory(
+        &self,
+        worker_id: &WorkerId,
+        path: &str,
+        metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<Vec<WorkerFileEntry>> {
+        let entries = self.retry_on_invalid_shard_id(
+            worker_id,
+            &(worker_id.clone(), path.to_string(), metadata),
+            |worker_executor_client, (worker_id, path, metadata)| {
+                Box::pin(async move {
+                    let response = worker_executor_client
+                        .list_worker_directory(ListWorkerDirectoryRequest {
+                            worker_id: Some(worker_id.clone().into()),
+                            path: path.clone(),
+                            account_id: metadata.account_id.clone().map(|id| id.into()),
+                        })
+                        .await
+                        .map_err(|err| {
+                            GolemError::RuntimeError(GolemErrorRuntimeError {
+                                details: err.to_string(),
+                            })
+                        })?;
+                    match response.into_inner() {
+                        workerexecutor::ListWorkerDirectoryResponse {
+                            result: Some(workerexecutor::list_worker_directory_response::Result::Success(success)),
+                        } => Ok(success.entries.into_iter().map(|entry| entry.into()).collect()),
+                        workerexecutor::ListWorkerDirectoryResponse {
+                            result: Some(workerexecutor::list_worker_directory_response::Result::Failure(err)),
+                        } => Err(err.try_into().unwrap()),
+                        workerexecutor::ListWorkerDirectoryResponse { .. } => {
+                            Err(GolemError::Unknown(GolemErrorUnknown {
+                                details: "Empty response".to_string(),
+                            }))
+                        }
+                    }
+                })
+            },
+        )
+            .await?;
+        Ok(entries)
+    }
+
+    async fn read_file(
+        &self,
+        worker_id: &WorkerId,
+        path: &str,
+        metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<WorkerFileStream> {
+        let stream = self.retry_on_invalid_shard_id(
+            worker_id,
+            &(worker_id.clone(), path.to_string(), metadata),
+            |worker_executor_client, (worker_id, path, metadata)| {
+                Box::pin(async move {
+                    let mut streaming = worker_executor_client
+                        .read_worker_file(ReadWorkerFileRequest {
+                            worker_id: Some(worker_id.clone().into()),
+                            path: path.clone(),
+                            account_id: metadata.account_id.clone().map(|id| id.into()),
+                        })
+                        .await
+                        .map_err(|err| {
+                            GolemError::RuntimeError(GolemErrorRuntimeError {
+                                details: err.to_string(),
+                            })
+                        })?
+                        .into_inner();
+
+                    // Failing to open the file is reported as the first message of the stream
+                    let first = streaming.message().await.map_err(|err| {
+                        GolemError::RuntimeError(GolemErrorRuntimeError {
+                            details: err.to_string(),
+                        })
+                    })?;
+                    match first {
+                        None => Ok(WorkerFileStream::new(None, streaming)),
+                        Some(workerexecutor::ReadWorkerFileResponse {
+                            result: Some(workerexecutor::read_worker_file_response::Result::SuccessChunk(chunk)),
+                        }) => Ok(WorkerFileStream::new(Some(chunk), streaming)),
+                        Some(workerexecutor::ReadWorkerFileResponse {
+                            result: Some(workerexecutor::read_worker_file_response::Result::Failure(err)),
+                        }) => Err(err.try_into().unwrap()),
+                        Some(workerexecutor::ReadWorkerFileResponse { .. }) => {
+                            Err(GolemError::Unknown(GolemErrorUnknown {
+                                details: "Empty response".to_string(),
+                            }))
+                        }
+                    }
+                })
+            },
+        )
+            .await?;
+        Ok(stream)
+    }
+
+    async fn get_pending_promises(
+        &self,
+        worker_id: &WorkerId,
+        metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<Vec<PendingPromise>> {
+        let promises = self.retry_on_invalid_shard_id(
+            worker_id,
+            &(worker_id.clone(), metadata),
+            |worker_executor_client, (worker_id, metadata)| {
+                Box::pin(async move {
+                    let response = worker_executor_client
+                        .get_pending_promises(GetPendingPromisesRequest {
+                            worker_id: Some(worker_id.clone().into()),
+                            account_id: metadata.account_id.clone().map(|id| id.into()),
+                        })
+                        .await
+                        .map_err(|err| {
+                            GolemError::RuntimeError(GolemErrorRuntimeError {
+                                details: err.to_string(),
+                            })
+                        })?;
+                    match response.into_inner() {
+                        workerexecutor::GetPendingPromisesResponse {
+                            result: Some(workerexecutor::get_pending_promises_response::Result::Success(success)),
+                        } => success
+                            .promises
+                            .into_iter()
+                            .map(|promise| {
+                                promise.try_into().map_err(|err: String| {
+                                    GolemError::Unknown(GolemErrorUnknown { details: err })
+                                })
+                            })
+                            .collect::<Result<Vec<_>, _>>(),
+                        workerexecutor::GetPendingPromisesResponse {
+                            result: Some(workerexecutor::get_pending_promises_response::Result::Failure(err)),
+                        } => Err(err.try_into().unwrap()),
+                        workerexecutor::GetPendingPromisesResponse { .. } => {
+                            Err(GolemError::Unknown(GolemErrorUnknown {
+                                details: "Empty response".to_string(),
+                            }))
+                        }
+                    }
+                })
+            },
+        )
+            .await?;
+        Ok(promises)
+    }
+}
+
+impl<AuthCtx> WorkerServiceDefault<AuthCtx>
+where
+    AuthCtx: Send + Sync,
+{
+    async fn to_oplog_response(
+        &self,
+        worker_id: &WorkerId,
+        page: OplogPage,
+        metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> WorkerResult<GetOplogResponse> {
+        let component = self
+            .try_get_component_for_worker(worker_id, metadata, auth_ctx)
+            .await?;
+
+        Ok(GetOplogResponse {
+            entries: page
+                .entries
+                .into_iter()
+                .map(|entry| to_public_oplog_entry(entry, &component))
+                .collect(),
+            next: page.next,
+            last_index: page.last_index,
+        })
+    }
+    async fn try_get_component_for_worker(
+        &self,
+        worker_id: &WorkerId,
+        request_metadata: WorkerRequestMetadata,
+        auth_ctx: &AuthCtx,
+    ) -> Result<Component, WorkerServiceError> {
+        match self
+            .get_metadata(worker_id, request_metadata, auth_ctx)
+            .await
+        {
+            Ok(metadata) => {
+                let component_version = metadata.component_version;
+                let component_details = self
+                    .component_service
+                    .get_by_version(&worker_id.component_id, component_version, auth_ctx)
+                    .await?;
+
+                Ok(component_details)
+            }
+            Err(WorkerServiceError::WorkerNotFound(_)) => Ok(self
+                .component_service
+                .get_latest(&worker_id.component_id, auth_ctx)
+                .await?),
+            Err(WorkerServiceError::Golem(GolemError::WorkerNotFound(_))) => Ok(self
+                .component_service
+                .get_latest(&worker_id.component_id, auth_ctx)
+                .await?),
+            Err(other) => Err(other),
+        }
+    }
+
+    async fn get_worker_executor_client(
+        &self,
+        worker_id: &WorkerId,
+    ) -> Result<Option<WorkerExecutorClient<Channel>>, GetWorkerExecutorClientError> {
+        let routing_table = self
+            .routing_table_service
+            .get_routing_table()
+            .await
+            .map_err(GetWorkerExecutorClientError::FailedToGetRoutingTable)?;
+        match routing_table.lookup(worker_id) {
+            None => Ok(None),
+            Some(pod) => {
+                let worker_executor_client = self
+                    .worker_executor_clients
+                    .lookup(pod.clone())
+                    .await
+                    .map_err(GetWorkerExecutorClientError::FailedToConnectToPod)?;
+                Ok(Some(worker_executor_client))
+            }
+        }
+    }
+
+    async fn retry_on_invalid_shard_id<F, In, Out>(
+        &self,
+        worker_id: &WorkerId,
+        i: &In,
+        f: F,
+    ) -> Result<Out, WorkerServiceError>
+    where
+        F: for<'b> Fn(
+            &'b mut WorkerExecutorClient<Channel>,
+            &'b In,
+        )
+            -> Pin<Box<dyn Future<Output = Result<Out, GolemError>> + 'b + Send>>,
+    {
+        loop {
+            match self.get_worker_executor_client(worker_id).await {
+                Ok(Some(mut worker_executor_client)) => {
+                    match f(&mut worker_executor_client, i).await {
+                        Ok(result) => return Ok(result),
+                        Err(GolemError::InvalidShardId(GolemErrorInvalidShardId {
+                            shard_id,
+                            shard_ids,
+                        })) => {
+                            info!("InvalidShardId: {} not in {:?}", shard_id, shard_ids);
+                            info!("Invalidating routing table");
+                            self.routing_table_service.invalidate_routing_table().await;
+                            sleep(Duration::from_secs(1)).await;
+                        }
+                        Err(GolemError::RuntimeError(GolemErrorRuntimeError { details }))
+                            if is_connection_failure(&details) =>
+                        {
+                            info!("Worker executor unavailable");
+                            info!("Invalidating routing table and retrying immediately");
+                            self.routing_table_service.invalidate_routing_table().await;
+                        }
+                        Err(other) => {
+                            debug!("Got {:?}, not retrying", other);
+                            return Err(WorkerServiceError::Golem(other));
+                        }
+                    }
+                }
+                Ok(None) => {
+                    info!("No active shards");
+                    info!("Invalidating routing table");
+                    self.routing_table_service.invalidate_routing_table().await;
+                    sleep(Duration::from_secs(1)).await;
+                }
+                Err(GetWorkerExecutorClientError::FailedToGetRoutingTable(
+                    RoutingTableError::Unexpected(details),
+                )) if is_connection_failure(&details) => {
+                    info!("Shard manager unavailable");
+                    info!("Invalidating routing table and retrying in 1 seconds");
+                    self.routing_table_service.invalidate_routing_table().await;
+                    sleep(Duration::from_secs(1)).await;
+                }
+                Err(GetWorkerExecutorClientError::FailedToConnectToPod(details))
                     if is_connection_failure(&details) =>
                 {
                     info!("Worker executor unavailable");
@@ -1484,7 +2318,7 @@ where
         &self,
         _worker_id: &WorkerId,
         _oplog_id: u64,
-        _data: Vec<u8>,
+        _payload: PromisePayload,
         _metadata: WorkerRequestMetadata,
         _auth_ctx: &AuthCtx,
     ) -> WorkerResult<bool> {
@@ -1566,4 +2400,178 @@ where
         };
         Err(WorkerServiceError::WorkerNotFound(worker_id))
     }
+
+    async fn get_oplog(
+        &self,
+        _worker_id: &WorkerId,
+        _from_oplog_index: u64,
+        _count: u64,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<GetOplogResponse> {
+        Ok(GetOplogResponse {
+            entries: vec![],
+            next: None,
+            last_index: 0,
+        })
+    }
+
+    async fn get_oplog_proto(
+        &self,
+        _worker_id: &WorkerId,
+        _from_oplog_index: u64,
+        _count: u64,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<OplogPage> {
+        Ok(OplogPage::default())
+    }
+
+    async fn search_oplog(
+        &self,
+        _worker_id: &WorkerId,
+        _filter: ProtoOplogFilter,
+        _cursor: Option<u64>,
+        _count: u64,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<GetOplogResponse> {
+        Ok(GetOplogResponse {
+            entries: vec![],
+            next: None,
+            last_index: 0,
+        })
+    }
+
+    async fn search_oplog_proto(
+        &self,
+        _worker_id: &WorkerId,
+        _filter: ProtoOplogFilter,
+        _cursor: Option<u64>,
+        _count: u64,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<OplogPage> {
+        Ok(OplogPage::default())
+    }
+
+    async fn fork(
+        &self,
+        _source_worker_id: &WorkerId,
+        _target_worker_id: &WorkerId,
+        _oplog_index_cutoff: u64,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<()> {
+        Ok(())
+    }
+
+    async fn revert(
+        &self,
+        _worker_id: &WorkerId,
+        _target: RevertWorkerTarget,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<()> {
+        Ok(())
+    }
+
+    async fn cancel_invocation(
+        &self,
+        _worker_id: &WorkerId,
+        _idempotency_key: &IdempotencyKey,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<CancelInvocationStatus> {
+        Ok(CancelInvocationStatus::NotFound)
+    }
+
+    async fn schedule_invocation(
+        &self,
+        _worker_id: &WorkerId,
+        function_name: String,
+        _params: Value,
+        _schedule: InvocationSchedule,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<ScheduledInvocation> {
+        Ok(ScheduledInvocation {
+            id: ScheduledInvocationId::new_v4(),
+            scheduled_at: Timestamp::now_utc(),
+            function_name,
+            params: None,
+            cron: None,
+        })
+    }
+
+    async fn schedule_invocation_proto(
+        &self,
+        _worker_id: &WorkerId,
+        _function_name: String,
+        _params: Vec<ProtoVal>,
+        _schedule: InvocationSchedule,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<ProtoScheduledInvocation> {
+        Ok(ProtoScheduledInvocation::default())
+    }
+
+    async fn get_scheduled_invocations(
+        &self,
+        _worker_id: &WorkerId,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<Vec<ScheduledInvocation>> {
+        Ok(vec![])
+    }
+
+    async fn get_scheduled_invocations_proto(
+        &self,
+        _worker_id: &WorkerId,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<Vec<ProtoScheduledInvocation>> {
+        Ok(vec![])
+    }
+
+    async fn cancel_scheduled_invocation(
+        &self,
+        _worker_id: &WorkerId,
+        _id: &ScheduledInvocationId,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<bool> {
+        Ok(false)
+    }
+
+    async fn list_directory(
+        &self,
+        _worker_id: &WorkerId,
+        _path: &str,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<Vec<WorkerFileEntry>> {
+        Ok(vec![])
+    }
+
+    async fn read_file(
+        &self,
+        _worker_id: &WorkerId,
+        _path: &str,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<WorkerFileStream> {
+        Err(WorkerServiceError::Internal(anyhow::Error::msg(
+            "Not supported",
+        )))
+    }
+
+    async fn get_pending_promises(
+        &self,
+        _worker_id: &WorkerId,
+        _metadata: WorkerRequestMetadata,
+        _auth_ctx: &AuthCtx,
+    ) -> WorkerResult<Vec<PendingPromise>> {
+        Ok(vec![])
+    }
 }
@@ -0,0 +1,103 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tonic::Streaming;
+
+use golem_api_grpc::proto::golem::workerexecutor::{
+    read_worker_file_response, ReadWorkerFileResponse,
+};
+use golem_service_base::model::{GolemError, GolemErrorRuntimeError, GolemErrorUnknown};
+
+/// The content of a file read from a worker's file system, streamed from the worker executor
+/// in chunks
+pub struct WorkerFileStream {
+    receiver: mpsc::Receiver<Result<Bytes, GolemError>>,
+    cancel: CancellationToken,
+}
+
+impl WorkerFileStream {
+    /// Forwards the remaining chunks of the streaming response, after its first chunk was
+    /// already consumed to check whether reading the file succeeded
+    pub fn new(first: Option<Vec<u8>>, streaming: Streaming<ReadWorkerFileResponse>) -> Self {
+        // Streaming is not Sync, so it is forwarded through a channel
+        let (sender, receiver) = mpsc::channel(32);
+        let mut streaming = streaming;
+
+        let cancel = CancellationToken::new();
+
+        tokio::spawn({
+            let cancel = cancel.clone();
+
+            let forward_loop = async move {
+                if let Some(first) = first {
+                    if sender.send(Ok(Bytes::from(first))).await.is_err() {
+                        return;
+                    }
+                }
+
+                while let Some(message) = streaming.next().await {
+                    let chunk = match message {
+                        Ok(ReadWorkerFileResponse {
+                            result: Some(read_worker_file_response::Result::SuccessChunk(chunk)),
+                        }) => Ok(Bytes::from(chunk)),
+                        Ok(ReadWorkerFileResponse {
+                            result: Some(read_worker_file_response::Result::Failure(err)),
+                        }) => Err(err.try_into().unwrap_or_else(|err| {
+                            GolemError::Unknown(GolemErrorUnknown { details: err })
+                        })),
+                        Ok(ReadWorkerFileResponse { .. }) => {
+                            Err(GolemError::Unknown(GolemErrorUnknown {
+                                details: "Empty response".to_string(),
+                            }))
+                        }
+                        Err(status) => Err(GolemError::RuntimeError(GolemErrorRuntimeError {
+                            details: status.to_string(),
+                        })),
+                    };
+                    let failed = chunk.is_err();
+                    if let Err(error) = sender.send(chunk).await {
+                        tracing::info!("Failed to forward WorkerFileStream: {error}");
+                        break;
+                    }
+                    if failed {
+                        break;
+                    }
+                }
+            };
+
+            async move {
+                tokio::select! {
+                    _ = cancel.cancelled() => {
+                        tracing::info!("WorkerFileStream cancelled");
+                    }
+                    _ = forward_loop => {}
+                };
+            }
+        });
+
+        Self { receiver, cancel }
+    }
+}
+
+impl Stream for WorkerFileStream {
+    type Item = Result<Bytes, GolemError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, GolemError>>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for WorkerFileStream {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
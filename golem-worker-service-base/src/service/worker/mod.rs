@@ -2,8 +2,16 @@ pub use connect_proxy::*;
 pub use connect_stream::*;
 pub use default::*;
 pub use error::*;
+pub use file_stream::*;
+pub use oplog::*;
+pub use promise::*;
+pub use scheduled::*;
 
 mod connect_proxy;
 mod connect_stream;
 mod default;
 mod error;
+mod file_stream;
+mod oplog;
+mod promise;
+mod scheduled;
@@ -0,0 +1,92 @@
+use golem_api_grpc::proto::golem::worker::{
+    CallingConvention as ProtoCallingConvention, OplogEntry,
+};
+use golem_common::model::CallingConvention;
+use golem_service_base::model::{Component, OplogEntryPayload, OplogEntryType, PublicOplogEntry};
+use golem_service_base::typechecker::TypeCheckOut;
+use golem_wasm_ast::analysis::{AnalysedFunctionParameter, AnalysedFunctionResult};
+use golem_wasm_rpc::json::get_json_from_typed_value;
+use golem_wasm_rpc::protobuf::Val as ProtoVal;
+use golem_wasm_rpc::TypeAnnotatedValue;
+
+/// A page of oplog entries as returned by the worker executors
+#[derive(Clone, Debug, Default)]
+pub struct OplogPage {
+    pub entries: Vec<OplogEntry>,
+    pub next: Option<u64>,
+    pub last_index: u64,
+}
+
+/// Converts an oplog entry to its public representation, decoding the function request and
+/// response payloads using the exported function types of the given component.
+///
+/// Payloads which cannot be decoded (for example because the function no longer exists in the
+/// given component version) are left empty.
+pub fn to_public_oplog_entry(entry: OplogEntry, component: &Component) -> PublicOplogEntry {
+    let entry_type: OplogEntryType = entry.entry_type().into();
+    let calling_convention = entry
+        .calling_convention
+        .and_then(|cc| ProtoCallingConvention::try_from(cc).ok())
+        .map(CallingConvention::from)
+        .unwrap_or(CallingConvention::Component);
+    let function_type = entry
+        .function_name
+        .as_ref()
+        .and_then(|name| component.metadata.function_by_name(name));
+
+    let request = match &function_type {
+        Some(function_type) if !entry.request.is_empty() => {
+            let expected_types = function_type
+                .parameters
+                .iter()
+                .map(|parameter| {
+                    let parameter: AnalysedFunctionParameter = parameter.clone().into();
+                    AnalysedFunctionResult {
+                        name: Some(parameter.name),
+                        typ: parameter.typ,
+                    }
+                })
+                .collect();
+            decode_payload(entry.request, expected_types, calling_convention)
+        }
+        _ => None,
+    };
+
+    let response = match &function_type {
+        Some(function_type) if !entry.response.is_empty() => {
+            let expected_types = function_type
+                .results
+                .iter()
+                .map(|result| result.clone().into())
+                .collect();
+            decode_payload(entry.response, expected_types, calling_convention)
+        }
+        _ => None,
+    };
+
+    PublicOplogEntry {
+        oplog_index: entry.oplog_index,
+        timestamp: entry.timestamp.unwrap_or_default().into(),
+        entry_type,
+        function_name: entry.function_name,
+        idempotency_key: entry.idempotency_key.map(|key| key.value),
+        request,
+        response,
+        details: entry.details,
+    }
+}
+
+pub(super) fn decode_payload(
+    values: Vec<ProtoVal>,
+    expected_types: Vec<AnalysedFunctionResult>,
+    calling_convention: CallingConvention,
+) -> Option<OplogEntryPayload> {
+    let typed_value: TypeAnnotatedValue = values
+        .validate_function_result(expected_types, calling_convention)
+        .ok()?;
+
+    Some(OplogEntryPayload {
+        json: get_json_from_typed_value(&typed_value),
+        wave: wasm_wave::to_string(&typed_value).ok(),
+    })
+}
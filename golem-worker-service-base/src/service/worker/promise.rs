@@ -0,0 +1,112 @@
+use golem_wasm_ast::analysis::AnalysedType;
+use golem_wasm_rpc::json::get_json_from_typed_value;
+use golem_wasm_rpc::TypeAnnotatedValue;
+use serde_json::Value;
+
+/// The value a promise gets completed with
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromisePayload {
+    Bytes(Vec<u8>),
+    Json(Value),
+    Wave(String),
+}
+
+impl PromisePayload {
+    /// Selects the payload from the fields of a complete request. The raw data is only used
+    /// if the value is not given as JSON or WAVE.
+    pub fn new(data: Vec<u8>, json: Option<Value>, wave: Option<String>) -> Result<Self, String> {
+        match (json, wave) {
+            (Some(_), Some(_)) => Err("Only one of json and wave can be given".to_string()),
+            (Some(_), None) | (None, Some(_)) if !data.is_empty() => {
+                Err("Data cannot be given along with a typed value".to_string())
+            }
+            (Some(json), None) => Ok(PromisePayload::Json(json)),
+            (None, Some(wave)) => Ok(PromisePayload::Wave(wave)),
+            (None, None) => Ok(PromisePayload::Bytes(data)),
+        }
+    }
+
+    /// WAVE values can only be parsed knowing the payload type of the promise
+    pub fn needs_payload_type(&self) -> bool {
+        matches!(self, PromisePayload::Wave(_))
+    }
+
+    /// Encodes the payload to the bytes delivered to the worker awaiting the promise. Typed
+    /// values are delivered as JSON, which the executor checks against the payload type of
+    /// the promise, just like raw data.
+    pub fn encode(self, payload_type: Option<&AnalysedType>) -> Result<Vec<u8>, String> {
+        match (self, payload_type) {
+            (PromisePayload::Bytes(data), _) => Ok(data),
+            (PromisePayload::Json(json), _) => Ok(json.to_string().into_bytes()),
+            (PromisePayload::Wave(_), None) => Err(
+                "The promise has no payload type, it can not be completed with a WAVE value"
+                    .to_string(),
+            ),
+            (PromisePayload::Wave(wave), Some(typ)) => {
+                let value: TypeAnnotatedValue = wasm_wave::from_str(typ, &wave)
+                    .map_err(|err| format!("Failed to parse wave value {wave}: {err:?}"))?;
+                Ok(get_json_from_typed_value(&value).to_string().into_bytes())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use golem_wasm_ast::analysis::AnalysedType;
+    use serde_json::json;
+
+    use super::PromisePayload;
+
+    fn record_type() -> AnalysedType {
+        AnalysedType::Record(vec![
+            ("name".to_string(), AnalysedType::Str),
+            ("count".to_string(), AnalysedType::U32),
+        ])
+    }
+
+    #[test]
+    fn bytes_are_delivered_as_is() {
+        let payload = PromisePayload::new(vec![1, 2, 3], None, None).unwrap();
+
+        assert_eq!(payload.encode(None), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn json_is_delivered_as_is() {
+        let value = json!({ "name": "x", "count": 2 });
+        let encoded = PromisePayload::Json(value.clone()).encode(None);
+
+        assert_eq!(encoded, Ok(value.to_string().into_bytes()));
+    }
+
+    #[test]
+    fn wave_is_delivered_as_json() {
+        let payload = PromisePayload::Wave("{name: \"x\", count: 2}".to_string());
+        let encoded = payload.encode(Some(&record_type())).unwrap();
+
+        let value: serde_json::Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(value, json!({ "name": "x", "count": 2 }));
+    }
+
+    #[test]
+    fn wave_values_need_a_payload_type() {
+        let payload = PromisePayload::new(vec![], None, Some("1".to_string())).unwrap();
+
+        assert!(payload.needs_payload_type());
+        assert!(payload.encode(None).is_err());
+    }
+
+    #[test]
+    fn wave_values_must_match_the_payload_type() {
+        let payload = PromisePayload::Wave("{name: 1}".to_string());
+
+        assert!(payload.encode(Some(&record_type())).is_err());
+    }
+
+    #[test]
+    fn only_one_representation_can_be_given() {
+        assert!(PromisePayload::new(vec![1], Some(json!(1)), None).is_err());
+        assert!(PromisePayload::new(vec![], Some(json!(1)), Some("1".to_string())).is_err());
+    }
+}
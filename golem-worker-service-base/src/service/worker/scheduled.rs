@@ -0,0 +1,44 @@
+use golem_api_grpc::proto::golem::worker::ScheduledInvocation as ProtoScheduledInvocation;
+use golem_common::model::{CallingConvention, ScheduledInvocationId};
+use golem_service_base::model::{Component, ScheduledInvocation};
+use golem_wasm_ast::analysis::{AnalysedFunctionParameter, AnalysedFunctionResult};
+
+use super::oplog::decode_payload;
+
+/// Converts a scheduled invocation to its public representation, decoding the function
+/// parameters using the exported function types of the given component.
+pub fn to_public_scheduled_invocation(
+    invocation: ProtoScheduledInvocation,
+    component: &Component,
+) -> Result<ScheduledInvocation, String> {
+    let id: ScheduledInvocationId = invocation.id.ok_or("Missing id")?.try_into()?;
+    let params = component
+        .metadata
+        .function_by_name(&invocation.function_name)
+        .and_then(|function_type| {
+            let expected_types = function_type
+                .parameters
+                .iter()
+                .map(|parameter| {
+                    let parameter: AnalysedFunctionParameter = parameter.clone().into();
+                    AnalysedFunctionResult {
+                        name: Some(parameter.name),
+                        typ: parameter.typ,
+                    }
+                })
+                .collect();
+            decode_payload(
+                invocation.input,
+                expected_types,
+                CallingConvention::Component,
+            )
+        });
+
+    Ok(ScheduledInvocation {
+        id,
+        scheduled_at: invocation.scheduled_at.unwrap_or_default().into(),
+        function_name: invocation.function_name,
+        params,
+        cron: invocation.cron,
+    })
+}
@@ -0,0 +1,361 @@
+use std::fmt::Display;
+
+use golem_wasm_ast::analysis::AnalysedType;
+
+// The static type of an expression. Mostly mirrors AnalysedType, with a few additions for
+// values whose exact type is only decided when a request is evaluated
+#[derive(Debug, Clone, PartialEq)]
+pub enum InferredType {
+    Bool,
+    S8,
+    U8,
+    S16,
+    U16,
+    S32,
+    U32,
+    S64,
+    U64,
+    F32,
+    F64,
+    Chr,
+    Str,
+    List(Box<InferredType>),
+    Tuple(Vec<InferredType>),
+    Record(Vec<(String, InferredType)>),
+    Flags(Vec<String>),
+    Enum(Vec<String>),
+    Option(Box<InferredType>),
+    Result {
+        ok: Option<Box<InferredType>>,
+        error: Option<Box<InferredType>>,
+    },
+    Variant(Vec<(String, Option<InferredType>)>),
+    Resource,
+    // A number literal, which can be passed as any numeric type it fits in
+    Number(NumberLiteral),
+    // Path, query and header values are typed as a number, boolean or string at runtime
+    Primitive,
+    // A record with arbitrary field names, such as the request headers
+    OpenRecord(Box<InferredType>),
+    // Only known at runtime, such as the request body
+    Unknown,
+    Unit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberLiteral {
+    Unsigned,
+    Signed,
+    Float,
+}
+
+impl InferredType {
+    // Whether the value can be turned into text, compared, or used as a worker name
+    pub fn is_primitive(&self) -> bool {
+        matches!(
+            self,
+            InferredType::Bool
+                | InferredType::S8
+                | InferredType::U8
+                | InferredType::S16
+                | InferredType::U16
+                | InferredType::S32
+                | InferredType::U32
+                | InferredType::S64
+                | InferredType::U64
+                | InferredType::F32
+                | InferredType::F64
+                | InferredType::Chr
+                | InferredType::Str
+                | InferredType::Number(_)
+                | InferredType::Primitive
+                | InferredType::Unknown
+        )
+    }
+
+    pub fn is_bool(&self) -> bool {
+        matches!(
+            self,
+            InferredType::Bool | InferredType::Primitive | InferredType::Unknown
+        )
+    }
+
+    // Whether a value of this type can be passed as a function argument of the expected type.
+    // Arguments are sent to the worker as JSON, so for example a string is accepted for
+    // an enum or a char, and a plain value is accepted for an option.
+    pub fn is_assignable_to(&self, expected: &AnalysedType) -> bool {
+        match (self, expected) {
+            (InferredType::Unknown, _) => true,
+            (InferredType::Primitive, expected) => matches!(
+                expected,
+                AnalysedType::Bool
+                    | AnalysedType::S8
+                    | AnalysedType::U8
+                    | AnalysedType::S16
+                    | AnalysedType::U16
+                    | AnalysedType::S32
+                    | AnalysedType::U32
+                    | AnalysedType::S64
+                    | AnalysedType::U64
+                    | AnalysedType::F32
+                    | AnalysedType::F64
+                    | AnalysedType::Chr
+                    | AnalysedType::Str
+                    | AnalysedType::Enum(_)
+            ),
+            (InferredType::Number(literal), expected) => match literal {
+                NumberLiteral::Unsigned => is_numeric(expected),
+                NumberLiteral::Signed => matches!(
+                    expected,
+                    AnalysedType::S8
+                        | AnalysedType::S16
+                        | AnalysedType::S32
+                        | AnalysedType::S64
+                        | AnalysedType::F32
+                        | AnalysedType::F64
+                ),
+                NumberLiteral::Float => {
+                    matches!(expected, AnalysedType::F32 | AnalysedType::F64)
+                }
+            },
+            (InferredType::Str, AnalysedType::Str | AnalysedType::Chr) => true,
+            (InferredType::Str, AnalysedType::Enum(cases)) => !cases.is_empty(),
+            (InferredType::Record(fields), AnalysedType::Record(expected_fields)) => {
+                expected_fields.iter().all(|(name, expected_type)| {
+                    match fields.iter().find(|(field_name, _)| field_name == name) {
+                        Some((_, typ)) => typ.is_assignable_to(expected_type),
+                        None => matches!(expected_type, AnalysedType::Option(_)),
+                    }
+                })
+            }
+            (InferredType::OpenRecord(_), AnalysedType::Record(_)) => true,
+            (InferredType::List(inner), AnalysedType::List(expected_inner)) => {
+                inner.is_assignable_to(expected_inner)
+            }
+            (InferredType::Tuple(items), AnalysedType::Tuple(expected_items)) => {
+                items.len() == expected_items.len()
+                    && items
+                        .iter()
+                        .zip(expected_items)
+                        .all(|(item, expected_item)| item.is_assignable_to(expected_item))
+            }
+            (InferredType::Flags(flags), AnalysedType::Flags(expected_flags)) => {
+                flags.iter().all(|flag| expected_flags.contains(flag))
+            }
+            (InferredType::Option(inner), AnalysedType::Option(expected_inner)) => {
+                inner.is_assignable_to(expected_inner)
+            }
+            (actual, AnalysedType::Option(expected_inner)) => {
+                actual.is_assignable_to(expected_inner)
+            }
+            (
+                InferredType::Result { ok, error },
+                AnalysedType::Result {
+                    ok: expected_ok,
+                    error: expected_error,
+                },
+            ) => {
+                let ok_matches = match (ok, expected_ok) {
+                    (Some(ok), Some(expected_ok)) => ok.is_assignable_to(expected_ok),
+                    (Some(_), None) => false,
+                    (None, _) => true,
+                };
+                let error_matches = match (error, expected_error) {
+                    (Some(error), Some(expected_error)) => error.is_assignable_to(expected_error),
+                    (Some(_), None) => false,
+                    (None, _) => true,
+                };
+                ok_matches && error_matches
+            }
+            (actual, expected) => *actual == InferredType::from(expected),
+        }
+    }
+
+    // The common type of the branches of a conditional, or the elements of a list
+    pub fn unify(&self, other: &InferredType) -> InferredType {
+        if self == other {
+            self.clone()
+        } else {
+            match (self, other) {
+                (InferredType::Number(left), InferredType::Number(right)) => {
+                    if *left == NumberLiteral::Float || *right == NumberLiteral::Float {
+                        InferredType::Number(NumberLiteral::Float)
+                    } else {
+                        InferredType::Number(NumberLiteral::Signed)
+                    }
+                }
+                (InferredType::Option(left), InferredType::Option(right)) => {
+                    InferredType::Option(Box::new(left.unify(right)))
+                }
+                (InferredType::List(left), InferredType::List(right)) => {
+                    InferredType::List(Box::new(left.unify(right)))
+                }
+                (
+                    InferredType::Result {
+                        ok: left_ok,
+                        error: left_error,
+                    },
+                    InferredType::Result {
+                        ok: right_ok,
+                        error: right_error,
+                    },
+                ) => InferredType::Result {
+                    ok: unify_optional(left_ok, right_ok),
+                    error: unify_optional(left_error, right_error),
+                },
+                _ => InferredType::Unknown,
+            }
+        }
+    }
+}
+
+fn unify_optional(
+    left: &Option<Box<InferredType>>,
+    right: &Option<Box<InferredType>>,
+) -> Option<Box<InferredType>> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(Box::new(left.unify(right))),
+        (Some(typ), None) | (None, Some(typ)) => Some(typ.clone()),
+        (None, None) => None,
+    }
+}
+
+fn is_numeric(typ: &AnalysedType) -> bool {
+    matches!(
+        typ,
+        AnalysedType::S8
+            | AnalysedType::U8
+            | AnalysedType::S16
+            | AnalysedType::U16
+            | AnalysedType::S32
+            | AnalysedType::U32
+            | AnalysedType::S64
+            | AnalysedType::U64
+            | AnalysedType::F32
+            | AnalysedType::F64
+    )
+}
+
+impl From<&AnalysedType> for InferredType {
+    fn from(typ: &AnalysedType) -> Self {
+        match typ {
+            AnalysedType::Bool => InferredType::Bool,
+            AnalysedType::S8 => InferredType::S8,
+            AnalysedType::U8 => InferredType::U8,
+            AnalysedType::S16 => InferredType::S16,
+            AnalysedType::U16 => InferredType::U16,
+            AnalysedType::S32 => InferredType::S32,
+            AnalysedType::U32 => InferredType::U32,
+            AnalysedType::S64 => InferredType::S64,
+            AnalysedType::U64 => InferredType::U64,
+            AnalysedType::F32 => InferredType::F32,
+            AnalysedType::F64 => InferredType::F64,
+            AnalysedType::Chr => InferredType::Chr,
+            AnalysedType::Str => InferredType::Str,
+            AnalysedType::List(inner) => InferredType::List(Box::new(inner.as_ref().into())),
+            AnalysedType::Tuple(items) => {
+                InferredType::Tuple(items.iter().map(|item| item.into()).collect())
+            }
+            AnalysedType::Record(fields) => InferredType::Record(
+                fields
+                    .iter()
+                    .map(|(name, typ)| (name.clone(), typ.into()))
+                    .collect(),
+            ),
+            AnalysedType::Flags(flags) => InferredType::Flags(flags.clone()),
+            AnalysedType::Enum(cases) => InferredType::Enum(cases.clone()),
+            AnalysedType::Option(inner) => InferredType::Option(Box::new(inner.as_ref().into())),
+            AnalysedType::Result { ok, error } => InferredType::Result {
+                ok: ok.as_ref().map(|typ| Box::new(typ.as_ref().into())),
+                error: error.as_ref().map(|typ| Box::new(typ.as_ref().into())),
+            },
+            AnalysedType::Variant(cases) => InferredType::Variant(
+                cases
+                    .iter()
+                    .map(|(name, typ)| (name.clone(), typ.as_ref().map(|typ| typ.into())))
+                    .collect(),
+            ),
+            AnalysedType::Resource { .. } => InferredType::Resource,
+        }
+    }
+}
+
+// Written in WIT syntax, as that is how users see the types of the component
+impl Display for InferredType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn write_list<T: Display>(
+            f: &mut std::fmt::Formatter<'_>,
+            items: impl IntoIterator<Item = T>,
+        ) -> std::fmt::Result {
+            for (idx, item) in items.into_iter().enumerate() {
+                if idx != 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{item}")?;
+            }
+            Ok(())
+        }
+
+        match self {
+            InferredType::Bool => write!(f, "bool"),
+            InferredType::S8 => write!(f, "s8"),
+            InferredType::U8 => write!(f, "u8"),
+            InferredType::S16 => write!(f, "s16"),
+            InferredType::U16 => write!(f, "u16"),
+            InferredType::S32 => write!(f, "s32"),
+            InferredType::U32 => write!(f, "u32"),
+            InferredType::S64 => write!(f, "s64"),
+            InferredType::U64 => write!(f, "u64"),
+            InferredType::F32 => write!(f, "f32"),
+            InferredType::F64 => write!(f, "f64"),
+            InferredType::Chr => write!(f, "char"),
+            InferredType::Str => write!(f, "string"),
+            InferredType::List(inner) => write!(f, "list<{inner}>"),
+            InferredType::Tuple(items) => {
+                write!(f, "tuple<")?;
+                write_list(f, items)?;
+                write!(f, ">")
+            }
+            InferredType::Record(fields) => {
+                write!(f, "record {{ ")?;
+                write_list(f, fields.iter().map(|(name, typ)| format!("{name}: {typ}")))?;
+                write!(f, " }}")
+            }
+            InferredType::Flags(flags) => {
+                write!(f, "flags {{ ")?;
+                write_list(f, flags)?;
+                write!(f, " }}")
+            }
+            InferredType::Enum(cases) => {
+                write!(f, "enum {{ ")?;
+                write_list(f, cases)?;
+                write!(f, " }}")
+            }
+            InferredType::Option(inner) => write!(f, "option<{inner}>"),
+            InferredType::Result { ok, error } => match (ok, error) {
+                (Some(ok), Some(error)) => write!(f, "result<{ok}, {error}>"),
+                (Some(ok), None) => write!(f, "result<{ok}>"),
+                (None, Some(error)) => write!(f, "result<_, {error}>"),
+                (None, None) => write!(f, "result"),
+            },
+            InferredType::Variant(cases) => {
+                write!(f, "variant {{ ")?;
+                write_list(
+                    f,
+                    cases.iter().map(|(name, typ)| match typ {
+                        Some(typ) => format!("{name}({typ})"),
+                        None => name.clone(),
+                    }),
+                )?;
+                write!(f, " }}")
+            }
+            InferredType::Resource => write!(f, "resource"),
+            InferredType::Number(NumberLiteral::Float) => write!(f, "float literal"),
+            InferredType::Number(_) => write!(f, "integer literal"),
+            InferredType::Primitive => write!(f, "request value"),
+            InferredType::OpenRecord(_) => write!(f, "record"),
+            InferredType::Unknown => write!(f, "unknown"),
+            InferredType::Unit => write!(f, "unit"),
+        }
+    }
+}
@@ -0,0 +1,762 @@
+pub(crate) use inferred_type::*;
+mod inferred_type;
+
+use std::fmt::Display;
+
+use golem_service_base::model::{ComponentMetadata, FunctionResult};
+use golem_wasm_ast::analysis::AnalysedType;
+
+use crate::api_definition::http::FunctionDefinition;
+use crate::evaluator::stdlib::Builtin;
+use crate::expression::{
+    self, ArmPattern, ConstructorTypeName, Expr, InBuiltConstructorInner, InnerNumber, SourceSpan,
+};
+
+// The variables an expression can refer to, along with their types.
+// Mirrors what the evaluator puts in the EvaluationContext at runtime.
+#[derive(Debug, Clone, Default)]
+pub struct TypeScope {
+    variables: Vec<(String, InferredType)>,
+}
+
+impl TypeScope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, typ: InferredType) {
+        let name = name.into();
+        match self.variables.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, existing)) => *existing = typ,
+            None => self.variables.push((name, typ)),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&InferredType> {
+        self.variables
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, typ)| typ)
+    }
+
+    // The result of a worker function call is available as `worker.response`
+    fn set_worker_response(&mut self, typ: InferredType) {
+        let worker = match self.get("worker") {
+            Some(InferredType::Record(fields)) => {
+                let mut fields: Vec<(String, InferredType)> = fields
+                    .iter()
+                    .filter(|(name, _)| name != "response")
+                    .cloned()
+                    .collect();
+                fields.push(("response".to_string(), typ));
+                InferredType::Record(fields)
+            }
+            _ => InferredType::Record(vec![("response".to_string(), typ)]),
+        };
+
+        self.insert("worker", worker);
+    }
+}
+
+// The functions an expression can call, besides the standard library
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FunctionScope<'a> {
+    // The component of the worker of the binding. Without it, worker functions can't be called
+    pub component: Option<&'a ComponentMetadata>,
+    // The named workers of the binding, called as `<name>.<function>(..)`
+    pub workers: &'a [(&'a str, &'a ComponentMetadata)],
+    // The functions of the API definition
+    pub functions: &'a [FunctionDefinition],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeCheckError {
+    pub span: SourceSpan,
+    pub message: String,
+}
+
+impl Display for TypeCheckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at {})", self.message, self.span)
+    }
+}
+
+// Infers the type of the expression, and reports every reference to an unknown variable or
+// field, and every call to a function which is not in scope or is called with the wrong
+// arguments. When no component is given, worker functions can't be called.
+pub fn type_check(
+    expr: &Expr,
+    scope: &TypeScope,
+    functions: FunctionScope,
+) -> Result<InferredType, Vec<TypeCheckError>> {
+    let mut checker = TypeChecker {
+        functions,
+        errors: vec![],
+    };
+    let mut scope = scope.clone();
+
+    let inferred_type = checker.check(expr, &mut scope);
+
+    if checker.errors.is_empty() {
+        Ok(inferred_type)
+    } else {
+        let spans = expression::to_string_with_spans(expr).map(|(_, spans)| spans);
+
+        Err(checker
+            .errors
+            .into_iter()
+            .map(|(location, message)| TypeCheckError {
+                span: match &spans {
+                    Ok(spans) => spans.span_of(location),
+                    Err(_) => SourceSpan { start: 0, end: 0 },
+                },
+                message,
+            })
+            .collect())
+    }
+}
+
+struct TypeChecker<'a> {
+    functions: FunctionScope<'a>,
+    errors: Vec<(&'a Expr, String)>,
+}
+
+impl<'a> TypeChecker<'a> {
+    fn error(&mut self, location: &'a Expr, message: String) -> InferredType {
+        self.errors.push((location, message));
+        InferredType::Unknown
+    }
+
+    fn check(&mut self, expr: &'a Expr, scope: &mut TypeScope) -> InferredType {
+        match expr {
+            Expr::Identifier(name) => match scope.get(name) {
+                Some(typ) => typ.clone(),
+                None => self.error(expr, format!("Unknown variable `{name}`")),
+            },
+
+            Expr::SelectField(inner, field_name) => {
+                let inner_type = self.check(inner, scope);
+                self.select_field(expr, &inner_type, field_name)
+            }
+
+            Expr::SelectIndex(inner, index) => {
+                let inner_type = self.check(inner, scope);
+                match inner_type {
+                    InferredType::List(element_type) => *element_type,
+                    InferredType::Tuple(ref items) => match items.get(*index) {
+                        Some(item) => item.clone(),
+                        None => self.error(
+                            expr,
+                            format!("Index {index} is out of bounds for {inner_type}"),
+                        ),
+                    },
+                    InferredType::Unknown => InferredType::Unknown,
+                    other => self.error(
+                        expr,
+                        format!("Cannot select index {index} from a value of type {other}"),
+                    ),
+                }
+            }
+
+            Expr::Sequence(items) => {
+                let mut element_type: Option<InferredType> = None;
+                for item in items {
+                    let item_type = self.check(item, scope);
+                    element_type = Some(match element_type {
+                        Some(element_type) => element_type.unify(&item_type),
+                        None => item_type,
+                    });
+                }
+                InferredType::List(Box::new(element_type.unwrap_or(InferredType::Unknown)))
+            }
+
+            Expr::Record(fields) => InferredType::Record(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), self.check(value, scope)))
+                    .collect(),
+            ),
+
+            Expr::Tuple(items) => {
+                InferredType::Tuple(items.iter().map(|item| self.check(item, scope)).collect())
+            }
+
+            Expr::Literal(_) => InferredType::Str,
+
+            Expr::Number(number) => InferredType::Number(match number {
+                InnerNumber::UnsignedInteger(_) => NumberLiteral::Unsigned,
+                InnerNumber::Integer(_) => NumberLiteral::Signed,
+                InnerNumber::Float(_) => NumberLiteral::Float,
+            }),
+
+            Expr::Flags(flags) => InferredType::Flags(flags.clone()),
+
+            Expr::Boolean(_) => InferredType::Bool,
+
+            Expr::Concat(parts) => {
+                for part in parts {
+                    let part_type = self.check(part, scope);
+                    if !part_type.is_primitive() {
+                        self.error(
+                            part,
+                            format!("Cannot use a value of type {part_type} as part of a text"),
+                        );
+                    }
+                }
+                InferredType::Str
+            }
+
+            Expr::Multiple(exprs) => {
+                let mut result = InferredType::Unit;
+                for expr in exprs {
+                    result = self.check(expr, scope);
+                    // The evaluator makes the fields of intermediate records available as variables
+                    if let InferredType::Record(fields) = &result {
+                        for (name, typ) in fields {
+                            scope.insert(name.clone(), typ.clone());
+                        }
+                    }
+                }
+                result
+            }
+
+            Expr::Not(inner) => {
+                let inner_type = self.check(inner, scope);
+                if !inner_type.is_bool() {
+                    self.error(inner, format!("Expected a bool, found {inner_type}"));
+                }
+                InferredType::Bool
+            }
+
+            Expr::GreaterThan(left, right)
+            | Expr::GreaterThanOrEqualTo(left, right)
+            | Expr::LessThanOrEqualTo(left, right)
+            | Expr::EqualTo(left, right)
+            | Expr::LessThan(left, right) => {
+                for operand in [left, right] {
+                    let operand_type = self.check(operand, scope);
+                    if !operand_type.is_primitive() {
+                        self.error(
+                            operand,
+                            format!("Cannot compare a value of type {operand_type}"),
+                        );
+                    }
+                }
+                InferredType::Bool
+            }
+
+            Expr::Cond(predicate, left, right) => {
+                let predicate_type = self.check(predicate, scope);
+                if !predicate_type.is_bool() {
+                    self.error(
+                        predicate,
+                        format!("Expected a bool predicate, found {predicate_type}"),
+                    );
+                }
+                let left_type = self.check(left, scope);
+                let right_type = self.check(right, scope);
+                left_type.unify(&right_type)
+            }
+
+            Expr::PatternMatch(match_expr, arms) => {
+                let match_type = self.check(match_expr, scope);
+                let mut result: Option<InferredType> = None;
+
+                for arm in arms {
+                    let (pattern, arm_body) = &arm.0;
+                    let mut arm_scope = scope.clone();
+                    self.bind_pattern(expr, pattern, &match_type, &mut arm_scope);
+                    let arm_type = self.check(arm_body, &mut arm_scope);
+                    result = Some(match result {
+                        Some(result) => result.unify(&arm_type),
+                        None => arm_type,
+                    });
+                }
+
+                result.unwrap_or(InferredType::Unknown)
+            }
+
+            Expr::Option(Some(inner)) => InferredType::Option(Box::new(self.check(inner, scope))),
+            Expr::Option(None) => InferredType::Option(Box::new(InferredType::Unknown)),
+
+            Expr::Result(Ok(inner)) => InferredType::Result {
+                ok: Some(Box::new(self.check(inner, scope))),
+                error: None,
+            },
+            Expr::Result(Err(inner)) => InferredType::Result {
+                ok: None,
+                error: Some(Box::new(self.check(inner, scope))),
+            },
+
+            Expr::Let(name, value) => {
+                let value_type = self.check(value, scope);
+                scope.insert(name.clone(), value_type);
+                InferredType::Unit
+            }
+
+            Expr::Call(function_name, args) => {
+                let named_worker = function_name.split_once('.').and_then(|(name, function)| {
+                    self.functions
+                        .workers
+                        .iter()
+                        .find(|(worker, _)| *worker == name)
+                        .map(|(_, component)| (*component, function))
+                });
+
+                // Same precedence as at runtime: functions of the API definition, then of the
+                // worker, then of the standard library
+                if named_worker.is_none() {
+                    let functions = self.functions.functions;
+                    if let Some(function) = functions.iter().find(|f| f.name == *function_name) {
+                        return self.check_defined_call(expr, function, args, scope);
+                    }
+
+                    let exported = self.functions.component.is_some_and(|component| {
+                        component.function_by_name(function_name).is_some()
+                    });
+
+                    if !exported {
+                        if let Some(builtin) = Builtin::from_name(function_name) {
+                            return self.check_builtin_call(expr, builtin, args, scope);
+                        }
+                    }
+                }
+
+                let arg_types: Vec<(&'a Expr, InferredType)> = args
+                    .iter()
+                    .map(|arg| (arg, self.check(arg, scope)))
+                    .collect();
+
+                let (component, function_name) = match (named_worker, self.functions.component) {
+                    (Some((component, function)), _) => (component, function),
+                    (None, Some(component)) => (component, function_name.as_str()),
+                    (None, None) => {
+                        return self.error(
+                            expr,
+                            format!("Function `{function_name}` cannot be called in this expression, as the worker is not known yet"),
+                        )
+                    }
+                };
+
+                let function = match component.function_by_name(function_name) {
+                    Some(function) => function,
+                    None => {
+                        return self.error(
+                            expr,
+                            format!("Function `{function_name}` is not exported by the component"),
+                        )
+                    }
+                };
+
+                if function.parameters.len() != arg_types.len() {
+                    self.error(
+                        expr,
+                        format!(
+                            "Function `{function_name}` expects {} arguments, but {} were given",
+                            function.parameters.len(),
+                            arg_types.len()
+                        ),
+                    );
+                } else {
+                    for ((arg, arg_type), parameter) in arg_types.iter().zip(&function.parameters) {
+                        let expected: AnalysedType = parameter.typ.clone().into();
+                        if !arg_type.is_assignable_to(&expected) {
+                            self.error(
+                                *arg,
+                                format!(
+                                    "Argument `{}` of function `{function_name}` expects {}, found {arg_type}",
+                                    parameter.name,
+                                    InferredType::from(&expected)
+                                ),
+                            );
+                        }
+                    }
+                }
+
+                let result_type = function_result_type(&function.results);
+                if result_type != InferredType::Unit && named_worker.is_none() {
+                    scope.set_worker_response(result_type.clone());
+                }
+                result_type
+            }
+
+            Expr::Lambda(_, _) => self.error(
+                expr,
+                "A function can only be passed as an argument of map, filter or fold".to_string(),
+            ),
+        }
+    }
+
+    fn check_defined_call(
+        &mut self,
+        expr: &'a Expr,
+        function: &'a FunctionDefinition,
+        args: &'a [Expr],
+        scope: &mut TypeScope,
+    ) -> InferredType {
+        let arg_types: Vec<InferredType> = args.iter().map(|arg| self.check(arg, scope)).collect();
+
+        if function.params.len() != arg_types.len() {
+            return self.error(
+                expr,
+                format!(
+                    "Function `{}` expects {} arguments, but {} were given",
+                    function.name,
+                    function.params.len(),
+                    arg_types.len()
+                ),
+            );
+        }
+
+        self.defined_function_type(expr, function, arg_types)
+    }
+
+    // The type of the body of a function of the API definition, given the types of its arguments.
+    // The body is checked at every call, and its errors are reported at the call
+    fn defined_function_type(
+        &mut self,
+        location: &'a Expr,
+        function: &FunctionDefinition,
+        arg_types: Vec<InferredType>,
+    ) -> InferredType {
+        if self
+            .functions
+            .functions
+            .iter()
+            .filter(|defined| defined.name == function.name)
+            .count()
+            > 1
+        {
+            return self.error(
+                location,
+                format!("Function `{}` is defined more than once", function.name),
+            );
+        }
+
+        let visible = FunctionDefinition::visible_from(self.functions.functions, &function.name);
+
+        let mut scope = TypeScope::new();
+        for (param, typ) in function.params.iter().zip(arg_types) {
+            scope.insert(param.clone(), typ);
+        }
+
+        let result = type_check(
+            &function.body,
+            &scope,
+            FunctionScope {
+                functions: &visible,
+                ..FunctionScope::default()
+            },
+        );
+
+        match result {
+            Ok(result_type) => result_type,
+            Err(errors) => self.error(
+                location,
+                format!(
+                    "Invalid call of function `{}`: {}",
+                    function.name,
+                    errors
+                        .into_iter()
+                        .map(|error| error.message)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ),
+        }
+    }
+
+    fn check_builtin_call(
+        &mut self,
+        expr: &'a Expr,
+        builtin: Builtin,
+        args: &'a [Expr],
+        scope: &mut TypeScope,
+    ) -> InferredType {
+        if !builtin.is_higher_order() {
+            let arg_types: Vec<InferredType> =
+                args.iter().map(|arg| self.check(arg, scope)).collect();
+
+            return match builtin.result_type(&arg_types) {
+                Ok(result_type) => result_type,
+                Err(message) => self.error(expr, message),
+            };
+        }
+
+        if args.len() != builtin.arity() {
+            return self.error(
+                expr,
+                format!(
+                    "Function `{}` expects {} arguments, but {} were given",
+                    builtin.name(),
+                    builtin.arity(),
+                    args.len()
+                ),
+            );
+        }
+
+        let element_type = match self.check(&args[0], scope) {
+            InferredType::List(element_type) => *element_type,
+            InferredType::Unknown => InferredType::Unknown,
+            other => {
+                return self.error(
+                    &args[0],
+                    format!(
+                        "Function `{}` expects a list, found {other}",
+                        builtin.name()
+                    ),
+                )
+            }
+        };
+
+        match builtin {
+            Builtin::Map => InferredType::List(Box::new(self.check_function_arg(
+                builtin,
+                &args[1],
+                vec![element_type],
+                scope,
+            ))),
+
+            Builtin::Filter => {
+                let result_type =
+                    self.check_function_arg(builtin, &args[1], vec![element_type.clone()], scope);
+                if !result_type.is_bool() {
+                    self.error(
+                        &args[1],
+                        format!("The function passed to `filter` should return a bool, found {result_type}"),
+                    );
+                }
+                InferredType::List(Box::new(element_type))
+            }
+
+            // fold
+            _ => {
+                let init_type = self.check(&args[1], scope);
+                let result_type = self.check_function_arg(
+                    builtin,
+                    &args[2],
+                    vec![init_type.clone(), element_type],
+                    scope,
+                );
+                init_type.unify(&result_type)
+            }
+        }
+    }
+
+    // The type of the result of a function passed to map, filter or fold
+    fn check_function_arg(
+        &mut self,
+        builtin: Builtin,
+        function: &'a Expr,
+        arg_types: Vec<InferredType>,
+        scope: &TypeScope,
+    ) -> InferredType {
+        match function {
+            Expr::Lambda(params, body) => {
+                if params.len() != arg_types.len() {
+                    return self.error(
+                        function,
+                        format!(
+                            "The function passed to `{}` should take {} arguments, but takes {}",
+                            builtin.name(),
+                            arg_types.len(),
+                            params.len()
+                        ),
+                    );
+                }
+
+                let mut lambda_scope = scope.clone();
+                for (param, typ) in params.iter().zip(arg_types) {
+                    lambda_scope.insert(param.clone(), typ);
+                }
+                self.check(body, &mut lambda_scope)
+            }
+
+            Expr::Identifier(name) => {
+                let functions = self.functions.functions;
+                match functions.iter().find(|f| f.name == *name) {
+                    Some(defined) if defined.params.len() == arg_types.len() => {
+                        self.defined_function_type(function, defined, arg_types)
+                    }
+                    Some(defined) => self.error(
+                        function,
+                        format!(
+                            "The function passed to `{}` should take {} arguments, but `{}` takes {}",
+                            builtin.name(),
+                            arg_types.len(),
+                            defined.name,
+                            defined.params.len()
+                        ),
+                    ),
+                    None => self.error(
+                        function,
+                        format!("`{name}` is not a function of the API definition"),
+                    ),
+                }
+            }
+
+            _ => self.error(
+                function,
+                format!(
+                    "The last argument of `{}` should be a function",
+                    builtin.name()
+                ),
+            ),
+        }
+    }
+
+    fn select_field(
+        &mut self,
+        expr: &'a Expr,
+        inner_type: &InferredType,
+        field_name: &str,
+    ) -> InferredType {
+        match inner_type {
+            InferredType::Record(fields) => {
+                match fields.iter().find(|(name, _)| name == field_name) {
+                    Some((_, typ)) => typ.clone(),
+                    None => {
+                        let available = fields
+                            .iter()
+                            .map(|(name, _)| name.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        self.error(
+                            expr,
+                            format!("Field `{field_name}` does not exist, available fields: {available}"),
+                        )
+                    }
+                }
+            }
+            InferredType::OpenRecord(field_type) => *field_type.clone(),
+            InferredType::Unknown => InferredType::Unknown,
+            other => self.error(
+                expr,
+                format!("Cannot select field `{field_name}` from a value of type {other}"),
+            ),
+        }
+    }
+
+    // Adds the variables bound by the pattern to the scope of the match arm
+    fn bind_pattern(
+        &mut self,
+        match_expr: &'a Expr,
+        pattern: &ArmPattern,
+        match_type: &InferredType,
+        scope: &mut TypeScope,
+    ) {
+        match pattern {
+            ArmPattern::WildCard => {}
+
+            ArmPattern::As(name, inner) => {
+                scope.insert(name.clone(), match_type.clone());
+                self.bind_pattern(match_expr, inner, match_type, scope);
+            }
+
+            ArmPattern::Constructor(ConstructorTypeName::InBuiltConstructor(constructor), vars) => {
+                let inner_type = match (constructor, match_type) {
+                    (_, InferredType::Unknown) => Some(InferredType::Unknown),
+                    (InBuiltConstructorInner::Ok, InferredType::Result { ok, .. }) => Some(
+                        ok.as_ref()
+                            .map(|ok| *ok.clone())
+                            .unwrap_or(InferredType::Unit),
+                    ),
+                    (InBuiltConstructorInner::Err, InferredType::Result { error, .. }) => Some(
+                        error
+                            .as_ref()
+                            .map(|error| *error.clone())
+                            .unwrap_or(InferredType::Unit),
+                    ),
+                    (
+                        InBuiltConstructorInner::Some | InBuiltConstructorInner::None,
+                        InferredType::Option(inner),
+                    ) => Some(*inner.clone()),
+                    _ => None,
+                };
+
+                match inner_type {
+                    Some(inner_type) => {
+                        for var in vars {
+                            self.bind_pattern(match_expr, var, &inner_type, scope);
+                        }
+                    }
+                    None => {
+                        self.error(
+                            match_expr,
+                            format!(
+                                "Pattern `{constructor}` cannot match a value of type {match_type}"
+                            ),
+                        );
+                    }
+                }
+            }
+
+            ArmPattern::Constructor(ConstructorTypeName::Identifier(name), vars) => {
+                if vars.is_empty() {
+                    scope.insert(name.clone(), match_type.clone());
+                } else {
+                    let payload_type = match match_type {
+                        InferredType::Variant(cases) => {
+                            match cases.iter().find(|(case_name, _)| case_name == name) {
+                                Some((_, payload)) => payload.clone().unwrap_or(InferredType::Unit),
+                                None => self.error(
+                                    match_expr,
+                                    format!("Case `{name}` does not exist in {match_type}"),
+                                ),
+                            }
+                        }
+                        InferredType::Unknown => InferredType::Unknown,
+                        other => self.error(
+                            match_expr,
+                            format!("Pattern `{name}` cannot match a value of type {other}"),
+                        ),
+                    };
+
+                    for var in vars {
+                        self.bind_pattern(match_expr, var, &payload_type, scope);
+                    }
+                }
+            }
+
+            ArmPattern::Literal(literal) => match literal.as_ref() {
+                Expr::Identifier(name) => scope.insert(name.clone(), match_type.clone()),
+                Expr::Option(_) | Expr::Result(_) | Expr::Multiple(_) => {
+                    let pattern = ArmPattern::from_expr(*literal.clone());
+                    if !matches!(pattern, ArmPattern::Literal(_)) {
+                        self.bind_pattern(match_expr, &pattern, match_type, scope);
+                    }
+                }
+                // Matching against a value doesn't bind anything
+                _ => {}
+            },
+        }
+    }
+}
+
+// Mirrors how RefinedWorkerResponse turns the results of an invocation into a single value
+fn function_result_type(results: &[FunctionResult]) -> InferredType {
+    if results.is_empty() {
+        InferredType::Unit
+    } else if results.iter().all(|result| result.name.is_none()) {
+        match results {
+            [result] => {
+                let typ: AnalysedType = result.typ.clone().into();
+                InferredType::from(&typ)
+            }
+            _ => InferredType::Unknown,
+        }
+    } else {
+        InferredType::Record(
+            results
+                .iter()
+                .map(|result| {
+                    let typ: AnalysedType = result.typ.clone().into();
+                    (
+                        result.name.clone().unwrap_or_default(),
+                        InferredType::from(&typ),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
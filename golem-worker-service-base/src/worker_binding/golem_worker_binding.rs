@@ -1,10 +1,58 @@
 use bincode::{Decode, Encode};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use golem_common::model::ComponentId;
 
 use crate::expression::Expr;
 
+// What serves the requests of a route. Only wit-worker bindings invoke a worker, the others
+// are evaluated by the gateway from the request details alone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum RouteBinding {
+    WitWorker(GolemWorkerBinding),
+    // The response mapping evaluates to the response itself, such as a redirect
+    Static(StaticBinding),
+    // The response mapping evaluates to the path of an asset in the asset store
+    FileServer(StaticBinding),
+}
+
+impl RouteBinding {
+    pub fn worker_binding(&self) -> Option<&GolemWorkerBinding> {
+        match self {
+            RouteBinding::WitWorker(binding) => Some(binding),
+            RouteBinding::Static(_) | RouteBinding::FileServer(_) => None,
+        }
+    }
+
+    pub fn component_id(&self) -> Option<&ComponentId> {
+        self.worker_binding().map(|binding| &binding.component_id)
+    }
+
+    pub fn response(&self) -> &ResponseMapping {
+        match self {
+            RouteBinding::WitWorker(binding) => &binding.response,
+            RouteBinding::Static(binding) | RouteBinding::FileServer(binding) => &binding.response,
+        }
+    }
+}
+
+// Bindings without a type are bound to a worker, as they were before the other types existed
+pub(crate) fn deserialize_route_binding<'de, D>(deserializer: D) -> Result<RouteBinding, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut binding = serde_json::Value::deserialize(deserializer)?;
+
+    if let Some(fields) = binding.as_object_mut() {
+        fields
+            .entry("type")
+            .or_insert_with(|| serde_json::Value::String("wit-worker".to_string()));
+    }
+
+    RouteBinding::deserialize(binding).map_err(serde::de::Error::custom)
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 #[serde(rename_all = "camelCase")]
 pub struct GolemWorkerBinding {
@@ -12,6 +60,34 @@ pub struct GolemWorkerBinding {
     pub worker_name: Expr,
     pub idempotency_key: Option<Expr>,
     pub response: ResponseMapping,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workers: Vec<WorkerReference>,
+}
+
+impl GolemWorkerBinding {
+    pub fn component_ids(&self) -> impl Iterator<Item = &ComponentId> {
+        std::iter::once(&self.component_id)
+            .chain(self.workers.iter().map(|worker| &worker.component_id))
+    }
+
+    pub fn worker(&self, name: &str) -> Option<&WorkerReference> {
+        self.workers.iter().find(|worker| worker.name == name)
+    }
+}
+
+// Another worker the response mapping calls functions of, as in `users.get-user(...)` for a
+// worker named `users`. Its name is resolved from the request, like the one of the binding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerReference {
+    pub name: String,
+    pub component_id: ComponentId,
+    pub worker_name: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct StaticBinding {
+    pub response: ResponseMapping,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
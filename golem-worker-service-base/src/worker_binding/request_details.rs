@@ -1,4 +1,5 @@
 use crate::api_definition::http::{QueryInfo, VarInfo};
+use crate::http::RequestBody;
 use crate::merge::Merge;
 
 use crate::primitive::GetPrimitive;
@@ -19,8 +20,9 @@ impl RequestDetails {
         path_params: &HashMap<VarInfo, &str>,
         query_variable_values: &HashMap<String, String>,
         query_variable_names: &[QueryInfo],
-        request_body: &Value,
+        request_body: &RequestBody,
         headers: &HeaderMap,
+        auth_claims: Option<&Value>,
     ) -> Result<Self, Vec<String>> {
         Ok(Self::Http(TypedHttRequestDetails::from_input_http_request(
             path_params,
@@ -28,9 +30,20 @@ impl RequestDetails {
             query_variable_names,
             request_body,
             headers,
+            auth_claims,
         )?))
     }
 
+    // Same request with a different body, used for each message received on a stream
+    pub fn with_body(&self, request_body: &RequestBody) -> Result<Self, Vec<String>> {
+        match self {
+            RequestDetails::Http(http) => Ok(RequestDetails::Http(TypedHttRequestDetails {
+                typed_request_body: TypedRequestBody::from(request_body)?,
+                ..http.clone()
+            })),
+        }
+    }
+
     pub fn to_type_annotated_value(&self) -> TypeAnnotatedValue {
         match self {
             RequestDetails::Http(http) => http.clone().to_type_annotated_value(),
@@ -44,6 +57,7 @@ pub struct TypedHttRequestDetails {
     pub typed_request_body: TypedRequestBody,
     pub typed_query_values: TypedQueryKeyValues,
     pub typed_header_values: TypedHeaderValues,
+    pub typed_auth_claims: Option<TypedAuthClaims>,
 }
 
 impl TypedHttRequestDetails {
@@ -61,37 +75,50 @@ impl TypedHttRequestDetails {
         let typed_query_values: TypeAnnotatedValue = self.typed_query_values.clone().0.into();
         let merged_type_annotated_value = typed_path_values.merge(&typed_query_values).clone();
 
-        TypeAnnotatedValue::Record {
-            typ: vec![
-                (
-                    "path".to_string(),
-                    AnalysedType::from(&merged_type_annotated_value),
-                ),
-                ("body".to_string(), (&self.typed_request_body.0).into()),
-                (
-                    "headers".to_string(),
-                    self.typed_header_values.0.clone().into(),
-                ),
-            ],
-            value: vec![
-                ("path".to_string(), merged_type_annotated_value),
-                ("body".to_string(), self.typed_request_body.clone().0),
-                (
-                    "headers".to_string(),
-                    self.typed_header_values.clone().0.into(),
-                ),
-            ],
+        let mut typ = vec![
+            (
+                "path".to_string(),
+                AnalysedType::from(&merged_type_annotated_value),
+            ),
+            ("body".to_string(), (&self.typed_request_body.0).into()),
+            (
+                "headers".to_string(),
+                self.typed_header_values.0.clone().into(),
+            ),
+        ];
+
+        let mut value = vec![
+            ("path".to_string(), merged_type_annotated_value),
+            ("body".to_string(), self.typed_request_body.clone().0),
+            (
+                "headers".to_string(),
+                self.typed_header_values.clone().0.into(),
+            ),
+        ];
+
+        if let Some(auth_claims) = &self.typed_auth_claims {
+            let auth = TypeAnnotatedValue::Record {
+                typ: vec![("claims".to_string(), (&auth_claims.0).into())],
+                value: vec![("claims".to_string(), auth_claims.0.clone())],
+            };
+
+            typ.push(("auth".to_string(), (&auth).into()));
+            value.push(("auth".to_string(), auth));
         }
+
+        TypeAnnotatedValue::Record { typ, value }
     }
 
     fn from_input_http_request(
         path_params: &HashMap<VarInfo, &str>,
         query_variable_values: &HashMap<String, String>,
         query_variable_names: &[QueryInfo],
-        request_body: &Value,
+        request_body: &RequestBody,
         headers: &HeaderMap,
+        auth_claims: Option<&Value>,
     ) -> Result<Self, Vec<String>> {
         let request_body = TypedRequestBody::from(request_body)?;
+        let auth_claims = auth_claims.map(TypedAuthClaims::from).transpose()?;
         let path_params = TypedPathKeyValues::from(path_params);
         let query_params = TypedQueryKeyValues::from(query_variable_values, query_variable_names)?;
         let header_params = TypedHeaderValues::from(headers)?;
@@ -101,6 +128,7 @@ impl TypedHttRequestDetails {
             typed_request_body: request_body,
             typed_query_values: query_params,
             typed_header_values: header_params,
+            typed_auth_claims: auth_claims,
         })
     }
 }
@@ -175,12 +203,54 @@ impl TypedHeaderValues {
 pub struct TypedRequestBody(TypeAnnotatedValue);
 
 impl TypedRequestBody {
-    fn from(request_body: &Value) -> Result<TypedRequestBody, Vec<String>> {
-        let inferred_type = infer_analysed_type(request_body);
-        let typed_value = get_typed_value_from_json(request_body, &inferred_type)?;
+    fn from(request_body: &RequestBody) -> Result<TypedRequestBody, Vec<String>> {
+        let typed_value = match request_body {
+            RequestBody::Json(json) => Self::from_json(json)?,
+            RequestBody::FormUrlEncoded(fields) => {
+                let mut form_fields = TypedKeyValueCollection::default();
+
+                for (name, value) in fields {
+                    form_fields.push(
+                        name.clone(),
+                        internal::get_typed_value_from_primitive(value),
+                    );
+                }
+
+                form_fields.into()
+            }
+            RequestBody::Multipart(fields) => {
+                let mut multipart_fields = TypedKeyValueCollection::default();
+
+                for field in fields {
+                    multipart_fields.push(
+                        field.name.clone(),
+                        internal::get_typed_multipart_field(field),
+                    );
+                }
+
+                multipart_fields.into()
+            }
+            RequestBody::Text(text) => TypeAnnotatedValue::Str(text.clone()),
+            RequestBody::Binary(bytes) => internal::get_typed_bytes(bytes),
+        };
 
         Ok(TypedRequestBody(typed_value))
     }
+
+    fn from_json(request_body: &Value) -> Result<TypeAnnotatedValue, Vec<String>> {
+        let inferred_type = infer_analysed_type(request_body);
+        get_typed_value_from_json(request_body, &inferred_type)
+    }
+}
+
+// Claims of the authenticated caller, only present for routes with a security requirement
+#[derive(Debug, Clone)]
+pub struct TypedAuthClaims(TypeAnnotatedValue);
+
+impl TypedAuthClaims {
+    fn from(auth_claims: &Value) -> Result<TypedAuthClaims, Vec<String>> {
+        Ok(TypedAuthClaims(TypedRequestBody::from_json(auth_claims)?))
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -228,7 +298,9 @@ pub struct TypedKeyValue {
 
 mod internal {
 
+    use crate::http::MultipartField;
     use crate::primitive::{Number, Primitive};
+    use golem_wasm_ast::analysis::AnalysedType;
     use golem_wasm_rpc::TypeAnnotatedValue;
 
     pub(crate) fn get_typed_value_from_primitive(value: impl AsRef<str>) -> TypeAnnotatedValue {
@@ -243,4 +315,58 @@ mod internal {
             Primitive::Bool(value) => TypeAnnotatedValue::Bool(value),
         }
     }
+
+    // Text fields of a multipart body are typed like form fields, while file uploads become
+    // a record holding the file name, the content type and the raw data
+    pub(crate) fn get_typed_multipart_field(field: &MultipartField) -> TypeAnnotatedValue {
+        match (&field.file_name, std::str::from_utf8(&field.data)) {
+            (None, Ok(text)) => get_typed_value_from_primitive(text),
+            _ => TypeAnnotatedValue::Record {
+                typ: vec![
+                    (
+                        "file-name".to_string(),
+                        AnalysedType::Option(Box::new(AnalysedType::Str)),
+                    ),
+                    (
+                        "content-type".to_string(),
+                        AnalysedType::Option(Box::new(AnalysedType::Str)),
+                    ),
+                    (
+                        "data".to_string(),
+                        AnalysedType::List(Box::new(AnalysedType::U8)),
+                    ),
+                ],
+                value: vec![
+                    (
+                        "file-name".to_string(),
+                        get_typed_optional_string(&field.file_name),
+                    ),
+                    (
+                        "content-type".to_string(),
+                        get_typed_optional_string(&field.content_type),
+                    ),
+                    ("data".to_string(), get_typed_bytes(&field.data)),
+                ],
+            },
+        }
+    }
+
+    pub(crate) fn get_typed_bytes(bytes: &[u8]) -> TypeAnnotatedValue {
+        TypeAnnotatedValue::List {
+            typ: AnalysedType::U8,
+            values: bytes
+                .iter()
+                .map(|byte| TypeAnnotatedValue::U8(*byte))
+                .collect(),
+        }
+    }
+
+    fn get_typed_optional_string(value: &Option<String>) -> TypeAnnotatedValue {
+        TypeAnnotatedValue::Option {
+            typ: AnalysedType::Str,
+            value: value
+                .as_ref()
+                .map(|value| Box::new(TypeAnnotatedValue::Str(value.clone()))),
+        }
+    }
 }
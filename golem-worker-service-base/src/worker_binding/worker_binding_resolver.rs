@@ -1,14 +1,21 @@
-use crate::api_definition::http::{HttpApiDefinition, VarInfo};
+use crate::api_definition::http::{
+    FunctionDefinition, HttpApiDefinition, RateLimit, StreamBinding, VarInfo,
+};
 use crate::evaluator::{
     DefaultEvaluator, EvaluationContext, EvaluationError, EvaluationResult, MetadataFetchError,
+    NamedWorker,
 };
 use crate::evaluator::{Evaluator, WorkerMetadataFetcher};
+use crate::expression::Expr;
 use crate::http::http_request::router;
 use crate::http::router::RouterPattern;
-use crate::http::InputHttpRequest;
+use crate::http::security::{authenticate, AuthClaims, AuthError};
+use crate::http::{InputHttpRequest, RequestBody};
 use crate::merge::Merge;
 use crate::primitive::GetPrimitive;
+use crate::service::jwks::JwksProvider;
 use async_trait::async_trait;
+use futures::future::{join, join_all};
 use golem_common::model::{ComponentId, IdempotencyKey};
 use golem_wasm_ast::analysis::AnalysedType;
 use golem_wasm_rpc::TypeAnnotatedValue;
@@ -18,7 +25,7 @@ use std::sync::Arc;
 
 use golem_service_base::model::{Id, WorkerId};
 
-use crate::worker_binding::{RequestDetails, ResponseMapping};
+use crate::worker_binding::{RequestDetails, ResponseMapping, RouteBinding};
 use crate::worker_bridge_execution::to_response::ToResponse;
 
 #[async_trait]
@@ -26,21 +33,48 @@ pub trait WorkerBindingResolver<ApiDefinition> {
     async fn resolve(
         &self,
         api_specification: &ApiDefinition,
-    ) -> Result<ResolvedWorkerBinding, WorkerBindingResolutionError>;
+        jwks_provider: &(dyn JwksProvider + Sync + Send),
+    ) -> Result<ResolvedBinding, WorkerBindingResolutionError>;
 }
 
 #[derive(Debug)]
-pub struct WorkerBindingResolutionError(pub String);
+pub enum WorkerBindingResolutionError {
+    Message(String),
+    Auth(AuthError),
+}
 
 impl<A: AsRef<str>> From<A> for WorkerBindingResolutionError {
     fn from(message: A) -> Self {
-        WorkerBindingResolutionError(message.as_ref().to_string())
+        WorkerBindingResolutionError::Message(message.as_ref().to_string())
     }
 }
 
 impl Display for WorkerBindingResolutionError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Worker binding resolution error: {}", self.0)
+        match self {
+            WorkerBindingResolutionError::Message(message) => {
+                write!(f, "Worker binding resolution error: {}", message)
+            }
+            WorkerBindingResolutionError::Auth(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ResolvedBinding {
+    Worker(ResolvedWorkerBinding),
+    Static(ResolvedStaticBinding),
+    FileServer(ResolvedStaticBinding),
+}
+
+impl ResolvedBinding {
+    pub fn rate_limit(&self) -> Option<&ResolvedRateLimit> {
+        match self {
+            ResolvedBinding::Worker(binding) => binding.rate_limit.as_ref(),
+            ResolvedBinding::Static(binding) | ResolvedBinding::FileServer(binding) => {
+                binding.rate_limit.as_ref()
+            }
+        }
     }
 }
 
@@ -49,6 +83,85 @@ pub struct ResolvedWorkerBinding {
     pub worker_detail: WorkerDetail,
     pub request_details: RequestDetails,
     pub response_mapping: ResponseMapping,
+    pub rate_limit: Option<ResolvedRateLimit>,
+    pub stream: Option<StreamBinding>,
+    // The other workers of the binding, by the name they are called with
+    pub workers: Vec<(String, WorkerDetail)>,
+    pub functions: Vec<FunctionDefinition>,
+}
+
+// Binding of a route served by the gateway itself, the response mapping being evaluated
+// against the request details only
+#[derive(Debug, Clone)]
+pub struct ResolvedStaticBinding {
+    pub request_details: RequestDetails,
+    pub response_mapping: ResponseMapping,
+    pub rate_limit: Option<ResolvedRateLimit>,
+    pub functions: Vec<FunctionDefinition>,
+}
+
+impl ResolvedStaticBinding {
+    // There is no worker to call, any function call in the response mapping fails
+    pub async fn evaluate(&self) -> Result<EvaluationResult, EvaluationError> {
+        let context = EvaluationContext::from_request_data(&self.request_details)
+            .with_functions(self.functions.clone());
+
+        DefaultEvaluator::noop()
+            .evaluate(&self.response_mapping.0, &context)
+            .await
+    }
+
+    pub async fn execute<R>(&self) -> R
+    where
+        EvaluationResult: ToResponse<R>,
+        EvaluationError: ToResponse<R>,
+    {
+        match self.evaluate().await {
+            Ok(result) => result.to_response(&self.request_details),
+            Err(err) => err.to_response(&self.request_details),
+        }
+    }
+
+    // Path of the asset served by a file server binding
+    pub async fn asset_path(&self) -> Result<String, String> {
+        let path = self
+            .evaluate()
+            .await
+            .map_err(|err| err.to_string())?
+            .get_primitive()
+            .ok_or("Asset path is not a string".to_string())?
+            .as_string();
+
+        Ok(path.trim_start_matches('/').to_string())
+    }
+}
+
+// Rate limit of the matched route, applied before the worker is invoked
+#[derive(Debug, Clone)]
+pub struct ResolvedRateLimit {
+    // Identifies the route across api definitions and their versions
+    pub route: String,
+    // Value of the key expression, None if callers are identified by their IP address
+    pub caller: Option<String>,
+    pub limit: RateLimit,
+}
+
+impl ResolvedRateLimit {
+    pub fn new(
+        api_definition: &HttpApiDefinition,
+        route: &str,
+        limit: &RateLimit,
+        caller: Option<String>,
+    ) -> Self {
+        ResolvedRateLimit {
+            route: format!(
+                "{}:{}:{}",
+                api_definition.id.0, api_definition.version.0, route
+            ),
+            caller,
+            limit: limit.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -59,6 +172,15 @@ pub struct WorkerDetail {
 }
 
 impl WorkerDetail {
+    pub fn worker_id(&self) -> Result<WorkerId, String> {
+        let worker_name = Id::try_from(self.worker_name.clone())?;
+
+        Ok(WorkerId {
+            component_id: self.component_id.clone(),
+            worker_name,
+        })
+    }
+
     pub fn to_type_annotated_value(&self) -> TypeAnnotatedValue {
         let mut required = TypeAnnotatedValue::Record {
             typ: vec![
@@ -100,6 +222,20 @@ impl WorkerDetail {
 }
 
 impl ResolvedWorkerBinding {
+    // Binding of a single message received on a stream, the message taking the place of
+    // the request body
+    pub fn with_message(&self, message: &RequestBody) -> Result<Self, String> {
+        let request_details = self
+            .request_details
+            .with_body(message)
+            .map_err(|err| format!("Invalid message {}", err.join(", ")))?;
+
+        Ok(ResolvedWorkerBinding {
+            request_details,
+            ..self.clone()
+        })
+    }
+
     pub async fn execute_with<R>(
         &self,
         evaluator: &Arc<dyn Evaluator + Sync + Send>,
@@ -110,21 +246,44 @@ impl ResolvedWorkerBinding {
         EvaluationError: ToResponse<R>,
         MetadataFetchError: ToResponse<R>,
     {
-        let worker_name = match Id::try_from(self.worker_detail.worker_name.clone()) {
-            Ok(worker_name) => worker_name,
-            Err(err) => {
-                return EvaluationError::Message(err.to_string()).to_response(&self.request_details)
-            }
+        let worker_id = match self.worker_detail.worker_id() {
+            Ok(worker_id) => worker_id,
+            Err(err) => return EvaluationError::Message(err).to_response(&self.request_details),
         };
 
-        let worker_id = WorkerId {
-            component_id: self.worker_detail.component_id.clone(),
-            worker_name,
-        };
+        let mut named_worker_ids = vec![];
 
-        let functions_available = worker_metadata_fetcher
-            .get_worker_metadata(&worker_id)
-            .await;
+        for (_, worker_detail) in &self.workers {
+            match worker_detail.worker_id() {
+                Ok(worker_id) => named_worker_ids.push(worker_id),
+                Err(err) => {
+                    return EvaluationError::Message(err).to_response(&self.request_details)
+                }
+            }
+        }
+
+        let (functions_available, named_worker_functions) = join(
+            worker_metadata_fetcher.get_worker_metadata(&worker_id),
+            join_all(
+                named_worker_ids
+                    .iter()
+                    .map(|worker_id| worker_metadata_fetcher.get_worker_metadata(worker_id)),
+            ),
+        )
+        .await;
+
+        let mut named_workers = vec![];
+
+        for ((name, worker_detail), functions) in self.workers.iter().zip(named_worker_functions) {
+            match functions {
+                Ok(analysed_functions) => named_workers.push(NamedWorker {
+                    name: name.clone(),
+                    worker_detail: worker_detail.clone(),
+                    analysed_functions,
+                }),
+                Err(err) => return err.to_response(&self.request_details),
+            }
+        }
 
         match functions_available {
             Ok(functions) => {
@@ -132,7 +291,9 @@ impl ResolvedWorkerBinding {
                     &self.worker_detail,
                     &self.request_details,
                     functions,
-                );
+                )
+                .with_workers(named_workers)
+                .with_functions(self.functions.clone());
 
                 let result = evaluator
                     .evaluate(&self.response_mapping.clone().0, &runtime)
@@ -153,23 +314,96 @@ impl WorkerBindingResolver<HttpApiDefinition> for InputHttpRequest {
     async fn resolve(
         &self,
         api_definition: &HttpApiDefinition,
-    ) -> Result<ResolvedWorkerBinding, WorkerBindingResolutionError> {
-        let default_evaluator = DefaultEvaluator::noop();
+        jwks_provider: &(dyn JwksProvider + Sync + Send),
+    ) -> Result<ResolvedBinding, WorkerBindingResolutionError> {
+        let route = self.route(api_definition)?;
+        let auth_claims = self
+            .authenticate_caller(api_definition, &route, jwks_provider)
+            .await?;
+
+        self.resolve_route(api_definition, &route, auth_claims.as_ref())
+            .await
+    }
+}
 
-        let api_request = self;
+impl InputHttpRequest {
+    // Finds the route matching the method and path of the request. Neither this nor the
+    // authentication of the caller look at the request body, so requests can be rejected
+    // before their body is read
+    pub fn route(
+        &self,
+        api_definition: &HttpApiDefinition,
+    ) -> Result<router::RouteEntry, WorkerBindingResolutionError> {
         let router = router::build(api_definition.routes.clone());
-        let path: Vec<&str> = RouterPattern::split(&api_request.input_path.base_path).collect();
+        let path: Vec<&str> = RouterPattern::split(&self.input_path.base_path).collect();
+
+        let route = router
+            .check_path(&self.req_method, &path)
+            .ok_or("Failed to resolve route")?;
+
+        Ok(route.clone())
+    }
+
+    // Authenticates the caller of a secured route. The verified claims are available to the
+    // worker name and idempotency key expressions, so this happens before anything is evaluated
+    pub async fn authenticate_caller(
+        &self,
+        api_definition: &HttpApiDefinition,
+        route: &router::RouteEntry,
+        jwks_provider: &(dyn JwksProvider + Sync + Send),
+    ) -> Result<Option<AuthClaims>, WorkerBindingResolutionError> {
+        match &route.security {
+            Some(route_security) => {
+                let scheme = api_definition
+                    .security_scheme(&route_security.scheme)
+                    .ok_or_else(|| {
+                        WorkerBindingResolutionError::Auth(AuthError::InvalidConfiguration(
+                            format!("Unknown security scheme {}", route_security.scheme),
+                        ))
+                    })?;
+
+                let auth_claims = authenticate(self, scheme, route_security, jwks_provider)
+                    .await
+                    .map_err(WorkerBindingResolutionError::Auth)?;
+
+                Ok(Some(auth_claims))
+            }
+            None => Ok(None),
+        }
+    }
+
+    // Resolves the binding of a route the caller is authenticated for
+    pub async fn resolve_route(
+        &self,
+        api_definition: &HttpApiDefinition,
+        route: &router::RouteEntry,
+        auth_claims: Option<&AuthClaims>,
+    ) -> Result<ResolvedBinding, WorkerBindingResolutionError> {
+        if route.security.is_some() && auth_claims.is_none() {
+            return Err(WorkerBindingResolutionError::Auth(
+                AuthError::InvalidConfiguration(
+                    "The caller of a secured route is not authenticated".to_string(),
+                ),
+            ));
+        }
+
+        let default_evaluator = DefaultEvaluator::noop();
+
+        let path: Vec<&str> = RouterPattern::split(&self.input_path.base_path).collect();
         let request_query_variables = self.input_path.query_components().unwrap_or_default();
         let request_body = &self.req_body;
         let headers = &self.headers;
+        let auth_claims = auth_claims.map(|auth_claims| &auth_claims.claims);
 
         let router::RouteEntry {
             path_params,
             query_params,
             binding,
-        } = router
-            .check_path(&api_request.req_method, &path)
-            .ok_or("Failed to resolve route")?;
+            rate_limit,
+            stream,
+            route,
+            ..
+        } = route;
 
         let zipped_path_params: HashMap<VarInfo, &str> = {
             path_params
@@ -184,22 +418,64 @@ impl WorkerBindingResolver<HttpApiDefinition> for InputHttpRequest {
             query_params,
             request_body,
             headers,
+            auth_claims,
         )
         .map_err(|err| format!("Failed to fetch input request details {}", err.join(", ")))?;
 
-        let request_evaluation_context = EvaluationContext::from_request_data(&request_details);
+        let request_evaluation_context = EvaluationContext::from_request_data(&request_details)
+            .with_functions(api_definition.functions.clone());
+
+        let rate_limit = if let Some(rate_limit) = rate_limit {
+            let caller = if let Some(expr) = &rate_limit.key {
+                let caller = default_evaluator
+                    .evaluate(expr, &request_evaluation_context)
+                    .await
+                    .map_err(|err| err.to_string())?
+                    .get_primitive()
+                    .ok_or("Rate limit key is not a string")?
+                    .as_string();
+
+                Some(caller)
+            } else {
+                None
+            };
+
+            Some(ResolvedRateLimit::new(
+                api_definition,
+                route,
+                rate_limit,
+                caller,
+            ))
+        } else {
+            None
+        };
 
-        let worker_name: String = default_evaluator
-            .evaluate(&binding.worker_name, &request_evaluation_context)
-            .await
-            .map_err(|err| err.to_string())?
-            .get_value()
-            .ok_or("Failed to evaluate worker name expression".to_string())?
-            .get_primitive()
-            .ok_or("Worker name is not a String".to_string())?
-            .as_string();
+        let binding = match binding {
+            RouteBinding::WitWorker(binding) => binding,
+            RouteBinding::Static(binding) => {
+                return Ok(ResolvedBinding::Static(ResolvedStaticBinding {
+                    request_details,
+                    response_mapping: binding.response.clone(),
+                    rate_limit,
+                    functions: api_definition.functions.clone(),
+                }))
+            }
+            RouteBinding::FileServer(binding) => {
+                return Ok(ResolvedBinding::FileServer(ResolvedStaticBinding {
+                    request_details,
+                    response_mapping: binding.response.clone(),
+                    rate_limit,
+                    functions: api_definition.functions.clone(),
+                }))
+            }
+        };
 
-        let component_id = &binding.component_id;
+        let worker_name = evaluate_worker_name(
+            &default_evaluator,
+            &binding.worker_name,
+            &request_evaluation_context,
+        )
+        .await?;
 
         let idempotency_key = if let Some(expr) = &binding.idempotency_key {
             let idempotency_key_value = default_evaluator
@@ -221,17 +497,59 @@ impl WorkerBindingResolver<HttpApiDefinition> for InputHttpRequest {
         };
 
         let worker_detail = WorkerDetail {
-            component_id: component_id.clone(),
+            component_id: binding.component_id.clone(),
             worker_name,
             idempotency_key,
         };
 
+        // Calls to the other workers are not retried with the idempotency key of the request
+        let mut workers = vec![];
+
+        for worker in &binding.workers {
+            let worker_name = evaluate_worker_name(
+                &default_evaluator,
+                &worker.worker_name,
+                &request_evaluation_context,
+            )
+            .await?;
+
+            let worker_detail = WorkerDetail {
+                component_id: worker.component_id.clone(),
+                worker_name,
+                idempotency_key: None,
+            };
+
+            workers.push((worker.name.clone(), worker_detail));
+        }
+
         let resolved_binding = ResolvedWorkerBinding {
             worker_detail,
             request_details,
             response_mapping: binding.response.clone(),
+            rate_limit,
+            stream: stream.clone(),
+            workers,
+            functions: api_definition.functions.clone(),
         };
 
-        Ok(resolved_binding)
+        Ok(ResolvedBinding::Worker(resolved_binding))
     }
 }
+
+async fn evaluate_worker_name(
+    evaluator: &DefaultEvaluator,
+    worker_name: &Expr,
+    request_evaluation_context: &EvaluationContext,
+) -> Result<String, WorkerBindingResolutionError> {
+    let worker_name = evaluator
+        .evaluate(worker_name, request_evaluation_context)
+        .await
+        .map_err(|err| err.to_string())?
+        .get_value()
+        .ok_or("Failed to evaluate worker name expression".to_string())?
+        .get_primitive()
+        .ok_or("Worker name is not a String".to_string())?
+        .as_string();
+
+    Ok(worker_name)
+}
@@ -5,9 +5,11 @@ use golem_common::model::{ComponentId, IdempotencyKey};
 mod content_type_mapper;
 mod refined_worker_response;
 pub mod to_response;
+mod worker_output_connector;
 mod worker_request_executor;
 
 pub use refined_worker_response::*;
+pub use worker_output_connector::*;
 pub use worker_request_executor::*;
 
 #[derive(PartialEq, Debug, Clone)]
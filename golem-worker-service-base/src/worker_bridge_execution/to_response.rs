@@ -1,8 +1,12 @@
+use crate::evaluator::getter::Getter;
+use crate::evaluator::path::Path;
 use crate::evaluator::{EvaluationError, EvaluationResult, MetadataFetchError};
 use crate::worker_binding::RequestDetails;
 
+use golem_wasm_rpc::json::get_json_from_typed_value;
 use http::StatusCode;
 use poem::Body;
+use serde_json::{json, Value};
 
 pub trait ToResponse<A> {
     fn to_response(&self, request_details: &RequestDetails) -> A;
@@ -40,6 +44,50 @@ impl ToResponse<poem::Response> for MetadataFetchError {
     }
 }
 
+// A single message sent to the client of a WebSocket or SSE route
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamMessage {
+    Result(Value),
+    Error(String),
+}
+
+impl StreamMessage {
+    pub fn to_json(&self) -> Value {
+        match self {
+            StreamMessage::Result(value) => value.clone(),
+            StreamMessage::Error(error) => json!({ "error": error }),
+        }
+    }
+}
+
+// Only the body of the response mapping is sent, as there is no status or headers per message
+impl ToResponse<StreamMessage> for EvaluationResult {
+    fn to_response(&self, _request_details: &RequestDetails) -> StreamMessage {
+        match self {
+            EvaluationResult::Value(type_annotated_value) => {
+                let body = type_annotated_value
+                    .get(&Path::from_key("body"))
+                    .unwrap_or_else(|_| type_annotated_value.clone());
+
+                StreamMessage::Result(get_json_from_typed_value(&body))
+            }
+            EvaluationResult::Unit => StreamMessage::Result(Value::Null),
+        }
+    }
+}
+
+impl ToResponse<StreamMessage> for EvaluationError {
+    fn to_response(&self, _request_details: &RequestDetails) -> StreamMessage {
+        StreamMessage::Error(format!("Error {}", self))
+    }
+}
+
+impl ToResponse<StreamMessage> for MetadataFetchError {
+    fn to_response(&self, _request_details: &RequestDetails) -> StreamMessage {
+        StreamMessage::Error(format!("Worker metadata fetch error {}", self))
+    }
+}
+
 mod internal {
     use crate::evaluator::{EvaluationError, EvaluationResult};
     use crate::primitive::{GetPrimitive, Primitive};
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use golem_service_base::model::WorkerId;
+
+use crate::service::worker::ConnectWorkerStream;
+use crate::worker_bridge_execution::WorkerRequestExecutorError;
+
+// Connects to the output (stdout, stderr and log calls) of a worker, for the routes
+// streaming it back to the client
+#[async_trait]
+pub trait WorkerOutputConnector {
+    async fn connect(
+        &self,
+        worker_id: &WorkerId,
+    ) -> Result<ConnectWorkerStream, WorkerRequestExecutorError>;
+}
+
+pub struct NoopWorkerOutputConnector;
+
+#[async_trait]
+impl WorkerOutputConnector for NoopWorkerOutputConnector {
+    async fn connect(
+        &self,
+        _worker_id: &WorkerId,
+    ) -> Result<ConnectWorkerStream, WorkerRequestExecutorError> {
+        Err(WorkerRequestExecutorError::from(
+            "NoopWorkerOutputConnector",
+        ))
+    }
+}
@@ -5,6 +5,7 @@ pub mod worker_connect;
 
 use crate::api::worker::WorkerApi;
 use crate::service::Services;
+use golem_worker_service_base::api::AssetApi;
 use golem_worker_service_base::api::CustomHttpRequestApi;
 use golem_worker_service_base::api::HealthcheckApi;
 use poem::endpoint::PrometheusExporter;
@@ -18,6 +19,7 @@ type ApiServices = (
     WorkerApi,
     register_api_definition::RegisterApiDefinitionApi,
     deploy_api_definition::ApiDeploymentApi,
+    AssetApi,
     HealthcheckApi,
 );
 
@@ -46,6 +48,11 @@ pub fn custom_request_route(services: Services) -> Route {
         services.worker_to_http_service,
         services.worker_metadata_fetcher,
         services.http_definition_lookup_service,
+        services.rate_limiter,
+        services.worker_output_connector,
+        services.asset_store,
+        services.request_body_limits,
+        services.jwks_provider,
     );
 
     Route::new().nest("/", custom_request_executor)
@@ -62,6 +69,10 @@ pub fn make_open_api_service(services: &Services) -> OpenApiService<ApiServices,
                 services.definition_service.clone(),
             ),
             deploy_api_definition::ApiDeploymentApi::new(services.deployment_service.clone()),
+            AssetApi::new(
+                services.asset_store.clone(),
+                services.asset_upload_limits.clone(),
+            ),
             HealthcheckApi,
         ),
         "Golem API",
@@ -10,8 +10,8 @@ use tracing::{error, info};
 use golem_service_base::api_tags::ApiTags;
 use golem_worker_service_base::api::ApiEndpointError;
 use golem_worker_service_base::api::HttpApiDefinition;
-use golem_worker_service_base::api_definition::http::get_api_definition;
 use golem_worker_service_base::api_definition::http::HttpApiDefinition as CoreHttpApiDefinition;
+use golem_worker_service_base::api_definition::http::{get_api_definition, get_openapi};
 use golem_worker_service_base::api_definition::{ApiDefinitionId, ApiVersion};
 use golem_worker_service_base::auth::{CommonNamespace, EmptyAuthCtx};
 use golem_worker_service_base::service::api_definition::ApiDefinitionService;
@@ -154,6 +154,49 @@ impl RegisterApiDefinitionApi {
         Ok(Json(value))
     }
 
+    #[oai(
+        path = "/:id/:version/openapi",
+        method = "get",
+        operation_id = "export_open_api"
+    )]
+    async fn export_open_api(
+        &self,
+        id: Path<ApiDefinitionId>,
+        version: Path<ApiVersion>,
+    ) -> Result<Json<JsonOpenApiDefinition>, ApiEndpointError> {
+        let api_definition_id = id.0;
+
+        let api_version = version.0;
+
+        info!(
+            "Export API definition as OpenAPI - id: {}, version: {}",
+            &api_definition_id, &api_version
+        );
+
+        let data = self
+            .definition_service
+            .get(
+                &api_definition_id,
+                &api_version,
+                CommonNamespace::default(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        let data = data.ok_or(ApiEndpointError::not_found(format!(
+            "Can't find api definition with id {api_definition_id}, and version {api_version}"
+        )))?;
+
+        let components = self
+            .definition_service
+            .get_components(&data, &EmptyAuthCtx {})
+            .await?;
+
+        let openapi = get_openapi(&data, &components).map_err(ApiEndpointError::internal)?;
+
+        Ok(Json(JsonOpenApiDefinition(openapi)))
+    }
+
     #[oai(
         path = "/:id/:version",
         method = "delete",
@@ -264,6 +307,9 @@ mod test {
             id: ApiDefinitionId("test".to_string()),
             version: ApiVersion("1.0".to_string()),
             routes: vec![],
+            security_schemes: vec![],
+            cors: None,
+            functions: vec![],
             draft: false,
         };
 
@@ -293,6 +339,9 @@ mod test {
             id: ApiDefinitionId("test".to_string()),
             version: ApiVersion("42.0".to_string()),
             routes: vec![],
+            security_schemes: vec![],
+            cors: None,
+            functions: vec![],
             draft: false,
         };
 
@@ -317,6 +366,9 @@ mod test {
             id: ApiDefinitionId("test".to_string()),
             version: ApiVersion("1.0".to_string()),
             routes: vec![],
+            security_schemes: vec![],
+            cors: None,
+            functions: vec![],
             draft: false,
         };
         let response = client
@@ -330,6 +382,9 @@ mod test {
             id: ApiDefinitionId("test".to_string()),
             version: ApiVersion("2.0".to_string()),
             routes: vec![],
+            security_schemes: vec![],
+            cors: None,
+            functions: vec![],
             draft: false,
         };
         let response = client
@@ -345,6 +400,45 @@ mod test {
         body.value().array().assert_len(2)
     }
 
+    #[tokio::test]
+    async fn export_openapi() {
+        let api = make_route();
+        let client = TestClient::new(api);
+
+        let definition = golem_worker_service_base::api_definition::http::HttpApiDefinition {
+            id: ApiDefinitionId("test".to_string()),
+            version: ApiVersion("1.0".to_string()),
+            routes: vec![],
+            security_schemes: vec![],
+            cors: None,
+            functions: vec![],
+            draft: false,
+        };
+        let response = client
+            .post("/v1/api/definitions")
+            .body_json(&definition)
+            .send()
+            .await;
+        response.assert_status_is_ok();
+
+        let response = client
+            .get("/v1/api/definitions/test/1.0/openapi")
+            .send()
+            .await;
+        response.assert_status_is_ok();
+        let body = response.json().await;
+        body.value()
+            .object()
+            .get("x-golem-api-definition-id")
+            .assert_string("test");
+
+        let response = client
+            .get("/v1/api/definitions/test/2.0/openapi")
+            .send()
+            .await;
+        response.assert_status(StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn decode_openapi_json() {
         let api = make_route();
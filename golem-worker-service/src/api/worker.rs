@@ -1,16 +1,19 @@
+use futures_util::TryStreamExt;
 use golem_common::model::{
-    CallingConvention, ComponentId, IdempotencyKey, ScanCursor, WorkerFilter,
+    CallingConvention, ComponentId, IdempotencyKey, ScanCursor, ScheduledInvocationId, WorkerFilter,
 };
 use golem_service_base::api_tags::ApiTags;
 use golem_worker_service_base::auth::EmptyAuthCtx;
+use poem::Body;
 use poem_openapi::param::{Header, Path, Query};
-use poem_openapi::payload::Json;
+use poem_openapi::payload::{Binary, Json};
 use poem_openapi::*;
 use std::str::FromStr;
 use tap::TapFallible;
 
 use golem_service_base::model::*;
 use golem_worker_service_base::api::WorkerApiBaseError;
+use golem_worker_service_base::service::worker::PromisePayload;
 
 use crate::empty_worker_metadata;
 use crate::service::{component::ComponentService, worker::WorkerService};
@@ -164,14 +167,24 @@ impl WorkerApi {
         params: Json<CompleteParameters>,
     ) -> Result<Json<bool>> {
         let worker_id = make_worker_id(component_id.0, worker_name.0)?;
-        let CompleteParameters { oplog_idx, data } = params.0;
+        let CompleteParameters {
+            oplog_idx,
+            data,
+            json,
+            wave,
+        } = params.0;
+        let payload = PromisePayload::new(data, json, wave).map_err(|error| {
+            WorkerApiBaseError::BadRequest(Json(ErrorsBody {
+                errors: vec![error],
+            }))
+        })?;
 
         let result = self
             .worker_service
             .complete_promise(
                 &worker_id,
                 oplog_idx,
-                data,
+                payload,
                 empty_worker_metadata(),
                 &EmptyAuthCtx {},
             )
@@ -339,6 +352,296 @@ impl WorkerApi {
 
         Ok(Json(UpdateWorkerResponse {}))
     }
+
+    #[oai(
+        path = "/:component_id/workers/:worker_name/oplog",
+        method = "get",
+        operation_id = "get_oplog"
+    )]
+    async fn get_oplog(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        from: Query<Option<u64>>,
+        count: Query<Option<u64>>,
+    ) -> Result<Json<GetOplogResponse>> {
+        let worker_id = make_worker_id(component_id.0, worker_name.0)?;
+
+        let result = self
+            .worker_service
+            .get_oplog(
+                &worker_id,
+                from.0.unwrap_or(1),
+                count.0.unwrap_or(50),
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(Json(result))
+    }
+
+    #[oai(
+        path = "/:component_id/workers/:worker_name/oplog/search",
+        method = "post",
+        operation_id = "search_oplog"
+    )]
+    async fn search_oplog(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        params: Json<SearchOplogRequest>,
+    ) -> Result<Json<GetOplogResponse>> {
+        let worker_id = make_worker_id(component_id.0, worker_name.0)?;
+        let cursor = params.cursor;
+        let count = params.count.unwrap_or(50);
+
+        let result = self
+            .worker_service
+            .search_oplog(
+                &worker_id,
+                params.0.into(),
+                cursor,
+                count,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(Json(result))
+    }
+
+    #[oai(
+        path = "/:component_id/workers/:worker_name/fork",
+        method = "post",
+        operation_id = "fork_worker"
+    )]
+    async fn fork_worker(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        params: Json<ForkWorkerRequest>,
+    ) -> Result<Json<ForkWorkerResponse>> {
+        let source_worker_id = make_worker_id(component_id.0.clone(), worker_name.0)?;
+        let target_worker_id = make_worker_id(component_id.0, params.0.target_worker_name)?;
+
+        self.worker_service
+            .fork(
+                &source_worker_id,
+                &target_worker_id,
+                params.0.oplog_index_cutoff,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(Json(ForkWorkerResponse {}))
+    }
+
+    #[oai(
+        path = "/:component_id/workers/:worker_name/revert",
+        method = "post",
+        operation_id = "revert_worker"
+    )]
+    async fn revert_worker(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        target: Json<RevertWorkerTarget>,
+    ) -> Result<Json<RevertWorkerResponse>> {
+        let worker_id = make_worker_id(component_id.0, worker_name.0)?;
+
+        self.worker_service
+            .revert(
+                &worker_id,
+                target.0.into(),
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(Json(RevertWorkerResponse {}))
+    }
+
+    #[oai(
+        path = "/:component_id/workers/:worker_name/invocations/:idempotency_key",
+        method = "delete",
+        operation_id = "cancel_invocation"
+    )]
+    async fn cancel_invocation(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        idempotency_key: Path<IdempotencyKey>,
+    ) -> Result<Json<CancelInvocationResponse>> {
+        let worker_id = make_worker_id(component_id.0, worker_name.0)?;
+
+        let status = self
+            .worker_service
+            .cancel_invocation(
+                &worker_id,
+                &idempotency_key.0,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(Json(CancelInvocationResponse { status }))
+    }
+
+    #[oai(
+        path = "/:component_id/workers/:worker_name/scheduled-invocations",
+        method = "post",
+        operation_id = "schedule_invocation"
+    )]
+    async fn schedule_invocation(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        function: Query<String>,
+        params: Json<ScheduleInvocationParameters>,
+    ) -> Result<Json<ScheduledInvocation>> {
+        let worker_id = make_worker_id(component_id.0, worker_name.0)?;
+        let schedule = params.0.schedule().map_err(|error| {
+            WorkerApiBaseError::BadRequest(Json(ErrorsBody {
+                errors: vec![error],
+            }))
+        })?;
+
+        let invocation = self
+            .worker_service
+            .schedule_invocation(
+                &worker_id,
+                function.0,
+                params.0.params,
+                schedule,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(Json(invocation))
+    }
+
+    #[oai(
+        path = "/:component_id/workers/:worker_name/scheduled-invocations",
+        method = "get",
+        operation_id = "get_scheduled_invocations"
+    )]
+    async fn get_scheduled_invocations(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+    ) -> Result<Json<GetScheduledInvocationsResponse>> {
+        let worker_id = make_worker_id(component_id.0, worker_name.0)?;
+
+        let invocations = self
+            .worker_service
+            .get_scheduled_invocations(&worker_id, empty_worker_metadata(), &EmptyAuthCtx {})
+            .await?;
+
+        Ok(Json(GetScheduledInvocationsResponse { invocations }))
+    }
+
+    #[oai(
+        path = "/:component_id/workers/:worker_name/scheduled-invocations/:schedule_id",
+        method = "delete",
+        operation_id = "cancel_scheduled_invocation"
+    )]
+    async fn cancel_scheduled_invocation(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        schedule_id: Path<ScheduledInvocationId>,
+    ) -> Result<Json<CancelScheduledInvocationResponse>> {
+        let worker_id = make_worker_id(component_id.0, worker_name.0)?;
+
+        let canceled = self
+            .worker_service
+            .cancel_scheduled_invocation(
+                &worker_id,
+                &schedule_id.0,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(Json(CancelScheduledInvocationResponse { canceled }))
+    }
+
+    #[oai(
+        path = "/:component_id/workers/:worker_name/files",
+        method = "get",
+        operation_id = "list_worker_directory"
+    )]
+    async fn list_worker_directory(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        path: Query<Option<String>>,
+    ) -> Result<Json<ListWorkerDirectoryResponse>> {
+        let worker_id = make_worker_id(component_id.0, worker_name.0)?;
+
+        let entries = self
+            .worker_service
+            .list_directory(
+                &worker_id,
+                path.0.as_deref().unwrap_or("/"),
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(Json(ListWorkerDirectoryResponse { entries }))
+    }
+
+    #[oai(
+        path = "/:component_id/workers/:worker_name/files/content",
+        method = "get",
+        operation_id = "read_worker_file"
+    )]
+    async fn read_worker_file(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        path: Query<String>,
+    ) -> Result<Binary<Body>> {
+        let worker_id = make_worker_id(component_id.0, worker_name.0)?;
+
+        let stream = self
+            .worker_service
+            .read_file(
+                &worker_id,
+                &path.0,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(Binary(Body::from_bytes_stream(stream.map_err(|e| {
+            std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
+        }))))
+    }
+
+    #[oai(
+        path = "/:component_id/workers/:worker_name/promises",
+        method = "get",
+        operation_id = "get_pending_promises"
+    )]
+    async fn get_pending_promises(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+    ) -> Result<Json<GetPendingPromisesResponse>> {
+        let worker_id = make_worker_id(component_id.0, worker_name.0)?;
+
+        let promises = self
+            .worker_service
+            .get_pending_promises(&worker_id, empty_worker_metadata(), &EmptyAuthCtx {})
+            .await?;
+
+        Ok(Json(GetPendingPromisesResponse { promises }))
+    }
 }
 
 fn make_worker_id(
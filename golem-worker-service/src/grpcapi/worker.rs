@@ -12,27 +12,47 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use futures::{Stream, StreamExt};
 use golem_api_grpc::proto::golem::common::{Empty, ErrorBody, ErrorsBody};
 use golem_api_grpc::proto::golem::worker::worker_service_server::WorkerService as GrpcWorkerService;
 use golem_api_grpc::proto::golem::worker::{
-    complete_promise_response, delete_worker_response, get_worker_metadata_response,
-    get_workers_metadata_response, interrupt_worker_response, invoke_and_await_response,
-    invoke_response, launch_new_worker_response, resume_worker_response, update_worker_response,
+    cancel_invocation_response, cancel_scheduled_invocation_response, complete_promise_response,
+    delete_worker_response, fork_worker_response, get_oplog_response,
+    get_pending_promises_response, get_scheduled_invocations_response,
+    get_worker_metadata_response, get_workers_metadata_response, interrupt_worker_response,
+    invoke_and_await_response, invoke_response, launch_new_worker_response,
+    list_worker_directory_response, read_worker_file_response, resume_worker_response,
+    revert_worker_response, schedule_invocation_response, search_oplog_response,
+    update_worker_response, CancelInvocationRequest, CancelInvocationResponse,
+    CancelInvocationStatus, CancelScheduledInvocationRequest, CancelScheduledInvocationResponse,
     CompletePromiseRequest, CompletePromiseResponse, ConnectWorkerRequest, DeleteWorkerRequest,
-    DeleteWorkerResponse, GetWorkerMetadataRequest, GetWorkerMetadataResponse,
-    GetWorkersMetadataRequest, GetWorkersMetadataResponse, GetWorkersMetadataSuccessResponse,
-    InterruptWorkerRequest, InterruptWorkerResponse, InvokeAndAwaitRequest, InvokeAndAwaitResponse,
-    InvokeRequest, InvokeResponse, LaunchNewWorkerRequest, LaunchNewWorkerResponse,
-    LaunchNewWorkerSuccessResponse, ResumeWorkerRequest, ResumeWorkerResponse, UpdateWorkerRequest,
-    UpdateWorkerResponse,
+    DeleteWorkerResponse, ForkWorkerRequest, ForkWorkerResponse, GetOplogRequest, GetOplogResponse,
+    GetOplogSuccessResponse, GetPendingPromisesRequest, GetPendingPromisesResponse,
+    GetPendingPromisesSuccessResponse, GetScheduledInvocationsRequest,
+    GetScheduledInvocationsResponse, GetScheduledInvocationsSuccessResponse,
+    GetWorkerMetadataRequest, GetWorkerMetadataResponse, GetWorkersMetadataRequest,
+    GetWorkersMetadataResponse, GetWorkersMetadataSuccessResponse, InterruptWorkerRequest,
+    InterruptWorkerResponse, InvokeAndAwaitRequest, InvokeAndAwaitResponse, InvokeRequest,
+    InvokeResponse, LaunchNewWorkerRequest, LaunchNewWorkerResponse,
+    LaunchNewWorkerSuccessResponse, ListWorkerDirectoryRequest, ListWorkerDirectoryResponse,
+    ListWorkerDirectorySuccessResponse, ReadWorkerFileRequest, ReadWorkerFileResponse,
+    ResumeWorkerRequest, ResumeWorkerResponse, RevertWorkerRequest, RevertWorkerResponse,
+    ScheduleInvocationRequest, ScheduleInvocationResponse, ScheduledInvocation, SearchOplogRequest,
+    SearchOplogResponse, SearchOplogSuccessResponse, UpdateWorkerRequest, UpdateWorkerResponse,
 };
 use golem_api_grpc::proto::golem::worker::{
-    worker_error, worker_execution_error, InvokeResult, WorkerError as GrpcWorkerError,
-    WorkerExecutionError, WorkerMetadata,
+    worker_error, worker_execution_error, InvokeResult, PendingPromise,
+    WorkerError as GrpcWorkerError, WorkerExecutionError, WorkerFileEntry, WorkerMetadata,
+};
+use golem_common::model::oplog::RevertWorkerTarget;
+use golem_common::model::{
+    ComponentVersion, InvocationSchedule, ScanCursor, ScheduledInvocationId, WorkerFilter, WorkerId,
 };
-use golem_common::model::{ComponentVersion, ScanCursor, WorkerFilter, WorkerId};
 use golem_worker_service_base::auth::EmptyAuthCtx;
-use golem_worker_service_base::service::worker::ConnectWorkerStream;
+use golem_worker_service_base::service::worker::{
+    ConnectWorkerStream, OplogPage, PromisePayload, WorkerFileStream,
+};
+use std::pin::Pin;
 use tap::TapFallible;
 use tonic::{Request, Response, Status};
 
@@ -220,6 +240,193 @@ impl GrpcWorkerService for WorkerGrpcApi {
             result: Some(response),
         }))
     }
+
+    async fn get_oplog(
+        &self,
+        request: Request<GetOplogRequest>,
+    ) -> Result<Response<GetOplogResponse>, Status> {
+        let response = match self.get_oplog(request.into_inner()).await {
+            Ok(page) => get_oplog_response::Result::Success(GetOplogSuccessResponse {
+                entries: page.entries,
+                next: page.next,
+                last_index: page.last_index,
+            }),
+            Err(error) => get_oplog_response::Result::Error(error),
+        };
+
+        Ok(Response::new(GetOplogResponse {
+            result: Some(response),
+        }))
+    }
+
+    async fn search_oplog(
+        &self,
+        request: Request<SearchOplogRequest>,
+    ) -> Result<Response<SearchOplogResponse>, Status> {
+        let response = match self.search_oplog(request.into_inner()).await {
+            Ok(page) => search_oplog_response::Result::Success(SearchOplogSuccessResponse {
+                entries: page.entries,
+                next: page.next,
+                last_index: page.last_index,
+            }),
+            Err(error) => search_oplog_response::Result::Error(error),
+        };
+
+        Ok(Response::new(SearchOplogResponse {
+            result: Some(response),
+        }))
+    }
+
+    async fn fork_worker(
+        &self,
+        request: Request<ForkWorkerRequest>,
+    ) -> Result<Response<ForkWorkerResponse>, Status> {
+        let response = match self.fork_worker(request.into_inner()).await {
+            Ok(()) => fork_worker_response::Result::Success(Empty {}),
+            Err(error) => fork_worker_response::Result::Error(error),
+        };
+
+        Ok(Response::new(ForkWorkerResponse {
+            result: Some(response),
+        }))
+    }
+
+    async fn revert_worker(
+        &self,
+        request: Request<RevertWorkerRequest>,
+    ) -> Result<Response<RevertWorkerResponse>, Status> {
+        let response = match self.revert_worker(request.into_inner()).await {
+            Ok(()) => revert_worker_response::Result::Success(Empty {}),
+            Err(error) => revert_worker_response::Result::Error(error),
+        };
+
+        Ok(Response::new(RevertWorkerResponse {
+            result: Some(response),
+        }))
+    }
+
+    async fn cancel_invocation(
+        &self,
+        request: Request<CancelInvocationRequest>,
+    ) -> Result<Response<CancelInvocationResponse>, Status> {
+        let response = match self.cancel_invocation(request.into_inner()).await {
+            Ok(status) => cancel_invocation_response::Result::Success(status.into()),
+            Err(error) => cancel_invocation_response::Result::Error(error),
+        };
+
+        Ok(Response::new(CancelInvocationResponse {
+            result: Some(response),
+        }))
+    }
+
+    async fn schedule_invocation(
+        &self,
+        request: Request<ScheduleInvocationRequest>,
+    ) -> Result<Response<ScheduleInvocationResponse>, Status> {
+        let response = match self.schedule_invocation(request.into_inner()).await {
+            Ok(invocation) => schedule_invocation_response::Result::Success(invocation),
+            Err(error) => schedule_invocation_response::Result::Error(error),
+        };
+
+        Ok(Response::new(ScheduleInvocationResponse {
+            result: Some(response),
+        }))
+    }
+
+    async fn get_scheduled_invocations(
+        &self,
+        request: Request<GetScheduledInvocationsRequest>,
+    ) -> Result<Response<GetScheduledInvocationsResponse>, Status> {
+        let response = match self.get_scheduled_invocations(request.into_inner()).await {
+            Ok(invocations) => get_scheduled_invocations_response::Result::Success(
+                GetScheduledInvocationsSuccessResponse { invocations },
+            ),
+            Err(error) => get_scheduled_invocations_response::Result::Error(error),
+        };
+
+        Ok(Response::new(GetScheduledInvocationsResponse {
+            result: Some(response),
+        }))
+    }
+
+    async fn cancel_scheduled_invocation(
+        &self,
+        request: Request<CancelScheduledInvocationRequest>,
+    ) -> Result<Response<CancelScheduledInvocationResponse>, Status> {
+        let response = match self.cancel_scheduled_invocation(request.into_inner()).await {
+            Ok(canceled) => cancel_scheduled_invocation_response::Result::Success(canceled),
+            Err(error) => cancel_scheduled_invocation_response::Result::Error(error),
+        };
+
+        Ok(Response::new(CancelScheduledInvocationResponse {
+            result: Some(response),
+        }))
+    }
+
+    async fn list_worker_directory(
+        &self,
+        request: Request<ListWorkerDirectoryRequest>,
+    ) -> Result<Response<ListWorkerDirectoryResponse>, Status> {
+        let response = match self.list_worker_directory(request.into_inner()).await {
+            Ok(entries) => list_worker_directory_response::Result::Success(
+                ListWorkerDirectorySuccessResponse { entries },
+            ),
+            Err(error) => list_worker_directory_response::Result::Error(error),
+        };
+
+        Ok(Response::new(ListWorkerDirectoryResponse {
+            result: Some(response),
+        }))
+    }
+
+    type ReadWorkerFileStream =
+        Pin<Box<dyn Stream<Item = Result<ReadWorkerFileResponse, Status>> + Send + 'static>>;
+
+    async fn read_worker_file(
+        &self,
+        request: Request<ReadWorkerFileRequest>,
+    ) -> Result<Response<Self::ReadWorkerFileStream>, Status> {
+        let stream: Self::ReadWorkerFileStream = match self
+            .read_worker_file(request.into_inner())
+            .await
+        {
+            Ok(stream) => Box::pin(stream.map(|chunk| {
+                let result = match chunk {
+                    Ok(chunk) => read_worker_file_response::Result::SuccessChunk(chunk.to_vec()),
+                    Err(error) => read_worker_file_response::Result::Error(GrpcWorkerError {
+                        error: Some(worker_error::Error::InternalError(error.into())),
+                    }),
+                };
+                Ok(ReadWorkerFileResponse {
+                    result: Some(result),
+                })
+            })),
+            Err(error) => Box::pin(futures::stream::once(async move {
+                Ok(ReadWorkerFileResponse {
+                    result: Some(read_worker_file_response::Result::Error(error)),
+                })
+            })),
+        };
+
+        Ok(Response::new(stream))
+    }
+
+    async fn get_pending_promises(
+        &self,
+        request: Request<GetPendingPromisesRequest>,
+    ) -> Result<Response<GetPendingPromisesResponse>, Status> {
+        let response =
+            match self.get_pending_promises(request.into_inner()).await {
+                Ok(promises) => get_pending_promises_response::Result::Success(
+                    GetPendingPromisesSuccessResponse { promises },
+                ),
+                Err(error) => get_pending_promises_response::Result::Error(error),
+            };
+
+        Ok(Response::new(GetPendingPromisesResponse {
+            result: Some(response),
+        }))
+    }
 }
 
 impl WorkerGrpcApi {
@@ -283,12 +490,20 @@ impl WorkerGrpcApi {
             .complete_parameters
             .ok_or_else(|| bad_request_error("Missing complete parameters"))?;
 
+        let json = parameters
+            .json
+            .map(|json| serde_json::from_str(&json))
+            .transpose()
+            .map_err(|err| bad_request_error(format!("Invalid json value: {err}")))?;
+        let payload = PromisePayload::new(parameters.data, json, parameters.wave)
+            .map_err(bad_request_error)?;
+
         let result = self
             .worker_service
             .complete_promise(
                 &worker_id,
                 parameters.oplog_idx,
-                parameters.data,
+                payload,
                 empty_worker_metadata(),
                 &EmptyAuthCtx {},
             )
@@ -455,6 +670,219 @@ impl WorkerGrpcApi {
 
         Ok(())
     }
+
+    async fn get_oplog(&self, request: GetOplogRequest) -> Result<OplogPage, GrpcWorkerError> {
+        let worker_id = make_crate_worker_id(request.worker_id)?;
+
+        let page = self
+            .worker_service
+            .get_oplog_proto(
+                &worker_id,
+                request.from_oplog_index,
+                request.count,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(page)
+    }
+
+    async fn search_oplog(
+        &self,
+        request: SearchOplogRequest,
+    ) -> Result<OplogPage, GrpcWorkerError> {
+        let worker_id = make_crate_worker_id(request.worker_id)?;
+
+        let page = self
+            .worker_service
+            .search_oplog_proto(
+                &worker_id,
+                request.filter.unwrap_or_default(),
+                request.cursor,
+                request.count,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(page)
+    }
+
+    async fn fork_worker(&self, request: ForkWorkerRequest) -> Result<(), GrpcWorkerError> {
+        let source_worker_id = make_crate_worker_id(request.source_worker_id)?;
+        let target_worker_id = make_crate_worker_id(request.target_worker_id)?;
+
+        self.worker_service
+            .fork(
+                &source_worker_id,
+                &target_worker_id,
+                request.oplog_index_cutoff,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn revert_worker(&self, request: RevertWorkerRequest) -> Result<(), GrpcWorkerError> {
+        let worker_id = make_crate_worker_id(request.worker_id)?;
+        let target: RevertWorkerTarget = request
+            .target
+            .ok_or_else(|| bad_request_error("Missing revert target"))?
+            .try_into()
+            .map_err(bad_request_error)?;
+
+        self.worker_service
+            .revert(
+                &worker_id,
+                target,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn cancel_invocation(
+        &self,
+        request: CancelInvocationRequest,
+    ) -> Result<CancelInvocationStatus, GrpcWorkerError> {
+        let worker_id = make_crate_worker_id(request.worker_id)?;
+        let idempotency_key = request
+            .idempotency_key
+            .ok_or_else(|| bad_request_error("Missing idempotency key"))?
+            .into();
+
+        let status = self
+            .worker_service
+            .cancel_invocation(
+                &worker_id,
+                &idempotency_key,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(status.into())
+    }
+
+    async fn schedule_invocation(
+        &self,
+        request: ScheduleInvocationRequest,
+    ) -> Result<ScheduledInvocation, GrpcWorkerError> {
+        let worker_id = make_crate_worker_id(request.worker_id)?;
+
+        let params = request
+            .invoke_parameters
+            .ok_or_else(|| bad_request_error("Missing invoke parameters"))?;
+        let schedule: InvocationSchedule = request
+            .schedule
+            .ok_or_else(|| bad_request_error("Missing schedule"))?
+            .try_into()
+            .map_err(bad_request_error)?;
+
+        let invocation = self
+            .worker_service
+            .schedule_invocation_proto(
+                &worker_id,
+                request.function,
+                params.params,
+                schedule,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(invocation)
+    }
+
+    async fn get_scheduled_invocations(
+        &self,
+        request: GetScheduledInvocationsRequest,
+    ) -> Result<Vec<ScheduledInvocation>, GrpcWorkerError> {
+        let worker_id = make_crate_worker_id(request.worker_id)?;
+
+        let invocations = self
+            .worker_service
+            .get_scheduled_invocations_proto(&worker_id, empty_worker_metadata(), &EmptyAuthCtx {})
+            .await?;
+
+        Ok(invocations)
+    }
+
+    async fn cancel_scheduled_invocation(
+        &self,
+        request: CancelScheduledInvocationRequest,
+    ) -> Result<bool, GrpcWorkerError> {
+        let worker_id = make_crate_worker_id(request.worker_id)?;
+        let id: ScheduledInvocationId = request
+            .id
+            .ok_or_else(|| bad_request_error("Missing scheduled invocation id"))?
+            .try_into()
+            .map_err(bad_request_error)?;
+
+        let canceled = self
+            .worker_service
+            .cancel_scheduled_invocation(&worker_id, &id, empty_worker_metadata(), &EmptyAuthCtx {})
+            .await?;
+
+        Ok(canceled)
+    }
+
+    async fn list_worker_directory(
+        &self,
+        request: ListWorkerDirectoryRequest,
+    ) -> Result<Vec<WorkerFileEntry>, GrpcWorkerError> {
+        let worker_id = make_crate_worker_id(request.worker_id)?;
+
+        let entries = self
+            .worker_service
+            .list_directory(
+                &worker_id,
+                &request.path,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(entries.into_iter().map(|entry| entry.into()).collect())
+    }
+
+    async fn read_worker_file(
+        &self,
+        request: ReadWorkerFileRequest,
+    ) -> Result<WorkerFileStream, GrpcWorkerError> {
+        let worker_id = make_crate_worker_id(request.worker_id)?;
+
+        let stream = self
+            .worker_service
+            .read_file(
+                &worker_id,
+                &request.path,
+                empty_worker_metadata(),
+                &EmptyAuthCtx {},
+            )
+            .await?;
+
+        Ok(stream)
+    }
+
+    async fn get_pending_promises(
+        &self,
+        request: GetPendingPromisesRequest,
+    ) -> Result<Vec<PendingPromise>, GrpcWorkerError> {
+        let worker_id = make_crate_worker_id(request.worker_id)?;
+
+        let promises = self
+            .worker_service
+            .get_pending_promises(&worker_id, empty_worker_metadata(), &EmptyAuthCtx {})
+            .await?;
+
+        Ok(promises.into_iter().map(|promise| promise.into()).collect())
+    }
 }
 
 fn make_worker_id(
@@ -545,6 +973,9 @@ fn error_to_status(error: GrpcWorkerError) -> Status {
                     "Promise Already Completed: Promise ID = {:?}",
                     err.promise_id
                 ),
+                worker_execution_error::Error::PromiseTimedOut(err) => {
+                    format!("Promise Timed Out: Promise ID = {:?}", err.promise_id)
+                }
                 worker_execution_error::Error::Interrupted(err) => format!(
                     "Interrupted: Recover Immediately = {}",
                     err.recover_immediately
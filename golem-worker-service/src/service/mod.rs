@@ -7,9 +7,11 @@ use crate::worker_bridge_request_executor::UnauthorisedWorkerRequestExecutor;
 
 use golem_worker_service_base::api_definition::http::HttpApiDefinition;
 
-use golem_worker_service_base::app_config::WorkerServiceBaseConfig;
+use golem_worker_service_base::app_config::{
+    AssetStoreConfig, RateLimitStoreConfig, WorkerServiceBaseConfig,
+};
 use golem_worker_service_base::auth::{CommonNamespace, EmptyAuthCtx};
-use golem_worker_service_base::http::InputHttpRequest;
+use golem_worker_service_base::http::{InputHttpRequest, RequestBodyLimits};
 use golem_worker_service_base::repo::api_definition_repo::{
     ApiDefinitionRepo, InMemoryRegistry, RedisApiRegistry,
 };
@@ -19,14 +21,23 @@ use golem_worker_service_base::service::api_definition::{
 use golem_worker_service_base::service::api_definition_lookup::ApiDefinitionLookup;
 use golem_worker_service_base::service::api_definition_validator::ApiDefinitionValidatorNoop;
 use golem_worker_service_base::service::api_definition_validator::ApiDefinitionValidatorService;
+use golem_worker_service_base::service::asset_store::{
+    AssetStore, AssetUploadLimits, FileSystemAssetStore, InMemoryAssetStore, S3AssetStore,
+};
 use golem_worker_service_base::service::component::{ComponentServiceNoop, RemoteComponentService};
 use golem_worker_service_base::service::http::http_api_definition_validator::{
     HttpApiDefinitionValidator, RouteValidationError,
 };
+use golem_worker_service_base::service::jwks::{ConfiguredJwksProvider, JwksProvider};
+use golem_worker_service_base::service::rate_limit::{
+    InMemoryRateLimiter, RateLimiter, RedisRateLimiter,
+};
 use golem_worker_service_base::service::worker::{
     WorkerRequestMetadata, WorkerServiceDefault, WorkerServiceNoOp,
 };
-use golem_worker_service_base::worker_bridge_execution::WorkerRequestExecutor;
+use golem_worker_service_base::worker_bridge_execution::{
+    WorkerOutputConnector, WorkerRequestExecutor,
+};
 
 use crate::worker_component_metadata_fetcher::DefaultWorkerComponentMetadataFetcher;
 use golem_worker_service_base::evaluator::WorkerMetadataFetcher;
@@ -60,6 +71,12 @@ pub struct Services {
     pub api_definition_validator_service: Arc<
         dyn ApiDefinitionValidatorService<HttpApiDefinition, RouteValidationError> + Sync + Send,
     >,
+    pub rate_limiter: Arc<dyn RateLimiter + Sync + Send>,
+    pub worker_output_connector: Arc<dyn WorkerOutputConnector + Sync + Send>,
+    pub asset_store: Arc<dyn AssetStore + Sync + Send>,
+    pub asset_upload_limits: AssetUploadLimits,
+    pub request_body_limits: RequestBodyLimits,
+    pub jwks_provider: Arc<dyn JwksProvider + Sync + Send>,
 }
 
 impl Services {
@@ -99,6 +116,10 @@ impl Services {
             UnauthorisedWorkerRequestExecutor::new(worker_service.clone()),
         );
 
+        let worker_output_connector: Arc<dyn WorkerOutputConnector + Sync + Send> = Arc::new(
+            UnauthorisedWorkerRequestExecutor::new(worker_service.clone()),
+        );
+
         let worker_metadata_fetcher: Arc<dyn WorkerMetadataFetcher + Sync + Send> = Arc::new(
             DefaultWorkerComponentMetadataFetcher::new(worker_service.clone()),
         );
@@ -129,6 +150,24 @@ impl Services {
 
         let api_definition_validator_service = Arc::new(HttpApiDefinitionValidator {});
 
+        let rate_limiter: Arc<dyn RateLimiter + Sync + Send> = match &config.rate_limit_store {
+            RateLimitStoreConfig::Redis => {
+                Arc::new(RedisRateLimiter::new(&config.redis).await.map_err(|e| {
+                    error!("RedisRateLimiter - init error: {}", e);
+                    format!("RedisRateLimiter - init error: {}", e)
+                })?)
+            }
+            RateLimitStoreConfig::InMemory(config) => Arc::new(InMemoryRateLimiter::new(config)),
+        };
+
+        let asset_store: Arc<dyn AssetStore + Sync + Send> = match &config.asset_store {
+            AssetStoreConfig::S3(config) => Arc::new(S3AssetStore::new(config).await),
+            AssetStoreConfig::LocalFileSystem(config) => {
+                Arc::new(FileSystemAssetStore::new(&config.root))
+            }
+            AssetStoreConfig::InMemory => Arc::new(InMemoryAssetStore::default()),
+        };
+
         let definition_service: Arc<
             dyn ApiDefinitionService<
                     EmptyAuthCtx,
@@ -152,6 +191,12 @@ impl Services {
             component_service,
             worker_metadata_fetcher,
             api_definition_validator_service,
+            rate_limiter,
+            worker_output_connector,
+            asset_store,
+            asset_upload_limits: config.asset_upload_limits.clone(),
+            request_body_limits: config.request_body_limits.clone(),
+            jwks_provider: Arc::new(ConfiguredJwksProvider::new(&config.jwks)),
         })
     }
 
@@ -201,10 +246,20 @@ impl Services {
             UnauthorisedWorkerRequestExecutor::new(worker_service.clone()),
         );
 
+        let worker_output_connector: Arc<dyn WorkerOutputConnector + Sync + Send> = Arc::new(
+            UnauthorisedWorkerRequestExecutor::new(worker_service.clone()),
+        );
+
         let worker_metadata_fetcher: Arc<dyn WorkerMetadataFetcher + Sync + Send> = Arc::new(
             DefaultWorkerComponentMetadataFetcher::new(worker_service.clone()),
         );
 
+        let rate_limiter: Arc<dyn RateLimiter + Sync + Send> =
+            Arc::new(InMemoryRateLimiter::new(&Default::default()));
+
+        let asset_store: Arc<dyn AssetStore + Sync + Send> =
+            Arc::new(InMemoryAssetStore::default());
+
         Services {
             worker_service,
             definition_service,
@@ -214,6 +269,12 @@ impl Services {
             component_service,
             worker_metadata_fetcher,
             api_definition_validator_service,
+            rate_limiter,
+            worker_output_connector,
+            asset_store,
+            asset_upload_limits: AssetUploadLimits::default(),
+            request_body_limits: RequestBodyLimits::default(),
+            jwks_provider: Arc::new(ConfiguredJwksProvider::new(&Default::default())),
         }
     }
 }
@@ -1,12 +1,16 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use golem_service_base::model::WorkerId;
 use golem_worker_service_base::auth::EmptyAuthCtx;
-use golem_worker_service_base::service::worker::WorkerService;
+use golem_worker_service_base::service::worker::{ConnectWorkerStream, WorkerService};
 use golem_worker_service_base::worker_bridge_execution::{
-    WorkerRequest, WorkerRequestExecutor, WorkerRequestExecutorError, WorkerResponse,
+    WorkerOutputConnector, WorkerRequest, WorkerRequestExecutor, WorkerRequestExecutorError,
+    WorkerResponse,
 };
 
+use crate::empty_worker_metadata;
+
 // The open source deviates from the proprietary codebase here, only in terms of authorisation
 pub struct UnauthorisedWorkerRequestExecutor {
     pub worker_service: Arc<dyn WorkerService<EmptyAuthCtx> + Sync + Send>,
@@ -28,6 +32,19 @@ impl WorkerRequestExecutor for UnauthorisedWorkerRequestExecutor {
     }
 }
 
+#[async_trait]
+impl WorkerOutputConnector for UnauthorisedWorkerRequestExecutor {
+    async fn connect(
+        &self,
+        worker_id: &WorkerId,
+    ) -> Result<ConnectWorkerStream, WorkerRequestExecutorError> {
+        self.worker_service
+            .connect(worker_id, empty_worker_metadata(), &EmptyAuthCtx {})
+            .await
+            .map_err(|e| e.to_string().into())
+    }
+}
+
 mod internal {
     use crate::empty_worker_metadata;
     use crate::worker_bridge_request_executor::UnauthorisedWorkerRequestExecutor;
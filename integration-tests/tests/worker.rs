@@ -15,6 +15,7 @@
 use crate::DEPS;
 use assert2::check;
 
+use golem_api_grpc::proto::golem::worker::CompleteParameters;
 use golem_test_framework::dsl::TestDsl;
 use golem_wasm_rpc::Value;
 use std::collections::{HashMap, HashSet};
@@ -498,3 +499,66 @@ async fn auto_update_on_idle_via_host_function() {
     check!(metadata.last_known_status.failed_updates.is_empty());
     check!(metadata.last_known_status.successful_updates.len() == 1);
 }
+
+#[tokio::test]
+#[tracing::instrument]
+async fn typed_promise() {
+    let component_id = DEPS.store_component("promise").await;
+    let worker_id = DEPS.start_worker(&component_id, "typed-promise-1").await;
+
+    let worker_id_clone = worker_id.clone();
+    let fiber = tokio::spawn(async move {
+        DEPS.invoke_and_await(&worker_id_clone, "run-typed", vec![])
+            .await
+    });
+
+    sleep(Duration::from_secs(10)).await;
+
+    let pending = DEPS.get_pending_promises(&worker_id).await.unwrap();
+    let oplog_idx = pending[0].promise_id.as_ref().unwrap().oplog_idx;
+
+    let invalid_json = DEPS
+        .complete_promise(
+            &worker_id,
+            CompleteParameters {
+                oplog_idx,
+                data: vec![],
+                json: Some(r#"{"name": 1}"#.to_string()),
+                wave: None,
+            },
+        )
+        .await;
+    let invalid_data = DEPS
+        .complete_promise(
+            &worker_id,
+            CompleteParameters {
+                oplog_idx,
+                data: vec![42],
+                json: None,
+                wave: None,
+            },
+        )
+        .await;
+    let valid_wave = DEPS
+        .complete_promise(
+            &worker_id,
+            CompleteParameters {
+                oplog_idx,
+                data: vec![],
+                json: None,
+                wave: Some(r#"{name: "x", count: 2}"#.to_string()),
+            },
+        )
+        .await;
+
+    let result = fiber.await.unwrap().unwrap();
+    let payload: serde_json::Value = match &result[..] {
+        [Value::String(payload)] => serde_json::from_str(payload).unwrap(),
+        _ => panic!("Unexpected result: {result:?}"),
+    };
+
+    check!(invalid_json.is_err());
+    check!(invalid_data.is_err());
+    check!(valid_wave == Ok(true));
+    check!(payload == serde_json::json!({ "name": "x", "count": 2 }));
+}
@@ -10,4 +10,30 @@ impl Guest for Component {
         let promise_id = golem_create_promise();
         golem_await_promise(&promise_id)
     }
+
+    fn run_with_expiry(expire_after_secs: u64) -> Result<Vec<u8>, String> {
+        let promise_id = golem_create_promise_with_options(PromiseOptions {
+            expire_after: Some(expire_after_secs * 1_000_000_000),
+            payload_type: None,
+        });
+        golem_await_promise_result(&promise_id).map_err(|err| match err {
+            PromiseError::TimedOut => "timed out".to_string(),
+        })
+    }
+
+    fn run_typed() -> String {
+        // record { name: string, count: u32 }
+        let payload_type = WitType {
+            nodes: vec![
+                WitTypeNode::RecordType(vec![("name".to_string(), 1), ("count".to_string(), 2)]),
+                WitTypeNode::PrimStringType,
+                WitTypeNode::PrimU32Type,
+            ],
+        };
+        let promise_id = golem_create_promise_with_options(PromiseOptions {
+            expire_after: None,
+            payload_type: Some(payload_type),
+        });
+        String::from_utf8(golem_await_promise(&promise_id)).unwrap()
+    }
 }
@@ -4,7 +4,11 @@ use crate::bindings::exports::golem::it::api::Guest;
 use crate::bindings::golem::api::host::*;
 use crate::bindings::wasi;
 use crate::bindings::wasi::io::streams::StreamError;
+use crate::bindings::wasi::clocks::wall_clock::{now, Datetime};
 use reqwest::{Client, Response};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static BUMPS: AtomicU64 = AtomicU64::new(0);
 
 struct Component;
 
@@ -184,6 +188,32 @@ impl Guest for Component {
         );
         bindings::golem::api::host::update_worker(&worker_id, component_version, update_mode);
     }
+
+    fn bump() {
+        let bumps = BUMPS.fetch_add(1, Ordering::SeqCst) + 1;
+        println!("Bumped to {bumps}");
+    }
+
+    fn get_bumps() -> u64 {
+        BUMPS.load(Ordering::SeqCst)
+    }
+
+    fn schedule_bump(delay_seconds: u64) -> ScheduledInvocationId {
+        let function_name = "golem:it/api/bump";
+        let self_uri = get_self_uri("bump");
+        let current = now();
+        let scheduled_at = Datetime {
+            seconds: current.seconds + delay_seconds,
+            nanoseconds: current.nanoseconds,
+        };
+        println!("Scheduling {function_name} at {scheduled_at:?}");
+        schedule_invocation(&self_uri, function_name, &[], scheduled_at)
+    }
+
+    fn cancel_scheduled_bump(id: ScheduledInvocationId) -> bool {
+        println!("Canceling scheduled invocation {id:?}");
+        cancel_scheduled_invocation(&id)
+    }
 }
 
 fn remote_call(param: u64) -> bool {